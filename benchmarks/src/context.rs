@@ -0,0 +1,47 @@
+use test::Bencher;
+
+#[derive(Clone)]
+struct Theme(usize);
+
+#[derive(Clone)]
+struct Locale(usize);
+
+#[derive(Clone)]
+struct Section(usize);
+
+// 10,000 rows at the bottom of a 20-deep tree, where only the root and one scope
+// along the way provide any context, each calling `use_context` twice
+#[bench]
+fn leptos_use_context_10k_rows(b: &mut Bencher) {
+    use leptos::*;
+
+    fn nested(cx: Scope, depth: usize) {
+        if depth == 0 {
+            let mut sum = 0;
+            for _ in 0..10_000 {
+                cx.child_scope(|cx| {
+                    sum += use_context::<Theme>(cx).unwrap().0;
+                    sum += use_context::<Locale>(cx).unwrap().0;
+                });
+            }
+            assert_eq!(sum, 30_000);
+        } else {
+            cx.child_scope(|cx| {
+                if depth == 10 {
+                    provide_context(cx, Section(depth));
+                }
+                nested(cx, depth - 1);
+            });
+        }
+    }
+
+    let runtime = create_runtime();
+    b.iter(|| {
+        create_scope(runtime, |cx| {
+            provide_context(cx, Theme(1));
+            provide_context(cx, Locale(2));
+            nested(cx, 20);
+        })
+        .dispose()
+    });
+}
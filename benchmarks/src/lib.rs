@@ -3,5 +3,6 @@
 extern crate test;
 
 //mod reactive;
+mod context;
 mod ssr;
 mod todomvc;
@@ -32,7 +32,7 @@ fn leptos_ssr_bench(b: &mut Bencher) {
 
 			assert_eq!(
 				rendered,
-				"<main data-hk=\"0-0\"><h1>Welcome to our benchmark page.</h1><p>Here's some introductory text.</p><!--#--><div data-hk=\"0-2-0\"><button>-1</button><span>Value: <!--#-->1<!--/-->!</span><button>+1</button></div><!--/--><!--#--><div data-hk=\"0-3-0\"><button>-1</button><span>Value: <!--#-->2<!--/-->!</span><button>+1</button></div><!--/--><!--#--><div data-hk=\"0-4-0\"><button>-1</button><span>Value: <!--#-->3<!--/-->!</span><button>+1</button></div><!--/--></main>"
+				"<main data-hk=\"0-0\"><h1>Welcome to our benchmark page.</h1><p>Here's some introductory text.</p><!--leptos-0--><div data-hk=\"0-2-0\"><button>-1</button><span>Value: <!--leptos-1-->1<!--/leptos-1-->!</span><button>+1</button></div><!--/leptos-0--><!--leptos-2--><div data-hk=\"0-3-0\"><button>-1</button><span>Value: <!--leptos-3-->2<!--/leptos-3-->!</span><button>+1</button></div><!--/leptos-2--><!--leptos-4--><div data-hk=\"0-4-0\"><button>-1</button><span>Value: <!--leptos-5-->3<!--/leptos-5-->!</span><button>+1</button></div><!--/leptos-4--></main>"
 			);
 		});
 	});
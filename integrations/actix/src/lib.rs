@@ -165,23 +165,25 @@ pub fn render_app_to_stream(
                 }
             };
 
-            let head = format!(r#"<!DOCTYPE html>
-                <html>
-                    <head>
+            let head = format!(r#"
                         <meta charset="utf-8"/>
                         <meta name="viewport" content="width=device-width, initial-scale=1"/>
                         <script type="module">import init, {{ hydrate }} from '/pkg/{client_pkg_name}.js'; init().then(hydrate);</script>"#);
             let tail = "</body></html>";
 
             HttpResponse::Ok().content_type("text/html").streaming(
-                futures::stream::once(async move { head.clone() })
+                futures::stream::once(async { "<!DOCTYPE html>".to_string() })
                     // TODO this leaks a runtime once per invocation
                     .chain(render_to_stream(move |cx| {
                         let app = app(cx);
-                        let head = use_context::<MetaContext>(cx)
-                            .map(|meta| meta.dehydrate())
+                        // the <html> and <body> tags are only rendered now, so that they
+                        // include any attributes set by <Html/> and <Body/> components
+                        let (html, meta, body) = use_context::<MetaContext>(cx)
+                            .map(|meta| {
+                                (meta.html_attributes(), meta.dehydrate(), meta.body_attributes())
+                            })
                             .unwrap_or_default();
-                        format!("{head}</head><body>{app}")
+                        format!("<html{html}><head>{head}{meta}</head><body{body}>{app}")
                     }))
                     .chain(futures::stream::once(async { tail.to_string() }))
                     .map(|html| Ok(web::Bytes::from(html)) as Result<web::Bytes>),
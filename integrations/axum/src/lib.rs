@@ -193,9 +193,7 @@ pub fn render_app_to_stream(
                 }
 
                 let head = format!(
-                    r#"<!DOCTYPE html>
-                    <html lang="en">
-                        <head>
+                    r#"
                             <meta charset="utf-8"/>
                             <meta name="viewport" content="width=device-width, initial-scale=1"/>
                             <script type="module">import init, {{ hydrate }} from '/pkg/{client_pkg_name}.js'; init().then(hydrate);</script>"#
@@ -228,10 +226,20 @@ pub fn render_app_to_stream(
                                                     );
                                                     provide_context(cx, MetaContext::new());
                                                     let app = app_fn(cx);
-                                                    let head = use_context::<MetaContext>(cx)
-                                                        .map(|meta| meta.dehydrate())
-                                                        .unwrap_or_default();
-                                                    format!("{head}</head><body>{app}")
+                                                    // the <html> and <body> tags are only
+                                                    // rendered now, so that they include any
+                                                    // attributes set by <Html/> and <Body/>
+                                                    let (html, meta, body) =
+                                                        use_context::<MetaContext>(cx)
+                                                            .map(|meta| {
+                                                                (
+                                                                    meta.html_attributes(),
+                                                                    meta.dehydrate(),
+                                                                    meta.body_attributes(),
+                                                                )
+                                                            })
+                                                            .unwrap_or_default();
+                                                    format!("<html{html}><head>{head}{meta}</head><body{body}>{app}")
                                                 }
                                             }));
                                             while let Some(fragment) = shell.next().await {
@@ -245,7 +253,7 @@ pub fn render_app_to_stream(
                     }
                 });
 
-                let stream = futures::stream::once(async move { head.clone() })
+                let stream = futures::stream::once(async { "<!DOCTYPE html>".to_string() })
                     .chain(rx)
                     .chain(futures::stream::once(async { tail.to_string() }))
                     .map(|html| Ok(Bytes::from(html)));
@@ -0,0 +1,69 @@
+#![cfg(all(target_arch = "wasm32", feature = "hydrate"))]
+
+use leptos::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[component]
+fn Counter(cx: Scope, initial: i32) -> Element {
+    let (value, set_value) = create_signal(cx, initial);
+    view! { cx,
+        <button on:click=move |_| set_value.update(|n| *n += 1)>{move || value.get()}</button>
+    }
+}
+
+// rendered on the server from
+// <main>
+//     <p>"Static"</p>
+//     <IslandRoot id="first".to_string()><Counter initial=1/></IslandRoot>
+//     <IslandRoot id="second".to_string()><Counter initial=2/></IslandRoot>
+// </main>
+const SERVER_HTML: &str = concat!(
+    r#"<main data-hk="0-0"><p>Static</p>"#,
+    r#"<!--leptos-0--><div data-hk="0-2-0" data-island="first">"#,
+    r#"<button data-hk="first:1-0"><!--leptos-1-->1<!--/leptos-1--></button>"#,
+    r#"</div><!--/leptos-0-->"#,
+    r#"<!--leptos-2--><div data-hk="0-3-0" data-island="second">"#,
+    r#"<button data-hk="second:1-0"><!--leptos-3-->2<!--/leptos-3--></button>"#,
+    r#"</div><!--/leptos-2--></main>"#
+);
+
+#[wasm_bindgen_test]
+fn island_hydrates_on_its_own() {
+    let page = document().create_element("div").unwrap();
+    page.set_inner_html(SERVER_HTML);
+    document().body().unwrap().append_child(&page).unwrap();
+
+    let island = |id: &str| {
+        page.query_selector(&format!(r#"[data-island="{id}"]"#))
+            .unwrap()
+            .unwrap()
+    };
+    let first_html = island("first").outer_html();
+    let button: web_sys::HtmlElement = island("second")
+        .query_selector("button")
+        .unwrap()
+        .unwrap()
+        .unchecked_into();
+
+    hydrate(island("second").unchecked_into(), |cx| {
+        view! { cx, <Counter initial=2/> }
+    });
+
+    // the server-rendered button is reused, and now responds to clicks
+    button.click();
+    assert_eq!(button.text_content().as_deref(), Some("3"));
+    assert_eq!(
+        island("second")
+            .query_selector_all("button")
+            .unwrap()
+            .length(),
+        1
+    );
+
+    // the other island hasn't been touched
+    assert_eq!(island("first").outer_html(), first_html);
+
+    page.remove();
+}
@@ -0,0 +1,113 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use leptos::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[component]
+fn Badge(cx: Scope, label: ReadSignal<String>) -> Element {
+    view! { cx, <span class="badge">{move || label.get()}</span> }
+}
+
+fn count_comments(node: &web_sys::Node) -> usize {
+    let mut count = 0;
+    let mut child = node.first_child();
+    while let Some(node) = child {
+        if node.node_type() == web_sys::Node::COMMENT_NODE {
+            count += 1;
+        }
+        count += count_comments(&node);
+        child = node.next_sibling();
+    }
+    count
+}
+
+fn remove_comments(node: &web_sys::Node) {
+    let mut child = node.first_child();
+    while let Some(node) = child {
+        child = node.next_sibling();
+        if node.node_type() == web_sys::Node::COMMENT_NODE {
+            node.parent_node().unwrap().remove_child(&node).unwrap();
+        } else {
+            remove_comments(&node);
+        }
+    }
+}
+
+// the rendered HTML, ignoring any comment nodes
+fn content(el: &web_sys::Element) -> String {
+    let el: web_sys::Element = el.clone_node_with_deep(true).unwrap().unchecked_into();
+    remove_comments(&el);
+    el.inner_html()
+}
+
+#[wasm_bindgen_test]
+fn markers_only_where_needed() {
+    run_scope(create_runtime(), |cx| {
+        let (unread, set_unread) = create_signal(cx, 0);
+        let (label, set_label) = create_signal(cx, "hi".to_string());
+        let (fresh, set_fresh) = create_signal(cx, false);
+
+        let el: web_sys::Element = view! { cx,
+            <div>
+                <h1>"Inbox"</h1>
+                {move || unread.get()}
+                " unread"
+                <Badge label=label/>
+                {move || fresh.get().then(|| view! { cx, <em>"new"</em> })}
+                <footer>"end"</footer>
+            </div>
+        };
+
+        if cfg!(feature = "hydrate") {
+            // each component and dynamic child is wrapped in a pair of markers
+            assert_eq!(count_comments(&el), 8);
+        } else {
+            // only `fresh` keeps its placeholder, as the `before` marker for <Badge/>
+            assert_eq!(count_comments(&el), 1);
+        }
+
+        let badge = r#"<span class="badge">hi</span>"#;
+        assert_eq!(
+            content(&el),
+            format!("<h1>Inbox</h1>0 unread{badge}<footer>end</footer>")
+        );
+
+        set_unread.set(3);
+        set_fresh.set(true);
+        assert_eq!(
+            content(&el),
+            format!("<h1>Inbox</h1>3 unread{badge}<em>new</em><footer>end</footer>")
+        );
+
+        set_label.set("bye".to_string());
+        set_fresh.set(false);
+        set_unread.set(4);
+        assert_eq!(
+            content(&el),
+            r#"<h1>Inbox</h1>4 unread<span class="badge">bye</span><footer>end</footer>"#
+        );
+    });
+}
+
+#[wasm_bindgen_test]
+fn adjacent_dynamic_children_keep_their_order() {
+    run_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, Some("a"));
+        let (b, set_b) = create_signal(cx, Some("b"));
+
+        let el: web_sys::Element = view! { cx,
+            <p>{move || a.get()}{move || b.get()}"!"</p>
+        };
+        assert_eq!(content(&el), "ab!");
+
+        set_a.set(None);
+        assert_eq!(content(&el), "b!");
+        set_a.set(Some("A"));
+        assert_eq!(content(&el), "Ab!");
+        set_b.set(None);
+        set_b.set(Some("B"));
+        assert_eq!(content(&el), "AB!");
+    });
+}
@@ -18,7 +18,7 @@ fn simple_ssr_test() {
 
         assert_eq!(
             rendered,
-            r#"<div data-hk="0-0"><button>-1</button><span>Value: <!--#-->0<!--/-->!</span><button>+1</button></div>"# //r#"<div data-hk="0" id="hydrated" data-hk="0"><button>-1</button><span>Value: <!--#-->0<!--/-->!</span><button>+1</button></div>"#
+            r#"<div data-hk="0-0"><button>-1</button><span>Value: <!--leptos-0-->0<!--/leptos-0-->!</span><button>+1</button></div>"# //r#"<div data-hk="0" id="hydrated" data-hk="0"><button>-1</button><span>Value: <!--#-->0<!--/-->!</span><button>+1</button></div>"#
         );
     });
 }
@@ -56,7 +56,7 @@ fn ssr_test_with_components() {
 
         assert_eq!(
             rendered,
-            "<div data-hk=\"0-0\" class=\"counters\"><!--#--><div data-hk=\"0-2-0\"><button>-1</button><span>Value: <!--#-->1<!--/-->!</span><button>+1</button></div><!--/--><!--#--><div data-hk=\"0-3-0\"><button>-1</button><span>Value: <!--#-->2<!--/-->!</span><button>+1</button></div><!--/--></div>"
+            "<div data-hk=\"0-0\" class=\"counters\"><!--leptos-0--><div data-hk=\"0-2-0\"><button>-1</button><span>Value: <!--leptos-1-->1<!--/leptos-1-->!</span><button>+1</button></div><!--/leptos-0--><!--leptos-2--><div data-hk=\"0-3-0\"><button>-1</button><span>Value: <!--leptos-3-->2<!--/leptos-3-->!</span><button>+1</button></div><!--/leptos-2--></div>"
         );
     });
 }
@@ -81,3 +81,373 @@ fn test_classes() {
         );
     });
 }
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_renders_initial_dynamic_values() {
+    use leptos_dom::*;
+    use leptos_macro::view;
+    use leptos_reactive::{create_runtime, create_scope, create_signal};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (selected, _) = create_signal(cx, true);
+        let (label, _) = create_signal(cx, "Home".to_string());
+        let rendered = view! {
+            cx,
+            <div>
+                <a class="tab" class:active=move || selected() aria-label=move || label()>"Home"</a>
+                <input type="checkbox" prop:checked=move || selected() prop:value=label/>
+                <input value="static" prop:value=label/>
+            </div>
+        };
+
+        assert_eq!(
+            rendered,
+            r#"<div data-hk="0-0"><a class="tab active" aria-label="Home">Home</a><input type="checkbox" checked value="Home"/><input value="static"/></div>"#
+        );
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_skips_properties_that_cant_be_attributes() {
+    use leptos_dom::wasm_bindgen::JsValue;
+    use leptos_dom::*;
+    use leptos_macro::view;
+    use leptos_reactive::{create_runtime, create_scope};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let rendered = view! {
+            cx,
+            <div>
+                <input prop:value=JsValue::NULL prop:checked=Some(true)/>
+                <input prop:value=move || JsValue::NULL prop:disabled=true/>
+            </div>
+        };
+
+        assert_eq!(
+            rendered,
+            r#"<div data-hk="0-0"><input/><input disabled/></div>"#
+        );
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_evaluates_dynamic_values_once() {
+    use leptos_dom::*;
+    use leptos_macro::view;
+    use leptos_reactive::{create_runtime, create_scope};
+    use std::{cell::Cell, rc::Rc};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let calls = Rc::new(Cell::new(0));
+        let once = {
+            let calls = Rc::clone(&calls);
+            move || {
+                if calls.get() > 0 {
+                    panic!("dynamic class evaluated more than once");
+                }
+                calls.set(1);
+                true
+            }
+        };
+        let rendered = view! {
+            cx,
+            <div class:active=once></div>
+        };
+
+        assert_eq!(rendered, r#"<div data-hk="0-0" class=" active"></div>"#);
+        assert_eq!(calls.get(), 1);
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_escapes_attribute_values() {
+    use leptos_dom::*;
+    use leptos_macro::view;
+    use leptos_reactive::{create_runtime, create_scope, create_signal};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let json = r#"{"a":1,"b":"</script>"}"#.to_string();
+        let (caption, _) = create_signal(cx, "line\u{2028}sep\r\nnull\0 &amp;".to_string());
+        let rendered = view! {
+            cx,
+            <img
+                class="hero & \"wide\""
+                title="Tom & \"Jerry\""
+                data-json=json
+                alt=move || caption()
+                srcset={Composite::srcset().part("a.jpg 1x").part("b.jpg?w=2&h=2 2x")}
+            />
+        };
+
+        assert_eq!(
+            rendered,
+            concat!(
+                r#"<img data-hk="0-0" class="hero &amp; &quot;wide&quot;""#,
+                r#" title="Tom &amp; &quot;Jerry&quot;""#,
+                r#" data-json="{&quot;a&quot;:1,&quot;b&quot;:&quot;&lt;/script&gt;&quot;}""#,
+                " alt=\"line\u{2028}sep&#13;\nnull &amp;amp;\"",
+                r#" srcset="a.jpg 1x, b.jpg?w=2&amp;h=2 2x"/>"#
+            )
+        );
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_renames_svg_attributes() {
+    use leptos_dom::*;
+    use leptos_macro::view;
+    use leptos_reactive::{create_runtime, create_scope, create_signal};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (width, _) = create_signal(cx, 2);
+        let rendered = view! {
+            cx,
+            <svg view_box="0 0 10 10" preserve_aspect_ratio="none">
+                <path d="M0 0L10 10" stroke_width=move || width() xlink_href="#line"/>
+            </svg>
+        };
+
+        assert_eq!(
+            rendered,
+            concat!(
+                r#"<svg data-hk="0-0" viewBox="0 0 10 10" preserveAspectRatio="none">"#,
+                r##"<path d="M0 0L10 10" stroke-width="2" xlink:href="#line"></path>"##,
+                r#"</svg>"#
+            )
+        );
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn event_listeners_are_typed_for_their_element() {
+    use leptos_dom::*;
+    use leptos_macro::view;
+    use leptos_reactive::{create_runtime, create_scope};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let rendered = view! {
+            cx,
+            <div on:progress=|ev: web_sys::ProgressEvent| _ = ev.loaded()>
+                <form on:submit:prevent:stop=|ev| _ = ev.submitter()>
+                    <button type="submit">"Submit"</button>
+                </form>
+                <video on:progress=|_: web_sys::Event| {}></video>
+            </div>
+        };
+
+        assert_eq!(
+            rendered,
+            r#"<div data-hk="0-0"><form><button type="submit">Submit</button></form><video></video></div>"#
+        );
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn async_children_are_not_awaited_on_the_server() {
+    use leptos_dom::*;
+    use leptos_macro::view;
+    use leptos_reactive::{create_runtime, create_scope};
+    use std::{future::Future, pin::Pin};
+
+    _ = create_scope(create_runtime(), |cx| {
+        let count: Pin<Box<dyn Future<Output = usize>>> = Box::pin(async { 3 });
+        let rendered = view! {
+            cx,
+            <p>{async { "Hello" }} " " {count}</p>
+        };
+
+        assert_eq!(
+            rendered,
+            r#"<p data-hk="0-0"><!--leptos-0--><!--/leptos-0--> <!--leptos-1--><!--/leptos-1--></p>"#
+        );
+    });
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn islands_restart_hydration_keys() {
+    use leptos::*;
+
+    #[component]
+    fn Counter(cx: Scope, initial: i32) -> Element {
+        let (value, set_value) = create_signal(cx, initial);
+        view! { cx,
+            <button on:click=move |_| set_value.update(|n| *n += 1)>{move || value.get()}</button>
+        }
+    }
+
+    let html = render_to_string(|cx| {
+        view! { cx,
+            <main>
+                <p>"Static"</p>
+                <IslandRoot id="first".to_string()><Counter initial=1/></IslandRoot>
+                <IslandRoot id="second".to_string()><Counter initial=2/></IslandRoot>
+            </main>
+        }
+    });
+    // keys inside each island start again from the island's root, prefixed with its id
+    assert_eq!(
+        html,
+        concat!(
+            r#"<main data-hk="0-0"><p>Static</p>"#,
+            r#"<!--leptos-0--><div data-hk="0-2-0" data-island="first">"#,
+            r#"<button data-hk="first:1-0"><!--leptos-1-->1<!--/leptos-1--></button>"#,
+            r#"</div><!--/leptos-0-->"#,
+            r#"<!--leptos-2--><div data-hk="0-3-0" data-island="second">"#,
+            r#"<button data-hk="second:1-0"><!--leptos-3-->2<!--/leptos-3--></button>"#,
+            r#"</div><!--/leptos-2--></main>"#
+        )
+    );
+}
+
+#[cfg(all(feature = "ssr", not(any(feature = "csr", feature = "hydrate"))))]
+#[test]
+fn effects_using_browser_apis_are_caught_on_the_server() {
+    use leptos::*;
+    use std::sync::Mutex;
+
+    static LOGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct Recorder;
+
+    impl log::Log for Recorder {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            LOGS.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    _ = log::set_logger(&Recorder);
+    log::set_max_level(log::LevelFilter::Error);
+
+    let line = line!() + 4;
+
+    #[component]
+    fn Width(cx: Scope) -> Element {
+        create_effect(cx, move |_| {
+            log::info!("width: {:?}", window().inner_width());
+        });
+        view! { cx, <p>"Width"</p> }
+    }
+
+    let runtime = create_runtime();
+    runtime.set_ssr_effect_policy(SsrEffectPolicy::RunAndCatch);
+    let html = run_scope(runtime, |cx| {
+        view! { cx,
+            <main>
+                <Width/>
+                <Width/>
+            </main>
+        }
+    });
+    runtime.dispose();
+
+    assert_eq!(
+        html,
+        concat!(
+            r#"<main data-hk="0-0">"#,
+            r#"<!--leptos-0--><p data-hk="0-2-0">Width</p><!--/leptos-0-->"#,
+            r#"<!--leptos-1--><p data-hk="0-3-0">Width</p><!--/leptos-1-->"#,
+            r#"</main>"#
+        )
+    );
+    // logged once for both effects, naming where they were created
+    let logs = LOGS.lock().unwrap();
+    let location = format!("ssr.rs:{line}:");
+    assert_eq!(
+        logs.iter()
+            .filter(|log| log.starts_with("[create_effect]") && log.contains(&location))
+            .count(),
+        1
+    );
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn event_handler_props_take_untyped_closures() {
+    use leptos_core as leptos;
+    use leptos_core::Prop;
+    use leptos_dom::*;
+    use leptos_macro::*;
+    use leptos_reactive::{create_runtime, create_scope, create_signal, Scope};
+
+    #[component]
+    fn FancyButton(
+        cx: Scope,
+        on_click: EventHandler<web_sys::MouseEvent>,
+        on_hover: Option<EventHandler<web_sys::MouseEvent>>,
+    ) -> Element {
+        view! {
+            cx,
+            <button
+                on:click=move |ev| on_click.call(ev)
+                on:mouseenter=move |ev| if let Some(on_hover) = &on_hover { on_hover.call(ev) }
+            >
+                "Click"
+            </button>
+        }
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (x, set_x) = create_signal(cx, 0);
+        let rendered = view! {
+            cx,
+            <div>
+                <FancyButton on_click=move |ev| set_x.set(ev.client_x())/>
+                <FancyButton
+                    on_click=move |ev| set_x.set(ev.client_y())
+                    on_hover=move |ev| set_x.set(ev.screen_x())
+                />
+            </div>
+        };
+
+        assert_eq!(x.get(), 0);
+        assert_eq!(
+            rendered,
+            "<div data-hk=\"0-0\"><!--leptos-0--><button data-hk=\"0-2-0\">Click</button><!--/leptos-0--><!--leptos-1--><button data-hk=\"0-3-0\">Click</button><!--/leptos-1--></div>"
+        );
+    });
+}
+
+// with the `stable` feature, an `EventHandler` isn't an `Fn`, so it has to be wrapped in a closure
+#[cfg(all(
+    not(any(feature = "csr", feature = "hydrate")),
+    not(feature = "stable")
+))]
+#[test]
+fn event_handler_props_can_be_forwarded() {
+    use leptos_core as leptos;
+    use leptos_core::Prop;
+    use leptos_dom::*;
+    use leptos_macro::*;
+    use leptos_reactive::{create_runtime, create_scope, create_signal, Scope};
+
+    #[component]
+    fn Inner(cx: Scope, on_change: EventHandler<i32>) -> Element {
+        on_change.call(1);
+        view! { cx, <span/> }
+    }
+
+    #[component]
+    fn Outer(cx: Scope, on_change: EventHandler<i32>) -> Element {
+        view! { cx, <div><Inner on_change=on_change/></div> }
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let (x, set_x) = create_signal(cx, 0);
+        _ = view! { cx, <Outer on_change=move |n| set_x.set(n)/> };
+        assert_eq!(x.get(), 1);
+    });
+}
@@ -0,0 +1,175 @@
+// the resources are resolved by hand, on the `TestRuntime`'s executor
+#![cfg(all(feature = "testing", not(any(feature = "csr", feature = "hydrate"))))]
+
+use futures::{channel::oneshot, task::noop_waker_ref, Stream};
+use leptos::testing::TestRuntime;
+use leptos::*;
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    future::Future,
+    rc::Rc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+// the senders that resolve each resource the page has started loading
+type Loads = Rc<RefCell<Vec<(&'static str, oneshot::Sender<String>)>>>;
+
+#[component]
+fn Page(cx: Scope, loads: Loads) -> Element {
+    let load = move |name: &'static str| {
+        let loads = Rc::clone(&loads);
+        create_resource(
+            cx,
+            || (),
+            move |_| {
+                let (tx, rx) = oneshot::channel();
+                loads.borrow_mut().push((name, tx));
+                async move { rx.await.unwrap_or_default() }
+            },
+        )
+    };
+    let (user, posts) = (load("user"), load("posts"));
+    let (comments, likes) = (load("comments"), load("likes"));
+
+    view! { cx,
+        <main>
+            <h1 class="title" id="page" data-page="profile">"Profile"</h1>
+            <Suspense fallback={"Loading profile...".to_string()}>
+                {move || user.read().zip(posts.read()).map(|(user, posts)| view! { cx,
+                    <section class="profile" title=user.clone()>{user} " wrote " {posts}</section>
+                })}
+            </Suspense>
+            <Suspense fallback={"Loading activity...".to_string()}>
+                {move || comments.read().zip(likes.read()).map(|(comments, likes)| view! { cx,
+                    <section class="activity">{comments} " and " {likes}</section>
+                })}
+            </Suspense>
+        </main>
+    }
+}
+
+// a small LCG, so that each run resolves the resources in a different, but repeatable, order
+fn shuffle<T>(items: &mut [T], seed: &mut u64) {
+    for i in (1..items.len()).rev() {
+        *seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        items.swap(i, (*seed >> 33) as usize % (i + 1));
+    }
+}
+
+// resolves every resource the page is waiting for, in a shuffled order, calling `poll` after each
+fn resolve_shuffled(rt: &TestRuntime, loads: &Loads, seed: &mut u64, mut poll: impl FnMut()) {
+    // the buffered render only starts once it's polled
+    poll();
+    rt.flush();
+    let mut pending = std::mem::take(&mut *loads.borrow_mut());
+    assert_eq!(pending.len(), 4);
+    shuffle(&mut pending, seed);
+    for (name, tx) in pending {
+        _ = tx.send(format!("{name} data"));
+        rt.flush();
+        poll();
+    }
+}
+
+#[test]
+fn buffered_render_is_identical_every_time() {
+    let mut seed = 7;
+    let mut renders = Vec::new();
+
+    for _ in 0..20 {
+        let rt = TestRuntime::new();
+        let loads = Loads::default();
+        let mut render = Box::pin(render_to_string_with_timeout(
+            {
+                let loads = Rc::clone(&loads);
+                move |cx| view! { cx, <Page loads=loads/> }
+            },
+            Duration::from_secs(10),
+        ));
+        let mut html = None;
+        let mut poll = || {
+            if html.is_some() {
+                return;
+            }
+            if let Poll::Ready(rendered) = render
+                .as_mut()
+                .poll(&mut Context::from_waker(noop_waker_ref()))
+            {
+                html = Some(rendered);
+            }
+        };
+        resolve_shuffled(&rt, &loads, &mut seed, &mut poll);
+        drop(poll);
+        renders.push(html.expect("the render should finish once every resource has resolved"));
+    }
+
+    assert!(renders[0].contains("user data"), "{}", renders[0]);
+    assert!(renders[0].contains("likes data"), "{}", renders[0]);
+    assert!(renders.iter().all(|html| html == &renders[0]));
+}
+
+#[test]
+fn streamed_render_has_identical_content_every_time() {
+    let mut seed = 7;
+    let mut renders = Vec::new();
+
+    for _ in 0..20 {
+        let rt = TestRuntime::new();
+        let loads = Loads::default();
+        let mut stream = Box::pin(render_to_stream({
+            let loads = Rc::clone(&loads);
+            move |cx| view! { cx, <Page loads=loads/> }
+        }));
+        let mut chunks = Vec::new();
+        let mut done = false;
+        let mut poll = || {
+            while !done {
+                let Poll::Ready(chunk) = stream
+                    .as_mut()
+                    .poll_next(&mut Context::from_waker(noop_waker_ref()))
+                else {
+                    break;
+                };
+                match chunk {
+                    Some(chunk) => chunks.push(chunk),
+                    None => done = true,
+                }
+            }
+        };
+        resolve_shuffled(&rt, &loads, &mut seed, &mut poll);
+        drop(poll);
+        assert!(
+            done,
+            "the stream should end once every resource has resolved"
+        );
+
+        // the shell and resource script come first, in order; the rest arrives as it resolves
+        let shell = chunks.remove(0);
+        let mut arrivals = chunks;
+        arrivals.retain(|chunk| !chunk.trim().is_empty());
+        arrivals.sort();
+        renders.push((shell, fragments(&arrivals), arrivals));
+    }
+
+    let (shell, fragments, _) = &renders[0];
+    assert!(shell.contains("Loading profile..."), "{shell}");
+    assert_eq!(fragments.len(), 2, "{fragments:?}");
+    assert!(renders.iter().all(|render| render == &renders[0]));
+}
+
+// the HTML of each `<Suspense/>` fragment, by its key
+fn fragments(chunks: &[String]) -> BTreeMap<String, String> {
+    chunks
+        .iter()
+        .filter_map(|chunk| {
+            let start = chunk.find("<template id=\"")? + "<template id=\"".len();
+            let (key, rest) = chunk[start..].split_once("\">")?;
+            let (html, _) = rest.split_once("</template>")?;
+            Some((key.to_string(), html.to_string()))
+        })
+        .collect()
+}
@@ -0,0 +1,135 @@
+#![cfg(all(target_arch = "wasm32", feature = "csr"))]
+
+use leptos::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[component]
+fn Row(cx: Scope, label: String) -> Element {
+    let (count, set_count) = create_signal(cx, 0);
+    view! { cx,
+        <li class="row">
+            <span class="label">{label}</span>
+            <button on:click=move |_| set_count.update(|n| *n += 1)>{move || count.get()}</button>
+        </li>
+    }
+}
+
+// the same row as <Row/>, built one node at a time
+fn built_row(cx: Scope, label: String) -> web_sys::Element {
+    let (count, set_count) = create_signal(cx, 0);
+    let row = create_element("li");
+    row.set_attribute("class", "row").unwrap();
+    let span = create_element("span");
+    span.set_attribute("class", "label").unwrap();
+    span.append_child(&create_text_node(&label)).unwrap();
+    let button = create_element("button");
+    add_event_listener(&button, "click", move |_: web_sys::MouseEvent| {
+        set_count.update(|n| *n += 1)
+    });
+    let text = create_text_node("");
+    create_effect(cx, {
+        let text = text.clone();
+        move |_| text.set_text_content(Some(&count.get().to_string()))
+    });
+    button.append_child(&text).unwrap();
+    row.append_child(&span).unwrap();
+    row.append_child(&button).unwrap();
+    row
+}
+
+fn mounted_list(cx: Scope, rows: usize, build: bool) -> web_sys::Element {
+    let list = create_element("ul");
+    for i in 0..rows {
+        let row = if build {
+            built_row(cx, format!("row {i}"))
+        } else {
+            view! { cx, <Row label=format!("row {i}")/> }
+        };
+        list.append_child(&row).unwrap();
+    }
+    document().body().unwrap().append_child(&list).unwrap();
+    list
+}
+
+fn click(list: &web_sys::Element, row: u32) {
+    list.children()
+        .item(row)
+        .unwrap()
+        .query_selector("button")
+        .unwrap()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+}
+
+#[wasm_bindgen_test]
+fn cloned_rows_behave_like_built_rows() {
+    run_scope(create_runtime(), |cx| {
+        let cloned = mounted_list(cx, 3, false);
+        let built = mounted_list(cx, 3, true);
+
+        for list in [&cloned, &built] {
+            click(list, 0);
+            click(list, 2);
+            click(list, 2);
+        }
+        // every row, including the one that first built the template, has its own state
+        assert_eq!(cloned.inner_html(), built.inner_html());
+        assert_eq!(
+            built.inner_html(),
+            concat!(
+                r#"<li class="row"><span class="label">row 0</span><button>1</button></li>"#,
+                r#"<li class="row"><span class="label">row 1</span><button>0</button></li>"#,
+                r#"<li class="row"><span class="label">row 2</span><button>2</button></li>"#,
+            )
+        );
+
+        cloned.remove();
+        built.remove();
+    });
+}
+
+#[wasm_bindgen_test]
+fn repeated_rows_are_cloned_from_one_template() {
+    if !cfg!(debug_assertions) {
+        return;
+    }
+
+    run_scope(create_runtime(), |cx| {
+        // the template itself isn't counted
+        reset_stats();
+        mounted_list(cx, 1, false).remove();
+        let one = stats();
+        // the `<li>`, `<span>`, and `<button>`
+        assert!(one.cloned >= 3, "{one:?}");
+
+        reset_stats();
+        mounted_list(cx, 1000, false).remove();
+        let many = stats();
+        assert_eq!(many.cloned, 1000 * one.cloned);
+        // only the `<ul>` and each row's dynamic text are created one at a time
+        assert_eq!(many.created - 1, 1000 * (one.created - 1));
+    });
+}
+
+// run with `wasm-pack test --release --headless --chrome -- --nocapture` to see the timings
+#[wasm_bindgen_test]
+fn mounting_1000_rows() {
+    fn time(f: impl FnOnce()) -> f64 {
+        let performance = window().performance().unwrap();
+        let start = performance.now();
+        f();
+        performance.now() - start
+    }
+
+    run_scope(create_runtime(), |cx| {
+        // warm up, so that neither run includes building the template
+        mounted_list(cx, 1, false).remove();
+
+        let built = time(|| mounted_list(cx, 1000, true).remove());
+        let cloned = time(|| mounted_list(cx, 1000, false).remove());
+        log!("mounting 1000 rows: {built:.1}ms built node by node, {cloned:.1}ms cloned");
+    });
+}
@@ -0,0 +1,90 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use leptos::*;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// a `<Transition/>` keyed by `item`, and the suspense context its resources would be read under
+fn keyed_transition(cx: Scope, item: ReadSignal<u32>) -> (impl Fn() -> Child, SuspenseContext) {
+    let render = Transition(
+        cx,
+        TransitionProps::builder()
+            .fallback("loading".to_string())
+            .key(move || item.get())
+            .children(Box::new(move || {
+                vec![move || format!("item {}", item.get())]
+            }))
+            .build(),
+    );
+    (render, use_context::<SuspenseContext>(cx).unwrap())
+}
+
+// the suspense context counts resources in a microtask
+async fn tick() {
+    JsFuture::from(js_sys::Promise::resolve(&wasm_bindgen::JsValue::UNDEFINED))
+        .await
+        .unwrap();
+}
+
+fn text(render: &impl Fn() -> Child) -> String {
+    let mut child = render();
+    while let Child::Fn(f) = child {
+        child = (f.borrow_mut())();
+    }
+    match child {
+        Child::Text(text) => text,
+        _ => panic!("expected text"),
+    }
+}
+
+#[wasm_bindgen_test]
+async fn changing_key_shows_fallback_while_new_key_loads() {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let (item, set_item) = create_signal(cx, 1);
+    let (render, context) = keyed_transition(cx, item);
+    assert_eq!(text(&render), "item 1");
+
+    // item 1 reloads, and item 2 is chosen before it has finished
+    context.increment();
+    tick().await;
+    assert_eq!(text(&render), "item 1");
+    set_item.set(2);
+    assert_eq!(text(&render), "loading");
+
+    context.decrement();
+    tick().await;
+    assert_eq!(text(&render), "item 2");
+
+    // item 3 is chosen while nothing is loading, and only then starts loading
+    set_item.set(3);
+    assert_eq!(text(&render), "item 3");
+    context.increment();
+    tick().await;
+    assert_eq!(text(&render), "loading");
+
+    context.decrement();
+    tick().await;
+    assert_eq!(text(&render), "item 3");
+
+    disposer.dispose();
+}
+
+#[wasm_bindgen_test]
+async fn reloading_same_key_keeps_previous_children() {
+    let (cx, disposer) = raw_scope_and_disposer(create_runtime());
+    let (item, _) = create_signal(cx, 1);
+    let (render, context) = keyed_transition(cx, item);
+    assert_eq!(text(&render), "item 1");
+
+    context.increment();
+    tick().await;
+    assert_eq!(text(&render), "item 1");
+
+    context.decrement();
+    tick().await;
+    assert_eq!(text(&render), "item 1");
+
+    disposer.dispose();
+}
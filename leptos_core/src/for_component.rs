@@ -1,11 +1,14 @@
 use leptos_dom::Element;
 use leptos_macro::*;
-use leptos_reactive::{Memo, Scope};
+use leptos_reactive::{create_memo, Memo, Scope, SignalVec};
+use std::any::Any;
+use std::cell::RefCell;
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::rc::Rc;
 
 use crate as leptos;
-use crate::map::map_keyed;
+use crate::map::{map_keyed_with_exits, map_signal_vec};
 
 /// Properties for the [For](crate::For) component, a keyed list.
 #[derive(Props)]
@@ -21,6 +24,17 @@ where
     pub each: E,
     /// A key function that will be applied to each item
     pub key: I,
+    /// Will be displayed in place of the list whenever `each` returns no items. Takes any
+    /// `Fn() -> Element`, like `fallback=|| view! { cx, <p>"No items"</p> }`.
+    #[builder(default, setter(transform = |fallback: impl Fn() -> Element + 'static| {
+        Some(Box::new(fallback) as Box<dyn Fn() -> Element>)
+    }))]
+    pub fallback: Option<Box<dyn Fn() -> Element>>,
+    /// Called with each item that is removed from `each`, and a `done` function. The item’s
+    /// nodes stay in the list, and its scope stays alive, until `done` is called, so that
+    /// it can play an exit animation first.
+    #[builder(default, setter(strip_option))]
+    pub on_remove: Option<Box<dyn Fn(&T, Rc<dyn Fn()>)>>,
     /// Should provide a single child function, which takes
     pub children: Box<dyn Fn() -> Vec<G>>,
 }
@@ -67,6 +81,96 @@ where
 ///   }
 /// }
 /// ```
+///
+/// If you’d like to show something else while the list is empty, you can pass a `fallback`
+/// function, which will be rendered in place of the list whenever `each` returns no items.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use leptos_macro::*;
+/// # use leptos_core::*;
+/// # use leptos_dom::*; use leptos::*;
+///
+/// fn Names(cx: Scope) -> Element {
+///   let (names, set_names) = create_signal::<Vec<String>>(cx, vec![]);
+///
+///   view! {
+///     cx,
+///     <ul>
+///       <For
+///         each=names
+///         key=|name| name.clone()
+///         fallback=move || view! { cx, <li>"No names yet."</li> }
+///       >
+///         {|cx: Scope, name: &String| view! { cx, <li>{name.clone()}</li> }}
+///       </For>
+///     </ul>
+///   }
+/// }
+/// ```
+///
+/// If `each` is a [SignalVec](leptos_reactive::SignalVec), the list isn’t diffed at all:
+/// each change to it is applied directly, so a row is only created for an item that is
+/// inserted, and only disposed of when its item is removed. (`key` is ignored, and this
+/// doesn’t apply if `on_remove` is given, or if the `SignalVec` is wrapped in a closure.)
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use leptos_macro::*;
+/// # use leptos_core::*;
+/// # use leptos_dom::*; use leptos::*;
+///
+/// fn Log(cx: Scope) -> Element {
+///   let lines = create_signal_vec::<String>(cx);
+///   let log = {
+///     let lines = lines.clone();
+///     move |_| lines.push("another line".to_string())
+///   };
+///
+///   view! {
+///     cx,
+///     <div>
+///       <button on:click=log>"Log"</button>
+///       <ol>
+///         <For each=lines key=|line| line.clone()>
+///           {|cx: Scope, line: &String| view! { cx, <li>{line.clone()}</li> }}
+///         </For>
+///       </ol>
+///     </div>
+///   }
+/// }
+/// ```
+///
+/// To animate items out of the list, pass an `on_remove` function. Each removed item is
+/// passed to it with a `done` function, and its nodes are only removed once `done` is called.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use leptos_macro::*;
+/// # use leptos_core::*;
+/// # use leptos_dom::*; use leptos::*;
+/// # use std::rc::Rc;
+///
+/// fn Toasts(cx: Scope) -> Element {
+///   let (toasts, set_toasts) = create_signal::<Vec<String>>(cx, vec![]);
+///
+///   view! {
+///     cx,
+///     <ul>
+///       <For
+///         each=toasts
+///         key=|toast| toast.clone()
+///         on_remove=Box::new(|_toast: &String, done: Rc<dyn Fn()>| {
+///           // start a CSS transition, then remove the toast once it has finished
+///           set_timeout(move || done(), std::time::Duration::from_millis(300));
+///         })
+///       >
+///         {|cx: Scope, toast: &String| view! { cx, <li>{toast.clone()}</li> }}
+///       </For>
+///     </ul>
+///   }
+/// }
+/// ```
 #[allow(non_snake_case)]
 pub fn For<E, T, G, I, K>(cx: Scope, props: ForProps<E, T, G, I, K>) -> Memo<Vec<Element>>
 where
@@ -77,5 +181,35 @@ where
     T: Eq + Debug + 'static,
 {
     let map_fn = (props.children)().swap_remove(0);
-    map_keyed(cx, props.each, map_fn, props.key)
+    // a `SignalVec` says which rows changed, so there's nothing to diff
+    let signal_vec = (&props.each as &dyn Any)
+        .downcast_ref::<SignalVec<T>>()
+        .filter(|_| props.on_remove.is_none())
+        .cloned();
+    let mapped = match signal_vec {
+        Some(list) => map_signal_vec(cx, &list, map_fn),
+        None => {
+            let on_remove = props.on_remove.map(Rc::from);
+            map_keyed_with_exits(cx, props.each, map_fn, props.key, on_remove)
+        }
+    };
+
+    match props.fallback {
+        None => mapped,
+        Some(fallback) => {
+            // the fallback is only created once, and reused whenever the list is empty again
+            let fallback_el: RefCell<Option<Element>> = RefCell::new(None);
+            create_memo(cx, move |_| {
+                if mapped.with(|items| items.is_empty()) {
+                    let el = fallback_el
+                        .borrow_mut()
+                        .get_or_insert_with(|| fallback())
+                        .clone();
+                    vec![el]
+                } else {
+                    mapped.get()
+                }
+            })
+        }
+    }
 }
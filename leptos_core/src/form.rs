@@ -0,0 +1,821 @@
+use leptos_dom::NodeRef;
+use leptos_reactive::{
+    create_isomorphic_effect, create_memo, create_rw_signal, create_signal_vec, Memo, RwSignal,
+    Scope, ScopeDisposer, Signal, SignalSetter, SignalVec, UntrackedGettableSignal,
+};
+use leptos_server::Action;
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Debug,
+    rc::Rc,
+    time::Duration,
+};
+
+/// The reactive state of a single form field: its current value, any validation error,
+/// and whether it has been edited (`dirty`) or interacted with (`touched`).
+#[derive(Debug, PartialEq, Eq)]
+pub struct FieldState<T>
+where
+    T: 'static,
+{
+    /// A unique, stable identifier for this field, which can be used as the `key`
+    /// when rendering a list of fields with [For](crate::For).
+    pub id: usize,
+    /// The current value of the field.
+    pub value: RwSignal<T>,
+    /// The current validation error for the field, if any. For the fields created by
+    /// [use_field_array], this is kept in sync with the form’s error for `name[index]`.
+    pub error: RwSignal<Option<String>>,
+    /// `true` if the current value differs from the value the field was created with.
+    pub dirty: Memo<bool>,
+    /// `true` once the field has been interacted with. This is not set automatically;
+    /// you’ll typically set it in an `on:blur` handler.
+    pub touched: RwSignal<bool>,
+}
+
+impl<T> Clone for FieldState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            value: self.value,
+            error: self.error,
+            dirty: self.dirty,
+            touched: self.touched,
+        }
+    }
+}
+
+impl<T> Copy for FieldState<T> {}
+
+impl<T> FieldState<T>
+where
+    T: Clone + PartialEq + Debug + 'static,
+{
+    /// Creates a new field state in the given scope, with the given initial value.
+    pub fn new(cx: Scope, id: usize, initial: T) -> Self {
+        let value = create_rw_signal(cx, initial.clone());
+        let dirty = create_memo(cx, move |_| value.with(|value| *value != initial));
+        Self {
+            id,
+            value,
+            error: create_rw_signal(cx, None),
+            dirty,
+            touched: create_rw_signal(cx, false),
+        }
+    }
+
+    /// Returns `true` if the field has no validation error.
+    pub fn is_valid(&self) -> bool {
+        self.error.with(|error| error.is_none())
+    }
+}
+
+/// Manages a dynamic list of repeating form fields (like a list of tags or a set of addresses),
+/// each with its own [FieldState], for the `Vec` that the [Form]’s model returns from
+/// [FormModel::field_array] for `name`.
+///
+/// Returns a tuple of
+/// 1. a [SignalVec] containing the current fields, which can be rendered with [For](crate::For)
+///    and reordered with [SignalVec::swap],
+/// 2. a function that appends a new field with the value returned by `default`, and
+/// 3. a function that removes the field with the given [FieldState::id].
+///
+/// The model’s `Vec` is kept in sync with the fields: it is updated whenever a field is added,
+/// removed, or moved, or its value changes. Each field’s [error](FieldState::error) is the
+/// form’s error for `name[index]` (like `tags[0]`), where `index` is the field’s current
+/// position. Each field’s signals are created in their own child scope, which is disposed
+/// when the field is removed, or along with `cx`.
+///
+/// ```
+/// # use leptos_reactive::{*, testing::TestRuntime};
+/// # use leptos_core::*;
+/// # use std::any::Any;
+/// # let rt = TestRuntime::new();
+/// # let cx = rt.scope();
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Post {
+///   tags: Vec<String>,
+/// }
+///
+/// impl FormModel for Post {
+///   fn validate(&self) -> Result<(), ValidationErrors> {
+///     let mut errors = ValidationErrors::new();
+///     for (index, tag) in self.tags.iter().enumerate() {
+///       if tag.is_empty() {
+///         errors.add(format!("tags[{index}]"), "Tags can’t be empty.");
+///       }
+///     }
+///     if errors.is_empty() { Ok(()) } else { Err(errors) }
+///   }
+///
+///   fn field_array(&mut self, name: &str) -> Option<&mut dyn Any> {
+///     match name {
+///       "tags" => Some(&mut self.tags),
+///       _ => None,
+///     }
+///   }
+/// }
+///
+/// let form = create_form(cx, Post { tags: vec!["rust".to_string()] });
+/// let (fields, append, remove) = use_field_array(cx, &form, "tags", String::new);
+/// assert_eq!(fields.len(), 1);
+///
+/// append();
+/// # rt.flush();
+/// let new_field = fields.with(|f| f[1]);
+/// assert_eq!(new_field.value.get(), "");
+/// assert!(!new_field.dirty.get());
+/// assert_eq!(new_field.error.get().as_deref(), Some("Tags can’t be empty."));
+///
+/// new_field.value.set("leptos".to_string());
+/// # rt.flush();
+/// assert!(new_field.dirty.get());
+/// assert!(new_field.error.get().is_none());
+/// assert_eq!(form.model.get().tags, ["rust", "leptos"]);
+///
+/// remove(fields.with(|f| f[0].id));
+/// assert_eq!(fields.len(), 1);
+/// assert_eq!(fields.with(|f| f[0].value.get()), "leptos");
+/// ```
+pub fn use_field_array<M, T>(
+    cx: Scope,
+    form: &Form<M>,
+    name: &'static str,
+    default: impl Fn() -> T + 'static,
+) -> (
+    SignalVec<FieldState<T>>,
+    impl Fn() + Clone,
+    impl Fn(usize) + Clone,
+)
+where
+    M: FormModel,
+    T: Clone + PartialEq + Debug + 'static,
+{
+    let model = form.model;
+    let next_id = Rc::new(Cell::new(0));
+    let disposers: Rc<RefCell<HashMap<usize, ScopeDisposer>>> = Default::default();
+
+    let create_field = {
+        let disposers = Rc::clone(&disposers);
+        move |value: T| {
+            let id = next_id.get();
+            next_id.set(id + 1);
+            let (field, disposer) = cx.run_child_scope(|cx| FieldState::new(cx, id, value));
+            disposers.borrow_mut().insert(id, disposer);
+            field
+        }
+    };
+
+    let initial = with_field_array(&mut model.get_untracked(), name, |values: &mut Vec<T>| {
+        values.clone()
+    })
+    .unwrap_or_else(|| {
+        leptos_dom::debug_warn!(
+            "[use_field_array] the model has no `Vec<{}>` called {name:?}: see \
+             `FormModel::field_array`",
+            std::any::type_name::<T>()
+        );
+        Vec::new()
+    });
+    let fields = create_signal_vec(cx);
+    for value in initial {
+        fields.push(create_field(value));
+    }
+
+    // write the fields back to the model whenever they change
+    create_isomorphic_effect(cx, {
+        let fields = fields.clone();
+        move |_| {
+            let values = fields.with(|fields| {
+                fields
+                    .iter()
+                    .map(|field| field.value.get())
+                    .collect::<Vec<_>>()
+            });
+            let mut next = model.get_untracked();
+            let changed = with_field_array(&mut next, name, |prev: &mut Vec<T>| {
+                let changed = *prev != values;
+                *prev = values;
+                changed
+            });
+            if changed == Some(true) {
+                model.set(next);
+            }
+        }
+    });
+
+    // show the form's error for each field, which moves with the field as it is reordered
+    create_isomorphic_effect(cx, {
+        let fields = fields.clone();
+        let errors = form.errors.clone();
+        move |_| {
+            let changed = errors.with(|errors| {
+                fields.with(|fields| {
+                    fields
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(index, field)| {
+                            let error = errors.get(&format!("{name}[{index}]"));
+                            (field.error.get_untracked().as_deref() != error)
+                                .then(|| (field.error, error.map(String::from)))
+                        })
+                        .collect::<Vec<_>>()
+                })
+            });
+            // set once nothing is borrowed, as setting may run other effects
+            for (field_error, error) in changed {
+                field_error.set(error);
+            }
+        }
+    });
+
+    let default = Rc::new(default);
+    let append = {
+        let fields = fields.clone();
+        move || fields.push(create_field(default()))
+    };
+
+    let remove = {
+        let fields = fields.clone();
+        move |id: usize| {
+            let index = cx.untrack(|| fields.with(|fields| fields.iter().position(|f| f.id == id)));
+            if let Some(index) = index {
+                fields.remove(index);
+            }
+            if let Some(disposer) = disposers.borrow_mut().remove(&id) {
+                disposer.dispose();
+            }
+        }
+    };
+
+    (fields, append, remove)
+}
+
+// calls `f` with the `Vec<T>` the model returns from `FormModel::field_array`, if it has one
+fn with_field_array<M, T, U>(
+    model: &mut M,
+    name: &str,
+    f: impl FnOnce(&mut Vec<T>) -> U,
+) -> Option<U>
+where
+    M: FormModel,
+    T: 'static,
+{
+    model
+        .field_array(name)
+        .and_then(|array| array.downcast_mut::<Vec<T>>())
+        .map(f)
+}
+
+/// The data model behind a form created with [create_form], typically a plain struct with
+/// one field per input.
+pub trait FormModel: Clone + PartialEq + 'static {
+    /// Checks the current values, returning an error message for each invalid field.
+    /// By default, every value is valid.
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Ok(())
+    }
+
+    /// Returns the `Vec` of values behind the repeating fields called `name`, for
+    /// [use_field_array]. By default, the model has no repeating fields.
+    fn field_array(&mut self, name: &str) -> Option<&mut dyn Any> {
+        _ = name;
+        None
+    }
+}
+
+/// Validation error messages for a [FormModel], keyed by field name.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ValidationErrors(BTreeMap<String, String>);
+
+impl ValidationErrors {
+    /// Creates an empty set of errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an error for the given field, replacing any previous error for it.
+    pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.0.insert(field.into(), message.into());
+    }
+
+    /// Returns the error for the given field, if any.
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.0.get(field).map(String::as_str)
+    }
+
+    /// Returns `true` if there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over `(field, message)` pairs, in order of field name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(field, message)| (field.as_str(), message.as_str()))
+    }
+}
+
+/// A form backed by a [FormModel], as created by [create_form].
+///
+/// Use [field](Form::field) to get a reactive handle to each of the model’s fields, and
+/// [submit](Form::submit) to send the current model to an [Action].
+pub struct Form<T>
+where
+    T: FormModel,
+{
+    cx: Scope,
+    initial: Rc<T>,
+    /// The current value of the whole model.
+    pub model: RwSignal<T>,
+    /// `true` if the model differs from the value the form was created with.
+    pub dirty: Signal<bool>,
+    /// The errors returned by [FormModel::validate] for the current model.
+    pub errors: Signal<ValidationErrors>,
+    touched: RwSignal<HashSet<&'static str>>,
+    all_touched: RwSignal<bool>,
+    debounced_errors: Option<RwSignal<ValidationErrors>>,
+}
+
+impl<T> Clone for Form<T>
+where
+    T: FormModel,
+{
+    fn clone(&self) -> Self {
+        Self {
+            cx: self.cx,
+            initial: Rc::clone(&self.initial),
+            model: self.model,
+            dirty: self.dirty.clone(),
+            errors: self.errors.clone(),
+            touched: self.touched,
+            all_touched: self.all_touched,
+            debounced_errors: self.debounced_errors,
+        }
+    }
+}
+
+impl<T> Form<T>
+where
+    T: FormModel,
+{
+    /// Returns a reactive handle to one field of the model, identified by `name` (which should
+    /// match the name used for its errors in [FormModel::validate]).
+    ///
+    /// `get` reads the field from the model and `set` writes it back: the field’s value only
+    /// notifies its subscribers when that field changes, not when any other part of the model does.
+    pub fn field<F, G, S>(&self, name: &'static str, get: G, set: S) -> FormField<F>
+    where
+        F: Clone + PartialEq + Debug + 'static,
+        G: Fn(&T) -> &F + 'static,
+        S: Fn(&mut T, F) + 'static,
+    {
+        let cx = self.cx;
+        let model = self.model;
+        let value = create_memo(cx, move |_| model.with(|model| get(model).clone()));
+        let set_value = SignalSetter::map(cx, move |value| model.update(|model| set(model, value)));
+
+        let touched = {
+            let (touched, all_touched) = (self.touched, self.all_touched);
+            create_memo(cx, move |_| {
+                all_touched.get() || touched.with(|touched| touched.contains(name))
+            })
+        };
+        let error = {
+            let errors = self.errors.clone();
+            create_memo(cx, move |_| {
+                errors.with(|errors| errors.get(name).map(String::from))
+            })
+        };
+
+        FormField {
+            name,
+            value: value.into(),
+            set_value,
+            touched: touched.into(),
+            error: error.into(),
+            set_touched: self.touched,
+        }
+    }
+
+    /// Returns `true` if the field with the given name has been touched, or if the form has
+    /// been submitted.
+    pub fn is_touched(&self, name: &str) -> bool {
+        self.all_touched.get() || self.touched.with(|touched| touched.contains(name))
+    }
+
+    /// Marks every field as touched, so that all their errors are shown.
+    pub fn touch_all(&self) {
+        self.all_touched.set(true);
+    }
+
+    /// Returns `true` if the current model has no validation errors.
+    pub fn is_valid(&self) -> bool {
+        self.errors.with(ValidationErrors::is_empty)
+    }
+
+    /// Restores the model to the value the form was created with, and clears all touched fields.
+    pub fn reset(&self) {
+        self.model.set((*self.initial).clone());
+        self.touched.update(HashSet::clear);
+        self.all_touched.set(false);
+    }
+
+    /// Marks every field as touched and validates the current model. If it is valid, the
+    /// model is dispatched to the `action`; otherwise nothing is dispatched.
+    ///
+    /// Validation here is never debounced, so a form can’t be submitted with stale errors.
+    /// Returns `true` if the action was dispatched.
+    pub fn submit<O>(&self, action: &Action<T, O>) -> bool
+    where
+        O: 'static,
+    {
+        self.touch_all();
+        let errors = self
+            .model
+            .with_untracked(|model| model.validate().err().unwrap_or_default());
+        let valid = errors.is_empty();
+        if let Some(debounced_errors) = self.debounced_errors {
+            debounced_errors.set(errors);
+        }
+        if valid {
+            action.dispatch(self.model.get_untracked());
+        }
+        valid
+    }
+}
+
+/// A reactive handle to one field of a [Form], as returned by [Form::field].
+#[derive(Debug, Clone)]
+pub struct FormField<F>
+where
+    F: Clone + 'static,
+{
+    /// The name of the field, as used in [ValidationErrors].
+    pub name: &'static str,
+    /// The current value of the field.
+    pub value: Signal<F>,
+    /// Sets the value of the field, updating the model.
+    pub set_value: SignalSetter<F>,
+    /// `true` once the field has been [touched](FormField::touch), or the form has been submitted.
+    pub touched: Signal<bool>,
+    /// The current validation error for this field, if any.
+    pub error: Signal<Option<String>>,
+    set_touched: RwSignal<HashSet<&'static str>>,
+}
+
+impl<F> FormField<F>
+where
+    F: Clone + 'static,
+{
+    /// Marks the field as touched. [bind_value] does this when the input loses focus.
+    pub fn touch(&self) {
+        let name = self.name;
+        if !self
+            .set_touched
+            .with_untracked(|touched| touched.contains(name))
+        {
+            self.set_touched.update(|touched| {
+                touched.insert(name);
+            });
+        }
+    }
+}
+
+/// Creates a [Form] whose model starts out as `initial`.
+///
+/// The form’s [errors](Form::errors) are recomputed by [FormModel::validate] whenever the model
+/// changes. To avoid validating on every keystroke, use [create_debounced_form] instead.
+///
+/// ```
+/// # use leptos_reactive::{*, testing::TestRuntime};
+/// # use leptos_core::*;
+/// # use leptos_server::create_action;
+/// # let rt = TestRuntime::new();
+/// # let cx = rt.scope();
+/// #[derive(Debug, Clone, PartialEq, Default)]
+/// struct SignUp {
+///   email: String,
+///   age: u8,
+/// }
+///
+/// impl FormModel for SignUp {
+///   fn validate(&self) -> Result<(), ValidationErrors> {
+///     let mut errors = ValidationErrors::new();
+///     if !self.email.contains('@') {
+///       errors.add("email", "Please enter a valid email address.");
+///     }
+///     if self.age < 13 {
+///       errors.add("age", "You must be at least 13 to sign up.");
+///     }
+///     if errors.is_empty() { Ok(()) } else { Err(errors) }
+///   }
+/// }
+///
+/// let form = create_form(cx, SignUp { email: String::new(), age: 18 });
+/// let email = form.field("email", |m| &m.email, |m, email| m.email = email);
+/// assert!(!form.dirty.get());
+/// assert_eq!(email.error.get().as_deref(), Some("Please enter a valid email address."));
+///
+/// // in a view, you’d typically call `bind_value(cx, input_ref, &email)` instead
+/// email.set_value.set("leptos".to_string());
+/// email.touch();
+/// assert!(form.dirty.get());
+/// assert!(email.touched.get());
+/// assert!(email.error.get().is_some());
+///
+/// let sign_up = create_action(cx, |model: &SignUp| {
+///   let email = model.email.clone();
+///   async move { email }
+/// });
+/// // submitting is blocked while there are errors
+/// assert!(!form.submit(&sign_up));
+/// assert_eq!(sign_up.version.get(), 0);
+///
+/// email.set_value.set("leptos@example.com".to_string());
+/// assert!(email.error.get().is_none());
+/// assert!(form.submit(&sign_up));
+/// # rt.flush();
+/// assert_eq!(sign_up.value.get(), Some("leptos@example.com".to_string()));
+/// ```
+pub fn create_form<T>(cx: Scope, initial: T) -> Form<T>
+where
+    T: FormModel,
+{
+    form(cx, initial, None)
+}
+
+/// Like [create_form], but waits until the model has stopped changing for `delay` before
+/// recomputing the [errors](Form::errors). [Form::submit] always validates immediately.
+///
+/// On the server, the errors are computed without any delay.
+pub fn create_debounced_form<T>(cx: Scope, initial: T, delay: Duration) -> Form<T>
+where
+    T: FormModel,
+{
+    form(cx, initial, Some(delay))
+}
+
+fn form<T>(cx: Scope, initial: T, delay: Option<Duration>) -> Form<T>
+where
+    T: FormModel,
+{
+    let initial = Rc::new(initial);
+    let model = create_rw_signal(cx, (*initial).clone());
+    let dirty = create_memo(cx, {
+        let initial = Rc::clone(&initial);
+        move |_| model.with(|model| model != &*initial)
+    });
+    let validate = move || model.with(|model| model.validate().err().unwrap_or_default());
+
+    let (errors, debounced_errors) = match delay {
+        Some(delay) => debounced(cx, validate, delay),
+        None => (create_memo(cx, move |_| validate()).into(), None),
+    };
+
+    Form {
+        cx,
+        initial,
+        model,
+        dirty: dirty.into(),
+        errors,
+        touched: create_rw_signal(cx, HashSet::new()),
+        all_touched: create_rw_signal(cx, false),
+        debounced_errors,
+    }
+}
+
+fn debounced(
+    cx: Scope,
+    validate: impl Fn() -> ValidationErrors + 'static,
+    delay: Duration,
+) -> (Signal<ValidationErrors>, Option<RwSignal<ValidationErrors>>) {
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let errors = create_rw_signal(cx, validate());
+            let validate = Rc::new(validate);
+            // only the latest change should be validated, once it has settled
+            let changes = Rc::new(Cell::new(0_usize));
+            leptos_reactive::create_effect(cx, move |prev: Option<()>| {
+                // track the model, but skip the initial run, which `errors` already covers
+                let _ = validate();
+                if prev.is_none() {
+                    return;
+                }
+                let change = changes.get() + 1;
+                changes.set(change);
+                let changes = Rc::clone(&changes);
+                let validate = Rc::clone(&validate);
+                leptos_dom::set_timeout(
+                    move || {
+                        if changes.get() == change {
+                            errors.set(cx.untrack(|| validate()));
+                        }
+                    },
+                    delay,
+                );
+            });
+            (errors.into(), Some(errors))
+        } else {
+            _ = delay;
+            (create_memo(cx, move |_| validate()).into(), None)
+        }
+    }
+}
+
+/// Binds a text input to a [FormField] in both directions: the input’s `value` is kept in
+/// sync with the field, the field is set on every `input` event, and the field is
+/// [touched](FormField::touch) when the input loses focus.
+///
+/// This only takes effect in the browser. To server-render the initial value, also set
+/// `value=move || field.value.get()` on the input.
+///
+/// ```
+/// # use leptos::*;
+/// # #[derive(Debug, Clone, PartialEq)]
+/// # struct Login { username: String }
+/// # impl FormModel for Login {}
+/// #[component]
+/// fn Username(cx: Scope, form: Form<Login>) -> Element {
+///   let username = form.field("username", |m| &m.username, |m, name| m.username = name);
+///   let input = NodeRef::new(cx);
+///   bind_value(cx, input, &username);
+///
+///   view! { cx,
+///     <label>
+///       "Username"
+///       <input _ref=input type="text"/>
+///       {move || username.touched.get().then(|| username.error.get()).flatten()}
+///     </label>
+///   }
+/// }
+/// ```
+pub fn bind_value(cx: Scope, input: NodeRef, field: &FormField<String>) {
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use leptos_dom::{event_target_value, use_event_listener, wasm_bindgen::JsValue};
+
+            let value = field.value.clone();
+            leptos_reactive::create_effect(cx, move |_| {
+                let value = value.get();
+                if let Some(el) = input.get() {
+                    leptos_dom::set_property(&el, "value", &Some(JsValue::from_str(&value)));
+                }
+            });
+
+            let set_value = field.set_value.clone();
+            use_event_listener(cx, input, "input", move |ev| {
+                set_value.set(event_target_value(&ev))
+            });
+
+            let field = field.clone();
+            use_event_listener(cx, input, "blur", move |_| field.touch());
+        } else {
+            _ = (cx, input, field);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::form::{create_form, use_field_array, FormModel, ValidationErrors};
+    use leptos_reactive::testing::TestRuntime;
+    use std::any::Any;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Post {
+        tags: Vec<String>,
+    }
+
+    impl FormModel for Post {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            let mut errors = ValidationErrors::new();
+            for (index, tag) in self.tags.iter().enumerate() {
+                if tag.is_empty() {
+                    errors.add(format!("tags[{index}]"), "empty");
+                }
+            }
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(errors)
+            }
+        }
+
+        fn field_array(&mut self, name: &str) -> Option<&mut dyn Any> {
+            match name {
+                "tags" => Some(&mut self.tags),
+                _ => None,
+            }
+        }
+    }
+
+    fn post(tags: &[&str]) -> Post {
+        Post {
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_field_array_append() {
+        // the model is updated by an effect, which runs when the test runtime is flushed
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let form = create_form(cx, post(&["rust"]));
+        let (fields, append, _) = use_field_array(cx, &form, "tags", || "new".to_string());
+
+        append();
+        rt.flush();
+        assert_eq!(form.model.get(), post(&["rust", "new"]));
+        assert!(form.dirty.get());
+
+        fields.with(|fields| fields[1].value.set("leptos".to_string()));
+        rt.flush();
+        assert_eq!(form.model.get(), post(&["rust", "leptos"]));
+    }
+
+    #[test]
+    fn test_field_array_remove() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let form = create_form(cx, post(&["rust", "leptos", "wasm"]));
+        let (fields, _, remove) = use_field_array(cx, &form, "tags", String::new);
+
+        let leptos = fields.with(|fields| fields[1]);
+        remove(leptos.id);
+        rt.flush();
+        assert_eq!(form.model.get(), post(&["rust", "wasm"]));
+        // the removed field's signals are disposed of with its scope
+        assert_eq!(leptos.value.try_get(), None);
+
+        // removing it again does nothing
+        remove(leptos.id);
+        assert_eq!(fields.len(), 2);
+
+        // editing a field after another was removed updates the right value
+        fields.with(|fields| fields[1].value.set("wasm32".to_string()));
+        rt.flush();
+        assert_eq!(form.model.get(), post(&["rust", "wasm32"]));
+    }
+
+    #[test]
+    fn test_field_array_reorder() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let form = create_form(cx, post(&["rust", "leptos", "wasm"]));
+        let (fields, _, _) = use_field_array(cx, &form, "tags", String::new);
+        let ids = || fields.with(|fields| fields.iter().map(|f| f.id).collect::<Vec<_>>());
+        let before = ids();
+
+        fields.swap(0, 2);
+        rt.flush();
+        assert_eq!(form.model.get(), post(&["wasm", "leptos", "rust"]));
+        // each field keeps its id, so it can be used as a key
+        assert_eq!(ids(), [before[2], before[1], before[0]]);
+
+        fields.with(|fields| fields[0].value.set("wasm32".to_string()));
+        rt.flush();
+        assert_eq!(form.model.get(), post(&["wasm32", "leptos", "rust"]));
+    }
+
+    #[test]
+    fn test_field_array_errors() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let form = create_form(cx, post(&["rust", ""]));
+        let (fields, append, remove) = use_field_array(cx, &form, "tags", String::new);
+        rt.flush();
+        let errors = || {
+            fields.with(|fields| {
+                fields
+                    .iter()
+                    .map(|field| field.error.get())
+                    .collect::<Vec<_>>()
+            })
+        };
+        assert_eq!(errors(), [None, Some("empty".to_string())]);
+
+        append();
+        rt.flush();
+        assert_eq!(
+            errors(),
+            [None, Some("empty".to_string()), Some("empty".to_string())]
+        );
+
+        // the error moves with the field
+        let (rust, empty) = fields.with(|fields| (fields[0], fields[1]));
+        fields.swap(0, 1);
+        rt.flush();
+        assert_eq!(empty.error.get().as_deref(), Some("empty"));
+        assert!(rust.is_valid());
+
+        fields.with(|fields| fields[0].value.set("leptos".to_string()));
+        remove(fields.with(|fields| fields[2].id));
+        rt.flush();
+        assert_eq!(errors(), [None, None]);
+        assert!(form.is_valid());
+    }
+}
@@ -0,0 +1,98 @@
+use crate as leptos;
+use cfg_if::cfg_if;
+use leptos_dom::{Element, IntoChild};
+use leptos_macro::Props;
+use leptos_reactive::Scope;
+
+/// Props for the [IslandRoot](crate::IslandRoot) component, which marks a part of the page
+/// that can be hydrated on its own.
+#[derive(Props)]
+pub struct IslandRootProps<E>
+where
+    E: IntoChild,
+{
+    /// Identifies the island, so that it can be found and hydrated in the browser.
+    /// This should be unique within the page.
+    pub id: String,
+    /// The contents of the island.
+    pub children: Box<dyn Fn() -> Vec<E>>,
+}
+
+/// Marks the root of an “island”: an interactive part of a mostly static, server-rendered page,
+/// which can be hydrated on its own, without hydrating the rest of the page.
+///
+/// The `children` are wrapped in a `<div data-island={id}>`. Their hydration keys are generated
+/// relative to the island (see [Scope::with_island](leptos_reactive::Scope::with_island)), so in
+/// the browser, the island can be hydrated by passing that `<div>` to
+/// [hydrate](leptos_dom::hydrate) with the same `children`, in any order and independently of
+/// any other islands.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Counter(cx: Scope) -> Element {
+///   let (count, set_count) = create_signal(cx, 0);
+///   view! { cx,
+///     <button on:click=move |_| set_count.update(|n| *n += 1)>{move || count.get()}</button>
+///   }
+/// }
+///
+/// // on the server
+/// # #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+/// let page = render_to_string(|cx| view! { cx,
+///   <main>
+///     <p>"Lots of static content."</p>
+///     <IslandRoot id="counter".to_string()><Counter/></IslandRoot>
+///   </main>
+/// });
+/// assert!(page.contains(r#"data-island="counter""#));
+/// assert!(page.contains(r#"<button data-hk="counter:1-0">"#));
+/// # }
+///
+/// // in the browser
+/// # #[cfg(feature = "hydrate")] if false {
+/// let island = document().query_selector(r#"[data-island="counter"]"#).unwrap().unwrap();
+/// hydrate(island.unchecked_into(), |cx| view! { cx, <Counter/> });
+/// # }
+/// ```
+#[allow(non_snake_case)]
+pub fn IslandRoot<E>(cx: Scope, props: IslandRootProps<E>) -> Element
+where
+    E: IntoChild,
+{
+    let IslandRootProps { id, children } = props;
+
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            // the wrapper is part of the surrounding page, so it uses the page's hydration key
+            #[cfg(feature = "hydrate")]
+            let el = cx.get_next_element(&leptos_dom::create_template("<div></div>"));
+            #[cfg(not(feature = "hydrate"))]
+            let el = leptos_dom::create_element("div");
+            leptos_dom::set_attribute(&el, "data-island", &id);
+
+            let child = cx.with_island(&id, || (children)().swap_remove(0).into_child(cx));
+            // while hydrating, the children have been found in the existing wrapper
+            if !el.has_child_nodes() {
+                leptos_dom::insert(
+                    cx,
+                    el.clone().into(),
+                    child,
+                    leptos_dom::Marker::NoChildren,
+                    None,
+                );
+            }
+            el
+        } else {
+            use leptos_dom::escape_attr;
+
+            // the wrapper is part of the surrounding page, so it uses the page's hydration key
+            let key = cx.next_hydration_key();
+            // render the whole island now, while its keys are being generated
+            let html = cx.with_island(&id, || {
+                (children)().swap_remove(0).into_child(cx).as_child_string()
+            });
+            format!(r#"<div data-hk="{key}" data-island="{}">{html}</div>"#, escape_attr(&id))
+        }
+    }
+}
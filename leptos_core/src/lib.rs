@@ -4,11 +4,15 @@
 //! They are all re-exported in the main `leptos` crate.
 
 mod for_component;
+mod form;
+mod island;
 mod map;
 mod suspense;
 mod transition;
 
 pub use for_component::*;
+pub use form::*;
+pub use island::*;
 pub use map::*;
 pub use suspense::*;
 pub use transition::*;
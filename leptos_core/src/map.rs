@@ -1,5 +1,15 @@
-use leptos_reactive::{create_memo, queue_microtask, Memo, Scope, ScopeDisposer};
-use std::{cell::RefCell, collections::HashMap, fmt::Debug, hash::Hash, ops::IndexMut};
+use leptos_reactive::{
+    create_memo, create_signal, on_cleanup, queue_microtask, Memo, Scope, ScopeDisposer, SignalVec,
+    VecDelta,
+};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    ops::IndexMut,
+    rc::Rc,
+};
 
 /// Function that maps a `Vec` to another `Vec` via a map function. The mapped `Vec` is lazy
 /// computed; its value will only be updated when requested. Modifications to the
@@ -22,6 +32,80 @@ pub fn map_keyed<T, U, K>(
     key_fn: impl Fn(&T) -> K + 'static,
 ) -> Memo<Vec<U>>
 //-> impl FnMut() -> Vec<U>
+where
+    T: PartialEq + Debug + 'static,
+    K: Eq + Hash,
+    U: PartialEq + Debug + Clone + 'static,
+{
+    map_keyed_with_exits(cx, list, map_fn, key_fn, None)
+}
+
+/// Maps a [SignalVec] to a `Vec` via a map function, like [map_keyed], but without diffing:
+/// each [VecDelta] is applied to the mapped `Vec` directly, so a row is only created for an
+/// item that was inserted or replaced, and only disposed of when its item is removed.
+///
+/// Like [map_keyed], the mapped `Vec` is only brought up to date when it is read, and the
+/// deltas recorded in the meantime are coalesced first.
+pub fn map_signal_vec<T, U>(
+    cx: Scope,
+    list: &SignalVec<T>,
+    map_fn: impl Fn(Scope, &T) -> U + 'static,
+) -> Memo<Vec<U>>
+where
+    T: 'static,
+    U: PartialEq + Debug + Clone + 'static,
+{
+    let deltas = list.subscribe();
+    let rows: RefCell<Vec<(U, ScopeDisposer)>> = RefCell::new(Vec::new());
+    let create_row = move |item: &T| {
+        let mut value = None;
+        let disposer = cx.child_scope(|cx| {
+            value = Some(map_fn(cx, item));
+        });
+        (value.unwrap(), disposer)
+    };
+
+    create_memo(cx, move |_| {
+        let mut rows = rows.borrow_mut();
+        deltas.for_each_delta(|delta| match delta {
+            VecDelta::Insert { index, value } => rows.insert(index, create_row(&value)),
+            VecDelta::Remove { index } => rows.remove(index).1.dispose(),
+            VecDelta::Set { index, value } => {
+                let (_, disposer) = std::mem::replace(&mut rows[index], create_row(&value));
+                disposer.dispose();
+            }
+            VecDelta::Swap { a, b } => rows.swap(a, b),
+            VecDelta::Clear => {
+                for (_, disposer) in rows.drain(..) {
+                    disposer.dispose();
+                }
+            }
+        });
+        rows.iter().map(|(value, _)| value.clone()).collect()
+    })
+}
+
+/// Called with each item that is removed from a keyed list, and a `done` function (see
+/// [ForProps::on_remove](crate::ForProps::on_remove)).
+pub(crate) type OnRemove<T> = Rc<dyn Fn(&T, Rc<dyn Fn()>)>;
+
+// a removed item that stays in the mapped `Vec`, with its scope alive, until its `done` is called
+struct Exit<U> {
+    id: usize,
+    // where it was in the mapped `Vec` of items that hadn't been removed
+    index: usize,
+    value: U,
+}
+
+/// Like [map_keyed], but if `on_remove` is given, each removed item is passed to it, and
+/// stays in the mapped `Vec` until the `done` function it is given is called.
+pub(crate) fn map_keyed_with_exits<T, U, K>(
+    cx: Scope,
+    list: impl Fn() -> Vec<T> + 'static,
+    map_fn: impl Fn(Scope, &T) -> U + 'static,
+    key_fn: impl Fn(&T) -> K + 'static,
+    on_remove: Option<OnRemove<T>>,
+) -> Memo<Vec<U>>
 where
     T: PartialEq + Debug + 'static,
     K: Eq + Hash,
@@ -32,10 +116,62 @@ where
     let prev_items: RefCell<Option<Vec<T>>> = RefCell::new(None);
     let mapped: RefCell<Vec<U>> = RefCell::new(Vec::new());
 
+    // Items that have been removed, but whose exits aren't done yet.
+    let exits: Rc<RefCell<Vec<Exit<U>>>> = Default::default();
+    let next_exit_id = Cell::new(0);
+    let (exited, set_exited) = create_signal(cx, ());
+    let diffing = Rc::new(Cell::new(false));
+    // their scopes are disposed along with this one
+    on_cleanup(cx, {
+        let exits = Rc::clone(&exits);
+        move || exits.borrow_mut().clear()
+    });
+
+    // Removes an item, either right away, or once its exit is done.
+    let remove = {
+        let has_on_remove = on_remove.is_some();
+        let exits = Rc::clone(&exits);
+        let diffing = Rc::clone(&diffing);
+        move |index: usize, value: U, disposer: ScopeDisposer| -> Option<Rc<dyn Fn()>> {
+            if !has_on_remove {
+                disposer.dispose();
+                return None;
+            }
+
+            let id = next_exit_id.get();
+            next_exit_id.set(id + 1);
+            exits.borrow_mut().push(Exit { id, index, value });
+
+            let exits = Rc::clone(&exits);
+            let diffing = Rc::clone(&diffing);
+            let disposer = Cell::new(Some(disposer));
+            Some(Rc::new(move || {
+                let removed = {
+                    let mut exits = exits.borrow_mut();
+                    let len = exits.len();
+                    exits.retain(|exit| exit.id != id);
+                    exits.len() < len
+                };
+                if let (true, Some(disposer)) = (removed, disposer.take()) {
+                    disposer.dispose();
+                    // an exit that's done straight away is left out of the current diff anyway
+                    if !diffing.get() {
+                        set_exited.set(());
+                    }
+                }
+            }))
+        }
+    };
+
     // Diff and update signal each time list is updated.
     create_memo(cx, move |_| {
+        exited.get();
+        diffing.set(true);
+
         let mut prev_items = prev_items.borrow_mut();
         let mut mapped = mapped.borrow_mut();
+        // the removed items, by their index in `items`, and their `done` functions
+        let mut removed = Vec::new();
 
         //let mut mapped = mapped.cloned().unwrap_or_default();
         let items = prev_items.take().unwrap_or_default();
@@ -44,7 +180,14 @@ where
 
         if new_items.is_empty() {
             // Fast path for removing all items.
-            let disposers = disposers.take();
+            let mut disposers = disposers.take();
+            if on_remove.is_some() {
+                for (i, disposer) in disposers.drain(..).enumerate() {
+                    if let Some(done) = disposer.and_then(|d| remove(i, mapped[i].clone(), d)) {
+                        removed.push((i, done));
+                    }
+                }
+            }
             // delay disposal until after the current microtask
             queue_microtask(move || {
                 for disposer in disposers.into_iter().flatten() {
@@ -114,8 +257,11 @@ where
                     temp_disposers[j] = disposers[i].take();
                     new_indices_next[j - start].and_then(|j| new_indices.insert(key_fn(item), j));
                 } else {
-                    // Create new.
-                    disposers[i].take().unwrap().dispose();
+                    // Removed.
+                    let disposer = disposers[i].take().unwrap();
+                    if let Some(done) = remove(i, mapped[i].clone(), disposer) {
+                        removed.push((i, done));
+                    }
                 }
             }
 
@@ -152,17 +298,32 @@ where
         mapped.truncate(new_items_len);
         disposers.borrow_mut().truncate(new_items_len);
 
+        // Start the exits of the removed items, and keep those that aren't done in place.
+        if let Some(on_remove) = &on_remove {
+            for (i, done) in removed {
+                on_remove(&items[i], done);
+            }
+        }
+        let mut output = mapped.to_vec();
+        let mut exits = exits.borrow_mut();
+        exits.sort_by_key(|exit| exit.index);
+        for exit in exits.iter() {
+            output.insert(exit.index.min(output.len()), exit.value.clone());
+        }
+        diffing.set(false);
+
         // 4) Return the mapped and new items, for use in next iteration
         *prev_items = Some(new_items);
 
-        mapped.to_vec()
+        output
     })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::map::map_keyed;
+    use crate::map::{map_keyed, map_keyed_with_exits, map_signal_vec};
     use leptos_reactive::*;
+    use std::{cell::RefCell, rc::Rc};
 
     #[test]
     fn test_map_keyed() {
@@ -190,4 +351,128 @@ mod tests {
             .dispose();
         }
     }
+
+    #[test]
+    fn test_map_keyed_with_exits() {
+        if !cfg!(any(feature = "csr", feature = "hydrate")) {
+            create_scope(create_runtime(), |cx| {
+                let (rows, set_rows) = create_signal(cx, vec![1, 2, 3]);
+                let cleaned_up = Rc::new(RefCell::new(Vec::new()));
+                let exiting: Rc<RefCell<Vec<(i32, Rc<dyn Fn()>)>>> = Default::default();
+
+                let keyed = map_keyed_with_exits(
+                    cx,
+                    move || rows.get(),
+                    {
+                        let cleaned_up = Rc::clone(&cleaned_up);
+                        move |cx, row: &i32| {
+                            let (row, cleaned_up) = (*row, Rc::clone(&cleaned_up));
+                            on_cleanup(cx, move || cleaned_up.borrow_mut().push(row));
+                            row
+                        }
+                    },
+                    |row| *row,
+                    Some(Rc::new({
+                        let exiting = Rc::clone(&exiting);
+                        move |row: &i32, done| exiting.borrow_mut().push((*row, done))
+                    })),
+                );
+                assert_eq!(keyed.get(), vec![1, 2, 3]);
+
+                // the removed row stays where it was until its exit is done
+                set_rows.set(vec![1, 3]);
+                assert_eq!(keyed.get(), vec![1, 2, 3]);
+                assert!(cleaned_up.borrow().is_empty());
+
+                let (row, done) = exiting.borrow_mut().remove(0);
+                assert_eq!(row, 2);
+                done();
+                assert_eq!(keyed.get(), vec![1, 3]);
+                assert_eq!(*cleaned_up.borrow(), vec![2]);
+            })
+            .dispose();
+        }
+    }
+
+    #[test]
+    fn test_map_signal_vec() {
+        create_scope(create_runtime(), |cx| {
+            let rows = create_signal_vec::<i32>(cx);
+            rows.splice(.., [1, 2, 3]);
+            let created = Rc::new(RefCell::new(Vec::new()));
+            let cleaned_up = Rc::new(RefCell::new(Vec::new()));
+
+            let mapped = map_signal_vec(cx, &rows, {
+                let created = Rc::clone(&created);
+                let cleaned_up = Rc::clone(&cleaned_up);
+                move |cx, row: &i32| {
+                    let (row, cleaned_up) = (*row, Rc::clone(&cleaned_up));
+                    created.borrow_mut().push(row);
+                    on_cleanup(cx, move || cleaned_up.borrow_mut().push(row));
+                    row * 10
+                }
+            });
+            assert_eq!(mapped.get(), vec![10, 20, 30]);
+
+            rows.push(4);
+            rows.remove(0);
+            rows.splice(1..2, [5]);
+            rows.swap(0, 2);
+            assert_eq!(mapped.get(), vec![40, 50, 20]);
+            // only the rows that were inserted or removed were created or disposed of
+            assert_eq!(*created.borrow(), vec![1, 2, 3, 4, 5]);
+            assert_eq!(*cleaned_up.borrow(), vec![1, 3]);
+        })
+        .dispose();
+    }
+
+    #[test]
+    fn test_map_signal_vec_applies_each_delta_to_the_rows() {
+        create_scope(create_runtime(), |cx| {
+            let rows = create_signal_vec::<i32>(cx);
+            let created = Rc::new(RefCell::new(Vec::new()));
+            let cleaned_up = Rc::new(RefCell::new(Vec::new()));
+
+            let mapped = map_signal_vec(cx, &rows, {
+                let created = Rc::clone(&created);
+                let cleaned_up = Rc::clone(&cleaned_up);
+                move |cx, row: &i32| {
+                    let (row, cleaned_up) = (*row, Rc::clone(&cleaned_up));
+                    created.borrow_mut().push(row);
+                    on_cleanup(cx, move || cleaned_up.borrow_mut().push(row));
+                    row.to_string()
+                }
+            });
+            assert!(mapped.get().is_empty());
+
+            // insert
+            rows.splice(.., [1, 2, 3]);
+            rows.insert(1, 4);
+            assert_eq!(mapped.get(), ["1", "4", "2", "3"]);
+            assert_eq!(*created.borrow(), vec![1, 2, 3, 4]);
+
+            // remove
+            rows.remove(2);
+            assert_eq!(mapped.get(), ["1", "4", "3"]);
+            assert_eq!(*cleaned_up.borrow(), vec![2]);
+
+            // move, without creating or disposing of any rows
+            rows.swap(0, 2);
+            assert_eq!(mapped.get(), ["3", "4", "1"]);
+            assert_eq!(created.borrow().len(), 4);
+            assert_eq!(cleaned_up.borrow().len(), 1);
+
+            // a row that comes and goes before the list is read is never created
+            rows.push(5);
+            rows.remove(3);
+            assert_eq!(mapped.get(), ["3", "4", "1"]);
+            assert_eq!(created.borrow().len(), 4);
+
+            // clear
+            rows.clear();
+            assert!(mapped.get().is_empty());
+            assert_eq!(*cleaned_up.borrow(), vec![2, 3, 4, 1]);
+        })
+        .dispose();
+    }
 }
@@ -1,7 +1,8 @@
 use crate as leptos;
-use leptos_dom::{Child, IntoChild};
+use leptos_dom::{Child, Element, IntoChild};
 use leptos_macro::Props;
 use leptos_reactive::{provide_context, Scope, SuspenseContext};
+use std::time::Duration;
 
 /// Props for the [Suspense](crate::Suspense) component, which shows a fallback
 /// while [Resource](leptos_reactive::Resource)s are being read.
@@ -14,10 +15,57 @@ where
 {
     /// Will be displayed while resources are pending.
     pub fallback: F,
+    /// Will be displayed instead of the `fallback` once resources have been pending for
+    /// `slow_after`, e.g., a “this is taking longer than expected” message. It is passed the
+    /// component’s [SuspenseContext], so that a “retry” button can call
+    /// [SuspenseContext::refetch_pending]. Only used in the browser.
+    #[builder(default, setter(strip_option))]
+    pub slow_fallback: Option<Box<dyn Fn(SuspenseContext) -> Element>>,
+    /// How long resources have to be pending before the `slow_fallback` is shown. Defaults to
+    /// three seconds.
+    #[builder(default = Duration::from_secs(3))]
+    pub slow_after: Duration,
     /// Will be displayed once all resources have resolved.
     pub children: Box<dyn Fn() -> Vec<G>>,
 }
 
+/// What a [Suspense](crate::Suspense) or [Transition](crate::Transition) shows once loading
+/// is slow, and a signal that says when it is.
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+pub(crate) struct SlowFallback {
+    slow: leptos_reactive::ReadSignal<bool>,
+    fallback: Box<dyn Fn(SuspenseContext) -> Element>,
+    // only created once, and reused whenever loading is slow again
+    el: std::cell::RefCell<Option<Element>>,
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+impl SlowFallback {
+    pub(crate) fn new(
+        cx: Scope,
+        context: SuspenseContext,
+        fallback: Option<Box<dyn Fn(SuspenseContext) -> Element>>,
+        after: Duration,
+    ) -> Option<Self> {
+        fallback.map(|fallback| Self {
+            slow: context.is_slow(cx, after),
+            fallback,
+            el: Default::default(),
+        })
+    }
+
+    // the slow fallback, if loading is slow
+    pub(crate) fn render(&self, cx: Scope, context: SuspenseContext) -> Option<Child> {
+        self.slow.get().then(|| {
+            self.el
+                .borrow_mut()
+                .get_or_insert_with(|| (self.fallback)(context))
+                .clone()
+                .into_child(cx)
+        })
+    }
+}
+
 /// If any [Resource](leptos_reactive::Resource)s are read in the `children` of this
 /// component, it will show the `fallback` while they are loading. Once all are resolved,
 /// it will render the `children`. If data begin loading again, falls back to `fallback` again.
@@ -29,6 +77,11 @@ where
 /// those resources are read under the suspense), so you cannot assume that resources have
 /// `Some` value in `children`.
 ///
+/// In the browser, a `slow_fallback` can take over from the `fallback` once loading has taken
+/// longer than `slow_after` (three seconds, unless you say otherwise). It is passed the
+/// [SuspenseContext], so it can offer to [refetch](SuspenseContext::refetch_pending) whatever
+/// is still loading.
+///
 /// ```
 /// # use leptos_reactive::*;
 /// # use leptos_core::*;
@@ -82,7 +135,15 @@ where
 
     let child = (props.children)().swap_remove(0);
 
-    render_suspense(cx, context, props.fallback, child)
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let slow = SlowFallback::new(cx, context, props.slow_fallback, props.slow_after);
+            render_suspense(cx, context, props.fallback, slow, child)
+        } else {
+            _ = (props.slow_fallback, props.slow_after);
+            render_suspense(cx, context, props.fallback, child)
+        }
+    }
 }
 
 #[cfg(any(feature = "csr", feature = "hydrate"))]
@@ -90,6 +151,7 @@ fn render_suspense<'a, F, E, G>(
     cx: Scope,
     context: SuspenseContext,
     fallback: F,
+    slow_fallback: Option<SlowFallback>,
     child: G,
 ) -> impl Fn() -> Child
 where
@@ -100,6 +162,8 @@ where
     move || {
         if context.ready() {
             (child)().into_child(cx)
+        } else if let Some(slow) = slow_fallback.as_ref().and_then(|s| s.render(cx, context)) {
+            slow
         } else {
             fallback.clone().into_child(cx)
         }
@@ -1,5 +1,11 @@
-use leptos_dom::{Child, IntoChild};
+use leptos_dom::{Child, Element, IntoChild};
 use leptos_reactive::{provide_context, Scope, SignalSetter, SuspenseContext};
+use std::{
+    cell::RefCell,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    time::Duration,
+};
 use typed_builder::TypedBuilder;
 
 /// Props for the [Suspense](crate::Suspense) component, which shows a fallback
@@ -13,15 +19,61 @@ where
 {
     /// Will be displayed while resources are pending.
     pub fallback: F,
+    /// Will be displayed instead of the `fallback` once resources have been pending for
+    /// `slow_after`. Like the `fallback`, it isn’t shown while the previous `children` are
+    /// being kept. See [SuspenseProps::slow_fallback](crate::SuspenseProps::slow_fallback).
+    #[builder(default, setter(strip_option))]
+    pub slow_fallback: Option<Box<dyn Fn(SuspenseContext) -> Element>>,
+    /// How long resources have to be pending before the `slow_fallback` is shown. Defaults to
+    /// three seconds.
+    #[builder(default = Duration::from_secs(3))]
+    pub slow_after: Duration,
     /// A function that will be called when the component transitions into or out of
     /// the `pending` state, with its argument indicating whether it is pending (`true`)
     /// or not pending (`false`).
     #[builder(default, setter(strip_option, into))]
     pub set_pending: Option<SignalSetter<bool>>,
+    /// Identifies what the `children` are showing, e.g., the ID of the item they render.
+    /// When it changes, the `fallback` is shown again while the new item loads, rather
+    /// than the previous item. Further loads with the same key keep showing the previous
+    /// `children`, as usual.
+    #[builder(default, setter(strip_option, into))]
+    pub key: Option<TransitionKey>,
     /// Will be displayed once all resources have resolved.
     pub children: Box<dyn Fn() -> Vec<G>>,
 }
 
+/// Identifies what the `children` of a [Transition] are showing (see [TransitionProps::key]).
+///
+/// This can be created from any function that returns a value that can be compared and
+/// hashed, like `move || id.get()`.
+pub struct TransitionKey {
+    // reads the key, returning its hash and whether it has changed since it was last read
+    read: Box<dyn Fn() -> (u64, bool)>,
+}
+
+impl<F, K> From<F> for TransitionKey
+where
+    F: Fn() -> K + 'static,
+    K: PartialEq + Hash + 'static,
+{
+    fn from(key: F) -> Self {
+        let prev = RefCell::new(None::<K>);
+        Self {
+            read: Box::new(move || {
+                let key = key();
+                let mut hasher = DefaultHasher::new();
+                key.hash(&mut hasher);
+
+                let mut prev = prev.borrow_mut();
+                let changed = prev.as_ref().map_or(false, |prev| prev != &key);
+                *prev = Some(key);
+                (hasher.finish(), changed)
+            }),
+        }
+    }
+}
+
 /// If any [Resource](leptos_reactive::Resource)s are read in the `children` of this
 /// component, it will show the `fallback` while they are loading. Once all are resolved,
 /// it will render the `children`. Unlike [`Suspense`](crate::Suspense), this will not fall
@@ -31,6 +83,10 @@ where
 /// those resources are read under the suspense), so you cannot assume that resources have
 /// `Some` value in `children`.
 ///
+/// If the same `Transition` goes on to show something else entirely, e.g., a different item
+/// when a parent’s prop changes, pass a `key` identifying what it shows, so that it shows the
+/// `fallback` for the new item rather than the previous one.
+///
 /// ```
 /// # use leptos_reactive::*;
 /// # use leptos_core::*;
@@ -88,7 +144,35 @@ where
 
     let child = (props.children)().swap_remove(0);
 
-    render_transition(cx, context, props.fallback, child, props.set_pending)
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let slow = crate::suspense::SlowFallback::new(
+                cx,
+                context,
+                props.slow_fallback,
+                props.slow_after,
+            );
+            render_transition(
+                cx,
+                context,
+                props.fallback,
+                slow,
+                child,
+                props.set_pending,
+                props.key,
+            )
+        } else {
+            _ = (props.slow_fallback, props.slow_after);
+            render_transition(
+                cx,
+                context,
+                props.fallback,
+                child,
+                props.set_pending,
+                props.key,
+            )
+        }
+    }
 }
 
 #[cfg(any(feature = "csr", feature = "hydrate"))]
@@ -96,22 +180,33 @@ fn render_transition<'a, F, E, G>(
     cx: Scope,
     context: SuspenseContext,
     fallback: F,
+    slow_fallback: Option<crate::suspense::SlowFallback>,
     child: G,
     set_pending: Option<SignalSetter<bool>>,
+    key: Option<TransitionKey>,
 ) -> impl Fn() -> Child
 where
     F: IntoChild + Clone,
     E: IntoChild,
     G: Fn() -> E,
 {
-    use std::cell::{Cell, RefCell};
+    use std::cell::Cell;
 
     let has_rendered_once = Cell::new(false);
     let prev_child = RefCell::new(Child::Null);
 
     move || {
+        // the previous key's children shouldn't stand in for the new key's while they load
+        let key_changed = key.as_ref().map_or(false, |key| (key.read)().1);
+        if key_changed {
+            has_rendered_once.set(false);
+        }
         if context.ready() {
-            has_rendered_once.set(true);
+            // resources that start loading for a new key are only counted after a microtask,
+            // so these children may still show the previous key, and shouldn't be kept
+            if !key_changed {
+                has_rendered_once.set(true);
+            }
             let current_child = (child)().into_child(cx);
             *prev_child.borrow_mut() = current_child.clone();
             if let Some(pending) = &set_pending {
@@ -127,7 +222,10 @@ where
             if let Some(pending) = &set_pending {
                 pending.set(true);
             }
-            let fallback = fallback.clone().into_child(cx);
+            let fallback = slow_fallback
+                .as_ref()
+                .and_then(|slow| slow.render(cx, context))
+                .unwrap_or_else(|| fallback.clone().into_child(cx));
             *prev_child.borrow_mut() = fallback.clone();
             fallback
         }
@@ -141,6 +239,7 @@ fn render_transition<'a, F, E, G>(
     fallback: F,
     orig_child: G,
     set_pending: Option<SignalSetter<bool>>,
+    transition_key: Option<TransitionKey>,
 ) -> impl Fn() -> Child
 where
     F: IntoChild + Clone,
@@ -165,7 +264,11 @@ where
         }
         // show the fallback, but also prepare to stream HTML
         else {
-            let key = cx.current_fragment_key();
+            let mut key = cx.current_fragment_key();
+            // so that fragments streamed for different keys don't collide
+            if let Some(transition_key) = &transition_key {
+                key = format!("{key}-{:x}", (transition_key.read)().0);
+            }
             cx.register_suspense(context, &key, move || {
                 orig_child().into_child(cx).as_child_string()
             });
@@ -1,7 +1,9 @@
-use std::rc::Rc;
+use std::{borrow::Cow, rc::Rc};
 
 use leptos_reactive::Scope;
 
+use crate::escape_attr;
+
 /// Represents the different possible values an attribute node could have.
 ///
 /// This mostly exists for the [`view`](https://docs.rs/leptos_macro/latest/leptos_macro/macro.view.html)
@@ -16,13 +18,16 @@ pub enum Attribute {
     Option(Option<String>),
     /// A boolean attribute, which sets the attribute if `true` and removes the attribute if `false`.
     Bool(bool),
+    /// A value joined together from several static or reactive parts, like a `srcset`, which
+    /// is updated as a whole by a single effect whenever any of its parts change.
+    Composite(Composite),
 }
 
 impl Attribute {
     /// Converts the attribute to its HTML value at that moment so it can be rendered on the server.
     pub fn as_value_string(&self, attr_name: &'static str) -> String {
         match self {
-            Attribute::String(value) => format!("{attr_name}=\"{value}\""),
+            Attribute::String(value) => format!("{attr_name}=\"{}\"", escape_attr(value)),
             Attribute::Fn(f) => {
                 let mut value = f();
                 while let Attribute::Fn(f) = value {
@@ -32,8 +37,11 @@ impl Attribute {
             }
             Attribute::Option(value) => value
                 .as_ref()
-                .map(|value| format!("{attr_name}=\"{value}\""))
+                .map(|value| format!("{attr_name}=\"{}\"", escape_attr(value)))
                 .unwrap_or_default(),
+            Attribute::Composite(composite) => {
+                format!("{attr_name}=\"{}\"", escape_attr(&composite.value()))
+            }
             Attribute::Bool(include) => {
                 if *include {
                     attr_name.to_string()
@@ -52,6 +60,7 @@ impl PartialEq for Attribute {
             (Self::Fn(_), Self::Fn(_)) => false,
             (Self::Option(l0), Self::Option(r0)) => l0 == r0,
             (Self::Bool(l0), Self::Bool(r0)) => l0 == r0,
+            (Self::Composite(_), Self::Composite(_)) => false,
             _ => false,
         }
     }
@@ -64,10 +73,113 @@ impl std::fmt::Debug for Attribute {
             Self::Fn(_) => f.debug_tuple("Fn").finish(),
             Self::Option(arg0) => f.debug_tuple("Option").field(arg0).finish(),
             Self::Bool(arg0) => f.debug_tuple("Bool").field(arg0).finish(),
+            Self::Composite(arg0) => f.debug_tuple("Composite").field(arg0).finish(),
+        }
+    }
+}
+
+/// One part of a [Composite] attribute value.
+#[derive(Clone)]
+pub enum AttributePart {
+    /// A part that never changes.
+    Static(String),
+    /// A (presumably reactive) part, which is left out while it is `None`.
+    Fn(Rc<dyn Fn() -> Option<String>>),
+}
+
+impl std::fmt::Debug for AttributePart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Static(arg0) => f.debug_tuple("Static").field(arg0).finish(),
+            Self::Fn(_) => f.debug_tuple("Fn").finish(),
         }
     }
 }
 
+/// An attribute value built from several parts, some of which may be reactive, which are
+/// joined with a separator, leaving out any that are empty.
+///
+/// This is useful for attributes that hold a list, like `srcset`, `class`, or `style`: the
+/// whole value is updated by one effect, rather than each part needing its own attribute.
+///
+/// ```
+/// # use leptos_reactive::*; use leptos_dom::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (retina, set_retina) = create_signal(cx, false);
+/// let srcset = Composite::srcset()
+///   .part("photo.jpg 1x")
+///   .reactive(move || retina.get().then(|| "photo@2x.jpg 2x".to_string()));
+/// assert_eq!(srcset.value(), "photo.jpg 1x");
+/// set_retina(true);
+/// assert_eq!(srcset.value(), "photo.jpg 1x, photo@2x.jpg 2x");
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Composite {
+    separator: &'static str,
+    parts: Vec<AttributePart>,
+}
+
+impl Composite {
+    /// Creates an empty value whose parts are joined with `separator`.
+    pub fn new(separator: &'static str) -> Self {
+        Self {
+            separator,
+            parts: Vec::new(),
+        }
+    }
+
+    /// Creates an empty value whose parts are joined like the candidates in a `srcset`.
+    pub fn srcset() -> Self {
+        Self::new(", ")
+    }
+
+    /// Creates an empty value whose parts are joined like the classes in a `class`.
+    pub fn class() -> Self {
+        Self::new(" ")
+    }
+
+    /// Creates an empty value whose parts are joined like the declarations in a `style`.
+    pub fn style() -> Self {
+        Self::new("; ")
+    }
+
+    /// Adds a part that never changes.
+    pub fn part(mut self, part: impl Into<String>) -> Self {
+        self.parts.push(AttributePart::Static(part.into()));
+        self
+    }
+
+    /// Adds a (presumably reactive) part, which is left out while it returns `None`.
+    pub fn reactive(mut self, part: impl Fn() -> Option<String> + 'static) -> Self {
+        self.parts.push(AttributePart::Fn(Rc::new(part)));
+        self
+    }
+
+    /// The parts as they are at the moment, joined together. This reads any signals in the
+    /// reactive parts, so calling it in an effect subscribes the effect to all of them.
+    pub fn value(&self) -> String {
+        let mut value = String::new();
+        for part in &self.parts {
+            let part = match part {
+                AttributePart::Static(part) => Cow::Borrowed(part.as_str()),
+                AttributePart::Fn(f) => match f() {
+                    Some(part) => Cow::Owned(part),
+                    None => continue,
+                },
+            };
+            if part.is_empty() {
+                continue;
+            }
+            if !value.is_empty() {
+                value.push_str(self.separator);
+            }
+            value.push_str(&part);
+        }
+        value
+    }
+}
+
 /// Converts some type into an [Attribute].
 ///
 /// This is implemented by default for Rust primitive and string types.
@@ -88,6 +200,12 @@ impl IntoAttribute for bool {
     }
 }
 
+impl IntoAttribute for Composite {
+    fn into_attribute(self, _cx: Scope) -> Attribute {
+        Attribute::Composite(self)
+    }
+}
+
 impl IntoAttribute for Option<String> {
     fn into_attribute(self, _cx: Scope) -> Attribute {
         Attribute::Option(self)
@@ -105,6 +223,17 @@ where
     }
 }
 
+// with the `stable` feature, signals can’t be called like closures, so they need their own impl
+#[cfg(feature = "stable")]
+impl<T> IntoAttribute for leptos_reactive::Signal<T>
+where
+    T: IntoAttribute + Clone,
+{
+    fn into_attribute(self, cx: Scope) -> Attribute {
+        (move || self.get()).into_attribute(cx)
+    }
+}
+
 macro_rules! attr_type {
     ($attr_type:ty) => {
         impl IntoAttribute for $attr_type {
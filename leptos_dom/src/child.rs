@@ -1,6 +1,6 @@
 use cfg_if::cfg_if;
 
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, future::Future, pin::Pin, rc::Rc};
 
 use leptos_reactive::Scope;
 
@@ -187,7 +187,7 @@ cfg_if! {
 // `stable` feature
 cfg_if! {
     if #[cfg(feature = "stable")] {
-        use leptos_reactive::{Memo, ReadSignal, RwSignal};
+        use leptos_reactive::{Memo, ReadSignal, RwSignal, Signal};
 
         impl IntoChild for Memo<Vec<crate::Element>> {
             fn into_child(self, cx: Scope) -> Child {
@@ -206,5 +206,96 @@ cfg_if! {
                 (move || self.get()).into_child(cx)
             }
         }
+
+        impl<T> IntoChild for Signal<T>
+        where
+            T: IntoChild + Clone,
+        {
+            fn into_child(self, cx: Scope) -> Child {
+                (move || self.get()).into_child(cx)
+            }
+        }
+    }
+}
+
+/// Wraps a `Future` so that its result can be used directly as a child in the view,
+/// without declaring a [Resource](leptos_reactive::Resource) yourself. This is useful
+/// for small, one-off async values like a relative timestamp or a count.
+///
+/// The `Future` is loaded by a [local resource](leptos_reactive::create_local_resource):
+/// nothing is rendered until it resolves, and its output is then rendered in place. Like
+/// any other resource, it counts as pending for a `<Suspense/>` that it’s rendered under.
+///
+/// The `view` macro does this for you when an `async` block is used as a child, and a
+/// boxed `Future` (like one returned by `FutureExt::boxed_local`) can be used as a child directly.
+///
+/// On the server, the `Future` is **not** awaited: only the empty placeholder is rendered,
+/// and the value is loaded in the browser after hydration. If you want the value to be
+/// included in the server-rendered HTML, use [create_resource](leptos_reactive::create_resource) instead.
+///
+/// ```
+/// # use leptos_reactive::*; use leptos_dom::*; use leptos_macro::view;
+/// async fn unread_count() -> usize {
+///   // fetch something from an API...
+///   3
+/// }
+///
+/// # run_scope(create_runtime(), |cx| {
+/// # if false {
+/// let badge = view! { cx,
+///   <span class="badge">{async_child(unread_count())}</span>
+/// };
+/// // or, equivalently
+/// let badge = view! { cx,
+///   <span class="badge">{async { unread_count().await }}</span>
+/// };
+/// # }
+/// # });
+/// ```
+pub fn async_child<F, C>(fut: F) -> AsyncChild<F>
+where
+    F: Future<Output = C> + 'static,
+    C: IntoChild,
+{
+    AsyncChild(fut)
+}
+
+/// A `Future` that will be rendered in place once it resolves. See [async_child].
+pub struct AsyncChild<F>(F);
+
+impl<F, C> IntoChild for AsyncChild<F>
+where
+    F: Future<Output = C> + 'static,
+    C: IntoChild,
+{
+    fn into_child(self, cx: Scope) -> Child {
+        use leptos_reactive::create_local_resource;
+
+        // the source never changes, so the fetcher only runs once
+        let fut = RefCell::new(Some(self.0));
+        let resource = create_local_resource(
+            cx,
+            || (),
+            move |_| {
+                let fut = fut.borrow_mut().take();
+                async move {
+                    match fut {
+                        Some(fut) => fut.await.into_child(cx),
+                        None => Child::Null,
+                    }
+                }
+            },
+        );
+
+        (move || resource.read()).into_child(cx)
+    }
+}
+
+impl<C> IntoChild for Pin<Box<dyn Future<Output = C>>>
+where
+    C: IntoChild + 'static,
+{
+    fn into_child(self, cx: Scope) -> Child {
+        async_child(self).into_child(cx)
     }
 }
@@ -0,0 +1,166 @@
+//! Writes to the `value` and `checked` properties of form controls, which the user can change
+//! as well as the app. Without care, writing back a value the user has just typed can drop or
+//! duplicate their next keystroke, move the cursor to the end, or cancel an IME composition.
+
+use wasm_bindgen::{closure::Closure, JsCast, JsValue, UnwrapThrowExt};
+
+use crate::document;
+
+// set on elements with a reactive `value` binding while an IME composition is in progress
+const COMPOSING: &str = "$$leptosComposing";
+// the last value written to an element during a composition, applied once it ends
+const DEFERRED: &str = "$$leptosDeferred";
+
+thread_local! {
+    static COMPOSITION_LISTENERS: (JsValue, JsValue) = (
+        Closure::wrap(Box::new(|ev: web_sys::Event| {
+            if let Some(el) = ev.current_target() {
+                _ = js_sys::Reflect::set(&el, &JsValue::from_str(COMPOSING), &JsValue::TRUE);
+            }
+        }) as Box<dyn Fn(web_sys::Event)>)
+        .into_js_value(),
+        Closure::wrap(Box::new(|ev: web_sys::Event| {
+            let Some(el) = ev.current_target() else {
+                return;
+            };
+            _ = js_sys::Reflect::set(&el, &JsValue::from_str(COMPOSING), &JsValue::FALSE);
+            let deferred = js_sys::Reflect::get(&el, &JsValue::from_str(DEFERRED))
+                .unwrap_or(JsValue::UNDEFINED);
+            if !deferred.is_undefined() {
+                let el = el.unchecked_into::<web_sys::Element>();
+                _ = js_sys::Reflect::delete_property(&el, &JsValue::from_str(DEFERRED));
+                set_property(&el, "value", deferred);
+            }
+        }) as Box<dyn Fn(web_sys::Event)>)
+        .into_js_value(),
+    );
+}
+
+/// Whether the property can be changed by the user, and so needs to be written with
+/// [set_property] rather than simply being set.
+pub(crate) fn is_controlled(prop_name: &str) -> bool {
+    matches!(prop_name, "value" | "checked")
+}
+
+/// Tracks IME compositions on an element with a reactive `value` binding, so that writes to
+/// its `value` can wait until the composition ends. Only adds the listeners once per element.
+pub(crate) fn track_composition(el: &web_sys::Element) {
+    let key = JsValue::from_str(COMPOSING);
+    if js_sys::Reflect::has(el, &key).unwrap_or(true) {
+        return;
+    }
+    _ = js_sys::Reflect::set(el, &key, &JsValue::FALSE);
+    COMPOSITION_LISTENERS.with(|(start, end)| {
+        _ = el.add_event_listener_with_callback("compositionstart", start.unchecked_ref());
+        _ = el.add_event_listener_with_callback("compositionend", end.unchecked_ref());
+    });
+}
+
+/// Writes a controlled property (see [is_controlled]), unless the element already has that
+/// value. A `value` written during a composition is held until it ends, and a `value` written
+/// to a focused text field keeps the selection in place relative to the text around it.
+pub(crate) fn set_property(el: &web_sys::Element, prop_name: &str, value: JsValue) {
+    let key = JsValue::from_str(prop_name);
+    if prop_name == "value" && is_composing(el) {
+        _ = js_sys::Reflect::set(el, &JsValue::from_str(DEFERRED), &value);
+        return;
+    }
+
+    // the live value is always a string, even if the bound value is a number
+    let live = js_sys::Reflect::get(el, &key).unwrap_or(JsValue::UNDEFINED);
+    if live.loose_eq(&value) {
+        return;
+    }
+
+    let selection = (prop_name == "value")
+        .then(|| focused_selection(el))
+        .flatten();
+    js_sys::Reflect::set(el, &key, &value).unwrap_throw();
+    if let Some((field, start, end, direction)) = selection {
+        let new = js_sys::Reflect::get(el, &key).unwrap_or(JsValue::UNDEFINED);
+        let (old, new) = (utf16(&live), utf16(&new));
+        let (start, end) = (
+            moved_offset(&old, &new, start),
+            moved_offset(&old, &new, end),
+        );
+        field.set_selection(start, end, &direction);
+    }
+}
+
+fn is_composing(el: &web_sys::Element) -> bool {
+    js_sys::Reflect::get(el, &JsValue::from_str(COMPOSING))
+        .map(|composing| composing.is_truthy())
+        .unwrap_or(false)
+}
+
+enum TextField {
+    Input(web_sys::HtmlInputElement),
+    TextArea(web_sys::HtmlTextAreaElement),
+}
+
+impl TextField {
+    fn set_selection(&self, start: u32, end: u32, direction: &str) {
+        // types like `number` don't support selection, and throw
+        _ = match self {
+            TextField::Input(el) => el.set_selection_range_with_direction(start, end, direction),
+            TextField::TextArea(el) => el.set_selection_range_with_direction(start, end, direction),
+        };
+    }
+}
+
+// the text field, selection range, and selection direction, if the element is focused
+fn focused_selection(el: &web_sys::Element) -> Option<(TextField, u32, u32, String)> {
+    if document().active_element().as_ref() != Some(el) {
+        return None;
+    }
+    let (field, start, end, direction) =
+        if let Some(input) = el.dyn_ref::<web_sys::HtmlInputElement>() {
+            (
+                TextField::Input(input.clone()),
+                input.selection_start().ok()??,
+                input.selection_end().ok()??,
+                input.selection_direction().ok()??,
+            )
+        } else {
+            let textarea = el.dyn_ref::<web_sys::HtmlTextAreaElement>()?;
+            (
+                TextField::TextArea(textarea.clone()),
+                textarea.selection_start().ok()??,
+                textarea.selection_end().ok()??,
+                textarea.selection_direction().ok()??,
+            )
+        };
+    Some((field, start, end, direction))
+}
+
+// selection offsets count UTF-16 code units
+fn utf16(value: &JsValue) -> Vec<u16> {
+    value
+        .as_string()
+        .unwrap_or_default()
+        .encode_utf16()
+        .collect()
+}
+
+/// Where an offset into `old` ends up in `new`: offsets in the text the two share at the start
+/// stay put, offsets in the text they share at the end move with it, and offsets in the part
+/// that changed move to the end of its replacement.
+fn moved_offset(old: &[u16], new: &[u16], offset: u32) -> u32 {
+    let offset = (offset as usize).min(old.len());
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let moved = if offset <= prefix {
+        offset
+    } else if offset >= old.len() - suffix {
+        offset + new.len() - old.len()
+    } else {
+        new.len() - suffix
+    };
+    moved as u32
+}
@@ -0,0 +1,201 @@
+use leptos_reactive::{on_cleanup, Scope, ScopeDisposer};
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+
+use crate::{body, create_element, document, event_delegation, insert, Child, Marker};
+
+// lays the container out at its natural size, where it can't be seen
+const MEASURING_STYLE: &str =
+    "position: absolute; visibility: hidden; pointer-events: none; left: -100000px; top: 0";
+
+// once attached, the container doesn't affect the layout of its contents
+const ATTACHED_STYLE: &str = "display: contents";
+
+/// Renders a [Child] into a detached DOM tree, which isn’t part of the page, in a child scope of `cx`.
+///
+/// The tree is fully reactive: its text, attributes, and properties are updated as the signals
+/// they depend on change, just like the rest of the page. This makes it possible to
+/// [measure](DetachedRender::measure) a view before deciding where it goes, to take an
+/// [HTML snapshot](DetachedRender::to_html_string) of a view for printing or exporting, or to
+/// prepare a view ahead of time and [attach](DetachedRender::attach) it to the page later
+/// without rendering it again.
+///
+/// The tree is rendered into a `<div>`, which is also what is attached to the page. The child
+/// scope, and the event listeners in the tree, are disposed of along with `cx`, or with
+/// [DetachedRender::dispose].
+///
+/// ```rust,ignore
+/// # use leptos_dom::*;
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (name, set_name) = create_signal(cx, "Leptos".to_string());
+/// let tooltip = render_detached(cx, |cx| {
+///   Child::Fn(std::rc::Rc::new(std::cell::RefCell::new(move || {
+///     Child::Text(format!("Hello, {}!", name.get()))
+///   })))
+/// });
+///
+/// // decide where the tooltip goes, depending on how wide it is
+/// let width = tooltip.measure().width();
+/// # let parent = document().body().unwrap();
+/// tooltip.attach(&parent);
+/// # });
+/// ```
+pub fn render_detached(cx: Scope, f: impl FnOnce(Scope) -> Child) -> DetachedRender {
+    let container = create_element("div").unchecked_into::<web_sys::HtmlElement>();
+    let disposer = cx.child_scope({
+        let container = container.clone();
+        move |cx| {
+            let child = f(cx);
+            insert(
+                cx,
+                container.clone().unchecked_into(),
+                child,
+                Marker::NoChildren,
+                None,
+            );
+            on_cleanup(cx, move || {
+                event_delegation::remove_delegated_listeners(&container);
+            });
+        }
+    });
+
+    DetachedRender {
+        container,
+        disposer: Some(disposer),
+    }
+}
+
+/// A DOM tree rendered by [render_detached].
+pub struct DetachedRender {
+    container: web_sys::HtmlElement,
+    disposer: Option<ScopeDisposer>,
+}
+
+impl DetachedRender {
+    /// The top-level nodes of the tree.
+    pub fn root_nodes(&self) -> Vec<web_sys::Node> {
+        let nodes = self.container.child_nodes();
+        (0..nodes.length())
+            .filter_map(|idx| nodes.item(idx))
+            .collect()
+    }
+
+    /// The `<div>` that the tree is rendered into.
+    pub fn container(&self) -> &web_sys::HtmlElement {
+        &self.container
+    }
+
+    /// Whether the tree has been [attached](DetachedRender::attach) to the page.
+    pub fn is_attached(&self) -> bool {
+        self.container.is_connected()
+    }
+
+    /// Measures the box around the tree.
+    ///
+    /// If the tree isn’t attached to the page, it is attached for as long as it takes to
+    /// measure it, out of sight and out of the flow of the page, so its width and height are
+    /// its natural size (its position isn’t meaningful). If it is attached, this measures it
+    /// where it is.
+    pub fn measure(&self) -> web_sys::DomRect {
+        let detached = !self.is_attached();
+        let style = self.container.get_attribute("style");
+        if detached {
+            _ = self.container.set_attribute("style", MEASURING_STYLE);
+            body()
+                .expect("no <body> to measure in")
+                .append_child(&self.container)
+                .unwrap_throw();
+        }
+
+        let range = document().create_range().unwrap_throw();
+        range.select_node_contents(&self.container).unwrap_throw();
+        let rect = range.get_bounding_client_rect();
+
+        if detached {
+            self.container.remove();
+            match style {
+                Some(style) => _ = self.container.set_attribute("style", &style),
+                None => _ = self.container.remove_attribute("style"),
+            }
+        }
+        rect
+    }
+
+    /// Serializes the tree as it is now, as HTML.
+    ///
+    /// Unlike reading its `innerHTML`, this includes the current state of form fields, which
+    /// the user or a `prop:` binding may have changed since their attributes were set: the
+    /// values of `<input>`s and `<textarea>`s, whether checkboxes and radio buttons are checked,
+    /// and which `<option>`s are selected.
+    pub fn to_html_string(&self) -> String {
+        let snapshot = self
+            .container
+            .clone_node_with_deep(true)
+            .unwrap_throw()
+            .unchecked_into::<web_sys::Element>();
+
+        // the copy has the same structure, so its fields are in the same order
+        let selector = "input, textarea, option";
+        let live = self.container.query_selector_all(selector).unwrap_throw();
+        let copies = snapshot.query_selector_all(selector).unwrap_throw();
+        for idx in 0..live.length() {
+            let (Some(live), Some(copy)) = (live.item(idx), copies.item(idx)) else {
+                continue;
+            };
+            let copy = copy.unchecked_into::<web_sys::Element>();
+            if let Some(input) = live.dyn_ref::<web_sys::HtmlInputElement>() {
+                match input.type_().as_str() {
+                    "checkbox" | "radio" => reflect_flag(&copy, "checked", input.checked()),
+                    _ => _ = copy.set_attribute("value", &input.value()),
+                }
+            } else if let Some(textarea) = live.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+                copy.set_text_content(Some(&textarea.value()));
+            } else if let Some(option) = live.dyn_ref::<web_sys::HtmlOptionElement>() {
+                reflect_flag(&copy, "selected", option.selected());
+            }
+        }
+
+        snapshot.inner_html()
+    }
+
+    /// Attaches the tree to the page, as the last child of `parent`, without rendering it again.
+    ///
+    /// The tree stays inside its `<div>`, which is given `display: contents`, so that its
+    /// contents are laid out as if they were children of `parent`. Event listeners in the
+    /// tree start receiving events once it is attached.
+    pub fn attach(&self, parent: &web_sys::Node) {
+        _ = self.container.set_attribute("style", ATTACHED_STYLE);
+        parent.append_child(&self.container).unwrap_throw();
+    }
+
+    /// Removes the tree from the page again, keeping it up to date while it is detached.
+    pub fn detach(&self) {
+        self.container.remove();
+        _ = self.container.remove_attribute("style");
+    }
+
+    /// Disposes of the child scope that the tree was rendered in, removes the event
+    /// listeners in the tree, and removes it from the page if it is attached.
+    pub fn dispose(mut self) {
+        if let Some(disposer) = self.disposer.take() {
+            disposer.dispose();
+        }
+        self.container.remove();
+    }
+}
+
+impl std::fmt::Debug for DetachedRender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DetachedRender")
+            .field("attached", &self.is_attached())
+            .finish()
+    }
+}
+
+fn reflect_flag(el: &web_sys::Element, name: &str, value: bool) {
+    if value {
+        _ = el.set_attribute(name, "");
+    } else {
+        _ = el.remove_attribute(name);
+    }
+}
@@ -0,0 +1,43 @@
+use std::borrow::Cow;
+
+/// Encodes strings to be used as text in HTML by escaping `&`, `<`, and `>`.
+pub fn escape_text(text: &str) -> Cow<'_, str> {
+    html_escape::encode_text(text)
+}
+
+/// Encodes strings to be used as double-quoted attribute values in HTML by escaping `&`, `<`,
+/// `>`, and `"`.
+///
+/// Carriage returns are escaped too, and null characters are removed (as they are when
+/// attributes are set in the browser), because the HTML parser would otherwise change them:
+/// this way, parsing the attribute gives back exactly the value it would have been set to
+/// in the browser.
+pub fn escape_attr(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>', '"', '\r', '\0']) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 16);
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\0' => {}
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Removes any null characters from an attribute value. The HTML parser replaces them, so
+/// values set in the browser would otherwise differ from the same values rendered on the server.
+#[cfg(any(doc, feature = "csr", feature = "hydrate"))] // `render` is built for the docs too
+pub(crate) fn strip_nulls(value: &str) -> Cow<'_, str> {
+    if value.contains('\0') {
+        Cow::Owned(value.replace('\0', ""))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
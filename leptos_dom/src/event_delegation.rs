@@ -1,21 +1,35 @@
 use std::cell::RefCell;
 use std::collections::HashSet;
 
-use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
 
-use crate::window_event_listener;
+use crate::{window, EventOptions};
 
 thread_local! {
-    pub static GLOBAL_EVENTS: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+    pub static GLOBAL_EVENTS: RefCell<HashSet<(&'static str, EventOptions)>> = RefCell::new(HashSet::new());
 }
 
 // cf eventHandler in ryansolid/dom-expressions
-pub(crate) fn add_event_listener(event_name: &'static str) {
+pub(crate) fn add_event_listener(event_name: &'static str, options: EventOptions) {
+    // `once` is handled by each element's handler; the global handler is shared
+    let options = EventOptions {
+        once: false,
+        ..options
+    };
     GLOBAL_EVENTS.with(|global_events| {
         let mut events = global_events.borrow_mut();
-        if !events.contains(event_name) {
-            // create global handler
-            let key = JsValue::from_str(&event_delegation_key(event_name));
+        if !events.contains(&(event_name, options)) {
+            // create global handler, which runs both the `once` and the other element handlers
+            let keys = [
+                JsValue::from_str(&event_delegation_key(event_name, options)),
+                JsValue::from_str(&event_delegation_key(
+                    event_name,
+                    EventOptions {
+                        once: true,
+                        ..options
+                    },
+                )),
+            ];
             let handler = move |ev: web_sys::Event| {
                 let target = ev.target();
                 let node = ev.composed_path().get(0);
@@ -29,13 +43,39 @@ pub(crate) fn add_event_listener(event_name: &'static str) {
 
                 // TODO simulate currentTarget
 
+                // collect the path first, so that capturing handlers can run from the outside in
+                let mut path = Vec::new();
                 while !node.is_null() {
+                    path.push(node.clone());
+
+                    // navigate up tree
+                    let host =
+                        js_sys::Reflect::get(&node, &JsValue::from_str("host")).unwrap_throw();
+                    if host.is_truthy() && host != node && host.dyn_ref::<web_sys::Node>().is_some()
+                    {
+                        node = host;
+                    } else if let Some(parent) =
+                        node.unchecked_into::<web_sys::Node>().parent_node()
+                    {
+                        node = parent.into()
+                    } else {
+                        node = JsValue::null()
+                    }
+                }
+                if options.capture {
+                    path.reverse();
+                }
+
+                for node in path {
                     let node_is_disabled =
                         js_sys::Reflect::get(&node, &JsValue::from_str("disabled"))
                             .unwrap_throw()
                             .is_truthy();
-                    if !node_is_disabled {
-                        let maybe_handler = js_sys::Reflect::get(&node, &key).unwrap_throw();
+                    if node_is_disabled {
+                        continue;
+                    }
+                    for key in &keys {
+                        let maybe_handler = js_sys::Reflect::get(&node, key).unwrap_throw();
                         if !maybe_handler.is_undefined() {
                             let f = maybe_handler.unchecked_ref::<js_sys::Function>();
                             if let Err(e) = f.call1(&node, &ev) {
@@ -52,33 +92,55 @@ pub(crate) fn add_event_listener(event_name: &'static str) {
                             }
                         }
                     }
-
-                    // navigate up tree
-                    let host =
-                        js_sys::Reflect::get(&node, &JsValue::from_str("host")).unwrap_throw();
-                    if host.is_truthy() && host != node && host.dyn_ref::<web_sys::Node>().is_some()
-                    {
-                        node = host;
-                    } else if let Some(parent) =
-                        node.unchecked_into::<web_sys::Node>().parent_node()
-                    {
-                        node = parent.into()
-                    } else {
-                        node = JsValue::null()
-                    }
                 }
             };
 
-            window_event_listener(event_name, handler);
+            let handler = Closure::wrap(Box::new(handler) as Box<dyn FnMut(web_sys::Event)>)
+                .into_js_value();
+            _ = window().add_event_listener_with_callback_and_add_event_listener_options(
+                event_name,
+                handler.unchecked_ref(),
+                &options.to_add_event_listener_options(),
+            );
 
             // register that we've created handler
-            events.insert(event_name);
+            events.insert((event_name, options));
         }
     })
 }
 
-pub(crate) fn event_delegation_key(event_name: &'static str) -> String {
+// removes the handlers of every delegated event from `root` and the elements inside it
+#[cfg(any(doc, feature = "csr", feature = "hydrate"))]
+pub(crate) fn remove_delegated_listeners(root: &web_sys::Element) {
+    let remove = |el: &web_sys::Element| {
+        for key in js_sys::Object::keys(el).iter() {
+            if key.as_string().is_some_and(|key| key.starts_with("$$$")) {
+                _ = js_sys::Reflect::delete_property(el, &key);
+            }
+        }
+    };
+    remove(root);
+    if let Ok(descendants) = root.query_selector_all("*") {
+        for el in (0..descendants.length()).filter_map(|idx| descendants.item(idx)) {
+            remove(el.unchecked_ref());
+        }
+    }
+}
+
+pub(crate) fn event_delegation_key(event_name: &'static str, options: EventOptions) -> String {
     let mut n = String::from("$$$");
     n.push_str(event_name);
+    // each set of options has its own global handler, so needs its own key
+    if options.passive {
+        n.push_str(":passive");
+    }
+    if options.capture {
+        n.push_str(":capture");
+    }
+    // a `once` handler shares the global handler, but not its key, so that it can be removed
+    // without removing another handler for the same event on the same element
+    if options.once {
+        n.push_str(":once");
+    }
     n
 }
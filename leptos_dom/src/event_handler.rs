@@ -0,0 +1,79 @@
+use std::rc::Rc;
+
+/// A handler for events of type `E`, like [web_sys::MouseEvent], for use as a component prop.
+///
+/// When a component prop is an `EventHandler<E>` (or an `Option<EventHandler<E>>`), its
+/// builder takes any `Fn(E)`, so the [view](https://docs.rs/leptos/latest/leptos/macro.view.html)
+/// macro can pass it a closure without annotating the type of the event, and the component
+/// receives an event of the right type without casting it.
+///
+/// An `EventHandler<E>` is itself an `Fn(E)`, so a component can forward the handler it was
+/// given to another component’s prop as it is. (With the `stable` feature, it isn’t, so pass
+/// `move |ev| handler.call(ev)` instead.)
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn FancyButton(cx: Scope, on_click: EventHandler<web_sys::MouseEvent>) -> Element {
+///   view! { cx,
+///     <button class="fancy" on:click=move |ev| on_click.call(ev)>"Click me"</button>
+///   }
+/// }
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///   let (x, set_x) = create_signal(cx, 0);
+///   view! { cx,
+///     // `ev` is a `MouseEvent`
+///     <FancyButton on_click=move |ev| set_x(ev.client_x())/>
+///   }
+/// }
+/// ```
+pub struct EventHandler<E>(Rc<dyn Fn(E)>);
+
+impl<E> EventHandler<E> {
+    /// Creates a handler that calls `handler` with each event.
+    pub fn new(handler: impl Fn(E) + 'static) -> Self {
+        Self(Rc::new(handler))
+    }
+
+    /// Calls the handler with the `event`.
+    pub fn call(&self, event: E) {
+        (self.0)(event)
+    }
+}
+
+impl<E> Clone for EventHandler<E> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<E> std::fmt::Debug for EventHandler<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("EventHandler").finish()
+    }
+}
+
+#[cfg(not(feature = "stable"))]
+impl<E> FnOnce<(E,)> for EventHandler<E> {
+    type Output = ();
+
+    extern "rust-call" fn call_once(self, args: (E,)) -> Self::Output {
+        self.call(args.0)
+    }
+}
+
+#[cfg(not(feature = "stable"))]
+impl<E> FnMut<(E,)> for EventHandler<E> {
+    extern "rust-call" fn call_mut(&mut self, args: (E,)) -> Self::Output {
+        self.call(args.0)
+    }
+}
+
+#[cfg(not(feature = "stable"))]
+impl<E> Fn<(E,)> for EventHandler<E> {
+    extern "rust-call" fn call(&self, args: (E,)) -> Self::Output {
+        EventHandler::call(self, args.0)
+    }
+}
@@ -0,0 +1,198 @@
+//! Keeps focus, and the selection in text fields, where it was when a reactive block replaces
+//! the part of the DOM that holds the focused element. Without this, rerunning a parent closure
+//! that renders "the same" `<input>` again drops focus on the floor mid-keystroke.
+//!
+//! Nodes that are patched or moved rather than replaced keep (or get back) their own focus. A
+//! replaced element is matched in the new nodes by its `id`, then by its `data-focus-key`
+//! attribute, then by its position among the focusable elements.
+
+use std::{fmt::Debug, rc::Rc};
+
+use leptos_reactive::{use_context, Scope};
+use wasm_bindgen::JsCast;
+
+use crate::{document, Child};
+
+/// Put this attribute on an element to leave focus alone when it, or anything inside it, is
+/// replaced, e.g., on a large, frequently re-rendered region whose focus doesn’t matter.
+pub const SKIP_FOCUS_ATTRIBUTE: &str = "data-leptos-skip-focus";
+
+/// Identifies an element for focus preservation when it has no `id`. The replacement element
+/// should carry the same key.
+pub const FOCUS_KEY_ATTRIBUTE: &str = "data-focus-key";
+
+const FOCUSABLE: &str = "a[href], button, input, select, textarea, [tabindex], [contenteditable]";
+
+/// Called with the old element when a reactive block replaced the focused element, and nothing
+/// in what replaced it matches it. Provide it as context to announce the change or to move
+/// focus somewhere sensible.
+/// ```ignore
+/// provide_context(cx, OnFocusLost::new(|_| {
+///     _ = document().get_element_by_id("main").map(|el| el.unchecked_into::<HtmlElement>().focus());
+/// }));
+/// ```
+#[derive(Clone)]
+pub struct OnFocusLost(pub Rc<dyn Fn(web_sys::Element)>);
+
+impl OnFocusLost {
+    /// Wraps a function to be called when focus is lost.
+    pub fn new(f: impl Fn(web_sys::Element) + 'static) -> Self {
+        Self(Rc::new(f))
+    }
+}
+
+impl Debug for OnFocusLost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OnFocusLost").finish()
+    }
+}
+
+// how to find the focused element again among the new nodes
+enum FocusTarget {
+    Id(String),
+    Key(String),
+    Position(usize),
+}
+
+// the focused element inside nodes that are about to be replaced
+pub(crate) struct FocusSnapshot {
+    element: web_sys::Element,
+    target: FocusTarget,
+    selection: Option<(u32, u32, Option<String>)>,
+}
+
+impl FocusSnapshot {
+    /// Records the focused element, if it is inside `current`.
+    pub(crate) fn capture(current: &Child) -> Option<Self> {
+        let nodes = nodes(current);
+        let element = document().active_element()?;
+        if !nodes
+            .iter()
+            .any(|node| node.contains(Some(element.as_ref())))
+        {
+            return None;
+        }
+        if element
+            .closest(&format!("[{SKIP_FOCUS_ATTRIBUTE}]"))
+            .ok()
+            .flatten()
+            .is_some()
+        {
+            return None;
+        }
+
+        let target = if !element.id().is_empty() {
+            FocusTarget::Id(element.id())
+        } else if let Some(key) = element.get_attribute(FOCUS_KEY_ATTRIBUTE) {
+            FocusTarget::Key(key)
+        } else {
+            FocusTarget::Position(
+                matching(nodes, FOCUSABLE)
+                    .iter()
+                    .position(|el| el == &element)?,
+            )
+        };
+
+        Some(Self {
+            selection: selection(&element),
+            element,
+            target,
+        })
+    }
+
+    /// Moves focus back to the element in `new` that matches the one recorded, or calls the
+    /// [OnFocusLost] hook if there isn’t one.
+    pub(crate) fn restore(self, cx: Scope, new: &Child) {
+        let nodes = nodes(new);
+        let inside =
+            |el: &web_sys::Element| nodes.iter().any(|node| node.contains(Some(el.as_ref())));
+
+        let found = if inside(&self.element) {
+            Some(self.element.clone())
+        } else {
+            match &self.target {
+                FocusTarget::Id(id) => document().get_element_by_id(id).filter(inside),
+                FocusTarget::Key(key) => matching(nodes, &format!("[{FOCUS_KEY_ATTRIBUTE}]"))
+                    .into_iter()
+                    .find(|el| el.get_attribute(FOCUS_KEY_ATTRIBUTE).as_ref() == Some(key)),
+                FocusTarget::Position(idx) => matching(nodes, FOCUSABLE).into_iter().nth(*idx),
+            }
+        };
+
+        match found {
+            Some(el) => {
+                if document().active_element().as_ref() == Some(&el) {
+                    return;
+                }
+                if let Some(el) = el.dyn_ref::<web_sys::HtmlElement>() {
+                    _ = el.focus();
+                }
+                if let Some((start, end, direction)) = &self.selection {
+                    set_selection(&el, *start, *end, direction.as_deref());
+                }
+            }
+            None => {
+                if let Some(on_focus_lost) = use_context::<OnFocusLost>(cx) {
+                    (on_focus_lost.0)(self.element);
+                }
+            }
+        }
+    }
+}
+
+fn nodes(child: &Child) -> &[web_sys::Node] {
+    match child {
+        Child::Node(node) => std::slice::from_ref(node),
+        Child::Nodes(nodes) => nodes,
+        _ => &[],
+    }
+}
+
+// every element in (or among) the nodes that matches the selector, in document order
+fn matching(nodes: &[web_sys::Node], selector: &str) -> Vec<web_sys::Element> {
+    let mut found = Vec::new();
+    for el in nodes
+        .iter()
+        .filter_map(|node| node.dyn_ref::<web_sys::Element>())
+    {
+        if el.matches(selector).unwrap_or(false) {
+            found.push(el.clone());
+        }
+        if let Ok(list) = el.query_selector_all(selector) {
+            found.extend(
+                (0..list.length())
+                    .filter_map(|idx| list.item(idx))
+                    .filter_map(|node| node.dyn_into::<web_sys::Element>().ok()),
+            );
+        }
+    }
+    found
+}
+
+fn selection(el: &web_sys::Element) -> Option<(u32, u32, Option<String>)> {
+    if let Some(input) = el.dyn_ref::<web_sys::HtmlInputElement>() {
+        // inputs like checkboxes have no selection, and throw when asked for one
+        Some((
+            input.selection_start().ok()??,
+            input.selection_end().ok()??,
+            input.selection_direction().ok().flatten(),
+        ))
+    } else if let Some(textarea) = el.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+        Some((
+            textarea.selection_start().ok()??,
+            textarea.selection_end().ok()??,
+            textarea.selection_direction().ok().flatten(),
+        ))
+    } else {
+        None
+    }
+}
+
+fn set_selection(el: &web_sys::Element, start: u32, end: u32, direction: Option<&str>) {
+    let direction = direction.unwrap_or("none");
+    if let Some(input) = el.dyn_ref::<web_sys::HtmlInputElement>() {
+        _ = input.set_selection_range_with_direction(start, end, direction);
+    } else if let Some(textarea) = el.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+        _ = textarea.set_selection_range_with_direction(start, end, direction);
+    }
+}
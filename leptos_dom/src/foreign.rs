@@ -0,0 +1,230 @@
+use cfg_if::cfg_if;
+use leptos_reactive::Scope;
+
+use crate::{Child, IntoChild};
+
+/// The attribute that marks the container of a [ForeignElement].
+pub const FOREIGN_ELEMENT_ATTRIBUTE: &str = "data-leptos-foreign";
+
+/// How many animation frames [foreign_element] waits for its container to become part of the
+/// page before giving up on mounting the widget (about ten seconds at 60 frames per second).
+pub const MAX_ATTACH_FRAMES: usize = 600;
+
+/// A widget rendered by something other than Leptos, as returned by the `mount` function
+/// passed to [foreign_element].
+pub trait ForeignHandle: 'static {
+    /// Tears the widget down, e.g., by calling the library’s own `destroy` or `unmount`. This is
+    /// called when the scope that created the [ForeignElement] is disposed, so the widget can
+    /// clean up before its container is removed from the page.
+    fn unmount(self);
+}
+
+/// Embeds a DOM tree rendered by something other than Leptos, like a React widget or a D3
+/// chart, in a view.
+///
+/// Leptos only creates an empty `<div>` to hold the widget. Once that container is part of the
+/// page, `mount` is called with it once, to render the widget into it and return a
+/// [ForeignHandle]. `update` is then called with the handle right away, and again whenever any
+/// signal it reads changes, so that it can push new data into the widget. When `cx` is
+/// disposed, the handle is [unmounted](ForeignHandle::unmount).
+///
+/// Leptos never touches the container’s children. On the server, the container is rendered
+/// empty, or with the HTML given to [ForeignElement::ssr_placeholder_html], and hydration
+/// claims it without replacing whatever is in it.
+///
+/// If the container still isn’t part of the page after [MAX_ATTACH_FRAMES] animation frames
+/// (e.g., because the [ForeignElement] was never inserted), `mount` is never called.
+///
+/// ```
+/// # use leptos_dom::*;
+/// # use leptos_reactive::*;
+/// # // the chart library’s bindings
+/// # fn create_chart(_container: &web_sys::HtmlElement) -> js_sys::Object { js_sys::Object::new() }
+/// # fn set_chart_data(_chart: &js_sys::Object, _data: &[f64]) {}
+/// struct Chart(js_sys::Object);
+///
+/// impl ForeignHandle for Chart {
+///   fn unmount(self) {
+///     // e.g., call the chart library’s `destroy()`
+///   }
+/// }
+///
+/// # create_scope(create_runtime(), |cx| {
+/// # if false {
+/// let (data, set_data) = create_signal(cx, vec![1.0, 2.0, 3.0]);
+/// let chart = foreign_element(
+///   cx,
+///   |container| Chart(create_chart(&container)),
+///   move |chart| set_chart_data(&chart.0, &data.get()),
+/// )
+/// .ssr_placeholder_html("<p>Loading chart…</p>");
+/// # }
+/// # }).dispose();
+/// ```
+pub fn foreign_element<H: ForeignHandle>(
+    cx: Scope,
+    mount: impl Fn(web_sys::HtmlElement) -> H + 'static,
+    update: impl Fn(&H) + 'static,
+) -> ForeignElement {
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use leptos_reactive::{create_effect, on_cleanup};
+            use std::{cell::{Cell, RefCell}, rc::Rc};
+
+            let container = claim_container(cx);
+            let handle: Rc<RefCell<Option<H>>> = Default::default();
+            let disposed = Rc::new(Cell::new(false));
+
+            on_cleanup(cx, {
+                let handle = Rc::clone(&handle);
+                let disposed = Rc::clone(&disposed);
+                move || {
+                    disposed.set(true);
+                    if let Some(handle) = handle.borrow_mut().take() {
+                        handle.unmount();
+                    }
+                }
+            });
+
+            when_attached(container.clone(), Rc::clone(&disposed), {
+                let container = container.clone();
+                move || {
+                    *handle.borrow_mut() = Some(mount(container));
+                    create_effect(cx, move |_| {
+                        if let Some(handle) = &*handle.borrow() {
+                            update(handle);
+                        }
+                    });
+                }
+            });
+
+            ForeignElement { container }
+        } else {
+            _ = (mount, update);
+            ForeignElement {
+                key: cx.next_hydration_key(),
+                placeholder: None,
+            }
+        }
+    }
+}
+
+/// The container of a widget rendered by something other than Leptos, created by
+/// [foreign_element].
+pub struct ForeignElement {
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    container: web_sys::HtmlElement,
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    key: String,
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    placeholder: Option<String>,
+}
+
+impl ForeignElement {
+    /// Sets HTML to render inside the container on the server, e.g., a loading message or a
+    /// static version of the widget. It is left in place when hydrating, until `mount` replaces
+    /// it. This does nothing in the browser.
+    pub fn ssr_placeholder_html(self, html: impl Into<String>) -> Self {
+        cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                _ = html;
+                self
+            } else {
+                Self {
+                    placeholder: Some(html.into()),
+                    ..self
+                }
+            }
+        }
+    }
+
+    /// The container the widget is rendered into.
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    pub fn container(&self) -> &web_sys::HtmlElement {
+        &self.container
+    }
+}
+
+impl std::fmt::Debug for ForeignElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ForeignElement").finish()
+    }
+}
+
+impl IntoChild for ForeignElement {
+    fn into_child(self, _cx: Scope) -> Child {
+        cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                Child::Node(self.container.into())
+            } else {
+                Child::Node(format!(
+                    r#"<div data-hk="{}" {FOREIGN_ELEMENT_ATTRIBUTE}>{}</div>"#,
+                    self.key,
+                    self.placeholder.unwrap_or_default()
+                ))
+            }
+        }
+    }
+}
+
+// the server-rendered container while hydrating, and a new one otherwise
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn claim_container(cx: Scope) -> web_sys::HtmlElement {
+    use wasm_bindgen::JsCast;
+
+    cfg_if! {
+        if #[cfg(feature = "hydrate")] {
+            thread_local! {
+                static TEMPLATE: web_sys::HtmlTemplateElement = crate::create_template(
+                    &format!("<div {FOREIGN_ELEMENT_ATTRIBUTE}></div>")
+                );
+            }
+            TEMPLATE
+                .with(|template| cx.get_next_element(template.unchecked_ref()))
+                .unchecked_into()
+        } else {
+            _ = cx;
+            let container = crate::create_element("div");
+            _ = container.set_attribute(FOREIGN_ELEMENT_ATTRIBUTE, "");
+            container.unchecked_into()
+        }
+    }
+}
+
+// runs `f` once `container` is part of the page (after the view it is in has been inserted),
+// unless `disposed` is set first or it isn't attached within `MAX_ATTACH_FRAMES` frames
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn when_attached(
+    container: web_sys::HtmlElement,
+    disposed: std::rc::Rc<std::cell::Cell<bool>>,
+    f: impl FnOnce() + 'static,
+) {
+    leptos_reactive::queue_microtask(move || check_attached(container, disposed, f, 0));
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn check_attached(
+    container: web_sys::HtmlElement,
+    disposed: std::rc::Rc<std::cell::Cell<bool>>,
+    f: impl FnOnce() + 'static,
+    frames: usize,
+) {
+    if disposed.get() {
+        return;
+    }
+    if container.is_connected() {
+        f();
+    } else if frames >= MAX_ATTACH_FRAMES {
+        crate::debug_warn!(
+            "[foreign_element] the container wasn’t added to the page within \
+             {MAX_ATTACH_FRAMES} animation frames, so the widget won’t be mounted"
+        );
+    } else {
+        let mut next = Some((container, disposed, f));
+        crate::request_animation_frame(move || {
+            if let Some((container, disposed, f)) = next.take() {
+                check_attached(container, disposed, f, frames + 1);
+            }
+        });
+    }
+}
@@ -0,0 +1,147 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope};
+
+/// The reactive state of the [Battery Status API](https://developer.mozilla.org/en-US/docs/Web/API/Battery_Status_API),
+/// as returned by [use_battery].
+///
+/// Where the API isn’t available (on the server, or in browsers that don’t support it), this
+/// keeps the values the specification gives for a fully-charged device plugged into the mains.
+#[derive(Copy, Clone, PartialEq)]
+pub struct BatteryState {
+    /// How much charge is left, from `0.0` to `1.0`.
+    pub level: ReadSignal<f32>,
+    /// Whether the battery is charging.
+    pub charging: ReadSignal<bool>,
+    /// The number of seconds until the battery is fully charged, or `0.0` if it already is.
+    /// This is `f64::INFINITY` while the battery is discharging.
+    pub charging_time: ReadSignal<f64>,
+    /// The number of seconds until the battery is empty. This is `f64::INFINITY` while the
+    /// battery is charging, or if the browser can’t tell.
+    pub discharging_time: ReadSignal<f64>,
+}
+
+/// Reactively tracks the device’s battery, using the
+/// [Battery Status API](https://developer.mozilla.org/en-US/docs/Web/API/Battery_Status_API).
+/// This is useful for doing less work (like polling or animations) when the battery is low.
+///
+/// The browser only hands out the battery manager asynchronously, so the signals keep their
+/// default values for a moment after this is called. Its event listeners are removed when the
+/// [Scope] is disposed.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Animations(cx: Scope) -> Element {
+///   let battery = use_battery(cx);
+///   let low_power = move || !battery.charging.get() && battery.level.get() < 0.2;
+///
+///   view! { cx,
+///     <div class:animated=move || !low_power()>
+///       {move || format!("{:.0}%", battery.level.get() * 100.0)}
+///     </div>
+///   }
+/// }
+/// ```
+pub fn use_battery(cx: Scope) -> BatteryState {
+    let (level, set_level) = create_signal(cx, 1.0);
+    let (charging, set_charging) = create_signal(cx, true);
+    let (charging_time, set_charging_time) = create_signal(cx, 0.0);
+    let (discharging_time, set_discharging_time) = create_signal(cx, f64::INFINITY);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use leptos_reactive::on_cleanup;
+            use std::{cell::{Cell, RefCell}, rc::Rc};
+            use wasm_bindgen::{prelude::Closure, JsCast};
+
+            const EVENTS: [&str; 4] = [
+                "levelchange",
+                "chargingchange",
+                "chargingtimechange",
+                "dischargingtimechange",
+            ];
+
+            let manager: Rc<RefCell<Option<(web_sys::BatteryManager, js_sys::Function)>>> =
+                Default::default();
+            let disposed = Rc::new(Cell::new(false));
+
+            // browsers without the API don’t define `getBattery` at all, so this fails
+            if let Ok(promise) = js::get_battery() {
+                leptos_reactive::spawn_local({
+                    let manager = Rc::clone(&manager);
+                    let disposed = Rc::clone(&disposed);
+                    async move {
+                        let battery = match wasm_bindgen_futures::JsFuture::from(promise).await {
+                            Ok(battery) => battery.unchecked_into::<web_sys::BatteryManager>(),
+                            Err(e) => {
+                                crate::debug_warn!("[use_battery] {e:?}");
+                                return;
+                            }
+                        };
+                        // the scope may have been disposed while the promise was pending
+                        if disposed.get() {
+                            return;
+                        }
+
+                        let update = {
+                            let battery = battery.clone();
+                            move || {
+                                set_level.set(battery.level() as f32);
+                                set_charging.set(battery.charging());
+                                set_charging_time.set(battery.charging_time());
+                                set_discharging_time.set(battery.discharging_time());
+                            }
+                        };
+                        update();
+
+                        let handler = Closure::wrap(Box::new(move |_: web_sys::Event| update())
+                            as Box<dyn Fn(web_sys::Event)>);
+                        let handler: js_sys::Function = handler.into_js_value().unchecked_into();
+                        for event_name in EVENTS {
+                            _ = battery.add_event_listener_with_callback(event_name, &handler);
+                        }
+                        *manager.borrow_mut() = Some((battery, handler));
+                    }
+                });
+            }
+
+            on_cleanup(cx, move || {
+                disposed.set(true);
+                if let Some((battery, handler)) = manager.take() {
+                    for event_name in EVENTS {
+                        _ = battery.remove_event_listener_with_callback(event_name, &handler);
+                    }
+                }
+            });
+        } else {
+            _ = (set_level, set_charging, set_charging_time, set_discharging_time);
+        }
+    }
+
+    BatteryState {
+        level,
+        charging,
+        charging_time,
+        discharging_time,
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod js {
+    use wasm_bindgen::{prelude::*, JsCast};
+
+    // newer versions of `web_sys` don’t include `getBattery`
+    #[wasm_bindgen]
+    extern "C" {
+        type BatteryNavigator;
+
+        #[wasm_bindgen(catch, method, js_name = getBattery)]
+        fn get_battery(this: &BatteryNavigator) -> Result<js_sys::Promise, JsValue>;
+    }
+
+    pub fn get_battery() -> Result<js_sys::Promise, JsValue> {
+        crate::window()
+            .navigator()
+            .unchecked_into::<BatteryNavigator>()
+            .get_battery()
+    }
+}
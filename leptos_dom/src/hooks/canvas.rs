@@ -0,0 +1,168 @@
+use crate::NodeRef;
+use leptos_reactive::{create_signal, ReadSignal, Scope};
+use std::rc::Rc;
+
+// paints the whole canvas
+type Paint = dyn Fn(&web_sys::CanvasRenderingContext2d);
+
+/// Gets the 2D drawing context of the `<canvas>` element in `canvas`, as a
+/// [`CanvasRenderingContext2d`](https://developer.mozilla.org/en-US/docs/Web/API/CanvasRenderingContext2D).
+///
+/// Returns a signal holding the context, which is `None` until the canvas has been rendered,
+/// and a function that sets how the canvas is painted. The painting function is called with
+/// the context as soon as there is one, and again whenever any signals it reads change, or
+/// the canvas is replaced by another, so it should paint the whole canvas each time. Calling
+/// the function again replaces the previous painting function.
+///
+/// On the server, the signal is always `None` and nothing is painted.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Gauge(cx: Scope, level: ReadSignal<f64>) -> Element {
+///   let canvas = NodeRef::new(cx);
+///   let (_context, draw) = use_canvas_2d(cx, canvas);
+///
+///   draw(Box::new(move |ctx: &web_sys::CanvasRenderingContext2d| {
+///     ctx.clear_rect(0.0, 0.0, 100.0, 20.0);
+///     ctx.fill_rect(0.0, 0.0, level.get() * 100.0, 20.0);
+///   }));
+///
+///   view! { cx, <canvas _ref=canvas width="100" height="20"/> }
+/// }
+/// ```
+pub fn use_canvas_2d(
+    cx: Scope,
+    canvas: NodeRef,
+) -> (
+    ReadSignal<Option<web_sys::CanvasRenderingContext2d>>,
+    impl Fn(Box<Paint>) + Clone,
+) {
+    let (context, set_context) = create_signal(cx, None::<web_sys::CanvasRenderingContext2d>);
+    let (paint, set_paint) = create_signal(cx, None::<Rc<Paint>>);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use leptos_reactive::create_effect;
+
+            create_effect(cx, move |_| set_context.set(canvas.get().and_then(js::context_2d)));
+
+            // reads any signals the painting function reads, so it repaints when they change
+            create_effect(cx, move |_| {
+                if let (Some(context), Some(paint)) = (context.get(), paint.get()) {
+                    paint(&context);
+                }
+            });
+        } else {
+            _ = (canvas, set_context, paint);
+        }
+    }
+
+    let draw = move |f: Box<Paint>| {
+        set_paint.set(Some(Rc::from(f)));
+    };
+
+    (context, draw)
+}
+
+/// Animates the `<canvas>` element in `canvas`, by calling `draw` with its 2D drawing
+/// context (see [use_canvas_2d]) once per animation frame, using
+/// [`Window.requestAnimationFrame`](https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame).
+///
+/// `draw` is also given the time the frame started, in milliseconds, as measured by
+/// [`performance.now()`](https://developer.mozilla.org/en-US/docs/Web/API/Performance/now), so that
+/// the animation can run at the same speed however often frames are painted. Frames are skipped
+/// while the canvas isn’t rendered, and the animation stops when the [Scope] is disposed.
+///
+/// On the server, nothing is drawn.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Spinner(cx: Scope) -> Element {
+///   let canvas = NodeRef::new(cx);
+///
+///   use_canvas_animation(cx, canvas, |ctx, time| {
+///     ctx.clear_rect(0.0, 0.0, 40.0, 40.0);
+///     ctx.begin_path();
+///     let start = time / 250.0;
+///     _ = ctx.arc(20.0, 20.0, 16.0, start, start + 4.0);
+///     ctx.stroke();
+///   });
+///
+///   view! { cx, <canvas _ref=canvas width="40" height="40"/> }
+/// }
+/// ```
+pub fn use_canvas_animation(
+    cx: Scope,
+    canvas: NodeRef,
+    draw: impl Fn(&web_sys::CanvasRenderingContext2d, f64) + 'static,
+) {
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use leptos_reactive::{on_cleanup, UntrackedGettableSignal};
+            use std::cell::Cell;
+            use wasm_bindgen::{closure::Closure, JsCast};
+
+            struct Animation<F> {
+                disposed: Cell<bool>,
+                context: ReadSignal<Option<web_sys::CanvasRenderingContext2d>>,
+                draw: F,
+            }
+
+            impl<F> Animation<F>
+            where
+                F: Fn(&web_sys::CanvasRenderingContext2d, f64) + 'static,
+            {
+                fn request_frame(self: &Rc<Self>) {
+                    let animation = Rc::clone(self);
+                    let frame = Closure::once_into_js(move |time: f64| animation.frame(time));
+                    _ = crate::window().request_animation_frame(frame.unchecked_ref());
+                }
+
+                fn frame(self: &Rc<Self>, time: f64) {
+                    // the frame requested before the scope was disposed ends the loop
+                    if self.disposed.get() {
+                        return;
+                    }
+                    if let Some(context) = self.context.get_untracked() {
+                        (self.draw)(&context, time);
+                    }
+                    self.request_frame();
+                }
+            }
+
+            let (context, _) = use_canvas_2d(cx, canvas);
+            let animation = Rc::new(Animation {
+                disposed: Cell::new(false),
+                context,
+                draw,
+            });
+            animation.request_frame();
+
+            on_cleanup(cx, move || animation.disposed.set(true));
+        } else {
+            _ = (cx, canvas, draw);
+        }
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod js {
+    use wasm_bindgen::JsCast;
+
+    // `None` if the element isn't a `<canvas>`, or already has a different kind of context
+    pub fn context_2d(el: web_sys::Element) -> Option<web_sys::CanvasRenderingContext2d> {
+        match el.dyn_into::<web_sys::HtmlCanvasElement>() {
+            Ok(canvas) => canvas
+                .get_context("2d")
+                .ok()
+                .flatten()
+                .map(JsCast::unchecked_into),
+            Err(_) => {
+                crate::debug_warn!("[use_canvas_2d] the NodeRef isn’t a <canvas> element");
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope, WriteSignal};
+
+/// Whether the user has allowed the page to use the clipboard, as tracked by [use_clipboard_item].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermissionState {
+    /// The browser will ask the user the next time the clipboard is used, or doesn’t say
+    /// whether it will.
+    #[default]
+    Prompt,
+    /// The user has allowed the page to use the clipboard.
+    Granted,
+    /// The user has refused, so using the clipboard will fail.
+    Denied,
+}
+
+/// The reactive state of the [Clipboard API](https://developer.mozilla.org/en-US/docs/Web/API/Clipboard_API)
+/// for images and other binary data, as returned by [use_clipboard_item].
+#[derive(Clone)]
+pub struct ClipboardItemState {
+    /// The image most recently read with [read_image](Self::read_image), if the clipboard
+    /// held one.
+    pub image: ReadSignal<Option<web_sys::Blob>>,
+    /// Whether the user has allowed the page to read the clipboard.
+    pub read_permission: ReadSignal<PermissionState>,
+    /// Whether the user has allowed the page to write to the clipboard.
+    pub write_permission: ReadSignal<PermissionState>,
+    set_image: WriteSignal<Option<web_sys::Blob>>,
+}
+
+impl ClipboardItemState {
+    /// Reads the first image on the clipboard into [image](Self::image), which is set to `None`
+    /// if the clipboard doesn’t hold one. Browsers may ask the user for permission first, and
+    /// only allow this in response to a user gesture, like a click.
+    pub fn read_image(&self) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                let set_image = self.set_image;
+                leptos_reactive::spawn_local(async move {
+                    match js::read_image().await {
+                        Ok(image) => set_image.set(image),
+                        Err(e) => crate::debug_warn!("[use_clipboard_item] {e:?}"),
+                    }
+                });
+            } else {
+                _ = self.set_image;
+            }
+        }
+    }
+
+    /// Writes an image to the clipboard, under its [type](web_sys::Blob::type_). Most browsers
+    /// only accept `image/png` images, and only in response to a user gesture, like a click.
+    pub fn write_image(&self, blob: &web_sys::Blob) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                let blob = blob.clone();
+                leptos_reactive::spawn_local(async move {
+                    if let Err(e) = js::write_blob(&blob).await {
+                        crate::debug_warn!("[use_clipboard_item] {e:?}");
+                    }
+                });
+            } else {
+                _ = blob;
+            }
+        }
+    }
+}
+
+/// Reads and writes images on the clipboard, using [`ClipboardItem`](https://developer.mozilla.org/en-US/docs/Web/API/ClipboardItem)s,
+/// and reactively tracks whether the user has allowed the page to do so, using the
+/// [Permissions API](https://developer.mozilla.org/en-US/docs/Web/API/Permissions_API).
+///
+/// Not every browser reports clipboard permissions: where they aren’t reported, they are
+/// always [PermissionState::Prompt]. On the server, the permissions are always
+/// [PermissionState::Prompt] and the clipboard is never used.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Chart(cx: Scope, png: ReadSignal<Option<web_sys::Blob>>) -> Element {
+///   let clipboard = use_clipboard_item(cx);
+///   let denied = move || clipboard.write_permission.get() == PermissionState::Denied;
+///
+///   view! { cx,
+///     <button
+///       disabled=denied
+///       on:click={
+///         let clipboard = clipboard.clone();
+///         move |_| if let Some(png) = png.get() {
+///           clipboard.write_image(&png);
+///         }
+///       }
+///     >
+///       "Copy chart"
+///     </button>
+///   }
+/// }
+/// ```
+pub fn use_clipboard_item(cx: Scope) -> ClipboardItemState {
+    let (image, set_image) = create_signal(cx, None);
+    let (read_permission, set_read_permission) = create_signal(cx, PermissionState::Prompt);
+    let (write_permission, set_write_permission) = create_signal(cx, PermissionState::Prompt);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use std::{cell::{Cell, RefCell}, rc::Rc};
+
+            let disposed = Rc::new(Cell::new(false));
+            let statuses = Rc::new(RefCell::new(Vec::<web_sys::PermissionStatus>::new()));
+            for (name, set_permission) in [
+                ("clipboard-read", set_read_permission),
+                ("clipboard-write", set_write_permission),
+            ] {
+                let disposed = Rc::clone(&disposed);
+                let statuses = Rc::clone(&statuses);
+                leptos_reactive::spawn_local(async move {
+                    let Some(status) = js::query_permission(name).await else {
+                        return;
+                    };
+                    if disposed.get() {
+                        return;
+                    }
+                    set_permission.set(permission_state(&status));
+
+                    let on_change = wasm_bindgen::closure::Closure::wrap(Box::new({
+                        let status = status.clone();
+                        move |_: web_sys::Event| set_permission.set(permission_state(&status))
+                    }) as Box<dyn Fn(web_sys::Event)>);
+                    status.set_onchange(Some(wasm_bindgen::JsCast::unchecked_ref(
+                        &on_change.into_js_value(),
+                    )));
+                    statuses.borrow_mut().push(status);
+                });
+            }
+
+            leptos_reactive::on_cleanup(cx, move || {
+                disposed.set(true);
+                for status in statuses.take() {
+                    status.set_onchange(None);
+                }
+            });
+        } else {
+            _ = (set_read_permission, set_write_permission);
+        }
+    }
+
+    ClipboardItemState {
+        image,
+        read_permission,
+        write_permission,
+        set_image,
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn permission_state(status: &web_sys::PermissionStatus) -> PermissionState {
+    match status.state() {
+        web_sys::PermissionState::Granted => PermissionState::Granted,
+        web_sys::PermissionState::Denied => PermissionState::Denied,
+        _ => PermissionState::Prompt,
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod js {
+    use wasm_bindgen::{prelude::*, JsCast};
+    use wasm_bindgen_futures::JsFuture;
+
+    // the asynchronous Clipboard API is only included in `web_sys` behind `web_sys_unstable_apis`
+    #[wasm_bindgen]
+    extern "C" {
+        type Clipboard;
+
+        #[wasm_bindgen(catch, method)]
+        fn read(this: &Clipboard) -> Result<js_sys::Promise, JsValue>;
+
+        #[wasm_bindgen(catch, method)]
+        fn write(this: &Clipboard, items: &js_sys::Array) -> Result<js_sys::Promise, JsValue>;
+
+        type ClipboardItem;
+
+        #[wasm_bindgen(catch, constructor)]
+        fn new(items: &js_sys::Object) -> Result<ClipboardItem, JsValue>;
+
+        #[wasm_bindgen(method, getter)]
+        fn types(this: &ClipboardItem) -> js_sys::Array;
+
+        #[wasm_bindgen(catch, method, js_name = getType)]
+        fn get_type(this: &ClipboardItem, type_: &str) -> Result<js_sys::Promise, JsValue>;
+    }
+
+    fn clipboard() -> Result<Clipboard, JsValue> {
+        let clipboard = js_sys::Reflect::get(
+            &crate::window().navigator(),
+            &JsValue::from_str("clipboard"),
+        )?;
+        if clipboard.is_undefined() {
+            return Err(JsValue::from_str("the Clipboard API isn’t available"));
+        }
+        Ok(clipboard.unchecked_into())
+    }
+
+    pub async fn read_image() -> Result<Option<web_sys::Blob>, JsValue> {
+        let items = JsFuture::from(clipboard()?.read()?).await?;
+        for item in js_sys::Array::from(&items).iter() {
+            let item = item.unchecked_into::<ClipboardItem>();
+            let image = item
+                .types()
+                .iter()
+                .filter_map(|type_| type_.as_string())
+                .find(|type_| type_.starts_with("image/"));
+            if let Some(type_) = image {
+                let blob = JsFuture::from(item.get_type(&type_)?).await?;
+                return Ok(Some(blob.unchecked_into()));
+            }
+        }
+        Ok(None)
+    }
+
+    pub async fn write_blob(blob: &web_sys::Blob) -> Result<(), JsValue> {
+        let data = js_sys::Object::new();
+        js_sys::Reflect::set(&data, &JsValue::from_str(&blob.type_()), blob)?;
+        let item = ClipboardItem::new(&data)?;
+        let items = js_sys::Array::of1(&item);
+        JsFuture::from(clipboard()?.write(&items)?).await?;
+        Ok(())
+    }
+
+    /// Resolves to `None` if the browser doesn’t report the permission.
+    pub async fn query_permission(name: &str) -> Option<web_sys::PermissionStatus> {
+        let descriptor = js_sys::Object::new();
+        js_sys::Reflect::set(
+            &descriptor,
+            &JsValue::from_str("name"),
+            &JsValue::from_str(name),
+        )
+        .ok()?;
+        let promise = crate::window()
+            .navigator()
+            .permissions()
+            .ok()?
+            .query(&descriptor)
+            .ok()?;
+        JsFuture::from(promise)
+            .await
+            .ok()
+            .map(JsCast::unchecked_into)
+    }
+}
@@ -0,0 +1,147 @@
+use leptos_reactive::{create_rw_signal, create_signal, ReadSignal, RwSignal, Scope};
+
+/// The key under which [use_color_mode] stores the chosen [ColorMode] in `localStorage`.
+pub const COLOR_MODE_STORAGE_KEY: &str = "leptos-color-mode";
+
+/// A choice of light or dark colors, as used by [use_color_mode].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorMode {
+    /// Follows the user’s system setting (the
+    /// [`prefers-color-scheme`](https://developer.mozilla.org/en-US/docs/Web/CSS/@media/prefers-color-scheme)
+    /// media query).
+    #[default]
+    Auto,
+    /// Light colors.
+    Light,
+    /// Dark colors.
+    Dark,
+}
+
+impl ColorMode {
+    /// The name of the mode, which is also how it is stored, and (for [ColorMode::Light] and
+    /// [ColorMode::Dark]) the class added to the `<html>` element.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColorMode::Auto => "auto",
+            ColorMode::Light => "light",
+            ColorMode::Dark => "dark",
+        }
+    }
+
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn parse(name: &str) -> Option<Self> {
+        [ColorMode::Auto, ColorMode::Light, ColorMode::Dark]
+            .into_iter()
+            .find(|mode| mode.as_str() == name)
+    }
+}
+
+/// The reactive state of the color mode, as returned by [use_color_mode].
+#[derive(Copy, Clone, PartialEq)]
+pub struct ColorModeState {
+    /// The mode the user has chosen. Set it to override the system setting, or to
+    /// [ColorMode::Auto] to follow it again.
+    pub mode: RwSignal<ColorMode>,
+    /// The mode actually in use: the same as `mode`, unless that is [ColorMode::Auto], in which
+    /// case it follows the system setting. Never [ColorMode::Auto].
+    pub effective_mode: ReadSignal<ColorMode>,
+}
+
+/// Tracks whether the page should use light or dark colors, letting the user override the
+/// system setting.
+///
+/// The chosen `mode` is stored in `localStorage` (under [COLOR_MODE_STORAGE_KEY]), so it is
+/// remembered across visits, and starts out as [ColorMode::Auto] if nothing is stored. While
+/// it is [ColorMode::Auto], `effective_mode` follows the
+/// [`prefers-color-scheme`](https://developer.mozilla.org/en-US/docs/Web/CSS/@media/prefers-color-scheme)
+/// media query, including when the system setting changes.
+///
+/// The `<html>` element always has a `light` or `dark` class matching `effective_mode`, so
+/// stylesheets can use `.dark` selectors.
+///
+/// On the server, nothing is read or stored, and the effective mode is always
+/// [ColorMode::Light].
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn ThemeSwitcher(cx: Scope) -> Element {
+///   let color_mode = use_color_mode(cx);
+///   let next = move |_| {
+///     color_mode.mode.update(|mode| {
+///       *mode = match mode {
+///         ColorMode::Auto => ColorMode::Light,
+///         ColorMode::Light => ColorMode::Dark,
+///         ColorMode::Dark => ColorMode::Auto,
+///       }
+///     })
+///   };
+///
+///   view! { cx,
+///     <button on:click=next>
+///       {move || format!("{} ({})", color_mode.mode.get().as_str(), color_mode.effective_mode.get().as_str())}
+///     </button>
+///   }
+/// }
+/// ```
+pub fn use_color_mode(cx: Scope) -> ColorModeState {
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use crate::{document, use_event_listener, window, ListenerTarget};
+            use leptos_reactive::create_effect;
+
+            let storage = window().local_storage().ok().flatten();
+            let stored = storage
+                .as_ref()
+                .and_then(|storage| storage.get_item(COLOR_MODE_STORAGE_KEY).ok().flatten())
+                .and_then(|name| ColorMode::parse(&name))
+                .unwrap_or_default();
+            let query = window().match_media("(prefers-color-scheme: dark)").ok().flatten();
+            let (prefers_dark, set_prefers_dark) =
+                create_signal(cx, query.as_ref().map(|query| query.matches()).unwrap_or(false));
+            if let Some(query) = query {
+                use_event_listener(
+                    cx,
+                    ListenerTarget::Target(query.clone().into()),
+                    "change",
+                    move |_| set_prefers_dark.set(query.matches()),
+                );
+            }
+
+            let resolve = move |mode: ColorMode| match mode {
+                ColorMode::Auto if prefers_dark.get() => ColorMode::Dark,
+                ColorMode::Auto => ColorMode::Light,
+                mode => mode,
+            };
+            let mode = create_rw_signal(cx, stored);
+            let (effective_mode, set_effective_mode) = create_signal(cx, resolve(stored));
+
+            create_effect(cx, move |_| {
+                let mode = mode.get();
+                let effective = resolve(mode);
+                set_effective_mode.set(effective);
+
+                if let Some(root) = document().document_element() {
+                    let classes = root.class_list();
+                    _ = classes.toggle_with_force("light", effective == ColorMode::Light);
+                    _ = classes.toggle_with_force("dark", effective == ColorMode::Dark);
+                }
+                if let Some(storage) = &storage {
+                    _ = if mode == ColorMode::Auto {
+                        storage.remove_item(COLOR_MODE_STORAGE_KEY)
+                    } else {
+                        storage.set_item(COLOR_MODE_STORAGE_KEY, mode.as_str())
+                    };
+                }
+            });
+        } else {
+            let mode = create_rw_signal(cx, ColorMode::Auto);
+            let (effective_mode, _) = create_signal(cx, ColorMode::Light);
+        }
+    }
+
+    ColorModeState {
+        mode,
+        effective_mode,
+    }
+}
@@ -0,0 +1,105 @@
+use crate::NodeRef;
+use leptos_reactive::Scope;
+
+/// The target of an event listener created with [use_event_listener].
+#[derive(Clone, PartialEq)]
+pub enum ListenerTarget {
+    /// The [`Window`](https://developer.mozilla.org/en-US/docs/Web/API/Window).
+    Window,
+    /// The [`Document`](https://developer.mozilla.org/en-US/docs/Web/API/Document).
+    Document,
+    /// Any other [`EventTarget`](https://developer.mozilla.org/en-US/docs/Web/API/EventTarget).
+    Target(web_sys::EventTarget),
+    /// The element in a [NodeRef]. The listener is moved to the new element whenever it changes.
+    NodeRef(NodeRef),
+}
+
+impl From<NodeRef> for ListenerTarget {
+    fn from(node_ref: NodeRef) -> Self {
+        Self::NodeRef(node_ref)
+    }
+}
+
+impl From<web_sys::Element> for ListenerTarget {
+    fn from(el: web_sys::Element) -> Self {
+        Self::Target(el.into())
+    }
+}
+
+impl From<web_sys::EventTarget> for ListenerTarget {
+    fn from(target: web_sys::EventTarget) -> Self {
+        Self::Target(target)
+    }
+}
+
+/// Adds an event listener to the given target, which is removed when the [Scope] is disposed.
+///
+/// Unlike the `on:` syntax in the `view` macro, this isn’t limited to elements in the view:
+/// it can be used to listen to events on the `Window`, the `Document`, or a [NodeRef].
+///
+/// ```
+/// # use leptos_reactive::*; use leptos_dom::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (width, set_width) = create_signal(cx, 0.0);
+/// use_event_listener(cx, ListenerTarget::Window, "resize", move |_| {
+///   set_width(window().inner_width().ok().and_then(|w| w.as_f64()).unwrap_or_default());
+/// });
+/// # });
+/// ```
+pub fn use_event_listener(
+    cx: Scope,
+    target: impl Into<ListenerTarget>,
+    event_name: &str,
+    handler: impl Fn(web_sys::Event) + 'static,
+) {
+    let target = target.into();
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use leptos_reactive::{create_effect, on_cleanup};
+            use std::{cell::RefCell, rc::Rc};
+            use wasm_bindgen::{prelude::Closure, JsCast};
+
+            let event_name = event_name.to_string();
+            let handler = Closure::wrap(Box::new(handler) as Box<dyn Fn(web_sys::Event)>);
+            let handler: Rc<js_sys::Function> = Rc::new(handler.into_js_value().unchecked_into());
+
+            let current: Rc<RefCell<Option<web_sys::EventTarget>>> = Default::default();
+            let remove = {
+                let event_name = event_name.clone();
+                let handler = Rc::clone(&handler);
+                let current = Rc::clone(&current);
+                move || {
+                    if let Some(target) = current.borrow_mut().take() {
+                        _ = target.remove_event_listener_with_callback(&event_name, &handler);
+                    }
+                }
+            };
+            let add = {
+                let remove = remove.clone();
+                move |target: web_sys::EventTarget| {
+                    remove();
+                    _ = target.add_event_listener_with_callback(&event_name, &handler);
+                    *current.borrow_mut() = Some(target);
+                }
+            };
+
+            match target {
+                ListenerTarget::Window => add(crate::window().into()),
+                ListenerTarget::Document => add(crate::document().into()),
+                ListenerTarget::Target(target) => add(target),
+                ListenerTarget::NodeRef(node_ref) => {
+                    let remove = remove.clone();
+                    create_effect(cx, move |_| match node_ref.get() {
+                        Some(el) => add(el.into()),
+                        None => remove(),
+                    });
+                }
+            }
+
+            on_cleanup(cx, remove);
+        } else {
+            _ = (cx, target, event_name, handler);
+        }
+    }
+}
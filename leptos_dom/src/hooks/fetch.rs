@@ -0,0 +1,170 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope};
+use serde::de::DeserializeOwned;
+use std::rc::Rc;
+
+/// The reactive state of a request made by [use_fetch].
+pub struct FetchState<T>
+where
+    T: 'static,
+{
+    /// The data returned by the last successful request, parsed as JSON. This is kept while
+    /// the next request is loading, and if it fails.
+    pub data: ReadSignal<Option<T>>,
+    /// Whether a request is in flight.
+    pub loading: ReadSignal<bool>,
+    /// Why the last request failed, if it did: a network error, an HTTP error status, or
+    /// a response that couldn’t be parsed.
+    pub error: ReadSignal<Option<String>>,
+    refetch: Rc<dyn Fn()>,
+}
+
+impl<T: 'static> Clone for FetchState<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data,
+            loading: self.loading,
+            error: self.error,
+            refetch: Rc::clone(&self.refetch),
+        }
+    }
+}
+
+impl<T: 'static> FetchState<T> {
+    /// Requests the current URL again, aborting the request in flight, if any. On the server,
+    /// this does nothing.
+    pub fn refetch(&self) {
+        (self.refetch)()
+    }
+}
+
+/// Fetches JSON from a URL with the [Fetch API](https://developer.mozilla.org/en-US/docs/Web/API/Fetch_API),
+/// and fetches it again whenever the URL returned by `url` changes.
+///
+/// This is a lighter alternative to [create_resource](leptos_reactive::create_resource) for
+/// simple requests that only need to be made in the browser: the data isn’t loaded on the
+/// server, and doesn’t cause a `<Suspense/>` to wait. Instead, the returned [FetchState]
+/// tracks whether the request is loading, and whether it failed.
+///
+/// Only the latest request counts: the request in flight is aborted (with an
+/// [AbortController](https://developer.mozilla.org/en-US/docs/Web/API/AbortController))
+/// when the URL changes, when [FetchState::refetch] is called, and when the [Scope] is disposed.
+///
+/// ```
+/// # use leptos::*;
+/// #[derive(serde::Deserialize)]
+/// struct User {
+///   name: String,
+/// }
+///
+/// #[component]
+/// fn Profile(cx: Scope, id: ReadSignal<u32>) -> Element {
+///   let user = use_fetch::<User>(cx, move || format!("/api/users/{}", id.get()));
+///
+///   view! { cx,
+///     <div class:loading=move || user.loading.get()>
+///       {move || user.error.get().unwrap_or_default()}
+///       {move || user.data.with(|user| user.as_ref().map(|user| user.name.clone()))}
+///     </div>
+///   }
+/// }
+/// ```
+pub fn use_fetch<T>(cx: Scope, url: impl Fn() -> String + 'static) -> FetchState<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    let (data, set_data) = create_signal(cx, None);
+    let (loading, set_loading) = create_signal(cx, false);
+    let (error, set_error) = create_signal(cx, None);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use std::cell::RefCell;
+
+            let controller: Rc<RefCell<Option<web_sys::AbortController>>> = Default::default();
+            let abort = {
+                let controller = Rc::clone(&controller);
+                move || {
+                    if let Some(controller) = controller.take() {
+                        controller.abort();
+                    }
+                }
+            };
+
+            let fetch = Rc::new({
+                let abort = abort.clone();
+                move |url: String| {
+                    abort();
+                    let next = match web_sys::AbortController::new() {
+                        Ok(controller) => controller,
+                        Err(e) => {
+                            crate::debug_warn!("[use_fetch] {e:?}");
+                            return;
+                        }
+                    };
+                    let signal = next.signal();
+                    *controller.borrow_mut() = Some(next);
+
+                    set_loading.set(true);
+                    leptos_reactive::spawn_local(async move {
+                        let result = fetch_json::<T>(&url, &signal).await;
+                        // a newer request has replaced this one, or the scope was disposed
+                        if signal.aborted() {
+                            return;
+                        }
+                        match result {
+                            Ok(value) => {
+                                set_data.set(Some(value));
+                                set_error.set(None);
+                            }
+                            Err(e) => set_error.set(Some(e)),
+                        }
+                        set_loading.set(false);
+                    });
+                }
+            });
+
+            let url = Rc::new(url);
+            leptos_reactive::create_effect(cx, {
+                let fetch = Rc::clone(&fetch);
+                let url = Rc::clone(&url);
+                move |_| fetch(url())
+            });
+            leptos_reactive::on_cleanup(cx, abort);
+
+            let refetch: Rc<dyn Fn()> = Rc::new(move || fetch(cx.untrack(|| url())));
+        } else {
+            _ = (url, set_data, set_loading, set_error);
+            let refetch: Rc<dyn Fn()> = Rc::new(|| {});
+        }
+    }
+
+    FetchState {
+        data,
+        loading,
+        error,
+        refetch,
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+async fn fetch_json<T>(url: &str, signal: &web_sys::AbortSignal) -> Result<T, String>
+where
+    T: DeserializeOwned,
+{
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    let init = web_sys::RequestInit::new();
+    init.set_signal(Some(signal));
+    let response = JsFuture::from(crate::window().fetch_with_str_and_init(url, &init))
+        .await
+        .map_err(|e| format!("{e:?}"))?
+        .unchecked_into::<web_sys::Response>();
+    if !response.ok() {
+        return Err(format!("{} {}", response.status(), response.status_text()));
+    }
+
+    let text = response.text().map_err(|e| format!("{e:?}"))?;
+    let text = JsFuture::from(text).await.map_err(|e| format!("{e:?}"))?;
+    serde_json::from_str(&text.as_string().unwrap_or_default()).map_err(|e| e.to_string())
+}
@@ -0,0 +1,125 @@
+use crate::NodeRef;
+use leptos_reactive::{Scope, Signal};
+
+/// Traps keyboard focus inside the element in `container` while `active` is `true`, as
+/// required for accessible modal dialogs.
+///
+/// While the trap is active, pressing `Tab` on the last focusable element inside the
+/// container moves focus back to the first one (and `Shift+Tab` on the first one moves
+/// focus to the last), rather than letting focus escape to the rest of the page. When the
+/// trap is activated, focus is moved into the container; when it is deactivated, focus
+/// returns to the element that was focused before.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Modal(cx: Scope, open: ReadSignal<bool>) -> Element {
+///   let dialog = NodeRef::new(cx);
+///   use_focus_trap(cx, dialog, open);
+///
+///   view! { cx,
+///     <div _ref=dialog role="dialog" aria-modal="true" hidden=move || !open()>
+///       <input type="text"/>
+///       <button>"OK"</button>
+///     </div>
+///   }
+/// }
+/// ```
+pub fn use_focus_trap(cx: Scope, container: NodeRef, active: impl Into<Signal<bool>>) {
+    let active = active.into();
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use crate::{document, use_event_listener};
+            use leptos_reactive::{create_effect, UntrackedGettableSignal};
+            use wasm_bindgen::JsCast;
+
+            /// A CSS selector matching elements that can receive keyboard focus.
+            const FOCUSABLE: &str = "a[href], area[href], button:not([disabled]), \
+                input:not([disabled]):not([type=\"hidden\"]), select:not([disabled]), \
+                textarea:not([disabled]), iframe, [contenteditable=\"true\"], \
+                [tabindex]:not([tabindex=\"-1\"])";
+
+            fn focusable(container: &web_sys::Element) -> Vec<web_sys::HtmlElement> {
+                let mut focusable = Vec::new();
+                if let Ok(nodes) = container.query_selector_all(FOCUSABLE) {
+                    for i in 0..nodes.length() {
+                        if let Some(el) = nodes.item(i).and_then(|n| n.dyn_into().ok()) {
+                            focusable.push(el);
+                        }
+                    }
+                }
+                focusable
+            }
+
+            fn focus_is_within(container: &web_sys::Element) -> bool {
+                document()
+                    .active_element()
+                    .map(|el| container.contains(Some(&el)))
+                    .unwrap_or(false)
+            }
+
+            // move focus into the container when activated, and back out when deactivated
+            create_effect(cx, {
+                let active = active.clone();
+                move |prev_focus: Option<Option<web_sys::HtmlElement>>| {
+                    let el = container.get()?;
+                    if active.get() {
+                        let prev_focus = document()
+                            .active_element()
+                            .and_then(|el| el.dyn_into::<web_sys::HtmlElement>().ok());
+                        if !focus_is_within(&el) {
+                            if let Some(first) = focusable(&el).first() {
+                                _ = first.focus();
+                            }
+                        }
+                        prev_focus
+                    } else {
+                        if let Some(prev_focus) = prev_focus.flatten() {
+                            _ = prev_focus.focus();
+                        }
+                        None
+                    }
+                }
+            });
+
+            use_event_listener(cx, container, "keydown", move |ev| {
+                let ev = ev.unchecked_into::<web_sys::KeyboardEvent>();
+                if ev.key() != "Tab" || !active.get_untracked() {
+                    return;
+                }
+                let el = match container.get() {
+                    Some(el) => el,
+                    None => return,
+                };
+
+                let focusable = focusable(&el);
+                let (first, last) = match (focusable.first(), focusable.last()) {
+                    (Some(first), Some(last)) => (first, last),
+                    // nothing to focus: just keep focus where it is
+                    _ => {
+                        ev.prevent_default();
+                        return;
+                    }
+                };
+
+                let current = document().active_element();
+                let is = |target: &web_sys::HtmlElement| {
+                    current
+                        .as_ref()
+                        .map(|el| el == target.unchecked_ref::<web_sys::Element>())
+                        .unwrap_or(false)
+                };
+                if ev.shift_key() && (is(first) || !focus_is_within(&el)) {
+                    ev.prevent_default();
+                    _ = last.focus();
+                } else if !ev.shift_key() && (is(last) || !focus_is_within(&el)) {
+                    ev.prevent_default();
+                    _ = first.focus();
+                }
+            });
+        } else {
+            _ = (cx, container, active);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::NodeRef;
+use leptos_reactive::{create_signal, ReadSignal, Scope};
+
+/// Reactively tracks whether the element in `target` is displayed fullscreen, using the
+/// [Fullscreen API](https://developer.mozilla.org/en-US/docs/Web/API/Fullscreen_API).
+///
+/// Returns a signal that is `true` while the element is fullscreen, and a function that
+/// toggles fullscreen for the element. (Browsers only allow entering fullscreen in response
+/// to a user gesture, like a click.) If the element is still fullscreen when the [Scope]
+/// is disposed, fullscreen is exited.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Video(cx: Scope) -> Element {
+///   let player = NodeRef::new(cx);
+///   let (is_fullscreen, toggle_fullscreen) = use_fullscreen(cx, player);
+///
+///   view! { cx,
+///     <div _ref=player>
+///       <video src="movie.mp4"/>
+///       <button on:click=move |_| toggle_fullscreen()>
+///         {move || if is_fullscreen() { "Exit fullscreen" } else { "Fullscreen" }}
+///       </button>
+///     </div>
+///   }
+/// }
+/// ```
+pub fn use_fullscreen(cx: Scope, target: NodeRef) -> (ReadSignal<bool>, impl Fn() + Clone) {
+    fullscreen(cx, Some(target))
+}
+
+/// Like [use_fullscreen], but for the whole document: the signal is `true` while any
+/// element is fullscreen, and the function toggles fullscreen for the root `<html>` element.
+pub fn use_document_fullscreen(cx: Scope) -> (ReadSignal<bool>, impl Fn() + Clone) {
+    fullscreen(cx, None)
+}
+
+fn fullscreen(cx: Scope, target: Option<NodeRef>) -> (ReadSignal<bool>, impl Fn() + Clone) {
+    let (is_fullscreen, set_fullscreen) = create_signal(cx, false);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use crate::{document, use_event_listener, ListenerTarget};
+            use leptos_reactive::{on_cleanup, UntrackedGettableSignal};
+
+            let target_el = move || match target {
+                Some(target) => target.get(),
+                None => document().document_element(),
+            };
+            let check = move || match (document().fullscreen_element(), target) {
+                (Some(el), Some(_)) => target_el() == Some(el),
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+
+            set_fullscreen.set(check());
+            use_event_listener(cx, ListenerTarget::Document, "fullscreenchange", move |_| {
+                set_fullscreen.set(check())
+            });
+
+            on_cleanup(cx, move || {
+                if is_fullscreen.get_untracked() {
+                    document().exit_fullscreen();
+                }
+            });
+
+            let toggle = move || {
+                if is_fullscreen.get_untracked() {
+                    document().exit_fullscreen();
+                } else if let Some(el) = target_el() {
+                    if let Err(e) = el.request_fullscreen() {
+                        crate::debug_warn!("[use_fullscreen] {e:?}");
+                    }
+                }
+            };
+        } else {
+            _ = (target, set_fullscreen);
+            let toggle = || {};
+        }
+    }
+
+    (is_fullscreen, toggle)
+}
@@ -0,0 +1,170 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope};
+
+/// The state of a connected gamepad, as returned by [use_gamepad].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GamepadState {
+    /// The index of the gamepad, which stays the same for as long as it is connected.
+    pub index: u32,
+    /// A description of the device, as given by the browser.
+    pub id: String,
+    /// The state of each of the gamepad’s buttons. If the browser recognizes the device, these
+    /// follow the [standard layout](https://w3c.github.io/gamepad/#remapping).
+    pub buttons: Vec<GamepadButtonState>,
+    /// The position of each of the gamepad’s axes, from `-1.0` to `1.0`.
+    pub axes: Vec<f64>,
+}
+
+/// The state of one button on a [GamepadState].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct GamepadButtonState {
+    /// Whether the button is pressed.
+    pub pressed: bool,
+    /// Whether the button is being touched, on devices that can detect touch.
+    pub touched: bool,
+    /// How far the button is pressed, from `0.0` to `1.0`, for analog buttons like triggers.
+    pub value: f64,
+}
+
+/// Reactively tracks the state of all connected gamepads, using the
+/// [Gamepad API](https://developer.mozilla.org/en-US/docs/Web/API/Gamepad_API).
+///
+/// The Gamepad API doesn’t fire events when buttons are pressed or axes are moved, so while any
+/// gamepad is connected, their state is polled once per animation frame, and the signal is only
+/// updated when something has changed. Polling starts when the browser reports a gamepad as
+/// connected (usually once the user has pressed one of its buttons), stops once all gamepads have
+/// been disconnected, and stops for good when the [Scope] is disposed.
+///
+/// On the server, the list is always empty.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Controller(cx: Scope) -> Element {
+///   let gamepads = use_gamepad(cx);
+///   // button 0 is the bottom face button (e.g., A on an Xbox controller)
+///   let jumping = move || {
+///     gamepads.with(|pads| pads.iter().any(|pad| pad.buttons.first().map_or(false, |b| b.pressed)))
+///   };
+///
+///   view! { cx,
+///     <p>
+///       {move || gamepads.with(|pads| pads.len())} " gamepad(s) connected. "
+///       {move || if jumping() { "Jumping!" } else { "" }}
+///     </p>
+///   }
+/// }
+/// ```
+pub fn use_gamepad(cx: Scope) -> ReadSignal<Vec<GamepadState>> {
+    let (gamepads, set_gamepads) = create_signal(cx, Vec::new());
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use crate::{use_event_listener, ListenerTarget};
+            use leptos_reactive::{on_cleanup, UntrackedGettableSignal, WriteSignal};
+            use std::{cell::Cell, rc::Rc};
+
+            struct Poll {
+                polling: Cell<bool>,
+                disposed: Cell<bool>,
+                gamepads: ReadSignal<Vec<GamepadState>>,
+                set_gamepads: WriteSignal<Vec<GamepadState>>,
+            }
+
+            impl Poll {
+                // returns whether any gamepads are still connected
+                fn update(&self) -> bool {
+                    let current = read_gamepads();
+                    let connected = !current.is_empty();
+                    if self.gamepads.with_untracked(|prev| prev != &current) {
+                        self.set_gamepads.set(current);
+                    }
+                    connected
+                }
+
+                fn start(self: &Rc<Self>) {
+                    if !self.polling.get() && !self.disposed.get() {
+                        self.polling.set(true);
+                        self.frame();
+                    }
+                }
+
+                fn frame(self: &Rc<Self>) {
+                    if self.disposed.get() || !self.update() {
+                        self.polling.set(false);
+                        return;
+                    }
+                    let poll = Rc::clone(self);
+                    crate::request_animation_frame(move || poll.frame());
+                }
+            }
+
+            let poll = Rc::new(Poll {
+                polling: Cell::new(false),
+                disposed: Cell::new(false),
+                gamepads,
+                set_gamepads,
+            });
+
+            // gamepads might already be connected, if another part of the page has used them
+            if poll.update() {
+                poll.start();
+            }
+
+            use_event_listener(cx, ListenerTarget::Window, "gamepadconnected", {
+                let poll = Rc::clone(&poll);
+                move |_| poll.start()
+            });
+            // the polling loop stops by itself once none are left, but the list should be
+            // updated right away even if it isn't running
+            use_event_listener(cx, ListenerTarget::Window, "gamepaddisconnected", {
+                let poll = Rc::clone(&poll);
+                move |_| {
+                    poll.update();
+                }
+            });
+
+            on_cleanup(cx, move || poll.disposed.set(true));
+        } else {
+            _ = set_gamepads;
+        }
+    }
+
+    gamepads
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn read_gamepads() -> Vec<GamepadState> {
+    use wasm_bindgen::JsCast;
+
+    // the array has an empty slot for each disconnected gamepad
+    let gamepads = match crate::window().navigator().get_gamepads() {
+        Ok(gamepads) => gamepads,
+        Err(_) => return Vec::new(),
+    };
+    gamepads
+        .iter()
+        .filter_map(|gamepad| gamepad.dyn_into::<web_sys::Gamepad>().ok())
+        .filter(|gamepad| gamepad.connected())
+        .map(|gamepad| GamepadState {
+            index: gamepad.index(),
+            id: gamepad.id(),
+            buttons: gamepad
+                .buttons()
+                .iter()
+                .map(|button| {
+                    let button = button.unchecked_into::<web_sys::GamepadButton>();
+                    GamepadButtonState {
+                        pressed: button.pressed(),
+                        touched: button.touched(),
+                        value: button.value(),
+                    }
+                })
+                .collect(),
+            axes: gamepad
+                .axes()
+                .iter()
+                .map(|axis| axis.as_f64().unwrap_or_default())
+                .collect(),
+        })
+        .collect()
+}
@@ -0,0 +1,159 @@
+use crate::KeyboardShortcut;
+use leptos_reactive::Scope;
+
+/// What a [hotkey](use_hotkeys) handler did with a key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HotkeyResult {
+    /// The key press was handled: its default action is prevented, and no other handler
+    /// for the same keys is called.
+    Handled,
+    /// The key press wasn’t handled, so it falls through to the handler registered before
+    /// this one, if any.
+    Propagate,
+}
+
+/// A key combination and the handler to call when it is pressed, as registered with [use_hotkeys].
+#[derive(Clone)]
+pub struct HotkeyBinding {
+    shortcut: KeyboardShortcut,
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    handler: std::rc::Rc<dyn Fn(&web_sys::KeyboardEvent) -> HotkeyResult>,
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    in_inputs: bool,
+}
+
+impl HotkeyBinding {
+    /// Binds the handler to a key combination like `"mod+k"` (see [KeyboardShortcut] for the format).
+    pub fn new(
+        shortcut: impl Into<KeyboardShortcut>,
+        handler: impl Fn(&web_sys::KeyboardEvent) -> HotkeyResult + 'static,
+    ) -> Self {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                Self {
+                    shortcut: shortcut.into(),
+                    handler: std::rc::Rc::new(handler),
+                    in_inputs: false,
+                }
+            } else {
+                _ = handler;
+                Self {
+                    shortcut: shortcut.into(),
+                }
+            }
+        }
+    }
+
+    /// Also calls the handler while the user is typing in an `<input>`, `<textarea>`, or other
+    /// editable element, where key presses are ignored by default.
+    pub fn in_inputs(self) -> Self {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                Self {
+                    in_inputs: true,
+                    ..self
+                }
+            } else {
+                self
+            }
+        }
+    }
+
+    /// The key combination this is bound to.
+    pub fn shortcut(&self) -> &KeyboardShortcut {
+        &self.shortcut
+    }
+}
+
+impl<S, F> From<(S, F)> for HotkeyBinding
+where
+    S: Into<KeyboardShortcut>,
+    F: Fn(&web_sys::KeyboardEvent) -> HotkeyResult + 'static,
+{
+    fn from((shortcut, handler): (S, F)) -> Self {
+        Self::new(shortcut, handler)
+    }
+}
+
+// marks the scopes in which `use_hotkeys` can be used
+#[derive(Clone, Copy, Debug)]
+struct HotkeyContext;
+
+/// Enables [use_hotkeys] in this [Scope] and its children. It is usually called once, near the
+/// root of the app.
+///
+/// Hotkeys are dispatched by the single `keydown` listener on the `document` that is shared
+/// with [use_keyboard_shortcut](crate::use_keyboard_shortcut), so this doesn’t add a listener
+/// of its own.
+pub fn provide_hotkey_context(cx: Scope) {
+    leptos_reactive::provide_context(cx, HotkeyContext);
+}
+
+/// Registers keyboard shortcuts, which are removed when the [Scope] is disposed. This needs
+/// [provide_hotkey_context] to have been called in this scope or one of its parents.
+///
+/// When more than one binding matches a key press, the one registered most recently is called
+/// first. Since a component’s children are created after it, this means the innermost component
+/// wins: for example, a modal dialog can bind `"escape"` to close itself while it is open,
+/// overriding a binding in the rest of the app, which applies again once the dialog’s scope is
+/// disposed. A handler can return [HotkeyResult::Propagate] to let the next binding handle
+/// the key press instead.
+///
+/// Key presses in an `<input>`, `<textarea>`, or other editable element are ignored, unless
+/// the binding opts in with [HotkeyBinding::in_inputs].
+///
+/// ```
+/// # use leptos_reactive::*; use leptos_dom::*;
+/// # run_scope(create_runtime(), |cx| {
+/// provide_hotkey_context(cx);
+///
+/// let (palette_open, set_palette_open) = create_signal(cx, false);
+/// use_hotkeys(
+///   cx,
+///   [
+///     HotkeyBinding::new("mod+k", move |_| {
+///       set_palette_open(true);
+///       HotkeyResult::Handled
+///     }),
+///     HotkeyBinding::new("escape", move |_| {
+///       if palette_open() {
+///         set_palette_open(false);
+///         HotkeyResult::Handled
+///       } else {
+///         HotkeyResult::Propagate
+///       }
+///     })
+///     .in_inputs(),
+///   ],
+/// );
+/// # });
+/// ```
+pub fn use_hotkeys(cx: Scope, bindings: impl IntoIterator<Item = impl Into<HotkeyBinding>>) {
+    let bindings = bindings.into_iter().map(Into::into).collect::<Vec<_>>();
+
+    if leptos_reactive::use_context::<HotkeyContext>(cx).is_none() {
+        crate::debug_warn!("[use_hotkeys] no hotkey context: call `provide_hotkey_context` first");
+        return;
+    }
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use super::keyboard_shortcut::registry;
+
+            let ids = bindings
+                .into_iter()
+                .map(|binding| {
+                    registry::register(registry::Registered {
+                        shortcut: binding.shortcut,
+                        handler: binding.handler,
+                        in_inputs: binding.in_inputs,
+                        exclusive: true,
+                    })
+                })
+                .collect::<Vec<_>>();
+            leptos_reactive::on_cleanup(cx, move || registry::unregister(&ids));
+        } else {
+            _ = bindings;
+        }
+    }
+}
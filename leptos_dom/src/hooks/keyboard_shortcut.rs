@@ -0,0 +1,278 @@
+use leptos_reactive::Scope;
+
+/// A key combination like `Ctrl+K` or `Shift+Alt+ArrowUp`, as used by [use_keyboard_shortcut].
+///
+/// Modifiers (`Ctrl`, `Shift`, `Alt`, and `Meta`) are separated from the key by `+`. `Mod` is
+/// `Meta` (i.e., `Command`) on Apple devices, and `Ctrl` everywhere else (including the server).
+/// The key is compared against [`KeyboardEvent.key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key),
+/// ignoring case, so `Ctrl+K` and `ctrl+k` are the same shortcut.
+///
+/// ```
+/// # use leptos_dom::*;
+/// let shortcut = KeyboardShortcut::parse("Ctrl+Shift+P");
+/// assert_eq!(shortcut.key, "p");
+/// assert!(shortcut.ctrl && shortcut.shift && !shortcut.alt && !shortcut.meta);
+///
+/// assert_eq!(KeyboardShortcut::parse("Cmd+Esc"), KeyboardShortcut::parse("meta+escape"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyboardShortcut {
+    /// The (lowercased) key, e.g., `"k"`, `"escape"`, or `"/"`.
+    pub key: String,
+    /// Whether the `Control` key must be held.
+    pub ctrl: bool,
+    /// Whether the `Shift` key must be held.
+    pub shift: bool,
+    /// Whether the `Alt` (or `Option`) key must be held.
+    pub alt: bool,
+    /// Whether the `Meta` (or `Command`/`Windows`) key must be held.
+    pub meta: bool,
+}
+
+impl KeyboardShortcut {
+    /// Parses a shortcut like `"Ctrl+K"`. Unknown modifiers are ignored.
+    pub fn parse(shortcut: &str) -> Self {
+        let shortcut = shortcut.trim();
+        let (modifiers, key) = if shortcut == "+" {
+            ("", "+")
+        } else if let Some(modifiers) = shortcut.strip_suffix("++") {
+            (modifiers, "+")
+        } else {
+            shortcut.rsplit_once('+').unwrap_or(("", shortcut))
+        };
+
+        let mut parsed = Self {
+            key: normalize_key(key),
+            ctrl: false,
+            shift: false,
+            alt: false,
+            meta: false,
+        };
+        for modifier in modifiers.split('+') {
+            match modifier.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => parsed.ctrl = true,
+                "shift" => parsed.shift = true,
+                "alt" | "option" => parsed.alt = true,
+                "meta" | "cmd" | "command" | "super" | "win" => parsed.meta = true,
+                "mod" if is_apple_platform() => parsed.meta = true,
+                "mod" => parsed.ctrl = true,
+                "" => {}
+                _ => crate::debug_warn!(
+                    "unknown modifier {modifier:?} in keyboard shortcut {shortcut:?}"
+                ),
+            }
+        }
+        parsed
+    }
+
+    /// Returns `true` if the event was fired by this key combination.
+    pub fn matches(&self, ev: &web_sys::KeyboardEvent) -> bool {
+        // `Shift` is needed to type many punctuation keys (like `?`), so it's only
+        // checked for letters, numbers, and named keys
+        let checks_shift =
+            self.key.chars().count() > 1 || self.key.chars().all(char::is_alphanumeric);
+
+        ev.ctrl_key() == self.ctrl
+            && ev.alt_key() == self.alt
+            && ev.meta_key() == self.meta
+            && (!checks_shift || ev.shift_key() == self.shift)
+            && self.matches_key(&ev.key(), &ev.code())
+    }
+
+    fn matches_key(&self, key: &str, code: &str) -> bool {
+        if key.to_lowercase() == self.key {
+            return true;
+        }
+        // `Alt` changes the character produced by most keys on macOS, so fall back to the
+        // physical key for letters and digits
+        match self.key.chars().next() {
+            Some(c) if self.key.len() == 1 && c.is_ascii_alphabetic() => {
+                code.eq_ignore_ascii_case(&format!("Key{c}"))
+            }
+            Some(c) if self.key.len() == 1 && c.is_ascii_digit() => code == format!("Digit{c}"),
+            _ => false,
+        }
+    }
+
+    /// Whether the shortcut can be typed as text, in which case it's ignored in text inputs.
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn is_typeable(&self) -> bool {
+        !(self.ctrl || self.alt || self.meta) && self.key.chars().count() == 1
+    }
+}
+
+impl From<&str> for KeyboardShortcut {
+    fn from(shortcut: &str) -> Self {
+        Self::parse(shortcut)
+    }
+}
+
+// whether the platform's shortcuts use `Command` rather than `Ctrl`
+fn is_apple_platform() -> bool {
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let platform = crate::window().navigator().platform().unwrap_or_default();
+            ["Mac", "iPhone", "iPad", "iPod"]
+                .iter()
+                .any(|apple| platform.starts_with(apple))
+        } else {
+            false
+        }
+    }
+}
+
+/// Whether the event was fired in an `<input>`, `<textarea>`, `<select>`, or other editable
+/// element, where keys are usually meant to type rather than to trigger shortcuts.
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn is_editable(target: Option<web_sys::EventTarget>) -> bool {
+    use wasm_bindgen::JsCast;
+
+    match target.and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok()) {
+        Some(el) => {
+            el.is_content_editable()
+                || matches!(el.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+        }
+        None => false,
+    }
+}
+
+fn normalize_key(key: &str) -> String {
+    let key = key.trim_matches(|c| c == ' ' || c == '\t');
+    let key = if key.is_empty() { " " } else { key };
+    match key.to_lowercase().as_str() {
+        "esc" => "escape".to_string(),
+        "space" | "spacebar" => " ".to_string(),
+        "up" => "arrowup".to_string(),
+        "down" => "arrowdown".to_string(),
+        "left" => "arrowleft".to_string(),
+        "right" => "arrowright".to_string(),
+        "del" => "delete".to_string(),
+        "return" => "enter".to_string(),
+        key => key.to_string(),
+    }
+}
+
+/// Calls `callback` whenever the given key combination (like `"Ctrl+K"`) is pressed anywhere on the page,
+/// until the [Scope] is disposed. See [KeyboardShortcut] for the format of the shortcut.
+///
+/// All shortcuts, and the bindings registered with [use_hotkeys](crate::use_hotkeys), share a
+/// single `keydown` listener on the `document`, so registering many shortcuts (or the same
+/// shortcut from many components) doesn’t add more listeners. A hotkey registered after this
+/// shortcut that handles the same key press keeps it from being called.
+/// When a shortcut matches, the event’s default action is prevented, so that shortcuts
+/// like `Ctrl+S` don’t also trigger the browser’s own behavior.
+///
+/// Shortcuts without `Ctrl`, `Alt`, or `Meta` that would type a character (like `"/"` or `"?"`)
+/// are ignored while the user is typing in an `<input>`, `<textarea>`, or other editable element.
+///
+/// ```
+/// # use leptos_reactive::*; use leptos_dom::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (search_open, set_search_open) = create_signal(cx, false);
+/// use_keyboard_shortcut(cx, "Ctrl+K", move || set_search_open(true));
+/// use_keyboard_shortcut(cx, "Escape", move || set_search_open(false));
+/// # });
+/// ```
+pub fn use_keyboard_shortcut(
+    cx: Scope,
+    shortcut: impl Into<KeyboardShortcut>,
+    callback: impl Fn() + 'static,
+) {
+    let shortcut = shortcut.into();
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use std::rc::Rc;
+
+            let id = registry::register(registry::Registered {
+                in_inputs: !shortcut.is_typeable(),
+                shortcut,
+                handler: Rc::new(move |_| {
+                    callback();
+                    crate::HotkeyResult::Handled
+                }),
+                exclusive: false,
+            });
+            leptos_reactive::on_cleanup(cx, move || registry::unregister(&[id]));
+        } else {
+            _ = (cx, shortcut, callback);
+        }
+    }
+}
+
+// the shortcuts registered by `use_keyboard_shortcut` and `use_hotkeys`, which share a single
+// `keydown` listener
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+pub(super) mod registry {
+    use super::{is_editable, KeyboardShortcut};
+    use crate::HotkeyResult;
+    use std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    };
+    use wasm_bindgen::{prelude::Closure, JsCast};
+
+    pub(in crate::hooks) struct Registered {
+        pub shortcut: KeyboardShortcut,
+        pub handler: Rc<dyn Fn(&web_sys::KeyboardEvent) -> HotkeyResult>,
+        // whether it is called while the user is typing in an editable element
+        pub in_inputs: bool,
+        // whether handling a key press keeps it from shortcuts registered before this one
+        pub exclusive: bool,
+    }
+
+    thread_local! {
+        // in the order they were registered
+        static SHORTCUTS: RefCell<Vec<(usize, Registered)>> = RefCell::new(Vec::new());
+        static NEXT_ID: Cell<usize> = Cell::new(0);
+        static LISTENING: Cell<bool> = Cell::new(false);
+    }
+
+    pub(in crate::hooks) fn register(registered: Registered) -> usize {
+        if !LISTENING.with(|listening| listening.replace(true)) {
+            let handler =
+                Closure::wrap(Box::new(on_keydown) as Box<dyn Fn(web_sys::KeyboardEvent)>);
+            _ = crate::document().add_event_listener_with_callback(
+                "keydown",
+                handler.into_js_value().unchecked_ref(),
+            );
+        }
+
+        let id = NEXT_ID.with(|next_id| {
+            let id = next_id.get();
+            next_id.set(id + 1);
+            id
+        });
+        SHORTCUTS.with(|shortcuts| shortcuts.borrow_mut().push((id, registered)));
+        id
+    }
+
+    pub(in crate::hooks) fn unregister(ids: &[usize]) {
+        SHORTCUTS.with(|shortcuts| shortcuts.borrow_mut().retain(|(id, _)| !ids.contains(id)));
+    }
+
+    // most recently registered first, so the innermost component wins
+    fn on_keydown(ev: web_sys::KeyboardEvent) {
+        let typing = is_editable(ev.target());
+        // collect first, so handlers can add or remove shortcuts
+        let matching = SHORTCUTS.with(|shortcuts| {
+            shortcuts
+                .borrow()
+                .iter()
+                .rev()
+                .filter(|(_, registered)| !typing || registered.in_inputs)
+                .filter(|(_, registered)| registered.shortcut.matches(&ev))
+                .map(|(_, registered)| (Rc::clone(&registered.handler), registered.exclusive))
+                .collect::<Vec<_>>()
+        });
+
+        for (handler, exclusive) in matching {
+            if handler(&ev) == HotkeyResult::Handled {
+                ev.prevent_default();
+                if exclusive {
+                    return;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Reactive wrappers for browser APIs.
+//!
+//! Each of these can be called in any rendering mode. Anything that touches browser
+//! APIs only runs in the browser (i.e., with the `csr` or `hydrate` features), and any
+//! event listeners or other resources are released when the [Scope](leptos_reactive::Scope)
+//! is disposed.
+
+mod battery;
+mod canvas;
+mod clipboard;
+mod color_mode;
+mod event_listener;
+mod fetch;
+mod focus_trap;
+mod fullscreen;
+mod gamepad;
+mod hotkeys;
+mod keyboard_shortcut;
+mod notifications;
+mod payment_request;
+mod picture_in_picture;
+mod pointer_lock;
+mod resize;
+mod screen_capture;
+mod share;
+mod speech_recognition;
+mod speech_synthesis;
+mod sse;
+mod storage;
+mod vibration;
+mod wake_lock;
+mod websocket;
+
+pub use battery::*;
+pub use canvas::*;
+pub use clipboard::*;
+pub use color_mode::*;
+pub use event_listener::*;
+pub use fetch::*;
+pub use focus_trap::*;
+pub use fullscreen::*;
+pub use gamepad::*;
+pub use hotkeys::*;
+pub use keyboard_shortcut::*;
+pub use notifications::*;
+pub use payment_request::*;
+pub use picture_in_picture::*;
+pub use pointer_lock::*;
+pub use resize::*;
+pub use screen_capture::*;
+pub use share::*;
+pub use speech_recognition::*;
+pub use speech_synthesis::*;
+pub use sse::*;
+pub use storage::*;
+pub use vibration::*;
+pub use wake_lock::*;
+pub use websocket::*;
@@ -0,0 +1,197 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope, WriteSignal};
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+use std::{cell::RefCell, rc::Rc};
+
+/// Whether the user has allowed the page to show [notifications](use_notifications).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotificationPermission {
+    /// The user hasn’t been asked yet, so notifications can’t be shown.
+    #[default]
+    Default,
+    /// The user has allowed notifications.
+    Granted,
+    /// The user has refused notifications, or the browser doesn’t support them.
+    Denied,
+}
+
+/// The contents of a notification shown with [NotificationState::notify].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NotificationOptions {
+    /// The title of the notification.
+    pub title: String,
+    /// The body text shown below the title.
+    pub body: Option<String>,
+    /// The URL of an icon to show alongside the notification.
+    pub icon: Option<String>,
+    /// An identifier for the notification: showing a notification with the same tag
+    /// replaces the previous one rather than adding another.
+    pub tag: Option<String>,
+    /// Whether the notification should be shown without any sound or vibration.
+    pub silent: bool,
+}
+
+impl From<&str> for NotificationOptions {
+    fn from(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The reactive state of the [Notifications API](https://developer.mozilla.org/en-US/docs/Web/API/Notifications_API),
+/// as returned by [use_notifications].
+#[derive(Clone)]
+pub struct NotificationState {
+    /// Whether the user has allowed notifications.
+    pub permission: ReadSignal<NotificationPermission>,
+    set_permission: WriteSignal<NotificationPermission>,
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    shown: Rc<RefCell<Vec<web_sys::Notification>>>,
+}
+
+impl NotificationState {
+    /// Asks the user for permission to show notifications, updating [permission](Self::permission)
+    /// once they have answered. Browsers only show the prompt in response to a user gesture, like a click.
+    pub fn request_permission(&self) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                let set_permission = self.set_permission;
+                match web_sys::Notification::request_permission() {
+                    Ok(promise) => leptos_reactive::spawn_local(async move {
+                        _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+                        set_permission.set(current_permission());
+                    }),
+                    Err(e) => crate::debug_warn!("[use_notifications] {e:?}"),
+                }
+            } else {
+                _ = self.set_permission;
+            }
+        }
+    }
+
+    /// Shows a notification, if the user has allowed notifications. Any notifications that are
+    /// still open are closed when the [Scope] is disposed.
+    pub fn notify(&self, options: impl Into<NotificationOptions>) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                use leptos_reactive::UntrackedGettableSignal;
+                use wasm_bindgen::JsCast;
+
+                if self.permission.get_untracked() != NotificationPermission::Granted {
+                    return;
+                }
+
+                let options = options.into();
+                let init = web_sys::NotificationOptions::new();
+                if let Some(body) = &options.body {
+                    init.set_body(body);
+                }
+                if let Some(icon) = &options.icon {
+                    init.set_icon(icon);
+                }
+                if let Some(tag) = &options.tag {
+                    init.set_tag(tag);
+                }
+                init.set_silent(Some(options.silent));
+
+                match web_sys::Notification::new_with_options(&options.title, &init) {
+                    Ok(notification) => {
+                        // stop tracking the notification once the user (or the browser) closes it
+                        let on_close = wasm_bindgen::closure::Closure::once_into_js({
+                            let shown = Rc::clone(&self.shown);
+                            let notification = notification.clone();
+                            move || shown.borrow_mut().retain(|n| n != &notification)
+                        });
+                        _ = notification
+                            .add_event_listener_with_callback("close", on_close.unchecked_ref());
+                        self.shown.borrow_mut().push(notification);
+                    }
+                    Err(e) => crate::debug_warn!("[use_notifications] {e:?}"),
+                }
+            } else {
+                _ = options;
+            }
+        }
+    }
+}
+
+/// Reactively tracks permission to show system notifications, using the
+/// [Notifications API](https://developer.mozilla.org/en-US/docs/Web/API/Notifications_API).
+///
+/// On the server, the permission is always [NotificationPermission::Default] and
+/// notifications are never shown.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Reminder(cx: Scope) -> Element {
+///   let notifications = use_notifications(cx);
+///   let allowed = move || notifications.permission.get() == NotificationPermission::Granted;
+///
+///   view! { cx,
+///     <div>
+///       <button
+///         disabled=allowed
+///         on:click={
+///           let notifications = notifications.clone();
+///           move |_| notifications.request_permission()
+///         }
+///       >
+///         "Enable notifications"
+///       </button>
+///       <button on:click=move |_| notifications.notify(NotificationOptions {
+///         title: "Time to stretch!".to_string(),
+///         body: Some("You’ve been sitting for an hour.".to_string()),
+///         ..Default::default()
+///       })>
+///         "Remind me"
+///       </button>
+///     </div>
+///   }
+/// }
+/// ```
+pub fn use_notifications(cx: Scope) -> NotificationState {
+    let (permission, set_permission) = create_signal(cx, NotificationPermission::Default);
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    let shown = Rc::new(RefCell::new(Vec::<web_sys::Notification>::new()));
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            set_permission.set(current_permission());
+
+            leptos_reactive::on_cleanup(cx, {
+                let shown = Rc::clone(&shown);
+                move || {
+                    for notification in shown.take() {
+                        notification.close();
+                    }
+                }
+            });
+        }
+    }
+
+    NotificationState {
+        permission,
+        set_permission,
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        shown,
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn current_permission() -> NotificationPermission {
+    let supported = js_sys::Reflect::has(
+        &crate::window(),
+        &wasm_bindgen::JsValue::from_str("Notification"),
+    )
+    .unwrap_or(false);
+    if !supported {
+        return NotificationPermission::Denied;
+    }
+    match web_sys::Notification::permission() {
+        web_sys::NotificationPermission::Granted => NotificationPermission::Granted,
+        web_sys::NotificationPermission::Denied => NotificationPermission::Denied,
+        _ => NotificationPermission::Default,
+    }
+}
@@ -0,0 +1,299 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope};
+use std::{cell::RefCell, future::Future, rc::Rc};
+use wasm_bindgen::JsValue;
+
+/// A payment method accepted by a [PaymentState], like `"https://google.com/pay"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentMethod {
+    /// The identifier of the payment method.
+    pub supported_methods: String,
+    /// Any data required by the payment method, as described in its documentation.
+    pub data: Option<serde_json::Value>,
+}
+
+/// An amount of money, like `{ currency: "USD", value: "9.99" }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentAmount {
+    /// A three-letter ISO 4217 currency code.
+    pub currency: String,
+    /// The amount, as a decimal string.
+    pub value: String,
+}
+
+/// A labelled amount shown on the payment sheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentItem {
+    /// The description of the item shown to the user.
+    pub label: String,
+    /// The amount of the item.
+    pub amount: PaymentAmount,
+}
+
+/// The details of a transaction, shown to the user on the payment sheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentDetails {
+    /// The total amount to be paid.
+    pub total: PaymentItem,
+    /// Line items shown above the total. The browser doesn’t check that they add up to it.
+    pub display_items: Vec<PaymentItem>,
+}
+
+/// Why [PaymentState::show] didn’t return a response.
+#[derive(Debug, Clone)]
+pub enum PaymentError {
+    /// The [Payment Request API](https://developer.mozilla.org/en-US/docs/Web/API/Payment_Request_API)
+    /// isn’t available in this browser, or this is running on the server.
+    Unsupported,
+    /// The browser refused to show the payment sheet, or the user closed it without paying,
+    /// or the request was [aborted](PaymentState::abort).
+    Rejected(JsValue),
+}
+
+/// A reactive wrapper for the [Payment Request API](https://developer.mozilla.org/en-US/docs/Web/API/Payment_Request_API),
+/// as returned by [create_payment_request].
+#[derive(Clone)]
+pub struct PaymentState {
+    /// Whether the user can pay with one of the accepted methods: `None` while this is
+    /// being checked (and always on the server).
+    pub can_make_payment: ReadSignal<Option<bool>>,
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    methods: Rc<Vec<PaymentMethod>>,
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    details: Rc<dyn Fn() -> PaymentDetails>,
+    active: Rc<RefCell<Option<JsValue>>>,
+}
+
+impl PaymentState {
+    /// Shows the browser’s payment sheet for the current details, resolving to the user’s
+    /// response once they have paid. Call [`complete`](web_sys::PaymentResponse::complete)
+    /// on the response once the payment has been processed, to close the sheet.
+    ///
+    /// Browsers only show the payment sheet in response to a user gesture, like a click.
+    pub fn show(&self) -> impl Future<Output = Result<web_sys::PaymentResponse, PaymentError>> {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                use wasm_bindgen::JsCast;
+
+                let request = js::PaymentRequest::build(&self.methods, &(self.details)());
+                if let Ok(request) = &request {
+                    *self.active.borrow_mut() = Some(request.clone().into());
+                }
+                let active = Rc::clone(&self.active);
+
+                async move {
+                    let request = request?;
+                    let promise = request.show().map_err(PaymentError::Rejected)?;
+                    let result = wasm_bindgen_futures::JsFuture::from(promise).await;
+                    active.borrow_mut().take();
+                    result
+                        .map(|response| response.unchecked_into())
+                        .map_err(PaymentError::Rejected)
+                }
+            } else {
+                async { Err(PaymentError::Unsupported) }
+            }
+        }
+    }
+
+    /// Closes the payment sheet, if it’s open, making [show](Self::show) return
+    /// [PaymentError::Rejected].
+    pub fn abort(&self) {
+        abort(&self.active);
+    }
+}
+
+/// Creates a reactive wrapper for the [Payment Request API](https://developer.mozilla.org/en-US/docs/Web/API/Payment_Request_API),
+/// which shows the browser’s native payment sheet for a checkout.
+///
+/// Whether the user can pay with one of the `methods` is checked again whenever the `details`
+/// change. If the payment sheet is still open when the [Scope] is disposed, it is closed.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Checkout(cx: Scope, price: ReadSignal<String>) -> Element {
+///   let payment = create_payment_request(
+///     cx,
+///     vec![PaymentMethod {
+///       supported_methods: "https://example.com/pay".to_string(),
+///       data: None,
+///     }],
+///     move || PaymentDetails {
+///       total: PaymentItem {
+///         label: "Total".to_string(),
+///         amount: PaymentAmount { currency: "USD".to_string(), value: price.get() },
+///       },
+///       display_items: vec![],
+///     },
+///   );
+///   let can_pay = payment.can_make_payment;
+///
+///   view! { cx,
+///     <button
+///       disabled=move || can_pay.get() != Some(true)
+///       on:click=move |_| {
+///         let payment = payment.clone();
+///         spawn_local(async move {
+///           if let Ok(response) = payment.show().await {
+///             // send `response.details()` to the server, then…
+///             response.complete();
+///           }
+///         });
+///       }
+///     >
+///       "Pay"
+///     </button>
+///   }
+/// }
+/// ```
+pub fn create_payment_request(
+    cx: Scope,
+    methods: Vec<PaymentMethod>,
+    details: impl Fn() -> PaymentDetails + 'static,
+) -> PaymentState {
+    let (can_make_payment, set_can_make_payment) = create_signal(cx, None);
+    let active = Rc::new(RefCell::new(None));
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use std::cell::Cell;
+
+            let methods = Rc::new(methods);
+            let details: Rc<dyn Fn() -> PaymentDetails> = Rc::new(details);
+
+            // only the latest check should update the signal, if the details change mid-check
+            let checks = Rc::new(Cell::new(0_usize));
+            leptos_reactive::create_effect(cx, {
+                let methods = Rc::clone(&methods);
+                let details = Rc::clone(&details);
+                move |_| {
+                    let check = checks.get() + 1;
+                    checks.set(check);
+                    set_can_make_payment.set(None);
+
+                    let promise = js::PaymentRequest::build(&methods, &details())
+                        .ok()
+                        .and_then(|request| request.can_make_payment().ok());
+                    let checks = Rc::clone(&checks);
+                    leptos_reactive::spawn_local(async move {
+                        let can_pay = match promise {
+                            Some(promise) => wasm_bindgen_futures::JsFuture::from(promise)
+                                .await
+                                .map(|can_pay| can_pay.is_truthy())
+                                .unwrap_or(false),
+                            None => false,
+                        };
+                        if checks.get() == check {
+                            set_can_make_payment.set(Some(can_pay));
+                        }
+                    });
+                }
+            });
+        } else {
+            _ = (set_can_make_payment, methods, details);
+        }
+    }
+
+    leptos_reactive::on_cleanup(cx, {
+        let active = Rc::clone(&active);
+        move || abort(&active)
+    });
+
+    PaymentState {
+        can_make_payment,
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        methods,
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        details,
+        active,
+    }
+}
+
+fn abort(active: &RefCell<Option<JsValue>>) {
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use wasm_bindgen::JsCast;
+
+            if let Some(request) = active.borrow_mut().take() {
+                let request = request.unchecked_into::<js::PaymentRequest>();
+                if let Ok(promise) = request.abort() {
+                    // the promise rejects if the sheet has already closed, which doesn't matter
+                    leptos_reactive::spawn_local(async move {
+                        _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+                    });
+                }
+            }
+        } else {
+            _ = active;
+        }
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod js {
+    use super::{PaymentDetails, PaymentError, PaymentItem, PaymentMethod};
+    use serde_json::json;
+    use wasm_bindgen::prelude::*;
+
+    // `PaymentRequest` isn't included in `web_sys`
+    #[wasm_bindgen]
+    extern "C" {
+        #[derive(Clone)]
+        pub type PaymentRequest;
+
+        #[wasm_bindgen(catch, constructor)]
+        fn new(methods: &JsValue, details: &JsValue) -> Result<PaymentRequest, JsValue>;
+
+        #[wasm_bindgen(catch, method)]
+        pub fn show(this: &PaymentRequest) -> Result<js_sys::Promise, JsValue>;
+
+        #[wasm_bindgen(catch, method)]
+        pub fn abort(this: &PaymentRequest) -> Result<js_sys::Promise, JsValue>;
+
+        #[wasm_bindgen(catch, method, js_name = canMakePayment)]
+        pub fn can_make_payment(this: &PaymentRequest) -> Result<js_sys::Promise, JsValue>;
+    }
+
+    impl PaymentRequest {
+        pub fn build(
+            methods: &[PaymentMethod],
+            details: &PaymentDetails,
+        ) -> Result<Self, PaymentError> {
+            let supported =
+                js_sys::Reflect::has(&crate::window(), &JsValue::from_str("PaymentRequest"))
+                    .unwrap_or(false);
+            if !supported {
+                return Err(PaymentError::Unsupported);
+            }
+
+            let methods = methods
+                .iter()
+                .map(|method| match &method.data {
+                    Some(data) => {
+                        json!({ "supportedMethods": method.supported_methods, "data": data })
+                    }
+                    None => json!({ "supportedMethods": method.supported_methods }),
+                })
+                .collect::<Vec<_>>();
+            let item = |item: &PaymentItem| {
+                json!({
+                    "label": item.label,
+                    "amount": { "currency": item.amount.currency, "value": item.amount.value },
+                })
+            };
+            let details = json!({
+                "total": item(&details.total),
+                "displayItems": details.display_items.iter().map(item).collect::<Vec<_>>(),
+            });
+
+            let methods = js_sys::JSON::parse(&serde_json::Value::from(methods).to_string());
+            let details = js_sys::JSON::parse(&details.to_string());
+            match (methods, details) {
+                (Ok(methods), Ok(details)) => {
+                    Self::new(&methods, &details).map_err(PaymentError::Rejected)
+                }
+                (Err(e), _) | (_, Err(e)) => Err(PaymentError::Rejected(e)),
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::NodeRef;
+use leptos_reactive::{create_signal, ReadSignal, Scope};
+
+/// Reactively tracks whether the `<video>` element in `video` is playing in a floating
+/// window, using the [Picture-in-Picture API](https://developer.mozilla.org/en-US/docs/Web/API/Picture-in-Picture_API).
+///
+/// Returns a signal that is `true` while the video is in picture-in-picture mode, and a
+/// function that toggles it. (Browsers only allow entering picture-in-picture in response to
+/// a user gesture, like a click.) If the video is still in picture-in-picture mode when the
+/// [Scope] is disposed, the floating window is closed.
+///
+/// If the browser doesn’t support picture-in-picture, or it has been disabled for the page
+/// (see [`document.pictureInPictureEnabled`](https://developer.mozilla.org/en-US/docs/Web/API/Document/pictureInPictureEnabled)),
+/// the signal is always `false` and the function does nothing. On the server, the same is true.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Player(cx: Scope) -> Element {
+///   let video = NodeRef::new(cx);
+///   let (is_pip, toggle_pip) = use_picture_in_picture(cx, video);
+///
+///   view! { cx,
+///     <div>
+///       <video _ref=video src="movie.mp4" controls/>
+///       <button on:click=move |_| toggle_pip()>
+///         {move || if is_pip() { "Back to page" } else { "Picture-in-picture" }}
+///       </button>
+///     </div>
+///   }
+/// }
+/// ```
+pub fn use_picture_in_picture(cx: Scope, video: NodeRef) -> (ReadSignal<bool>, impl Fn() + Clone) {
+    let (is_pip, set_pip) = create_signal(cx, false);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use crate::use_event_listener;
+            use leptos_reactive::{on_cleanup, UntrackedGettableSignal};
+
+            let enabled = js::enabled();
+            let check = move || enabled && js::element().is_some_and(|el| video.get() == Some(el));
+
+            set_pip.set(check());
+            use_event_listener(cx, video, "enterpictureinpicture", move |_| set_pip.set(check()));
+            use_event_listener(cx, video, "leavepictureinpicture", move |_| set_pip.set(check()));
+
+            on_cleanup(cx, move || {
+                if is_pip.get_untracked() {
+                    js::exit();
+                }
+            });
+
+            let toggle = move || {
+                if !enabled {
+                    crate::debug_warn!("[use_picture_in_picture] picture-in-picture is disabled");
+                } else if is_pip.get_untracked() {
+                    js::exit();
+                } else if let Some(video) = video.get() {
+                    js::request(video);
+                }
+            };
+        } else {
+            _ = (video, set_pip);
+            let toggle = || {};
+        }
+    }
+
+    (is_pip, toggle)
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod js {
+    use wasm_bindgen::{prelude::*, JsCast};
+    use wasm_bindgen_futures::JsFuture;
+
+    // the Picture-in-Picture API isn’t included in `web_sys`
+    #[wasm_bindgen]
+    extern "C" {
+        type PipDocument;
+
+        #[wasm_bindgen(method, getter, js_name = pictureInPictureEnabled)]
+        fn picture_in_picture_enabled(this: &PipDocument) -> Option<bool>;
+
+        #[wasm_bindgen(method, getter, js_name = pictureInPictureElement)]
+        fn picture_in_picture_element(this: &PipDocument) -> Option<web_sys::Element>;
+
+        #[wasm_bindgen(catch, method, js_name = exitPictureInPicture)]
+        fn exit_picture_in_picture(this: &PipDocument) -> Result<js_sys::Promise, JsValue>;
+
+        type PipVideo;
+
+        #[wasm_bindgen(catch, method, js_name = requestPictureInPicture)]
+        fn request_picture_in_picture(this: &PipVideo) -> Result<js_sys::Promise, JsValue>;
+    }
+
+    fn document() -> PipDocument {
+        crate::document().unchecked_into()
+    }
+
+    pub fn enabled() -> bool {
+        document().picture_in_picture_enabled().unwrap_or(false)
+    }
+
+    pub fn element() -> Option<web_sys::Element> {
+        document().picture_in_picture_element()
+    }
+
+    pub fn exit() {
+        settle(document().exit_picture_in_picture());
+    }
+
+    pub fn request(video: web_sys::Element) {
+        settle(
+            video
+                .unchecked_into::<PipVideo>()
+                .request_picture_in_picture(),
+        );
+    }
+
+    // logs the error if the browser refuses, e.g. without a user gesture
+    fn settle(promise: Result<js_sys::Promise, JsValue>) {
+        match promise {
+            Ok(promise) => leptos_reactive::spawn_local(async move {
+                if let Err(e) = JsFuture::from(promise).await {
+                    crate::debug_warn!("[use_picture_in_picture] {e:?}");
+                }
+            }),
+            Err(e) => crate::debug_warn!("[use_picture_in_picture] {e:?}"),
+        }
+    }
+}
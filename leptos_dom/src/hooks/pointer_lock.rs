@@ -0,0 +1,113 @@
+use crate::NodeRef;
+use leptos_reactive::{create_signal, ReadSignal, Scope};
+
+/// The reactive state of the [Pointer Lock API](https://developer.mozilla.org/en-US/docs/Web/API/Pointer_Lock_API),
+/// as returned by [use_pointer_lock].
+#[derive(Copy, Clone, PartialEq)]
+pub struct PointerLockState {
+    /// `true` while the pointer is locked to the element.
+    pub locked: ReadSignal<bool>,
+    /// The total mouse movement `(x, y)` since the pointer was last locked, in pixels.
+    /// Because the pointer is hidden and can’t reach the edge of the screen, this keeps
+    /// changing for as long as the mouse moves.
+    pub movement: ReadSignal<(f64, f64)>,
+    target: NodeRef,
+}
+
+impl PointerLockState {
+    /// Asks the browser to lock the pointer to the element. This only works in
+    /// response to a user gesture, like a click.
+    pub fn lock(&self) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                if let Some(el) = self.target.get() {
+                    el.request_pointer_lock();
+                }
+            }
+        }
+    }
+
+    /// Releases the pointer lock, if the pointer is locked to the element.
+    pub fn unlock(&self) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                use leptos_reactive::UntrackedGettableSignal;
+
+                if self.locked.get_untracked() {
+                    crate::document().exit_pointer_lock();
+                }
+            }
+        }
+    }
+}
+
+/// Reactively tracks the [Pointer Lock API](https://developer.mozilla.org/en-US/docs/Web/API/Pointer_Lock_API)
+/// for the element in `target`, which hides the pointer and reports raw mouse movement
+/// instead. This is useful for games and 3D viewers.
+///
+/// The pointer lock is released when the [Scope] is disposed.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Viewer(cx: Scope) -> Element {
+///   let canvas = NodeRef::new(cx);
+///   let pointer = use_pointer_lock(cx, canvas);
+///   let rotation = move || {
+///     let (x, y) = pointer.movement.get();
+///     format!("rotate({}deg, {}deg)", x / 4.0, y / 4.0)
+///   };
+///
+///   view! { cx,
+///     <canvas _ref=canvas on:click=move |_| pointer.lock() data-rotation=rotation/>
+///   }
+/// }
+/// ```
+pub fn use_pointer_lock(cx: Scope, target: NodeRef) -> PointerLockState {
+    let (locked, set_locked) = create_signal(cx, false);
+    let (movement, set_movement) = create_signal(cx, (0.0, 0.0));
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use crate::{document, use_event_listener, ListenerTarget};
+            use leptos_reactive::{on_cleanup, UntrackedGettableSignal};
+            use wasm_bindgen::JsCast;
+
+            use_event_listener(cx, ListenerTarget::Document, "pointerlockchange", move |_| {
+                let is_locked = match (document().pointer_lock_element(), target.get()) {
+                    (Some(locked_el), Some(el)) => locked_el == el,
+                    _ => false,
+                };
+                if is_locked {
+                    set_movement.set((0.0, 0.0));
+                }
+                set_locked.set(is_locked);
+            });
+
+            use_event_listener(cx, ListenerTarget::Document, "mousemove", move |ev| {
+                if locked.get_untracked() {
+                    let ev = ev.unchecked_into::<web_sys::MouseEvent>();
+                    let (dx, dy) = (ev.movement_x() as f64, ev.movement_y() as f64);
+                    set_movement.update(|(x, y)| {
+                        *x += dx;
+                        *y += dy;
+                    });
+                }
+            });
+
+            on_cleanup(cx, move || {
+                if locked.get_untracked() {
+                    document().exit_pointer_lock();
+                }
+            });
+        } else {
+            _ = (set_locked, set_movement);
+        }
+    }
+
+    PointerLockState {
+        locked,
+        movement,
+        target,
+    }
+}
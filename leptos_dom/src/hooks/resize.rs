@@ -0,0 +1,102 @@
+use crate::NodeRef;
+use leptos_reactive::{create_signal, ReadSignal, Scope};
+
+/// The size of an element, as returned by [use_resize].
+#[derive(Copy, Clone, PartialEq)]
+pub struct ResizeState {
+    /// The width of the element’s content box, in CSS pixels.
+    pub width: ReadSignal<f64>,
+    /// The height of the element’s content box, in CSS pixels.
+    pub height: ReadSignal<f64>,
+    /// The element’s border box, as measured by
+    /// [`Element.getBoundingClientRect`](https://developer.mozilla.org/en-US/docs/Web/API/Element/getBoundingClientRect)
+    /// when it was last resized. Its position is relative to the viewport at that moment, and
+    /// it includes any CSS transforms. This is `None` while the element isn’t rendered.
+    pub border_box: ReadSignal<Option<web_sys::DomRectReadOnly>>,
+}
+
+/// Reactively tracks the size of the element in `element_ref`, using a
+/// [`ResizeObserver`](https://developer.mozilla.org/en-US/docs/Web/API/ResizeObserver).
+///
+/// The sizes are `0.0` until the element is rendered, and go back to `0.0` if it is removed
+/// from the [NodeRef]. If the [NodeRef] is loaded with another element, that one is observed
+/// instead. The observer is disconnected when the [Scope] is disposed.
+///
+/// On the server, the sizes are always `0.0`.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Chart(cx: Scope) -> Element {
+///   let container = NodeRef::new(cx);
+///   let size = use_resize(cx, container);
+///   let columns = move || (size.width.get() / 200.0).floor().max(1.0);
+///
+///   view! { cx,
+///     <div _ref=container>
+///       {move || format!("{} columns", columns())}
+///     </div>
+///   }
+/// }
+/// ```
+pub fn use_resize(cx: Scope, element_ref: NodeRef) -> ResizeState {
+    let (width, set_width) = create_signal(cx, 0.0);
+    let (height, set_height) = create_signal(cx, 0.0);
+    let (border_box, set_border_box) = create_signal(cx, None::<web_sys::DomRectReadOnly>);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use leptos_reactive::{create_effect, on_cleanup};
+            use wasm_bindgen::{prelude::Closure, JsCast};
+
+            let on_resize = Closure::wrap(Box::new(move |entries: js_sys::Array| {
+                // only one element is observed at a time, so the last entry is the latest size
+                if let Some(entry) = entries.iter().last() {
+                    let entry = entry.unchecked_into::<web_sys::ResizeObserverEntry>();
+                    let content_rect = entry.content_rect();
+                    set_width.set(content_rect.width());
+                    set_height.set(content_rect.height());
+                    set_border_box.set(Some(entry.target().get_bounding_client_rect().into()));
+                }
+            }) as Box<dyn Fn(js_sys::Array)>);
+            let on_resize = on_resize.into_js_value();
+            let observer = match web_sys::ResizeObserver::new(on_resize.unchecked_ref()) {
+                Ok(observer) => observer,
+                Err(e) => {
+                    crate::debug_warn!("[use_resize] {e:?}");
+                    return ResizeState { width, height, border_box };
+                }
+            };
+
+            create_effect(cx, {
+                let observer = observer.clone();
+                move |prev: Option<Option<web_sys::Element>>| {
+                    let el = element_ref.get();
+                    if let Some(Some(prev)) = prev {
+                        observer.unobserve(&prev);
+                    }
+                    match &el {
+                        // the observer reports the element’s size as soon as it starts observing
+                        Some(el) => observer.observe(el),
+                        None => {
+                            set_width.set(0.0);
+                            set_height.set(0.0);
+                            set_border_box.set(None);
+                        }
+                    }
+                    el
+                }
+            });
+
+            on_cleanup(cx, move || observer.disconnect());
+        } else {
+            _ = (element_ref, set_width, set_height, set_border_box);
+        }
+    }
+
+    ResizeState {
+        width,
+        height,
+        border_box,
+    }
+}
@@ -0,0 +1,193 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope, WriteSignal};
+use std::future::Future;
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+use std::{cell::Cell, rc::Rc};
+
+/// The reactive state of screen sharing with the
+/// [Screen Capture API](https://developer.mozilla.org/en-US/docs/Web/API/Screen_Capture_API),
+/// as returned by [use_screen_capture].
+#[derive(Clone)]
+pub struct ScreenCaptureState {
+    /// The captured screen, window, or tab, while it is being captured. This can be shown in a
+    /// `<video>` element by setting its `srcObject`, recorded, or sent to a peer.
+    pub stream: ReadSignal<Option<web_sys::MediaStream>>,
+    /// `true` while capturing.
+    pub active: ReadSignal<bool>,
+    /// Why capturing didn’t start the last time it was [started](Self::start), if it didn’t:
+    /// for example, because the user cancelled, or the API isn’t available.
+    pub error: ReadSignal<Option<String>>,
+    set_stream: WriteSignal<Option<web_sys::MediaStream>>,
+    set_active: WriteSignal<bool>,
+    set_error: WriteSignal<Option<String>>,
+    // bumped whenever capturing stops, so a capture still being chosen is dropped
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    generation: Rc<Cell<usize>>,
+}
+
+impl ScreenCaptureState {
+    /// Asks the user to choose a screen, window, or tab to capture, and starts capturing it.
+    /// Resolves once capturing has started, or failed to. (Browsers only ask in response to a
+    /// user gesture, like a click.) Does nothing if already capturing.
+    pub fn start(&self) -> impl Future<Output = ()> + 'static {
+        let state = self.clone();
+        async move {
+            cfg_if::cfg_if! {
+                if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                    use leptos_reactive::UntrackedGettableSignal;
+
+                    if state.active.get_untracked() {
+                        return;
+                    }
+                    let requested_in = state.generation.get();
+                    let stream = js::get_display_media().await;
+                    // stopped, disposed, or started again while the user was choosing
+                    if state.generation.get() != requested_in || state.active.get_untracked() {
+                        if let Ok(stream) = stream {
+                            js::stop_tracks(&stream);
+                        }
+                        return;
+                    }
+
+                    match stream {
+                        Ok(stream) => {
+                            js::on_ended(&stream, {
+                                let state = state.clone();
+                                move || state.stop()
+                            });
+                            state.set_error.set(None);
+                            state.set_stream.set(Some(stream));
+                            state.set_active.set(true);
+                        }
+                        Err(e) => state.set_error.set(Some(e)),
+                    }
+                } else {
+                    _ = (state.set_stream, state.set_active, state.set_error);
+                }
+            }
+        }
+    }
+
+    /// Stops capturing.
+    pub fn stop(&self) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                use leptos_reactive::UntrackedGettableSignal;
+
+                self.generation.set(self.generation.get() + 1);
+                if let Some(stream) = self.stream.get_untracked() {
+                    js::stop_tracks(&stream);
+                    self.set_stream.set(None);
+                    self.set_active.set(false);
+                }
+            }
+        }
+    }
+}
+
+/// Captures the user’s screen, a window, or a browser tab as a video stream, using the
+/// [Screen Capture API](https://developer.mozilla.org/en-US/docs/Web/API/Screen_Capture_API).
+/// This is useful for screen recording, collaborative tools, and remote support.
+///
+/// Nothing is captured until [start](ScreenCaptureState::start) is called. Capturing stops
+/// when [stop](ScreenCaptureState::stop) is called, when the user stops sharing from the
+/// browser’s own controls, or when the [Scope] is disposed.
+///
+/// On the server, nothing is ever captured.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn ShareScreen(cx: Scope) -> Element {
+///   let capture = use_screen_capture(cx);
+///   let (active, stream) = (capture.active, capture.stream);
+///   let preview = NodeRef::new(cx);
+///
+///   create_effect(cx, move |_| {
+///     if let Some(video) = preview.get() {
+///       let video = video.unchecked_into::<web_sys::HtmlMediaElement>();
+///       video.set_src_object(stream.get().as_ref());
+///     }
+///   });
+///
+///   view! { cx,
+///     <div>
+///       <button on:click=move |_| if active.get() {
+///         capture.stop()
+///       } else {
+///         spawn_local(capture.start())
+///       }>
+///         {move || if active.get() { "Stop sharing" } else { "Share screen" }}
+///       </button>
+///       <video _ref=preview autoplay muted/>
+///     </div>
+///   }
+/// }
+/// ```
+pub fn use_screen_capture(cx: Scope) -> ScreenCaptureState {
+    let (stream, set_stream) = create_signal(cx, None::<web_sys::MediaStream>);
+    let (active, set_active) = create_signal(cx, false);
+    let (error, set_error) = create_signal(cx, None);
+
+    let state = ScreenCaptureState {
+        stream,
+        active,
+        error,
+        set_stream,
+        set_active,
+        set_error,
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        generation: Rc::new(Cell::new(0)),
+    };
+
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    leptos_reactive::on_cleanup(cx, {
+        let state = state.clone();
+        move || state.stop()
+    });
+
+    state
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod js {
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    pub async fn get_display_media() -> Result<web_sys::MediaStream, String> {
+        let promise = crate::window()
+            .navigator()
+            .media_devices()
+            .and_then(|devices| devices.get_display_media())
+            .map_err(|_| "the Screen Capture API isn’t available".to_string())?;
+        JsFuture::from(promise)
+            .await
+            .map(JsCast::unchecked_into)
+            .map_err(message)
+    }
+
+    pub fn stop_tracks(stream: &web_sys::MediaStream) {
+        for track in stream.get_tracks().iter() {
+            let track = track.unchecked_into::<web_sys::MediaStreamTrack>();
+            track.set_onended(None);
+            track.stop();
+        }
+    }
+
+    // called when the user stops sharing from the browser's own controls
+    pub fn on_ended(stream: &web_sys::MediaStream, f: impl Fn() + 'static) {
+        let on_ended =
+            Closure::wrap(Box::new(move |_: web_sys::Event| f()) as Box<dyn Fn(web_sys::Event)>)
+                .into_js_value();
+        for track in stream.get_video_tracks().iter() {
+            let track = track.unchecked_into::<web_sys::MediaStreamTrack>();
+            track.set_onended(Some(on_ended.unchecked_ref()));
+        }
+    }
+
+    fn message(e: JsValue) -> String {
+        match e.dyn_ref::<js_sys::Error>() {
+            Some(e) => String::from(e.message()),
+            None => format!("{e:?}"),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope};
+use std::{future::Future, pin::Pin};
+use wasm_bindgen::JsValue;
+
+/// What to share with [use_share]. At least one of the fields should be set.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ShareData {
+    /// The title of what is being shared.
+    pub title: Option<String>,
+    /// The text being shared.
+    pub text: Option<String>,
+    /// The URL being shared.
+    pub url: Option<String>,
+}
+
+/// Why sharing with [use_share] didn’t complete.
+#[derive(Debug, Clone)]
+pub enum ShareError {
+    /// The [Web Share API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Share_API)
+    /// isn’t available in this browser, or this is running on the server.
+    Unsupported,
+    /// The user closed the share sheet without sharing.
+    Cancelled,
+    /// The browser refused to share the data, for example because it wasn’t called in
+    /// response to a user gesture.
+    Rejected(JsValue),
+}
+
+/// Shares text, links, or other data using the platform’s native share sheet, with the
+/// [Web Share API](https://developer.mozilla.org/en-US/docs/Web/API/Web_Share_API).
+///
+/// Returns a signal holding whether sharing is available, and a function that opens the share
+/// sheet, which resolves once the user has shared the data or cancelled. Browsers only show
+/// the share sheet in response to a user gesture, like a click.
+///
+/// On the server, and in browsers that don’t support sharing, the signal is always `false`,
+/// and the function resolves to [ShareError::Unsupported] without doing anything.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn ShareButton(cx: Scope, url: String) -> Element {
+///   let (can_share, share) = use_share(cx);
+///
+///   view! { cx,
+///     <button
+///       hidden=move || !can_share.get()
+///       on:click=move |_| {
+///         let shared = share(ShareData {
+///           url: Some(url.clone()),
+///           ..Default::default()
+///         });
+///         spawn_local(async move {
+///           if let Err(ShareError::Rejected(e)) = shared.await {
+///             log::error!("{e:?}");
+///           }
+///         });
+///       }
+///     >
+///       "Share"
+///     </button>
+///   }
+/// }
+/// ```
+pub fn use_share(
+    cx: Scope,
+) -> (
+    ReadSignal<bool>,
+    impl Fn(ShareData) -> Pin<Box<dyn Future<Output = Result<(), ShareError>>>> + Clone,
+) {
+    let (can_share, set_can_share) = create_signal(cx, false);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            set_can_share.set(js::share_function().is_some());
+        } else {
+            _ = set_can_share;
+        }
+    }
+
+    let share = |data: ShareData| -> Pin<Box<dyn Future<Output = Result<(), ShareError>>>> {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                Box::pin(js::share(data))
+            } else {
+                _ = data;
+                Box::pin(async { Err(ShareError::Unsupported) })
+            }
+        }
+    };
+
+    (can_share, share)
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod js {
+    use super::{ShareData, ShareError};
+    use wasm_bindgen::{JsCast, JsValue};
+
+    // `navigator.share` is only included in `web_sys` behind `web_sys_unstable_apis`
+    pub fn share_function() -> Option<js_sys::Function> {
+        js_sys::Reflect::get(&crate::window().navigator(), &JsValue::from_str("share"))
+            .ok()?
+            .dyn_into()
+            .ok()
+    }
+
+    pub async fn share(data: ShareData) -> Result<(), ShareError> {
+        let share = share_function().ok_or(ShareError::Unsupported)?;
+
+        let init = js_sys::Object::new();
+        for (key, value) in [
+            ("title", data.title),
+            ("text", data.text),
+            ("url", data.url),
+        ] {
+            if let Some(value) = value {
+                _ = js_sys::Reflect::set(
+                    &init,
+                    &JsValue::from_str(key),
+                    &JsValue::from_str(&value),
+                );
+            }
+        }
+
+        let promise = share
+            .call1(&crate::window().navigator(), &init)
+            .map_err(ShareError::Rejected)?
+            .unchecked_into::<js_sys::Promise>();
+        wasm_bindgen_futures::JsFuture::from(promise)
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                let cancelled = e
+                    .dyn_ref::<js_sys::Error>()
+                    .map(|e| e.name() == "AbortError")
+                    .unwrap_or(false);
+                if cancelled {
+                    ShareError::Cancelled
+                } else {
+                    ShareError::Rejected(e)
+                }
+            })
+    }
+}
@@ -0,0 +1,271 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope, WriteSignal};
+
+/// How [use_speech_recognition] listens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpeechRecognitionOptions {
+    /// The language to recognize, as a BCP 47 language tag like `"en-GB"`, if not the
+    /// language of the page.
+    pub lang: Option<String>,
+    /// Whether to keep listening after the user stops speaking, until
+    /// [stop](SpeechRecognitionState::stop) is called. Defaults to `false`.
+    pub continuous: bool,
+    /// Whether to report what the user is saying before it is final, in
+    /// [interim_transcript](SpeechRecognitionState::interim_transcript). Defaults to `true`.
+    pub interim_results: bool,
+}
+
+impl Default for SpeechRecognitionOptions {
+    fn default() -> Self {
+        Self {
+            lang: None,
+            continuous: false,
+            interim_results: true,
+        }
+    }
+}
+
+/// Why [use_speech_recognition] stopped listening.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpeechRecognitionError {
+    /// The [Web Speech API](https://developer.mozilla.org/en-US/docs/Web/API/SpeechRecognition)
+    /// isn’t available in this browser, or this is running on the server.
+    Unsupported,
+    /// No speech was heard.
+    NoSpeech,
+    /// Listening was aborted.
+    Aborted,
+    /// The microphone couldn’t be used.
+    AudioCapture,
+    /// The recognition service couldn’t be reached.
+    Network,
+    /// The user, or the browser, didn’t allow the page to use the microphone.
+    NotAllowed,
+    /// The browser didn’t allow the page to use its recognition service.
+    ServiceNotAllowed,
+    /// The [language](SpeechRecognitionOptions::lang) isn’t supported.
+    LanguageNotSupported,
+    /// Any other error, with the browser’s description of it.
+    Other(String),
+}
+
+/// The reactive state of the [Web Speech API](https://developer.mozilla.org/en-US/docs/Web/API/SpeechRecognition)’s
+/// speech recognition, as returned by [use_speech_recognition].
+#[derive(Clone)]
+pub struct SpeechRecognitionState {
+    /// Everything the user has said since listening [started](Self::start), once it is final.
+    pub transcript: ReadSignal<String>,
+    /// What the user is saying at the moment, which may still change before it is added to
+    /// the [transcript](Self::transcript).
+    pub interim_transcript: ReadSignal<String>,
+    /// `true` while listening.
+    pub listening: ReadSignal<bool>,
+    /// Why listening stopped the last time it stopped because of an error, if it did.
+    pub error: ReadSignal<Option<SpeechRecognitionError>>,
+    set_transcript: WriteSignal<String>,
+    set_interim_transcript: WriteSignal<String>,
+    set_error: WriteSignal<Option<SpeechRecognitionError>>,
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    recognition: Option<web_sys::SpeechRecognition>,
+}
+
+impl SpeechRecognitionState {
+    /// Starts listening, clearing the transcript. Browsers ask the user for permission to use
+    /// the microphone the first time. Does nothing if already listening.
+    pub fn start(&self) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                use leptos_reactive::UntrackedGettableSignal;
+
+                let Some(recognition) = &self.recognition else {
+                    self.set_error.set(Some(SpeechRecognitionError::Unsupported));
+                    return;
+                };
+                if self.listening.get_untracked() {
+                    return;
+                }
+                self.set_transcript.set(String::new());
+                self.set_interim_transcript.set(String::new());
+                if let Err(e) = recognition.start() {
+                    crate::debug_warn!("[use_speech_recognition] {e:?}");
+                }
+            } else {
+                _ = (self.set_transcript, self.set_interim_transcript, self.set_error);
+            }
+        }
+    }
+
+    /// Stops listening. Anything the user has already said is still added to the transcript.
+    pub fn stop(&self) {
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        if let Some(recognition) = &self.recognition {
+            recognition.stop();
+        }
+    }
+}
+
+/// Turns what the user says into text, using the [Web Speech API](https://developer.mozilla.org/en-US/docs/Web/API/SpeechRecognition).
+/// This is useful for voice-controlled interfaces, or for dictation.
+///
+/// Nothing is heard until [start](SpeechRecognitionState::start) is called. Listening stops
+/// when the [Scope] is disposed.
+///
+/// On the server, and in browsers that don’t support speech recognition, nothing is ever
+/// heard, and starting sets the [error](SpeechRecognitionState::error) to
+/// [SpeechRecognitionError::Unsupported] in the browser.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Dictation(cx: Scope) -> Element {
+///   let speech = use_speech_recognition(
+///     cx,
+///     SpeechRecognitionOptions {
+///       continuous: true,
+///       ..Default::default()
+///     },
+///   );
+///   let (transcript, interim) = (speech.transcript, speech.interim_transcript);
+///   let listening = speech.listening;
+///
+///   view! { cx,
+///     <div>
+///       <button on:click=move |_| if listening.get() { speech.stop() } else { speech.start() }>
+///         {move || if listening.get() { "Stop" } else { "Dictate" }}
+///       </button>
+///       <p>{move || transcript.get()} <em>{move || interim.get()}</em></p>
+///     </div>
+///   }
+/// }
+/// ```
+pub fn use_speech_recognition(
+    cx: Scope,
+    options: SpeechRecognitionOptions,
+) -> SpeechRecognitionState {
+    let (transcript, set_transcript) = create_signal(cx, String::new());
+    let (interim_transcript, set_interim_transcript) = create_signal(cx, String::new());
+    let (listening, set_listening) = create_signal(cx, false);
+    let (error, set_error) = create_signal(cx, None);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use wasm_bindgen::{closure::Closure, JsCast};
+
+            let recognition = js::speech_recognition();
+            if let Some(recognition) = &recognition {
+                if let Some(lang) = &options.lang {
+                    recognition.set_lang(lang);
+                }
+                // newer versions of `web_sys` return a `Result` here
+                _ = recognition.set_continuous(options.continuous);
+                recognition.set_interim_results(options.interim_results);
+
+                let on_start = Closure::wrap(Box::new(move |_: web_sys::Event| {
+                    set_error.set(None);
+                    set_listening.set(true);
+                }) as Box<dyn Fn(web_sys::Event)>)
+                .into_js_value();
+                let on_end = Closure::wrap(Box::new(move |_: web_sys::Event| {
+                    set_interim_transcript.set(String::new());
+                    set_listening.set(false);
+                }) as Box<dyn Fn(web_sys::Event)>)
+                .into_js_value();
+                let on_result = Closure::wrap(Box::new(move |ev: web_sys::SpeechRecognitionEvent| {
+                    // the results cover everything heard since listening started
+                    let (transcript, interim) = js::transcripts(&ev);
+                    set_transcript.set(transcript);
+                    set_interim_transcript.set(interim);
+                }) as Box<dyn Fn(web_sys::SpeechRecognitionEvent)>)
+                .into_js_value();
+                let on_error = Closure::wrap(Box::new(move |ev: web_sys::SpeechRecognitionError| {
+                    set_error.set(Some(js::error(&ev)));
+                }) as Box<dyn Fn(web_sys::SpeechRecognitionError)>)
+                .into_js_value();
+
+                recognition.set_onstart(Some(on_start.unchecked_ref()));
+                recognition.set_onend(Some(on_end.unchecked_ref()));
+                recognition.set_onresult(Some(on_result.unchecked_ref()));
+                recognition.set_onerror(Some(on_error.unchecked_ref()));
+
+                leptos_reactive::on_cleanup(cx, {
+                    let recognition = recognition.clone();
+                    move || {
+                        recognition.set_onstart(None);
+                        recognition.set_onend(None);
+                        recognition.set_onresult(None);
+                        recognition.set_onerror(None);
+                        recognition.abort();
+                    }
+                });
+            }
+        } else {
+            _ = (options, set_listening);
+        }
+    }
+
+    SpeechRecognitionState {
+        transcript,
+        interim_transcript,
+        listening,
+        error,
+        set_transcript,
+        set_interim_transcript,
+        set_error,
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        recognition,
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod js {
+    use super::SpeechRecognitionError;
+    use wasm_bindgen::{JsCast, JsValue};
+
+    // Chrome and Safari only provide the API with a `webkit` prefix
+    pub fn speech_recognition() -> Option<web_sys::SpeechRecognition> {
+        let window = crate::window();
+        let constructor = ["SpeechRecognition", "webkitSpeechRecognition"]
+            .into_iter()
+            .filter_map(|name| js_sys::Reflect::get(&window, &JsValue::from_str(name)).ok())
+            .find_map(|constructor| constructor.dyn_into::<js_sys::Function>().ok())?;
+        js_sys::Reflect::construct(&constructor, &js_sys::Array::new())
+            .ok()
+            .map(JsCast::unchecked_into)
+    }
+
+    /// Returns the final transcript and the interim transcript.
+    pub fn transcripts(ev: &web_sys::SpeechRecognitionEvent) -> (String, String) {
+        let mut transcript = String::new();
+        let mut interim = String::new();
+        if let Some(results) = ev.results() {
+            for i in 0..results.length() {
+                let Some(result) = results.get(i) else {
+                    continue;
+                };
+                let Some(alternative) = result.get(0) else {
+                    continue;
+                };
+                if result.is_final() {
+                    transcript.push_str(&alternative.transcript());
+                } else {
+                    interim.push_str(&alternative.transcript());
+                }
+            }
+        }
+        (transcript, interim)
+    }
+
+    pub fn error(ev: &web_sys::SpeechRecognitionError) -> SpeechRecognitionError {
+        use web_sys::SpeechRecognitionErrorCode as Code;
+
+        match ev.error() {
+            Code::NoSpeech => SpeechRecognitionError::NoSpeech,
+            Code::Aborted => SpeechRecognitionError::Aborted,
+            Code::AudioCapture => SpeechRecognitionError::AudioCapture,
+            Code::Network => SpeechRecognitionError::Network,
+            Code::NotAllowed => SpeechRecognitionError::NotAllowed,
+            Code::ServiceNotAllowed => SpeechRecognitionError::ServiceNotAllowed,
+            Code::LanguageNotSupported => SpeechRecognitionError::LanguageNotSupported,
+            _ => SpeechRecognitionError::Other(ev.message().unwrap_or_default()),
+        }
+    }
+}
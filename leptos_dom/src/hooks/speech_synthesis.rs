@@ -0,0 +1,289 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope, WriteSignal};
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+use std::{cell::Cell, rc::Rc};
+
+/// A voice that text can be spoken in, as listed by [use_speech_synthesis].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Voice {
+    /// The name of the voice, like `"Google UK English Female"`.
+    pub name: String,
+    /// The language of the voice, as a BCP 47 language tag like `"en-GB"`.
+    pub lang: String,
+    /// Identifies the voice, for [SpeechOptions::voice].
+    pub uri: String,
+    /// Whether the voice is synthesized locally, rather than by a remote service.
+    pub local: bool,
+    /// Whether this is the browser’s default voice.
+    pub default: bool,
+}
+
+/// What to say with [SpeechSynthesisState::speak], and how.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechOptions {
+    /// The text to speak.
+    pub text: String,
+    /// The [uri](Voice::uri) of the voice to speak in, if not the default voice.
+    pub voice: Option<String>,
+    /// The language of the text, as a BCP 47 language tag like `"en-GB"`, if not the
+    /// language of the page.
+    pub lang: Option<String>,
+    /// How fast to speak, from `0.1` to `10.0`. Defaults to `1.0`.
+    pub rate: f32,
+    /// How high to speak, from `0.0` to `2.0`. Defaults to `1.0`.
+    pub pitch: f32,
+    /// How loud to speak, from `0.0` to `1.0`. Defaults to `1.0`.
+    pub volume: f32,
+}
+
+impl SpeechOptions {
+    /// Speaks the text in the default voice, at the normal rate, pitch, and volume.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            voice: None,
+            lang: None,
+            rate: 1.0,
+            pitch: 1.0,
+            volume: 1.0,
+        }
+    }
+}
+
+impl From<&str> for SpeechOptions {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for SpeechOptions {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+/// The reactive state of the [Web Speech API](https://developer.mozilla.org/en-US/docs/Web/API/SpeechSynthesis)’s
+/// speech synthesis, as returned by [use_speech_synthesis].
+#[derive(Clone)]
+pub struct SpeechSynthesisState {
+    /// `true` while text passed to [speak](Self::speak) is being spoken, including while it
+    /// is paused.
+    pub speaking: ReadSignal<bool>,
+    /// The voices the browser can speak in. This is empty until the browser has loaded them.
+    pub voices: ReadSignal<Vec<Voice>>,
+    set_speaking: WriteSignal<bool>,
+    // utterances queued with `speak` that haven’t finished yet
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    queued: Rc<Cell<usize>>,
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    disposed: Rc<Cell<bool>>,
+}
+
+impl SpeechSynthesisState {
+    /// Speaks the text, after anything that is already being spoken.
+    pub fn speak(&self, options: impl Into<SpeechOptions>) {
+        let options = options.into();
+
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                use wasm_bindgen::{closure::Closure, JsCast};
+
+                let Some(synth) = js::speech_synthesis() else {
+                    crate::debug_warn!("[use_speech_synthesis] speech synthesis isn’t available");
+                    return;
+                };
+                let utterance = web_sys::SpeechSynthesisUtterance::new_with_text(&options.text);
+                let utterance = match utterance {
+                    Ok(utterance) => utterance,
+                    Err(e) => {
+                        crate::debug_warn!("[use_speech_synthesis] {e:?}");
+                        return;
+                    }
+                };
+                if let Some(uri) = &options.voice {
+                    let voice = synth
+                        .get_voices()
+                        .iter()
+                        .map(JsCast::unchecked_into::<web_sys::SpeechSynthesisVoice>)
+                        .find(|voice| &voice.voice_uri() == uri);
+                    match voice {
+                        Some(voice) => utterance.set_voice(Some(&voice)),
+                        None => crate::debug_warn!("[use_speech_synthesis] no voice {uri:?}"),
+                    }
+                }
+                if let Some(lang) = &options.lang {
+                    utterance.set_lang(lang);
+                }
+                utterance.set_rate(options.rate);
+                utterance.set_pitch(options.pitch);
+                utterance.set_volume(options.volume);
+
+                let set_speaking = self.set_speaking;
+                let on_start = Closure::wrap(Box::new({
+                    let disposed = Rc::clone(&self.disposed);
+                    move |_: web_sys::Event| {
+                        if !disposed.get() {
+                            set_speaking.set(true);
+                        }
+                    }
+                }) as Box<dyn Fn(web_sys::Event)>)
+                .into_js_value();
+                // the utterance has finished, whether it was spoken, cancelled, or failed
+                let on_end = Closure::wrap(Box::new({
+                    let queued = Rc::clone(&self.queued);
+                    let disposed = Rc::clone(&self.disposed);
+                    let finished = Cell::new(false);
+                    move |_: web_sys::Event| {
+                        if finished.replace(true) {
+                            return;
+                        }
+                        queued.set(queued.get().saturating_sub(1));
+                        if !disposed.get() && queued.get() == 0 {
+                            set_speaking.set(false);
+                        }
+                    }
+                }) as Box<dyn Fn(web_sys::Event)>)
+                .into_js_value();
+                utterance.set_onstart(Some(on_start.unchecked_ref()));
+                utterance.set_onend(Some(on_end.unchecked_ref()));
+                utterance.set_onerror(Some(on_end.unchecked_ref()));
+
+                self.queued.set(self.queued.get() + 1);
+                synth.speak(&utterance);
+            } else {
+                _ = (options, self.set_speaking);
+            }
+        }
+    }
+
+    /// Pauses speaking, until [resume](Self::resume) is called.
+    pub fn pause(&self) {
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        if let Some(synth) = js::speech_synthesis() {
+            synth.pause();
+        }
+    }
+
+    /// Resumes speaking, if it was [paused](Self::pause).
+    pub fn resume(&self) {
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        if let Some(synth) = js::speech_synthesis() {
+            synth.resume();
+        }
+    }
+
+    /// Stops speaking, and drops anything else waiting to be spoken.
+    pub fn cancel(&self) {
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        if let Some(synth) = js::speech_synthesis() {
+            synth.cancel();
+        }
+    }
+}
+
+/// Speaks text aloud, using the [Web Speech API](https://developer.mozilla.org/en-US/docs/Web/API/SpeechSynthesis).
+/// This is useful for audio feedback, for example to make an app more accessible.
+///
+/// The browser loads its [voices](SpeechSynthesisState::voices) asynchronously, so the list
+/// is empty at first, and updates when they arrive. If anything passed to
+/// [speak](SpeechSynthesisState::speak) is still being spoken when the [Scope] is disposed,
+/// speaking is cancelled.
+///
+/// On the server, nothing is ever spoken, and the list of voices is always empty.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn ReadAloud(cx: Scope, text: String) -> Element {
+///   let speech = use_speech_synthesis(cx);
+///   let speaking = speech.speaking;
+///
+///   view! { cx,
+///     <button
+///       disabled=move || speaking.get()
+///       on:click=move |_| speech.speak(SpeechOptions {
+///         rate: 1.25,
+///         ..SpeechOptions::new(text.clone())
+///       })
+///     >
+///       "Read aloud"
+///     </button>
+///   }
+/// }
+/// ```
+pub fn use_speech_synthesis(cx: Scope) -> SpeechSynthesisState {
+    let (speaking, set_speaking) = create_signal(cx, false);
+    let (voices, set_voices) = create_signal(cx, Vec::new());
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    let queued = Rc::new(Cell::new(0));
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    let disposed = Rc::new(Cell::new(false));
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use crate::{use_event_listener, ListenerTarget};
+
+            if let Some(synth) = js::speech_synthesis() {
+                set_voices.set(js::voices(&synth));
+                use_event_listener(
+                    cx,
+                    ListenerTarget::Target(synth.clone().into()),
+                    "voiceschanged",
+                    {
+                        let synth = synth.clone();
+                        move |_| set_voices.set(js::voices(&synth))
+                    },
+                );
+
+                leptos_reactive::on_cleanup(cx, {
+                    let queued = Rc::clone(&queued);
+                    let disposed = Rc::clone(&disposed);
+                    move || {
+                        disposed.set(true);
+                        if queued.get() > 0 {
+                            synth.cancel();
+                        }
+                    }
+                });
+            }
+        } else {
+            _ = set_voices;
+        }
+    }
+
+    SpeechSynthesisState {
+        speaking,
+        voices,
+        set_speaking,
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        queued,
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        disposed,
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod js {
+    use super::Voice;
+    use wasm_bindgen::JsCast;
+
+    pub fn speech_synthesis() -> Option<web_sys::SpeechSynthesis> {
+        crate::window().speech_synthesis().ok()
+    }
+
+    pub fn voices(synth: &web_sys::SpeechSynthesis) -> Vec<Voice> {
+        synth
+            .get_voices()
+            .iter()
+            .map(|voice| {
+                let voice = voice.unchecked_into::<web_sys::SpeechSynthesisVoice>();
+                Voice {
+                    name: voice.name(),
+                    lang: voice.lang(),
+                    uri: voice.voice_uri(),
+                    local: voice.local_service(),
+                    default: voice.default(),
+                }
+            })
+            .collect()
+    }
+}
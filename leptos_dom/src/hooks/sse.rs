@@ -0,0 +1,124 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope};
+use serde::de::DeserializeOwned;
+
+/// The state of the connection opened by [use_sse].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventSourceStatus {
+    /// The connection is opening, either for the first time or after it was lost.
+    #[default]
+    Connecting,
+    /// The connection is open, so events are being received.
+    Open,
+    /// The connection has failed for good (for example, because the server responded with an
+    /// error) or was closed, and won’t be retried.
+    Closed,
+}
+
+/// Reactively receives [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events)
+/// from `url`, using an [EventSource](https://developer.mozilla.org/en-US/docs/Web/API/EventSource).
+///
+/// Returns a signal holding the data of the last event of the given `event_type` (`"message"`
+/// for events that don’t name their type), parsed as JSON, and a signal holding the status of
+/// the connection. Events whose data can’t be parsed as a `T` are ignored.
+///
+/// If the connection is lost, the browser reconnects on its own, waiting as long as the server
+/// asks it to (with the `retry` field). The connection is closed when the [Scope] is disposed.
+///
+/// On the server, no connection is opened, and the status is always [EventSourceStatus::Closed].
+///
+/// ```
+/// # use leptos::*;
+/// #[derive(serde::Deserialize, Clone)]
+/// struct Stats {
+///   visitors: u32,
+/// }
+///
+/// #[component]
+/// fn Dashboard(cx: Scope) -> Element {
+///   let (stats, status) = use_sse::<Stats>(cx, "/api/stats", "stats");
+///
+///   view! { cx,
+///     <div class:offline=move || status.get() != EventSourceStatus::Open>
+///       {move || stats.get().map(|stats| stats.visitors.to_string())}
+///     </div>
+///   }
+/// }
+/// ```
+pub fn use_sse<T>(
+    cx: Scope,
+    url: &str,
+    event_type: &str,
+) -> (ReadSignal<Option<T>>, ReadSignal<EventSourceStatus>)
+where
+    T: DeserializeOwned + 'static,
+{
+    let initial_status = if cfg!(any(feature = "csr", feature = "hydrate")) {
+        EventSourceStatus::Connecting
+    } else {
+        EventSourceStatus::Closed
+    };
+    let (data, set_data) = create_signal(cx, None);
+    let (status, set_status) = create_signal(cx, initial_status);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use wasm_bindgen::{closure::Closure, JsCast};
+
+            let source = match web_sys::EventSource::new(url) {
+                Ok(source) => source,
+                Err(e) => {
+                    crate::debug_warn!("[use_sse] {e:?}");
+                    set_status.set(EventSourceStatus::Closed);
+                    return (data, status);
+                }
+            };
+
+            let on_open = Closure::wrap(Box::new(move |_: web_sys::Event| {
+                set_status.set(EventSourceStatus::Open)
+            }) as Box<dyn Fn(web_sys::Event)>);
+
+            // the browser is either retrying, or has given up
+            let on_error = Closure::wrap(Box::new({
+                let source = source.clone();
+                move |_: web_sys::Event| {
+                    set_status.set(if source.ready_state() == web_sys::EventSource::CLOSED {
+                        EventSourceStatus::Closed
+                    } else {
+                        EventSourceStatus::Connecting
+                    })
+                }
+            }) as Box<dyn Fn(web_sys::Event)>);
+
+            let on_event = Closure::wrap(Box::new(move |event: web_sys::Event| {
+                let data = event.unchecked_into::<web_sys::MessageEvent>().data();
+                let Some(json) = data.as_string() else {
+                    return;
+                };
+                match serde_json::from_str(&json) {
+                    Ok(value) => set_data.set(Some(value)),
+                    Err(e) => crate::debug_warn!("[use_sse] {e}"),
+                }
+            }) as Box<dyn Fn(web_sys::Event)>);
+
+            let listeners = [
+                ("open".to_string(), on_open.into_js_value().unchecked_into::<js_sys::Function>()),
+                ("error".to_string(), on_error.into_js_value().unchecked_into()),
+                (event_type.to_string(), on_event.into_js_value().unchecked_into()),
+            ];
+            for (event_name, listener) in &listeners {
+                _ = source.add_event_listener_with_callback(event_name, listener);
+            }
+
+            leptos_reactive::on_cleanup(cx, move || {
+                for (event_name, listener) in &listeners {
+                    _ = source.remove_event_listener_with_callback(event_name, listener);
+                }
+                source.close();
+            });
+        } else {
+            _ = (url, event_type, set_data, set_status);
+        }
+    }
+
+    (data, status)
+}
@@ -0,0 +1,122 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope, WriteSignal};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Reactively reads and writes the value stored under `key` in
+/// [`localStorage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/localStorage) as
+/// JSON, upgrading it from an older format if needed.
+///
+/// The stored value is parsed as a `V2`. If that fails, it is parsed as a `V1` instead, passed
+/// through `migrate`, and the result is written back straight away, so the old format is only
+/// ever read once. A value that can’t be parsed as either is treated as missing.
+///
+/// Returns a signal holding the value (`None` if nothing is stored), and a setter. Whatever
+/// is set is written to storage as JSON, and setting `None` removes the key.
+///
+/// On the server, and when storage is unavailable (for example, because the user has
+/// blocked it), the value starts out as `None` and nothing is written.
+///
+/// ```
+/// # use leptos::*;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct SettingsV1 {
+///   dark: bool,
+/// }
+///
+/// #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+/// enum Theme {
+///   Light,
+///   Dark,
+///   System,
+/// }
+///
+/// #[derive(serde::Serialize, serde::Deserialize, Clone)]
+/// struct Settings {
+///   theme: Theme,
+/// }
+///
+/// #[component]
+/// fn ThemeToggle(cx: Scope) -> Element {
+///   let (settings, set_settings) =
+///     use_local_storage_migration(cx, "settings", |old: SettingsV1| Settings {
+///       theme: if old.dark { Theme::Dark } else { Theme::Light },
+///     });
+///   let dark = move || settings.with(|s| matches!(s, Some(s) if s.theme == Theme::Dark));
+///
+///   view! { cx,
+///     <button on:click=move |_| set_settings(Some(Settings { theme: Theme::Dark }))>
+///       {move || if dark() { "Dark" } else { "Light" }}
+///     </button>
+///   }
+/// }
+/// ```
+pub fn use_local_storage_migration<V1, V2>(
+    cx: Scope,
+    key: &str,
+    migrate: impl FnOnce(V1) -> V2,
+) -> (ReadSignal<Option<V2>>, WriteSignal<Option<V2>>)
+where
+    V1: DeserializeOwned,
+    V2: Serialize + DeserializeOwned + 'static,
+{
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let storage = crate::window().local_storage().ok().flatten();
+            let initial = storage.as_ref().and_then(|storage| {
+                let json = storage.get_item(key).ok()??;
+                if let Ok(value) = serde_json::from_str::<V2>(&json) {
+                    return Some(value);
+                }
+                match serde_json::from_str::<V1>(&json) {
+                    Ok(old) => {
+                        let value = migrate(old);
+                        write(storage, key, Some(&value));
+                        Some(value)
+                    }
+                    Err(e) => {
+                        crate::debug_warn!("[use_local_storage_migration] {key}: {e}");
+                        None
+                    }
+                }
+            });
+        } else {
+            _ = (key, migrate);
+            let initial = None;
+        }
+    }
+
+    let (value, set_value) = create_signal(cx, initial);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            if let Some(storage) = storage {
+                let key = key.to_string();
+                leptos_reactive::create_effect(cx, move |prev: Option<()>| {
+                    value.with(|value| {
+                        // the first run reads what is already stored
+                        if prev.is_some() {
+                            write(&storage, &key, value.as_ref());
+                        }
+                    })
+                });
+            }
+        }
+    }
+
+    (value, set_value)
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn write<T: Serialize>(storage: &web_sys::Storage, key: &str, value: Option<&T>) {
+    let result = match value.map(serde_json::to_string) {
+        Some(Ok(json)) => storage.set_item(key, &json),
+        Some(Err(e)) => {
+            crate::debug_warn!("[use_local_storage_migration] {key}: {e}");
+            return;
+        }
+        None => storage.remove_item(key),
+    };
+    // e.g., because the quota has been exceeded
+    if let Err(e) = result {
+        crate::debug_warn!("[use_local_storage_migration] {key}: {e:?}");
+    }
+}
@@ -0,0 +1,113 @@
+use leptos_reactive::Scope;
+
+/// Vibrates the device, using the [Vibration API](https://developer.mozilla.org/en-US/docs/Web/API/Vibration_API).
+///
+/// The `pattern` alternates between the number of milliseconds to vibrate for and the number
+/// of milliseconds to pause for, starting with a vibration: `&[200, 100, 200]` vibrates twice.
+/// Any vibration already in progress is replaced, and an empty pattern stops it. (Browsers
+/// only vibrate once the user has interacted with the page.)
+///
+/// On the server, and in browsers that can’t vibrate, this does nothing.
+///
+/// To stop vibrating when a component is unmounted, use [use_vibration] instead.
+pub fn vibrate(cx: Scope, pattern: &[u32]) {
+    _ = cx;
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            js::vibrate(pattern);
+        } else {
+            _ = pattern;
+        }
+    }
+}
+
+/// Stops any vibration in progress, using the [Vibration API](https://developer.mozilla.org/en-US/docs/Web/API/Vibration_API).
+///
+/// On the server, and in browsers that can’t vibrate, this does nothing.
+pub fn cancel_vibration(cx: Scope) {
+    vibrate(cx, &[]);
+}
+
+/// Vibrates the device, using the [Vibration API](https://developer.mozilla.org/en-US/docs/Web/API/Vibration_API).
+///
+/// Returns a function that vibrates in a pattern, as with [vibrate], and a function that stops
+/// vibrating, as with [cancel_vibration]. If a vibration started with the first function is
+/// still going when the [Scope] is disposed, it is stopped, and once the [Scope] is disposed
+/// neither function does anything.
+///
+/// On the server, and in browsers that can’t vibrate, neither function does anything.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn DeleteButton(cx: Scope) -> Element {
+///   let (vibrate, _cancel) = use_vibration(cx);
+///
+///   view! { cx,
+///     <button on:click=move |_| vibrate(&[50, 50, 50])>
+///       "Delete"
+///     </button>
+///   }
+/// }
+/// ```
+pub fn use_vibration(cx: Scope) -> (impl Fn(&[u32]) + Clone, impl Fn() + Clone) {
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use std::{cell::Cell, rc::Rc};
+
+            // when the last vibration started here ends, or `None` once the scope is disposed
+            let vibrating_until = Rc::new(Cell::new(Some(0.0)));
+
+            leptos_reactive::on_cleanup(cx, {
+                let vibrating_until = Rc::clone(&vibrating_until);
+                move || {
+                    if let Some(until) = vibrating_until.take() {
+                        if js_sys::Date::now() < until {
+                            js::vibrate(&[]);
+                        }
+                    }
+                }
+            });
+
+            let vibrate = {
+                let vibrating_until = Rc::clone(&vibrating_until);
+                move |pattern: &[u32]| {
+                    if vibrating_until.get().is_some() && js::vibrate(pattern) {
+                        let duration = pattern.iter().map(|ms| *ms as f64).sum::<f64>();
+                        vibrating_until.set(Some(js_sys::Date::now() + duration));
+                    }
+                }
+            };
+            let cancel = move || {
+                if vibrating_until.get().is_some() {
+                    js::vibrate(&[]);
+                }
+            };
+        } else {
+            _ = cx;
+            let vibrate = |_: &[u32]| {};
+            let cancel = || {};
+        }
+    }
+
+    (vibrate, cancel)
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod js {
+    use wasm_bindgen::JsValue;
+
+    // returns `false` if the browser can't vibrate, or refused to
+    pub fn vibrate(pattern: &[u32]) -> bool {
+        let navigator = crate::window().navigator();
+        // Safari and desktop Firefox don't have `navigator.vibrate` at all
+        if !js_sys::Reflect::has(&navigator, &JsValue::from_str("vibrate")).unwrap_or(false) {
+            return false;
+        }
+        let pattern = pattern
+            .iter()
+            .map(|ms| JsValue::from(*ms))
+            .collect::<js_sys::Array>();
+        navigator.vibrate_with_pattern(&pattern)
+    }
+}
@@ -0,0 +1,203 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope};
+
+/// The kind of wake lock to acquire with [use_wake_lock].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WakeLockType {
+    /// Keeps the screen from dimming or locking.
+    #[default]
+    Screen,
+}
+
+impl WakeLockType {
+    /// The name of the lock type in the [Screen Wake Lock API](https://developer.mozilla.org/en-US/docs/Web/API/Screen_Wake_Lock_API).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WakeLockType::Screen => "screen",
+        }
+    }
+}
+
+/// The reactive state of a wake lock, as returned by [use_wake_lock].
+#[derive(Copy, Clone, PartialEq)]
+pub struct WakeLockState {
+    /// `true` while the wake lock is held.
+    pub acquired: ReadSignal<bool>,
+    /// Why the wake lock couldn’t be acquired the last time it was requested, if it couldn’t:
+    /// for example, because the API isn’t available, or the battery is low.
+    pub error: ReadSignal<Option<String>>,
+}
+
+/// Keeps the screen awake while `active` is `true`, using the
+/// [Screen Wake Lock API](https://developer.mozilla.org/en-US/docs/Web/API/Screen_Wake_Lock_API).
+///
+/// The wake lock is released when `active` becomes `false`, and when the [Scope] is disposed.
+/// Browsers also release it whenever the page is hidden, for example when the user switches
+/// tabs: it is acquired again as soon as the page is visible, if `active` is still `true`.
+///
+/// On the server, the wake lock is never acquired.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Recipe(cx: Scope) -> Element {
+///   let (cooking, set_cooking) = create_signal(cx, false);
+///   let wake_lock = use_wake_lock(cx, WakeLockType::Screen, cooking);
+///
+///   view! { cx,
+///     <div>
+///       <label>
+///         <input type="checkbox" on:change=move |ev| set_cooking(event_target_checked(&ev))/>
+///         "Keep the screen on"
+///       </label>
+///       <p>{move || wake_lock.error.get().unwrap_or_default()}</p>
+///     </div>
+///   }
+/// }
+/// ```
+pub fn use_wake_lock(
+    cx: Scope,
+    lock_type: WakeLockType,
+    active: ReadSignal<bool>,
+) -> WakeLockState {
+    let (acquired, set_acquired) = create_signal(cx, false);
+    let (error, set_error) = create_signal(cx, None);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            use crate::{document, use_event_listener, ListenerTarget};
+            use leptos_reactive::{create_effect, on_cleanup, UntrackedGettableSignal};
+            use std::{cell::{Cell, RefCell}, rc::Rc};
+
+            let sentinel = Rc::new(RefCell::new(None::<js::WakeLockSentinel>));
+            let pending = Rc::new(Cell::new(false));
+            // bumped whenever the lock is released, so requests still in flight are dropped
+            let generation = Rc::new(Cell::new(0_usize));
+
+            let acquire = {
+                let sentinel = Rc::clone(&sentinel);
+                let generation = Rc::clone(&generation);
+                move || {
+                    // browsers refuse wake locks for hidden pages
+                    if sentinel.borrow().is_some() || pending.get() || document().hidden() {
+                        return;
+                    }
+                    pending.set(true);
+
+                    let sentinel = Rc::clone(&sentinel);
+                    let pending = Rc::clone(&pending);
+                    let generation = Rc::clone(&generation);
+                    let requested_in = generation.get();
+                    leptos_reactive::spawn_local(async move {
+                        let result = js::request(lock_type.as_str()).await;
+                        pending.set(false);
+                        if generation.get() != requested_in {
+                            if let Ok(lock) = result {
+                                _ = lock.release();
+                            }
+                            return;
+                        }
+
+                        match result {
+                            Ok(lock) => {
+                                let on_release = wasm_bindgen::closure::Closure::wrap(Box::new({
+                                    let sentinel = Rc::clone(&sentinel);
+                                    move |_: web_sys::Event| {
+                                        sentinel.borrow_mut().take();
+                                        set_acquired.set(false);
+                                    }
+                                }) as Box<dyn Fn(web_sys::Event)>);
+                                lock.set_onrelease(Some(wasm_bindgen::JsCast::unchecked_ref(
+                                    &on_release.into_js_value(),
+                                )));
+                                *sentinel.borrow_mut() = Some(lock);
+                                set_error.set(None);
+                                set_acquired.set(true);
+                            }
+                            Err(e) => set_error.set(Some(e)),
+                        }
+                    });
+                }
+            };
+
+            let release = move || {
+                generation.set(generation.get() + 1);
+                if let Some(lock) = sentinel.borrow_mut().take() {
+                    lock.set_onrelease(None);
+                    _ = lock.release();
+                }
+                set_acquired.set(false);
+            };
+
+            create_effect(cx, {
+                let acquire = acquire.clone();
+                let release = release.clone();
+                move |_| {
+                    if active.get() {
+                        acquire();
+                    } else {
+                        release();
+                    }
+                }
+            });
+
+            use_event_listener(cx, ListenerTarget::Document, "visibilitychange", move |_| {
+                if !document().hidden() && active.get_untracked() {
+                    acquire();
+                }
+            });
+
+            on_cleanup(cx, release);
+        } else {
+            _ = (lock_type, active, set_acquired, set_error);
+        }
+    }
+
+    WakeLockState { acquired, error }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod js {
+    use wasm_bindgen::{prelude::*, JsCast};
+    use wasm_bindgen_futures::JsFuture;
+
+    // the Screen Wake Lock API is only included in `web_sys` behind `web_sys_unstable_apis`
+    #[wasm_bindgen]
+    extern "C" {
+        type WakeLock;
+
+        #[wasm_bindgen(catch, method)]
+        fn request(this: &WakeLock, type_: &str) -> Result<js_sys::Promise, JsValue>;
+
+        pub type WakeLockSentinel;
+
+        #[wasm_bindgen(catch, method)]
+        pub fn release(this: &WakeLockSentinel) -> Result<js_sys::Promise, JsValue>;
+
+        #[wasm_bindgen(method, setter)]
+        pub fn set_onrelease(this: &WakeLockSentinel, handler: Option<&js_sys::Function>);
+    }
+
+    pub async fn request(type_: &str) -> Result<WakeLockSentinel, String> {
+        let wake_lock =
+            js_sys::Reflect::get(&crate::window().navigator(), &JsValue::from_str("wakeLock"))
+                .map_err(message)?;
+        if wake_lock.is_undefined() {
+            return Err("the Screen Wake Lock API isn’t available".to_string());
+        }
+        let promise = wake_lock
+            .unchecked_into::<WakeLock>()
+            .request(type_)
+            .map_err(message)?;
+        JsFuture::from(promise)
+            .await
+            .map(JsCast::unchecked_into)
+            .map_err(message)
+    }
+
+    fn message(e: JsValue) -> String {
+        match e.dyn_ref::<js_sys::Error>() {
+            Some(e) => String::from(e.message()),
+            None => format!("{e:?}"),
+        }
+    }
+}
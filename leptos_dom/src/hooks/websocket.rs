@@ -0,0 +1,288 @@
+use leptos_reactive::{create_signal, ReadSignal, Scope, WriteSignal};
+use std::time::Duration;
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+/// How long to wait before the first attempt to reconnect after the connection drops.
+/// Each failed attempt doubles the delay, up to [MAX_RECONNECT_DELAY].
+pub const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// The longest [use_websocket] waits between attempts to reconnect.
+pub const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// The state of the connection opened by [use_websocket].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WsStatus {
+    /// The socket is opening, either for the first time or to reconnect.
+    #[default]
+    Connecting,
+    /// The socket is open, so messages can be sent and received.
+    Connected,
+    /// The socket has closed. Unless it was closed with [WebSocketState::close], it will
+    /// try to reconnect shortly.
+    Disconnected,
+    /// The connection failed. It will try to reconnect shortly.
+    Error,
+}
+
+/// The reactive state of a [WebSocket](https://developer.mozilla.org/en-US/docs/Web/API/WebSocket),
+/// as returned by [use_websocket].
+#[derive(Clone)]
+pub struct WebSocketState {
+    /// The state of the connection.
+    pub status: ReadSignal<WsStatus>,
+    /// The last text message received, if any.
+    pub message: ReadSignal<Option<String>>,
+    /// The last binary message received, if any.
+    pub message_bytes: ReadSignal<Option<Vec<u8>>>,
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    connection: Rc<Connection>,
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+struct Connection {
+    url: String,
+    socket: RefCell<Option<web_sys::WebSocket>>,
+    // the number of attempts to reconnect since the socket was last open
+    attempts: Cell<u32>,
+    // set once the socket is closed on purpose, so it isn’t reopened
+    closed: Cell<bool>,
+    status: ReadSignal<WsStatus>,
+    set_status: WriteSignal<WsStatus>,
+    set_message: WriteSignal<Option<String>>,
+    set_message_bytes: WriteSignal<Option<Vec<u8>>>,
+}
+
+impl WebSocketState {
+    /// Sends a text message. Messages sent while the socket isn’t [connected](WsStatus::Connected)
+    /// are dropped.
+    pub fn send(&self, message: &str) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                if let Some(socket) = self.connection.open_socket() {
+                    if let Err(e) = socket.send_with_str(message) {
+                        crate::debug_warn!("[use_websocket] {e:?}");
+                    }
+                }
+            } else {
+                _ = message;
+            }
+        }
+    }
+
+    /// Sends a binary message. Messages sent while the socket isn’t [connected](WsStatus::Connected)
+    /// are dropped.
+    pub fn send_bytes(&self, message: &[u8]) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                if let Some(socket) = self.connection.open_socket() {
+                    if let Err(e) = socket.send_with_u8_array(message) {
+                        crate::debug_warn!("[use_websocket] {e:?}");
+                    }
+                }
+            } else {
+                _ = message;
+            }
+        }
+    }
+
+    /// Closes the socket. It won’t reconnect after this.
+    pub fn close(&self) {
+        cfg_if::cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                self.connection.closed.set(true);
+                match self.connection.socket.borrow().as_ref() {
+                    // the status changes once the socket’s `close` event fires
+                    Some(socket) => _ = socket.close(),
+                    // waiting to reconnect
+                    None => self.connection.set_status.set(WsStatus::Disconnected),
+                }
+            }
+        }
+    }
+}
+
+/// Opens a [WebSocket](https://developer.mozilla.org/en-US/docs/Web/API/WebSocket) to `url`,
+/// and reactively tracks its status and the last message it received.
+///
+/// If the connection drops without being closed by [WebSocketState::close], it reconnects,
+/// waiting [INITIAL_RECONNECT_DELAY] before the first attempt and twice as long after each
+/// failed attempt, up to [MAX_RECONNECT_DELAY]. The socket is closed when the [Scope] is
+/// disposed.
+///
+/// On the server, no socket is opened and the status is always [WsStatus::Disconnected].
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Chat(cx: Scope) -> Element {
+///   let socket = use_websocket(cx, "wss://example.com/chat");
+///   let connected = move || socket.status.get() == WsStatus::Connected;
+///
+///   view! { cx,
+///     <div>
+///       <p>{move || socket.message.get().unwrap_or_default()}</p>
+///       <button
+///         disabled=move || !connected()
+///         on:click={
+///           let socket = socket.clone();
+///           move |_| socket.send("Hello!")
+///         }
+///       >
+///         "Say hello"
+///       </button>
+///     </div>
+///   }
+/// }
+/// ```
+pub fn use_websocket(cx: Scope, url: &str) -> WebSocketState {
+    let initial_status = if cfg!(any(feature = "csr", feature = "hydrate")) {
+        WsStatus::Connecting
+    } else {
+        WsStatus::Disconnected
+    };
+    let (status, set_status) = create_signal(cx, initial_status);
+    let (message, set_message) = create_signal(cx, None);
+    let (message_bytes, set_message_bytes) = create_signal(cx, None);
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let connection = Rc::new(Connection {
+                url: url.to_string(),
+                socket: Default::default(),
+                attempts: Cell::new(0),
+                closed: Cell::new(false),
+                status,
+                set_status,
+                set_message,
+                set_message_bytes,
+            });
+            connect(&connection);
+
+            leptos_reactive::on_cleanup(cx, {
+                let connection = Rc::clone(&connection);
+                move || {
+                    connection.closed.set(true);
+                    if let Some(socket) = connection.socket.take() {
+                        socket.set_onopen(None);
+                        socket.set_onmessage(None);
+                        socket.set_onerror(None);
+                        socket.set_onclose(None);
+                        _ = socket.close();
+                    }
+                }
+            });
+        } else {
+            _ = (url, set_status, set_message, set_message_bytes);
+        }
+    }
+
+    WebSocketState {
+        status,
+        message,
+        message_bytes,
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        connection,
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+impl Connection {
+    fn open_socket(&self) -> Option<web_sys::WebSocket> {
+        let socket = self.socket.borrow().clone();
+        match socket {
+            Some(socket) if socket.ready_state() == web_sys::WebSocket::OPEN => Some(socket),
+            _ => {
+                crate::debug_warn!(
+                    "[use_websocket] tried to send a message while the socket isn’t open"
+                );
+                None
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn connect(connection: &Rc<Connection>) {
+    use leptos_reactive::UntrackedGettableSignal;
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    connection.set_status.set(WsStatus::Connecting);
+    let socket = match web_sys::WebSocket::new(&connection.url) {
+        Ok(socket) => socket,
+        Err(e) => {
+            // an invalid URL won’t get any better by retrying
+            crate::debug_warn!("[use_websocket] {e:?}");
+            connection.set_status.set(WsStatus::Error);
+            return;
+        }
+    };
+    socket.set_binary_type(web_sys::BinaryType::Arraybuffer);
+
+    // the handlers only hold weak references, so they don’t keep the connection alive
+    // once the scope has been disposed
+    let handler = |f: Box<dyn Fn(Rc<Connection>, web_sys::Event)>| {
+        let connection = Rc::downgrade(connection);
+        let handler = Closure::wrap(Box::new(move |event: web_sys::Event| {
+            if let Some(connection) = connection.upgrade() {
+                f(connection, event);
+            }
+        }) as Box<dyn Fn(web_sys::Event)>);
+        handler.into_js_value().unchecked_into::<js_sys::Function>()
+    };
+
+    socket.set_onopen(Some(&handler(Box::new(|connection, _| {
+        connection.attempts.set(0);
+        connection.set_status.set(WsStatus::Connected);
+    }))));
+
+    socket.set_onmessage(Some(&handler(Box::new(|connection, event| {
+        let data = event.unchecked_into::<web_sys::MessageEvent>().data();
+        if let Some(text) = data.as_string() {
+            connection.set_message.set(Some(text));
+        } else if let Some(buffer) = data.dyn_ref::<js_sys::ArrayBuffer>() {
+            let bytes = js_sys::Uint8Array::new(buffer).to_vec();
+            connection.set_message_bytes.set(Some(bytes));
+        }
+    }))));
+
+    socket.set_onerror(Some(&handler(Box::new(|connection, _| {
+        connection.set_status.set(WsStatus::Error);
+    }))));
+
+    // browsers always follow `error` with `close`, so reconnecting is only handled here
+    socket.set_onclose(Some(&handler(Box::new(|connection, _| {
+        connection.socket.take();
+        if connection.closed.get() {
+            connection.set_status.set(WsStatus::Disconnected);
+            return;
+        }
+
+        // keep showing the error, if there was one, until the next attempt
+        if connection.status.get_untracked() != WsStatus::Error {
+            connection.set_status.set(WsStatus::Disconnected);
+        }
+        let attempt = connection.attempts.get();
+        connection.attempts.set(attempt.saturating_add(1));
+        let delay = INITIAL_RECONNECT_DELAY
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_RECONNECT_DELAY);
+
+        let connection = Rc::downgrade(&connection);
+        crate::set_timeout(
+            move || {
+                if let Some(connection) = connection.upgrade() {
+                    if !connection.closed.get() {
+                        connect(&connection);
+                    }
+                }
+            },
+            delay,
+        );
+    }))));
+
+    *connection.socket.borrow_mut() = Some(socket);
+}
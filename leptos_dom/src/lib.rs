@@ -21,12 +21,18 @@ use cfg_if::cfg_if;
 mod attribute;
 mod child;
 mod class;
+mod escape;
 mod event_delegation;
+mod event_handler;
+mod foreign;
+mod hooks;
 mod logging;
 mod mount;
 mod node_ref;
 mod operations;
 mod property;
+mod stateful;
+mod stats;
 
 cfg_if! {
     // can only include this if we're *only* enabling SSR, as it's the lowest-priority feature
@@ -42,9 +48,15 @@ cfg_if! {
 
         mod render_to_string;
         pub use render_to_string::*;
+        mod controlled;
+        mod detached;
+        mod focus;
         mod reconcile;
         mod render;
+        mod write_batch;
 
+        pub use detached::*;
+        pub use focus::*;
         pub use reconcile::*;
         pub use render::*;
     } else if #[cfg(not(any(feature = "hydrate", feature = "csr")))] {
@@ -70,9 +82,15 @@ cfg_if! {
         /// this is a DOM `Node`.
         pub type Node = web_sys::Node;
 
+        mod controlled;
+        mod detached;
+        mod focus;
         mod reconcile;
         mod render;
+        mod write_batch;
 
+        pub use detached::*;
+        pub use focus::*;
         pub use reconcile::*;
         pub use render::*;
     }
@@ -81,11 +99,17 @@ cfg_if! {
 pub use attribute::*;
 pub use child::*;
 pub use class::*;
+pub use escape::*;
+pub use event_handler::*;
+pub use foreign::*;
+pub use hooks::*;
 pub use logging::*;
 pub use mount::*;
 pub use node_ref::*;
 pub use operations::*;
 pub use property::*;
+pub use stateful::*;
+pub use stats::*;
 
 pub use js_sys;
 pub use wasm_bindgen;
@@ -109,6 +133,49 @@ where
     }
 }
 
+// Hidden because these are only used by the `view` macro, as
+// `(&ComponentProps::new(props)).mount(cx, Component)`: in strict mode, a component is
+// mounted, unmounted, and mounted again (see `Scope::run_component`), which is only possible
+// if its props can be cloned, so `MountComponent` is picked over `MountComponentOnce` when
+// they can be.
+#[doc(hidden)]
+pub struct ComponentProps<P>(std::cell::Cell<Option<P>>);
+
+impl<P> ComponentProps<P> {
+    pub fn new(props: P) -> Self {
+        Self(std::cell::Cell::new(Some(props)))
+    }
+
+    fn take(&self) -> P {
+        self.0
+            .take()
+            .expect("a component's props can only be used to mount it once")
+    }
+}
+
+#[doc(hidden)]
+pub trait MountComponent<P> {
+    fn mount<T>(&self, cx: Scope, component: impl Fn(Scope, P) -> T) -> T;
+}
+
+impl<P: Clone> MountComponent<P> for ComponentProps<P> {
+    fn mount<T>(&self, cx: Scope, component: impl Fn(Scope, P) -> T) -> T {
+        let props = self.take();
+        cx.run_component(|| component(cx, props.clone()))
+    }
+}
+
+#[doc(hidden)]
+pub trait MountComponentOnce<P> {
+    fn mount<T>(&self, cx: Scope, component: impl Fn(Scope, P) -> T) -> T;
+}
+
+impl<P> MountComponentOnce<P> for &ComponentProps<P> {
+    fn mount<T>(&self, cx: Scope, component: impl Fn(Scope, P) -> T) -> T {
+        component(cx, self.take())
+    }
+}
+
 /// Shorthand to test for whether an `ssr` feature is enabled.
 ///
 /// In the past, this was implemented by checking whether `not(target_arch = "wasm32")`.
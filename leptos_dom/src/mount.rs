@@ -84,6 +84,9 @@ where
 /// while reusing the existing DOM nodes, by running the given function beginning with
 /// the parent node.
 ///
+/// The parent can also be the `<div data-island>` around an island rendered on the server with
+/// `<IslandRoot/>`, to hydrate just that island.
+///
 /// ```
 /// // rehydrate a very simple Leptos application
 /// # use leptos_dom::*; use leptos_dom::wasm_bindgen::JsCast;
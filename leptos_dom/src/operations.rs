@@ -3,7 +3,7 @@ use std::time::Duration;
 use wasm_bindgen::convert::FromWasmAbi;
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
 
-use crate::{debug_warn, event_delegation, is_server};
+use crate::{debug_warn, event_delegation, is_server, stats};
 
 thread_local! {
     pub(crate) static WINDOW: web_sys::Window = web_sys::window().unwrap_throw();
@@ -35,12 +35,14 @@ pub fn body() -> Option<web_sys::HtmlElement> {
 /// Creates a DOM [`Element`](https://developer.mozilla.org/en-US/docs/Web/API/Element). See
 /// [`Document.createElement`](https://developer.mozilla.org/en-US/docs/Web/API/Document/createElement).
 pub fn create_element(tag_name: &str) -> web_sys::Element {
+    stats::count_created();
     document().create_element(tag_name).unwrap_throw()
 }
 
 /// Creates a DOM [`Text`](https://developer.mozilla.org/en-US/docs/Web/API/Text) node. See
 /// [`Document.createTextNode`](https://developer.mozilla.org/en-US/docs/Web/API/Document/createTextNode).
 pub fn create_text_node(data: &str) -> web_sys::Text {
+    stats::count_created();
     document().create_text_node(data)
 }
 
@@ -53,13 +55,15 @@ pub fn create_fragment() -> web_sys::DocumentFragment {
 /// Creates a [`Comment`](https://developer.mozilla.org/en-US/docs/Web/API/Comment) node.
 /// See [`Document.createCommentNode`](https://developer.mozilla.org/en-US/docs/Web/API/Document/createComment).
 pub fn create_comment_node() -> web_sys::Node {
+    stats::count_created();
     document().create_comment("").unchecked_into()
 }
 
 /// Creates an [`HTMLTemplateElement`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLTemplateElement)
 /// and sets its `innerHTML` to the given HTML string.
 pub fn create_template(html: &str) -> web_sys::HtmlTemplateElement {
-    let template = create_element("template");
+    // the template itself is never mounted, so it isn't counted in `stats()`
+    let template = document().create_element("template").unwrap_throw();
     template.set_inner_html(html);
     template.unchecked_into()
 }
@@ -67,13 +71,14 @@ pub fn create_template(html: &str) -> web_sys::HtmlTemplateElement {
 /// Clones an an [`HTMLTemplateElement`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLTemplateElement)
 /// and returns its first element child.
 pub fn clone_template(template: &web_sys::HtmlTemplateElement) -> web_sys::Element {
-    template
+    let root = template
         .content()
         .first_element_child()
         .unwrap_throw()
         .clone_node_with_deep(true)
-        .unwrap_throw()
-        .unchecked_into()
+        .unwrap_throw();
+    stats::count_cloned(&root);
+    root.unchecked_into()
 }
 
 /// Appends a child node to the parent element.
@@ -131,14 +136,37 @@ pub fn set_data(node: &web_sys::Text, value: &str) {
 
 /// Sets the value of an attribute on a DOM element.
 /// See [`Element.setAttribute`](https://developer.mozilla.org/en-US/docs/Web/API/Element/setAttribute).
+///
+/// Attributes with an `xlink:` or `xml:` prefix, like SVG’s `xlink:href`, are set in the XLink or
+/// XML namespace, with [`Element.setAttributeNS`](https://developer.mozilla.org/en-US/docs/Web/API/Element/setAttributeNS).
 pub fn set_attribute(el: &web_sys::Element, attr_name: &str, value: &str) {
-    _ = el.set_attribute(attr_name, value);
+    match attribute_namespace(attr_name) {
+        Some((namespace, _)) => _ = el.set_attribute_ns(Some(namespace), attr_name, value),
+        None => _ = el.set_attribute(attr_name, value),
+    }
 }
 
 /// Removes an attribute from a DOM element.
 /// See [`Element.removeAttribute`](https://developer.mozilla.org/en-US/docs/Web/API/Element/removeAttribute).
+///
+/// Attributes with an `xlink:` or `xml:` prefix are removed from the XLink or XML namespace,
+/// as with [set_attribute].
 pub fn remove_attribute(el: &web_sys::Element, attr_name: &str) {
-    _ = el.remove_attribute(attr_name);
+    match attribute_namespace(attr_name) {
+        Some((namespace, local_name)) => _ = el.remove_attribute_ns(Some(namespace), local_name),
+        None => _ = el.remove_attribute(attr_name),
+    }
+}
+
+// the namespace and local name of an attribute whose prefix has a namespace in HTML documents
+fn attribute_namespace(attr_name: &str) -> Option<(&'static str, &str)> {
+    let (prefix, local_name) = attr_name.split_once(':')?;
+    let namespace = match prefix {
+        "xlink" => "http://www.w3.org/1999/xlink",
+        "xml" => "http://www.w3.org/XML/1998/namespace",
+        _ => return None,
+    };
+    Some((namespace, local_name))
 }
 
 /// Sets a property on a DOM element.
@@ -220,7 +248,14 @@ pub fn request_idle_callback(cb: impl Fn() + 'static) {
 
 /// Executes the given function after the given duration of time has passed.
 /// [`setTimeout()`](https://developer.mozilla.org/en-US/docs/Web/API/setTimeout).
+///
+/// With the `testing` feature, this uses the virtual clock of the current `TestRuntime`, if any.
 pub fn set_timeout(cb: impl FnOnce() + 'static, duration: Duration) {
+    #[cfg(feature = "testing")]
+    if leptos_reactive::testing::is_active() {
+        return leptos_reactive::testing::set_timeout(cb, duration);
+    }
+
     let cb = Closure::once_into_js(Box::new(cb) as Box<dyn FnOnce()>);
     _ = window().set_timeout_with_callback_and_timeout_and_arguments_0(
         cb.as_ref().unchecked_ref(),
@@ -236,16 +271,29 @@ impl IntervalHandle {
     /// Cancels the repeating event to which this refers.
     /// See [`clearInterval()`](https://developer.mozilla.org/en-US/docs/Web/API/clearInterval)
     pub fn clear(&self) {
+        #[cfg(feature = "testing")]
+        if leptos_reactive::testing::is_active() {
+            return leptos_reactive::testing::clear_interval(self.0 as usize);
+        }
+
         window().clear_interval_with_handle(self.0);
     }
 }
 
 /// Repeatedly calls the given function, with a delay of the given duration between calls.
 /// See [`setInterval()`](https://developer.mozilla.org/en-US/docs/Web/API/setInterval).
+///
+/// With the `testing` feature, this uses the virtual clock of the current `TestRuntime`, if any.
 pub fn set_interval(
     cb: impl Fn() + 'static,
     duration: Duration,
 ) -> Result<IntervalHandle, JsValue> {
+    #[cfg(feature = "testing")]
+    if leptos_reactive::testing::is_active() {
+        let id = leptos_reactive::testing::set_interval(cb, duration);
+        return Ok(IntervalHandle(id as i32));
+    }
+
     let cb = Closure::wrap(Box::new(cb) as Box<dyn Fn()>).into_js_value();
     let handle = window().set_interval_with_callback_and_timeout_and_arguments_0(
         cb.as_ref().unchecked_ref(),
@@ -262,10 +310,39 @@ pub fn add_event_listener<E>(
 ) where
     E: FromWasmAbi + 'static,
 {
-    let cb = Closure::wrap(Box::new(cb) as Box<dyn FnMut(E)>).into_js_value();
-    let key = event_delegation::event_delegation_key(event_name);
-    _ = js_sys::Reflect::set(target, &JsValue::from_str(&key), &cb);
-    event_delegation::add_event_listener(event_name);
+    add_event_listener_with_options(target, event_name, cb, EventOptions::default())
+}
+
+/// Adds an event listener to the target DOM element using implicit event delegation,
+/// with the given [EventOptions].
+///
+/// Each distinct combination of `passive` and `capture` gets its own delegated listener on
+/// the `Window`. A `once` listener is removed from the element after it first runs.
+///
+/// In the [`view`](https://docs.rs/leptos_macro/latest/leptos_macro/macro.view.html) macro,
+/// options can be added to an event listener as modifiers, like `on:wheel:passive` or `on:click:once`.
+pub fn add_event_listener_with_options<E>(
+    target: &web_sys::Element,
+    event_name: &'static str,
+    mut cb: impl FnMut(E) + 'static,
+    options: EventOptions,
+) where
+    E: FromWasmAbi + 'static,
+{
+    let key = JsValue::from_str(&event_delegation::event_delegation_key(event_name, options));
+    let cb = if options.once {
+        let target = target.clone();
+        let key = key.clone();
+        Closure::wrap(Box::new(move |ev| {
+            _ = js_sys::Reflect::delete_property(&target, &key);
+            cb(ev)
+        }) as Box<dyn FnMut(E)>)
+    } else {
+        Closure::wrap(Box::new(cb) as Box<dyn FnMut(E)>)
+    }
+    .into_js_value();
+    _ = js_sys::Reflect::set(target, &key, &cb);
+    event_delegation::add_event_listener(event_name, options);
 }
 
 #[doc(hidden)]
@@ -275,9 +352,94 @@ pub fn add_event_listener_undelegated<E>(
     cb: impl FnMut(E) + 'static,
 ) where
     E: FromWasmAbi + 'static,
+{
+    add_event_listener_undelegated_with_options(target, event_name, cb, EventOptions::default())
+}
+
+#[doc(hidden)]
+pub fn add_event_listener_undelegated_with_options<E>(
+    target: &web_sys::Element,
+    event_name: &'static str,
+    cb: impl FnMut(E) + 'static,
+    options: EventOptions,
+) where
+    E: FromWasmAbi + 'static,
 {
     let cb = Closure::wrap(Box::new(cb) as Box<dyn FnMut(E)>).into_js_value();
-    _ = target.add_event_listener_with_callback(event_name, cb.unchecked_ref());
+    _ = target.add_event_listener_with_callback_and_add_event_listener_options(
+        event_name,
+        cb.unchecked_ref(),
+        &options.to_add_event_listener_options(),
+    );
+}
+
+/// Options for an event listener, as passed to
+/// [`addEventListener()`](https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/addEventListener).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventOptions {
+    /// The listener will never call `preventDefault()`, so the browser can start scrolling
+    /// without waiting for it. Use this for `touchmove` and `wheel` listeners.
+    pub passive: bool,
+    /// The listener runs during the capture phase, i.e., before listeners on elements inside this one.
+    pub capture: bool,
+    /// The listener is removed after it runs for the first time.
+    pub once: bool,
+}
+
+impl EventOptions {
+    pub(crate) fn to_add_event_listener_options(self) -> web_sys::AddEventListenerOptions {
+        let options = web_sys::AddEventListenerOptions::new();
+        options.set_passive(self.passive);
+        options.set_capture(self.capture);
+        options.set_once(self.once);
+        options
+    }
+}
+
+/// Wraps an event handler so that it calls
+/// [`preventDefault()`](https://developer.mozilla.org/en-US/docs/Web/API/Event/preventDefault)
+/// on the event before running.
+///
+/// In the [`view`](https://docs.rs/leptos_macro/latest/leptos_macro/macro.view.html) macro,
+/// the `:prevent` modifier does the same thing: `on:submit:prevent=|ev| ...`.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// view! { cx,
+///   <form on:submit=prevent_default(|ev: web_sys::SubmitEvent| log!("submitted!"))>
+///     <button type="submit">"Submit"</button>
+///   </form>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+pub fn prevent_default<E>(mut handler: impl FnMut(E)) -> impl FnMut(E)
+where
+    E: AsRef<web_sys::Event>,
+{
+    move |ev: E| {
+        ev.as_ref().prevent_default();
+        handler(ev)
+    }
+}
+
+/// Wraps an event handler so that it calls
+/// [`stopPropagation()`](https://developer.mozilla.org/en-US/docs/Web/API/Event/stopPropagation)
+/// on the event before running, so that it isn’t handled by any parent elements.
+///
+/// In the [`view`](https://docs.rs/leptos_macro/latest/leptos_macro/macro.view.html) macro,
+/// the `:stop` modifier does the same thing: `on:click:stop=|ev| ...`.
+pub fn stop_propagation<E>(mut handler: impl FnMut(E)) -> impl FnMut(E)
+where
+    E: AsRef<web_sys::Event>,
+{
+    move |ev: E| {
+        ev.as_ref().stop_propagation();
+        handler(ev)
+    }
 }
 
 #[doc(hidden)]
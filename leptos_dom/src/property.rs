@@ -32,6 +32,17 @@ where
     }
 }
 
+// with the `stable` feature, signals can’t be called like closures, so they need their own impl
+#[cfg(feature = "stable")]
+impl<T> IntoProperty for leptos_reactive::Signal<T>
+where
+    T: Into<JsValue> + Clone,
+{
+    fn into_property(self, cx: Scope) -> Property {
+        (move || self.get()).into_property(cx)
+    }
+}
+
 macro_rules! prop_type {
     ($prop_type:ty) => {
         impl IntoProperty for $prop_type {
@@ -67,3 +78,40 @@ prop_type!(i128);
 prop_type!(f32);
 prop_type!(f64);
 prop_type!(bool);
+
+// Hidden because this is only used by the `view` macro, which reflects some properties as
+// attributes when rendering on the server. Calling `reflect_as_attribute` on a
+// `&PropertyReflection` uses `ReflectAsAttribute` if the value can be an attribute, and falls
+// back to `ReflectNothing` (which renders nothing) for values like a `JsValue` that can’t.
+#[doc(hidden)]
+pub struct PropertyReflection<T>(pub std::cell::Cell<Option<T>>);
+
+#[doc(hidden)]
+pub trait ReflectAsAttribute {
+    fn reflect_as_attribute(&self, cx: Scope, name: &'static str) -> String;
+}
+
+impl<T: crate::IntoAttribute> ReflectAsAttribute for PropertyReflection<T> {
+    fn reflect_as_attribute(&self, cx: Scope, name: &'static str) -> String {
+        let attr = match self.0.take() {
+            Some(value) => value.into_attribute(cx).as_value_string(name),
+            None => String::new(),
+        };
+        if attr.is_empty() {
+            attr
+        } else {
+            format!(" {attr}")
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait ReflectNothing {
+    fn reflect_as_attribute(&self, cx: Scope, name: &'static str) -> String;
+}
+
+impl<T> ReflectNothing for &PropertyReflection<T> {
+    fn reflect_as_attribute(&self, _cx: Scope, _name: &'static str) -> String {
+        String::new()
+    }
+}
@@ -1,10 +1,17 @@
-use leptos_reactive::{create_render_effect, Scope};
+use leptos_reactive::{create_render_effect, RwSignal, Scope};
 use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
 
 use crate::{
-    append_child, create_text_node, debug_warn, insert_before, reconcile::reconcile_arrays,
-    remove_attribute, remove_child, replace_child, replace_with, set_attribute, Attribute, Child,
-    Class, Property,
+    append_child,
+    controlled::{is_controlled, set_property, track_composition},
+    create_text_node, debug_warn,
+    focus::FocusSnapshot,
+    insert_before,
+    reconcile::reconcile_arrays,
+    remove_attribute, remove_child, replace_child, replace_with, set_attribute, strip_nulls,
+    use_event_listener,
+    write_batch::{write, Write},
+    Attribute, Child, Class, IntoProperty, ListenerTarget, Property,
 };
 
 /// Marks the node relative to which an operation should occur.
@@ -45,25 +52,43 @@ impl std::fmt::Debug for Marker {
 /// it will [create an effect](leptos_reactive::create_effect) to make fine-grained reactive updates
 /// to the attribute value.
 ///
+/// While hydrating, attributes the browser or the user may already have acted on, like
+/// `autofocus` or `open`, aren’t written, and are only updated once the value changes (see
+/// [add_stateful_attribute](crate::add_stateful_attribute)).
+///
 /// This is used by the [`view`](https://docs.rs/leptos_macro/latest/leptos_macro/macro.view.html) macro.
 /// You usually won't need to interact with it directly.
 pub fn attribute(cx: Scope, el: &web_sys::Element, attr_name: &'static str, value: Attribute) {
+    let keep_live = keeps_live_state(cx, attr_name);
     match value {
         Attribute::Fn(f) => {
             let el = el.clone();
             create_render_effect(cx, move |old| {
                 let new = f();
-                if old.as_ref() != Some(&new) {
-                    attribute_expression(&el, attr_name, new.clone());
+                if old.as_ref() != Some(&new) && !(old.is_none() && keep_live) {
+                    write(cx, &el, Write::Attribute(attr_name, new.clone()));
+                }
+                new
+            });
+        }
+        // a single effect updates the whole value when any of its parts change
+        Attribute::Composite(composite) => {
+            let el = el.clone();
+            create_render_effect(cx, move |old| {
+                let new = composite.value();
+                if old.as_ref() != Some(&new) && !(old.is_none() && keep_live) {
+                    let value = Attribute::String(new.clone());
+                    write(cx, &el, Write::Attribute(attr_name, value));
                 }
                 new
             });
         }
+        _ if keep_live => {}
         _ => attribute_expression(el, attr_name, value),
     }
 }
 
-fn attribute_expression(el: &web_sys::Element, attr_name: &str, value: Attribute) {
+pub(crate) fn attribute_expression(el: &web_sys::Element, attr_name: &str, value: Attribute) {
     match value {
         Attribute::String(value) => {
             let value = wasm_bindgen::intern(&value);
@@ -71,7 +96,7 @@ fn attribute_expression(el: &web_sys::Element, attr_name: &str, value: Attribute
                 el.set_inner_html(&value);
             } else {
                 let attr_name = wasm_bindgen::intern(attr_name);
-                set_attribute(el, attr_name, &value)
+                set_attribute(el, attr_name, &strip_nulls(&value))
             }
         }
         Attribute::Option(value) => {
@@ -82,7 +107,7 @@ fn attribute_expression(el: &web_sys::Element, attr_name: &str, value: Attribute
                 match value {
                     Some(value) => {
                         let value = wasm_bindgen::intern(&value);
-                        set_attribute(el, attr_name, &value)
+                        set_attribute(el, attr_name, &strip_nulls(value))
                     }
                     None => remove_attribute(el, attr_name),
                 }
@@ -96,6 +121,9 @@ fn attribute_expression(el: &web_sys::Element, attr_name: &str, value: Attribute
                 remove_attribute(el, attr_name);
             }
         }
+        Attribute::Composite(composite) => {
+            attribute_expression(el, attr_name, Attribute::String(composite.value()))
+        }
         _ => panic!("Remove nested Fn in Attribute"),
     }
 }
@@ -104,21 +132,36 @@ fn attribute_expression(el: &web_sys::Element, attr_name: &str, value: Attribute
 /// it will [create an effect](leptos_reactive::create_effect) to make fine-grained reactive updates
 /// to the property.
 ///
+/// The `value` and `checked` properties can also be changed by the user, so they are only
+/// written when they differ from the element's current value. A `value` isn't written while the
+/// user is composing text with an IME, but once they finish, and writing it to a focused text
+/// field keeps the cursor in place relative to the text around it.
+///
+/// Like [attribute], this doesn’t write properties like `currentTime` or `open` while hydrating,
+/// and only writes them once the value changes.
+///
 /// This is used by the [`view`](https://docs.rs/leptos_macro/latest/leptos_macro/macro.view.html) macro.
 /// You usually won't need to interact with it directly.
 pub fn property(cx: Scope, el: &web_sys::Element, prop_name: &'static str, value: Property) {
+    let keep_live = keeps_live_state(cx, prop_name);
     match value {
         Property::Fn(f) => {
+            if prop_name == "value" {
+                track_composition(el);
+            }
             let el = el.clone();
             create_render_effect(cx, move |old| {
                 let new = f();
-                if old.as_ref() != Some(&new) && !(old == None && new == JsValue::UNDEFINED) {
-                    wasm_bindgen::intern(prop_name);
-                    property_expression(&el, prop_name, new.clone())
+                // the user may have changed a controlled property since it was last written, so
+                // it is written even if unchanged, and compared against the element's value instead
+                let changed = is_controlled(prop_name) || old.as_ref() != Some(&new);
+                if changed && !(old == None && (new == JsValue::UNDEFINED || keep_live)) {
+                    write(cx, &el, Write::Property(prop_name, new.clone()))
                 }
                 new
             });
         }
+        Property::Value(_) if keep_live => {}
         Property::Value(value) => {
             wasm_bindgen::intern(prop_name);
             property_expression(el, prop_name, value)
@@ -126,8 +169,61 @@ pub fn property(cx: Scope, el: &web_sys::Element, prop_name: &'static str, value
     }
 }
 
-fn property_expression(el: &web_sys::Element, prop_name: &str, value: JsValue) {
-    js_sys::Reflect::set(el, &JsValue::from_str(prop_name), &value).unwrap_throw();
+/// Binds the `open` state of a `<details>` or `<dialog>` to the signal in both directions: the
+/// element is opened or closed when the signal changes, and the signal is updated when the user
+/// opens or closes the element.
+///
+/// While hydrating, the signal is set to whether the element is open, in case the user opened
+/// or closed it before the page was hydrated.
+///
+/// This is used by the [`view`](https://docs.rs/leptos_macro/latest/leptos_macro/macro.view.html)
+/// macro for `bind:open`. You usually won't need to interact with it directly.
+pub fn bind_open(cx: Scope, el: &web_sys::Element, open: RwSignal<bool>) {
+    fn is_open(el: &web_sys::Element) -> bool {
+        js_sys::Reflect::get(el, &JsValue::from_str("open"))
+            .ok()
+            .and_then(|open| open.as_bool())
+            .unwrap_or(false)
+    }
+
+    if keeps_live_state(cx, "open") {
+        open.set_if_changed(is_open(el));
+    }
+    property(cx, el, "open", (move || open.get()).into_property(cx));
+
+    // a `<dialog>` fires `close` rather than `toggle` in some browsers
+    for event_name in ["toggle", "close"] {
+        let el = el.clone();
+        use_event_listener(
+            cx,
+            ListenerTarget::Target(el.clone().into()),
+            event_name,
+            move |_| {
+                open.set_if_changed(is_open(&el));
+            },
+        );
+    }
+}
+
+// whether a binding should keep the element's live state rather than writing its value,
+// because the page is being hydrated and the attribute or property is stateful
+fn keeps_live_state(cx: Scope, name: &str) -> bool {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "hydrate")] {
+            cx.is_hydrating() && crate::is_stateful_attribute(name)
+        } else {
+            _ = (cx, name);
+            false
+        }
+    }
+}
+
+pub(crate) fn property_expression(el: &web_sys::Element, prop_name: &str, value: JsValue) {
+    if is_controlled(prop_name) {
+        set_property(el, prop_name, value);
+    } else {
+        js_sys::Reflect::set(el, &JsValue::from_str(prop_name), &value).unwrap_throw();
+    }
 }
 
 /// Binds the `value` to the class `class_name` on this `el`'s `classList`. If the class value is reactive,
@@ -142,8 +238,9 @@ pub fn class(cx: Scope, el: &web_sys::Element, class_name: &'static str, value:
             let el = el.clone();
             create_render_effect(cx, move |old| {
                 let new = f();
-                if old.as_ref() != Some(&new) && (old.is_some() || new) {
-                    class_expression(&el, class_name, new)
+                // when hydrating, the server may have rendered a class the client no longer wants
+                if old.as_ref() != Some(&new) && (old.is_some() || new || cfg!(feature = "hydrate")) {
+                    write(cx, &el, Write::Class(class_name, new))
                 }
                 new
             });
@@ -152,7 +249,7 @@ pub fn class(cx: Scope, el: &web_sys::Element, class_name: &'static str, value:
     }
 }
 
-fn class_expression(el: &web_sys::Element, class_name: &str, value: bool) {
+pub(crate) fn class_expression(el: &web_sys::Element, class_name: &str, value: bool) {
     let class_list = el.class_list();
     let class_name = wasm_bindgen::intern(class_name);
     if value {
@@ -200,13 +297,18 @@ pub fn insert(
                         value = (f.borrow_mut())();
                     }
 
-                    Some(insert_expression(
+                    let focus = FocusSnapshot::capture(&current);
+                    let value = insert_expression(
                         cx,
                         parent.clone().unchecked_into(),
                         &value,
                         current,
                         &before,
-                    ))
+                    );
+                    if let Some(focus) = focus {
+                        focus.restore(cx, &value);
+                    }
+                    Some(value)
                 } else {
                     Some(current)
                 }
@@ -1,15 +1,4 @@
 use cfg_if::cfg_if;
-use std::borrow::Cow;
-
-/// Encodes strings to be used as text in HTML by escaping `&`, `<`, and `>`.
-pub fn escape_text(text: &str) -> Cow<'_, str> {
-    html_escape::encode_text(text)
-}
-
-/// Encodes strings to be used as attribute values in HTML by escaping `&`, `<`, `>`, and `"`.
-pub fn escape_attr(text: &str) -> Cow<'_, str> {
-    html_escape::encode_double_quoted_attribute(text)
-}
 
 cfg_if! {
     if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
@@ -17,6 +6,7 @@ cfg_if! {
 
         use crate::Element;
         use futures::{stream::FuturesUnordered, Stream, StreamExt};
+        use thiserror::Error;
 
         /// Renders a component to a static HTML string.
         ///
@@ -36,51 +26,184 @@ cfg_if! {
             html
         }
 
+        /// Renders a component to an HTML string, waiting up to `timeout` for any `<Suspense/>`
+        /// boundaries to resolve.
+        ///
+        /// Each `<Suspense/>` whose resources resolve before the timeout is rendered with its
+        /// actual children. If the timeout is reached first, this returns a best-effort
+        /// partial render: any `<Suspense/>` that is still pending keeps showing its `fallback`
+        /// (like a loading spinner), rather than delaying the response indefinitely.
+        ///
+        /// Unlike [render_to_stream], this does not serialize [Resource](leptos_reactive::Resource)
+        /// data into the page, so resources will load again in the browser if the page is hydrated.
+        /// The timeout is measured with [sleep](leptos_reactive::sleep), so with the `ssr` feature
+        /// this needs to run inside a `tokio` runtime.
+        ///
+        /// ```
+        /// # cfg_if::cfg_if! { if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+        /// # use leptos_reactive::*; use leptos_dom::*; use leptos_macro::view;
+        /// # use std::time::Duration;
+        /// let html = futures::executor::block_on(render_to_string_with_timeout(
+        ///   |cx| view! { cx, <p>"Hello, world!"</p> },
+        ///   Duration::from_millis(100),
+        /// ));
+        /// assert_eq!(html, r#"<p data-hk="0-0">Hello, world!</p>"#);
+        /// # }}
+        /// ```
+        pub async fn render_to_string_with_timeout(
+            view: impl FnOnce(Scope) -> Element + 'static,
+            timeout: std::time::Duration,
+        ) -> String {
+            use futures::future::{select, Either};
+
+            let runtime = create_runtime();
+
+            let ((mut html, pending_fragments), _, disposer) =
+                run_scope_undisposed(runtime, move |cx| {
+                    let shell = view(cx);
+                    (shell, cx.pending_fragments())
+                });
+
+            let mut fragments = FuturesUnordered::new();
+            for (fragment_id, fut) in pending_fragments {
+                fragments.push(async move { (fragment_id, fut.await) })
+            }
+
+            let mut timeout = leptos_reactive::sleep(timeout);
+
+            // swap in each fragment as it resolves, until they're all done or we run out of time
+            while !fragments.is_empty() {
+                match select(fragments.next(), timeout).await {
+                    Either::Left((Some((fragment_id, fragment)), rest)) => {
+                        // skipped fragments keep their fallback
+                        if let Some(fragment) = fragment {
+                            replace_fragment(&mut html, &fragment_id, &fragment);
+                        }
+                        timeout = rest;
+                    }
+                    Either::Left((None, _)) | Either::Right(_) => break,
+                }
+            }
+
+            drop(fragments);
+            disposer.dispose();
+            runtime.dispose();
+            html
+        }
+
+        // Replaces the `<div data-fragment-id=...>` wrapper around a `<Suspense/>` fallback
+        // with the resolved HTML for that fragment.
+        fn replace_fragment(html: &mut String, fragment_id: &str, fragment: &str) {
+            let marker = format!(r#"data-fragment-id="{fragment_id}""#);
+            let start = match html.find(&marker).and_then(|pos| html[..pos].rfind("<div")) {
+                Some(start) => start,
+                None => return,
+            };
+
+            // find the matching closing tag, accounting for any <div>s in the fallback
+            let mut depth = 0;
+            let mut pos = start;
+            while pos < html.len() {
+                let rest = &html[pos..];
+                if rest.starts_with("<div") {
+                    depth += 1;
+                    pos += 4;
+                } else if rest.starts_with("</div>") {
+                    depth -= 1;
+                    pos += 6;
+                    if depth == 0 {
+                        html.replace_range(start..pos, fragment);
+                        return;
+                    }
+                } else {
+                    pos += rest.chars().next().map(char::len_utf8).unwrap_or(1);
+                }
+            }
+        }
+
         /// Renders a component to a stream of HTML strings.
         ///
         /// This renders:
         /// 1) the application shell
         ///   a) HTML for everything that is not under a `<Suspense/>`,
         ///   b) the `fallback` for any `<Suspense/>` component that is not already resolved, and
-        ///   c) JavaScript necessary to receive streaming [Resource](leptos_reactive::Resource) data.
+        ///   c) JavaScript necessary to receive streaming [Resource](leptos_reactive::Resource) data,
+        ///      including which resources belong to each [island](leptos_reactive::Scope::with_island).
         /// 2) streaming [Resource](leptos_reactive::Resource) data. Resources begin loading on the
         ///    server and are sent down to the browser to resolve. On the browser, if the app sees that
         ///    it is waiting for a resource to resolve from the server, it doesn't run it initially.
+        ///    Later changes to [patchable resources](leptos_reactive::create_patchable_resource) are
+        ///    sent as [JSON patches](leptos_reactive::JsonPatch) until the rest of the stream is done.
         /// 3) HTML fragments to replace each `<Suspense/>` fallback with its actual data as the resources
         ///    read under that `<Suspense/>` resolve.
         pub fn render_to_stream(view: impl FnOnce(Scope) -> Element + 'static) -> impl Stream<Item = String> {
+            let (shell, _, script, resources_and_fragments) = render_stream_parts(view, |_| String::new());
+
+            // HTML for the view function and script to store resources
+            futures::stream::once(async move {
+                format!(
+                    r#"
+                        {shell}
+                        {script}
+                    "#
+                )
+            })
+            .chain(resources_and_fragments)
+        }
+
+        // Renders the view, then runs `head` in the same scope. Returns the view's HTML, the HTML
+        // returned by `head`, the script that sets up streaming resources, and a stream of resource
+        // data and `<Suspense/>` fragments, which disposes of the runtime once it is done.
+        fn render_stream_parts(
+            view: impl FnOnce(Scope) -> Element + 'static,
+            head: impl FnOnce(Scope) -> String + 'static,
+        ) -> (String, String, String, impl Stream<Item = String>) {
             // create the runtime
             let runtime = create_runtime();
 
-            let ((shell, pending_resources, pending_fragments, serializers), _, disposer) =
+            let ((shell, head, keys, pending_fragments, serializers, patches), _, disposer) =
                 run_scope_undisposed(runtime, {
                     move |cx| {
                         // the actual app body/template code
                         // this does NOT contain any of the data being loaded asynchronously in resources
                         let shell = view(cx);
+                        // run after the view, so it can include anything the view set up
+                        let head = head(cx);
 
-                        let resources = cx.all_resources();
-                        let pending_resources = serde_json::to_string(&resources).unwrap();
+                        // resources are sent under keys derived from where they were created, so the
+                        // browser can match them up even if it doesn't create exactly the same ones
+                        let keys = cx.resource_keys();
 
                         (
                             shell,
-                            pending_resources,
+                            head,
+                            keys,
                             cx.pending_fragments(),
                             cx.serialization_resolvers(),
+                            cx.serialization_patches(),
                         )
                     }
                 });
 
+            // sorted, so that the same page is always rendered with the same script
+            let mut pending_resources = keys.values().collect::<Vec<_>>();
+            pending_resources.sort();
+            let pending_resources = serde_json::to_string(&pending_resources).unwrap();
+            let patch_keys = keys.clone();
+
             let fragments = FuturesUnordered::new();
             for (fragment_id, fut) in pending_fragments {
                 fragments.push(async move { (fragment_id, fut.await) })
             }
 
+            // patches stop once every resource and fragment has been sent, so the response can end
+            let (done_tx, done_rx) = futures::channel::oneshot::channel::<()>();
+
             // resources and fragments
             let resources_and_fragments = futures::stream::select(
                 // stream data for each Resource as it resolves
-                serializers.map(|(id, json)| {
-                    let id = serde_json::to_string(&id).unwrap();
+                serializers.map(move |(id, json)| {
+                    let id = serde_json::to_string(&keys[&id]).unwrap();
                     format!(
                         r#"<script>
                                 if(__LEPTOS_RESOURCE_RESOLVERS.get({id})) {{
@@ -91,10 +214,12 @@ cfg_if! {
                             </script>"#,
                     )
                 }),
-                // stream HTML for each <Suspense/> as it resolves
-                fragments.map(|(fragment_id, html)| {
-                    format!(
-                        r#"
+                // stream HTML for each <Suspense/> as it resolves, leaving the fallback of any that
+                // were skipped because their scope was disposed first
+                fragments.filter_map(|(fragment_id, html)| async move {
+                    html.map(|html| {
+                        format!(
+                            r#"
                             <template id="{fragment_id}">{html}</template>
                             <script>
                                 var frag = document.querySelector(`[data-fragment-id="{fragment_id}"]`);
@@ -102,30 +227,176 @@ cfg_if! {
                                 if(frag) frag.replaceWith(tpl.content.cloneNode(true));
                             </script>
                             "#
-                    )
+                        )
+                    })
                 })
-            );
+            )
+            .chain(futures::stream::once(async move {
+                _ = done_tx.send(());
+                String::new()
+            }));
 
-            // HTML for the view function and script to store resources
-            futures::stream::once(async move {
+            // stream a JSON patch each time a patchable Resource changes after it was sent
+            let patches = patches.take_until(done_rx).map(move |(id, patch)| {
+                let id = serde_json::to_string(&patch_keys[&id]).unwrap();
                 format!(
-                    r#"
-                        {shell}
-                        <script>
+                    r#"<script>
+                            if(__LEPTOS_RESOURCE_PATCHERS[{id:?}]) {{
+                                __LEPTOS_RESOURCE_PATCHERS[{id:?}]({patch:?});
+                            }} else {{
+                                (__LEPTOS_PENDING_PATCHES[{id:?}] = __LEPTOS_PENDING_PATCHES[{id:?}] || []).push({patch:?});
+                            }}
+                        </script>"#,
+                )
+            });
+            let resources_and_fragments = futures::stream::select(resources_and_fragments, patches)
+                // dispose of Scope and Runtime
+                .chain(futures::stream::once(async move {
+                    disposer.dispose();
+                    runtime.dispose();
+                    Default::default()
+                }));
+
+            let script = format!(
+                r#"<script>
                             __LEPTOS_PENDING_RESOURCES = {pending_resources};
                             __LEPTOS_RESOLVED_RESOURCES = new Map();
                             __LEPTOS_RESOURCE_RESOLVERS = new Map();
-                        </script>
-                    "#
-                )
-            })
+                            __LEPTOS_RESOURCE_PATCHERS = {{}};
+                            __LEPTOS_PENDING_PATCHES = {{}};
+                        </script>"#
+            );
+
+            (shell, head, script, resources_and_fragments)
+        }
+
+        /// The marker at which [render_to_string_in_template] and [render_to_stream_in_template]
+        /// insert the HTML returned by their `head` function, such as the tags collected by
+        /// `leptos_meta`. This usually goes just before `</head>`.
+        pub const HEAD_MARKER: &str = "<!--leptos-head-->";
+
+        /// The marker at which [render_to_stream_in_template] inserts the scripts that send
+        /// [Resource](leptos_reactive::Resource) data and `<Suspense/>` fragments. This usually goes
+        /// just before `</body>`.
+        pub const SCRIPTS_MARKER: &str = "<!--leptos-scripts-->";
+
+        /// Describes errors that can occur while splitting an HTML template at its markers.
+        #[derive(Debug, Clone, PartialEq, Eq, Error)]
+        pub enum TemplateError {
+            /// The template doesn’t contain these markers.
+            #[error("the HTML template is missing the markers {}", .0.join(", "))]
+            MissingMarkers(Vec<String>),
+            /// The markers aren’t in the order the template is rendered in.
+            #[error("the markers in the HTML template should be in the order {}", .0.join(", "))]
+            MarkersOutOfOrder(Vec<String>),
+        }
+
+        // Splits the template at each of the markers, checking that they are all there, in order.
+        fn split_template<'a>(template: &'a str, markers: &[&str]) -> Result<Vec<&'a str>, TemplateError> {
+            let positions = markers.iter().map(|marker| template.find(marker)).collect::<Vec<_>>();
+            let missing = markers
+                .iter()
+                .zip(&positions)
+                .filter(|(_, pos)| pos.is_none())
+                .map(|(marker, _)| marker.to_string())
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                return Err(TemplateError::MissingMarkers(missing));
+            }
+
+            let positions = positions.into_iter().flatten().collect::<Vec<_>>();
+            if positions.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return Err(TemplateError::MarkersOutOfOrder(
+                    markers.iter().map(|marker| marker.to_string()).collect(),
+                ));
+            }
+
+            let mut parts = Vec::with_capacity(markers.len() + 1);
+            let mut start = 0;
+            for (marker, pos) in markers.iter().zip(positions) {
+                parts.push(&template[start..pos]);
+                start = pos + marker.len();
+            }
+            parts.push(&template[start..]);
+            Ok(parts)
+        }
+
+        /// Renders a component to a static HTML string, inside an existing HTML document.
+        ///
+        /// The app is rendered in place of `marker` (e.g., `<!--leptos-app-->`), and whatever `head`
+        /// returns in place of [HEAD_MARKER]. `head` is run after the app has been rendered, in the
+        /// same [Scope], so it can return anything the app has registered, like the `leptos_meta` tags.
+        ///
+        /// Returns an error listing any markers that are missing from the template.
+        ///
+        /// ```
+        /// # cfg_if::cfg_if! { if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+        /// # use leptos_reactive::*; use leptos_dom::*; use leptos_macro::view;
+        /// let template = "<html><head><!--leptos-head--></head><body><!--leptos-app--></body></html>";
+        /// let html = render_to_string_in_template(
+        ///   template,
+        ///   "<!--leptos-app-->",
+        ///   |cx| view! { cx, <p>"Hello, world!"</p> },
+        ///   |_| "<title>Hello</title>".to_string(),
+        /// ).unwrap();
+        /// assert_eq!(
+        ///   html,
+        ///   r#"<html><head><title>Hello</title></head><body><p data-hk="0-0">Hello, world!</p></body></html>"#
+        /// );
+        /// # }}
+        /// ```
+        pub fn render_to_string_in_template(
+            template: &str,
+            marker: &str,
+            view: impl FnOnce(Scope) -> Element + 'static,
+            head: impl FnOnce(Scope) -> String + 'static,
+        ) -> Result<String, TemplateError> {
+            let parts = split_template(template, &[HEAD_MARKER, marker])?;
+
+            let runtime = create_runtime();
+            let (app, head) = run_scope(runtime, move |cx| {
+                let app = view(cx);
+                (app, head(cx))
+            });
+            runtime.dispose();
+
+            Ok([parts[0], &head, parts[1], &app, parts[2]].concat())
+        }
+
+        /// Renders a component to a stream of HTML strings, inside an existing HTML document.
+        ///
+        /// This streams the same content as [render_to_stream], along with the template:
+        /// 1) the template up to [HEAD_MARKER], and whatever `head` returns (see
+        ///    [render_to_string_in_template]),
+        /// 2) the template up to `marker`, followed by the application shell,
+        /// 3) the template up to [SCRIPTS_MARKER], followed by the script to receive resources,
+        /// 4) streaming [Resource](leptos_reactive::Resource) data and `<Suspense/>` fragments, and
+        /// 5) the rest of the template, once everything else has been sent.
+        ///
+        /// Returns an error listing any markers that are missing from the template.
+        pub fn render_to_stream_in_template(
+            template: &str,
+            marker: &str,
+            view: impl FnOnce(Scope) -> Element + 'static,
+            head: impl FnOnce(Scope) -> String + 'static,
+        ) -> Result<impl Stream<Item = String>, TemplateError> {
+            let parts = split_template(template, &[HEAD_MARKER, marker, SCRIPTS_MARKER])?;
+            let (before_head, before_app, before_scripts, tail) = (
+                parts[0].to_string(),
+                parts[1].to_string(),
+                parts[2].to_string(),
+                parts[3].to_string(),
+            );
+
+            let (shell, head, script, resources_and_fragments) = render_stream_parts(view, head);
+
+            Ok(futures::stream::iter([
+                format!("{before_head}{head}"),
+                format!("{before_app}{shell}"),
+                format!("{before_scripts}{script}"),
+            ])
             .chain(resources_and_fragments)
-            // dispose of Scope and Runtime
-            .chain(futures::stream::once(async move {
-                disposer.dispose();
-                runtime.dispose();
-                Default::default()
-            }))
+            .chain(futures::stream::once(async move { tail })))
         }
     }
 }
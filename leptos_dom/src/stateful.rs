@@ -0,0 +1,37 @@
+//! Attributes and properties that carry state the browser or the user can change, which
+//! hydration shouldn’t overwrite with the value the server rendered.
+//!
+//! When a server-rendered page loads, the browser acts on some attributes as it parses them:
+//! it focuses an `autofocus` field and starts playing an `autoplay` video. The user can also
+//! open a `<details>` before the app has loaded. Writing these again during hydration would
+//! focus the field a second time, restart the video, or undo the user’s toggle, so their
+//! bindings keep the element’s live state when hydrating, and only write once the bound value
+//! changes afterwards.
+
+use std::{cell::RefCell, collections::HashSet};
+
+/// The attributes and properties that keep the element’s live state during hydration by default.
+///
+/// - `autofocus`: the browser has already focused the element.
+/// - `autoplay` and `currentTime`: a media element may already be playing.
+/// - `open`: the user may have opened or closed a `<details>` or `<dialog>`.
+pub const DEFAULT_STATEFUL_ATTRIBUTES: &[&str] = &["autofocus", "autoplay", "currentTime", "open"];
+
+thread_local! {
+    static STATEFUL: RefCell<HashSet<&'static str>> =
+        RefCell::new(DEFAULT_STATEFUL_ATTRIBUTES.iter().copied().collect());
+}
+
+/// Marks an attribute or property as carrying state that the browser or user can change (see
+/// [DEFAULT_STATEFUL_ATTRIBUTES]), so that bindings to it aren’t applied while hydrating.
+///
+/// This is useful for custom elements that act on an attribute when they’re first connected.
+pub fn add_stateful_attribute(name: &'static str) {
+    STATEFUL.with(|stateful| stateful.borrow_mut().insert(name));
+}
+
+/// Whether bindings to the attribute or property `name` keep the element’s live state while
+/// hydrating (see [add_stateful_attribute]).
+pub fn is_stateful_attribute(name: &str) -> bool {
+    STATEFUL.with(|stateful| stateful.borrow().contains(name))
+}
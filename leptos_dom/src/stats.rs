@@ -0,0 +1,68 @@
+use std::cell::Cell;
+
+/// How many DOM nodes Leptos has created, as returned by [stats].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeStats {
+    /// Nodes created one at a time, with [create_element](crate::create_element),
+    /// [create_text_node](crate::create_text_node), or [create_comment_node](crate::create_comment_node).
+    pub created: usize,
+    /// Nodes created by cloning a view’s template with [clone_template](crate::clone_template),
+    /// counting every node in each clone.
+    pub cloned: usize,
+}
+
+thread_local! {
+    static STATS: Cell<NodeStats> = Cell::new(NodeStats::default());
+}
+
+/// Returns how many DOM nodes Leptos has created one at a time, and how many it has created by
+/// cloning templates, since the page loaded or [reset_stats] was last called.
+///
+/// The [view](https://docs.rs/leptos/latest/leptos/macro.view.html) macro builds a `<template>`
+/// for the static parts of each view the first time it is rendered, and clones it for every
+/// instance, only creating nodes one at a time for dynamic text and the like. So mounting many
+/// instances of the same component should mostly count as `cloned`.
+///
+/// Nodes are only counted in debug builds. In release builds, both counts are always `0`.
+pub fn stats() -> NodeStats {
+    STATS.with(Cell::get)
+}
+
+/// Resets the counts returned by [stats] to `0`.
+pub fn reset_stats() {
+    STATS.with(Cell::take);
+}
+
+pub(crate) fn count_created() {
+    #[cfg(debug_assertions)]
+    STATS.with(|stats| {
+        let mut counts = stats.get();
+        counts.created += 1;
+        stats.set(counts);
+    });
+}
+
+pub(crate) fn count_cloned(node: &web_sys::Node) {
+    cfg_if::cfg_if! {
+        if #[cfg(debug_assertions)] {
+            fn count_nodes(node: &web_sys::Node) -> usize {
+                let mut count = 1;
+                let mut child = node.first_child();
+                while let Some(node) = child {
+                    count += count_nodes(&node);
+                    child = node.next_sibling();
+                }
+                count
+            }
+
+            let cloned = count_nodes(node);
+            STATS.with(|stats| {
+                let mut counts = stats.get();
+                counts.cloned += cloned;
+                stats.set(counts);
+            });
+        } else {
+            _ = node;
+        }
+    }
+}
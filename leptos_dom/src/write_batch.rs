@@ -0,0 +1,104 @@
+use std::cell::RefCell;
+
+use leptos_reactive::{is_flushing_render_effects, on_render_flush, Scope};
+use wasm_bindgen::JsValue;
+
+use crate::{
+    render::{attribute_expression, class_expression, property_expression},
+    Attribute,
+};
+
+/// A single write to an element made by a reactive binding.
+pub(crate) enum Write {
+    Attribute(&'static str, Attribute),
+    Class(&'static str, bool),
+    Property(&'static str, JsValue),
+}
+
+impl Write {
+    fn is_same_target(&self, other: &Write) -> bool {
+        match (self, other) {
+            (Write::Attribute(a, _), Write::Attribute(b, _)) => a == b,
+            (Write::Class(a, _), Write::Class(b, _)) => a == b,
+            (Write::Property(a, _), Write::Property(b, _)) => a == b,
+            _ => false,
+        }
+    }
+
+    fn apply(self, el: &web_sys::Element) {
+        match self {
+            Write::Attribute(name, value) => attribute_expression(el, name, value),
+            Write::Class(name, value) => class_expression(el, name, value),
+            Write::Property(name, value) => {
+                wasm_bindgen::intern(name);
+                property_expression(el, name, value)
+            }
+        }
+    }
+}
+
+struct PendingWrites {
+    el: web_sys::Element,
+    writes: Vec<Write>,
+}
+
+thread_local! {
+    static PENDING: RefCell<Vec<PendingWrites>> = Default::default();
+}
+
+// the index of an element's entry in `PENDING`, stored on the element itself while it has
+// writes waiting, so that finding it doesn't mean comparing against every other element
+const PENDING_INDEX: &str = "$$leptosPending";
+
+/// Applies the write to the element. If it is made while render effects are being rerun after
+/// a signal change, it is staged instead, and applied along with every other write to the same
+/// element once they have all run. A later write to the same attribute, class, or property
+/// within that flush replaces the staged one.
+///
+/// Elements are written in the order they were first written to, and each element’s writes
+/// in the order their targets were first written to, so the order is stable between flushes.
+pub(crate) fn write(cx: Scope, el: &web_sys::Element, write: Write) {
+    if !is_flushing_render_effects(cx) {
+        write.apply(el);
+        return;
+    }
+
+    on_render_flush(cx, flush);
+    PENDING.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        let key = JsValue::from_str(PENDING_INDEX);
+        let index = js_sys::Reflect::get(el, &key)
+            .ok()
+            .and_then(|index| index.as_f64())
+            .map(|index| index as usize);
+        let index = match index {
+            Some(index) => index,
+            None => {
+                let index = pending.len();
+                _ = js_sys::Reflect::set(el, &key, &JsValue::from(index as u32));
+                pending.push(PendingWrites {
+                    el: el.clone(),
+                    writes: Vec::new(),
+                });
+                index
+            }
+        };
+
+        let writes = &mut pending[index].writes;
+        match writes.iter_mut().find(|prev| prev.is_same_target(&write)) {
+            Some(prev) => *prev = write,
+            None => writes.push(write),
+        }
+    });
+}
+
+fn flush() {
+    let pending = PENDING.with(|pending| pending.take());
+    let key = JsValue::from_str(PENDING_INDEX);
+    for PendingWrites { el, writes } in pending {
+        _ = js_sys::Reflect::delete_property(&el, &key);
+        for write in writes {
+            write.apply(&el);
+        }
+    }
+}
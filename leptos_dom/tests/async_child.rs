@@ -0,0 +1,63 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use futures::channel::oneshot;
+use leptos_dom::*;
+use leptos_reactive::{create_runtime, create_scope, ScopeDisposer};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// resolves once the browser has run a macrotask, by which time resolved futures have been polled
+async fn tick() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        window().set_timeout_with_callback(&resolve).unwrap();
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+// renders an async child between `<b>` and `<i>` siblings, resolving to whatever is sent
+fn render(parent: &web_sys::Element) -> (ScopeDisposer, oneshot::Sender<String>) {
+    parent.set_inner_html("<b>before</b><i>after</i>");
+    let (tx, rx) = oneshot::channel::<String>();
+    let disposer = create_scope(create_runtime(), {
+        let parent = parent.clone();
+        move |cx| {
+            let after = parent.last_child().unwrap();
+            let child = async_child(async move { rx.await.unwrap_or_default() }).into_child(cx);
+            insert(cx, parent.into(), child, Marker::BeforeChild(after), None);
+        }
+    });
+    (disposer, tx)
+}
+
+#[wasm_bindgen_test]
+async fn async_child_renders_nothing_until_it_resolves_in_place() {
+    let parent = document().create_element("div").unwrap();
+    let (disposer, tx) = render(&parent);
+
+    tick().await;
+    assert_eq!(parent.text_content().unwrap(), "beforeafter");
+
+    tx.send("3 unread".to_string()).unwrap();
+    tick().await;
+    assert_eq!(parent.text_content().unwrap(), "before3 unreadafter");
+    // the siblings are untouched
+    assert_eq!(parent.first_element_child().unwrap().tag_name(), "B");
+    assert_eq!(parent.last_element_child().unwrap().tag_name(), "I");
+
+    disposer.dispose();
+}
+
+#[wasm_bindgen_test]
+async fn async_child_is_dropped_if_disposed_before_it_resolves() {
+    let parent = document().create_element("div").unwrap();
+    let (disposer, tx) = render(&parent);
+    tick().await;
+    let before = parent.inner_html();
+
+    disposer.dispose();
+    _ = tx.send("3 unread".to_string());
+    tick().await;
+    assert_eq!(parent.inner_html(), before);
+    assert_eq!(parent.text_content().unwrap(), "beforeafter");
+}
@@ -0,0 +1,69 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use leptos_dom::*;
+use leptos_reactive::{create_runtime, create_scope, create_signal};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const HOSTILE: [&str; 8] = [
+    r#"say "hi""#,
+    "it's",
+    r#"{"a":1,"b":[true,null]}"#,
+    "</script><script>alert(1)</script>",
+    "line\u{2028}separator\u{2029}",
+    "carriage\r\nreturn",
+    "null\0byte",
+    "&amp; &lt;",
+];
+
+// the value the browser parses from the attribute as it would be rendered on the server
+fn parsed_from_server(value: &str) -> Option<String> {
+    let html = format!(
+        "<div {}></div>",
+        Attribute::String(value.to_string()).as_value_string("data-x")
+    );
+    let template = create_element("template").unchecked_into::<web_sys::HtmlTemplateElement>();
+    template.set_inner_html(&html);
+    template
+        .content()
+        .first_element_child()
+        .unwrap()
+        .get_attribute("data-x")
+}
+
+#[wasm_bindgen_test]
+fn client_values_match_server_values() {
+    create_scope(create_runtime(), |cx| {
+        for value in HOSTILE {
+            let el = create_element("div");
+            attribute(cx, &el, "data-x", value.into_attribute(cx));
+            assert_eq!(
+                el.get_attribute("data-x"),
+                parsed_from_server(value),
+                "{value:?}"
+            );
+        }
+    })
+    .dispose();
+}
+
+#[wasm_bindgen_test]
+fn composite_values_update_as_a_whole() {
+    create_scope(create_runtime(), |cx| {
+        let (retina, set_retina) = create_signal(cx, false);
+        let el = create_element("img");
+        let srcset = Composite::srcset()
+            .part("a.jpg 1x")
+            .reactive(move || retina.get().then(|| "b.jpg?w=2&h=2 2x".to_string()));
+        attribute(cx, &el, "srcset", srcset.into_attribute(cx));
+        assert_eq!(el.get_attribute("srcset").as_deref(), Some("a.jpg 1x"));
+
+        set_retina(true);
+        let expected = "a.jpg 1x, b.jpg?w=2&h=2 2x";
+        assert_eq!(el.get_attribute("srcset").as_deref(), Some(expected));
+        assert_eq!(parsed_from_server(expected).as_deref(), Some(expected));
+    })
+    .dispose();
+}
@@ -0,0 +1,71 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use leptos_dom::*;
+use leptos_reactive::{create_runtime, create_scope};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn stored() -> Option<String> {
+    window()
+        .local_storage()
+        .unwrap()
+        .unwrap()
+        .get_item(COLOR_MODE_STORAGE_KEY)
+        .unwrap()
+}
+
+fn has_class(class: &str) -> bool {
+    document()
+        .document_element()
+        .unwrap()
+        .class_list()
+        .contains(class)
+}
+
+#[wasm_bindgen_test]
+fn overrides_are_applied_and_remembered() {
+    window()
+        .local_storage()
+        .unwrap()
+        .unwrap()
+        .remove_item(COLOR_MODE_STORAGE_KEY)
+        .unwrap();
+    let prefers_dark = window()
+        .match_media("(prefers-color-scheme: dark)")
+        .unwrap()
+        .map(|query| query.matches())
+        .unwrap_or(false);
+    let system = if prefers_dark {
+        ColorMode::Dark
+    } else {
+        ColorMode::Light
+    };
+
+    create_scope(create_runtime(), |cx| {
+        let color_mode = use_color_mode(cx);
+        assert_eq!(color_mode.mode.get(), ColorMode::Auto);
+        assert_eq!(color_mode.effective_mode.get(), system);
+
+        color_mode.mode.set(ColorMode::Dark);
+        assert_eq!(color_mode.effective_mode.get(), ColorMode::Dark);
+        assert!(has_class("dark") && !has_class("light"));
+        assert_eq!(stored().as_deref(), Some("dark"));
+
+        color_mode.mode.set(ColorMode::Light);
+        assert_eq!(color_mode.effective_mode.get(), ColorMode::Light);
+        assert!(has_class("light") && !has_class("dark"));
+    })
+    .dispose();
+
+    // the override is still there next time
+    create_scope(create_runtime(), |cx| {
+        let color_mode = use_color_mode(cx);
+        assert_eq!(color_mode.mode.get(), ColorMode::Light);
+
+        color_mode.mode.set(ColorMode::Auto);
+        assert_eq!(color_mode.effective_mode.get(), system);
+        assert_eq!(stored(), None);
+    })
+    .dispose();
+}
@@ -0,0 +1,139 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use leptos_dom::*;
+use leptos_reactive::{create_runtime, create_scope, create_signal, Scope, WriteSignal};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// a focused `<input>` with its `value` bound to a signal, which its `input` handler writes to
+// after passing what the user typed through `filter`
+fn controlled_input(
+    cx: Scope,
+    initial: &str,
+    filter: impl Fn(String) -> String + 'static,
+) -> (web_sys::HtmlInputElement, WriteSignal<String>) {
+    let (value, set_value) = create_signal(cx, initial.to_string());
+    let el = create_element("input");
+    document().body().unwrap().append_child(&el).unwrap();
+    property(cx, &el, "value", (move || value.get()).into_property(cx));
+    use_event_listener(
+        cx,
+        ListenerTarget::Target(el.clone().into()),
+        "input",
+        move |ev| set_value.set(filter(event_target_value(&ev))),
+    );
+
+    let input = el.unchecked_into::<web_sys::HtmlInputElement>();
+    input.focus().unwrap();
+    (input, set_value)
+}
+
+// what the browser does when the user types `text` at the cursor
+fn type_text(input: &web_sys::HtmlInputElement, text: &str) {
+    let value = input.value().encode_utf16().collect::<Vec<_>>();
+    let start = input.selection_start().unwrap().unwrap() as usize;
+    let end = input.selection_end().unwrap().unwrap() as usize;
+    let text = text.encode_utf16().collect::<Vec<_>>();
+    let new = [&value[..start], &text, &value[end..]].concat();
+    input.set_value(&String::from_utf16(&new).unwrap());
+    let cursor = (start + text.len()) as u32;
+    input.set_selection_range(cursor, cursor).unwrap();
+    dispatch(input, "input");
+}
+
+fn dispatch(input: &web_sys::HtmlInputElement, event_name: &str) {
+    let ev = web_sys::Event::new(event_name).unwrap();
+    input.dispatch_event(&ev).unwrap();
+}
+
+fn selection(input: &web_sys::HtmlInputElement) -> (u32, u32) {
+    (
+        input.selection_start().unwrap().unwrap(),
+        input.selection_end().unwrap().unwrap(),
+    )
+}
+
+#[wasm_bindgen_test]
+fn typing_interleaved_with_signal_writes_keeps_every_keystroke() {
+    create_scope(create_runtime(), |cx| {
+        let (input, set_value) = controlled_input(cx, "", |value| value);
+
+        type_text(&input, "h");
+        type_text(&input, "e");
+        // the app writes to the signal between keystrokes
+        set_value.set("hel".to_string());
+        input.set_selection_range(3, 3).unwrap();
+        type_text(&input, "l");
+        type_text(&input, "o");
+        assert_eq!(input.value(), "hello");
+        assert_eq!(selection(&input), (5, 5));
+
+        // typing in the middle doesn't move the cursor to the end
+        input.set_selection_range(1, 1).unwrap();
+        type_text(&input, "a");
+        assert_eq!(input.value(), "haello");
+        assert_eq!(selection(&input), (2, 2));
+    })
+    .dispose()
+}
+
+#[wasm_bindgen_test]
+fn selection_is_kept_around_changed_text() {
+    create_scope(create_runtime(), |cx| {
+        // digits are rejected
+        let (input, set_value) = controlled_input(cx, "hello world", |value| {
+            value.replace(|c: char| c.is_ascii_digit(), "")
+        });
+
+        // the typed digit is removed, and the cursor stays where it was typed
+        input.set_selection_range(5, 5).unwrap();
+        type_text(&input, "1");
+        assert_eq!(input.value(), "hello world");
+        assert_eq!(selection(&input), (5, 5));
+
+        // text changed before the selection moves it along with the text around it
+        input.set_selection_range(6, 8).unwrap();
+        set_value.set("HELLO, world".to_string());
+        assert_eq!(input.value(), "HELLO, world");
+        assert_eq!(selection(&input), (7, 9));
+
+        // a selection inside the changed text moves to the end of its replacement
+        input.set_selection_range(3, 3).unwrap();
+        set_value.set("Hi, world".to_string());
+        assert_eq!(selection(&input), (2, 2));
+
+        // a cursor just after the changed text stays just after it
+        set_value.set("Hey, world".to_string());
+        assert_eq!(selection(&input), (3, 3));
+    })
+    .dispose()
+}
+
+#[wasm_bindgen_test]
+fn writes_wait_for_composition_to_end() {
+    create_scope(create_runtime(), |cx| {
+        let (input, set_value) = controlled_input(cx, "", |value| value);
+
+        dispatch(&input, "compositionstart");
+        // the IME shows the text being composed in the input
+        input.set_value("nihon");
+        set_value.set("overwritten".to_string());
+        assert_eq!(input.value(), "nihon");
+
+        input.set_value("日本");
+        input.set_selection_range(2, 2).unwrap();
+        dispatch(&input, "input");
+        assert_eq!(input.value(), "日本");
+
+        dispatch(&input, "compositionend");
+        assert_eq!(input.value(), "日本");
+        assert_eq!(selection(&input), (2, 2));
+
+        // once the composition has ended, writes apply straight away
+        set_value.set("日本語".to_string());
+        assert_eq!(input.value(), "日本語");
+    })
+    .dispose()
+}
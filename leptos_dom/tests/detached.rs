@@ -0,0 +1,99 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use std::{cell::Cell, cell::RefCell, rc::Rc};
+
+use leptos_dom::*;
+use leptos_reactive::{create_runtime, create_scope, create_signal};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn click() -> web_sys::Event {
+    let init = web_sys::EventInit::new();
+    init.set_bubbles(true);
+    web_sys::Event::new_with_event_init_dict("click", &init).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn detached_render_can_be_measured_updated_and_attached() {
+    create_scope(create_runtime(), |cx| {
+        let (text, set_text) = create_signal(cx, "Hello".to_string());
+        let clicks = Rc::new(Cell::new(0));
+        let button = document().create_element("button").unwrap();
+        add_event_listener(&button, "click", {
+            let clicks = Rc::clone(&clicks);
+            move |_: web_sys::MouseEvent| clicks.set(clicks.get() + 1)
+        });
+
+        let detached = render_detached(cx, {
+            let button = button.clone();
+            move |cx| {
+                let p = document().create_element("p").unwrap();
+                insert(
+                    cx,
+                    p.clone().unchecked_into(),
+                    Child::Fn(Rc::new(RefCell::new(move || Child::Text(text.get())))),
+                    Marker::NoChildren,
+                    None,
+                );
+                Child::Nodes(vec![p.unchecked_into(), button.unchecked_into()])
+            }
+        });
+        assert!(!detached.is_attached());
+        assert_eq!(detached.root_nodes().len(), 2);
+
+        let rect = detached.measure();
+        assert!(rect.width() > 0.0);
+        assert!(rect.height() > 0.0);
+        // measuring leaves it detached
+        assert!(!detached.is_attached());
+
+        set_text.set("Goodbye".to_string());
+        assert_eq!(detached.to_html_string(), "<p>Goodbye</p><button></button>");
+
+        button.dispatch_event(&click()).unwrap();
+        assert_eq!(clicks.get(), 0);
+
+        let body = document().body().unwrap();
+        detached.attach(&body);
+        assert!(detached.is_attached());
+        assert_eq!(detached.root_nodes()[1], button.clone().unchecked_into());
+        button.dispatch_event(&click()).unwrap();
+        assert_eq!(clicks.get(), 1);
+
+        detached.dispose();
+        assert!(!button.is_connected());
+        body.append_child(&button).unwrap();
+        button.dispatch_event(&click()).unwrap();
+        assert_eq!(clicks.get(), 1);
+    })
+    .dispose();
+}
+
+#[wasm_bindgen_test]
+fn html_snapshot_includes_the_current_values_of_fields() {
+    create_scope(create_runtime(), |cx| {
+        let detached = render_detached(cx, |_| {
+            let input = document().create_element("input").unwrap();
+            let checkbox = document().create_element("input").unwrap();
+            checkbox.set_attribute("type", "checkbox").unwrap();
+            Child::Nodes(vec![input.unchecked_into(), checkbox.unchecked_into()])
+        });
+
+        let nodes = detached.root_nodes();
+        nodes[0]
+            .unchecked_ref::<web_sys::HtmlInputElement>()
+            .set_value("typed");
+        nodes[1]
+            .unchecked_ref::<web_sys::HtmlInputElement>()
+            .set_checked(true);
+
+        assert_eq!(
+            detached.to_html_string(),
+            r#"<input value="typed"><input type="checkbox" checked="">"#
+        );
+        detached.dispose();
+    })
+    .dispose();
+}
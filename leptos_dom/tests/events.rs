@@ -0,0 +1,132 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use std::{cell::Cell, rc::Rc};
+
+use leptos_dom::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn mounted(tag: &str) -> web_sys::HtmlElement {
+    let el = document().create_element(tag).unwrap();
+    document().body().unwrap().append_child(&el).unwrap();
+    el.unchecked_into()
+}
+
+fn event(name: &str) -> web_sys::Event {
+    let init = web_sys::EventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    web_sys::Event::new_with_event_init_dict(name, &init).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn passive_listener_cannot_prevent_default() {
+    let el = mounted("div");
+    let ran = Rc::new(Cell::new(false));
+    add_event_listener_with_options(
+        &el,
+        "wheel",
+        {
+            let ran = Rc::clone(&ran);
+            move |ev: web_sys::WheelEvent| {
+                ev.prevent_default();
+                ran.set(true);
+            }
+        },
+        EventOptions {
+            passive: true,
+            ..Default::default()
+        },
+    );
+
+    let init = web_sys::WheelEventInit::new();
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    let ev = web_sys::WheelEvent::new_with_event_init_dict("wheel", &init).unwrap();
+    let not_canceled = el.dispatch_event(&ev).unwrap();
+
+    assert!(ran.get());
+    assert!(not_canceled);
+    assert!(!ev.default_prevented());
+}
+
+#[wasm_bindgen_test]
+fn once_listener_fires_once() {
+    let el = mounted("button");
+    let clicks = Rc::new(Cell::new(0));
+    add_event_listener_with_options(
+        &el,
+        "click",
+        {
+            let clicks = Rc::clone(&clicks);
+            move |_: web_sys::MouseEvent| clicks.set(clicks.get() + 1)
+        },
+        EventOptions {
+            once: true,
+            ..Default::default()
+        },
+    );
+
+    el.click();
+    el.click();
+    assert_eq!(clicks.get(), 1);
+}
+
+#[wasm_bindgen_test]
+fn once_listener_does_not_replace_other_listeners() {
+    let el = mounted("button");
+    let clicks = Rc::new(Cell::new(0));
+    let first_clicks = Rc::new(Cell::new(0));
+    add_event_listener(&el, "click", {
+        let clicks = Rc::clone(&clicks);
+        move |_: web_sys::MouseEvent| clicks.set(clicks.get() + 1)
+    });
+    add_event_listener_with_options(
+        &el,
+        "click",
+        {
+            let first_clicks = Rc::clone(&first_clicks);
+            move |_: web_sys::MouseEvent| first_clicks.set(first_clicks.get() + 1)
+        },
+        EventOptions {
+            once: true,
+            ..Default::default()
+        },
+    );
+
+    el.click();
+    el.click();
+    assert_eq!((clicks.get(), first_clicks.get()), (2, 1));
+}
+
+#[wasm_bindgen_test]
+fn prevent_default_and_stop_propagation_wrappers() {
+    let outer = mounted("div");
+    let form = document().create_element("form").unwrap();
+    outer.append_child(&form).unwrap();
+
+    let outer_submits = Rc::new(Cell::new(0));
+    add_event_listener(&outer, "submit", {
+        let outer_submits = Rc::clone(&outer_submits);
+        move |_: web_sys::Event| outer_submits.set(outer_submits.get() + 1)
+    });
+
+    let submits = Rc::new(Cell::new(0));
+    add_event_listener(
+        &form,
+        "submit",
+        prevent_default(stop_propagation({
+            let submits = Rc::clone(&submits);
+            move |_: web_sys::Event| submits.set(submits.get() + 1)
+        })),
+    );
+
+    let ev = event("submit");
+    form.dispatch_event(&ev).unwrap();
+
+    assert_eq!(submits.get(), 1);
+    assert!(ev.default_prevented());
+    assert_eq!(outer_submits.get(), 0);
+}
@@ -0,0 +1,95 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use std::{cell::RefCell, rc::Rc};
+
+use leptos_dom::*;
+use leptos_reactive::{create_runtime, create_scope, create_signal, provide_context, Scope};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// a `<div>` in the body whose contents `render` rebuilds from scratch each time it runs
+fn reactive_parent(cx: Scope, render: impl FnMut() -> Child + 'static) -> web_sys::Element {
+    let parent = create_element("div");
+    document().body().unwrap().append_child(&parent).unwrap();
+    insert(
+        cx,
+        parent.clone().into(),
+        Child::Fn(Rc::new(RefCell::new(render))),
+        Marker::NoChildren,
+        None,
+    );
+    parent
+}
+
+fn active_element() -> web_sys::Element {
+    document().active_element().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn focused_input_in_a_replaced_fragment_keeps_focus_and_selection() {
+    create_scope(create_runtime(), |cx| {
+        let (count, set_count) = create_signal(cx, 0);
+        reactive_parent(cx, move || {
+            let label = create_element("span");
+            label.set_text_content(Some(&count.get().to_string()));
+            let input = create_element("input");
+            input.set_attribute("id", "focus-name").unwrap();
+            input.set_attribute("value", "hello").unwrap();
+            Child::Nodes(vec![label.into(), input.into()])
+        });
+
+        let old = document()
+            .get_element_by_id("focus-name")
+            .unwrap()
+            .unchecked_into::<web_sys::HtmlInputElement>();
+        old.focus().unwrap();
+        old.set_selection_range(1, 3).unwrap();
+
+        set_count.set(1);
+
+        let new = active_element().unchecked_into::<web_sys::HtmlInputElement>();
+        assert_ne!(new, old);
+        assert_eq!(new.id(), "focus-name");
+        assert_eq!(new.selection_start().unwrap(), Some(1));
+        assert_eq!(new.selection_end().unwrap(), Some(3));
+    })
+    .dispose();
+}
+
+#[wasm_bindgen_test]
+fn unmatched_focus_calls_on_focus_lost() {
+    create_scope(create_runtime(), |cx| {
+        let lost = Rc::new(RefCell::new(None::<web_sys::Element>));
+        provide_context(cx, {
+            let lost = Rc::clone(&lost);
+            OnFocusLost::new(move |el| *lost.borrow_mut() = Some(el))
+        });
+
+        let (show_input, set_show_input) = create_signal(cx, true);
+        reactive_parent(cx, move || {
+            if show_input.get() {
+                let input = create_element("input");
+                input.set_attribute(FOCUS_KEY_ATTRIBUTE, "search").unwrap();
+                Child::Node(input.into())
+            } else {
+                Child::Text("no results".to_string())
+            }
+        });
+
+        let input = document()
+            .query_selector(&format!("[{FOCUS_KEY_ATTRIBUTE}=search]"))
+            .unwrap()
+            .unwrap();
+        input
+            .unchecked_ref::<web_sys::HtmlElement>()
+            .focus()
+            .unwrap();
+
+        set_show_input.set(false);
+
+        assert_eq!(lost.borrow().as_ref(), Some(&input));
+    })
+    .dispose();
+}
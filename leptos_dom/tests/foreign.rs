@@ -0,0 +1,149 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use std::{cell::Cell, cell::RefCell, rc::Rc};
+
+use leptos_dom::*;
+use leptos_reactive::{create_runtime, create_scope, create_signal};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// how many times the fake library has been called
+#[derive(Default)]
+struct Calls {
+    mount: Cell<usize>,
+    update: Cell<usize>,
+    unmount: Cell<usize>,
+}
+
+// a widget from a fake library, which renders its own `<span>`
+struct FakeWidget {
+    span: web_sys::Element,
+    calls: Rc<Calls>,
+}
+
+impl ForeignHandle for FakeWidget {
+    fn unmount(self) {
+        self.span.remove();
+        self.calls.unmount.set(self.calls.unmount.get() + 1);
+    }
+}
+
+fn fake_widget(calls: &Rc<Calls>) -> impl Fn(web_sys::HtmlElement) -> FakeWidget + 'static {
+    let calls = Rc::clone(calls);
+    move |container| {
+        calls.mount.set(calls.mount.get() + 1);
+        let span = document().create_element("span").unwrap();
+        container.append_child(&span).unwrap();
+        FakeWidget {
+            span,
+            calls: Rc::clone(&calls),
+        }
+    }
+}
+
+// resolves after the next animation frame, by which time the container has been checked again
+async fn next_frame() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        request_animation_frame(move || {
+            _ = resolve.call0(&wasm_bindgen::JsValue::NULL);
+        });
+    });
+    wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
+}
+
+#[wasm_bindgen_test]
+async fn foreign_element_mounts_updates_and_unmounts() {
+    let calls = Rc::new(Calls::default());
+    let parent = document().create_element("div").unwrap();
+    body().unwrap().append_child(&parent).unwrap();
+
+    let set_text = Rc::new(Cell::new(None));
+    let disposer = create_scope(create_runtime(), {
+        let calls = Rc::clone(&calls);
+        let set_text = Rc::clone(&set_text);
+        let parent = parent.clone();
+        move |cx| {
+            let (text, set) = create_signal(cx, "one".to_string());
+            set_text.set(Some(set));
+            let mount = fake_widget(&calls);
+            let widget = foreign_element(cx, mount, move |widget: &FakeWidget| {
+                widget.span.set_text_content(Some(&text.get()));
+                calls.update.set(calls.update.get() + 1);
+            });
+            let container = widget.container().clone();
+            insert(
+                cx,
+                parent.clone().unchecked_into(),
+                widget.into_child(cx),
+                Marker::NoChildren,
+                None,
+            );
+            assert!(container.is_connected());
+        }
+    });
+    // `mount` waits until the view has been inserted
+    assert_eq!(calls.mount.get(), 0);
+
+    next_frame().await;
+    assert_eq!((calls.mount.get(), calls.update.get()), (1, 1));
+    assert_eq!(parent.text_content().unwrap(), "one");
+
+    let set_text = set_text.get().unwrap();
+    set_text.set("two".to_string());
+    set_text.set("three".to_string());
+    assert_eq!((calls.mount.get(), calls.update.get()), (1, 3));
+    assert_eq!(parent.text_content().unwrap(), "three");
+
+    disposer.dispose();
+    assert_eq!(calls.unmount.get(), 1);
+    assert_eq!(parent.text_content().unwrap(), "");
+    parent.remove();
+}
+
+#[wasm_bindgen_test]
+async fn foreign_element_waits_until_attached_and_is_not_mounted_once_disposed() {
+    let calls = Rc::new(Calls::default());
+    let container = Rc::new(RefCell::new(None));
+    let disposer = create_scope(create_runtime(), {
+        let mount = fake_widget(&calls);
+        let container = Rc::clone(&container);
+        move |cx| {
+            let widget = foreign_element(cx, mount, |_: &FakeWidget| {});
+            *container.borrow_mut() = Some(widget.container().clone());
+        }
+    });
+    let container = container.take().unwrap();
+
+    next_frame().await;
+    assert_eq!(calls.mount.get(), 0);
+
+    body().unwrap().append_child(&container).unwrap();
+    next_frame().await;
+    next_frame().await;
+    assert_eq!(calls.mount.get(), 1);
+    assert_eq!(container.child_element_count(), 1);
+    disposer.dispose();
+    assert_eq!(calls.unmount.get(), 1);
+    container.remove();
+
+    // disposed before it was ever attached
+    let calls = Rc::new(Calls::default());
+    let container = Rc::new(RefCell::new(None));
+    create_scope(create_runtime(), {
+        let mount = fake_widget(&calls);
+        let container = Rc::clone(&container);
+        move |cx| {
+            let widget = foreign_element(cx, mount, |_: &FakeWidget| {});
+            *container.borrow_mut() = Some(widget.container().clone());
+        }
+    })
+    .dispose();
+    let container = container.take().unwrap();
+    body().unwrap().append_child(&container).unwrap();
+    next_frame().await;
+    next_frame().await;
+    assert_eq!((calls.mount.get(), calls.unmount.get()), (0, 0));
+    container.remove();
+}
@@ -0,0 +1,150 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use std::{cell::RefCell, rc::Rc};
+
+use leptos_dom::*;
+use leptos_reactive::{create_runtime, create_scope, Scope};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type Calls = Rc<RefCell<Vec<&'static str>>>;
+
+fn press(target: &web_sys::EventTarget, key: &str) -> web_sys::KeyboardEvent {
+    let init = web_sys::KeyboardEventInit::new();
+    init.set_key(key);
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    let ev = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap();
+    target.dispatch_event(&ev).unwrap();
+    ev
+}
+
+// a binding that records its name whenever it is called
+fn recording(
+    calls: &Calls,
+    name: &'static str,
+    shortcut: &str,
+    result: HotkeyResult,
+) -> HotkeyBinding {
+    let calls = Rc::clone(calls);
+    HotkeyBinding::new(shortcut, move |_| {
+        calls.borrow_mut().push(name);
+        result
+    })
+}
+
+fn with_escape_bindings(calls: &Calls, f: impl FnOnce(Scope) + 'static) {
+    let calls = Rc::clone(calls);
+    create_scope(create_runtime(), move |cx| {
+        provide_hotkey_context(cx);
+        use_hotkeys(
+            cx,
+            [recording(&calls, "outer", "escape", HotkeyResult::Handled)],
+        );
+        f(cx);
+    })
+    .dispose();
+}
+
+#[wasm_bindgen_test]
+fn innermost_binding_wins_until_its_scope_is_disposed() {
+    let calls = Calls::default();
+    with_escape_bindings(&calls, {
+        let calls = Rc::clone(&calls);
+        move |cx| {
+            let inner = cx.child_scope(|cx| {
+                use_hotkeys(
+                    cx,
+                    [recording(&calls, "inner", "escape", HotkeyResult::Handled)],
+                );
+            });
+
+            let ev = press(&document(), "Escape");
+            assert!(ev.default_prevented());
+            assert_eq!(*calls.borrow(), ["inner"]);
+
+            inner.dispose();
+            press(&document(), "Escape");
+            assert_eq!(*calls.borrow(), ["inner", "outer"]);
+        }
+    });
+}
+
+#[wasm_bindgen_test]
+fn propagated_key_presses_fall_through() {
+    let calls = Calls::default();
+    with_escape_bindings(&calls, {
+        let calls = Rc::clone(&calls);
+        move |cx| {
+            _ = cx.child_scope(|cx| {
+                use_hotkeys(
+                    cx,
+                    [recording(
+                        &calls,
+                        "inner",
+                        "escape",
+                        HotkeyResult::Propagate,
+                    )],
+                );
+            });
+
+            press(&document(), "Escape");
+            assert_eq!(*calls.borrow(), ["inner", "outer"]);
+        }
+    });
+}
+
+#[wasm_bindgen_test]
+fn key_presses_in_inputs_are_skipped_unless_opted_in() {
+    let input = document().create_element("input").unwrap();
+    document().body().unwrap().append_child(&input).unwrap();
+    let input = input.unchecked_into::<web_sys::HtmlElement>();
+    input.focus().unwrap();
+
+    let calls = Calls::default();
+    with_escape_bindings(&calls, {
+        let calls = Rc::clone(&calls);
+        move |cx| {
+            press(&input, "Escape");
+            assert!(calls.borrow().is_empty());
+
+            use_hotkeys(
+                cx,
+                [recording(&calls, "input", "escape", HotkeyResult::Handled).in_inputs()],
+            );
+            press(&input, "Escape");
+            assert_eq!(*calls.borrow(), ["input"]);
+        }
+    });
+}
+
+#[wasm_bindgen_test]
+fn hotkeys_share_key_presses_with_keyboard_shortcuts() {
+    let calls = Calls::default();
+    create_scope(create_runtime(), {
+        let calls = Rc::clone(&calls);
+        move |cx| {
+            provide_hotkey_context(cx);
+            use_keyboard_shortcut(cx, "escape", {
+                let calls = Rc::clone(&calls);
+                move || calls.borrow_mut().push("shortcut")
+            });
+
+            let hotkey = cx.child_scope(|cx| {
+                use_hotkeys(
+                    cx,
+                    [recording(&calls, "hotkey", "escape", HotkeyResult::Handled)],
+                );
+            });
+            press(&document(), "Escape");
+            assert_eq!(*calls.borrow(), ["hotkey"]);
+
+            hotkey.dispose();
+            press(&document(), "Escape");
+            assert_eq!(*calls.borrow(), ["hotkey", "shortcut"]);
+        }
+    })
+    .dispose();
+}
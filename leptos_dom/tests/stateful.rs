@@ -0,0 +1,112 @@
+#![cfg(all(target_arch = "wasm32", feature = "hydrate"))]
+
+use std::{cell::Cell, rc::Rc};
+
+use leptos_dom::*;
+use leptos_reactive::{create_runtime, create_rw_signal, create_scope, create_signal};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// an element as the server rendered it, with the given attributes
+fn server_rendered(tag: &str, attrs: &[&str]) -> web_sys::Element {
+    let el = document().create_element(tag).unwrap();
+    for attr in attrs {
+        el.set_attribute(attr, "").unwrap();
+    }
+    document().body().unwrap().append_child(&el).unwrap();
+    el
+}
+
+fn is_open(el: &web_sys::Element) -> bool {
+    js_sys::Reflect::get(el, &JsValue::from_str("open"))
+        .unwrap()
+        .as_bool()
+        .unwrap()
+}
+
+fn set_open(el: &web_sys::Element, open: bool) {
+    js_sys::Reflect::set(el, &JsValue::from_str("open"), &JsValue::from_bool(open)).unwrap();
+}
+
+#[wasm_bindgen_test]
+fn hydrating_keeps_a_details_the_user_closed() {
+    let el = server_rendered("details", &["open"]);
+    // the user closes it before the app has loaded
+    set_open(&el, false);
+
+    create_scope(create_runtime(), move |cx| {
+        let (open, set_open_signal) = create_signal(cx, true);
+        cx.start_hydration(&document().body().unwrap());
+        attribute(cx, &el, "open", (move || open.get()).into_attribute(cx));
+        cx.end_hydration();
+        assert!(!is_open(&el));
+
+        // once hydrated, changes to the signal are applied as usual
+        set_open_signal.set(false);
+        assert!(!is_open(&el));
+        set_open_signal.set(true);
+        assert!(is_open(&el));
+    })
+    .dispose();
+}
+
+#[wasm_bindgen_test]
+fn bind_open_starts_from_and_follows_the_live_state() {
+    let el = server_rendered("details", &["open"]);
+    set_open(&el, false);
+
+    create_scope(create_runtime(), move |cx| {
+        let open = create_rw_signal(cx, true);
+        cx.start_hydration(&document().body().unwrap());
+        bind_open(cx, &el, open);
+        cx.end_hydration();
+        assert!(!open.get());
+        assert!(!is_open(&el));
+
+        // the user opens it again
+        set_open(&el, true);
+        el.dispatch_event(&web_sys::Event::new("toggle").unwrap())
+            .unwrap();
+        assert!(open.get());
+
+        open.set(false);
+        assert!(!is_open(&el));
+    })
+    .dispose();
+}
+
+#[wasm_bindgen_test]
+fn hydrating_does_not_focus_an_autofocus_field_again() {
+    let field = server_rendered("input", &["autofocus"]);
+    let other = server_rendered("input", &[]);
+    // the user has already moved on to the next field
+    other
+        .unchecked_ref::<web_sys::HtmlElement>()
+        .focus()
+        .unwrap();
+
+    create_scope(create_runtime(), move |cx| {
+        let focused = Rc::new(Cell::new(0));
+        use_event_listener(cx, field.clone(), "focus", {
+            let focused = Rc::clone(&focused);
+            move |_| focused.set(focused.get() + 1)
+        });
+
+        let (autofocus, _) = create_signal(cx, true);
+        cx.start_hydration(&document().body().unwrap());
+        attribute(
+            cx,
+            &field,
+            "autofocus",
+            (move || autofocus.get()).into_attribute(cx),
+        );
+        attribute(cx, &field, "autofocus", true.into_attribute(cx));
+        cx.end_hydration();
+
+        assert_eq!(focused.get(), 0);
+        assert_eq!(document().active_element(), Some(other.clone()));
+    })
+    .dispose();
+}
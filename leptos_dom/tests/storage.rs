@@ -0,0 +1,77 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use leptos_dom::*;
+use leptos_reactive::{create_runtime, create_scope};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Deserialize)]
+struct NameV1 {
+    name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct NameV2 {
+    first: String,
+    last: String,
+}
+
+fn migrate(old: NameV1) -> NameV2 {
+    let (first, last) = old.name.split_once(' ').unwrap_or((&old.name, ""));
+    NameV2 {
+        first: first.to_string(),
+        last: last.to_string(),
+    }
+}
+
+fn storage() -> web_sys::Storage {
+    window().local_storage().unwrap().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn old_values_are_migrated_and_written_back() {
+    storage()
+        .set_item("storage-migrated", r#"{"name":"Ada Lovelace"}"#)
+        .unwrap();
+
+    create_scope(create_runtime(), |cx| {
+        let (name, _) = use_local_storage_migration(cx, "storage-migrated", migrate);
+        let expected = NameV2 {
+            first: "Ada".to_string(),
+            last: "Lovelace".to_string(),
+        };
+        assert_eq!(name.get(), Some(expected));
+        assert_eq!(
+            storage().get_item("storage-migrated").unwrap().as_deref(),
+            Some(r#"{"first":"Ada","last":"Lovelace"}"#)
+        );
+    })
+    .dispose();
+}
+
+#[wasm_bindgen_test]
+fn current_values_are_read_and_writes_are_stored() {
+    storage()
+        .set_item("storage-current", r#"{"first":"Grace","last":"Hopper"}"#)
+        .unwrap();
+
+    create_scope(create_runtime(), |cx| {
+        let (name, set_name) = use_local_storage_migration(cx, "storage-current", migrate);
+        assert_eq!(name.get().unwrap().first, "Grace");
+
+        set_name.set(Some(NameV2 {
+            first: "Alan".to_string(),
+            last: "Turing".to_string(),
+        }));
+        assert_eq!(
+            storage().get_item("storage-current").unwrap().as_deref(),
+            Some(r#"{"first":"Alan","last":"Turing"}"#)
+        );
+
+        set_name.set(None);
+        assert_eq!(storage().get_item("storage-current").unwrap(), None);
+    })
+    .dispose();
+}
@@ -0,0 +1,128 @@
+#![cfg(not(any(feature = "csr", feature = "hydrate")))]
+
+use futures::StreamExt;
+use leptos_dom::*;
+use leptos_reactive::SuspenseContext;
+
+const TEMPLATE: &str = r#"<!DOCTYPE html><html><head><meta charset="utf-8"/><!--leptos-head--></head><body><!--leptos-app--><footer></footer><!--leptos-scripts--></body></html>"#;
+
+#[test]
+fn renders_into_each_marker() {
+    let html = render_to_string_in_template(
+        TEMPLATE,
+        "<!--leptos-app-->",
+        |_| "<main>app</main>".to_string(),
+        |_| "<title>Page</title>".to_string(),
+    )
+    .unwrap();
+    assert_eq!(
+        html,
+        r#"<!DOCTYPE html><html><head><meta charset="utf-8"/><title>Page</title></head><body><main>app</main><footer></footer><!--leptos-scripts--></body></html>"#
+    );
+
+    let chunks = futures::executor::block_on(
+        render_to_stream_in_template(
+            TEMPLATE,
+            "<!--leptos-app-->",
+            |_| "<main>app</main>".to_string(),
+            |_| "<title>Page</title>".to_string(),
+        )
+        .unwrap()
+        .collect::<Vec<_>>(),
+    );
+    let html = chunks.concat();
+    let head = html.find("<title>Page</title>").unwrap();
+    let app = html.find("<main>app</main>").unwrap();
+    let scripts = html.find("__LEPTOS_PENDING_RESOURCES").unwrap();
+    let footer = html.find("<footer>").unwrap();
+    assert!(head < html.find("</head>").unwrap());
+    assert!(app < footer && footer < scripts);
+    assert!(scripts < html.find("</body>").unwrap());
+    assert!(chunks[0].ends_with("<title>Page</title>"));
+}
+
+#[test]
+fn missing_markers_are_listed() {
+    let template = "<html><head></head><body></body></html>";
+    assert_eq!(
+        render_to_string_in_template(
+            template,
+            "<!--leptos-app-->",
+            |_| String::new(),
+            |_| String::new()
+        ),
+        Err(TemplateError::MissingMarkers(vec![
+            HEAD_MARKER.to_string(),
+            "<!--leptos-app-->".to_string()
+        ]))
+    );
+
+    let template = "<html><head><!--leptos-head--></head><body></body></html>";
+    let err = render_to_stream_in_template(
+        template,
+        "<!--leptos-app-->",
+        |_| String::new(),
+        |_| String::new(),
+    )
+    .err()
+    .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "the HTML template is missing the markers <!--leptos-app-->, <!--leptos-scripts-->"
+    );
+}
+
+#[test]
+fn fragments_are_streamed_before_the_tail() {
+    let stream = render_to_stream_in_template(
+        TEMPLATE,
+        "<!--leptos-app-->",
+        |cx| {
+            cx.register_suspense(SuspenseContext::new(cx), "fragment", || {
+                "<p>loaded</p>".to_string()
+            });
+            r#"<div data-fragment-id="fragment">loading</div>"#.to_string()
+        },
+        |_| String::new(),
+    )
+    .unwrap();
+    let chunks = futures::executor::block_on(stream.collect::<Vec<_>>());
+
+    let fragment = chunks
+        .iter()
+        .position(|chunk| chunk.contains("<p>loaded</p>"))
+        .unwrap();
+    let tail = chunks
+        .iter()
+        .position(|chunk| chunk.contains("</body>"))
+        .unwrap();
+    assert!(fragment < tail);
+    assert_eq!(chunks[tail], "</body></html>");
+}
+
+#[test]
+fn fragments_of_disposed_scopes_are_skipped() {
+    let stream = render_to_stream_in_template(
+        TEMPLATE,
+        "<!--leptos-app-->",
+        |cx| {
+            let (_, disposer) = cx.run_child_scope(|cx| {
+                let context = SuspenseContext::new(cx);
+                context.increment();
+                cx.register_suspense(context, "fragment", || "<p>stale</p>".to_string());
+            });
+            // e.g. the request was aborted before the fragment's resources resolved
+            disposer.dispose();
+            r#"<div data-fragment-id="fragment">loading</div>"#.to_string()
+        },
+        |_| String::new(),
+    )
+    .unwrap();
+    let chunks = futures::executor::block_on(stream.collect::<Vec<_>>());
+
+    assert!(chunks.iter().all(|chunk| !chunk.contains("stale")));
+    assert!(chunks
+        .iter()
+        .all(|chunk| !chunk.contains(r#"<template id="fragment">"#)));
+    assert_eq!(chunks.last().unwrap(), "</body></html>");
+}
@@ -0,0 +1,140 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use std::{cell::RefCell, rc::Rc};
+
+use leptos_dom::*;
+use leptos_reactive::{
+    create_effect, create_runtime, create_scope, create_signal, ReadSignal, Scope,
+};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const ATTRS: [&str; 6] = ["data-a", "data-b", "data-c", "data-d", "data-e", "data-f"];
+
+fn mounted(tag: &str) -> web_sys::Element {
+    let el = document().create_element(tag).unwrap();
+    document().body().unwrap().append_child(&el).unwrap();
+    el
+}
+
+// an element with every attribute in `ATTRS` bound to the signal
+fn bound_element(cx: Scope, parent: &web_sys::Element, value: ReadSignal<i32>) -> web_sys::Element {
+    let el = create_element("span");
+    parent.append_child(&el).unwrap();
+    for name in ATTRS {
+        attribute(cx, &el, name, (move || value.get()).into_attribute(cx));
+    }
+    el
+}
+
+fn observe(target: &web_sys::Element) -> web_sys::MutationObserver {
+    let observer = web_sys::MutationObserver::new(&js_sys::Function::new_no_args("")).unwrap();
+    let init = web_sys::MutationObserverInit::new();
+    init.set_attributes(true);
+    init.set_subtree(true);
+    observer.observe_with_options(target, &init).unwrap();
+    observer
+}
+
+fn mutated_targets(observer: &web_sys::MutationObserver) -> Vec<web_sys::Node> {
+    observer
+        .take_records()
+        .iter()
+        .map(|record| {
+            record
+                .unchecked_into::<web_sys::MutationRecord>()
+                .target()
+                .unwrap()
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn writes_to_each_element_are_applied_together() {
+    let parent = mounted("div");
+    let observer = observe(&parent);
+    let targets = Rc::new(RefCell::new(Vec::new()));
+
+    create_scope(create_runtime(), {
+        let targets = Rc::clone(&targets);
+        move |cx| {
+            let (value, set_value) = create_signal(cx, 0);
+            let els = (0..3)
+                .map(|_| bound_element(cx, &parent, value))
+                .collect::<Vec<_>>();
+            // discard the initial render
+            mutated_targets(&observer);
+
+            set_value.set(1);
+            *targets.borrow_mut() = mutated_targets(&observer);
+
+            for el in els {
+                for name in ATTRS {
+                    assert_eq!(el.get_attribute(name).as_deref(), Some("1"));
+                }
+            }
+        }
+    })
+    .dispose();
+
+    let targets = targets.borrow();
+    assert_eq!(targets.len(), 3 * ATTRS.len());
+    let batches = targets.chunks(ATTRS.len()).collect::<Vec<_>>();
+    for batch in &batches {
+        assert!(batch.iter().all(|target| target == &batch[0]));
+    }
+    assert!(batches[0][0] != batches[1][0] && batches[1][0] != batches[2][0]);
+}
+
+#[wasm_bindgen_test]
+fn effects_see_written_values() {
+    let parent = mounted("div");
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    create_scope(create_runtime(), {
+        let seen = Rc::clone(&seen);
+        move |cx| {
+            let (value, set_value) = create_signal(cx, 0);
+            let el = bound_element(cx, &parent, value);
+            create_effect(cx, move |_| {
+                value.get();
+                seen.borrow_mut().push(el.get_attribute("data-f"));
+            });
+
+            set_value.set(1);
+            set_value.set(2);
+        }
+    })
+    .dispose();
+
+    assert_eq!(
+        *seen.borrow(),
+        vec![Some("0".into()), Some("1".into()), Some("2".into())]
+    );
+}
+
+// not an assertion, but logs the time taken so changes to the write path can be compared
+#[wasm_bindgen_test]
+fn benchmark_six_attributes_on_500_elements() {
+    let parent = mounted("div");
+
+    create_scope(create_runtime(), move |cx| {
+        let (value, set_value) = create_signal(cx, 0);
+        for _ in 0..500 {
+            bound_element(cx, &parent, value);
+        }
+
+        let performance = window().performance().unwrap();
+        let start = performance.now();
+        for n in 1..=100 {
+            set_value.set(n);
+        }
+        let elapsed = performance.now() - start;
+        console_log(&format!(
+            "toggling 6 attributes × 500 elements 100 times took {elapsed:.1}ms"
+        ));
+    })
+    .dispose();
+}
@@ -158,6 +158,38 @@ impl ToTokens for InlinePropsBody {
             (quote! { #lifetime, }, fn_generics, quote! { #generics })
         };
 
+        // the props can be cloned whenever all of the arguments can, which lets the `view` macro
+        // mount the component again in strict mode; the bounds are higher-ranked so that they
+        // are checked where the props are used, rather than failing to compile here
+        let (impl_generics, ty_generics, _) = generics.split_for_impl();
+        let mut clone_where_clause = where_clause
+            .clone()
+            .unwrap_or_else(|| parse_quote! { where });
+        let mut cloned_fields = Vec::new();
+        for arg in inputs {
+            if let FnArg::Typed(PatType { pat, ty, .. }) = arg {
+                if let Pat::Ident(PatIdent { ident, .. }) = &**pat {
+                    clone_where_clause
+                        .predicates
+                        .push(parse_quote! { for<'__leptos_clone> #ty: ::core::clone::Clone });
+                    cloned_fields.push(ident);
+                }
+            }
+        }
+        let clone_impl = (cloned_fields.len() == inputs.len()).then(|| {
+            quote! {
+                impl #impl_generics ::core::clone::Clone for #struct_name #ty_generics
+                #clone_where_clause
+                {
+                    fn clone(&self) -> Self {
+                        Self {
+                            #(#cloned_fields: ::core::clone::Clone::clone(&self.#cloned_fields)),*
+                        }
+                    }
+                }
+            }
+        });
+
         out_tokens.append_all(quote! {
             #modifiers
             #[allow(non_camel_case_types)]
@@ -167,6 +199,8 @@ impl ToTokens for InlinePropsBody {
                 #(#fields),*
             }
 
+            #clone_impl
+
             #[allow(non_snake_case)]
             #(#attrs)*
             #vis fn #ident #fn_generics (#cx_token: Scope, props: #struct_name #struct_generics) #output
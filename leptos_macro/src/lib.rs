@@ -92,6 +92,9 @@ mod server;
 ///    Attributes can take a wide variety of primitive types that can be converted to strings. They can also
 ///    take an `Option`, in which case `Some` sets the attribute and `None` removes the attribute.
 ///
+///    A child can also be an `async` block, like `{async { fetch_name().await }}`: nothing is rendered until
+///    it resolves, and then its output is rendered in its place (see `async_child`).
+///
 /// ```rust
 /// # use leptos_reactive::*; use leptos_dom::*; use leptos_macro::view; use leptos_dom::wasm_bindgen::JsCast; use leptos_dom as leptos; use leptos_dom::Marker;
 /// # run_scope(create_runtime(), |cx| {
@@ -130,9 +133,35 @@ mod server;
 /// # });
 /// ```
 ///
+///    Listener options can be added as modifiers after the event name: `:passive`, `:capture`, and `:once`
+///    (see `EventOptions`). The `:prevent` and `:stop` modifiers call `preventDefault()` and `stopPropagation()`
+///    on the event before running the handler.
+///
+///    The type of the event depends on its name and on the element it's fired at: `on:submit` takes a
+///    `SubmitEvent`, and `on:progress` takes a `ProgressEvent`, except on `<audio>` and `<video>`, which fire a plain `Event`.
+/// ```rust
+/// # use leptos_reactive::*; use leptos_dom::*; use leptos_macro::view; use leptos_dom::wasm_bindgen::JsCast;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// view! {
+///   cx,
+///   <div on:wheel:passive=|ev: web_sys::WheelEvent| log::debug!("scrolled {}", ev.delta_y())>
+///     <button on:click:once=|_| log::debug!("only the first click counts")>"Click me"</button>
+///     <form on:submit:prevent=|ev| log::debug!("submitted by {:?}", ev.submitter())>
+///       <button type="submit">"Submit"</button>
+///     </form>
+///   </div>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
 /// 6. DOM properties can be set with `prop:` attributes, which take any primitive type or `JsValue` (or a signal
 ///    that returns a primitive or JsValue). They can also take an `Option`, in which case `Some` sets the property
-///    and `None` deletes the property.
+///    and `None` deletes the property. Properties can't be set during server rendering, but the initial value of
+///    a reflected property (like `value`, `checked`, or `disabled`) is rendered as the matching attribute, unless the
+///    element already sets that attribute itself.
 /// ```rust
 /// # use leptos_reactive::*; use leptos_dom::*; use leptos_macro::view; use leptos_dom::wasm_bindgen::JsCast;
 /// # run_scope(create_runtime(), |cx| {
@@ -193,6 +222,46 @@ mod server;
 /// # });
 /// ```
 ///
+/// 9. SVG attributes that are camelCase (`viewBox`), hyphenated (`stroke-width`), or prefixed (`xlink:href`)
+/// can also be written in `snake_case`, which makes it easier to set them dynamically. Prefixed attributes
+/// are set in the XLink or XML namespace, as the browser expects.
+/// ```rust
+/// # use leptos_reactive::*; use leptos_dom::*; use leptos_macro::view; use leptos_dom::wasm_bindgen::JsCast;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let (width, set_width) = create_signal(cx, 1);
+/// view! { cx,
+///   <svg view_box="0 0 100 100">
+///     <circle cx="50" cy="50" r="40" stroke_width=move || width()/>
+///     <image xlink_href="cat.png" width="100" height="100"/>
+///   </svg>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
+/// 10. The `open` state of a `<details>` or `<dialog>` can be bound to an [RwSignal](leptos::RwSignal)
+/// with `bind:open`, which opens or closes the element when the signal changes, and updates the signal
+/// when the user opens or closes it. When hydrating, attributes that the browser or the user may already
+/// have acted on, like `autofocus`, `autoplay`, or `open`, keep the element’s state rather than being
+/// applied again (see [add_stateful_attribute](leptos_dom::add_stateful_attribute)).
+/// ```rust
+/// # use leptos_reactive::*; use leptos_dom::*; use leptos_macro::view; use leptos_dom::wasm_bindgen::JsCast;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let open = create_rw_signal(cx, false);
+/// view! { cx,
+///   <details bind:open=open>
+///     <summary>"More"</summary>
+///     <input type="text" autofocus/>
+///   </details>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
 /// Here’s a simple example that shows off several of these features, put together
 /// ```rust
 /// # use leptos_reactive::*; use leptos_dom::*; use leptos_macro::*; use leptos_dom as leptos; use leptos_dom::Marker; use leptos_dom::wasm_bindgen::JsCast;
@@ -64,8 +64,9 @@ mod struct_info {
 
     use super::field_info::{FieldBuilderAttr, FieldInfo};
     use super::util::{
-        empty_type, empty_type_tuple, expr_to_single_string, make_punctuated_single,
-        modify_types_generics_hack, path_to_single_string, strip_raw_ident_prefix, type_tuple,
+        empty_type, empty_type_tuple, event_handler_event_type, expr_to_single_string,
+        make_punctuated_single, modify_types_generics_hack, path_to_single_string,
+        strip_raw_ident_prefix, type_tuple,
     };
 
     #[derive(Debug)]
@@ -363,6 +364,13 @@ mod struct_info {
             } else {
                 field_type
             };
+            // an `EventHandler<E>` prop takes any `Fn(E)`, so that the type of a closure's
+            // event can be inferred
+            let event_type = (field.builder_attr.setter.auto_into.is_none()
+                && field.builder_attr.setter.transform.is_none())
+            .then(|| event_handler_event_type(arg_type))
+            .flatten();
+
             let (arg_type, arg_expr) = if field.builder_attr.setter.auto_into.is_some() {
                 (
                     quote!(impl ::core::convert::Into<#arg_type>),
@@ -377,6 +385,17 @@ mod struct_info {
                     let params = transform.params.iter().map(|(pat, ty)| quote!(#pat: #ty));
                     let body = &transform.body;
                     (quote!(#(#params),*), quote!({ #body }))
+                } else if let Some(event_type) = event_type {
+                    let handler = quote!(<#arg_type>::new(#field_name));
+                    let arg_expr = if field.builder_attr.setter.strip_option.is_some() {
+                        quote!(Some(#handler))
+                    } else {
+                        handler
+                    };
+                    (
+                        quote!(#field_name: impl Fn(#event_type) + 'static),
+                        arg_expr,
+                    )
                 } else if field.builder_attr.setter.strip_option.is_some() {
                     (quote!(#field_name: #arg_type), quote!(Some(#arg_expr)))
                 } else {
@@ -1173,6 +1192,24 @@ mod field_info {
 mod util {
     use quote::ToTokens;
 
+    /// If the type is an `EventHandler<E>`, returns `E`.
+    pub fn event_handler_event_type(ty: &syn::Type) -> Option<&syn::Type> {
+        let syn::Type::Path(type_path) = ty else {
+            return None;
+        };
+        let segment = type_path.path.segments.last()?;
+        if segment.ident != "EventHandler" {
+            return None;
+        }
+        let syn::PathArguments::AngleBracketed(generic_params) = &segment.arguments else {
+            return None;
+        };
+        match generic_params.args.first()? {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }
+    }
+
     pub fn path_to_single_string(path: &syn::Path) -> Option<String> {
         if path.leading_colon.is_some() {
             return None;
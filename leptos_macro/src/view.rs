@@ -21,6 +21,17 @@ const NON_BUBBLING_EVENTS: [&str; 11] = [
     "loadend",
 ];
 
+// DOM properties whose initial value can be rendered as an attribute of the same name
+const REFLECTED_PROPERTIES: [&str; 7] = [
+    "value",
+    "checked",
+    "selected",
+    "disabled",
+    "hidden",
+    "multiple",
+    "required",
+];
+
 lazy_static::lazy_static! {
     // Specialized event type
     // https://github.com/yewstack/yew/blob/d422b533ea19a09cddf9b31ecd6cd5e5ce35ce3f/packages/yew/src/html/listener/events.rs
@@ -92,6 +103,81 @@ lazy_static::lazy_static! {
         m.insert("transitionstart", "TransitionEvent");
         m
     };
+
+    // Events whose type depends on the element they're fired at, which take precedence over `EVENTS`
+    static ref ELEMENT_EVENTS: HashMap<&'static str, HashMap<&'static str, &'static str>> = {
+        let mut m = HashMap::new();
+        // media elements fire plain events while loading, rather than `ProgressEvent`s
+        let media = HashMap::from([("loadstart", "Event"), ("progress", "Event")]);
+        m.insert("audio", media.clone());
+        m.insert("video", media);
+        // no text is inserted when an option is chosen, so this isn't an `InputEvent`
+        m.insert("select", HashMap::from([("input", "Event")]));
+        m
+    };
+
+    // SVG attributes whose names can't be written as-is, by the snake_case names they can be
+    // written with instead, e.g., `view_box` for `viewBox`
+    static ref SVG_ATTRIBUTES: HashMap<String, &'static str> = {
+        let camel_case = [
+            "attributeName", "baseFrequency", "calcMode", "clipPathUnits", "diffuseConstant",
+            "edgeMode", "filterUnits", "gradientTransform", "gradientUnits", "kernelMatrix",
+            "kernelUnitLength", "keyPoints", "keySplines", "keyTimes", "lengthAdjust",
+            "limitingConeAngle", "markerHeight", "markerUnits", "markerWidth",
+            "maskContentUnits", "maskUnits", "numOctaves", "pathLength",
+            "patternContentUnits", "patternTransform", "patternUnits", "pointsAtX", "pointsAtY",
+            "pointsAtZ", "preserveAlpha", "preserveAspectRatio", "primitiveUnits", "refX", "refY",
+            "repeatCount", "repeatDur", "requiredExtensions", "specularConstant",
+            "specularExponent", "spreadMethod", "startOffset", "stdDeviation", "stitchTiles",
+            "surfaceScale", "systemLanguage", "tableValues", "targetX", "targetY", "textLength",
+            "viewBox", "xChannelSelector", "yChannelSelector", "zoomAndPan",
+        ];
+        let hyphenated = [
+            "alignment-baseline", "baseline-shift", "clip-path", "clip-rule",
+            "color-interpolation", "color-interpolation-filters", "dominant-baseline",
+            "fill-opacity", "fill-rule", "flood-color", "flood-opacity", "font-family",
+            "font-size", "font-size-adjust", "font-stretch", "font-style", "font-variant",
+            "font-weight", "image-rendering", "letter-spacing", "lighting-color", "marker-end",
+            "marker-mid", "marker-start", "paint-order", "pointer-events", "shape-rendering",
+            "stop-color", "stop-opacity", "stroke-dasharray", "stroke-dashoffset",
+            "stroke-linecap", "stroke-linejoin", "stroke-miterlimit", "stroke-opacity",
+            "stroke-width", "text-anchor", "text-decoration", "text-rendering",
+            "transform-origin", "unicode-bidi", "vector-effect", "word-spacing", "writing-mode",
+        ];
+        // these are in the XLink and XML namespaces, which `leptos_dom` sets them in
+        let prefixed = [
+            "xlink:actuate", "xlink:arcrole", "xlink:href", "xlink:role", "xlink:show",
+            "xlink:title", "xlink:type", "xml:lang", "xml:space",
+        ];
+
+        let mut m = HashMap::new();
+        for name in camel_case {
+            let snake_case = name.chars().fold(String::new(), |mut snake_case, c| {
+                if c.is_ascii_uppercase() {
+                    snake_case.push('_');
+                }
+                snake_case.push(c.to_ascii_lowercase());
+                snake_case
+            });
+            m.insert(snake_case, name);
+        }
+        for name in hyphenated.into_iter().chain(prefixed) {
+            m.insert(name.replace(['-', ':'], "_"), name);
+        }
+        m
+    };
+}
+
+/// The `web_sys` type of the event with this name, when it's fired at an element with this tag.
+fn event_type(tag: &str, event_name: &str) -> TokenStream {
+    let event_type = ELEMENT_EVENTS
+        .get(tag)
+        .and_then(|events| events.get(event_name))
+        .or_else(|| EVENTS.get(event_name))
+        .copied()
+        .unwrap_or("Event");
+    let event_type = Ident::new(event_type, Span::call_site());
+    quote! { web_sys::#event_type }
 }
 
 pub(crate) fn render_view(cx: &Ident, nodes: &[Node], mode: Mode) -> TokenStream {
@@ -299,18 +385,20 @@ fn element_to_tokens(
     }
 
     // for SSR: merge all class: attributes and class attribute
+    let mut class_expressions = Vec::new();
     if mode == Mode::Ssr {
         let class_attr = attributes(node)
             .find(|a| a.key.to_string() == "class")
             .map(|node| {
+                let value = node
+                    .value
+                    .as_ref()
+                    .and_then(|n| String::try_from(n).ok())
+                    .unwrap_or_default();
+                // escaped here, as the value is pushed to the buffer as-is
                 (
                     node.key.span(),
-                    node.value
-                        .as_ref()
-                        .and_then(|n| String::try_from(n).ok())
-                        .unwrap_or_default()
-                        .trim()
-                        .to_string(),
+                    leptos_dom::escape_attr(value.trim()).into_owned(),
                 )
             });
 
@@ -328,7 +416,7 @@ fn element_to_tokens(
                     let span = node.key.span();
                     Some(quote_spanned! { 
                         span => leptos_buffer.push(' ');
-                            leptos_buffer.push_str(&{#value}.into_class(#cx).as_value_string(#name));
+                            leptos_buffer.push_str(#cx.untrack(|| {#value}.into_class(#cx).as_value_string(#name)));
                     })
                 } else {
                     None
@@ -337,36 +425,65 @@ fn element_to_tokens(
             .collect::<Vec<_>>();
 
         if class_attr.is_some() || !class_attrs.is_empty() {
-            expressions.push(quote::quote_spanned! {
+            class_expressions.push(quote::quote_spanned! {
                 span => leptos_buffer.push_str(" class=\"");
             });
             if let Some((span, value)) = class_attr {
-                expressions.push(quote::quote_spanned! {
+                class_expressions.push(quote::quote_spanned! {
                     span => leptos_buffer.push_str(#value);
                 });
             }
-            for attr in class_attrs {
-                expressions.push(attr);
-            }
-            expressions.push(quote::quote_spanned! {
+            class_expressions.extend(class_attrs);
+            class_expressions.push(quote::quote_spanned! {
                 span => leptos_buffer.push('"');
             });
         }
     }
 
-    // attributes
+    // attributes, in the order they're declared, so that the same view always renders the same HTML
     for attr in attributes(node) {
-        // SSR class attribute has just been handled
-        if !(mode == Mode::Ssr && attr.key.to_string() == "class") {
-            attr_to_tokens(
-                cx,
-                attr,
-                &this_el_ident,
-                template,
-                expressions,
-                mode,
-            );
+        let name = attr.key.to_string();
+        if mode == Mode::Ssr {
+            // SSR: the merged classes go where the first of them is declared
+            let is_class =
+                name == "class" || name.starts_with("class:") || name.starts_with("class-");
+            if is_class {
+                expressions.append(&mut class_expressions);
+                if name == "class" {
+                    continue;
+                }
+            }
+
+            // SSR: properties can't be set, so reflect the ones that have a matching
+            // attribute, unless that attribute has been given explicitly or the value can't be
+            // an attribute
+            if let Some(prop_name) = name.strip_prefix("prop:") {
+                let is_reflected = REFLECTED_PROPERTIES.contains(&prop_name);
+                let has_attr = attributes(node).any(|a| a.key.to_string() == prop_name);
+                if is_reflected && !has_attr {
+                    let value = attr.value.as_ref().expect("prop: blocks need values").as_ref();
+                    let span = attr.key.span();
+                    expressions.push(quote_spanned! {
+                        span => leptos_buffer.push_str(&#cx.untrack(|| {
+                                    // renders nothing if the value can't be an attribute
+                                    use leptos_dom::{ReflectAsAttribute as _, ReflectNothing as _};
+                                    let value = leptos_dom::PropertyReflection(std::cell::Cell::new(Some({#value})));
+                                    (&value).reflect_as_attribute(#cx, #prop_name)
+                                }));
+                    });
+                }
+            }
         }
+
+        attr_to_tokens(
+            cx,
+            attr,
+            &node.name.to_string(),
+            &this_el_ident,
+            template,
+            expressions,
+            mode,
+        );
     }
 
     // navigation for this el
@@ -452,6 +569,7 @@ fn element_to_tokens(
             multi,
             mode,
             idx == 0,
+            idx > 0 && is_dynamic_child(&node.children[idx - 1]),
         );
 
         prev_sib = match curr_id {
@@ -477,6 +595,39 @@ fn element_to_tokens(
     this_el_ident
 }
 
+// whether the child is rendered by inserting it at runtime, rather than being part of the template
+fn is_dynamic_child(node: &Node) -> bool {
+    match node {
+        Node::Element(node) => is_component_node(node),
+        Node::Block(node) => literal_value(node.value.as_ref()).is_none(),
+        _ => false,
+    }
+}
+
+fn literal_value(value: &syn::Expr) -> Option<String> {
+    match value {
+        syn::Expr::Lit(lit) => match &lit.lit {
+            syn::Lit::Str(s) => Some(s.value()),
+            syn::Lit::Char(c) => Some(c.value().to_string()),
+            syn::Lit::Int(i) => Some(i.base10_digits().to_string()),
+            syn::Lit::Float(f) => Some(f.base10_digits().to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn async_block(value: &syn::Expr) -> Option<&syn::ExprAsync> {
+    match value {
+        syn::Expr::Async(block) => Some(block),
+        syn::Expr::Block(block) => match block.block.stmts.as_slice() {
+            [syn::Stmt::Expr(expr)] => async_block(expr),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn next_sibling_node(children: &[Node], idx: usize, next_el_id: &mut usize) -> Option<Ident> {
     if children.len() <= idx {
         None
@@ -501,6 +652,7 @@ fn next_sibling_node(children: &[Node], idx: usize, next_el_id: &mut usize) -> O
 fn attr_to_tokens(
     cx: &Ident,
     node: &NodeAttribute,
+    tag: &str,
     el_id: &Ident,
     template: &mut String,
     expressions: &mut Vec<TokenStream>,
@@ -517,6 +669,10 @@ fn attr_to_tokens(
     } else {
         name
     };
+    let name = match SVG_ATTRIBUTES.get(name.as_str()) {
+        Some(svg_name) => svg_name.to_string(),
+        None => name,
+    };
     let value = match &node.value {
         Some(expr) => match expr.as_ref() {
             syn::Expr::Lit(expr_lit) => {
@@ -560,32 +716,38 @@ fn attr_to_tokens(
             .as_ref();
 
         let name = name.replacen("on:", "", 1);
-        let event_type = EVENTS.get(&name.as_str()).copied().unwrap_or("Event");
-        let event_type = event_type.parse::<TokenStream>().expect("couldn't parse event name");
+        let (name, options, wrappers) = event_name_and_options(&name, span);
+        let event_type = event_type(tag, name);
 
         if mode != Mode::Ssr {
-            if NON_BUBBLING_EVENTS.contains(&name.as_str()) {
-                expressions.push(quote_spanned! {
-                    span => ::leptos::add_event_listener_undelegated::<web_sys::#event_type>(#el_id.unchecked_ref(), #name, #handler);
-                });
-            } else {
-                expressions.push(quote_spanned! {
-                    span => ::leptos::add_event_listener::<web_sys::#event_type>(#el_id.unchecked_ref(), #name, #handler);
-                });
+            let handler = wrappers.wrap(handler, &event_type, span);
+            match (NON_BUBBLING_EVENTS.contains(&name), options) {
+                (true, None) => expressions.push(quote_spanned! {
+                    span => ::leptos::add_event_listener_undelegated::<#event_type>(#el_id.unchecked_ref(), #name, #handler);
+                }),
+                (true, Some(options)) => expressions.push(quote_spanned! {
+                    span => ::leptos::add_event_listener_undelegated_with_options::<#event_type>(#el_id.unchecked_ref(), #name, #handler, #options);
+                }),
+                (false, None) => expressions.push(quote_spanned! {
+                    span => ::leptos::add_event_listener::<#event_type>(#el_id.unchecked_ref(), #name, #handler);
+                }),
+                (false, Some(options)) => expressions.push(quote_spanned! {
+                    span => ::leptos::add_event_listener_with_options::<#event_type>(#el_id.unchecked_ref(), #name, #handler, #options);
+                }),
             }
         } else {
             
             // this is here to avoid warnings about unused signals
             // that are used in event listeners. I'm open to better solutions.
             expressions.push(quote_spanned! {
-                span => let _  = ssr_event_listener::<web_sys::#event_type>(#handler);
+                span => let _  = ssr_event_listener::<#event_type>(#handler);
             });
         }
     }
     // Properties
     else if name.starts_with("prop:") {
         let name = name.replacen("prop:", "", 1);
-        // can't set properties in SSR
+        // can't set properties in SSR; reflected properties are handled in `element_to_tokens`
         if mode != Mode::Ssr {
             let value = node
                 .value
@@ -597,6 +759,28 @@ fn attr_to_tokens(
             });
         }
     }
+    // Two-way bindings
+    else if let Some(bind_name) = name.strip_prefix("bind:") {
+        if bind_name != "open" {
+            panic!("only `bind:open` is supported, on <details> and <dialog> elements")
+        }
+        let value = node
+            .value
+            .as_ref()
+            .expect("bind: attributes need a signal")
+            .as_ref();
+        if mode == Mode::Ssr {
+            expressions.push(quote_spanned! {
+                span => if #cx.untrack(|| #value.get()) {
+                            leptos_buffer.push_str(" open");
+                        }
+            });
+        } else {
+            expressions.push(quote_spanned! {
+                span => leptos_dom::bind_open(#cx, #el_id.unchecked_ref(), #value)
+            });
+        }
+    }
     // Classes
     else if name.starts_with("class:") {
         let name = name.replacen("class:", "", 1);
@@ -644,7 +828,7 @@ fn attr_to_tokens(
                 template.push(' ');
                 template.push_str(&name);
                 template.push_str("=\"");
-                template.push_str(&value);
+                template.push_str(&leptos_dom::escape_attr(&value));
                 template.push('"');
             }
 
@@ -652,7 +836,7 @@ fn attr_to_tokens(
             (AttributeValue::Dynamic(value), Mode::Ssr) => {
                 expressions.push(quote_spanned! {
                     span => leptos_buffer.push(' ');
-                            leptos_buffer.push_str(&{#value}.into_attribute(#cx).as_value_string(#name));
+                            leptos_buffer.push_str(&#cx.untrack(|| {#value}.into_attribute(#cx).as_value_string(#name)));
                 });
             }
             (AttributeValue::Dynamic(value), _) => {
@@ -666,6 +850,65 @@ fn attr_to_tokens(
     }
 }
 
+/// Splits an event listener name with modifiers (like `wheel:passive` in `on:wheel:passive`)
+/// into the event name, the `EventOptions` for the modifiers if there are any, and the
+/// wrappers for the `prevent` and `stop` modifiers.
+fn event_name_and_options(name: &str, span: Span) -> (&str, Option<TokenStream>, EventWrappers) {
+    let mut parts = name.split(':');
+    let event_name = parts.next().unwrap_or_default();
+    let (mut passive, mut capture, mut once) = (false, false, false);
+    let mut wrappers = EventWrappers::default();
+    let mut has_modifiers = false;
+    for modifier in parts {
+        match modifier {
+            "passive" => passive = true,
+            "capture" => capture = true,
+            "once" => once = true,
+            "prevent" => {
+                wrappers.prevent_default = true;
+                continue;
+            }
+            "stop" => {
+                wrappers.stop_propagation = true;
+                continue;
+            }
+            _ => panic!("unknown event modifier `{modifier}` on `on:{name}`; expected `passive`, `capture`, `once`, `prevent`, or `stop`"),
+        }
+        has_modifiers = true;
+    }
+    if passive && wrappers.prevent_default {
+        panic!("`on:{name}` can't be both `passive` and `prevent`: passive listeners can't prevent the default action");
+    }
+    let options = has_modifiers.then(|| {
+        quote_spanned! {
+            span => ::leptos::EventOptions { passive: #passive, capture: #capture, once: #once }
+        }
+    });
+    (event_name, options, wrappers)
+}
+
+/// The `prevent` and `stop` event modifiers, which wrap the handler in
+/// `prevent_default` and `stop_propagation`.
+#[derive(Default)]
+struct EventWrappers {
+    prevent_default: bool,
+    stop_propagation: bool,
+}
+
+impl EventWrappers {
+    fn wrap(&self, handler: &syn::Expr, event_type: &TokenStream, span: Span) -> TokenStream {
+        let mut handler = quote_spanned! { span => #handler };
+        if self.prevent_default {
+            handler = quote_spanned! { span => ::leptos::prevent_default::<#event_type>(#handler) };
+        }
+        if self.stop_propagation {
+            handler =
+                quote_spanned! { span => ::leptos::stop_propagation::<#event_type>(#handler) };
+        }
+        handler
+    }
+}
+
 enum AttributeValue<'a> {
     Static(String),
     Dynamic(&'a syn::Expr),
@@ -687,6 +930,7 @@ fn child_to_tokens(
     multi: bool,
     mode: Mode,
     is_first_child: bool,
+    follows_dynamic_child: bool,
 ) -> PrevSibChange {
     match node {
         Node::Element(node) => {
@@ -735,6 +979,7 @@ fn child_to_tokens(
             expressions,
             navigations,
             mode,
+            follows_dynamic_child,
         ),
         Node::Block(node) => block_to_tokens(
             cx,
@@ -749,6 +994,7 @@ fn child_to_tokens(
             expressions,
             navigations,
             mode,
+            follows_dynamic_child,
         ),
         _ => panic!("unexpected child node type"),
     }
@@ -768,19 +1014,15 @@ fn block_to_tokens(
     expressions: &mut Vec<TokenStream>,
     navigations: &mut Vec<TokenStream>,
     mode: Mode,
+    follows_dynamic_child: bool,
 ) -> PrevSibChange {
     let value = value.as_ref();
-    let str_value = match value {
-        syn::Expr::Lit(lit) => match &lit.lit {
-            syn::Lit::Str(s) => Some(s.value()),
-            syn::Lit::Char(c) => Some(c.value().to_string()),
-            syn::Lit::Int(i) => Some(i.base10_digits().to_string()),
-            syn::Lit::Float(f) => Some(f.base10_digits().to_string()),
-            _ => None,
-        },
-        _ => None,
+    let str_value = literal_value(value);
+    // a `Future` can't implement `IntoChild` alongside closures, so async blocks are wrapped here
+    let value = match async_block(value) {
+        Some(block) => quote_spanned! { span => ::leptos::async_child(#block) },
+        None => quote! { #value },
     };
-    let current: Option<Ident> = None;
 
     // code to navigate to this text node
 
@@ -836,14 +1078,17 @@ fn block_to_tokens(
     } else {
         // these markers are one of the primary templating differences across modes
         match mode {
-            // in CSR, simply insert a comment node: it will be picked up and replaced with the value
+            // in CSR, simply insert a comment node: it will be picked up and replaced with the value,
+            // unless a dynamic sibling just before this one needs it to stay as its `before` marker
             Mode::Client => {
                 template.push_str("<!>");
                 navigations.push(location);
 
-                let current = match current {
-                    Some(i) => quote! { Some(#i.into_child(#cx)) },
-                    None => quote! { None },
+                let current = match &name {
+                    Some(name) if !follows_dynamic_child => {
+                        quote! { Some(#name.clone().into_child(#cx)) }
+                    }
+                    _ => quote! { None },
                 };
                 expressions.push(quote! {
                     leptos::insert(
@@ -864,7 +1109,7 @@ fn block_to_tokens(
                 let co = comment_ident(*next_co_id, span);
                 //next_sib = Some(el.clone());
 
-                template.push_str("<!#><!/>");
+                template.push_str("<!--leptos--><!--/leptos-->");
                 navigations.push(quote! {
                     #location;
                     let (#el, #co) = #cx.get_next_marker(&#name);
@@ -885,9 +1130,12 @@ fn block_to_tokens(
             }
             // in SSR, it needs to insert the value, wrapped in comments
             Mode::Ssr => expressions.push(quote::quote_spanned! {
-                span => leptos_buffer.push_str("<!--#-->");
-                        leptos_buffer.push_str(&#value.into_child(#cx).as_child_string());
-                        leptos_buffer.push_str("<!--/-->");
+                span => {
+                    let leptos_marker_id = #cx.next_marker_id();
+                    leptos_buffer.push_str(&format!("<!--leptos-{leptos_marker_id}-->"));
+                    leptos_buffer.push_str(&#value.into_child(#cx).as_child_string());
+                    leptos_buffer.push_str(&format!("<!--/leptos-{leptos_marker_id}-->"));
+                }
             }),
         }
 
@@ -937,9 +1185,12 @@ fn component_to_tokens(
         if mode == Mode::Ssr {
             expressions.push(quote::quote_spanned! {
                 span => // TODO wrap components but use get_next_element() instead of first_child/next_sibling?
-                        leptos_buffer.push_str("<!--#-->");
-                        leptos_buffer.push_str(&#create_component.into_child(#cx).as_child_string());
-                        leptos_buffer.push_str("<!--/-->");
+                        {
+                            let leptos_marker_id = #cx.next_marker_id();
+                            leptos_buffer.push_str(&format!("<!--leptos-{leptos_marker_id}-->"));
+                            leptos_buffer.push_str(&#create_component.into_child(#cx).as_child_string());
+                            leptos_buffer.push_str(&format!("<!--/leptos-{leptos_marker_id}-->"));
+                        }
 
             });
         } else if mode == Mode::Hydrate {
@@ -966,7 +1217,7 @@ fn component_to_tokens(
 
             current = Some(el.clone());
 
-            template.push_str("<!#><!/>");
+            template.push_str("<!--leptos--><!--/leptos-->");
             navigations.push(quote! {
                 let (#el, #co) = #cx.get_next_marker(&#starts_at);
             });
@@ -1064,17 +1315,18 @@ fn create_component(cx: &Ident, node: &NodeElement, mode: Mode) -> TokenStream {
                 .as_ref()
                 .expect("on: event listener attributes need a value")
                 .as_ref();
+            let (event_name, options, wrappers) = event_name_and_options(event_name, span);
+            let options = options.unwrap_or_else(|| quote_spanned! { span => ::leptos::EventOptions::default() });
+            // a component could render any element, so only the name of the event is known
+            let event_type = event_type("", event_name);
+            let handler = wrappers.wrap(handler, &event_type, span);
             if NON_BUBBLING_EVENTS.contains(&event_name) {
                 Some(quote_spanned! {
-                    span => ::leptos::add_event_listener_undelegated(#component_name.unchecked_ref(), #event_name, #handler);
-                })
-            } else if let Some(event_type) = EVENTS.get(event_name).map(|&e| e.parse::<TokenStream>().unwrap_or_default()) {
-                Some(quote_spanned! {
-                    span => ::leptos::add_event_listener::<#event_type>(#component_name.unchecked_ref(), #event_name, #handler);
+                    span => ::leptos::add_event_listener_undelegated_with_options::<#event_type>(#component_name.unchecked_ref(), #event_name, #handler, #options);
                 })
             } else {
                 Some(quote_spanned! {
-                    span => ::leptos::add_event_listener::<web_sys::Event>(#component_name.unchecked_ref(), #event_name, #handler)
+                    span => ::leptos::add_event_listener_with_options::<#event_type>(#component_name.unchecked_ref(), #event_name, #handler, #options);
                 })
             }
         }
@@ -1096,28 +1348,25 @@ fn create_component(cx: &Ident, node: &NodeElement, mode: Mode) -> TokenStream {
             }) }
     }).peekable();
 
+    // mounted twice in strict mode if the props can be cloned; see `leptos_dom::ComponentProps`
     if other_attrs.peek().is_none() {
         quote_spanned! {
             span => create_component(#cx, move || {
-                #component_name(
-                    #cx,
-                    #component_props_name::builder()
-                        #(#props)*
-                        #children
-                        .build(),
-                )
+                let props = #component_props_name::builder()
+                    #(#props)*
+                    #children
+                    .build();
+                (&ComponentProps::new(props)).mount(#cx, #component_name)
             })
         }
     } else {
         quote_spanned! {
             span => create_component(#cx, move || {
-                let #component_name = #component_name(
-                    #cx,
-                    #component_props_name::builder()
-                        #(#props)*
-                        #children
-                        .build(),
-                );
+                let props = #component_props_name::builder()
+                    #(#props)*
+                    #children
+                    .build();
+                let #component_name = (&ComponentProps::new(props)).mount(#cx, #component_name);
                 #(#other_attrs);*;
                 #component_name
             })
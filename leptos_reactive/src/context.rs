@@ -3,7 +3,11 @@ use std::{
     collections::HashMap,
 };
 
-use crate::{runtime::with_runtime, Scope};
+use crate::{
+    debug_warn,
+    runtime::{with_runtime, Runtime},
+    Scope, ScopeId,
+};
 
 /// Provides a context value of type `T` to the current reactive [Scope](crate::Scope)
 /// and all of its descendants. This can be consumed using [use_context](crate::use_context).
@@ -48,13 +52,89 @@ where
     T: Clone + 'static,
 {
     let id = value.type_id();
-    with_runtime(cx.runtime, |runtime| {
+    let provided = with_runtime(cx.runtime, |runtime| {
         let mut contexts = runtime.scope_contexts.borrow_mut();
         let context = contexts.entry(cx.id).unwrap().or_insert_with(HashMap::new);
         context.insert(id, Box::new(value) as Box<dyn Any>);
+
+        // scopes below this one that already resolved `T` may now need to find it here instead
+        runtime.context_providers.borrow_mut().remove(&id);
     });
+    if let Err(e) = provided {
+        debug_warn!(
+            "[provide_context] The context of type {} wasn’t provided, because {e}.",
+            std::any::type_name::<T>()
+        );
+    }
+}
+
+/// Provides several context values to the current reactive [Scope](crate::Scope) and all of
+/// its descendants at once, rather than calling [provide_context] for each of them.
+///
+/// The values are given as a tuple, and each can be consumed with [use_context] by its type.
+/// If two values have the same type, the later one is provided.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Theme(&'static str);
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Locale(&'static str);
+///
+/// create_context_provider(cx, (Theme("dark"), Locale("en-GB")));
+///
+/// cx.child_scope(|cx| {
+///   assert_eq!(use_context::<Theme>(cx), Some(Theme("dark")));
+///   assert_eq!(use_context::<Locale>(cx), Some(Locale("en-GB")));
+/// });
+/// # }).dispose();
+/// ```
+///
+/// The [ctx_provider](crate::ctx_provider) macro does the same with a list of values.
+pub fn create_context_provider<T>(cx: Scope, values: T)
+where
+    T: ContextValues,
+{
+    values.provide(cx);
+}
+
+/// A tuple of context values, each of which is `Clone + 'static`, that can be provided
+/// at once with [create_context_provider].
+pub trait ContextValues {
+    /// Provides each value with [provide_context].
+    fn provide(self, cx: Scope);
 }
 
+macro_rules! impl_context_values {
+    ($($ty:ident),+) => {
+        impl<$($ty),+> ContextValues for ($($ty,)+)
+        where
+            $($ty: Clone + 'static),+
+        {
+            #[allow(non_snake_case)]
+            fn provide(self, cx: Scope) {
+                let ($($ty,)+) = self;
+                $(provide_context(cx, $ty);)+
+            }
+        }
+    };
+}
+
+impl_context_values!(A);
+impl_context_values!(A, B);
+impl_context_values!(A, B, C);
+impl_context_values!(A, B, C, D);
+impl_context_values!(A, B, C, D, E);
+impl_context_values!(A, B, C, D, E, F);
+impl_context_values!(A, B, C, D, E, F, G);
+impl_context_values!(A, B, C, D, E, F, G, H);
+impl_context_values!(A, B, C, D, E, F, G, H, I);
+impl_context_values!(A, B, C, D, E, F, G, H, I, J);
+impl_context_values!(A, B, C, D, E, F, G, H, I, J, K);
+impl_context_values!(A, B, C, D, E, F, G, H, I, J, K, L);
+
 /// Extracts a context value of type `T` from the reactive system by traversing
 /// it upwards, beginning from the current [Scope](crate::Scope) and iterating
 /// through its parents, if any. The context value should have been provided elsewhere
@@ -64,6 +144,9 @@ where
 /// hierarchy without needs to “prop drill” by passing them through each layer as
 /// arguments to a function or properties of a component.
 ///
+/// Returns `None` if no scope up the tree has provided a value of type `T`, or if the
+/// runtime has already been disposed.
+///
 /// ```
 /// # use leptos_reactive::*;
 /// # create_scope(create_runtime(), |cx| {
@@ -101,25 +184,50 @@ where
 {
     let id = TypeId::of::<T>();
     with_runtime(cx.runtime, |runtime| {
-        let local_value = {
-            let contexts = runtime.scope_contexts.borrow();
-            let context = contexts.get(cx.id);
-            context
-                .and_then(|context| context.get(&id).and_then(|val| val.downcast_ref::<T>()))
-                .cloned()
-        };
-        match local_value {
-            Some(val) => Some(val),
-            None => runtime
-                .scope_parents
-                .borrow()
-                .get(cx.id)
-                .and_then(|parent| {
-                    use_context::<T>(Scope {
-                        runtime: cx.runtime,
-                        id: *parent,
-                    })
-                }),
-        }
+        let provider = find_provider(runtime, cx.id, id)?;
+        let contexts = runtime.scope_contexts.borrow();
+        contexts
+            .get(provider)
+            .and_then(|context| context.get(&id))
+            .and_then(|val| val.downcast_ref::<T>())
+            .cloned()
     })
+    .ok()
+    .flatten()
+}
+
+// Finds the nearest scope, starting from `scope` and walking up through its parents, that has
+// provided a context of type `ty`. The result is cached for every scope visited along the way,
+// so that later lookups from the same scope or any of its descendants (like the rows of a list)
+// stop at the nearest scope that has already been resolved.
+fn find_provider(runtime: &Runtime, scope: ScopeId, ty: TypeId) -> Option<ScopeId> {
+    let mut providers = runtime.context_providers.borrow_mut();
+    let providers = providers.entry(ty).or_default();
+    let contexts = runtime.scope_contexts.borrow();
+    let parents = runtime.scope_parents.borrow();
+
+    let mut visited = Vec::new();
+    let mut current = Some(scope);
+    let mut provider = None;
+    while let Some(id) = current {
+        if let Some(cached) = providers.get(id) {
+            provider = *cached;
+            break;
+        }
+        if contexts
+            .get(id)
+            .map(|context| context.contains_key(&ty))
+            .unwrap_or(false)
+        {
+            provider = Some(id);
+            break;
+        }
+        visited.push(id);
+        current = parents.get(id).copied();
+    }
+
+    for id in visited {
+        providers.insert(id, provider);
+    }
+    provider
 }
@@ -0,0 +1,95 @@
+use crate::{runtime::with_runtime, Scope, ScopeProperty};
+
+/// A handle to something outside the reactive system (like a WebGL context, a worker, or a
+/// pool of database connections) that a [Scope] can own, so that it is disposed of along with
+/// the scope, just like its signals, effects, and resources. See [Scope::register_disposable].
+pub trait Disposable {
+    /// Releases whatever the handle holds. This is called at most once.
+    fn dispose(&mut self);
+
+    /// A name to tell the handle apart in diagnostics, like [Scope::lookup_disposable].
+    fn debug_name(&self) -> Option<String> {
+        None
+    }
+}
+
+slotmap::new_key_type! {
+    /// Unique ID assigned to a [Disposable] registered with [Scope::register_disposable].
+    pub struct DisposableId;
+}
+
+impl Scope {
+    /// Hands a [Disposable] to this scope, which will dispose of it when the scope is disposed:
+    /// after its cleanup functions have run, in the same order as (and alongside) the signals,
+    /// effects, resources, and other disposables the scope owns.
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// struct Worker(&'static str);
+    ///
+    /// impl Disposable for Worker {
+    ///     fn dispose(&mut self) {
+    ///         println!("terminating {}", self.0);
+    ///     }
+    /// }
+    ///
+    /// create_scope(create_runtime(), |cx| {
+    ///     cx.register_disposable(Worker("indexer"));
+    /// })
+    /// // prints "terminating indexer"
+    /// .dispose();
+    /// ```
+    ///
+    /// If the scope's runtime has already been disposed, the disposable is disposed of
+    /// straight away, as nothing would ever do it later.
+    pub fn register_disposable(&self, disposable: impl Disposable + 'static) -> DisposableId {
+        let mut disposable = Some(disposable);
+        let id = with_runtime(self.runtime, |runtime| {
+            runtime
+                .disposables
+                .borrow_mut()
+                .insert(Box::new(disposable.take().unwrap()))
+        });
+        match id {
+            Ok(id) => {
+                self.with_scope_property(|prop| prop.push(ScopeProperty::Custom(id)));
+                id
+            }
+            Err(_) => {
+                if let Some(mut disposable) = disposable {
+                    disposable.dispose();
+                }
+                DisposableId::default()
+            }
+        }
+    }
+
+    /// Disposes of a [Disposable] straight away, rather than waiting for the scope that owns it
+    /// to be disposed, and forgets about it. Returns `false` if it had already been disposed of.
+    pub fn take_disposable(&self, id: DisposableId) -> bool {
+        let disposable = with_runtime(self.runtime, |runtime| {
+            let disposable = runtime.disposables.borrow_mut().remove(id)?;
+            // usually owned by this scope, but it could have been registered with any other
+            let scopes = runtime.scopes.borrow();
+            let owner = scopes.get(self.id).into_iter().chain(scopes.values());
+            for owned in owner {
+                let mut owned = owned.borrow_mut();
+                if let Some(idx) = owned.iter().position(|p| *p == ScopeProperty::Custom(id)) {
+                    owned.remove(idx);
+                    break;
+                }
+            }
+            Some(disposable)
+        })
+        .ok()
+        .flatten();
+        // outside the runtime's borrows, in case it registers or disposes of anything itself
+        match disposable {
+            Some(mut disposable) => {
+                disposable.dispose();
+                true
+            }
+            None => false,
+        }
+    }
+}
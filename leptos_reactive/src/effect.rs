@@ -1,8 +1,11 @@
-use crate::runtime::{with_runtime, RuntimeId};
-use crate::{debug_warn, Runtime, Scope, ScopeProperty};
+use crate::runtime::{expect_runtime, with_runtime, RuntimeId};
+use crate::{debug_warn, on_cleanup, Runtime, Scope, ScopeId, ScopeProperty};
 use cfg_if::cfg_if;
 use std::cell::RefCell;
 use std::fmt::Debug;
+use std::panic::Location;
+use std::rc::Rc;
+use thiserror::Error;
 
 /// Effects run a certain chunk of code whenever the signals they depend on change.
 /// `create_effect` immediately runs the given function once, tracks its dependence
@@ -19,7 +22,8 @@ use std::fmt::Debug;
 ///
 /// By default, effects **do not run on the server**. This means you can call browser-specific
 /// APIs within the effect function without causing issues. If you need an effect to run on
-/// the server, use [create_isomorphic_effect].
+/// the server, use [create_isomorphic_effect]. (To find effects that would break if they did
+/// run on the server, see [SsrEffectPolicy::RunAndCatch].)
 /// ```
 /// # use leptos_reactive::*;
 /// # use log::*;
@@ -47,6 +51,7 @@ use std::fmt::Debug;
 /// # }
 /// # }).dispose();
 /// ```
+#[track_caller]
 pub fn create_effect<T>(cx: Scope, f: impl Fn(Option<T>) -> T + 'static)
 where
     T: Debug + 'static,
@@ -55,15 +60,99 @@ where
         if #[cfg(not(feature = "ssr"))] {
             create_isomorphic_effect(cx, f);
         } else {
-            // clear warnings
-            _ = cx;
-            _ = f;
+            let location = std::panic::Location::caller();
+            let policy = with_runtime(cx.runtime, |runtime| runtime.ssr_effect_policy.get());
+            match policy.unwrap_or_default() {
+                SsrEffectPolicy::Skip => _ = f,
+                SsrEffectPolicy::RunAndCatch => {
+                    create_isomorphic_effect(cx, catch_browser_api_panics(cx, location, f))
+                }
+            }
+        }
+    }
+}
+
+/// How [create_effect] treats effects while rendering on the server, as set with
+/// [RuntimeId::set_ssr_effect_policy].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SsrEffectPolicy {
+    /// Effects don’t run on the server, so they can use browser APIs freely.
+    #[default]
+    Skip,
+    /// Effects run on the server, like [create_isomorphic_effect]. If an effect panics because
+    /// it uses a browser API, like `window()`, the panic is caught and logged as an
+    /// [SsrEffectError] (once for each place such an effect is created), the effect is not
+    /// run again, and rendering carries on. Any other panic is not caught.
+    RunAndCatch,
+}
+
+/// An effect that panicked while rendering on the server, because it used a browser API.
+/// See [SsrEffectPolicy::RunAndCatch].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error(
+    "effect created at {location} in scope {scope} uses a browser API, so it can’t run on \
+     the server: {message}"
+)]
+pub struct SsrEffectError {
+    /// Where the effect was created.
+    pub location: &'static Location<'static>,
+    /// The scope that owns the effect.
+    pub scope: ScopeId,
+    /// The panic message.
+    pub message: String,
+}
+
+// what wasm-bindgen panics with when a browser API is called outside the browser
+#[cfg(feature = "ssr")]
+const BROWSER_API_PANIC: &str = "cannot call wasm-bindgen imported functions on non-wasm targets";
+
+#[cfg(feature = "ssr")]
+fn catch_browser_api_panics<T>(
+    cx: Scope,
+    location: &'static Location<'static>,
+    f: impl Fn(Option<T>) -> T,
+) -> impl Fn(Option<Option<T>>) -> Option<T> {
+    use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+
+    let failed = std::cell::Cell::new(false);
+    move |prev| {
+        if failed.get() {
+            return None;
+        }
+        let payload = match catch_unwind(AssertUnwindSafe(|| f(prev.flatten()))) {
+            Ok(value) => return Some(value),
+            Err(payload) => payload,
+        };
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        if !message.contains(BROWSER_API_PANIC) {
+            resume_unwind(payload);
         }
+
+        failed.set(true);
+        let first = expect_runtime(cx.runtime, |runtime| {
+            runtime.ssr_effect_panics.borrow_mut().insert(location)
+        });
+        if first {
+            let error = SsrEffectError {
+                location,
+                scope: cx.id,
+                message,
+            };
+            log::error!("[create_effect] {error}");
+        }
+        None
     }
 }
 
 /// Creates an effect; unlike effects created by [create_effect], isomorphic effects will run on
 /// the server as well as the client.
+///
+/// This is the way to opt in to running an effect during server rendering, so the effect
+/// must not use browser APIs like `window()`, which panic on the server.
 /// ```
 /// # use leptos_reactive::*;
 /// # use log::*;
@@ -88,25 +177,275 @@ where
 /// });
 /// # assert_eq!(b(), 2);
 /// # }).dispose();
+#[track_caller]
 pub fn create_isomorphic_effect<T>(cx: Scope, f: impl Fn(Option<T>) -> T + 'static)
 where
     T: Debug + 'static,
 {
-    let e = cx.runtime.create_effect(f);
+    let e = cx.runtime.create_effect(strict(cx, Location::caller(), f));
     cx.with_scope_property(|prop| prop.push(ScopeProperty::Effect(e)))
 }
 
+// In strict mode, runs the effect a second time, as though it had just been created, straight
+// after its first run, so that effects that aren't idempotent (like one that adds an event
+// listener without ever removing it) show up in development. The cleanups registered during the
+// first run are run before the second, as they would be if the effect were disposed of.
+fn strict<T>(
+    cx: Scope,
+    location: &'static Location<'static>,
+    f: impl Fn(Option<T>) -> T,
+) -> impl Fn(Option<T>) -> T
+where
+    T: Debug,
+{
+    let first_run = std::cell::Cell::new(cx.runtime.is_strict_mode());
+    move |prev| {
+        if !first_run.replace(false) {
+            return f(prev);
+        }
+        let cleanups = with_runtime(cx.runtime, |runtime| cx.cleanup_count(runtime)).unwrap_or(0);
+        let value = f(prev);
+        cx.run_cleanups_since(cleanups);
+        let again = f(None);
+        let (first, second) = (format!("{value:?}"), format!("{again:?}"));
+        if first != second {
+            debug_warn!(
+                "[strict mode] The effect created at {location} returned {first} when it first \
+                 ran, but {second} when it ran again: it may not be idempotent."
+            );
+        }
+        again
+    }
+}
+
+/// Creates an effect, like [create_effect], that is guaranteed to run after each of the effects
+/// in `after` whenever they rerun in response to the same change. Returns the new effect’s ID,
+/// so that other effects can in turn be ordered after it.
+///
+/// Effects otherwise run in an unspecified order, which matters when several of them update
+/// the same part of the DOM, or when one reads something (like an element’s size) that another
+/// one changes. Pass an empty list to create an effect that others can be ordered after.
+///
+/// Like [create_effect], this does not run on the server, where it returns an ID that
+/// doesn’t refer to any effect.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::RefCell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let (count, set_count) = create_signal(cx, 0);
+/// let log = Rc::new(RefCell::new(Vec::new()));
+///
+/// let render = create_effect_after(cx, [], {
+///   let log = Rc::clone(&log);
+///   move |_| log.borrow_mut().push(format!("render {}", count()))
+/// });
+/// // always sees the result of `render`
+/// create_effect_after(cx, [render], {
+///   let log = Rc::clone(&log);
+///   move |_| log.borrow_mut().push(format!("measure {}", count()))
+/// });
+///
+/// set_count(1);
+/// # if !cfg!(feature = "ssr") {
+/// assert_eq!(log.borrow()[2..], ["render 1", "measure 1"]);
+/// # }
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn create_effect_after<T>(
+    cx: Scope,
+    after: impl IntoIterator<Item = EffectId>,
+    f: impl Fn(Option<T>) -> T + 'static,
+) -> EffectId
+where
+    T: Debug + 'static,
+{
+    cfg_if! {
+        if #[cfg(not(feature = "ssr"))] {
+            let after = after.into_iter().collect::<Vec<_>>();
+            let e = cx.runtime.create_effect(strict(cx, Location::caller(), f));
+            if !after.is_empty() {
+                _ = with_runtime(cx.runtime, |runtime| {
+                    runtime.effects_after.borrow_mut().insert(e, after);
+                });
+            }
+            cx.with_scope_property(|prop| prop.push(ScopeProperty::Effect(e)));
+            e
+        } else {
+            // clear warnings
+            _ = cx;
+            _ = after;
+            _ = f;
+            EffectId::default()
+        }
+    }
+}
+
+/// Creates an effect, like [create_effect], whose function returns a cleanup function along
+/// with its value. Each cleanup is handed to the next run of the effect in a [PrevEffect],
+/// which runs it when it is dropped (or earlier, with [PrevEffect::cleanup]), and the cleanup
+/// from the last run is called when the scope is disposed. This keeps whatever the effect sets
+/// up and tears down in one place, without calls to [on_cleanup](crate::on_cleanup).
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::RefCell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let (topic, set_topic) = create_signal(cx, "news");
+/// let subscribed = Rc::new(RefCell::new(Vec::new()));
+///
+/// create_effect_with_cleanup(cx, {
+///   let subscribed = Rc::clone(&subscribed);
+///   move |prev: PrevEffect<()>| {
+///     // unsubscribes from the previous topic first
+///     drop(prev);
+///     let topic = topic();
+///     subscribed.borrow_mut().push(topic);
+///     let subscribed = Rc::clone(&subscribed);
+///     ((), move || subscribed.borrow_mut().retain(|t| *t != topic))
+///   }
+/// });
+///
+/// set_topic("sports");
+/// # if !cfg!(feature = "ssr") {
+/// assert_eq!(*subscribed.borrow(), ["sports"]);
+/// # }
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn create_effect_with_cleanup<T, C>(cx: Scope, f: impl Fn(PrevEffect<T>) -> (T, C) + 'static)
+where
+    T: Debug + 'static,
+    C: FnOnce() + 'static,
+{
+    let cleanup = Rc::new(RefCell::new(None::<Box<dyn FnOnce()>>));
+    create_effect(cx, {
+        let cleanup = Rc::clone(&cleanup);
+        move |prev| {
+            // in strict mode, the effect runs again as though it had just been created, so the
+            // first run is cleaned up after first
+            if prev.is_none() {
+                let stale = cleanup.borrow_mut().take();
+                if let Some(stale) = stale {
+                    stale();
+                }
+            }
+            let prev = PrevEffect {
+                value: prev,
+                cleanup: cleanup.borrow_mut().take(),
+            };
+            let (value, next) = f(prev);
+            *cleanup.borrow_mut() = Some(Box::new(next));
+            value
+        }
+    });
+    on_cleanup(cx, move || {
+        let last = cleanup.borrow_mut().take();
+        if let Some(last) = last {
+            last();
+        }
+    });
+}
+
+/// What an effect created with [create_effect_with_cleanup] returned the last time it ran:
+/// its value, and its cleanup function, which is called when this is dropped, unless it has
+/// already been called with [PrevEffect::cleanup]. Both are `None` the first time the effect
+/// runs.
+pub struct PrevEffect<T> {
+    value: Option<T>,
+    cleanup: Option<Box<dyn FnOnce()>>,
+}
+
+impl<T> PrevEffect<T> {
+    /// The value the effect returned the last time it ran.
+    pub fn value(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    /// Takes the value the effect returned the last time it ran, e.g., to reuse it.
+    pub fn take_value(&mut self) -> Option<T> {
+        self.value.take()
+    }
+
+    /// Calls the cleanup function returned the last time the effect ran, if it hasn’t been
+    /// called yet.
+    pub fn cleanup(&mut self) {
+        if let Some(cleanup) = self.cleanup.take() {
+            cleanup();
+        }
+    }
+}
+
+impl<T> Drop for PrevEffect<T> {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
+
+impl<T> Debug for PrevEffect<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrevEffect")
+            .field("value", &self.value)
+            .field("cleanup", &self.cleanup.is_some())
+            .finish()
+    }
+}
+
 #[doc(hidden)]
+#[track_caller]
 pub fn create_render_effect<T>(cx: Scope, f: impl Fn(Option<T>) -> T + 'static)
 where
     T: Debug + 'static,
 {
-    create_effect(cx, f);
+    cfg_if! {
+        if #[cfg(not(feature = "ssr"))] {
+            let e = cx.runtime.create_effect(f);
+            _ = with_runtime(cx.runtime, |runtime| {
+                runtime.render_effects.borrow_mut().insert(e, ());
+            });
+            cx.with_scope_property(|prop| prop.push(ScopeProperty::Effect(e)))
+        } else {
+            // clear warnings
+            _ = cx;
+            _ = f;
+        }
+    }
+}
+
+/// Whether render effects are currently being rerun in response to a signal change. While they
+/// are, the renderer can stage its DOM writes, and apply them all at once in the function given
+/// to [on_render_flush], which runs before any other effects.
+#[doc(hidden)]
+pub fn is_flushing_render_effects(cx: Scope) -> bool {
+    expect_runtime(cx.runtime, |runtime| runtime.flushing_render_effects.get())
+}
+
+/// Sets the function that applies any DOM writes staged while [is_flushing_render_effects].
+#[doc(hidden)]
+pub fn on_render_flush(cx: Scope, flush: fn()) {
+    expect_runtime(cx.runtime, |runtime| runtime.render_flush.set(Some(flush)))
 }
 
 slotmap::new_key_type! {
-    /// Unique ID assigned to an [Effect](crate::Effect).
-    pub(crate) struct EffectId;
+    /// Unique ID assigned to an effect, which can be used to [order](create_effect_after)
+    /// other effects relative to it.
+    pub struct EffectId;
+}
+
+/// How up to date an effect (or the effect that computes a [Memo](crate::Memo)) is.
+///
+/// When a signal changes, the memos and effects that read it directly are marked `Dirty`,
+/// and everything downstream of those memos is marked `Check`: it may need to rerun, but only
+/// if one of the memos it reads turns out to have changed. Checking those memos first, before
+/// deciding whether to rerun, means that nothing ever runs with a mix of old and new values.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum EffectState {
+    #[default]
+    Clean,
+    Check,
+    Dirty,
 }
 
 pub(crate) struct Effect<T, F>
@@ -128,7 +467,7 @@ where
     F: Fn(Option<T>) -> T,
 {
     fn run(&self, id: EffectId, runtime: RuntimeId) {
-        with_runtime(runtime, |runtime| {
+        expect_runtime(runtime, |runtime| {
             // clear previous dependencies
             id.cleanup(runtime);
 
@@ -148,8 +487,8 @@ where
 }
 
 impl EffectId {
-    pub(crate) fn run<T>(&self, runtime_id: RuntimeId) {
-        with_runtime(runtime_id, |runtime| {
+    pub(crate) fn run(&self, runtime_id: RuntimeId) {
+        expect_runtime(runtime_id, |runtime| {
             let effect = {
                 let effects = runtime.effects.borrow();
                 effects.get(*self).cloned()
@@ -157,7 +496,7 @@ impl EffectId {
             if let Some(effect) = effect {
                 effect.run(*self, runtime_id);
             } else {
-                debug_warn!("[Effect] Trying to run an Effect that has been disposed. This is probably either a logic error in a component that creates and disposes of scopes, or a Resource resolving after its scope has been dropped without having been cleaned up.")
+                debug_warn!("[Effect] Trying to run {self}, which has been disposed. This is probably either a logic error in a component that creates and disposes of scopes, or a Resource resolving after its scope has been dropped without having been cleaned up.")
             }
         })
     }
@@ -171,6 +510,7 @@ impl EffectId {
                     source.borrow_mut().remove(self);
                 }
             }
+            sources.borrow_mut().clear();
         }
     }
 }
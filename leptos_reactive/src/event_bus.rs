@@ -0,0 +1,223 @@
+use crate::{debug_warn, on_cleanup, provide_context, use_context, Scope};
+use std::{
+    any::type_name,
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    rc::Rc,
+};
+
+/// Provides an event bus for events of type `T` to the current [Scope](crate::Scope)
+/// and all of its descendants, so that they can send each other events with
+/// [use_event_emitter] and [on_event].
+///
+/// This is useful for occasional commands between siblings, like a toolbar telling a canvas to
+/// clear itself, that don’t need to be stored in a signal and shouldn’t cause anything to re-run.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::RefCell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// #[derive(Debug, PartialEq)]
+/// enum CanvasCommand {
+///     Clear,
+///     Undo,
+/// }
+///
+/// provide_event_bus::<CanvasCommand>(cx);
+///
+/// // the canvas
+/// let received = Rc::new(RefCell::new(Vec::new()));
+/// cx.child_scope({
+///     let received = Rc::clone(&received);
+///     move |cx| {
+///         on_event(cx, move |command: &CanvasCommand| {
+///             received.borrow_mut().push(format!("{command:?}"))
+///         })
+///     }
+/// });
+///
+/// // the toolbar
+/// cx.child_scope(|cx| {
+///     let emitter = use_event_emitter::<CanvasCommand>(cx);
+///     emitter.emit(CanvasCommand::Undo);
+///     emitter.emit(CanvasCommand::Clear);
+/// });
+///
+/// assert_eq!(*received.borrow(), ["Undo", "Clear"]);
+/// # }).dispose();
+/// ```
+pub fn provide_event_bus<T>(cx: Scope)
+where
+    T: 'static,
+{
+    provide_context(cx, EventBus::<T>::default());
+}
+
+/// Returns an [Emitter] that sends events of type `T` to every handler registered with
+/// [on_event] on the nearest event bus provided with [provide_event_bus].
+///
+/// If no event bus for `T` has been provided, the emitter does nothing, and a warning is
+/// logged in debug builds.
+pub fn use_event_emitter<T>(cx: Scope) -> Emitter<T>
+where
+    T: 'static,
+{
+    Emitter {
+        cx,
+        bus: use_event_bus(cx, "use_event_emitter"),
+    }
+}
+
+/// Calls `handler` with every event of type `T` sent on the nearest event bus provided with
+/// [provide_event_bus], until `cx` is disposed.
+///
+/// Handlers are called synchronously, in the order they were registered.
+///
+/// If no event bus for `T` has been provided, `handler` is never called, and a warning is
+/// logged in debug builds.
+pub fn on_event<T>(cx: Scope, handler: impl Fn(&T) + 'static)
+where
+    T: 'static,
+{
+    if let Some(bus) = use_event_bus::<T>(cx, "on_event") {
+        let id = bus.subscribe(Rc::new(handler));
+        on_cleanup(cx, move || bus.unsubscribe(id));
+    }
+}
+
+/// Sends events of type `T` to the handlers registered with [on_event]. Created with
+/// [use_event_emitter].
+pub struct Emitter<T> {
+    cx: Scope,
+    bus: Option<EventBus<T>>,
+}
+
+impl<T> Clone for Emitter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            cx: self.cx,
+            bus: self.bus.clone(),
+        }
+    }
+}
+
+impl<T> Emitter<T>
+where
+    T: 'static,
+{
+    /// Calls every handler registered for `T` with `value`, in the order they were registered.
+    ///
+    /// Handlers are always called untracked, so any signals they read don’t become dependencies
+    /// of an effect or memo that happens to emit an event. An event is something that happens
+    /// once, not a value that can change, so there’s nothing for it to re-run with; use a
+    /// signal instead if the receiver should react to a value.
+    ///
+    /// If this is called from within a handler, `value` is queued, and sent once every handler
+    /// has been called with the current event.
+    pub fn emit(&self, value: T) {
+        if let Some(bus) = &self.bus {
+            self.cx.untrack(|| bus.emit(value));
+        }
+    }
+}
+
+fn use_event_bus<T>(cx: Scope, hook: &str) -> Option<EventBus<T>>
+where
+    T: 'static,
+{
+    #[cfg(not(debug_assertions))]
+    let _ = hook;
+
+    let bus = use_context::<EventBus<T>>(cx);
+    if bus.is_none() {
+        debug_warn!(
+            "[{hook}] no event bus for `{}` has been provided. Call `provide_event_bus::<{}>(cx)` in a parent scope.",
+            type_name::<T>(),
+            type_name::<T>()
+        );
+    }
+    bus
+}
+
+type Handler<T> = Rc<dyn Fn(&T)>;
+
+struct EventBus<T>(Rc<EventBusState<T>>);
+
+struct EventBusState<T> {
+    handlers: RefCell<Vec<(usize, Handler<T>)>>,
+    next_id: Cell<usize>,
+    queue: RefCell<VecDeque<T>>,
+    dispatching: Cell<bool>,
+}
+
+impl<T> Default for EventBus<T> {
+    fn default() -> Self {
+        Self(Rc::new(EventBusState {
+            handlers: Default::default(),
+            next_id: Default::default(),
+            queue: Default::default(),
+            dispatching: Default::default(),
+        }))
+    }
+}
+
+impl<T> Clone for EventBus<T> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<T> EventBus<T> {
+    fn subscribe(&self, handler: Handler<T>) -> usize {
+        let id = self.0.next_id.get();
+        self.0.next_id.set(id + 1);
+        self.0.handlers.borrow_mut().push((id, handler));
+        id
+    }
+
+    fn unsubscribe(&self, id: usize) {
+        self.0
+            .handlers
+            .borrow_mut()
+            .retain(|(handler_id, _)| *handler_id != id);
+    }
+
+    fn emit(&self, value: T) {
+        self.0.queue.borrow_mut().push_back(value);
+        // an event sent by a handler waits for the current one to reach every handler
+        if self.0.dispatching.replace(true) {
+            return;
+        }
+        let _dispatching = Dispatching(&self.0);
+
+        loop {
+            let value = self.0.queue.borrow_mut().pop_front();
+            let Some(value) = value else {
+                break;
+            };
+            // handlers can subscribe or unsubscribe others while they run
+            let handlers = self.0.handlers.borrow().clone();
+            for (id, handler) in handlers {
+                let subscribed = self
+                    .0
+                    .handlers
+                    .borrow()
+                    .iter()
+                    .any(|(handler_id, _)| *handler_id == id);
+                if subscribed {
+                    handler(&value);
+                }
+            }
+        }
+    }
+}
+
+// if a handler panics, the bus can still send later events
+struct Dispatching<'a, T>(&'a EventBusState<T>);
+
+impl<'a, T> Drop for Dispatching<'a, T> {
+    fn drop(&mut self) {
+        self.0.queue.borrow_mut().clear();
+        self.0.dispatching.set(false);
+    }
+}
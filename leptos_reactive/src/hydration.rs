@@ -1,9 +1,5 @@
-use crate::ResourceId;
-use std::{
-    collections::{HashMap, HashSet},
-    future::Future,
-    pin::Pin,
-};
+use crate::{PinnedFuture, ResourceId};
+use std::collections::{HashMap, HashSet};
 
 #[derive(Default)]
 pub struct SharedContext {
@@ -11,9 +7,22 @@ pub struct SharedContext {
     pub events: Vec<()>,
     pub context: Option<HydrationContext>,
     pub registry: HashMap<String, web_sys::Element>,
-    pub pending_resources: HashSet<ResourceId>,
-    pub resolved_resources: HashMap<ResourceId, String>,
-    pub pending_fragments: HashMap<String, Pin<Box<dyn Future<Output = String>>>>,
+    /// The keys (see [next_resource_key](Self::next_resource_key)) of the resources the server
+    /// was still loading when it sent the page.
+    pub pending_resources: HashSet<String>,
+    /// The serialized values the server has already sent, by resource key.
+    pub resolved_resources: HashMap<String, String>,
+    /// The `<Suspense/>` fragments still waiting to be rendered, in the order they were
+    /// registered, so that they are always sent in the same order.
+    pub pending_fragments: Vec<(String, PinnedFuture<Option<String>>)>,
+    pub marker_count: usize,
+    pub fragment_count: usize,
+    /// The island currently being rendered or hydrated, if any (see [Scope::with_island](crate::Scope::with_island)).
+    pub island: Option<String>,
+    /// The serializable resources created in each island, in the order they were created.
+    pub island_resources: HashMap<String, Vec<ResourceId>>,
+    /// The number of resources created by each line of code at each hydration position so far.
+    pub resource_counts: HashMap<String, usize>,
 }
 
 impl std::fmt::Debug for SharedContext {
@@ -36,13 +45,18 @@ impl PartialEq for SharedContext {
 impl Eq for SharedContext {}
 
 impl SharedContext {
+    /// Starts hydrating the elements in the `registry`. If they belong to an `island`, their keys
+    /// (and the keys of the resources created in it) are prefixed with its id.
     #[cfg(feature = "hydrate")]
-    pub fn new_with_registry(registry: HashMap<String, web_sys::Element>) -> Self {
+    pub fn new_with_registry(
+        registry: HashMap<String, web_sys::Element>,
+        island: Option<String>,
+    ) -> Self {
         let pending_resources = js_sys::Reflect::get(
             &web_sys::window().unwrap(),
             &wasm_bindgen::JsValue::from_str("__LEPTOS_PENDING_RESOURCES"),
         );
-        let pending_resources: HashSet<ResourceId> = pending_resources
+        let pending_resources: HashSet<String> = pending_resources
             .map_err(|_| ())
             .and_then(|pr| serde_wasm_bindgen::from_value(pr).map_err(|_| ()))
             .unwrap_or_default();
@@ -59,14 +73,22 @@ impl SharedContext {
         Self {
             completed: Default::default(),
             events: Default::default(),
-            context: Some(HydrationContext {
-                id: "".into(),
-                count: -1,
+            context: Some(match &island {
+                Some(island) => HydrationContext::island(island),
+                None => HydrationContext {
+                    id: "".into(),
+                    count: -1,
+                },
             }),
             registry,
             pending_resources,
             resolved_resources,
             pending_fragments: Default::default(),
+            marker_count: 0,
+            fragment_count: 0,
+            island,
+            island_resources: Default::default(),
+            resource_counts: Default::default(),
         }
     }
 
@@ -84,15 +106,88 @@ impl SharedContext {
         }
     }
 
-    pub fn current_fragment_key(&self) -> String {
+    /// Returns the key under which the serializable resource with the given `id`, created at
+    /// the current position by the code at `location`, is serialized: the key of the next
+    /// element to be hydrated, then `r`, a hash of the `location`, and the number of resources
+    /// already created by that code at that position (e.g., `0-2-1r5f3c0e1a.0`).
+    ///
+    /// Unlike the `id`, this only depends on where the resource is created (both in the view,
+    /// and in the code), so the server and the browser agree on it even if one of them creates
+    /// a resource that the other doesn’t. When server rendering an island, the resource is
+    /// also recorded as part of the island.
+    pub fn next_resource_key(
+        &mut self,
+        id: ResourceId,
+        location: &'static std::panic::Location<'static>,
+    ) -> String {
+        let position = match &self.context {
+            Some(context) => format!("{}{}", context.id, context.count),
+            // the key `next_hydration_key` would return
+            None => "0-0".to_string(),
+        };
+        let site = format!("{position}r{:08x}", location_hash(location));
+        let count = self.resource_counts.entry(site.clone()).or_default();
+        let key = format!("{site}.{count}");
+        *count += 1;
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "hydrate")] {
+                _ = id;
+            } else {
+                if let Some(island) = &self.island {
+                    self.island_resources
+                        .entry(island.clone())
+                        .or_default()
+                        .push(id);
+                }
+            }
+        }
+        key
+    }
+
+    pub fn next_marker_id(&mut self) -> usize {
+        let id = self.marker_count;
+        self.marker_count += 1;
+        id
+    }
+
+    pub fn current_fragment_key(&mut self) -> String {
         if let Some(context) = &self.context {
             format!("{}{}f", context.id, context.count)
         } else {
-            "0f".to_string()
+            // there are no hydration keys to derive it from, so every call gets a new key
+            let id = self.fragment_count;
+            self.fragment_count += 1;
+            format!("{id}f")
         }
     }
 }
 
+// A hash of the source location that is the same in every build of the same code (unlike
+// `DefaultHasher`, which could differ between the server’s and the browser’s standard library).
+// Only the file’s name is used, as the path to it can differ between the two builds.
+fn location_hash(location: &std::panic::Location) -> u32 {
+    let file = location
+        .file()
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default();
+    let location = format!("{file}:{}:{}", location.line(), location.column());
+    // 32-bit FNV-1a
+    let mut hash: u32 = 0x811c9dc5;
+    for byte in location.bytes() {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x01000193);
+    }
+    hash
+}
+
+/// The hydration position a resource key (see [SharedContext::next_resource_key]) was created at.
+#[cfg(all(feature = "hydrate", debug_assertions))]
+pub(crate) fn resource_key_position(key: &str) -> &str {
+    key.rsplit_once('r').map_or(key, |(position, _)| position)
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct HydrationContext {
     id: String,
@@ -100,6 +195,15 @@ pub struct HydrationContext {
 }
 
 impl HydrationContext {
+    /// The context at the root of an island, whose keys are all prefixed with the island’s
+    /// `id`, so that they don’t depend on anything rendered outside the island.
+    pub fn island(id: &str) -> HydrationContext {
+        HydrationContext {
+            id: format!("{id}:"),
+            count: 0,
+        }
+    }
+
     pub fn next_hydration_context(&mut self) -> HydrationContext {
         self.count += 1;
         HydrationContext {
@@ -108,3 +212,203 @@ impl HydrationContext {
         }
     }
 }
+
+/// The comment nodes that mark the beginning and end of a dynamic child or component
+/// in server-rendered HTML, i.e., `<!--leptos-{id}-->` and `<!--/leptos-{id}-->`.
+/// Markers in client-side templates don't need to be unique, and have an empty `id`.
+#[cfg(any(feature = "hydrate", doc, test))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum HydrationMarker {
+    Open(String),
+    Close(String),
+}
+
+#[cfg(any(feature = "hydrate", doc, test))]
+impl HydrationMarker {
+    /// Parses the value of a comment node.
+    pub fn parse(comment: &str) -> Option<Self> {
+        match comment {
+            "leptos" => Some(Self::Open(String::new())),
+            "/leptos" => Some(Self::Close(String::new())),
+            // markers written by older versions of the server renderer
+            #[cfg(feature = "legacy-hydration-markers")]
+            "#" => Some(Self::Open("#".to_string())),
+            #[cfg(feature = "legacy-hydration-markers")]
+            "/" => Some(Self::Close("#".to_string())),
+            _ => {
+                if let Some(id) = comment.strip_prefix("leptos-") {
+                    Some(Self::Open(id.to_string()))
+                } else {
+                    comment
+                        .strip_prefix("/leptos-")
+                        .map(|id| Self::Close(id.to_string()))
+                }
+            }
+        }
+    }
+}
+
+/// What a sibling node looks like, as far as finding hydration markers is concerned.
+#[cfg(any(feature = "hydrate", doc, test))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MarkerNode {
+    Marker(HydrationMarker),
+    Whitespace,
+    Other,
+}
+
+/// The result of searching a list of sibling nodes for a marked span.
+#[cfg(any(feature = "hydrate", doc, test))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum MarkerSpan {
+    /// The span runs from the opening marker at `start` to the closing marker at `end`, inclusive.
+    Found { start: usize, end: usize },
+    /// The first node (other than whitespace) was not an opening marker.
+    NoOpening,
+    /// An opening marker was found at `start`, but no matching closing marker. The server-rendered
+    /// nodes are assumed to run up to `end` (exclusive): the closing marker of the component
+    /// around this one, or the end of the list.
+    Unclosed { start: usize, end: usize },
+}
+
+/// Finds the span marked by the first opening marker and its matching closing marker.
+///
+/// Leading whitespace-only text nodes (which may be added by minifiers or proxies) are skipped.
+/// Markers are matched by `id`; nested markers with the same `id` (only possible for
+/// client-side templates or legacy markers) are balanced.
+#[cfg(any(feature = "hydrate", doc, test))]
+pub(crate) fn find_marker_span(nodes: impl IntoIterator<Item = MarkerNode>) -> MarkerSpan {
+    let mut opening: Option<(usize, String)> = None;
+    let mut depth = 0;
+    // the other markers opened since the opening marker, which are closed inside the span
+    let mut nested = Vec::new();
+    let mut len = 0;
+
+    for (idx, node) in nodes.into_iter().enumerate() {
+        len = idx + 1;
+        match (&opening, node) {
+            (None, MarkerNode::Whitespace) => {}
+            (None, MarkerNode::Marker(HydrationMarker::Open(id))) => {
+                opening = Some((idx, id));
+                depth = 1;
+            }
+            (None, _) => return MarkerSpan::NoOpening,
+            (Some((_, open_id)), MarkerNode::Marker(HydrationMarker::Open(id))) => {
+                if *open_id == id {
+                    depth += 1;
+                } else {
+                    nested.push(id);
+                }
+            }
+            (Some((start, open_id)), MarkerNode::Marker(HydrationMarker::Close(id))) => {
+                if *open_id == id {
+                    depth -= 1;
+                    if depth == 0 {
+                        return MarkerSpan::Found {
+                            start: *start,
+                            end: idx,
+                        };
+                    }
+                } else if let Some(pos) = nested.iter().rposition(|nested| *nested == id) {
+                    nested.remove(pos);
+                } else {
+                    // closes the component this one is in, so this one must have ended before it
+                    return MarkerSpan::Unclosed {
+                        start: *start,
+                        end: idx,
+                    };
+                }
+            }
+            (Some(_), _) => {}
+        }
+    }
+
+    match opening {
+        Some((start, _)) => MarkerSpan::Unclosed { start, end: len },
+        None => MarkerSpan::NoOpening,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open(id: &str) -> MarkerNode {
+        MarkerNode::Marker(HydrationMarker::Open(id.to_string()))
+    }
+
+    fn close(id: &str) -> MarkerNode {
+        MarkerNode::Marker(HydrationMarker::Close(id.to_string()))
+    }
+
+    #[test]
+    fn parses_markers() {
+        assert_eq!(
+            HydrationMarker::parse("leptos-12"),
+            Some(HydrationMarker::Open("12".into()))
+        );
+        assert_eq!(
+            HydrationMarker::parse("/leptos-12"),
+            Some(HydrationMarker::Close("12".into()))
+        );
+        assert_eq!(
+            HydrationMarker::parse("leptos"),
+            Some(HydrationMarker::Open("".into()))
+        );
+        assert_eq!(HydrationMarker::parse("some other comment"), None);
+    }
+
+    #[test]
+    fn matches_by_id() {
+        let nodes = vec![
+            open("0"),
+            MarkerNode::Other,
+            open("1"),
+            MarkerNode::Other,
+            close("1"),
+            close("0"),
+            MarkerNode::Other,
+        ];
+        assert_eq!(find_marker_span(nodes), MarkerSpan::Found { start: 0, end: 5 });
+    }
+
+    #[test]
+    fn balances_markers_without_ids() {
+        let nodes = vec![open(""), open(""), close(""), close(""), open(""), close("")];
+        assert_eq!(find_marker_span(nodes), MarkerSpan::Found { start: 0, end: 3 });
+    }
+
+    #[test]
+    fn tolerates_collapsed_whitespace() {
+        // a minifier removed the text node between the markers,
+        // and a proxy added whitespace before the opening marker
+        let nodes = vec![MarkerNode::Whitespace, open("3"), close("3"), MarkerNode::Other];
+        assert_eq!(find_marker_span(nodes), MarkerSpan::Found { start: 1, end: 2 });
+    }
+
+    #[test]
+    fn handles_stripped_markers() {
+        // all comments were stripped
+        let nodes = vec![MarkerNode::Other, MarkerNode::Other];
+        assert_eq!(find_marker_span(nodes), MarkerSpan::NoOpening);
+
+        // only the closing comment was stripped: the server-rendered nodes run to the end
+        let nodes = vec![open("4"), MarkerNode::Other, open("5"), close("5")];
+        assert_eq!(find_marker_span(nodes), MarkerSpan::Unclosed { start: 0, end: 4 });
+    }
+
+    #[test]
+    fn unclosed_span_ends_with_the_enclosing_component() {
+        // `5` is inside `4`, and its closing marker was stripped; `6` is inside `5`
+        let nodes = vec![
+            open("5"),
+            MarkerNode::Other,
+            open("6"),
+            close("6"),
+            MarkerNode::Whitespace,
+            close("4"),
+            MarkerNode::Other,
+        ];
+        assert_eq!(find_marker_span(nodes), MarkerSpan::Unclosed { start: 0, end: 5 });
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    runtime::{with_runtime, PinnedFuture, RuntimeId},
+    ResourceId,
+};
+use futures::future::{select, Either};
+use std::{task::Poll, time::Duration};
+
+/// What [Scope::wait_for_idle](crate::Scope::wait_for_idle) found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdleResult {
+    /// Every resource has settled, and every suspense fragment has resolved.
+    Idle,
+    /// The timeout passed while there was still work pending.
+    TimedOut {
+        /// The resources that were still loading.
+        resources: Vec<ResourceId>,
+        /// The keys of the suspense fragments that were still waiting for resources.
+        fragments: Vec<String>,
+    },
+}
+
+pub(crate) async fn wait_for_idle(runtime: RuntimeId, timeout: Option<Duration>) -> IdleResult {
+    let idle = Box::pin(idle(runtime));
+    let Some(timeout) = timeout else {
+        idle.await;
+        return IdleResult::Idle;
+    };
+
+    match select(idle, sleep(timeout)).await {
+        Either::Left(_) => IdleResult::Idle,
+        Either::Right(_) => {
+            let pending = with_runtime(runtime, |runtime| {
+                (runtime.loading_resources(), runtime.pending_fragment_keys())
+            })
+            .ok();
+            match pending {
+                Some((resources, fragments)) if !resources.is_empty() || !fragments.is_empty() => {
+                    IdleResult::TimedOut {
+                        resources,
+                        fragments,
+                    }
+                }
+                // it became idle at the same moment
+                _ => IdleResult::Idle,
+            }
+        }
+    }
+}
+
+// resolves once the runtime has been idle for two polls in a row, so that anything that was
+// scheduled by the last piece of work to finish gets a chance to start more work first
+async fn idle(runtime: RuntimeId) {
+    let mut was_idle = false;
+    futures::future::poll_fn(|cx| {
+        let idle = with_runtime(runtime, |runtime| {
+            let idle = runtime.is_idle();
+            if !idle {
+                runtime.idle_wakers.borrow_mut().push(cx.waker().clone());
+            }
+            idle
+        })
+        .ok();
+        match idle {
+            // a disposed runtime can't do any more work
+            None => Poll::Ready(()),
+            Some(true) if was_idle => Poll::Ready(()),
+            Some(true) => {
+                was_idle = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            Some(false) => {
+                was_idle = false;
+                Poll::Pending
+            }
+        }
+    })
+    .await
+}
+
+/// Resolves once `duration` has passed, using `setTimeout` in the browser and `tokio`’s timer
+/// with the `ssr` feature. With a [TestRuntime](crate::testing::TestRuntime), it waits on the
+/// test’s clock instead (see [testing::sleep](crate::testing::sleep)).
+pub fn sleep(duration: Duration) -> PinnedFuture<()> {
+    #[cfg(any(test, feature = "testing"))]
+    if crate::testing::is_active() {
+        return Box::pin(crate::testing::sleep(duration));
+    }
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let promise = js_sys::Promise::new(&mut |resolve, _| {
+                _ = web_sys::window()
+                    .expect("no window")
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(
+                        &resolve,
+                        duration.as_millis() as i32,
+                    );
+            });
+            Box::pin(async move {
+                _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+            })
+        } else if #[cfg(feature = "ssr")] {
+            // only created once polled, since it needs to be inside a `tokio` runtime
+            Box::pin(async move { tokio::time::sleep(duration).await })
+        } else {
+            // there's no timer without a browser or an async runtime, so it never fires
+            _ = duration;
+            crate::debug_warn!("timers need the `csr`, `hydrate`, `ssr`, or `testing` feature");
+            Box::pin(futures::future::pending())
+        }
+    }
+}
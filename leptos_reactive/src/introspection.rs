@@ -0,0 +1,424 @@
+//! Read-only access to the reactive graph, for diagnostics and devtools: given the ID of a
+//! signal, effect, or resource (e.g., from a warning), [Scope::lookup_signal] and friends
+//! return which scope owns it, how connected it is, and, with the `trace` feature, where it
+//! was created and what it holds.
+//!
+//! With the `trace-signals` feature, every write to a signal is also logged, with where it
+//! came from (and, for signals registered with [Scope::trace_values], its old and new values).
+//!
+//! IDs are [displayed](std::fmt::Display) in a short form (like `signal#3v1`) that stays the
+//! same for as long as the node exists, and is used by the runtime’s own warnings, so log
+//! lines can be matched up with each other and with these lookups.
+
+use std::{fmt::Display, panic::Location};
+
+use slotmap::Key;
+
+use crate::{
+    runtime::with_runtime, DisposableId, EffectId, ResourceId, Runtime, Scope, ScopeId,
+    ScopeProperty, SignalId,
+};
+
+/// Identifies a signal, effect, resource, or disposable, i.e., anything a [Scope] can own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeId {
+    /// A signal, including the signal that holds a [Memo](crate::Memo)’s value.
+    Signal(SignalId),
+    /// An effect, including the effect that computes a [Memo](crate::Memo)’s value.
+    Effect(EffectId),
+    /// A [Resource](crate::Resource).
+    Resource(ResourceId),
+    /// A [Disposable](crate::Disposable) registered with [Scope::register_disposable].
+    Disposable(DisposableId),
+}
+
+impl From<SignalId> for NodeId {
+    fn from(id: SignalId) -> Self {
+        Self::Signal(id)
+    }
+}
+
+impl From<EffectId> for NodeId {
+    fn from(id: EffectId) -> Self {
+        Self::Effect(id)
+    }
+}
+
+impl From<ResourceId> for NodeId {
+    fn from(id: ResourceId) -> Self {
+        Self::Resource(id)
+    }
+}
+
+impl From<DisposableId> for NodeId {
+    fn from(id: DisposableId) -> Self {
+        Self::Disposable(id)
+    }
+}
+
+impl From<&ScopeProperty> for NodeId {
+    fn from(property: &ScopeProperty) -> Self {
+        match property {
+            ScopeProperty::Signal(id) => Self::Signal(*id),
+            ScopeProperty::Effect(id) => Self::Effect(*id),
+            ScopeProperty::Resource(id) => Self::Resource(*id),
+            ScopeProperty::Custom(id) => Self::Disposable(*id),
+        }
+    }
+}
+
+// `kind#index v version`, which identifies the node for as long as it exists
+fn fmt_id(f: &mut std::fmt::Formatter<'_>, kind: &str, id: impl Key) -> std::fmt::Result {
+    write!(f, "{kind}#{:?}", id.data())
+}
+
+impl Display for NodeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeId::Signal(id) => id.fmt(f),
+            NodeId::Effect(id) => id.fmt(f),
+            NodeId::Resource(id) => id.fmt(f),
+            NodeId::Disposable(id) => id.fmt(f),
+        }
+    }
+}
+
+impl Display for SignalId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_id(f, "signal", *self)
+    }
+}
+
+impl Display for EffectId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_id(f, "effect", *self)
+    }
+}
+
+impl Display for ResourceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_id(f, "resource", *self)
+    }
+}
+
+impl Display for DisposableId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_id(f, "disposable", *self)
+    }
+}
+
+impl Display for ScopeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_id(f, "scope", *self)
+    }
+}
+
+/// What the runtime knows about a signal. See [Scope::lookup_signal].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignalInfo {
+    /// The signal.
+    pub id: SignalId,
+    /// The scope that owns the signal, and disposes of it along with itself. The signal that
+    /// holds a memo’s value is owned by the scope the memo was created in.
+    pub owner: Option<ScopeId>,
+    /// Where the signal was created (only recorded with the `trace` feature).
+    pub location: Option<&'static Location<'static>>,
+    /// The name given to the signal with [Scope::set_debug_name] (only with the `trace` feature).
+    pub name: Option<String>,
+    /// The type of the value the signal holds (only recorded with the `trace` feature).
+    pub type_name: Option<&'static str>,
+    /// How many effects and memos read the signal the last time they ran.
+    pub subscribers: usize,
+}
+
+/// What the runtime knows about an effect. See [Scope::lookup_effect].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffectInfo {
+    /// The effect.
+    pub id: EffectId,
+    /// The scope that owns the effect, and disposes of it along with itself. The effect that
+    /// computes a memo’s value is owned by the scope the memo was created in.
+    pub owner: Option<ScopeId>,
+    /// Where the effect was created (only recorded with the `trace` feature).
+    pub location: Option<&'static Location<'static>>,
+    /// The name given to the effect with [Scope::set_debug_name] (only with the `trace` feature).
+    pub name: Option<String>,
+    /// How many signals and memos the effect read the last time it ran.
+    pub sources: usize,
+    /// If the effect computes the value of a memo, how many effects and memos read that value
+    /// the last time they ran; otherwise `0`.
+    pub subscribers: usize,
+}
+
+/// What the runtime knows about a resource. See [Scope::lookup_resource].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceInfo {
+    /// The resource.
+    pub id: ResourceId,
+    /// The scope that owns the resource, and disposes of it along with itself.
+    pub owner: Option<ScopeId>,
+    /// Where the resource was created (only recorded with the `trace` feature).
+    pub location: Option<&'static Location<'static>>,
+    /// The name given to the resource with [Scope::set_debug_name] (only with the `trace` feature).
+    pub name: Option<String>,
+    /// The type of the value the resource loads (only recorded with the `trace` feature).
+    pub type_name: Option<&'static str>,
+}
+
+/// What the runtime knows about a [Disposable](crate::Disposable). See
+/// [Scope::lookup_disposable].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisposableInfo {
+    /// The disposable.
+    pub id: DisposableId,
+    /// The scope that owns the disposable, and disposes of it along with itself.
+    pub owner: Option<ScopeId>,
+    /// The name returned by its [debug_name](crate::Disposable::debug_name).
+    pub name: Option<String>,
+}
+
+/// What the `trace` feature records about each node when it is created.
+#[cfg(feature = "trace")]
+#[derive(Debug, Clone)]
+pub(crate) struct Trace {
+    pub location: &'static Location<'static>,
+    pub type_name: Option<&'static str>,
+    pub name: Option<String>,
+}
+
+impl Runtime {
+    /// Records where a node was created, and the type of its value, with the `trace` feature.
+    #[cfg_attr(not(feature = "trace"), allow(unused_variables))]
+    pub(crate) fn trace(
+        &self,
+        id: NodeId,
+        location: &'static Location<'static>,
+        type_name: Option<&'static str>,
+    ) {
+        #[cfg(feature = "trace")]
+        self.traces.borrow_mut().insert(
+            id,
+            Trace {
+                location,
+                type_name,
+                name: None,
+            },
+        );
+    }
+
+    // scopes don't keep track of what owns them, so this looks through all of them: that's
+    // fine for diagnostics, and costs nothing otherwise
+    fn owner(&self, id: NodeId) -> Option<ScopeId> {
+        self.scopes
+            .borrow()
+            .iter()
+            .find(|(_, owned)| owned.borrow().iter().any(|p| NodeId::from(p) == id))
+            .map(|(scope, _)| scope)
+    }
+
+    // where the node was created, its name, and the type of its value
+    #[allow(clippy::type_complexity)]
+    fn traced(
+        &self,
+        id: NodeId,
+    ) -> (
+        Option<&'static Location<'static>>,
+        Option<String>,
+        Option<&'static str>,
+    ) {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "trace")] {
+                match self.traces.borrow().get(&id) {
+                    Some(trace) => (Some(trace.location), trace.name.clone(), trace.type_name),
+                    None => (None, None, None),
+                }
+            } else {
+                _ = id;
+                (None, None, None)
+            }
+        }
+    }
+
+    // prints a signal's value, if it was registered with `Scope::trace_values`
+    #[cfg(feature = "trace-signals")]
+    pub(crate) fn format_value(&self, id: SignalId, value: &dyn std::any::Any) -> Option<String> {
+        let format = *self.value_formatters.borrow().get(id)?;
+        Some(format(value))
+    }
+
+    // logs a write to a signal, with the `trace-signals` feature
+    #[cfg(feature = "trace-signals")]
+    pub(crate) fn trace_write<T: 'static>(
+        &self,
+        id: SignalId,
+        location: &'static Location<'static>,
+        old: Option<String>,
+        new: &T,
+    ) {
+        let (_, name, _) = self.traced(id.into());
+        let name = name.map(|name| format!(" ({name})")).unwrap_or_default();
+        match (old, self.format_value(id, new)) {
+            (Some(old), Some(new)) => {
+                log::info!("[trace-signals] {id}{name} set at {location}: {old} -> {new}")
+            }
+            _ => log::info!(
+                "[trace-signals] {id}{name} set at {location} (Signal<{}>)",
+                std::any::type_name::<T>()
+            ),
+        }
+    }
+
+    fn subscribers(&self, id: SignalId) -> usize {
+        self.signal_subscribers
+            .borrow()
+            .get(id)
+            .map(|subs| subs.borrow().len())
+            .unwrap_or(0)
+    }
+}
+
+impl Scope {
+    /// Looks up a signal (or the signal that holds a [Memo](crate::Memo)’s value) anywhere in
+    /// this scope’s runtime, returning `None` if it has been disposed of.
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, _) = create_signal(cx, 0);
+    /// let info = cx.lookup_signal(count.id()).unwrap();
+    /// assert_eq!(info.owner, Some(cx.id()));
+    /// assert_eq!(info.subscribers, 0);
+    /// # }).dispose();
+    /// ```
+    pub fn lookup_signal(&self, id: SignalId) -> Option<SignalInfo> {
+        with_runtime(self.runtime, |runtime| {
+            if !runtime.signals.borrow().contains_key(id) {
+                return None;
+            }
+            let (location, name, type_name) = runtime.traced(id.into());
+            Some(SignalInfo {
+                id,
+                owner: runtime.owner(id.into()),
+                location,
+                name,
+                type_name,
+                subscribers: runtime.subscribers(id),
+            })
+        })
+        .ok()
+        .flatten()
+    }
+
+    /// Looks up an effect (or the effect that computes a [Memo](crate::Memo)’s value) anywhere
+    /// in this scope’s runtime, returning `None` if it has been disposed of.
+    pub fn lookup_effect(&self, id: EffectId) -> Option<EffectInfo> {
+        with_runtime(self.runtime, |runtime| {
+            if !runtime.effects.borrow().contains_key(id) {
+                return None;
+            }
+            let (location, name, _) = runtime.traced(id.into());
+            let memo = runtime.memo_signals.borrow().get(id).copied();
+            Some(EffectInfo {
+                id,
+                owner: runtime.owner(id.into()),
+                location,
+                name,
+                sources: runtime
+                    .effect_sources
+                    .borrow()
+                    .get(id)
+                    .map(|sources| sources.borrow().len())
+                    .unwrap_or(0),
+                subscribers: memo.map(|memo| runtime.subscribers(memo)).unwrap_or(0),
+            })
+        })
+        .ok()
+        .flatten()
+    }
+
+    /// Looks up a resource anywhere in this scope’s runtime, returning `None` if it has been
+    /// disposed of.
+    pub fn lookup_resource(&self, id: ResourceId) -> Option<ResourceInfo> {
+        with_runtime(self.runtime, |runtime| {
+            if !runtime.resources.borrow().contains_key(id) {
+                return None;
+            }
+            let (location, name, type_name) = runtime.traced(id.into());
+            Some(ResourceInfo {
+                id,
+                owner: runtime.owner(id.into()),
+                location,
+                name,
+                type_name,
+            })
+        })
+        .ok()
+        .flatten()
+    }
+
+    /// Looks up a [Disposable](crate::Disposable) anywhere in this scope’s runtime, returning
+    /// `None` if it has been disposed of.
+    pub fn lookup_disposable(&self, id: DisposableId) -> Option<DisposableInfo> {
+        with_runtime(self.runtime, |runtime| {
+            let name = runtime.disposables.borrow().get(id)?.debug_name();
+            Some(DisposableInfo {
+                id,
+                owner: runtime.owner(id.into()),
+                name,
+            })
+        })
+        .ok()
+        .flatten()
+    }
+
+    /// With the `trace-signals` feature, each write to a signal is logged (at the `info` level)
+    /// along with where it was written from; this makes those logs include the signal’s
+    /// values before and after each write, too. Otherwise, this does nothing.
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    /// cx.trace_values(count);
+    /// // logs "[trace-signals] signal#1v1 set at src/main.rs:6:1: 0 -> 1"
+    /// set_count.set(1);
+    /// # }).dispose();
+    /// ```
+    pub fn trace_values<T>(&self, signal: impl crate::TrackedSignal<T>)
+    where
+        T: std::fmt::Debug + 'static,
+    {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "trace-signals")] {
+                fn format<T: std::fmt::Debug + 'static>(value: &dyn std::any::Any) -> String {
+                    match value.downcast_ref::<T>() {
+                        Some(value) => format!("{value:?}"),
+                        None => String::new(),
+                    }
+                }
+                _ = with_runtime(self.runtime, |runtime| {
+                    runtime
+                        .value_formatters
+                        .borrow_mut()
+                        .insert(signal.tracked_id(), format::<T>);
+                });
+            } else {
+                _ = signal;
+            }
+        }
+    }
+
+    /// Names a signal, effect, or resource, so that it is easier to recognize when it is
+    /// [looked up](Scope::lookup_signal). Names are only kept with the `trace` feature:
+    /// otherwise, this does nothing.
+    #[cfg_attr(not(feature = "trace"), allow(unused_variables))]
+    pub fn set_debug_name(&self, id: impl Into<NodeId>, name: impl Into<String>) {
+        #[cfg(feature = "trace")]
+        with_runtime(self.runtime, |runtime| {
+            if let Some(trace) = runtime.traces.borrow_mut().get_mut(&id.into()) {
+                trace.name = Some(name.into());
+            }
+        })
+        .ok();
+    }
+}
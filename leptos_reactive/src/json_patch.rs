@@ -0,0 +1,293 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+/// A list of changes to a JSON document, as described by
+/// [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902).
+///
+/// This is used to stream updates to [patchable resources](crate::create_patchable_resource)
+/// from the server to the client without sending their whole value again.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// use serde_json::json;
+///
+/// let before = json!({ "todos": ["write docs", "fix bug"], "done": 0 });
+/// let after = json!({ "todos": ["write docs"], "done": 1 });
+///
+/// let patch = JsonPatch::diff(&before, &after);
+/// assert_eq!(
+///   serde_json::to_value(&patch).unwrap(),
+///   json!([
+///     { "op": "replace", "path": "/done", "value": 1 },
+///     { "op": "remove", "path": "/todos/1" },
+///   ])
+/// );
+///
+/// let mut value = before;
+/// patch.apply(&mut value).unwrap();
+/// assert_eq!(value, after);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JsonPatch(pub Vec<PatchOperation>);
+
+/// A single change in a [JsonPatch]. Each `path` is a
+/// [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901) to the value that changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Inserts a value into an array, or adds a member to an object.
+    Add {
+        /// Where to add the value.
+        path: String,
+        /// The value to add.
+        value: Value,
+    },
+    /// Removes the value at the path.
+    Remove {
+        /// The value to remove.
+        path: String,
+    },
+    /// Replaces the value at the path.
+    Replace {
+        /// The value to replace.
+        path: String,
+        /// The new value.
+        value: Value,
+    },
+}
+
+/// Describes errors that can occur while applying a [JsonPatch].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PatchError {
+    /// The path in an operation doesn’t point to a value in the document.
+    #[error("no value at JSON Pointer {0:?}")]
+    InvalidPath(String),
+}
+
+impl JsonPatch {
+    /// Returns the operations needed to turn `from` into `to`.
+    ///
+    /// Objects are compared member by member and arrays element by element, so only the
+    /// parts of the document that have changed are included. (Elements aren’t matched up if
+    /// they move within an array, so inserting at the start of an array replaces every element.)
+    pub fn diff(from: &Value, to: &Value) -> Self {
+        let mut ops = Vec::new();
+        diff(from, to, &mut String::new(), &mut ops);
+        Self(ops)
+    }
+
+    /// Applies each operation to `value` in order, stopping at the first one that fails.
+    pub fn apply(&self, value: &mut Value) -> Result<(), PatchError> {
+        self.0.iter().try_for_each(|op| op.apply(value))
+    }
+
+    /// Returns `true` if the patch doesn’t change anything.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+fn diff(from: &Value, to: &Value, path: &mut String, ops: &mut Vec<PatchOperation>) {
+    let len = path.len();
+    match (from, to) {
+        (Value::Object(from), Value::Object(to)) => {
+            for (key, from) in from {
+                push_token(path, key);
+                match to.get(key) {
+                    Some(to) => diff(from, to, path, ops),
+                    None => ops.push(PatchOperation::Remove { path: path.clone() }),
+                }
+                path.truncate(len);
+            }
+            for (key, to) in to {
+                if !from.contains_key(key) {
+                    push_token(path, key);
+                    ops.push(PatchOperation::Add {
+                        path: path.clone(),
+                        value: to.clone(),
+                    });
+                    path.truncate(len);
+                }
+            }
+        }
+        (Value::Array(from), Value::Array(to)) => {
+            for (idx, (from, to)) in from.iter().zip(to).enumerate() {
+                push_token(path, &idx.to_string());
+                diff(from, to, path, ops);
+                path.truncate(len);
+            }
+            for (idx, to) in to.iter().enumerate().skip(from.len()) {
+                push_token(path, &idx.to_string());
+                ops.push(PatchOperation::Add {
+                    path: path.clone(),
+                    value: to.clone(),
+                });
+                path.truncate(len);
+            }
+            // remove from the end, so the remaining indices stay the same
+            for idx in (to.len()..from.len()).rev() {
+                push_token(path, &idx.to_string());
+                ops.push(PatchOperation::Remove { path: path.clone() });
+                path.truncate(len);
+            }
+        }
+        (from, to) if from != to => ops.push(PatchOperation::Replace {
+            path: path.clone(),
+            value: to.clone(),
+        }),
+        _ => {}
+    }
+}
+
+// appends a reference token to a JSON Pointer, escaping it as described in RFC 6901
+fn push_token(path: &mut String, token: &str) {
+    path.push('/');
+    path.push_str(&token.replace('~', "~0").replace('/', "~1"));
+}
+
+impl PatchOperation {
+    fn apply(&self, value: &mut Value) -> Result<(), PatchError> {
+        match self {
+            PatchOperation::Replace { path, value: new } => {
+                *value
+                    .pointer_mut(path)
+                    .ok_or_else(|| PatchError::InvalidPath(path.clone()))? = new.clone();
+            }
+            PatchOperation::Add { path, value: new } if path.is_empty() => *value = new.clone(),
+            PatchOperation::Add { path, value: new } => {
+                let (parent, key) = split_path(value, path)?;
+                match parent {
+                    Value::Object(map) => {
+                        map.insert(key, new.clone());
+                    }
+                    Value::Array(items) => {
+                        let idx = if key == "-" {
+                            items.len()
+                        } else {
+                            key.parse()
+                                .ok()
+                                .filter(|idx| *idx <= items.len())
+                                .ok_or_else(|| PatchError::InvalidPath(path.clone()))?
+                        };
+                        items.insert(idx, new.clone());
+                    }
+                    _ => return Err(PatchError::InvalidPath(path.clone())),
+                }
+            }
+            PatchOperation::Remove { path } => {
+                let (parent, key) = split_path(value, path)?;
+                let removed = match parent {
+                    Value::Object(map) => map.remove(&key).is_some(),
+                    Value::Array(items) => match key.parse::<usize>() {
+                        Ok(idx) if idx < items.len() => {
+                            items.remove(idx);
+                            true
+                        }
+                        _ => false,
+                    },
+                    _ => false,
+                };
+                if !removed {
+                    return Err(PatchError::InvalidPath(path.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// splits a JSON Pointer into the value it points into and the (unescaped) last token
+fn split_path<'a>(value: &'a mut Value, path: &str) -> Result<(&'a mut Value, String), PatchError> {
+    let (parent, key) = path
+        .rsplit_once('/')
+        .ok_or_else(|| PatchError::InvalidPath(path.to_string()))?;
+    let parent = value
+        .pointer_mut(parent)
+        .ok_or_else(|| PatchError::InvalidPath(path.to_string()))?;
+    Ok((parent, key.replace("~1", "/").replace("~0", "~")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn round_trip(from: Value, to: Value) -> JsonPatch {
+        let patch = JsonPatch::diff(&from, &to);
+        let mut value = from;
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, to);
+        patch
+    }
+
+    #[test]
+    fn unchanged_values_produce_empty_patch() {
+        let value = json!({ "a": [1, 2, { "b": null }] });
+        assert!(round_trip(value.clone(), value).is_empty());
+    }
+
+    #[test]
+    fn only_changed_parts_are_included() {
+        let patch = round_trip(
+            json!({ "title": "Todos", "items": [{ "done": false }, { "done": false }] }),
+            json!({ "title": "Todos", "items": [{ "done": false }, { "done": true }] }),
+        );
+        assert_eq!(
+            patch.0,
+            vec![PatchOperation::Replace {
+                path: "/items/1/done".into(),
+                value: json!(true)
+            }]
+        );
+    }
+
+    #[test]
+    fn arrays_grow_and_shrink() {
+        round_trip(json!([1, 2]), json!([1, 2, 3, 4]));
+        let patch = round_trip(json!([1, 2, 3, 4]), json!([1]));
+        assert_eq!(
+            patch.0,
+            vec![
+                PatchOperation::Remove { path: "/3".into() },
+                PatchOperation::Remove { path: "/2".into() },
+                PatchOperation::Remove { path: "/1".into() },
+            ]
+        );
+    }
+
+    #[test]
+    fn keys_are_escaped() {
+        let patch = round_trip(json!({ "a/b": 1, "c~d": 2 }), json!({ "a/b": 2 }));
+        assert_eq!(
+            patch.0,
+            vec![
+                PatchOperation::Replace {
+                    path: "/a~1b".into(),
+                    value: json!(2)
+                },
+                PatchOperation::Remove {
+                    path: "/c~0d".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn root_can_be_replaced() {
+        round_trip(json!([1]), json!({ "a": 1 }));
+        round_trip(json!("a"), json!(null));
+    }
+
+    #[test]
+    fn invalid_paths_are_errors() {
+        let mut value = json!({ "items": [] });
+        let patch: JsonPatch =
+            serde_json::from_value(json!([{ "op": "remove", "path": "/items/0" }])).unwrap();
+        assert_eq!(
+            patch.apply(&mut value),
+            Err(PatchError::InvalidPath("/items/0".into()))
+        );
+    }
+}
@@ -66,34 +66,58 @@
 //! ```
 
 mod context;
+mod disposable;
 mod effect;
+mod event_bus;
 mod hydration;
+mod idle;
+mod introspection;
+mod json_patch;
 mod memo;
+mod reducer;
 
 mod resource;
+mod retry;
 mod runtime;
 mod scope;
 mod selector;
 mod serialization;
 mod signal;
+mod signal_map;
+mod signal_vec;
 mod signal_wrappers_read;
 mod signal_wrappers_write;
+mod snapshot;
 mod spawn;
+mod streaming_resource;
 mod suspense;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
 pub use context::*;
+pub use disposable::*;
 pub use effect::*;
+pub use event_bus::*;
+pub use idle::*;
+pub use introspection::*;
+pub use json_patch::*;
 pub use memo::*;
+pub use reducer::*;
 pub use resource::*;
+pub use retry::*;
 use runtime::*;
-pub use runtime::{create_runtime, RuntimeId};
+pub use runtime::{create_runtime, try_with_runtime, RuntimeDisposed, RuntimeId};
 pub use scope::*;
 pub use selector::*;
 pub use serialization::*;
 pub use signal::*;
+pub use signal_map::*;
+pub use signal_vec::*;
 pub use signal_wrappers_read::*;
 pub use signal_wrappers_write::*;
+pub use snapshot::*;
 pub use spawn::*;
+pub use streaming_resource::*;
 pub use suspense::*;
 
 /// Trait implemented for all signal types which you can `get` a value
@@ -125,6 +149,32 @@ pub trait UntrackedSettableSignal<T> {
     fn update_untracked(&self, f: impl FnOnce(&mut T));
 }
 
+/// Provides each of a list of context values to the current reactive [Scope], like calling
+/// [provide_context] with each of them. See also [create_context_provider].
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// #[derive(Clone)]
+/// struct AuthCtx { user: Option<String> }
+/// #[derive(Clone)]
+/// struct ThemeCtx { dark: bool }
+///
+/// ctx_provider!(cx, [AuthCtx { user: None }, ThemeCtx { dark: true }]);
+///
+/// assert!(use_context::<ThemeCtx>(cx).unwrap().dark);
+/// # }).dispose();
+/// ```
+#[macro_export]
+macro_rules! ctx_provider {
+    ($cx:expr, [$($value:expr),* $(,)?]) => {
+        {
+            let cx = $cx;
+            $($crate::provide_context(cx, $value);)*
+        }
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! debug_warn {
@@ -1,5 +1,5 @@
-use crate::{ReadSignal, Scope, SignalError, UntrackedGettableSignal};
-use std::fmt::Debug;
+use crate::{runtime::with_runtime, ReadSignal, Scope, SignalId, UntrackedGettableSignal};
+use std::{cell::RefCell, fmt::Debug};
 
 /// Creates an efficient derived reactive value based on other reactive values.
 ///
@@ -14,8 +14,12 @@ use std::fmt::Debug;
 /// create a derived signal. But if the derivation calculation is expensive, you should
 /// create a memo.
 ///
+/// Memos are lazy: the calculation doesn't run until the memo is first read, and when one of
+/// its dependencies changes, it only runs again once the memo is next read.
+///
 /// As with [create_effect](crate::create_effect), the argument to the memo function is the previous value,
 /// i.e., the current value of the memo, which will be `None` for the initial calculation.
+/// To start from a value of your own instead, use [create_memo_seeded].
 ///
 /// ```
 /// # use leptos_reactive::*;
@@ -41,10 +45,10 @@ use std::fmt::Debug;
 /// });
 ///
 /// // instead, we create a memo
-/// // 🆗 run #1: the calculation runs once immediately
+/// // 🆗 the calculation doesn't run until the memo is first read
 /// let memoized = create_memo(cx, move |_| really_expensive_computation(value()));
 /// create_effect(cx, move |_| {
-///  // 🆗 reads the current value of the memo
+///   // 🆗 run #1: the calculation runs once, the first time the memo is read
 ///   log::debug!("memoized = {}", memoized());
 /// });
 /// create_effect(cx, move |_| {
@@ -54,11 +58,51 @@ use std::fmt::Debug;
 /// });
 /// # }).dispose();
 /// ```
+#[track_caller]
 pub fn create_memo<T>(cx: Scope, f: impl Fn(Option<&T>) -> T + 'static) -> Memo<T>
 where
     T: PartialEq + Debug + 'static,
 {
-    cx.runtime.create_memo(f)
+    cx.runtime.create_memo(cx, f)
+}
+
+/// Creates a [Memo] like [create_memo], but passes `&seed` as the previous value the first time
+/// the calculation runs, instead of `None`. This makes it easy to fold a series of values into
+/// an accumulator.
+///
+/// The seed is dropped once it has been used.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (value, set_value) = create_signal(cx, 1);
+///
+/// // the largest value the signal has held while the memo was being read
+/// let max = create_memo_seeded(cx, 0, move |max| value.get().max(*max));
+/// assert_eq!(max.get(), 1);
+///
+/// set_value.set(5);
+/// assert_eq!(max.get(), 5);
+/// set_value.set(3);
+/// assert_eq!(max.get(), 5);
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn create_memo_seeded<T>(cx: Scope, seed: T, f: impl Fn(&T) -> T + 'static) -> Memo<T>
+where
+    T: PartialEq + Debug + 'static,
+{
+    let seed = RefCell::new(Some(seed));
+    cx.runtime.create_memo(cx, move |prev| match prev {
+        Some(prev) => f(prev),
+        None => {
+            let seed = seed
+                .borrow_mut()
+                .take()
+                .expect("seeded memo has no previous value after its first run");
+            f(&seed)
+        }
+    })
 }
 
 /// An efficient derived reactive value based on other reactive values.
@@ -101,10 +145,10 @@ where
 /// });
 ///
 /// // instead, we create a memo
-/// // 🆗 run #1: the calculation runs once immediately
+/// // 🆗 the calculation doesn't run until the memo is first read
 /// let memoized = create_memo(cx, move |_| really_expensive_computation(value()));
 /// create_effect(cx, move |_| {
-///  // 🆗 reads the current value of the memo
+///   // 🆗 run #1: the calculation runs once, the first time the memo is read
 ///   log::debug!("memoized = {}", memoized());
 /// });
 /// create_effect(cx, move |_| {
@@ -135,14 +179,16 @@ impl<T> UntrackedGettableSignal<T> for Memo<T> {
     where
         T: Clone,
     {
-        // Unwrapping is fine because `T` will already be `Some(T)` by
-        // the time this method can be called
+        // Unwrapping is fine because `T` will be `Some(T)` once the memo
+        // has been brought up to date
+        self.update();
         self.0.get_untracked().unwrap()
     }
 
     fn with_untracked<O>(&self, f: impl FnOnce(&T) -> O) -> O {
         // Unwrapping here is fine for the same reasons as <Memo as
         // UntrackedSignal>::get_untracked
+        self.update();
         self.0.with_untracked(|v| f(v.as_ref().unwrap()))
     }
 }
@@ -151,6 +197,12 @@ impl<T> Memo<T>
 where
     T: 'static,
 {
+    /// Identifies the signal that holds the memo’s value, for example to
+    /// [look it up](crate::Scope::lookup_signal).
+    pub fn id(&self) -> SignalId {
+        self.0.id
+    }
+
     /// Clones and returns the current value of the memo, and subscribes
     /// the running effect to the memo.
     /// ```
@@ -195,21 +247,46 @@ where
     /// #
     /// ```
     pub fn with<U>(&self, f: impl FnOnce(&T) -> U) -> U {
-        // okay to unwrap here, because the value will *always* have initially
-        // been set by the effect, synchronously
+        // okay to unwrap here, because the value will *always* have been set
+        // by the time the memo has been brought up to date
+        self.update();
         self.0
             .with(|n| f(n.as_ref().expect("Memo is missing its initial value")))
     }
 
-    pub(crate) fn try_with<U>(&self, f: impl Fn(&T) -> U) -> Result<U, SignalError> {
+    /// Applies a function to the current value of the memo, and subscribes the running effect
+    /// to it, like [Memo::with], or returns `None` if the memo (or the runtime it belongs to)
+    /// has already been disposed of.
+    pub fn try_with<U>(&self, f: impl FnOnce(&T) -> U) -> Option<U> {
+        self.update();
         self.0
             .try_with(|n| f(n.as_ref().expect("Memo is missing its initial value")))
     }
 
     #[cfg(feature = "hydrate")]
     pub(crate) fn subscribe(&self) {
+        // a memo that has never run has no dependencies, so would never change
+        self.update();
         self.0.subscribe()
     }
+
+    /// Clones and returns the current value of the memo, and subscribes the running effect to
+    /// it, like [Memo::get], or returns `None` if the memo (or the runtime it belongs to) has
+    /// already been disposed of.
+    pub fn try_get(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.try_with(T::clone)
+    }
+
+    // runs the calculation, if the memo has never been read or its dependencies have changed
+    fn update(&self) {
+        // if the runtime is gone, reading the value will fail anyway
+        _ = with_runtime(self.0.runtime, |runtime| {
+            runtime.update_memo(self.0.runtime, self.0.id)
+        });
+    }
 }
 
 #[cfg(not(feature = "stable"))]
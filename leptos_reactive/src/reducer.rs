@@ -0,0 +1,361 @@
+use crate::{create_signal, debug_warn, ReadSignal, Scope, UntrackedGettableSignal, WriteSignal};
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    rc::Rc,
+};
+
+/// Creates a signal that can only be changed by dispatching actions to a `reducer`, which
+/// returns the next state from the current state and an action.
+///
+/// This is useful for state with many interdependent ways to change, like a multi-step form,
+/// where a single `reducer` is easier to follow than many setters.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// enum Action {
+///     Increment,
+///     Reset,
+/// }
+///
+/// let (count, dispatcher) = create_reducer(cx, 0, |count, action| match action {
+///     Action::Increment => count + 1,
+///     Action::Reset => 0,
+/// });
+///
+/// dispatcher.dispatch(Action::Increment);
+/// dispatcher.dispatch(Action::Increment);
+/// assert_eq!(count(), 2);
+///
+/// dispatcher.dispatch(Action::Reset);
+/// assert_eq!(count(), 0);
+/// # }).dispose();
+/// ```
+pub fn create_reducer<S, A>(
+    cx: Scope,
+    initial: S,
+    reducer: impl Fn(&S, A) -> S + 'static,
+) -> (ReadSignal<S>, Dispatcher<S, A>)
+where
+    S: 'static,
+    A: 'static,
+{
+    create_reducer_inner(cx, initial, Box::new(reducer), None)
+}
+
+/// Like [create_reducer], but also keeps up to `limit` previous states, which can be restored
+/// with [Dispatcher::undo] and [Dispatcher::redo].
+///
+/// Dispatching an action clears the states that could be restored with [Dispatcher::redo].
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (text, dispatcher) = create_reducer_with_history(
+///     cx,
+///     String::new(),
+///     |text, word: &str| format!("{text}{word}"),
+///     100,
+/// );
+/// let can_undo = dispatcher.can_undo();
+///
+/// dispatcher.dispatch("Hello");
+/// dispatcher.dispatch(", world");
+/// assert_eq!(text(), "Hello, world");
+///
+/// dispatcher.undo();
+/// assert_eq!(text(), "Hello");
+/// dispatcher.redo();
+/// assert_eq!(text(), "Hello, world");
+///
+/// dispatcher.undo();
+/// dispatcher.undo();
+/// assert_eq!(text(), "");
+/// assert!(!can_undo());
+/// # }).dispose();
+/// ```
+pub fn create_reducer_with_history<S, A>(
+    cx: Scope,
+    initial: S,
+    reducer: impl Fn(&S, A) -> S + 'static,
+    limit: usize,
+) -> (ReadSignal<S>, Dispatcher<S, A>)
+where
+    S: 'static,
+    A: 'static,
+{
+    create_reducer_inner(cx, initial, Box::new(reducer), Some(limit))
+}
+
+fn create_reducer_inner<S, A>(
+    cx: Scope,
+    initial: S,
+    reducer: Reducer<S, A>,
+    history_limit: Option<usize>,
+) -> (ReadSignal<S>, Dispatcher<S, A>)
+where
+    S: 'static,
+    A: 'static,
+{
+    let (state, set_state) = create_signal(cx, initial);
+    let (can_undo, set_can_undo) = create_signal(cx, false);
+    let (can_redo, set_can_redo) = create_signal(cx, false);
+    let history = history_limit.map(|limit| History {
+        limit,
+        past: Default::default(),
+        future: Default::default(),
+        set_can_undo,
+        set_can_redo,
+    });
+
+    let (inner, _) = create_signal(
+        cx,
+        Rc::new(ReducerState {
+            cx,
+            state,
+            set_state,
+            reducer,
+            middleware: Default::default(),
+            history,
+            queue: Default::default(),
+            dispatching: Default::default(),
+        }),
+    );
+
+    (
+        state,
+        Dispatcher {
+            inner,
+            can_undo,
+            can_redo,
+        },
+    )
+}
+
+/// Dispatches actions to the reducer of a signal created with [create_reducer] or
+/// [create_reducer_with_history].
+///
+/// Each action is passed to the reducer with the current state, and the state it returns is
+/// written to the signal, notifying its subscribers once.
+///
+/// If an action is dispatched while another is being handled, e.g., by an effect that runs
+/// because of the new state, it is queued, and handled once the current one is done.
+pub struct Dispatcher<S, A>
+where
+    S: 'static,
+    A: 'static,
+{
+    inner: ReadSignal<Rc<ReducerState<S, A>>>,
+    can_undo: ReadSignal<bool>,
+    can_redo: ReadSignal<bool>,
+}
+
+impl<S, A> Clone for Dispatcher<S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner,
+            can_undo: self.can_undo,
+            can_redo: self.can_redo,
+        }
+    }
+}
+
+impl<S, A> Copy for Dispatcher<S, A> {}
+
+impl<S, A> Dispatcher<S, A>
+where
+    S: 'static,
+    A: 'static,
+{
+    /// Passes `action` to the reducer with the current state, and writes the state it returns.
+    pub fn dispatch(&self, action: A) {
+        self.run(Operation::Dispatch(action));
+    }
+
+    /// Calls `middleware` with the current state and each action that is dispatched from now
+    /// on, before it is passed to the reducer. This is useful for logging actions.
+    pub fn with_middleware(&self, middleware: impl Fn(&S, &A) + 'static) {
+        self.inner
+            .with_untracked(|inner| inner.middleware.borrow_mut().push(Rc::new(middleware)));
+    }
+
+    /// Restores the state from before the last action that hasn’t been undone.
+    ///
+    /// This does nothing if there’s nothing to undo, or if the reducer was created without
+    /// a history; see [create_reducer_with_history].
+    pub fn undo(&self) {
+        self.run(Operation::Undo);
+    }
+
+    /// Restores the state from before the last [Dispatcher::undo].
+    ///
+    /// This does nothing if there’s nothing to redo, or if the reducer was created without
+    /// a history; see [create_reducer_with_history].
+    pub fn redo(&self) {
+        self.run(Operation::Redo);
+    }
+
+    /// Whether [Dispatcher::undo] would restore a previous state.
+    pub fn can_undo(&self) -> ReadSignal<bool> {
+        self.can_undo
+    }
+
+    /// Whether [Dispatcher::redo] would restore a state that was undone.
+    pub fn can_redo(&self) -> ReadSignal<bool> {
+        self.can_redo
+    }
+
+    fn run(&self, operation: Operation<A>) {
+        let inner = self.inner.get_untracked();
+        inner.run(operation);
+    }
+}
+
+enum Operation<A> {
+    Dispatch(A),
+    Undo,
+    Redo,
+}
+
+type Reducer<S, A> = Box<dyn Fn(&S, A) -> S>;
+
+type Middleware<S, A> = Rc<dyn Fn(&S, &A)>;
+
+struct ReducerState<S, A>
+where
+    S: 'static,
+{
+    cx: Scope,
+    state: ReadSignal<S>,
+    set_state: WriteSignal<S>,
+    reducer: Reducer<S, A>,
+    middleware: RefCell<Vec<Middleware<S, A>>>,
+    history: Option<History<S>>,
+    queue: RefCell<VecDeque<Operation<A>>>,
+    dispatching: Cell<bool>,
+}
+
+struct History<S>
+where
+    S: 'static,
+{
+    limit: usize,
+    past: RefCell<VecDeque<S>>,
+    future: RefCell<Vec<S>>,
+    set_can_undo: WriteSignal<bool>,
+    set_can_redo: WriteSignal<bool>,
+}
+
+impl<S, A> ReducerState<S, A>
+where
+    S: 'static,
+    A: 'static,
+{
+    fn run(&self, operation: Operation<A>) {
+        self.queue.borrow_mut().push_back(operation);
+        // an action dispatched while another is handled waits for it to finish
+        if self.dispatching.replace(true) {
+            return;
+        }
+        let _dispatching = Dispatching(self);
+
+        self.cx.untrack(|| loop {
+            let operation = self.queue.borrow_mut().pop_front();
+            match operation {
+                None => break,
+                Some(Operation::Dispatch(action)) => self.dispatch(action),
+                Some(Operation::Undo) => self.undo(),
+                Some(Operation::Redo) => self.redo(),
+            }
+        });
+    }
+
+    fn dispatch(&self, action: A) {
+        let middleware = self.middleware.borrow().clone();
+        let next = self.state.with_untracked(|state| {
+            for middleware in middleware {
+                middleware(state, &action);
+            }
+            (self.reducer)(state, action)
+        });
+
+        match &self.history {
+            None => self.set_state.set(next),
+            Some(history) => {
+                if let Some(prev) = self.replace_state(next) {
+                    let mut past = history.past.borrow_mut();
+                    past.push_back(prev);
+                    while past.len() > history.limit {
+                        past.pop_front();
+                    }
+                    drop(past);
+                    history.future.borrow_mut().clear();
+                    history.update_signals();
+                }
+            }
+        }
+    }
+
+    fn undo(&self) {
+        let Some(history) = self.history() else {
+            return;
+        };
+        let prev = history.past.borrow_mut().pop_back();
+        if let Some(next) = prev.and_then(|prev| self.replace_state(prev)) {
+            history.future.borrow_mut().push(next);
+            history.update_signals();
+        }
+    }
+
+    fn redo(&self) {
+        let Some(history) = self.history() else {
+            return;
+        };
+        let next = history.future.borrow_mut().pop();
+        if let Some(prev) = next.and_then(|next| self.replace_state(next)) {
+            history.past.borrow_mut().push_back(prev);
+            history.update_signals();
+        }
+    }
+
+    fn history(&self) -> Option<&History<S>> {
+        if self.history.is_none() {
+            debug_warn!(
+                "[Dispatcher] undo and redo do nothing, because this reducer has no history. Use `create_reducer_with_history` instead."
+            );
+        }
+        self.history.as_ref()
+    }
+
+    // writes the new state, notifying subscribers once, and returns the previous one, unless
+    // the signal has been disposed
+    fn replace_state(&self, state: S) -> Option<S> {
+        self.set_state
+            .update_returning(|current| std::mem::replace(current, state))
+    }
+}
+
+impl<S> History<S> {
+    fn update_signals(&self) {
+        self.set_can_undo
+            .set_if_changed(!self.past.borrow().is_empty());
+        self.set_can_redo
+            .set_if_changed(!self.future.borrow().is_empty());
+    }
+}
+
+// if the reducer panics, the dispatcher can still handle later actions
+struct Dispatching<'a, S, A>(&'a ReducerState<S, A>)
+where
+    S: 'static;
+
+impl<'a, S, A> Drop for Dispatching<'a, S, A>
+where
+    S: 'static,
+{
+    fn drop(&mut self) {
+        self.0.queue.borrow_mut().clear();
+        self.0.dispatching.set(false);
+    }
+}
@@ -4,17 +4,23 @@ use std::{
     collections::HashSet,
     fmt::Debug,
     future::Future,
+    hash::Hash,
     marker::PhantomData,
     pin::Pin,
     rc::Rc,
 };
 
 use crate::{
-    create_effect, create_isomorphic_effect, create_memo, create_signal, queue_microtask,
-    runtime::{with_runtime, RuntimeId},
+    create_effect, create_isomorphic_effect, create_memo, create_signal, debug_warn,
+    queue_microtask,
+    retry::{Retry, RetryState},
+    runtime::{
+        expect_runtime, with_runtime, PinnedFuture, PinnedStream, RuntimeDisposed, RuntimeId,
+    },
     serialization::Serializable,
     spawn::spawn_local,
-    use_context, Memo, ReadSignal, Scope, ScopeProperty, SuspenseContext, WriteSignal,
+    use_context, JsonPatch, Memo, ReadSignal, RetryPolicy, Scope, ScopeProperty, Signal,
+    SuspenseContext, UntrackedGettableSignal, WriteSignal,
 };
 
 /// Creates [Resource](crate::Resource), which is a signal that reflects the
@@ -25,6 +31,10 @@ use crate::{
 /// `source` signal that provides the argument for the `fetcher`. Whenever the
 /// value of the `source` changes, a new [Future] will be created and run.
 ///
+/// The `source` is memoized: when it reruns (for example, because one of the signals it
+/// reads was set) but returns a value equal to the previous one, nothing is fetched, and any
+/// `<Suspense/>` reading the resource keeps showing its content rather than its fallback.
+///
 /// When server-side rendering is used, the server will handle running the
 /// [Future] and will stream the result to the client. This process requires the
 /// output type of the Future to be [Serializable]. If your output cannot be
@@ -60,6 +70,7 @@ use crate::{
 /// # }
 /// # }).dispose();
 /// ```
+#[track_caller]
 pub fn create_resource<S, T, Fu>(
     cx: Scope,
     source: impl Fn() -> S + 'static,
@@ -84,6 +95,7 @@ where
 /// output type of the Future to be [Serializable]. If your output cannot be
 /// serialized, or you just want to make sure the [Future] runs locally, use
 /// [create_local_resource_with_initial_value()].
+#[track_caller]
 pub fn create_resource_with_initial_value<S, T, Fu>(
     cx: Scope,
     source: impl Fn() -> S + 'static,
@@ -95,12 +107,291 @@ where
     T: Debug + Serializable + 'static,
     Fu: Future<Output = T> + 'static,
 {
+    serializable_resource(cx, source, fetcher, initial_value, None, false)
+}
+
+/// Creates a [Resource](crate::Resource) configured by the given [ResourceOptions], for
+/// example to give it an initial value, or to [retry](ResourceOptions::retry) failed fetches.
+///
+/// Otherwise, this works just like [create_resource].
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::time::Duration;
+/// # create_scope(create_runtime(), |cx| {
+/// # if false {
+/// async fn fetch_user(id: u32) -> Result<String, String> {
+///   // pretend we're calling an API that's sometimes unavailable
+///   Ok(format!("user {id}"))
+/// }
+///
+/// let (id, set_id) = create_signal(cx, 1);
+/// let user = create_resource_with_options(
+///   cx,
+///   id,
+///   fetch_user,
+///   ResourceOptions::new().retry(RetryPolicy::new(4).base_delay(Duration::from_millis(500))),
+/// );
+///
+/// let status = move || match user.read() {
+///   None if user.attempt().get() > 0 => format!("Retrying ({})...", user.attempt().get()),
+///   None => "Loading...".to_string(),
+///   Some(Ok(user)) => user,
+///   Some(Err(e)) => format!("Error: {e}"),
+/// };
+/// # }
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn create_resource_with_options<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    options: ResourceOptions<T>,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Debug + Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let resource = serializable_resource(
+        cx,
+        source,
+        fetcher,
+        options.initial_value,
+        options.retry,
+        false,
+    );
+    if let Some(preload_href) = options.preload_href {
+        if cfg!(not(any(feature = "csr", feature = "hydrate"))) {
+            preload_asset(cx, preload_href());
+        }
+    }
+    resource
+}
+
+/// Options for a [Resource](crate::Resource) created with [create_resource_with_options] or
+/// [create_local_resource_with_options].
+pub struct ResourceOptions<T> {
+    initial_value: Option<T>,
+    retry: Option<Retry<T>>,
+    preload_href: Option<Box<dyn FnOnce() -> String>>,
+}
+
+impl<T> ResourceOptions<T> {
+    /// The options of a resource created with [create_resource].
+    pub fn new() -> Self {
+        Self {
+            initial_value: None,
+            retry: None,
+            preload_href: None,
+        }
+    }
+
+    /// Sets the value of the resource until the `source` first changes, like
+    /// [create_resource_with_initial_value].
+    pub fn initial_value(mut self, initial_value: T) -> Self {
+        self.initial_value = Some(initial_value);
+        self
+    }
+
+    /// Declares an asset that the resource’s data will use, like an image, so that the browser
+    /// can start loading it before the page has hydrated: while rendering on the server, `f` is
+    /// called as soon as the resource is created, and the URL it returns is preloaded (e.g.,
+    /// `leptos_meta` adds a `<link rel="preload">` for it to the `<head>`).
+    ///
+    /// This only applies to resources created with [create_resource_with_options].
+    pub fn preload_href(mut self, f: impl FnOnce() -> String + 'static) -> Self {
+        self.preload_href = Some(Box::new(f));
+        self
+    }
+}
+
+impl<T, E> ResourceOptions<Result<T, E>>
+where
+    T: 'static,
+    E: 'static,
+{
+    /// Retries fetches that fail according to the `policy`. See [RetryPolicy].
+    pub fn retry(mut self, policy: RetryPolicy<E>) -> Self {
+        self.retry = Some(policy.into_retry());
+        self
+    }
+}
+
+impl<T> Default for ResourceOptions<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for ResourceOptions<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResourceOptions")
+            .field("initial_value", &self.initial_value)
+            .field("retry", &self.retry.is_some())
+            .field("preload_href", &self.preload_href.is_some())
+            .finish()
+    }
+}
+
+/// Sets the function that is passed the URL of each asset declared with
+/// [ResourceOptions::preload_href] while rendering on the server, with the scope of its
+/// resource. Those declared before this is called are passed to it right away.
+#[doc(hidden)]
+pub fn on_resource_preload(cx: Scope, preload: fn(Scope, String)) {
+    let pending = expect_runtime(cx.runtime, |runtime| {
+        runtime.resource_preload.set(Some(preload));
+        runtime.pending_preloads.take()
+    });
+    for (cx, href) in pending {
+        preload(cx, href);
+    }
+}
+
+// passes the URL to the function set by `on_resource_preload`, or keeps it until there is one
+fn preload_asset(cx: Scope, href: String) {
+    let preload = expect_runtime(cx.runtime, |runtime| runtime.resource_preload.get());
+    match preload {
+        Some(preload) => preload(cx, href),
+        None => expect_runtime(cx.runtime, |runtime| {
+            runtime.pending_preloads.borrow_mut().push((cx, href))
+        }),
+    }
+}
+
+/// Creates a [Resource](crate::Resource) that streams its updates from the server as
+/// [JSON patches](crate::JsonPatch), rather than as complete values.
+///
+/// This works just like [create_resource], except when its value changes on the server
+/// after it was first serialized (for example, because its `source` changed) while the
+/// response is still streaming. Normally the new value wouldn’t be sent at all; here, it is
+/// sent as a patch computed against the previous value, which the client applies to
+/// reconstruct the current value. This keeps the payload small for large values that only
+/// change a little at a time.
+///
+/// The value is always serialized in full the first time.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// # if false {
+/// async fn load_leaderboard(page: usize) -> Vec<(String, u32)> {
+///   // pretend we're loading a long list of scores
+///   vec![("Alice".to_string(), 100)]
+/// }
+///
+/// let (page, set_page) = create_signal(cx, 1);
+/// let scores = create_patchable_resource(cx, page, load_leaderboard);
+/// # }
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn create_patchable_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Debug + Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    serializable_resource(cx, source, fetcher, None, None, true)
+}
+
+/// Creates a [Resource](crate::Resource) that shares its fetches with every other cached
+/// resource that has the same cache key.
+///
+/// This works just like [create_resource], except the key returned by `cache_key` for each
+/// `source` value is looked up in a cache that belongs to the whole reactive runtime. If another
+/// cached resource has already fetched that key, its [Future] (whether it is still in flight or
+/// has already resolved) is reused instead of calling `fetcher` again. This means the same data
+/// can be loaded by several components while only being fetched once.
+///
+/// Because the result is cached, [Resource::refetch] reuses it too. Use
+/// [invalidate_cached_resource] to remove a key from the cache so that it is fetched again.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// # if false {
+/// async fn fetch_user(id: u32) -> String {
+///   // pretend we're calling an API
+///   format!("user {id}")
+/// }
+///
+/// let (id, set_id) = create_signal(cx, 1);
+/// // both resources share a single request for each user ID
+/// let avatar = create_cached_resource(cx, id, fetch_user, |id| *id);
+/// let profile = create_cached_resource(cx, id, fetch_user, |id| *id);
+/// # }
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn create_cached_resource<S, K, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    cache_key: impl Fn(&S) -> K + 'static,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    K: Eq + Hash + 'static,
+    T: Debug + Clone + Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let runtime = cx.runtime;
+    create_resource(cx, source, move |source| {
+        let key = cache_key(&source);
+        expect_runtime(runtime, |runtime| {
+            runtime.cached_resource_future(key, || Box::pin(fetcher(source)) as PinnedFuture<T>)
+        })
+    })
+}
+
+/// Removes a key from the cache shared by [cached resources](create_cached_resource) whose
+/// keys have type `K` and values have type `T`, so that the next time a resource loads that
+/// key (for example, after [Resource::refetch]), it is fetched again.
+pub fn invalidate_cached_resource<K, T>(cx: Scope, key: &K)
+where
+    K: Eq + Hash + 'static,
+    T: Clone + 'static,
+{
+    expect_runtime(cx.runtime, |runtime| {
+        runtime.invalidate_cached_resource::<K, T>(key)
+    })
+}
+
+#[track_caller]
+fn serializable_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    initial_value: Option<T>,
+    retry: Option<Retry<T>>,
+    patchable: bool,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Debug + Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let location = std::panic::Location::caller();
+    if let Err(e) = with_runtime(cx.runtime, |_| ()) {
+        return refused_resource(cx, location, e);
+    }
+
     let resolved = initial_value.is_some();
     let (value, set_value) = create_signal(cx, initial_value);
 
     let (loading, set_loading) = create_signal(cx, false);
 
     let fetcher = Rc::new(move |s| Box::pin(fetcher(s)) as Pin<Box<dyn Future<Output = T>>>);
+    let (fetcher, retry) = with_retry(cx, fetcher, retry);
     let source = create_memo(cx, move |_| source());
 
     let r = Rc::new(ResourceState {
@@ -112,18 +403,25 @@ where
         source,
         fetcher,
         resolved: Rc::new(Cell::new(resolved)),
+        in_flight: Rc::new(Cell::new(0)),
         scheduled: Rc::new(Cell::new(false)),
         suspense_contexts: Default::default(),
+        patchable,
+        retry,
     });
 
-    let id = with_runtime(cx.runtime, |runtime| {
-        runtime.create_serializable_resource(Rc::clone(&r))
+    let (id, key) = expect_runtime(cx.runtime, |runtime| {
+        let id = runtime.create_serializable_resource(Rc::clone(&r));
+        runtime.trace(id.into(), location, Some(std::any::type_name::<T>()));
+        (id, runtime.next_resource_key(id, location))
     });
 
     create_isomorphic_effect(cx, {
         let r = Rc::clone(&r);
+        // only the first load can use the data sent by the server
+        let key = Cell::new(key);
         move |_| {
-            load_resource(cx, id, r.clone());
+            load_resource(cx, key.take(), r.clone());
         }
     });
 
@@ -167,6 +465,7 @@ where
 /// # }
 /// # }).dispose();
 /// ```
+#[track_caller]
 pub fn create_local_resource<S, T, Fu>(
     cx: Scope,
     source: impl Fn() -> S + 'static,
@@ -188,6 +487,7 @@ where
 /// Unlike [create_resource_with_initial_value()], this [Future] will always run
 /// on the local system and therefore its output type does not need to be
 /// [Serializable].
+#[track_caller]
 pub fn create_local_resource_with_initial_value<S, T, Fu>(
     cx: Scope,
     source: impl Fn() -> S + 'static,
@@ -199,12 +499,34 @@ where
     T: Debug + 'static,
     Fu: Future<Output = T> + 'static,
 {
+    local_resource(cx, source, fetcher, initial_value, None)
+}
+
+#[track_caller]
+fn local_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    initial_value: Option<T>,
+    retry: Option<Retry<T>>,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Debug + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let location = std::panic::Location::caller();
+    if let Err(e) = with_runtime(cx.runtime, |_| ()) {
+        return refused_resource(cx, location, e);
+    }
+
     let resolved = initial_value.is_some();
     let (value, set_value) = create_signal(cx, initial_value);
 
     let (loading, set_loading) = create_signal(cx, false);
 
     let fetcher = Rc::new(move |s| Box::pin(fetcher(s)) as Pin<Box<dyn Future<Output = T>>>);
+    let (fetcher, retry) = with_retry(cx, fetcher, retry);
     let source = create_memo(cx, move |_| source());
 
     let r = Rc::new(ResourceState {
@@ -216,12 +538,17 @@ where
         source,
         fetcher,
         resolved: Rc::new(Cell::new(resolved)),
+        in_flight: Rc::new(Cell::new(0)),
         scheduled: Rc::new(Cell::new(false)),
         suspense_contexts: Default::default(),
+        patchable: false,
+        retry,
     });
 
-    let id = with_runtime(cx.runtime, |runtime| {
-        runtime.create_unserializable_resource(Rc::clone(&r))
+    let id = expect_runtime(cx.runtime, |runtime| {
+        let id = runtime.create_unserializable_resource(Rc::clone(&r));
+        runtime.trace(id.into(), location, Some(std::any::type_name::<T>()));
+        id
     });
 
     create_effect(cx, {
@@ -241,8 +568,76 @@ where
     }
 }
 
+/// Creates a _local_ [Resource](crate::Resource) configured by the given [ResourceOptions].
+///
+/// Unlike [create_resource_with_options()], this [Future] will always run on the local system
+/// and therefore its output type does not need to be [Serializable].
+#[track_caller]
+pub fn create_local_resource_with_options<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    options: ResourceOptions<T>,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Debug + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    local_resource(cx, source, fetcher, options.initial_value, options.retry)
+}
+
+// a resource that was created after its runtime had been disposed, so it never loads
+fn refused_resource<S, T>(
+    cx: Scope,
+    location: &'static std::panic::Location<'static>,
+    e: RuntimeDisposed,
+) -> Resource<S, T>
+where
+    S: Debug + 'static,
+    T: Debug + 'static,
+{
+    debug_warn!("[create_resource] The resource created at {location} won’t load, because {e}.");
+    Resource {
+        runtime: cx.runtime,
+        id: ResourceId::default(),
+        source_ty: PhantomData,
+        out_ty: PhantomData,
+    }
+}
+
+// wraps the fetcher so that it retries failed fetches; on the server, the first error is final
+#[allow(clippy::type_complexity)]
+fn with_retry<S, T>(
+    cx: Scope,
+    fetcher: Rc<dyn Fn(S) -> Pin<Box<dyn Future<Output = T>>>>,
+    retry: Option<Retry<T>>,
+) -> (
+    Rc<dyn Fn(S) -> Pin<Box<dyn Future<Output = T>>>>,
+    Option<Rc<RetryState>>,
+)
+where
+    S: Clone + 'static,
+    T: 'static,
+{
+    match retry {
+        Some(retry) if cfg!(not(feature = "ssr")) => {
+            let state = RetryState::new(cx);
+            let fetcher = Rc::new({
+                let state = Rc::clone(&state);
+                move |s: S| {
+                    let fetcher = Rc::clone(&fetcher);
+                    retry(&state, Box::new(move || fetcher(s.clone())))
+                }
+            });
+            (fetcher, Some(state))
+        }
+        _ => (fetcher, None),
+    }
+}
+
 #[cfg(not(feature = "hydrate"))]
-fn load_resource<S, T>(_cx: Scope, _id: ResourceId, r: Rc<ResourceState<S, T>>)
+fn load_resource<S, T>(_cx: Scope, _key: Option<String>, r: Rc<ResourceState<S, T>>)
 where
     S: PartialEq + Debug + Clone + 'static,
     T: Debug + 'static,
@@ -251,43 +646,56 @@ where
 }
 
 #[cfg(feature = "hydrate")]
-fn load_resource<S, T>(cx: Scope, id: ResourceId, r: Rc<ResourceState<S, T>>)
+fn load_resource<S, T>(cx: Scope, key: Option<String>, r: Rc<ResourceState<S, T>>)
 where
     S: PartialEq + Debug + Clone + 'static,
     T: Debug + Serializable + 'static,
 {
     use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
-    with_runtime(cx.runtime, |runtime| {
+    // not hydrating, or already loaded once
+    let Some(key) = key else {
+        return r.load(false);
+    };
+
+    expect_runtime(cx.runtime, |runtime| {
         if let Some(ref mut context) = *runtime.shared_context.borrow_mut() {
-            if let Some(data) = context.resolved_resources.remove(&id) {
+            if let Some(data) = context.resolved_resources.remove(&key) {
                 // The server already sent us the serialized resource value, so
                 // deserialize & set it now
-                context.pending_resources.remove(&id); // no longer pending
+                context.pending_resources.remove(&key); // no longer pending
                 r.resolved.set(true);
 
                 let res = T::from_json(&data).expect_throw("could not deserialize Resource JSON");
                 r.set_value.update(|n| *n = Some(res));
                 r.set_loading.update(|n| *n = false);
+                if r.patchable {
+                    receive_patches(&key, r.set_value, &data);
+                }
 
                 // for reactivity
                 r.source.subscribe();
-            } else if context.pending_resources.remove(&id) {
+            } else if context.pending_resources.remove(&key) {
                 // We're still waiting for the resource, add a "resolver" closure so
                 // that it will be set as soon as the server sends the serialized
                 // value
                 r.set_loading.update(|n| *n = true);
 
                 let resolve = {
+                    let key = key.clone();
                     let resolved = r.resolved.clone();
                     let set_value = r.set_value;
                     let set_loading = r.set_loading;
+                    let patchable = r.patchable;
                     move |res: String| {
-                        let res =
+                        let value =
                             T::from_json(&res).expect_throw("could not deserialize Resource JSON");
                         resolved.set(true);
-                        set_value.update(|n| *n = Some(res));
+                        set_value.update(|n| *n = Some(value));
                         set_loading.update(|n| *n = false);
+                        if patchable {
+                            receive_patches(&key, set_value, &res);
+                        }
                     }
                 };
                 let resolve =
@@ -297,18 +705,24 @@ where
                     &wasm_bindgen::JsValue::from_str("__LEPTOS_RESOURCE_RESOLVERS"),
                 )
                 .expect_throw("no __LEPTOS_RESOURCE_RESOLVERS found in the JS global scope");
-                let id = serde_json::to_string(&id).expect_throw("could not serialize Resource ID");
+                let key =
+                    serde_json::to_string(&key).expect_throw("could not serialize Resource key");
                 _ = js_sys::Reflect::set(
                     &resource_resolvers,
-                    &wasm_bindgen::JsValue::from_str(&id),
+                    &wasm_bindgen::JsValue::from_str(&key),
                     resolve.as_ref().unchecked_ref(),
                 );
 
                 // for reactivity
                 r.source.subscribe()
             } else {
-                // Server didn't mark the resource as pending, so load it on the
+                // Server didn't send anything for this resource, so load it on the
                 // client
+                crate::debug_warn!(
+                    "[hydration] the server didn’t send any data for the resource {key:?}, \
+                     created at position {:?}, so it will be loaded in the browser",
+                    crate::hydration::resource_key_position(&key)
+                );
                 r.load(false);
             }
         } else {
@@ -317,49 +731,161 @@ where
     })
 }
 
+// Applies the patches the server streams for a patchable resource, starting from the value
+// it sent in full, including any that arrived before the resource was hydrated.
+#[cfg(feature = "hydrate")]
+fn receive_patches<T>(key: &str, set_value: WriteSignal<Option<T>>, json: &str)
+where
+    T: Serializable + 'static,
+{
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let mut current: serde_json::Value = match serde_json::from_str(json) {
+        Ok(current) => current,
+        Err(e) => {
+            crate::debug_warn!("[create_patchable_resource] {e}");
+            return;
+        }
+    };
+    let mut apply = move |patch: String| {
+        let patched = serde_json::from_str::<JsonPatch>(&patch)
+            .map_err(|e| e.to_string())
+            .and_then(|patch| patch.apply(&mut current).map_err(|e| e.to_string()))
+            .and_then(|_| T::from_json(&current.to_string()).map_err(|e| e.to_string()));
+        match patched {
+            Ok(value) => set_value.set(Some(value)),
+            Err(e) => crate::debug_warn!("[create_patchable_resource] {e}"),
+        }
+    };
+
+    let window = web_sys::window().unwrap();
+    let key = JsValue::from_str(&serde_json::to_string(key).unwrap());
+    if let Ok(pending) =
+        js_sys::Reflect::get(&window, &JsValue::from_str("__LEPTOS_PENDING_PATCHES"))
+    {
+        if let Ok(patches) = js_sys::Reflect::get(&pending, &key) {
+            if let Ok(patches) = patches.dyn_into::<js_sys::Array>() {
+                for patch in patches.iter().filter_map(|patch| patch.as_string()) {
+                    apply(patch);
+                }
+            }
+            _ = js_sys::Reflect::delete_property(pending.unchecked_ref::<js_sys::Object>(), &key);
+        }
+    }
+
+    let apply = wasm_bindgen::closure::Closure::wrap(Box::new(apply) as Box<dyn FnMut(String)>);
+    if let Ok(patchers) =
+        js_sys::Reflect::get(&window, &JsValue::from_str("__LEPTOS_RESOURCE_PATCHERS"))
+    {
+        _ = js_sys::Reflect::set(&patchers, &key, &apply.into_js_value());
+    }
+}
+
 impl<S, T> Resource<S, T>
 where
     S: Debug + Clone + 'static,
     T: Debug + 'static,
 {
+    /// Identifies the resource, for example in [IdleResult::TimedOut](crate::IdleResult::TimedOut).
+    pub fn id(&self) -> ResourceId {
+        self.id
+    }
+
     /// Clones and returns the current value of the resource ([Option::None] if the
     /// resource is still pending). Also subscribes the running effect to this
     /// resource.
     ///
     /// If you want to get the value without cloning it, use [Resource::with].
     /// (`value.read()` is equivalent to `value.with(T::clone)`.)
+    ///
+    /// If the resource’s [Scope] has already been disposed, this returns [Option::None]
+    /// rather than whatever value it had, so that late reads (for example, while resolving a
+    /// server-rendered `<Suspense/>` fragment after the request was aborted) can’t render
+    /// stale data.
     pub fn read(&self) -> Option<T>
     where
         T: Clone,
     {
-        with_runtime(self.runtime, |runtime| {
-            runtime.resource(self.id, |resource: &ResourceState<S, T>| resource.read())
-        })
+        self.with(T::clone)
     }
 
     /// Applies a function to the current value of the resource, and subscribes
     /// the running effect to this resource. If the resource hasn't yet
-    /// resolved, the function won't be called and this will return
-    /// [Option::None].
+    /// resolved, or has been disposed, the function won't be called and this
+    /// will return [Option::None].
     ///
     /// If you want to get the value by cloning it, you can use
     /// [Resource::read].
     pub fn with<U>(&self, f: impl FnOnce(&T) -> U) -> Option<U> {
-        with_runtime(self.runtime, |runtime| {
-            runtime.resource(self.id, |resource: &ResourceState<S, T>| resource.with(f))
+        let value = with_runtime(self.runtime, |runtime| {
+            runtime.try_resource(self.id, |resource: &ResourceState<S, T>| resource.with(f))
         })
+        .ok()
+        .flatten();
+        match value {
+            Some(value) => value,
+            None => {
+                log::debug!(
+                    "[Resource] {} (Resource<{}, {}>) was read after it was disposed",
+                    self.id,
+                    std::any::type_name::<S>(),
+                    std::any::type_name::<T>(),
+                );
+                None
+            }
+        }
     }
 
     /// Returns a signal that indicates whether the resource is currently loading.
+    ///
+    /// Unlike checking whether [Resource::read] returns [None], this is also `true` while
+    /// the resource is re-fetching after it has already resolved, during which time the
+    /// previous value can still be read. This makes it possible to show a loading
+    /// indicator over stale content, rather than hiding it.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// # if false {
+    /// let (page, set_page) = create_signal(cx, 1);
+    /// let posts = create_resource(cx, page, |page| async move { vec![page] });
+    ///
+    /// // the posts are still there, but should be shown as outdated
+    /// let is_stale = move || posts.loading().get() && posts.read().is_some();
+    /// # }
+    /// # }).dispose();
+    /// ```
     pub fn loading(&self) -> ReadSignal<bool> {
-        with_runtime(self.runtime, |runtime| {
+        expect_runtime(self.runtime, |runtime| {
             runtime.resource(self.id, |resource: &ResourceState<S, T>| resource.loading)
         })
     }
 
+    /// Returns a signal that holds the current value of the resource’s `source`, i.e., the
+    /// argument of the fetch that is in flight or most recently resolved, e.g., to show what is
+    /// being loaded.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// # if false {
+    /// let (query, set_query) = create_signal(cx, "rust".to_string());
+    /// let results = create_resource(cx, query, |query| async move { vec![query] });
+    ///
+    /// let status = move || format!("Searching for {}…", results.source().get());
+    /// # }
+    /// # }).dispose();
+    /// ```
+    pub fn source(&self) -> Signal<S> {
+        expect_runtime(self.runtime, |runtime| {
+            runtime.resource(self.id, |resource: &ResourceState<S, T>| resource.source)
+        })
+        .into()
+    }
+
     /// Re-runs the async function with the current source data.
+    ///
+    /// This does nothing if the resource’s runtime has already been disposed.
     pub fn refetch(&self) {
-        with_runtime(self.runtime, |runtime| {
+        _ = with_runtime(self.runtime, |runtime| {
             runtime.resource(self.id, |resource: &ResourceState<S, T>| resource.refetch())
         });
     }
@@ -371,7 +897,7 @@ where
     where
         T: Serializable,
     {
-        with_runtime(self.runtime, |runtime| {
+        expect_runtime(self.runtime, |runtime| {
             runtime.resource(self.id, |resource: &ResourceState<S, T>| {
                 resource.to_serialization_resolver(self.id)
             })
@@ -380,6 +906,88 @@ where
     }
 }
 
+impl<S, T, E> Resource<S, Result<T, E>>
+where
+    S: Debug + Clone + 'static,
+    T: Debug + 'static,
+    E: Debug + Clone + 'static,
+{
+    /// Returns a signal that holds the error returned by the most recent fetch, or [None] if
+    /// it succeeded or the resource hasn’t resolved yet.
+    ///
+    /// The error is kept while the resource is re-fetching, until the next fetch resolves.
+    /// Unlike [Resource::read], reading it doesn’t cause a `<Suspense/>` to wait for the resource.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// # if false {
+    /// let (id, set_id) = create_signal(cx, 1);
+    /// let user = create_resource(cx, id, |id| async move {
+    ///   if id > 0 {
+    ///     Ok(format!("user {id}"))
+    ///   } else {
+    ///     Err("no such user".to_string())
+    ///   }
+    /// });
+    ///
+    /// let error_message = move || user.error().get().map(|e| format!("Error: {e}"));
+    /// # }
+    /// # }).dispose();
+    /// ```
+    pub fn error(&self) -> Signal<Option<E>> {
+        let (cx, value) = expect_runtime(self.runtime, |runtime| {
+            runtime.resource(self.id, |resource: &ResourceState<S, Result<T, E>>| {
+                (resource.scope, resource.value)
+            })
+        });
+        Signal::derive(cx, move || {
+            value.with(|value| match value {
+                Some(Err(e)) => Some(e.clone()),
+                _ => None,
+            })
+        })
+    }
+}
+
+impl<S, T, E> Resource<S, Result<T, E>>
+where
+    S: Debug + Clone + 'static,
+    T: Debug + 'static,
+    E: Debug + 'static,
+{
+    /// Returns a signal that holds how many times the current fetch has been retried, counting
+    /// a retry that is still waiting to start, for a resource created with a [RetryPolicy].
+    ///
+    /// It goes back to `0` whenever the resource fetches again, and is always `0` if the
+    /// resource doesn’t retry, including on the server.
+    pub fn attempt(&self) -> Signal<u32> {
+        let (cx, retry) = expect_runtime(self.runtime, |runtime| {
+            runtime.resource(self.id, |resource: &ResourceState<S, Result<T, E>>| {
+                (resource.scope, resource.retry.clone())
+            })
+        });
+        match retry {
+            Some(retry) => retry.attempt.into(),
+            None => Signal::derive(cx, || 0),
+        }
+    }
+
+    /// If the resource is waiting to retry a failed fetch, stops waiting and retries it right
+    /// away, even if the browser is offline. Otherwise, this does nothing.
+    pub fn retry_now(&self) {
+        let retry = with_runtime(self.runtime, |runtime| {
+            runtime.resource(self.id, |resource: &ResourceState<S, Result<T, E>>| {
+                resource.retry.clone()
+            })
+        })
+        .ok()
+        .flatten();
+        if let Some(retry) = retry {
+            retry.retry_now();
+        }
+    }
+}
+
 /// A signal that reflects the
 /// current state of an asynchronous task, allowing you to integrate `async`
 /// [Future]s into the synchronous reactive system.
@@ -388,6 +996,10 @@ where
 /// `source` signal that provides the argument for the `fetcher`. Whenever the
 /// value of the `source` changes, a new [Future] will be created and run.
 ///
+/// The `source` is memoized: when it reruns (for example, because one of the signals it
+/// reads was set) but returns a value equal to the previous one, nothing is fetched, and any
+/// `<Suspense/>` reading the resource keeps showing its content rather than its fallback.
+///
 /// When server-side rendering is used, the server will handle running the
 /// [Future] and will stream the result to the client. This process requires the
 /// output type of the Future to be [Serializable]. If your output cannot be
@@ -505,16 +1117,20 @@ where
     T: Debug + 'static,
 {
     scope: Scope,
-    value: ReadSignal<Option<T>>,
-    set_value: WriteSignal<Option<T>>,
+    pub(crate) value: ReadSignal<Option<T>>,
+    pub(crate) set_value: WriteSignal<Option<T>>,
     pub loading: ReadSignal<bool>,
     set_loading: WriteSignal<bool>,
     source: Memo<S>,
     #[allow(clippy::type_complexity)]
     fetcher: Rc<dyn Fn(S) -> Pin<Box<dyn Future<Output = T>>>>,
     resolved: Rc<Cell<bool>>,
+    // the number of fetches that haven't resolved yet
+    in_flight: Rc<Cell<usize>>,
     scheduled: Rc<Cell<bool>>,
     suspense_contexts: Rc<RefCell<HashSet<SuspenseContext>>>,
+    patchable: bool,
+    retry: Option<Rc<RetryState>>,
 }
 
 impl<S, T> ResourceState<S, T>
@@ -522,24 +1138,16 @@ where
     S: Debug + Clone + 'static,
     T: Debug + 'static,
 {
-    pub fn read(&self) -> Option<T>
-    where
-        T: Clone,
-    {
-        self.with(T::clone)
-    }
-
     pub fn with<U>(&self, f: impl FnOnce(&T) -> U) -> Option<U> {
         let suspense_cx = use_context::<SuspenseContext>(self.scope);
 
         let v = self
             .value
-            .try_with(|n| n.as_ref().map(|n| Some(f(n))))
-            .ok()?
+            .try_with(|n| n.as_ref().map(|n| Some(f(n))))?
             .flatten();
 
         let suspense_contexts = self.suspense_contexts.clone();
-        let has_value = v.is_some();
+        let value = self.value;
 
         let increment = move |_: Option<()>| {
             if let Some(s) = &suspense_cx {
@@ -550,7 +1158,8 @@ where
                     // on subsequent reads, increment will be triggered in load()
                     // because the context has been tracked here
                     // on the first read, resource is already loading without having incremented
-                    if !has_value {
+                    // (checked here rather than when reading, in case it resolved in between)
+                    if value.with_untracked(Option::is_none) {
                         s.increment();
                     }
                 }
@@ -585,6 +1194,7 @@ where
                 }
             });
 
+            self.in_flight.set(self.in_flight.get() + 1);
             self.set_loading.update(|n| *n = true);
 
             // increment counter everywhere it's read
@@ -597,19 +1207,27 @@ where
             // run the Future
             spawn_local({
                 let resolved = self.resolved.clone();
+                let in_flight = self.in_flight.clone();
                 let set_value = self.set_value;
                 let set_loading = self.set_loading;
+                let runtime = self.scope.runtime;
                 async move {
                     let res = fut.await;
 
                     resolved.set(true);
+                    in_flight.set(in_flight.get() - 1);
 
                     set_value.update(|n| *n = Some(res));
-                    set_loading.update(|n| *n = false);
+                    // an earlier fetch resolving doesn't mean a later one has
+                    if in_flight.get() == 0 {
+                        set_loading.update(|n| *n = false);
+                    }
 
                     for suspense_context in suspense_contexts.borrow().iter() {
                         suspense_context.decrement();
                     }
+
+                    _ = with_runtime(runtime, |runtime| runtime.wake_idle_waiters());
                 }
             })
         });
@@ -633,8 +1251,12 @@ where
                     let mut tx = tx.clone();
                     move |value| {
                         if let Some(value) = value.as_ref() {
-                            tx.try_send((id, value.to_json().expect("could not serialize Resource")))
-                                .expect("failed while trying to write to Resource serializer");
+                            // only the first value is needed: any later ones are either sent
+                            // as patches, or not at all
+                            _ = tx.try_send((
+                                id,
+                                value.to_json().expect("could not serialize Resource"),
+                            ));
                         }
                     }
                 })
@@ -644,20 +1266,77 @@ where
             rx.next().await.expect("failed while trying to resolve Resource serializer")
         })
     }
+
+    pub fn resource_to_patch_stream(&self, id: ResourceId) -> PinnedStream<(ResourceId, String)>
+    where
+        T: Serializable,
+    {
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let value = self.value;
+        let prev = RefCell::new(None::<serde_json::Value>);
+        create_isomorphic_effect(self.scope, move |_| {
+            value.with(|value| {
+                let json = match value.as_ref() {
+                    Some(value) => value.to_json().expect("could not serialize Resource"),
+                    None => return,
+                };
+                let current: serde_json::Value =
+                    serde_json::from_str(&json).expect("could not parse Resource JSON");
+                let mut prev = prev.borrow_mut();
+                // the first value is sent in full by the serialization resolver
+                if let Some(prev) = prev.as_ref() {
+                    let patch = JsonPatch::diff(prev, &current);
+                    if !patch.is_empty() {
+                        let patch =
+                            serde_json::to_string(&patch).expect("could not serialize patch");
+                        _ = tx.unbounded_send((id, patch));
+                    }
+                }
+                *prev = Some(current);
+            })
+        });
+        Box::pin(rx)
+    }
 }
 
+#[derive(Clone)]
 pub(crate) enum AnyResource {
     Unserializable(Rc<dyn UnserializableResource>),
     Serializable(Rc<dyn SerializableResource>),
 }
 
+impl AnyResource {
+    // whether a fetch is in flight, and the resource has been read under `context`
+    pub(crate) fn is_pending_under(&self, context: &SuspenseContext) -> bool {
+        match self {
+            AnyResource::Unserializable(res) => res.is_pending_under(context),
+            AnyResource::Serializable(res) => res.is_pending_under(context),
+        }
+    }
+
+    pub(crate) fn refetch(&self) {
+        match self {
+            AnyResource::Unserializable(res) => res.refetch(),
+            AnyResource::Serializable(res) => res.refetch(),
+        }
+    }
+}
+
 pub(crate) trait SerializableResource {
     fn as_any(&self) -> &dyn Any;
 
+    fn is_loading(&self) -> bool;
+
+    fn is_pending_under(&self, context: &SuspenseContext) -> bool;
+
+    fn refetch(&self);
+
     fn to_serialization_resolver(
         &self,
         id: ResourceId,
     ) -> Pin<Box<dyn Future<Output = (ResourceId, String)>>>;
+
+    fn to_patch_stream(&self, id: ResourceId) -> Option<PinnedStream<(ResourceId, String)>>;
 }
 
 impl<S, T> SerializableResource for ResourceState<S, T>
@@ -669,6 +1348,18 @@ where
         self
     }
 
+    fn is_loading(&self) -> bool {
+        self.in_flight.get() > 0
+    }
+
+    fn is_pending_under(&self, context: &SuspenseContext) -> bool {
+        self.in_flight.get() > 0 && self.suspense_contexts.borrow().contains(context)
+    }
+
+    fn refetch(&self) {
+        self.load(true);
+    }
+
     fn to_serialization_resolver(
         &self,
         id: ResourceId,
@@ -676,18 +1367,40 @@ where
         let fut = self.resource_to_serialization_resolver(id);
         Box::pin(fut)
     }
+
+    fn to_patch_stream(&self, id: ResourceId) -> Option<PinnedStream<(ResourceId, String)>> {
+        self.patchable.then(|| self.resource_to_patch_stream(id))
+    }
 }
 
 pub(crate) trait UnserializableResource {
     fn as_any(&self) -> &dyn Any;
+
+    fn is_loading(&self) -> bool;
+
+    fn is_pending_under(&self, context: &SuspenseContext) -> bool;
+
+    fn refetch(&self);
 }
 
 impl<S, T> UnserializableResource for ResourceState<S, T>
 where
-    S: Debug,
+    S: Debug + Clone,
     T: Debug,
 {
     fn as_any(&self) -> &dyn Any {
         self
     }
+
+    fn is_loading(&self) -> bool {
+        self.in_flight.get() > 0
+    }
+
+    fn is_pending_under(&self, context: &SuspenseContext) -> bool {
+        self.in_flight.get() > 0 && self.suspense_contexts.borrow().contains(context)
+    }
+
+    fn refetch(&self) {
+        self.load(true);
+    }
 }
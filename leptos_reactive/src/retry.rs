@@ -0,0 +1,453 @@
+use crate::{
+    create_signal, idle::sleep, on_cleanup, runtime::PinnedFuture, ReadSignal, Scope, WriteSignal,
+};
+use futures::{channel::oneshot, future::select};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+/// How a resource whose fetcher returns a [Result] retries a fetch that fails, as set with
+/// [ResourceOptions::retry](crate::ResourceOptions::retry).
+///
+/// After the `n`th failed attempt, the resource waits for `base_delay * factor^(n - 1)` before
+/// trying again, until it has made `max_attempts` attempts in all, when it gives up and
+/// resolves to the last error. While it’s retrying, the resource is still loading, and
+/// [Resource::attempt](crate::Resource::attempt) counts the retries. If the browser is offline
+/// when the wait is over, the next attempt waits until it’s back online.
+///
+/// Retries only happen in the browser: on the server, the resource resolves to the first error.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::time::Duration;
+/// #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// enum ApiError {
+///   Unavailable,
+///   NotFound,
+/// }
+///
+/// let policy = RetryPolicy::new(5)
+///   .base_delay(Duration::from_millis(250))
+///   .jitter(0.5)
+///   // there’s no point asking again for something that doesn’t exist
+///   .should_retry(|e: &ApiError| matches!(e, ApiError::Unavailable));
+/// ```
+pub struct RetryPolicy<E> {
+    max_attempts: u32,
+    base_delay: Duration,
+    factor: f64,
+    jitter: f64,
+    should_retry: Rc<dyn Fn(&E) -> bool>,
+}
+
+impl<E> RetryPolicy<E> {
+    /// A policy that makes up to `max_attempts` attempts in all (including the first), waiting
+    /// for one second after the first failure and twice as long after each one after that.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            base_delay: Duration::from_secs(1),
+            factor: 2.0,
+            jitter: 0.0,
+            should_retry: Rc::new(|_| true),
+        }
+    }
+
+    /// Sets how long to wait after the first failed attempt.
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Sets how many times longer each wait is than the one before it.
+    pub fn factor(mut self, factor: f64) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    /// Shortens each wait by a random fraction of up to `jitter` (between `0.0` and `1.0`),
+    /// so that many clients that failed at once don’t all retry at once.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Only retries errors for which `should_retry` returns `true`; any other error is
+    /// returned right away.
+    pub fn should_retry(mut self, should_retry: impl Fn(&E) -> bool + 'static) -> Self {
+        self.should_retry = Rc::new(should_retry);
+        self
+    }
+
+    /// How long to wait after the `failures`th failed attempt.
+    fn delay(&self, failures: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .mul_f64(self.factor.max(0.0).powi(failures as i32 - 1));
+        if self.jitter > 0.0 {
+            delay.mul_f64(1.0 - self.jitter * random())
+        } else {
+            delay
+        }
+    }
+}
+
+impl<E> Clone for RetryPolicy<E> {
+    fn clone(&self) -> Self {
+        Self {
+            max_attempts: self.max_attempts,
+            base_delay: self.base_delay,
+            factor: self.factor,
+            jitter: self.jitter,
+            should_retry: Rc::clone(&self.should_retry),
+        }
+    }
+}
+
+impl<E> Default for RetryPolicy<E> {
+    /// Makes up to three attempts, waiting one and then two seconds in between.
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl<E> std::fmt::Debug for RetryPolicy<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("factor", &self.factor)
+            .field("jitter", &self.jitter)
+            .finish()
+    }
+}
+
+/// Makes a fetch, retrying it as the policy says.
+pub(crate) type Retry<T> =
+    Rc<dyn Fn(&Rc<RetryState>, Box<dyn Fn() -> PinnedFuture<T>>) -> PinnedFuture<T>>;
+
+impl<E> RetryPolicy<E> {
+    pub(crate) fn into_retry<T>(self) -> Retry<Result<T, E>>
+    where
+        T: 'static,
+        E: 'static,
+    {
+        let policy = Rc::new(self);
+        Rc::new(move |state, fetch| {
+            let policy = Rc::clone(&policy);
+            let state = Rc::clone(state);
+            let fetch_id = state.start();
+            Box::pin(async move {
+                let mut failures = 0;
+                loop {
+                    let result = fetch().await;
+                    let Err(e) = &result else {
+                        return result;
+                    };
+                    failures += 1;
+                    if failures >= policy.max_attempts
+                        || !(policy.should_retry)(e)
+                        || !state.is_current(fetch_id)
+                    {
+                        return result;
+                    }
+
+                    state.set_attempt.set(failures);
+                    state.wait(policy.delay(failures)).await;
+                    // the resource has fetched again, or has been disposed, in the meantime
+                    if !state.is_current(fetch_id) {
+                        return result;
+                    }
+                }
+            })
+        })
+    }
+}
+
+/// The retries of a resource created with a [RetryPolicy].
+pub(crate) struct RetryState {
+    pub(crate) attempt: ReadSignal<u32>,
+    set_attempt: WriteSignal<u32>,
+    // identifies the latest fetch: an earlier one stops retrying
+    fetch_id: Cell<usize>,
+    disposed: Cell<bool>,
+    // ends the current wait early
+    skip_wait: RefCell<Option<oneshot::Sender<()>>>,
+}
+
+impl RetryState {
+    pub(crate) fn new(cx: Scope) -> Rc<Self> {
+        let (attempt, set_attempt) = create_signal(cx, 0);
+        let state = Rc::new(Self {
+            attempt,
+            set_attempt,
+            fetch_id: Cell::new(0),
+            disposed: Cell::new(false),
+            skip_wait: Default::default(),
+        });
+        on_cleanup(cx, {
+            let state = Rc::clone(&state);
+            move || {
+                state.disposed.set(true);
+                state.retry_now();
+            }
+        });
+        state
+    }
+
+    /// Ends the current wait, so the next attempt is made right away.
+    pub(crate) fn retry_now(&self) {
+        if let Some(tx) = self.skip_wait.take() {
+            _ = tx.send(());
+        }
+    }
+
+    fn start(&self) -> usize {
+        let fetch_id = self.fetch_id.get() + 1;
+        self.fetch_id.set(fetch_id);
+        // wakes up the previous fetch, if it's waiting, so that it stops
+        self.retry_now();
+        self.set_attempt.set_if_changed(0);
+        fetch_id
+    }
+
+    fn is_current(&self, fetch_id: usize) -> bool {
+        !self.disposed.get() && self.fetch_id.get() == fetch_id
+    }
+
+    async fn wait(&self, delay: Duration) {
+        let (tx, rx) = oneshot::channel();
+        *self.skip_wait.borrow_mut() = Some(tx);
+        let wait = Box::pin(async move {
+            sleep(delay).await;
+            until_online().await;
+        });
+        select(wait, rx).await;
+    }
+}
+
+// resolves once the browser is online, which it usually already is
+fn until_online() -> PinnedFuture<()> {
+    #[cfg(any(test, feature = "testing"))]
+    if crate::testing::is_active() {
+        return Box::pin(crate::testing::until_online());
+    }
+
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let window = web_sys::window().expect("no window");
+            if window.navigator().on_line() {
+                return Box::pin(async {});
+            }
+            let promise = js_sys::Promise::new(&mut |resolve, _| {
+                let options = web_sys::AddEventListenerOptions::new();
+                options.set_once(true);
+                _ = window.add_event_listener_with_callback_and_add_event_listener_options(
+                    "online", &resolve, &options,
+                );
+            });
+            Box::pin(async move {
+                _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+            })
+        } else {
+            Box::pin(async {})
+        }
+    }
+}
+
+// a number in `0.0..1.0`, which doesn't need to be unpredictable
+fn random() -> f64 {
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            js_sys::Math::random()
+        } else {
+            use std::{
+                collections::hash_map::RandomState,
+                hash::{BuildHasher, Hasher},
+            };
+
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u64(
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|now| now.as_nanos() as u64)
+                    .unwrap_or_default(),
+            );
+            (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+        }
+    }
+}
+
+#[cfg(all(test, not(any(feature = "csr", feature = "hydrate", feature = "ssr"))))]
+mod tests {
+    use super::*;
+    use crate::{create_resource_with_options, testing::TestRuntime, Resource, ResourceOptions};
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    enum Error {
+        Unavailable,
+        NotFound,
+    }
+
+    type Flaky = Resource<(), Result<u32, Error>>;
+
+    // a resource whose fetcher fails with each of the `errors` in turn, then succeeds
+    fn flaky(cx: Scope, errors: Vec<Error>, policy: RetryPolicy<Error>) -> (Flaky, Rc<Cell<u32>>) {
+        let calls = Rc::new(Cell::new(0));
+        let resource = create_resource_with_options(
+            cx,
+            || (),
+            {
+                let calls = Rc::clone(&calls);
+                move |_| {
+                    let call = calls.get() + 1;
+                    calls.set(call);
+                    let result = match errors.get(call as usize - 1) {
+                        Some(e) => Err(e.clone()),
+                        None => Ok(call),
+                    };
+                    async move { result }
+                }
+            },
+            ResourceOptions::new().retry(policy),
+        );
+        (resource, calls)
+    }
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn retries_with_exponential_backoff_until_it_succeeds() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let (resource, calls) = flaky(
+            cx,
+            vec![Error::Unavailable; 3],
+            RetryPolicy::new(5).base_delay(ms(100)),
+        );
+        let attempt = resource.attempt();
+
+        rt.flush();
+        assert_eq!(calls.get(), 1);
+        assert_eq!(attempt.get(), 1);
+        assert!(resource.loading().get());
+        assert_eq!(resource.read(), None);
+
+        rt.advance(ms(99));
+        assert_eq!(calls.get(), 1);
+        rt.advance(ms(1));
+        assert_eq!(calls.get(), 2);
+        assert_eq!(attempt.get(), 2);
+
+        rt.advance(ms(200));
+        assert_eq!(calls.get(), 3);
+        assert_eq!(attempt.get(), 3);
+        assert!(resource.loading().get());
+
+        rt.advance(ms(400));
+        assert_eq!(calls.get(), 4);
+        assert_eq!(resource.read(), Some(Ok(4)));
+        assert!(!resource.loading().get());
+    }
+
+    #[test]
+    fn gives_up_after_the_last_attempt() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let (resource, calls) = flaky(
+            cx,
+            vec![Error::Unavailable; 5],
+            RetryPolicy::new(3).base_delay(ms(100)).factor(1.0),
+        );
+
+        rt.flush();
+        rt.advance(ms(100));
+        assert_eq!(resource.read(), None);
+        rt.advance(ms(100));
+        assert_eq!(calls.get(), 3);
+        assert_eq!(resource.read(), Some(Err(Error::Unavailable)));
+        assert!(!resource.loading().get());
+
+        rt.advance(ms(10_000));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn only_retries_some_errors() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let (resource, calls) = flaky(
+            cx,
+            vec![Error::Unavailable, Error::NotFound],
+            RetryPolicy::new(5)
+                .base_delay(ms(100))
+                .should_retry(|e| *e == Error::Unavailable),
+        );
+
+        rt.flush();
+        rt.advance(ms(100));
+        assert_eq!(calls.get(), 2);
+        assert_eq!(resource.read(), Some(Err(Error::NotFound)));
+    }
+
+    #[test]
+    fn retry_now_skips_the_wait() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let (resource, calls) = flaky(
+            cx,
+            vec![Error::Unavailable],
+            RetryPolicy::new(2).base_delay(ms(1000)),
+        );
+
+        rt.flush();
+        assert_eq!(calls.get(), 1);
+        resource.retry_now();
+        rt.flush();
+        assert_eq!(calls.get(), 2);
+        assert_eq!(resource.read(), Some(Ok(2)));
+        assert_eq!(rt.now(), Duration::ZERO);
+    }
+
+    #[test]
+    fn waits_until_online_to_retry() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let (resource, calls) = flaky(
+            cx,
+            vec![Error::Unavailable],
+            RetryPolicy::new(2).base_delay(ms(100)),
+        );
+
+        rt.set_online(false);
+        rt.flush();
+        rt.advance(ms(1000));
+        assert_eq!(calls.get(), 1);
+        assert!(resource.loading().get());
+
+        rt.set_online(true);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(resource.read(), Some(Ok(2)));
+    }
+
+    #[test]
+    fn stops_retrying_when_disposed() {
+        let rt = TestRuntime::new();
+        let (calls, disposer) = rt.scope().run_child_scope(|cx| {
+            let (_, calls) = flaky(
+                cx,
+                vec![Error::Unavailable; 5],
+                RetryPolicy::new(5).base_delay(ms(100)),
+            );
+            calls
+        });
+        rt.flush();
+        disposer.dispose();
+        rt.advance(ms(1000));
+        assert_eq!(calls.get(), 1);
+    }
+}
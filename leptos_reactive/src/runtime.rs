@@ -1,10 +1,15 @@
 use crate::{
-    hydration::SharedContext, serialization::Serializable, AnyEffect, AnyResource, Effect,
-    EffectId, Memo, ReadSignal, ResourceId, ResourceState, RwSignal, Scope, ScopeDisposer, ScopeId,
-    ScopeProperty, SignalId, WriteSignal,
+    hydration::SharedContext, serialization::Serializable, AnyEffect, AnyResource, Disposable,
+    DisposableId, Effect, EffectId, EffectState, Memo, ReadSignal, ResourceId, ResourceState,
+    RwSignal, Scope, ScopeDisposer, ScopeId, ScopeProperty, SignalId, SsrEffectPolicy,
+    SuspenseContext, WriteSignal,
 };
 use cfg_if::cfg_if;
-use futures::stream::FuturesUnordered;
+use futures::{
+    future::Shared,
+    stream::{FuturesUnordered, SelectAll},
+    FutureExt, Stream,
+};
 use slotmap::{SecondaryMap, SlotMap, SparseSecondaryMap};
 use std::{
     any::{Any, TypeId},
@@ -12,12 +17,16 @@ use std::{
     collections::{HashMap, HashSet},
     fmt::Debug,
     future::Future,
+    hash::Hash,
     marker::PhantomData,
     pin::Pin,
     rc::Rc,
+    task::Waker,
 };
+use thiserror::Error;
 
 pub(crate) type PinnedFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+pub(crate) type PinnedStream<T> = Pin<Box<dyn Stream<Item = T>>>;
 
 cfg_if! {
     if #[cfg(any(feature = "csr", feature = "hydrate"))] {
@@ -33,20 +42,118 @@ cfg_if! {
 
 /// Get the selected runtime from the thread-local set of runtimes. On the server,
 /// this will return the correct runtime. In the browser, there should only be one runtime.
-pub(crate) fn with_runtime<T>(id: RuntimeId, f: impl FnOnce(&Runtime) -> T) -> T {
+///
+/// Returns [RuntimeDisposed] if the runtime has already been disposed (or if the
+/// thread-local storage holding it is being torn down), which can happen to code that
+/// outlives it, like an async callback that finishes after a server render.
+pub(crate) fn with_runtime<T>(
+    id: RuntimeId,
+    f: impl FnOnce(&Runtime) -> T,
+) -> Result<T, RuntimeDisposed> {
     // in the browser, everything should exist under one runtime
     cfg_if! {
         if #[cfg(any(feature = "csr", feature = "hydrate"))] {
             _ = id;
-            RUNTIME.with(|runtime| f(runtime))
+            RUNTIME.try_with(|runtime| f(runtime)).map_err(|_| RuntimeDisposed)
         } else {
-            RUNTIMES.with(|runtimes| {
+            RUNTIMES
+                .try_with(|runtimes| {
+                    let runtimes = runtimes.borrow();
+                    runtimes.get(id).map(f)
+                })
+                .ok()
+                .flatten()
+                .ok_or(RuntimeDisposed)
+        }
+    }
+}
+
+/// Like [with_runtime], for the runtime's own bookkeeping and the APIs that are documented to
+/// panic when their runtime is gone, because carrying on would hide a real bug.
+///
+/// # Panics
+/// Panics if the runtime has already been disposed.
+pub(crate) fn expect_runtime<T>(id: RuntimeId, f: impl FnOnce(&Runtime) -> T) -> T {
+    match with_runtime(id, f) {
+        Ok(value) => value,
+        Err(e) => panic!("{e}"),
+    }
+}
+
+/// Runs `f` if the runtime still exists, or returns [RuntimeDisposed] without running it.
+///
+/// This is an escape hatch for integration code (like a server that renders each request in
+/// its own runtime, or a callback from a JavaScript library) that may be called after the
+/// runtime it was set up with has been disposed, and would rather skip its work than call
+/// into the reactive system and find out.
+///
+/// Most APIs already handle a disposed runtime on their own: the `try_` variants of signal
+/// reads return `None`, writes are dropped (with a warning in debug builds), and creating an
+/// effect or resource is refused. The APIs that still panic, because ignoring the call would
+/// hide a bug, are:
+/// - reading a signal or memo with `get` or `with` (use `try_get` or `try_with` instead)
+/// - creating a signal, memo, or child [Scope]
+/// - getting a [Resource](crate::Resource)’s `loading`, `source`, `error`, or `attempt` signal
+/// - the renderer’s hydration and serialization hooks on [Scope]
+/// - the [testing](crate::testing) helpers
+///
+/// The runtime is not borrowed while `f` runs, so it can use the reactive system as usual.
+/// ```
+/// # use leptos_reactive::*;
+/// let runtime = create_runtime();
+/// assert_eq!(try_with_runtime(runtime, || 1), Ok(1));
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// runtime.dispose();
+/// assert_eq!(try_with_runtime(runtime, || 1), Err(RuntimeDisposed));
+/// # }
+/// ```
+pub fn try_with_runtime<T>(
+    runtime: RuntimeId,
+    f: impl FnOnce() -> T,
+) -> Result<T, RuntimeDisposed> {
+    with_runtime(runtime, |_| ())?;
+    Ok(f())
+}
+
+/// The error returned when using a [Runtime](crate::RuntimeId) that has already been disposed.
+/// See [try_with_runtime].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("tried to access a runtime that has already been disposed")]
+pub struct RuntimeDisposed;
+
+/// Runs `f` without subscribing the running effect to the signals it reads, whichever runtime
+/// that effect belongs to. This is for reading signals without a [Scope], like in a derived
+/// [Signal](crate::Signal) created from a closure.
+#[cfg_attr(not(feature = "stable"), allow(dead_code))] // only closures are converted on stable
+pub(crate) fn untrack_all<T>(f: impl FnOnce() -> T) -> T {
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let prev_observer = RUNTIME.try_with(|runtime| runtime.observer.take()).ok().flatten();
+            let untracked_result = f();
+            if let Some(prev_observer) = prev_observer {
+                _ = RUNTIME.try_with(|runtime| runtime.observer.set(Some(prev_observer)));
+            }
+            untracked_result
+        } else {
+            let prev_observers = RUNTIMES
+                .try_with(|runtimes| {
+                    runtimes
+                        .borrow()
+                        .iter()
+                        .filter_map(|(id, runtime)| Some((id, runtime.observer.take()?)))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let untracked_result = f();
+            _ = RUNTIMES.try_with(|runtimes| {
                 let runtimes = runtimes.borrow();
-                let runtime = runtimes
-                    .get(id)
-                    .expect("Tried to access a Runtime that no longer exists.");
-                f(runtime)
-            })
+                for (id, prev_observer) in prev_observers {
+                    if let Some(runtime) = runtimes.get(id) {
+                        runtime.observer.set(Some(prev_observer));
+                    }
+                }
+            });
+            untracked_result
         }
     }
 }
@@ -71,28 +178,68 @@ slotmap::new_key_type! {
 
 impl RuntimeId {
     /// Removes the runtime, disposing all its child [Scope](crate::Scope)s.
+    ///
+    /// This does nothing in the browser, where the runtime lives as long as the page.
     pub fn dispose(self) {
         cfg_if! {
             if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
-                let runtime = RUNTIMES.with(move |runtimes| runtimes.borrow_mut().remove(self));
-                if let Some(runtime) = runtime {
-                    for (scope_id, _) in runtime.scopes.borrow().iter() {
-                        let scope = Scope {
-                            runtime: self,
-                            id: scope_id,
-                        };
-                        scope.dispose();
-                    }
+                // root scopes dispose of their children, and their cleanups may still need
+                // the runtime, so it is only removed once they have all been disposed
+                let roots = with_runtime(self, |runtime| {
+                    let parents = runtime.scope_parents.borrow();
+                    runtime
+                        .scopes
+                        .borrow()
+                        .keys()
+                        .filter(|id| !parents.contains_key(*id))
+                        .collect::<Vec<_>>()
+                })
+                .ok();
+                for id in roots.into_iter().flatten() {
+                    Scope { runtime: self, id }.dispose();
                 }
+                _ = RUNTIMES.try_with(move |runtimes| runtimes.borrow_mut().remove(self));
             }
         }
     }
 
+    /// Sets how [create_effect](crate::create_effect) treats effects while rendering on the
+    /// server. This has no effect in the browser, where effects always run.
+    pub fn set_ssr_effect_policy(self, policy: SsrEffectPolicy) {
+        expect_runtime(self, |runtime| runtime.ssr_effect_policy.set(policy))
+    }
+
+    /// Turns strict mode on or off, for development, to make code that isn’t idempotent or
+    /// doesn’t clean up after itself (like an effect that adds an event listener, but never
+    /// removes it) easy to spot:
+    /// - each effect created with [create_effect](crate::create_effect),
+    ///   [create_isomorphic_effect](crate::create_isomorphic_effect),
+    ///   [create_effect_after](crate::create_effect_after), or
+    ///   [create_effect_with_cleanup](crate::create_effect_with_cleanup) runs twice when it is
+    ///   created. Before the second run, the cleanups registered during the first one (with
+    ///   [on_cleanup](crate::on_cleanup), or returned to `create_effect_with_cleanup`) are run,
+    ///   as though the effect had been disposed of and created again. If the two runs return
+    ///   different values, a warning is logged.
+    /// - each component whose props can be cloned is created, unmounted, and created again when
+    ///   it is rendered, as described in [Scope::run_component](crate::Scope::run_component).
+    ///
+    /// This does nothing in release builds.
+    pub fn set_strict_mode(self, enabled: bool) {
+        expect_runtime(self, |runtime| runtime.strict_mode.set(enabled))
+    }
+
+    /// Whether strict mode is on (see [set_strict_mode](Self::set_strict_mode)). This is always
+    /// `false` in release builds.
+    pub fn is_strict_mode(self) -> bool {
+        cfg!(debug_assertions)
+            && with_runtime(self, |runtime| runtime.strict_mode.get()).unwrap_or(false)
+    }
+
     pub(crate) fn raw_scope_and_disposer(self) -> (Scope, ScopeDisposer) {
-        with_runtime(self, |runtime| {
+        expect_runtime(self, |runtime| {
             let id = { runtime.scopes.borrow_mut().insert(Default::default()) };
             let scope = Scope { runtime: self, id };
-            let disposer = ScopeDisposer(Box::new(move || scope.dispose()));
+            let disposer = ScopeDisposer(scope);
             (scope, disposer)
         })
     }
@@ -102,14 +249,14 @@ impl RuntimeId {
         f: impl FnOnce(Scope) -> T,
         parent: Option<Scope>,
     ) -> (T, ScopeId, ScopeDisposer) {
-        with_runtime(self, |runtime| {
+        expect_runtime(self, |runtime| {
             let id = { runtime.scopes.borrow_mut().insert(Default::default()) };
             if let Some(parent) = parent {
                 runtime.scope_parents.borrow_mut().insert(id, parent.id);
             }
             let scope = Scope { runtime: self, id };
             let val = f(scope);
-            let disposer = ScopeDisposer(Box::new(move || scope.dispose()));
+            let disposer = ScopeDisposer(scope);
             (val, id, disposer)
         })
     }
@@ -120,15 +267,19 @@ impl RuntimeId {
         ret
     }
 
+    #[track_caller]
     pub(crate) fn create_signal<T>(self, value: T) -> (ReadSignal<T>, WriteSignal<T>)
     where
         T: Any + 'static,
     {
-        let id = with_runtime(self, |runtime| {
-            runtime
+        let location = std::panic::Location::caller();
+        let id = expect_runtime(self, |runtime| {
+            let id = runtime
                 .signals
                 .borrow_mut()
-                .insert(Rc::new(RefCell::new(value)))
+                .insert(Rc::new(RefCell::new(value)));
+            runtime.trace(id.into(), location, Some(std::any::type_name::<T>()));
+            id
         });
         (
             ReadSignal {
@@ -144,15 +295,19 @@ impl RuntimeId {
         )
     }
 
+    #[track_caller]
     pub(crate) fn create_rw_signal<T>(self, value: T) -> RwSignal<T>
     where
         T: Any + 'static,
     {
-        let id = with_runtime(self, |runtime| {
-            runtime
+        let location = std::panic::Location::caller();
+        let id = expect_runtime(self, |runtime| {
+            let id = runtime
                 .signals
                 .borrow_mut()
-                .insert(Rc::new(RefCell::new(value)))
+                .insert(Rc::new(RefCell::new(value)));
+            runtime.trace(id.into(), location, Some(std::any::type_name::<T>()));
+            id
         });
         RwSignal {
             runtime: self,
@@ -161,37 +316,75 @@ impl RuntimeId {
         }
     }
 
+    #[track_caller]
     pub(crate) fn create_effect<T>(self, f: impl Fn(Option<T>) -> T + 'static) -> EffectId
     where
         T: Any + 'static,
     {
+        let location = std::panic::Location::caller();
         with_runtime(self, |runtime| {
             let effect = Effect {
                 f,
                 value: RefCell::new(None),
             };
             let id = { runtime.effects.borrow_mut().insert(Rc::new(effect)) };
-            id.run::<T>(self);
+            runtime.trace(id.into(), location, None);
+            runtime
+                .effect_states
+                .borrow_mut()
+                .insert(id, EffectState::Dirty);
+            if !runtime.defer_effect(id) {
+                runtime.update_if_necessary(self, id);
+            }
             id
         })
+        .unwrap_or_else(|e| {
+            crate::debug_warn!(
+                "[create_effect] The effect created at {location} won’t run, because {e}."
+            );
+            EffectId::default()
+        })
     }
 
-    pub(crate) fn create_memo<T>(self, f: impl Fn(Option<&T>) -> T + 'static) -> Memo<T>
+    #[track_caller]
+    pub(crate) fn create_memo<T>(self, cx: Scope, f: impl Fn(Option<&T>) -> T + 'static) -> Memo<T>
     where
         T: PartialEq + Any + 'static,
     {
+        let location = std::panic::Location::caller();
         let (read, write) = self.create_signal(None);
 
-        self.create_effect(move |_| {
-            let (new, changed) = read.with_no_subscription(|p| {
-                let new = f(p.as_ref());
-                let changed = Some(&new) != p.as_ref();
-                (new, changed)
-            });
+        let effect = Effect {
+            f: move |_| {
+                let (new, changed) = read.with_no_subscription(|p| {
+                    let new = f(p.as_ref());
+                    let changed = Some(&new) != p.as_ref();
+                    (new, changed)
+                });
 
-            if changed {
-                write.update(|n| *n = Some(new));
-            }
+                if changed {
+                    write.update(|n| *n = Some(new));
+                }
+            },
+            value: RefCell::new(None),
+        };
+
+        // memos don't run until they're first read
+        let id = expect_runtime(self, |runtime| {
+            let id = { runtime.effects.borrow_mut().insert(Rc::new(effect)) };
+            runtime.trace(id.into(), location, None);
+            runtime
+                .effect_states
+                .borrow_mut()
+                .insert(id, EffectState::Dirty);
+            runtime.memos.borrow_mut().insert(read.id, id);
+            runtime.memo_signals.borrow_mut().insert(id, read.id);
+            id
+        });
+        // so that both are disposed of with the scope
+        cx.with_scope_property(|prop| {
+            prop.push(ScopeProperty::Signal(read.id));
+            prop.push(ScopeProperty::Effect(id));
         });
 
         Memo(read)
@@ -203,17 +396,69 @@ pub(crate) struct Runtime {
     pub shared_context: RefCell<Option<SharedContext>>,
     pub observer: Cell<Option<EffectId>>,
     pub scopes: RefCell<SlotMap<ScopeId, RefCell<Vec<ScopeProperty>>>>,
-    pub scope_parents: RefCell<SparseSecondaryMap<ScopeId, ScopeId>>,
+    pub scope_parents: RefCell<SecondaryMap<ScopeId, ScopeId>>,
     pub scope_children: RefCell<SparseSecondaryMap<ScopeId, Vec<ScopeId>>>,
+    // dense rather than sparse, so that `use_context` can skip over the (many) scopes
+    // that have never provided a context with a cheap indexed lookup
     #[allow(clippy::type_complexity)]
-    pub scope_contexts: RefCell<SparseSecondaryMap<ScopeId, HashMap<TypeId, Box<dyn Any>>>>,
+    pub scope_contexts: RefCell<SecondaryMap<ScopeId, HashMap<TypeId, Box<dyn Any>>>>,
+    // for each context type, the scope that each already-resolved scope gets it from
+    pub context_providers: RefCell<HashMap<TypeId, SecondaryMap<ScopeId, Option<ScopeId>>>>,
     #[allow(clippy::type_complexity)]
     pub scope_cleanups: RefCell<SparseSecondaryMap<ScopeId, Vec<Box<dyn FnOnce()>>>>,
     pub signals: RefCell<SlotMap<SignalId, Rc<RefCell<dyn Any>>>>,
     pub signal_subscribers: RefCell<SecondaryMap<SignalId, RefCell<HashSet<EffectId>>>>,
     pub effects: RefCell<SlotMap<EffectId, Rc<dyn AnyEffect>>>,
     pub effect_sources: RefCell<SecondaryMap<EffectId, RefCell<HashSet<SignalId>>>>,
+    pub effect_states: RefCell<SecondaryMap<EffectId, EffectState>>,
+    // the effect that computes each memo's value, and the signal that holds it
+    pub memos: RefCell<SecondaryMap<SignalId, EffectId>>,
+    pub memo_signals: RefCell<SecondaryMap<EffectId, SignalId>>,
+    // effects created by the renderer: these rerun before any others, so that the DOM writes
+    // they stage can be applied together before user effects get a chance to read the DOM
+    pub render_effects: RefCell<SparseSecondaryMap<EffectId, ()>>,
+    pub flushing_render_effects: Cell<bool>,
+    pub render_flush: Cell<Option<fn()>>,
+    // the effects that each effect created with `create_effect_after` must run after
+    pub effects_after: RefCell<SparseSecondaryMap<EffectId, Vec<EffectId>>>,
     pub resources: RefCell<SlotMap<ResourceId, AnyResource>>,
+    // the key each serializable resource is serialized under, while server rendering or hydrating
+    pub resource_keys: RefCell<SecondaryMap<ResourceId, String>>,
+    // the futures shared by cached resources, keyed by the types of their cache keys and values
+    pub resource_cache: RefCell<HashMap<TypeId, Box<dyn Any>>>,
+    // receives the assets declared with `ResourceOptions::preload_href` while server rendering
+    // (see `on_resource_preload`), and those declared before it was set
+    #[allow(clippy::type_complexity)]
+    pub resource_preload: Cell<Option<fn(Scope, String)>>,
+    pub pending_preloads: RefCell<Vec<(Scope, String)>>,
+    // the handles registered with `Scope::register_disposable`, until they are disposed of
+    pub disposables: RefCell<SlotMap<DisposableId, Box<dyn Disposable>>>,
+    // the total of every suspense context's pending resources, created when first needed
+    pub pending_resources: Cell<Option<RwSignal<usize>>>,
+    // the suspense context of each fragment registered with `Scope::register_suspense`, by key
+    pub suspense_fragments: RefCell<HashMap<String, SuspenseContext>>,
+    // tasks waiting in `Scope::wait_for_idle`, woken whenever a resource or fragment may have settled
+    pub idle_wakers: RefCell<Vec<Waker>>,
+    // how `create_effect` treats effects while server rendering, and where those that have
+    // already panicked because they used a browser API were created, so each is logged once
+    pub ssr_effect_policy: Cell<SsrEffectPolicy>,
+    pub strict_mode: Cell<bool>,
+    // whether signal writes notify their subscribers; see `Scope::batch_untrack`
+    pub silent: Cell<bool>,
+    // signals owned by scopes that are being disposed of a chunk at a time, which can no
+    // longer be written to (see `ScopeDisposer::dispose_deferred`)
+    pub tombstoned_signals: RefCell<HashSet<SignalId>>,
+    // where each signal, effect, and resource was created, for `Scope::lookup_signal` etc.
+    #[cfg(feature = "trace")]
+    pub traces: RefCell<HashMap<crate::NodeId, crate::Trace>>,
+    // how to print the values of the signals registered with `Scope::trace_values`
+    #[cfg(feature = "trace-signals")]
+    #[allow(clippy::type_complexity)]
+    pub value_formatters: RefCell<SecondaryMap<SignalId, fn(&dyn Any) -> String>>,
+    #[cfg(feature = "ssr")]
+    pub ssr_effect_panics: RefCell<HashSet<&'static std::panic::Location<'static>>>,
+    #[cfg(any(test, feature = "testing"))]
+    pub effect_queue: RefCell<Option<crate::testing::EffectQueue>>,
 }
 
 impl Debug for Runtime {
@@ -228,6 +473,8 @@ impl Debug for Runtime {
             .field("signal_subscribers", &self.signal_subscribers)
             .field("effects", &self.effects.borrow().len())
             .field("effect_sources", &self.effect_sources)
+            .field("effect_states", &self.effect_states)
+            .field("disposables", &self.disposables.borrow().len())
             .finish()
     }
 }
@@ -237,6 +484,195 @@ impl Runtime {
         Self::default()
     }
 
+    /// Queues the effect to be run later, if effects are being deferred by a `TestRuntime`.
+    /// Returns `false` if it should run now.
+    pub(crate) fn defer_effect(&self, id: EffectId) -> bool {
+        cfg_if! {
+            if #[cfg(any(test, feature = "testing"))] {
+                match &mut *self.effect_queue.borrow_mut() {
+                    Some(queue) => queue.defer(id),
+                    None => false,
+                }
+            } else {
+                _ = id;
+                false
+            }
+        }
+    }
+
+    /// Marks everything that depends on the signal as out of date, then reruns the effects
+    /// that turn out to need it.
+    ///
+    /// Render effects run first, followed by the renderer's flush of any DOM writes they
+    /// staged, so that other effects always see the updated DOM.
+    pub(crate) fn notify(&self, runtime_id: RuntimeId, signal: SignalId) {
+        self.notify_all(runtime_id, &[signal]);
+    }
+
+    /// Like [notify](Self::notify), for several signals that have changed together: each effect
+    /// that depends on any of them runs once.
+    pub(crate) fn notify_all(&self, runtime_id: RuntimeId, signals: &[SignalId]) {
+        // inside `Scope::batch_untrack`
+        if self.silent.get() {
+            return;
+        }
+        let mut effects = Vec::new();
+        for signal in signals {
+            self.mark_subscribers(*signal, EffectState::Dirty, &mut effects);
+        }
+        let effects = self.order_effects(effects);
+        let (render_effects, effects): (Vec<_>, Vec<_>) = {
+            let render_effects = self.render_effects.borrow();
+            effects
+                .into_iter()
+                .partition(|id| render_effects.contains_key(*id))
+        };
+
+        if !render_effects.is_empty() {
+            let prev_flushing = self.flushing_render_effects.replace(true);
+            for id in render_effects {
+                if !self.defer_effect(id) {
+                    self.update_if_necessary(runtime_id, id);
+                }
+            }
+            self.flushing_render_effects.set(prev_flushing);
+            if let Some(flush) = self.render_flush.get() {
+                flush();
+            }
+        }
+
+        for id in effects {
+            if !self.defer_effect(id) {
+                self.update_if_necessary(runtime_id, id);
+            }
+        }
+    }
+
+    /// Sorts the effects so that each one comes after any it has been ordered after (directly,
+    /// or through other effects) with [create_effect_after](crate::create_effect_after).
+    /// Otherwise, they keep the order they were marked in.
+    fn order_effects(&self, effects: Vec<EffectId>) -> Vec<EffectId> {
+        let effects_after = self.effects_after.borrow();
+        if effects_after.is_empty() {
+            return effects;
+        }
+
+        fn visit(
+            id: EffectId,
+            effects_after: &SparseSecondaryMap<EffectId, Vec<EffectId>>,
+            pending: &HashSet<EffectId>,
+            visited: &mut HashSet<EffectId>,
+            ordered: &mut Vec<EffectId>,
+        ) {
+            if !visited.insert(id) {
+                return;
+            }
+            // an effect can only be ordered after effects that already exist, so this can't cycle
+            for before in effects_after.get(id).into_iter().flatten() {
+                visit(*before, effects_after, pending, visited, ordered);
+            }
+            if pending.contains(&id) {
+                ordered.push(id);
+            }
+        }
+
+        let pending = effects.iter().copied().collect::<HashSet<_>>();
+        let mut visited = HashSet::new();
+        let mut ordered = Vec::with_capacity(effects.len());
+        for id in effects {
+            visit(id, &effects_after, &pending, &mut visited, &mut ordered);
+        }
+        ordered
+    }
+
+    fn mark_subscribers(&self, signal: SignalId, state: EffectState, effects: &mut Vec<EffectId>) {
+        let subs = {
+            let subs = self.signal_subscribers.borrow();
+            subs.get(signal).map(|subs| subs.borrow().clone())
+        };
+        for sub in subs.into_iter().flatten() {
+            self.mark(sub, state, effects);
+        }
+    }
+
+    // memos pass the mark on to their own subscribers, but only as `Check`: whether those
+    // need to rerun depends on whether the memo's value actually changes
+    fn mark(&self, id: EffectId, state: EffectState, effects: &mut Vec<EffectId>) {
+        let prev = {
+            let mut states = self.effect_states.borrow_mut();
+            match states.entry(id) {
+                Some(entry) => {
+                    let current = entry.or_default();
+                    let prev = *current;
+                    if prev >= state {
+                        return;
+                    }
+                    *current = state;
+                    prev
+                }
+                None => return,
+            }
+        };
+
+        // anything that wasn't clean has already passed the mark on
+        if prev == EffectState::Clean {
+            let memo_signal = self.memo_signals.borrow().get(id).copied();
+            match memo_signal {
+                Some(signal) => self.mark_subscribers(signal, EffectState::Check, effects),
+                None => effects.push(id),
+            }
+        }
+    }
+
+    fn state(&self, id: EffectId) -> EffectState {
+        self.effect_states
+            .borrow()
+            .get(id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn set_state(&self, id: EffectId, state: EffectState) {
+        if let Some(current) = self.effect_states.borrow_mut().get_mut(id) {
+            *current = state;
+        }
+    }
+
+    /// Brings an effect or memo up to date, rerunning it if it is `Dirty`, or if it is
+    /// `Check` and any of the memos it reads have changed.
+    pub(crate) fn update_if_necessary(&self, runtime_id: RuntimeId, id: EffectId) {
+        if self.state(id) == EffectState::Check {
+            let sources = {
+                let sources = self.effect_sources.borrow();
+                sources.get(id).map(|sources| sources.borrow().clone())
+            };
+            for source in sources.into_iter().flatten() {
+                let memo = self.memos.borrow().get(source).copied();
+                if let Some(memo) = memo {
+                    // if the memo changes, it marks this as dirty
+                    self.update_if_necessary(runtime_id, memo);
+                    if self.state(id) == EffectState::Dirty {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let dirty = self.state(id) == EffectState::Dirty;
+        self.set_state(id, EffectState::Clean);
+        if dirty {
+            id.run(runtime_id);
+        }
+    }
+
+    /// Computes the memo whose value is held in the signal, if it is out of date.
+    pub(crate) fn update_memo(&self, runtime_id: RuntimeId, signal: SignalId) {
+        let memo = self.memos.borrow().get(signal).copied();
+        if let Some(memo) = memo {
+            self.update_if_necessary(runtime_id, memo);
+        }
+    }
+
     pub(crate) fn create_unserializable_resource<S, T>(
         &self,
         state: Rc<ResourceState<S, T>>,
@@ -263,6 +699,26 @@ impl Runtime {
             .insert(AnyResource::Serializable(state))
     }
 
+    /// Assigns the key the resource with the given `id` is serialized under (see
+    /// [SharedContext::next_resource_key]). Resources only have keys while server rendering,
+    /// or while hydrating: otherwise there is no data to match them up with.
+    pub(crate) fn next_resource_key(
+        &self,
+        id: ResourceId,
+        location: &'static std::panic::Location<'static>,
+    ) -> Option<String> {
+        let mut sc = self.shared_context.borrow_mut();
+        let hydrating = sc.as_ref().and_then(|sc| sc.context.as_ref()).is_some();
+        if cfg!(any(feature = "csr", feature = "hydrate")) && !hydrating {
+            return None;
+        }
+        let key = sc
+            .get_or_insert_with(SharedContext::default)
+            .next_resource_key(id, location);
+        self.resource_keys.borrow_mut().insert(id, key.clone());
+        Some(key)
+    }
+
     #[cfg(feature = "hydrate")]
     pub fn start_hydration(&self, element: &web_sys::Element) {
         use wasm_bindgen::{JsCast, UnwrapThrowExt};
@@ -280,16 +736,103 @@ impl Runtime {
             }
         }
 
-        *self.shared_context.borrow_mut() = Some(SharedContext::new_with_registry(registry));
+        // an island's keys are generated relative to the island, rather than the whole page
+        let island = element.get_attribute("data-island");
+
+        *self.shared_context.borrow_mut() =
+            Some(SharedContext::new_with_registry(registry, island));
     }
 
     #[cfg(feature = "hydrate")]
     pub fn end_hydration(&self) {
         if let Some(ref mut sc) = *self.shared_context.borrow_mut() {
+            // any data that wasn't claimed by now was sent for a resource that was never created
+            // in the browser; when hydrating an island, the data for the rest of the page is expected
+            #[cfg(debug_assertions)]
+            {
+                let prefix = sc.island.as_ref().map(|island| format!("{island}:"));
+                let unclaimed = sc
+                    .resolved_resources
+                    .keys()
+                    .chain(sc.pending_resources.iter())
+                    .filter(|key| {
+                        prefix
+                            .as_ref()
+                            .map_or(true, |prefix| key.starts_with(prefix))
+                    });
+                for key in unclaimed {
+                    crate::debug_warn!(
+                        "[hydration] the server sent data for the resource {key:?}, but no \
+                         resource was created at position {:?} while hydrating",
+                        crate::hydration::resource_key_position(key)
+                    );
+                }
+            }
             sc.context = None;
+            sc.island = None;
         }
     }
 
+    /// Returns the future for a cached resource’s key, calling `fetch` to create it if no
+    /// resource has fetched that key yet.
+    pub(crate) fn cached_resource_future<K, T>(
+        &self,
+        key: K,
+        fetch: impl FnOnce() -> PinnedFuture<T>,
+    ) -> Shared<PinnedFuture<T>>
+    where
+        K: Eq + Hash + 'static,
+        T: Clone + 'static,
+    {
+        let cached = self.with_resource_cache::<K, T, _>(|cache| cache.get(&key).cloned());
+        cached.unwrap_or_else(|| {
+            // not borrowing the cache while the fetcher runs
+            let fut = fetch().shared();
+            self.with_resource_cache(|cache| cache.insert(key, fut.clone()));
+            fut
+        })
+    }
+
+    /// Removes a key from the resource cache, so that it is fetched again next time.
+    pub(crate) fn invalidate_cached_resource<K, T>(&self, key: &K)
+    where
+        K: Eq + Hash + 'static,
+        T: Clone + 'static,
+    {
+        self.with_resource_cache::<K, T, _>(|cache| cache.remove(key));
+    }
+
+    fn with_resource_cache<K, T, U>(
+        &self,
+        f: impl FnOnce(&mut HashMap<K, Shared<PinnedFuture<T>>>) -> U,
+    ) -> U
+    where
+        K: Eq + Hash + 'static,
+        T: Clone + 'static,
+    {
+        let mut caches = self.resource_cache.borrow_mut();
+        let cache = caches
+            .entry(TypeId::of::<(K, T)>())
+            .or_insert_with(|| Box::<HashMap<K, Shared<PinnedFuture<T>>>>::default())
+            .downcast_mut()
+            .expect("resource cache should only contain caches of its own type");
+        f(cache)
+    }
+
+    /// Like [resource](Self::resource), but returns `None` if the resource has been disposed.
+    pub(crate) fn try_resource<S, T, U>(
+        &self,
+        id: ResourceId,
+        f: impl FnOnce(&ResourceState<S, T>) -> U,
+    ) -> Option<U>
+    where
+        S: Debug + 'static,
+        T: Debug + 'static,
+    {
+        let exists = self.resources.borrow().contains_key(id);
+        exists.then(|| self.resource(id, f))
+    }
+
     pub(crate) fn resource<S, T, U>(
         &self,
         id: ResourceId,
@@ -312,13 +855,13 @@ impl Runtime {
                 f(n)
             } else {
                 panic!(
-                    "couldn't convert {id:?} to ResourceState<{}, {}>",
+                    "couldn't convert {id} to ResourceState<{}, {}>",
                     std::any::type_name::<S>(),
                     std::any::type_name::<T>(),
                 );
             }
         } else {
-            panic!("couldn't locate {id:?}");
+            panic!("couldn't locate {id}");
         }
     }
 
@@ -331,6 +874,99 @@ impl Runtime {
             .collect()
     }
 
+    /// The resources with a fetch that hasn't resolved yet.
+    pub(crate) fn loading_resources(&self) -> Vec<ResourceId> {
+        self.resources
+            .borrow()
+            .iter()
+            .filter(|(_, resource)| match resource {
+                AnyResource::Unserializable(res) => res.is_loading(),
+                AnyResource::Serializable(res) => res.is_loading(),
+            })
+            .map(|(resource_id, _)| resource_id)
+            .collect()
+    }
+
+    /// The resources with a fetch that hasn't resolved yet, which have been read under `context`.
+    pub(crate) fn resources_pending_under(&self, context: &SuspenseContext) -> Vec<AnyResource> {
+        self.resources
+            .borrow()
+            .values()
+            .filter(|resource| resource.is_pending_under(context))
+            .cloned()
+            .collect()
+    }
+
+    /// The keys of the suspense fragments whose resources haven't all resolved yet.
+    pub(crate) fn pending_fragment_keys(&self) -> Vec<String> {
+        let fragments = self.suspense_fragments.borrow();
+        let mut keys = fragments
+            .iter()
+            .filter(|(_, context)| {
+                let id = context.pending_resources.id;
+                id.try_with_no_subscription(self, |pending: &usize| *pending > 0)
+                    .unwrap_or(false)
+            })
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        keys.sort();
+        keys
+    }
+
+    /// Forgets the suspense fragment registered with `key`, once it has resolved or its scope
+    /// has been disposed, unless it has since been registered again with another context.
+    pub(crate) fn forget_suspense_fragment(&self, key: &str, context: SuspenseContext) {
+        let mut fragments = self.suspense_fragments.borrow_mut();
+        if fragments.get(key) == Some(&context) {
+            fragments.remove(key);
+        }
+    }
+
+    /// Whether no resource is loading, no suspense fragment is pending, and (with a
+    /// `TestRuntime`) no effect is waiting to run, any of which could start more work.
+    pub(crate) fn is_idle(&self) -> bool {
+        #[cfg(any(test, feature = "testing"))]
+        if let Some(queue) = &*self.effect_queue.borrow() {
+            if !queue.is_empty() {
+                return false;
+            }
+        }
+        self.loading_resources().is_empty() && self.pending_fragment_keys().is_empty()
+    }
+
+    pub(crate) fn wake_idle_waiters(&self) {
+        let wakers = std::mem::take(&mut *self.idle_wakers.borrow_mut());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// The number of resources that are pending in all [SuspenseContext](crate::SuspenseContext)s.
+    pub(crate) fn pending_resources(&self, runtime_id: RuntimeId) -> RwSignal<usize> {
+        if let Some(pending) = self.pending_resources.get() {
+            return pending;
+        }
+        let pending = runtime_id.create_rw_signal(0);
+        self.pending_resources.set(Some(pending));
+        pending
+    }
+
+    pub(crate) fn resource_keys(&self) -> HashMap<ResourceId, String> {
+        self.resource_keys
+            .borrow()
+            .iter()
+            .map(|(id, key)| (id, key.clone()))
+            .collect()
+    }
+
+    pub(crate) fn island_resources(&self) -> HashMap<String, Vec<ResourceId>> {
+        self.shared_context
+            .borrow()
+            .as_ref()
+            .map(|sc| sc.island_resources.clone())
+            .unwrap_or_default()
+    }
+
     pub(crate) fn serialization_resolvers(
         &self,
     ) -> FuturesUnordered<PinnedFuture<(ResourceId, String)>> {
@@ -342,6 +978,18 @@ impl Runtime {
         }
         f
     }
+
+    pub(crate) fn serialization_patches(&self) -> SelectAll<PinnedStream<(ResourceId, String)>> {
+        let mut patches = SelectAll::new();
+        for (id, resource) in self.resources.borrow().iter() {
+            if let AnyResource::Serializable(resource) = resource {
+                if let Some(stream) = resource.to_patch_stream(id) {
+                    patches.push(stream);
+                }
+            }
+        }
+        patches
+    }
 }
 
 impl PartialEq for Runtime {
@@ -1,12 +1,17 @@
 use cfg_if::cfg_if;
 
-use crate::runtime::{with_runtime, RuntimeId};
-use crate::{hydration::SharedContext, EffectId, ResourceId, SignalId};
-use crate::{PinnedFuture, SuspenseContext};
-use futures::stream::FuturesUnordered;
-use std::collections::HashMap;
+use crate::runtime::{expect_runtime, with_runtime, Runtime, RuntimeId};
+use crate::{hydration::SharedContext, DisposableId, EffectId, ResourceId, SignalId};
+use crate::{queue_microtask, IdleResult, PinnedFuture, PinnedStream, ReadSignal, SuspenseContext};
+use futures::stream::{FuturesUnordered, SelectAll};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::Debug;
-use std::{future::Future, pin::Pin};
+use std::time::Duration;
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    rc::Rc,
+};
 
 #[doc(hidden)]
 #[must_use = "Scope will leak memory if the disposer function is never called"]
@@ -107,7 +112,7 @@ impl Scope {
     /// has navigated away from the route.)
     pub fn run_child_scope<T>(self, f: impl FnOnce(Scope) -> T) -> (T, ScopeDisposer) {
         let (res, child_id, disposer) = self.runtime.run_scope_undisposed(f, Some(self));
-        with_runtime(self.runtime, |runtime| {
+        expect_runtime(self.runtime, |runtime| {
             let mut children = runtime.scope_children.borrow_mut();
             children
                 .entry(self.id)
@@ -118,6 +123,33 @@ impl Scope {
         (res, disposer)
     }
 
+    /// Runs `f`, which creates a component in this scope, as the renderer does for each
+    /// component in a view, and returns what it returns.
+    ///
+    /// In [strict mode](crate::RuntimeId::set_strict_mode), `f` runs, then everything it added to
+    /// this scope is disposed of, as though the component had been unmounted (its child scopes
+    /// and cleanup functions, and the signals, effects, and resources it created), and then it
+    /// runs again. Components that aren’t idempotent, or don’t clean up after themselves, are
+    /// then easy to spot in development.
+    ///
+    /// The `view` macro only does this for components whose props can be cloned, so that it can
+    /// pass them in twice; `#[component]` props can be cloned when all of the component’s
+    /// arguments can, which is not the case for `children`.
+    ///
+    /// On the server and while hydrating, `f` only ever runs once, as running it again would
+    /// claim a different set of hydration keys.
+    pub fn run_component<T>(self, f: impl Fn() -> T) -> T {
+        if !self.remounts_components() {
+            return f();
+        }
+        let before = with_runtime(self.runtime, |runtime| self.owned(runtime)).ok();
+        drop(f());
+        if let Some(before) = before {
+            self.unmount_since(before);
+        }
+        f()
+    }
+
     /// Suspends reactive tracking while running the given function.
     ///
     /// This can be used to isolate parts of the reactive graph from one another.
@@ -144,12 +176,46 @@ impl Scope {
     /// # });
     /// ```
     pub fn untrack<T>(&self, f: impl FnOnce() -> T) -> T {
-        with_runtime(self.runtime, |runtime| {
-            let prev_observer = runtime.observer.take();
-            let untracked_result = f();
-            runtime.observer.set(prev_observer);
-            untracked_result
-        })
+        // without a runtime, there's nothing to track anyway
+        let prev_observer = with_runtime(self.runtime, |runtime| runtime.observer.take()).ok();
+        let untracked_result = f();
+        if let Some(prev_observer) = prev_observer {
+            _ = with_runtime(self.runtime, |runtime| runtime.observer.set(prev_observer));
+        }
+        untracked_result
+    }
+
+    /// Like [untrack](Scope::untrack), but writes to signals inside `f` don’t notify anything
+    /// either, as though they were all made with `set_untracked`. This is useful for
+    /// initialization code that reads and sets many signals at once.
+    ///
+    /// Nothing that depends on the signals written here reruns until one of its dependencies
+    /// changes again.
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (a, set_a) = create_signal(cx, 1);
+    /// let (b, set_b) = create_signal(cx, 2);
+    /// let sum = create_memo(cx, move |_| a() + b());
+    /// assert_eq!(sum(), 3);
+    ///
+    /// cx.batch_untrack(|| {
+    ///     set_a(a() * 10);
+    ///     set_b(b() * 10);
+    /// });
+    /// assert_eq!((a(), b()), (10, 20));
+    /// // hasn't updated, because it wasn't notified
+    /// assert_eq!(sum(), 3);
+    /// # }).dispose();
+    /// ```
+    pub fn batch_untrack<T>(&self, f: impl FnOnce() -> T) -> T {
+        let prev_silent = with_runtime(self.runtime, |runtime| runtime.silent.replace(true)).ok();
+        let result = self.untrack(f);
+        if let Some(prev_silent) = prev_silent {
+            _ = with_runtime(self.runtime, |runtime| runtime.silent.set(prev_silent));
+        }
+        result
     }
 }
 
@@ -157,7 +223,8 @@ impl Scope {
 
 impl Scope {
     pub(crate) fn dispose(self) {
-        with_runtime(self.runtime, |runtime| {
+        // everything the scope owned went with the runtime, if it has already been disposed
+        _ = with_runtime(self.runtime, |runtime| {
             // dispose of all child scopes
             let children = {
                 let mut children = runtime.scope_children.borrow_mut();
@@ -188,44 +255,212 @@ impl Scope {
             };
             if let Some(owned) = owned {
                 for property in owned {
-                    match property {
-                        ScopeProperty::Signal(id) => {
-                            // remove the signal
-                            runtime.signals.borrow_mut().remove(id);
-                            let subs = runtime.signal_subscribers.borrow_mut().remove(id);
-
-                            // each of the subs needs to remove the signal from its dependencies
-                            // so that it doesn't try to read the (now disposed) signal
-                            if let Some(subs) = subs {
-                                let source_map = runtime.effect_sources.borrow();
-                                for effect in subs.borrow().iter() {
-                                    if let Some(effect_sources) = source_map.get(*effect) {
-                                        effect_sources.borrow_mut().remove(&id);
-                                    }
-                                }
-                            }
-                        }
-                        ScopeProperty::Effect(id) => {
-                            runtime.effects.borrow_mut().remove(id);
-                            runtime.effect_sources.borrow_mut().remove(id);
-                        }
-                        ScopeProperty::Resource(id) => {
-                            runtime.resources.borrow_mut().remove(id);
-                        }
+                    dispose_property(runtime, property);
+                }
+            }
+        });
+    }
+
+    /// Detaches the scope and everything beneath it from the reactive graph, without disposing
+    /// of anything yet: its signals refuse writes and its effects stop running. Returns the
+    /// scopes to dispose of, in the order [Scope::dispose] would dispose of them.
+    fn tombstone(self, runtime: &Runtime) -> VecDeque<ScopeId> {
+        if let Some(parent) = runtime.scope_parents.borrow_mut().remove(self.id) {
+            if let Some(siblings) = runtime.scope_children.borrow_mut().get_mut(parent) {
+                siblings.retain(|id| *id != self.id);
+            }
+        }
+
+        // children before their parents, as each scope disposes of its children first
+        let mut order = VecDeque::new();
+        let mut stack = vec![(self.id, false)];
+        while let Some((id, visited)) = stack.pop() {
+            if visited {
+                order.push_back(id);
+                continue;
+            }
+            stack.push((id, true));
+            if let Some(children) = runtime.scope_children.borrow().get(id) {
+                stack.extend(children.iter().rev().map(|child| (*child, false)));
+            }
+        }
+
+        let scopes = runtime.scopes.borrow();
+        let mut tombstoned_signals = runtime.tombstoned_signals.borrow_mut();
+        let mut effect_states = runtime.effect_states.borrow_mut();
+        for owned in order.iter().filter_map(|id| scopes.get(*id)) {
+            for property in owned.borrow().iter() {
+                match property {
+                    ScopeProperty::Signal(id) => {
+                        tombstoned_signals.insert(*id);
+                    }
+                    // effects without a state are never marked to run again
+                    ScopeProperty::Effect(id) => {
+                        effect_states.remove(*id);
                     }
+                    ScopeProperty::Resource(_) | ScopeProperty::Custom(_) => {}
                 }
             }
-        })
+        }
+        order
     }
 
+    fn remounts_components(self) -> bool {
+        cfg_if! {
+            if #[cfg(feature = "ssr")] {
+                false
+            } else {
+                #[cfg(feature = "hydrate")]
+                if self.is_hydrating() {
+                    return false;
+                }
+                self.runtime.is_strict_mode()
+            }
+        }
+    }
+
+    // the child scopes, cleanup functions, and properties the scope has, so that those added
+    // after this can be told apart by `unmount_since`
+    fn owned(self, runtime: &Runtime) -> OwnedSnapshot {
+        OwnedSnapshot {
+            children: runtime
+                .scope_children
+                .borrow()
+                .get(self.id)
+                .map(|children| children.iter().copied().collect())
+                .unwrap_or_default(),
+            cleanups: self.cleanup_count(runtime),
+            properties: runtime
+                .scopes
+                .borrow()
+                .get(self.id)
+                .map(|owned| owned.borrow().iter().copied().collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    // how many cleanup functions have been registered with `on_cleanup`
+    pub(crate) fn cleanup_count(self, runtime: &Runtime) -> usize {
+        runtime
+            .scope_cleanups
+            .borrow()
+            .get(self.id)
+            .map_or(0, Vec::len)
+    }
+
+    // runs the cleanup functions registered after the first `count`, and forgets them
+    pub(crate) fn run_cleanups_since(self, count: usize) {
+        // cleanups can't be removed, only added, so the new ones are at the end
+        let cleanups = with_runtime(self.runtime, |runtime| {
+            let mut cleanups = runtime.scope_cleanups.borrow_mut();
+            let cleanups = cleanups.get_mut(self.id)?;
+            Some(cleanups.split_off(count.min(cleanups.len())))
+        });
+        for cleanup in cleanups.ok().flatten().unwrap_or_default() {
+            cleanup();
+        }
+    }
+
+    // disposes of everything added to the scope since `before`, in the same order as `dispose`
+    fn unmount_since(self, before: OwnedSnapshot) {
+        let children = with_runtime(self.runtime, |runtime| {
+            let mut children = runtime.scope_children.borrow_mut();
+            let children = children.get_mut(self.id)?;
+            let (kept, added): (Vec<_>, Vec<_>) = children
+                .drain(..)
+                .partition(|child| before.children.contains(child));
+            *children = kept;
+            Some(added)
+        });
+        for id in children.ok().flatten().unwrap_or_default() {
+            Scope {
+                runtime: self.runtime,
+                id,
+            }
+            .dispose();
+        }
+
+        self.run_cleanups_since(before.cleanups);
+
+        _ = with_runtime(self.runtime, |runtime| {
+            let added = {
+                let scopes = runtime.scopes.borrow();
+                let Some(owned) = scopes.get(self.id) else {
+                    return;
+                };
+                let mut owned = owned.borrow_mut();
+                let (kept, added): (Vec<_>, Vec<_>) = owned
+                    .drain(..)
+                    .partition(|property| before.properties.contains(property));
+                *owned = kept;
+                added
+            };
+            for property in added {
+                dispose_property(runtime, property);
+            }
+        });
+    }
+
+    // does nothing if the runtime has been disposed, as whatever was refused along with it
     pub(crate) fn with_scope_property(&self, f: impl FnOnce(&mut Vec<ScopeProperty>)) {
-        with_runtime(self.runtime, |runtime| {
+        _ = with_runtime(self.runtime, |runtime| {
             let scopes = runtime.scopes.borrow();
             let scope = scopes
                 .get(self.id)
                 .expect("tried to add property to a scope that has been disposed");
             f(&mut scope.borrow_mut());
-        })
+        });
+    }
+}
+
+// what a scope owned at some point, as taken by `Scope::owned`
+struct OwnedSnapshot {
+    children: HashSet<ScopeId>,
+    cleanups: usize,
+    properties: HashSet<ScopeProperty>,
+}
+
+// removes a signal, effect, resource, or disposable that a scope owned from the runtime
+fn dispose_property(runtime: &Runtime, property: ScopeProperty) {
+    #[cfg(feature = "trace")]
+    runtime.traces.borrow_mut().remove(&(&property).into());
+    match property {
+        ScopeProperty::Signal(id) => {
+            // remove the signal
+            runtime.signals.borrow_mut().remove(id);
+            runtime.tombstoned_signals.borrow_mut().remove(&id);
+            runtime.memos.borrow_mut().remove(id);
+            let subs = runtime.signal_subscribers.borrow_mut().remove(id);
+
+            // each of the subs needs to remove the signal from its dependencies
+            // so that it doesn't try to read the (now disposed) signal
+            if let Some(subs) = subs {
+                let source_map = runtime.effect_sources.borrow();
+                for effect in subs.borrow().iter() {
+                    if let Some(effect_sources) = source_map.get(*effect) {
+                        effect_sources.borrow_mut().remove(&id);
+                    }
+                }
+            }
+        }
+        ScopeProperty::Effect(id) => {
+            runtime.effects.borrow_mut().remove(id);
+            runtime.effect_sources.borrow_mut().remove(id);
+            runtime.effect_states.borrow_mut().remove(id);
+            runtime.memo_signals.borrow_mut().remove(id);
+            runtime.render_effects.borrow_mut().remove(id);
+            runtime.effects_after.borrow_mut().remove(id);
+        }
+        ScopeProperty::Resource(id) => {
+            runtime.resources.borrow_mut().remove(id);
+            runtime.resource_keys.borrow_mut().remove(id);
+        }
+        ScopeProperty::Custom(id) => {
+            let disposable = runtime.disposables.borrow_mut().remove(id);
+            if let Some(mut disposable) = disposable {
+                disposable.dispose();
+            }
+        }
     }
 }
 
@@ -233,15 +468,25 @@ impl Scope {
 ///
 /// It runs after child scopes have been disposed, but before signals, effects, and resources
 /// are invalidated.
+///
+/// If the runtime has already been disposed (for example, when this is called from an async
+/// task that outlives a server render), the cleanup function is run immediately.
 pub fn on_cleanup(cx: Scope, cleanup_fn: impl FnOnce() + 'static) {
-    with_runtime(cx.runtime, |runtime| {
+    let mut cleanup_fn = Some(cleanup_fn);
+    _ = with_runtime(cx.runtime, |runtime| {
         let mut cleanups = runtime.scope_cleanups.borrow_mut();
         let cleanups = cleanups
             .entry(cx.id)
             .expect("trying to clean up a Scope that has already been disposed")
             .or_insert_with(Default::default);
-        cleanups.push(Box::new(cleanup_fn));
-    })
+        if let Some(cleanup_fn) = cleanup_fn.take() {
+            cleanups.push(Box::new(cleanup_fn));
+        }
+    });
+    // the runtime has already been disposed, so there's nothing left to wait for
+    if let Some(cleanup_fn) = cleanup_fn {
+        cleanup_fn();
+    }
 }
 
 slotmap::new_key_type! {
@@ -249,11 +494,12 @@ slotmap::new_key_type! {
     pub struct ScopeId;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum ScopeProperty {
     Signal(SignalId),
     Effect(EffectId),
     Resource(ResourceId),
+    Custom(DisposableId),
 }
 
 /// Creating a [Scope](crate::Scope) gives you a disposer, which can be called
@@ -262,8 +508,9 @@ pub(crate) enum ScopeProperty {
 /// This will
 /// 1. dispose of all child `Scope`s
 /// 2. run all cleanup functions defined for this scope by [on_cleanup](crate::on_cleanup).
-/// 3. dispose of all signals, effects, and resources owned by this `Scope`.
-pub struct ScopeDisposer(pub(crate) Box<dyn FnOnce()>);
+/// 3. dispose of all signals, effects, resources, and [Disposable](crate::Disposable)s owned
+///    by this `Scope`.
+pub struct ScopeDisposer(pub(crate) Scope);
 
 impl ScopeDisposer {
     /// Disposes of a reactive [Scope](crate::Scope).
@@ -271,9 +518,135 @@ impl ScopeDisposer {
     /// This will
     /// 1. dispose of all child `Scope`s
     /// 2. run all cleanup functions defined for this scope by [on_cleanup](crate::on_cleanup).
-    /// 3. dispose of all signals, effects, and resources owned by this `Scope`.
+    /// 3. dispose of all signals, effects, resources, and [Disposable](crate::Disposable)s owned
+    ///    by this `Scope`.
     pub fn dispose(self) {
-        (self.0)()
+        self.0.dispose()
+    }
+
+    /// Whether the scope has already been disposed of, e.g., along with its parent scope or its
+    /// runtime. Disposing of it again does nothing.
+    ///
+    /// A scope that is being [disposed of a chunk at a time](Self::dispose_deferred) only counts
+    /// as disposed of once its own turn has come.
+    pub fn is_disposed(&self) -> bool {
+        let scope = self.0;
+        with_runtime(scope.runtime, |runtime| {
+            !runtime.scopes.borrow().contains_key(scope.id)
+        })
+        .unwrap_or(true)
+    }
+
+    /// Disposes of a reactive [Scope](crate::Scope) a little at a time, so that disposing of
+    /// a large tree of scopes (like a page with thousands of rows) doesn’t block the main thread.
+    ///
+    /// The scope is detached from the reactive graph immediately: its effects, and those of
+    /// its children, never run again, and writing to their signals does nothing. The scopes
+    /// are then disposed of in the same order as [dispose](Self::dispose), running their cleanup
+    /// functions, in chunks that each take roughly `budget`: in the browser, each chunk runs
+    /// in an idle callback, and in a `TestRuntime`, on its virtual clock. On the server, everything is disposed of straight away.
+    ///
+    /// Use [DeferredDisposal::finish] to dispose of whatever is left immediately.
+    pub fn dispose_deferred(self, budget: Duration) -> DeferredDisposal {
+        let scope = self.0;
+        let queue = with_runtime(scope.runtime, |runtime| scope.tombstone(runtime)).ok();
+        let inner = Rc::new(DeferredDisposalInner {
+            runtime: scope.runtime,
+            queue: RefCell::new(queue.unwrap_or_default()),
+            budget,
+        });
+        Rc::clone(&inner).run();
+        DeferredDisposal { inner }
+    }
+}
+
+/// A [Scope](crate::Scope) that is being disposed of a chunk at a time, as returned by
+/// [ScopeDisposer::dispose_deferred].
+///
+/// Dropping this doesn’t stop the disposal.
+pub struct DeferredDisposal {
+    inner: Rc<DeferredDisposalInner>,
+}
+
+impl DeferredDisposal {
+    /// Whether every scope has been disposed of.
+    pub fn is_finished(&self) -> bool {
+        self.inner.queue.borrow().is_empty()
+    }
+
+    /// Disposes of every scope that is left straight away.
+    pub fn finish(self) {
+        while self.inner.dispose_next().is_some() {}
+    }
+}
+
+struct DeferredDisposalInner {
+    runtime: RuntimeId,
+    queue: RefCell<VecDeque<ScopeId>>,
+    budget: Duration,
+}
+
+impl DeferredDisposalInner {
+    fn dispose_next(&self) -> Option<()> {
+        let id = self.queue.borrow_mut().pop_front()?;
+        // its children have already been disposed of
+        Scope {
+            runtime: self.runtime,
+            id,
+        }
+        .dispose();
+        Some(())
+    }
+
+    /// Disposes of scopes until the budget runs out. Returns `true` if there are any left.
+    fn run_chunk(&self) -> bool {
+        cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                // `Instant` isn't available in the browser
+                let start = js_sys::Date::now();
+                let elapsed = || {
+                    Duration::from_secs_f64((js_sys::Date::now() - start).max(0.0) / 1000.0)
+                };
+            } else {
+                let start = std::time::Instant::now();
+                let elapsed = || start.elapsed();
+            }
+        }
+
+        while self.dispose_next().is_some() {
+            if elapsed() >= self.budget {
+                return !self.queue.borrow().is_empty();
+            }
+        }
+        false
+    }
+
+    fn run(self: Rc<Self>) {
+        #[cfg(any(test, feature = "testing"))]
+        if crate::testing::is_active() {
+            if self.run_chunk() {
+                crate::testing::set_timeout(move || self.run(), Duration::ZERO);
+            }
+            return;
+        }
+
+        cfg_if! {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                use wasm_bindgen::{closure::Closure, JsCast};
+
+                if self.run_chunk() {
+                    let next = Closure::once_into_js(move || self.run());
+                    let window = web_sys::window().expect("no window");
+                    // Safari doesn't support idle callbacks
+                    if window.request_idle_callback(next.unchecked_ref()).is_err() {
+                        _ = window.set_timeout_with_callback(next.unchecked_ref());
+                    }
+                }
+            } else {
+                // there's nothing to yield to on the server
+                while self.run_chunk() {}
+            }
+        }
     }
 }
 
@@ -284,20 +657,21 @@ impl Scope {
             /// `hydrate` only: Whether we're currently hydrating the page.
             pub fn is_hydrating(&self) -> bool {
                 with_runtime(self.runtime, |runtime| {
-                runtime.shared_context.borrow().is_some()
+                    runtime.shared_context.borrow().is_some()
                 })
+                .unwrap_or(false)
             }
 
             /// `hydrate` only: Begins the hydration process.
             pub fn start_hydration(&self, element: &web_sys::Element) {
-                with_runtime(self.runtime, |runtime| {
+                expect_runtime(self.runtime, |runtime| {
                     runtime.start_hydration(element);
                 })
             }
 
             /// `hydrate` only: Ends the hydration process.
             pub fn end_hydration(&self) {
-                with_runtime(self.runtime, |runtime| {
+                expect_runtime(self.runtime, |runtime| {
                     runtime.end_hydration();
                 })
             }
@@ -319,7 +693,7 @@ impl Scope {
                     t
                 };
 
-                with_runtime(self.runtime, |runtime| {
+                expect_runtime(self.runtime, |runtime| {
                     if let Some(ref mut shared_context) = &mut *runtime.shared_context.borrow_mut() {
                         if shared_context.context.is_some() {
                             let key = shared_context.next_hydration_key();
@@ -348,48 +722,111 @@ impl Scope {
 
     /// `hydrate` only: Given the current node, gets the span of the next component that has
     /// been marked for hydration, returning its starting node and the set of all its nodes.
-    #[cfg(any(feature = "csr", feature = "hydrate", doc))]
+    ///
+    /// The span begins with a `<!--leptos-{id}-->` comment and ends with the matching
+    /// `<!--/leptos-{id}-->` comment. Whitespace-only text nodes before the opening marker are
+    /// skipped. If the markers can’t be found (for example, because some intermediary has
+    /// stripped comments from the HTML), this logs a warning and claims only a single node,
+    /// so that just this part of the page will be rendered again on the client. If only the
+    /// closing marker is missing, the server-rendered nodes after the opening marker are
+    /// removed first, up to the closing marker of the enclosing component (or the last sibling).
+    #[cfg(any(feature = "hydrate", doc))]
     pub fn get_next_marker(&self, start: &web_sys::Node) -> (web_sys::Node, Vec<web_sys::Node>) {
-        let mut end = Some(start.clone());
-        let mut count = 0;
-        let mut current = Vec::new();
-        let mut start = start.clone();
+        use crate::hydration::{find_marker_span, HydrationMarker, MarkerNode, MarkerSpan};
 
-        with_runtime(self.runtime, |runtime| {
-            if runtime
+        let is_hydrating = expect_runtime(self.runtime, |runtime| {
+            runtime
                 .shared_context
                 .borrow()
                 .as_ref()
                 .map(|sc| sc.context.as_ref())
                 .is_some()
-            {
-                while let Some(curr) = end {
-                    start = curr.clone();
-                    if curr.node_type() == 8 {
-                        // COMMENT
-                        let v = curr.node_value();
-                        if v == Some("#".to_string()) {
-                            count += 1;
-                        } else if v == Some("/".to_string()) {
-                            count -= 1;
-                            if count == 0 {
-                                current.push(curr.clone());
-                                return (curr, current);
-                            }
-                        }
+        });
+        if !is_hydrating {
+            return (start.clone(), Vec::new());
+        }
+
+        let mut nodes = Vec::new();
+        let siblings = std::iter::successors(Some(start.clone()), |node| node.next_sibling())
+            .map(|node| {
+                let kind = match node.node_type() {
+                    // COMMENT
+                    8 => node
+                        .node_value()
+                        .and_then(|value| HydrationMarker::parse(&value))
+                        .map(MarkerNode::Marker)
+                        .unwrap_or(MarkerNode::Other),
+                    // TEXT
+                    3 if node
+                        .node_value()
+                        .map(|value| value.trim().is_empty())
+                        .unwrap_or(true) =>
+                    {
+                        MarkerNode::Whitespace
+                    }
+                    _ => MarkerNode::Other,
+                };
+                nodes.push(node);
+                kind
+            });
+
+        match find_marker_span(siblings) {
+            MarkerSpan::Found { start, end } => {
+                let end_node = nodes[end].clone();
+                nodes.truncate(end + 1);
+                nodes.drain(..start);
+                (end_node, nodes)
+            }
+            MarkerSpan::Unclosed { start, end } => {
+                crate::debug_warn!(
+                    "[get_next_marker] could not find the closing hydration marker for this \
+                     component; it will be rendered on the client instead."
+                );
+                // remove the server-rendered nodes, so they aren't shown next to the client render
+                for node in nodes.drain(start + 1..end) {
+                    if let Some(parent) = node.parent_node() {
+                        _ = parent.remove_child(&node);
                     }
-                    current.push(curr.clone());
-                    end = curr.next_sibling();
                 }
+                let open = nodes.swap_remove(start);
+                (open.clone(), vec![open])
             }
+            MarkerSpan::NoOpening => {
+                crate::debug_warn!(
+                    "[get_next_marker] could not find the opening hydration marker for this \
+                     component; it will be rendered on the client instead."
+                );
+                let placeholder: web_sys::Node = web_sys::window()
+                    .and_then(|window| window.document())
+                    .map(|document| document.create_comment("").into())
+                    .unwrap_or_else(|| start.clone());
+                if let Some(parent) = start.parent_node() {
+                    _ = parent.insert_before(&placeholder, Some(start));
+                }
+                (placeholder.clone(), vec![placeholder])
+            }
+        }
+    }
 
-            (start, current)
+    /// Generates a unique ID for the comment markers (`<!--leptos-{id}-->` and `<!--/leptos-{id}-->`)
+    /// that surround each component or dynamic child in server-rendered HTML.
+    pub fn next_marker_id(&self) -> usize {
+        expect_runtime(self.runtime, |runtime| {
+            let mut sc = runtime.shared_context.borrow_mut();
+            if let Some(ref mut sc) = *sc {
+                sc.next_marker_id()
+            } else {
+                let mut new_sc = SharedContext::default();
+                let id = new_sc.next_marker_id();
+                *sc = Some(new_sc);
+                id
+            }
         })
     }
 
     /// On either the server side or the browser side, generates the next key in the hydration process.
     pub fn next_hydration_key(&self) -> String {
-        with_runtime(self.runtime, |runtime| {
+        expect_runtime(self.runtime, |runtime| {
             let mut sc = runtime.shared_context.borrow_mut();
             if let Some(ref mut sc) = *sc {
                 sc.next_hydration_key()
@@ -404,7 +841,7 @@ impl Scope {
 
     /// Runs the given function with the next hydration context.
     pub fn with_next_context<T>(&self, f: impl FnOnce() -> T) -> T {
-        with_runtime(self.runtime, |runtime| {
+        expect_runtime(self.runtime, |runtime| {
             if runtime
                 .shared_context
                 .borrow()
@@ -437,30 +874,169 @@ impl Scope {
         })
     }
 
+    /// Runs the given function as the root of an “island”: an independent part of a page that
+    /// can be hydrated on its own, without hydrating the rest of the page.
+    ///
+    /// Hydration keys generated in the island are prefixed with its `id` and start again from
+    /// the beginning, so they match the keys generated when the island’s element (marked with a
+    /// `data-island` attribute containing the same `id`) is passed to
+    /// [start_hydration](Self::start_hydration) in the browser. The serializable resources
+    /// created in the island are listed in [island_resources](Self::island_resources).
+    ///
+    /// In the browser, this only makes a difference while hydrating.
+    pub fn with_island<T>(&self, id: &str, f: impl FnOnce() -> T) -> T {
+        use crate::hydration::HydrationContext;
+
+        let prev = expect_runtime(self.runtime, |runtime| {
+            let mut sc = runtime.shared_context.borrow_mut();
+            let hydrating = sc.as_ref().and_then(|sc| sc.context.as_ref()).is_some();
+            if self.rendering_mode() != RenderingMode::Ssr && !hydrating {
+                return None;
+            }
+            let sc = sc.get_or_insert_with(SharedContext::default);
+            Some((
+                sc.context.replace(HydrationContext::island(id)),
+                sc.island.replace(id.to_string()),
+            ))
+        });
+
+        let res = f();
+
+        if let Some((context, island)) = prev {
+            expect_runtime(self.runtime, |runtime| {
+                if let Some(ref mut sc) = *runtime.shared_context.borrow_mut() {
+                    sc.context = context;
+                    sc.island = island;
+                }
+            });
+        }
+        res
+    }
+
+    /// The IDs of the serializable [Resource](crate::Resource)s created in each island (see
+    /// [with_island](Self::with_island)) while server rendering, in the order they were created.
+    ///
+    /// Their [keys](Self::resource_keys) are prefixed with the island’s `id`, so they can be
+    /// matched up with the serialized data when only that island is hydrated.
+    pub fn island_resources(&self) -> HashMap<String, Vec<ResourceId>> {
+        with_runtime(self.runtime, |runtime| runtime.island_resources()).unwrap_or_default()
+    }
+
+    /// The key each serializable [Resource](crate::Resource) is serialized under while server
+    /// rendering, which is derived from where it was created in the view.
+    ///
+    /// When hydrating, the browser derives the same key for each resource, and uses the data
+    /// that was serialized under it, so the data is matched up with the right resource even if
+    /// the server and the browser don’t create exactly the same resources.
+    pub fn resource_keys(&self) -> HashMap<ResourceId, String> {
+        with_runtime(self.runtime, |runtime| runtime.resource_keys()).unwrap_or_default()
+    }
+
     /// Returns IDs for all [Resource](crate::Resource)s found on any scope.
     pub fn all_resources(&self) -> Vec<ResourceId> {
-        with_runtime(self.runtime, |runtime| runtime.all_resources())
+        with_runtime(self.runtime, |runtime| runtime.all_resources()).unwrap_or_default()
+    }
+
+    /// The number of resources that are currently pending in every [SuspenseContext] in the
+    /// runtime, updated along with each context’s own
+    /// [pending_resources](SuspenseContext::pending_resources).
+    ///
+    /// Unlike [use_global_pending](crate::use_global_pending), this can briefly drop to `0`
+    /// between one resource resolving and the next one starting to load.
+    pub fn pending_resources(&self) -> ReadSignal<usize> {
+        expect_runtime(self.runtime, |runtime| {
+            runtime.pending_resources(self.runtime).read_only()
+        })
+    }
+
+    /// The number of [Resource](crate::Resource)s in the runtime that are currently loading,
+    /// i.e., that have a fetch that hasn’t resolved yet.
+    pub fn pending_resource_count(&self) -> usize {
+        with_runtime(self.runtime, |runtime| runtime.loading_resources().len()).unwrap_or(0)
+    }
+
+    /// Resolves once the runtime is idle: no [Resource](crate::Resource) is loading, and every
+    /// fragment registered with [register_suspense](Self::register_suspense) has resolved.
+    ///
+    /// Work that starts while waiting (for example, a resource that refetches because another
+    /// one resolved) is waited for too, so this only resolves once nothing is left to do. If
+    /// `timeout` passes first, it resolves to [IdleResult::TimedOut], listing what was still
+    /// pending. This is useful for making tests deterministic, and for letting in-flight work
+    /// finish before a server shuts down.
+    ///
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # use std::time::Duration;
+    /// # create_scope(create_runtime(), |cx| {
+    /// # if false {
+    /// let user = create_resource(cx, || (), |_| async { "user 1".to_string() });
+    /// let idle = futures::executor::block_on(cx.wait_for_idle(Some(Duration::from_secs(1))));
+    /// assert_eq!(idle, IdleResult::Idle);
+    /// assert_eq!(user.read(), Some("user 1".to_string()));
+    /// # }
+    /// # }).dispose();
+    /// ```
+    pub fn wait_for_idle(
+        &self,
+        timeout: Option<Duration>,
+    ) -> impl Future<Output = IdleResult> + 'static {
+        crate::idle::wait_for_idle(self.runtime, timeout)
+    }
+
+    /// The rendering mode this app was compiled for, as set by the `csr`, `hydrate`, and `ssr`
+    /// features. Without any of those features, this is [RenderingMode::Ssr].
+    ///
+    /// Unlike [is_hydrating](Self::is_hydrating), this is the same for the app’s whole lifetime,
+    /// so code that is generic over rendering modes can use it to choose what to do.
+    pub fn rendering_mode(&self) -> RenderingMode {
+        cfg_if! {
+            if #[cfg(feature = "hydrate")] {
+                RenderingMode::Hydrate
+            } else if #[cfg(feature = "csr")] {
+                RenderingMode::Csr
+            } else {
+                RenderingMode::Ssr
+            }
+        }
     }
 
     /// The current key for an HTML fragment created by server-rendering a `<Suspense/>` component.
+    ///
+    /// While rendering with hydration keys, this is derived from the current key, so it matches
+    /// between the server and the client. Otherwise (for example, in a client-side-rendered app),
+    /// every call returns a new key, so it can be used as a unique ID in any rendering mode.
     pub fn current_fragment_key(&self) -> String {
-        with_runtime(self.runtime, |runtime| {
+        expect_runtime(self.runtime, |runtime| {
             runtime
                 .shared_context
-                .borrow()
-                .as_ref()
-                .map(|context| context.current_fragment_key())
-                .unwrap_or_else(|| String::from("0f"))
+                .borrow_mut()
+                .get_or_insert_with(SharedContext::default)
+                .current_fragment_key()
         })
     }
 
     /// Returns IDs for all [Resource](crate::Resource)s found on any scope.
     pub fn serialization_resolvers(&self) -> FuturesUnordered<PinnedFuture<(ResourceId, String)>> {
-        with_runtime(self.runtime, |runtime| runtime.serialization_resolvers())
+        expect_runtime(self.runtime, |runtime| runtime.serialization_resolvers())
+    }
+
+    /// Returns a stream of [JSON patches](crate::JsonPatch) for every
+    /// [patchable resource](crate::create_patchable_resource), each sent when the resource’s
+    /// value changes after it was first serialized.
+    ///
+    /// This should be called at the same time as [serialization_resolvers](Self::serialization_resolvers),
+    /// so that the first patch for each resource applies to the value serialized there.
+    pub fn serialization_patches(&self) -> SelectAll<PinnedStream<(ResourceId, String)>> {
+        expect_runtime(self.runtime, |runtime| runtime.serialization_patches())
     }
 
     /// Registers the given [SuspenseContext](crate::SuspenseContext) with the current scope,
     /// calling the `resolver` when its resources are all resolved.
+    ///
+    /// When server rendering, the `resolver` should return the HTML for the fragment with the
+    /// given key, which is streamed once it resolves (see [Self::pending_fragments]). If this
+    /// scope is disposed first, the `resolver` is never called, and the fragment is skipped.
+    /// In the browser, the `resolver` is simply called once, and whatever it returns is ignored.
     pub fn register_suspense(
         &self,
         context: SuspenseContext,
@@ -468,44 +1044,255 @@ impl Scope {
         resolver: impl FnOnce() -> String + 'static,
     ) {
         use crate::create_isomorphic_effect;
+
+        expect_runtime(self.runtime, |runtime| {
+            runtime
+                .suspense_fragments
+                .borrow_mut()
+                .insert(key.to_string(), context)
+        });
+        let runtime_id = self.runtime;
+        let forget = {
+            let key = key.to_string();
+            move || {
+                _ = with_runtime(runtime_id, |runtime| {
+                    runtime.forget_suspense_fragment(&key, context)
+                });
+            }
+        };
+        crate::on_cleanup(*self, forget.clone());
+
+        if self.rendering_mode() != RenderingMode::Ssr {
+            let resolver = Rc::new(Cell::new(Some(resolver)));
+            create_isomorphic_effect(*self, move |_| {
+                if context.ready() {
+                    // resources read just before this was registered are only counted in
+                    // a microtask, so check again once they have been
+                    let resolver = Rc::clone(&resolver);
+                    let forget = forget.clone();
+                    queue_microtask(move || {
+                        if context.ready() {
+                            if let Some(resolver) = resolver.take() {
+                                resolver();
+                                forget();
+                            }
+                        }
+                    });
+                }
+            });
+            return;
+        }
+
         use futures::StreamExt;
 
-        with_runtime(self.runtime, |runtime| {
-            if let Some(ref mut shared_context) = *runtime.shared_context.borrow_mut() {
-                let (tx, mut rx) = futures::channel::mpsc::unbounded();
+        let scope_id = self.id;
+        expect_runtime(self.runtime, |runtime| {
+            let (tx, mut rx) = futures::channel::mpsc::unbounded();
 
-                create_isomorphic_effect(*self, move |_| {
-                    let pending = context.pending_resources.try_with(|n| *n).unwrap_or(0);
-                    if pending == 0 {
-                        _ = tx.unbounded_send(());
+            create_isomorphic_effect(*self, move |_| {
+                let pending = context.pending_resources.try_with(|n| *n).unwrap_or(0);
+                if pending == 0 {
+                    _ = tx.unbounded_send(());
+                }
+            });
+
+            let fragment: PinnedFuture<Option<String>> = Box::pin({
+                let key = key.to_string();
+                async move {
+                    // the channel closes without a message if the scope is disposed first
+                    let ready = rx.next().await.is_some();
+                    let alive = with_runtime(runtime_id, |runtime| {
+                        runtime.scopes.borrow().contains_key(scope_id)
+                    })
+                    .unwrap_or(false);
+                    if ready && alive {
+                        forget();
+                        Some(resolver())
+                    } else {
+                        log::warn!(
+                            "[Scope::register_suspense] skipping fragment {key:?}, because \
+                             its scope was disposed before its resources resolved"
+                        );
+                        None
                     }
-                });
+                }
+            });
 
-                shared_context.pending_fragments.insert(
-                    key.to_string(),
-                    Box::pin(async move {
-                        rx.next().await;
-                        resolver()
-                    }),
-                );
+            let mut shared_context = runtime.shared_context.borrow_mut();
+            let fragments = &mut shared_context
+                .get_or_insert_with(SharedContext::default)
+                .pending_fragments;
+            // a fragment registered again replaces the previous one, but keeps its place
+            match fragments.iter_mut().find(|(pending_key, _)| pending_key == key) {
+                Some((_, pending)) => *pending = fragment,
+                None => fragments.push((key.to_string(), fragment)),
             }
         })
     }
 
-    /// The set of all HTML fragments current pending, by their keys (see [Self::current_fragment_key]).
-    pub fn pending_fragments(&self) -> HashMap<String, Pin<Box<dyn Future<Output = String>>>> {
+    /// All HTML fragments currently pending, with their keys (see [Self::current_fragment_key]),
+    /// in the order they were registered.
+    ///
+    /// Each resolves to [None] if the scope that registered it was disposed before its resources
+    /// resolved, in which case the fragment should be skipped, leaving its fallback in place.
+    pub fn pending_fragments(
+        &self,
+    ) -> Vec<(String, PinnedFuture<Option<String>>)> {
         with_runtime(self.runtime, |runtime| {
             if let Some(ref mut shared_context) = *runtime.shared_context.borrow_mut() {
                 std::mem::take(&mut shared_context.pending_fragments)
             } else {
-                HashMap::new()
+                Vec::new()
             }
         })
+        .unwrap_or_default()
     }
 }
 
+/// The rendering mode an app was compiled for, as returned by [Scope::rendering_mode].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderingMode {
+    /// Rendered entirely in the browser (the `csr` feature).
+    Csr,
+    /// Rendered to HTML on the server (the `ssr` feature).
+    Ssr,
+    /// Rendered on the server, then hydrated in the browser (the `hydrate` feature).
+    Hydrate,
+}
+
 impl Debug for ScopeDisposer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_tuple("ScopeDisposer").finish()
     }
 }
+
+// each chunk of a deferred disposal is timed with `Instant`, which isn't available in the browser
+#[cfg(all(test, not(any(feature = "csr", feature = "hydrate"))))]
+mod tests {
+    use super::*;
+    use crate::{create_memo, create_signal, testing::TestRuntime, ReadSignal, WriteSignal};
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    // a table whose rows each have a signal, an effect reading `source`, and a cleanup
+    fn table(
+        rt: &TestRuntime,
+        source: ReadSignal<i32>,
+        log: &Log,
+    ) -> (ScopeDisposer, Vec<WriteSignal<i32>>) {
+        let mut rows = Vec::new();
+        let disposer = rt.scope().child_scope(|cx| {
+            for i in 0..3 {
+                _ = cx.child_scope(|cx| {
+                    let (_, set_value) = create_signal(cx, i);
+                    rows.push(set_value);
+                    rt.create_effect(cx, move |_| source.get());
+                    let log = Rc::clone(log);
+                    on_cleanup(cx, move || log.borrow_mut().push(format!("row {i}")));
+                });
+            }
+            let log = Rc::clone(log);
+            on_cleanup(cx, move || log.borrow_mut().push("table".to_string()));
+        });
+        rt.flush();
+        (disposer, rows)
+    }
+
+    #[test]
+    fn tombstoned_signals_refuse_writes() {
+        let rt = TestRuntime::new();
+        let (source, set_source) = create_signal(rt.scope(), 0);
+        let log = Log::default();
+        let (disposer, rows) = table(&rt, source, &log);
+        let runs = Rc::new(Cell::new(0));
+        _ = rt.scope().child_scope(|cx| {
+            let runs = Rc::clone(&runs);
+            rt.create_effect(cx, move |_| {
+                source.get();
+                runs.set(runs.get() + 1);
+            });
+        });
+        rt.flush();
+
+        let disposal = disposer.dispose_deferred(Duration::ZERO);
+        assert!(!disposal.is_finished());
+        let updated = rows.last().unwrap().update_returning(|value| *value += 1);
+        assert_eq!(updated, None);
+
+        // only the effect outside the table reruns
+        set_source.set(1);
+        rt.flush();
+        assert_eq!(runs.get(), 2);
+        disposal.finish();
+    }
+
+    #[test]
+    fn deferred_cleanups_run_once_in_order() {
+        let sync_log = Log::default();
+        {
+            let rt = TestRuntime::new();
+            let (source, _) = create_signal(rt.scope(), 0);
+            let (disposer, _) = table(&rt, source, &sync_log);
+            disposer.dispose();
+        }
+
+        let rt = TestRuntime::new();
+        let (source, _) = create_signal(rt.scope(), 0);
+        let log = Log::default();
+        let (disposer, _) = table(&rt, source, &log);
+        let disposal = disposer.dispose_deferred(Duration::ZERO);
+        // one scope at a time
+        assert_eq!(*log.borrow(), ["row 0"]);
+
+        rt.advance(Duration::ZERO);
+        assert!(disposal.is_finished());
+        assert_eq!(*log.borrow(), ["row 0", "row 1", "row 2", "table"]);
+        assert_eq!(*log.borrow(), *sync_log.borrow());
+
+        disposal.finish();
+        rt.advance(Duration::ZERO);
+        assert_eq!(log.borrow().len(), 4);
+    }
+
+    #[test]
+    fn deferred_disposal_matches_synchronous_disposal() {
+        let sync = {
+            let rt = TestRuntime::new();
+            let (source, _) = create_signal(rt.scope(), 0);
+            let (disposer, _) = table(&rt, source, &Log::default());
+            disposer.dispose();
+            rt.diagnostics()
+        };
+
+        let rt = TestRuntime::new();
+        let (source, _) = create_signal(rt.scope(), 0);
+        let (disposer, _) = table(&rt, source, &Log::default());
+        let before = rt.diagnostics();
+        disposer.dispose_deferred(Duration::ZERO).finish();
+        assert_eq!(rt.diagnostics(), sync);
+        assert_ne!(before, sync);
+    }
+
+    #[test]
+    fn disposing_a_memo_forgets_it() {
+        let rt = TestRuntime::new();
+        let (source, _) = create_signal(rt.scope(), 0);
+        let memos = || {
+            with_runtime(rt.scope().runtime, |runtime| {
+                (
+                    runtime.memos.borrow().len(),
+                    runtime.memo_signals.borrow().len(),
+                )
+            })
+            .unwrap()
+        };
+        let disposer = rt.scope().child_scope(|cx| {
+            let doubled = create_memo(cx, move |_| source.get() * 2);
+            assert_eq!(doubled.get(), 0);
+        });
+        assert_eq!(memos(), (1, 1));
+
+        disposer.dispose();
+        assert_eq!(memos(), (0, 0));
+    }
+}
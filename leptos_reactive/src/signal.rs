@@ -1,6 +1,6 @@
 use crate::{
     debug_warn,
-    runtime::{with_runtime, RuntimeId},
+    runtime::{expect_runtime, with_runtime, RuntimeId},
     spawn_local, Runtime, Scope, ScopeProperty, UntrackedGettableSignal, UntrackedSettableSignal,
 };
 use futures::Stream;
@@ -45,6 +45,7 @@ use thiserror::Error;
 /// # }).dispose();
 /// #
 /// ```
+#[track_caller]
 pub fn create_signal<T>(cx: Scope, value: T) -> (ReadSignal<T>, WriteSignal<T>) {
     let s = cx.runtime.create_signal(value);
     cx.with_scope_property(|prop| prop.push(ScopeProperty::Signal(s.0.id)));
@@ -139,6 +140,11 @@ impl<T> ReadSignal<T>
 where
     T: 'static,
 {
+    /// Identifies the signal, for example to [look it up](crate::Scope::lookup_signal).
+    pub fn id(&self) -> SignalId {
+        self.id
+    }
+
     /// Applies a function to the current value of the signal, and subscribes
     /// the running effect to this signal.
     /// ```
@@ -167,7 +173,7 @@ where
 
     #[cfg(feature = "hydrate")]
     pub(crate) fn subscribe(&self) {
-        with_runtime(self.runtime, |runtime| self.id.subscribe(runtime))
+        expect_runtime(self.runtime, |runtime| self.id.subscribe(runtime))
     }
 
     /// Clones and returns the current value of the signal, and subscribes
@@ -191,10 +197,35 @@ where
         self.id.with(self.runtime, T::clone)
     }
 
-    /// Applies the function to the current Signal, if it exists, and subscribes
-    /// the running effect.
-    pub(crate) fn try_with<U>(&self, f: impl FnOnce(&T) -> U) -> Result<U, SignalError> {
-        with_runtime(self.runtime, |runtime| self.id.try_with(runtime, f))
+    /// Applies a function to the current value of the signal, and subscribes the running
+    /// effect to this signal, like [ReadSignal::with], or returns `None` if the signal (or
+    /// the runtime it belongs to) has already been disposed of.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// let runtime = create_runtime();
+    /// let (cx, _) = raw_scope_and_disposer(runtime);
+    /// let (count, _) = create_signal(cx, 1);
+    /// assert_eq!(count.try_with(|n| n * 2), Some(2));
+    ///
+    /// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+    /// runtime.dispose();
+    /// assert_eq!(count.try_with(|n| n * 2), None);
+    /// # }
+    /// ```
+    pub fn try_with<U>(&self, f: impl FnOnce(&T) -> U) -> Option<U> {
+        with_runtime(self.runtime, |runtime| self.id.try_with(runtime, f).ok())
+            .ok()
+            .flatten()
+    }
+
+    /// Clones and returns the current value of the signal, and subscribes the running effect
+    /// to this signal, like [ReadSignal::get], or returns `None` if the signal (or the runtime
+    /// it belongs to) has already been disposed of.
+    pub fn try_get(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.try_with(T::clone)
     }
 
     /// Generates a [Stream] that emits the new value of the signal whenever it changes.
@@ -304,11 +335,13 @@ impl<T> UntrackedSettableSignal<T> for WriteSignal<T>
 where
     T: 'static,
 {
+    #[track_caller]
     fn set_untracked(&self, new_value: T) {
         self.id
             .update_with_no_effect(self.runtime, |v| *v = new_value);
     }
 
+    #[track_caller]
     fn update_untracked(&self, f: impl FnOnce(&mut T)) {
         self.id.update_with_no_effect(self.runtime, f);
     }
@@ -318,6 +351,11 @@ impl<T> WriteSignal<T>
 where
     T: 'static,
 {
+    /// Identifies the signal, for example to [look it up](crate::Scope::lookup_signal).
+    pub fn id(&self) -> SignalId {
+        self.id
+    }
+
     /// Applies a function to the current value to mutate it in place
     /// and notifies subscribers that the signal has changed.
     ///
@@ -338,6 +376,7 @@ where
     /// assert_eq!(count(), 1);
     /// # }).dispose();
     /// ```
+    #[track_caller]
     pub fn update(&self, f: impl FnOnce(&mut T)) {
         self.id.update(self.runtime, f)
     }
@@ -361,9 +400,84 @@ where
     /// assert_eq!(count(), 1);
     /// # }).dispose();
     /// ```
+    #[track_caller]
     pub fn set(&self, new_value: T) {
         self.id.update(self.runtime, |n| *n = new_value)
     }
+
+    /// Applies a function to the current value to mutate it in place, notifies subscribers
+    /// that the signal has changed, and returns whatever the function returns.
+    ///
+    /// Returns `None` without calling the function if the signal has been disposed.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (todos, set_todos) = create_signal(cx, vec!["wake up", "eat breakfast"]);
+    ///
+    /// let done = set_todos.update_returning(|todos| todos.pop());
+    /// assert_eq!(done, Some(Some("eat breakfast")));
+    /// assert_eq!(todos().len(), 1);
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn update_returning<U>(&self, f: impl FnOnce(&mut T) -> U) -> Option<U> {
+        self.id.update_returning(self.runtime, f)
+    }
+
+    /// Applies a function to the current value to mutate it in place, and only notifies
+    /// subscribers if it returns `true`. Returns whether subscribers were notified.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    ///
+    /// // only counts up to 3
+    /// let increment = move || set_count.update_if(|n| {
+    ///   if *n < 3 {
+    ///     *n += 1;
+    ///     true
+    ///   } else {
+    ///     false
+    ///   }
+    /// });
+    /// for _ in 0..3 {
+    ///   assert!(increment());
+    /// }
+    /// assert!(!increment());
+    /// assert_eq!(count(), 3);
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn update_if(&self, f: impl FnOnce(&mut T) -> bool) -> bool {
+        self.id.update_if(self.runtime, f)
+    }
+
+    /// Sets the signal’s value and notifies subscribers, unless it is equal to the current
+    /// value. Returns whether subscribers were notified.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 0);
+    ///
+    /// assert!(set_count.set_if_changed(1));
+    /// // doesn't notify subscribers
+    /// assert!(!set_count.set_if_changed(1));
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn set_if_changed(&self, new_value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.id.update_if(self.runtime, |n| {
+            if *n != new_value {
+                *n = new_value;
+                true
+            } else {
+                false
+            }
+        })
+    }
 }
 
 impl<T> Clone for WriteSignal<T> {
@@ -431,6 +545,7 @@ where
 /// # }).dispose();
 /// #
 /// ```
+#[track_caller]
 pub fn create_rw_signal<T>(cx: Scope, value: T) -> RwSignal<T> {
     let s = cx.runtime.create_rw_signal(value);
     cx.with_scope_property(|prop| prop.push(ScopeProperty::Signal(s.id)));
@@ -495,11 +610,13 @@ impl<T> UntrackedGettableSignal<T> for RwSignal<T> {
 }
 
 impl<T> UntrackedSettableSignal<T> for RwSignal<T> {
+    #[track_caller]
     fn set_untracked(&self, new_value: T) {
         self.id
             .update_with_no_effect(self.runtime, |v| *v = new_value)
     }
 
+    #[track_caller]
     fn update_untracked(&self, f: impl FnOnce(&mut T)) {
         self.id.update_with_no_effect(self.runtime, f)
     }
@@ -509,6 +626,11 @@ impl<T> RwSignal<T>
 where
     T: 'static,
 {
+    /// Identifies the signal, for example to [look it up](crate::Scope::lookup_signal).
+    pub fn id(&self) -> SignalId {
+        self.id
+    }
+
     /// Applies a function to the current value of the signal, and subscribes
     /// the running effect to this signal.
     /// ```
@@ -553,6 +675,25 @@ where
         self.id.with(self.runtime, T::clone)
     }
 
+    /// Applies a function to the current value of the signal, and subscribes the running
+    /// effect to this signal, like [RwSignal::with], or returns `None` if the signal (or
+    /// the runtime it belongs to) has already been disposed of.
+    pub fn try_with<U>(&self, f: impl FnOnce(&T) -> U) -> Option<U> {
+        with_runtime(self.runtime, |runtime| self.id.try_with(runtime, f).ok())
+            .ok()
+            .flatten()
+    }
+
+    /// Clones and returns the current value of the signal, and subscribes the running effect
+    /// to this signal, like [RwSignal::get], or returns `None` if the signal (or the runtime
+    /// it belongs to) has already been disposed of.
+    pub fn try_get(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.try_with(T::clone)
+    }
+
     /// Applies a function to the current value to mutate it in place
     /// and notifies subscribers that the signal has changed.
     /// ```
@@ -570,6 +711,7 @@ where
     /// assert_eq!(count(), 1);
     /// # }).dispose();
     /// ```
+    #[track_caller]
     pub fn update(&self, f: impl FnOnce(&mut T)) {
         self.id.update(self.runtime, f)
     }
@@ -588,10 +730,80 @@ where
     /// assert_eq!(count(), 1);
     /// # }).dispose();
     /// ```
+    #[track_caller]
     pub fn set(&self, value: T) {
         self.id.update(self.runtime, |n| *n = value)
     }
 
+    /// Applies a function to the current value to mutate it in place, notifies subscribers
+    /// that the signal has changed, and returns whatever the function returns.
+    ///
+    /// Returns `None` without calling the function if the signal has been disposed.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let todos = create_rw_signal(cx, vec!["wake up", "eat breakfast"]);
+    ///
+    /// let done = todos.update_returning(|todos| todos.pop());
+    /// assert_eq!(done, Some(Some("eat breakfast")));
+    /// assert_eq!(todos().len(), 1);
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn update_returning<U>(&self, f: impl FnOnce(&mut T) -> U) -> Option<U> {
+        self.id.update_returning(self.runtime, f)
+    }
+
+    /// Applies a function to the current value to mutate it in place, and only notifies
+    /// subscribers if it returns `true`. Returns whether subscribers were notified.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let names = create_rw_signal(cx, vec!["Alice".to_string()]);
+    ///
+    /// let add = move |name: &str| names.update_if(|names| {
+    ///   let is_new = !names.iter().any(|n| n == name);
+    ///   if is_new {
+    ///     names.push(name.to_string());
+    ///   }
+    ///   is_new
+    /// });
+    /// assert!(add("Bob"));
+    /// assert!(!add("Alice"));
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn update_if(&self, f: impl FnOnce(&mut T) -> bool) -> bool {
+        self.id.update_if(self.runtime, f)
+    }
+
+    /// Sets the signal’s value and notifies subscribers, unless it is equal to the current
+    /// value. Returns whether subscribers were notified.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let count = create_rw_signal(cx, 0);
+    ///
+    /// assert!(count.set_if_changed(1));
+    /// // doesn't notify subscribers
+    /// assert!(!count.set_if_changed(1));
+    /// # }).dispose();
+    /// ```
+    #[track_caller]
+    pub fn set_if_changed(&self, value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.id.update_if(self.runtime, |n| {
+            if *n != value {
+                *n = value;
+                true
+            } else {
+                false
+            }
+        })
+    }
+
     /// Returns a read-only handle to the signal.
     ///
     /// Useful if you're trying to give read access to another component but ensure that it can't write
@@ -729,6 +941,12 @@ impl SignalId {
             if let Some(subs) = subs.entry(*self) {
                 subs.or_default().borrow_mut().insert(observer);
             }
+
+            // add to the observer's sources, so it can unsubscribe when it reruns
+            let mut sources = runtime.effect_sources.borrow_mut();
+            if let Some(sources) = sources.entry(observer) {
+                sources.or_default().borrow_mut().insert(*self);
+            }
         }
     }
 
@@ -746,14 +964,14 @@ impl SignalId {
             match signals.get(*self).cloned().ok_or(SignalError::Disposed) {
                 Ok(s) => Ok(s),
                 Err(e) => {
-                    debug_warn!("[Signal::try_with] {e}");
+                    debug_warn!("[Signal::try_with] {self}: {e}");
                     Err(e)
                 }
             }
         }?;
         let value = value.try_borrow().unwrap_or_else(|e| {
             debug_warn!(
-                "Signal::try_with_no_subscription failed on Signal<{}>. It seems you're trying to read the value of a signal within an effect caused by updating the signal.",
+                "Signal::try_with_no_subscription failed on {self} (Signal<{}>). It seems you're trying to read the value of a signal within an effect caused by updating the signal.",
                 std::any::type_name::<T>()
             );
             panic!("{e}");
@@ -785,7 +1003,7 @@ impl SignalId {
     where
         T: 'static,
     {
-        with_runtime(runtime, |runtime| {
+        expect_runtime(runtime, |runtime| {
             self.try_with_no_subscription(runtime, f).unwrap()
         })
     }
@@ -794,14 +1012,24 @@ impl SignalId {
     where
         T: 'static,
     {
-        with_runtime(runtime, |runtime| self.try_with(runtime, f).unwrap())
+        expect_runtime(runtime, |runtime| self.try_with(runtime, f).unwrap())
     }
 
-    fn update_value<T>(&self, runtime: RuntimeId, f: impl FnOnce(&mut T)) -> bool
+    #[track_caller]
+    fn update_value<T, U>(&self, runtime: RuntimeId, f: impl FnOnce(&mut T) -> U) -> Option<U>
     where
         T: 'static,
     {
+        #[cfg(feature = "trace-signals")]
+        let location = std::panic::Location::caller();
         with_runtime(runtime, |runtime| {
+            if runtime.tombstoned_signals.borrow().contains(self) {
+                debug_warn!(
+                    "[Signal::update] You’re trying to update {self} (Signal<{}>), whose scope is being disposed of.",
+                    std::any::type_name::<T>()
+                );
+                return None;
+            }
             let value = {
                 let signals = runtime.signals.borrow();
                 signals.get(*self).cloned()
@@ -809,60 +1037,84 @@ impl SignalId {
             if let Some(value) = value {
                 let mut value = value.borrow_mut();
                 if let Some(value) = value.downcast_mut::<T>() {
-                    f(value);
-                    true
+                    #[cfg(feature = "trace-signals")]
+                    let old = runtime.format_value(*self, value);
+                    let result = f(value);
+                    #[cfg(feature = "trace-signals")]
+                    runtime.trace_write(*self, location, old, value);
+                    Some(result)
                 } else {
                     debug_warn!(
-                        "[Signal::update] failed when downcasting to Signal<{}>",
+                        "[Signal::update] failed when downcasting {self} to Signal<{}>",
                         std::any::type_name::<T>()
                     );
-                    false
+                    None
                 }
             } else {
                 debug_warn!(
-                    "[Signal::update] You’re trying to update a Signal<{}> that has already been disposed of. This is probably either a logic error in a component that creates and disposes of scopes, or a Resource resolving after its scope has been dropped without having been cleaned up.",
+                    "[Signal::update] You’re trying to update {self} (Signal<{}>), which has already been disposed of. This is probably either a logic error in a component that creates and disposes of scopes, or a Resource resolving after its scope has been dropped without having been cleaned up.",
                     std::any::type_name::<T>()
                 );
-                false
+                None
             }
         })
+        .unwrap_or_else(|e| {
+            debug_warn!(
+                "[Signal::update] You’re trying to update {self} (Signal<{}>), but {e}, so the update was dropped.",
+                std::any::type_name::<T>()
+            );
+            None
+        })
     }
 
+    #[track_caller]
     pub(crate) fn update<T>(&self, runtime_id: RuntimeId, f: impl FnOnce(&mut T))
     where
         T: 'static,
     {
-        with_runtime(runtime_id, |runtime| {
-            // update the value
-            let updated = self.update_value(runtime_id, f);
-
-            // notify subscribers
-            if updated {
-                let subs = {
-                    let subs = runtime.signal_subscribers.borrow();
-                    let subs = subs.get(*self);
-                    subs.map(|subs| subs.borrow().clone())
-                };
-                if let Some(subs) = subs {
-                    for sub in subs {
-                        let effect = {
-                            let effects = runtime.effects.borrow();
-                            effects.get(sub).cloned()
-                        };
-                        if let Some(effect) = effect {
-                            effect.run(sub, runtime_id);
-                        }
-                    }
-                }
-            }
-        })
+        _ = self.update_returning(runtime_id, f);
+    }
+
+    /// Updates the value and notifies subscribers, returning whatever `f` returns, or `None`
+    /// if the signal couldn't be updated.
+    #[track_caller]
+    pub(crate) fn update_returning<T, U>(
+        &self,
+        runtime_id: RuntimeId,
+        f: impl FnOnce(&mut T) -> U,
+    ) -> Option<U>
+    where
+        T: 'static,
+    {
+        // update the value
+        let value = self.update_value(runtime_id, f);
+
+        // notify subscribers
+        if value.is_some() {
+            _ = with_runtime(runtime_id, |runtime| runtime.notify(runtime_id, *self));
+        }
+        value
+    }
+
+    /// Updates the value, but only notifies subscribers if `f` returns `true`.
+    #[track_caller]
+    pub(crate) fn update_if<T>(&self, runtime_id: RuntimeId, f: impl FnOnce(&mut T) -> bool) -> bool
+    where
+        T: 'static,
+    {
+        let changed = self.update_value(runtime_id, f).unwrap_or(false);
+        if changed {
+            _ = with_runtime(runtime_id, |runtime| runtime.notify(runtime_id, *self));
+        }
+        changed
     }
 
+    #[track_caller]
     pub(crate) fn update_with_no_effect<T>(&self, runtime: RuntimeId, f: impl FnOnce(&mut T))
     where
         T: 'static,
     {
         // update the value
-        self.update_value(runtime, f);
+        _ = self.update_value(runtime, f);
     }
 }
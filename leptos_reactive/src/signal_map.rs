@@ -0,0 +1,254 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::Hash,
+    rc::Rc,
+};
+
+use crate::{create_rw_signal, on_cleanup, RwSignal, Scope, ScopeDisposer, Signal};
+
+/// Creates a reactive map in which each key can be tracked separately.
+///
+/// A signal that holds a whole [HashMap] notifies every subscriber whenever any entry
+/// changes. A [SignalMap] instead lazily creates a signal for each key that is read
+/// reactively, so that changing one entry only notifies the subscribers that read that key.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let scores = create_signal_map::<&str, u32>(cx);
+/// scores.insert("alice", 0);
+/// scores.insert("bob", 0);
+///
+/// let alice = scores.get_signal(&"alice");
+/// let alice_runs = Rc::new(Cell::new(0));
+/// create_isomorphic_effect(cx, {
+///   let alice = alice.clone();
+///   let alice_runs = Rc::clone(&alice_runs);
+///   move |_| {
+///     alice.get();
+///     alice_runs.set(alice_runs.get() + 1);
+///   }
+/// });
+///
+/// // writing to another key doesn't rerun the effect
+/// scores.update(&"bob", |score| *score += 1);
+/// assert_eq!(alice_runs.get(), 1);
+///
+/// scores.update(&"alice", |score| *score += 1);
+/// assert_eq!(alice.get(), Some(1));
+/// assert_eq!(alice_runs.get(), 2);
+/// # }).dispose();
+/// ```
+pub fn create_signal_map<K, V>(cx: Scope) -> SignalMap<K, V>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: 'static,
+{
+    let inner = Rc::new(SignalMapState {
+        cx,
+        values: Default::default(),
+        signals: Default::default(),
+        keys: create_rw_signal(cx, ()),
+        disposed: Cell::new(false),
+    });
+
+    on_cleanup(cx, {
+        let inner = Rc::clone(&inner);
+        move || {
+            // the per-key signals belong to child scopes, which are disposed along with `cx`
+            inner.disposed.set(true);
+            inner.values.borrow_mut().clear();
+            inner.signals.borrow_mut().clear();
+        }
+    });
+
+    SignalMap { inner }
+}
+
+/// A reactive map with a separate signal for each key, created by [create_signal_map].
+///
+/// Reading a key with [SignalMap::get_signal] only subscribes to changes to that key, while
+/// [SignalMap::keys] only notifies its subscribers when keys are inserted or removed.
+pub struct SignalMap<K, V>
+where
+    K: 'static,
+    V: 'static,
+{
+    inner: Rc<SignalMapState<K, V>>,
+}
+
+struct SignalMapState<K, V>
+where
+    K: 'static,
+    V: 'static,
+{
+    cx: Scope,
+    values: RefCell<HashMap<K, V>>,
+    // each key's signal is created in its own child scope, so it can be disposed on removal
+    signals: RefCell<HashMap<K, (RwSignal<()>, ScopeDisposer)>>,
+    keys: RwSignal<()>,
+    disposed: Cell<bool>,
+}
+
+impl<K, V> Clone for SignalMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<K, V> Debug for SignalMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.inner.values.borrow().iter())
+            .finish()
+    }
+}
+
+impl<K, V> SignalMap<K, V>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: 'static,
+{
+    /// Inserts a value, notifying subscribers to that key, and to [SignalMap::keys] if the
+    /// key is new. Returns the previous value, if any.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let prev = self.inner.values.borrow_mut().insert(key.clone(), value);
+        self.inner.notify(&key);
+        if prev.is_none() {
+            self.inner.keys.update(|_| ());
+        }
+        prev
+    }
+
+    /// Removes a key, notifying subscribers to that key and to [SignalMap::keys], and disposes
+    /// of the key’s signal. Returns the removed value, if any.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let prev = self.inner.values.borrow_mut().remove(key);
+        let signal = self.inner.signals.borrow_mut().remove(key);
+        if let Some((signal, disposer)) = signal {
+            signal.update(|_| ());
+            disposer.dispose();
+        }
+        if prev.is_some() {
+            self.inner.keys.update(|_| ());
+        }
+        prev
+    }
+
+    /// Applies a function to the value for a key to mutate it in place, and notifies
+    /// subscribers to that key. Returns `false` without calling the function if the key
+    /// isn’t in the map.
+    pub fn update(&self, key: &K, f: impl FnOnce(&mut V)) -> bool {
+        let updated = match self.inner.values.borrow_mut().get_mut(key) {
+            Some(value) => {
+                f(value);
+                true
+            }
+            None => false,
+        };
+        if updated {
+            self.inner.notify(key);
+        }
+        updated
+    }
+
+    /// Returns a signal for the value of a single key, which is [None] while the key
+    /// isn’t in the map. Reading it only subscribes to changes to that key.
+    pub fn get_signal(&self, key: &K) -> Signal<Option<V>>
+    where
+        V: Clone,
+    {
+        let inner = Rc::clone(&self.inner);
+        let key = key.clone();
+        Signal::derive(self.inner.cx, move || {
+            inner.track(&key);
+            inner.values.borrow().get(&key).cloned()
+        })
+    }
+
+    /// Returns a signal for the keys in the map, in arbitrary order, which only notifies its
+    /// subscribers when keys are inserted or removed.
+    pub fn keys(&self) -> Signal<Vec<K>> {
+        let inner = Rc::clone(&self.inner);
+        Signal::derive(self.inner.cx, move || {
+            if !inner.disposed.get() {
+                inner.keys.with(|_| ());
+            }
+            inner.values.borrow().keys().cloned().collect()
+        })
+    }
+
+    /// Inserts every entry, then notifies the subscribers to each key that was written once,
+    /// and to [SignalMap::keys] once if any key is new.
+    pub fn extend(&self, entries: impl IntoIterator<Item = (K, V)>) {
+        let mut written = HashSet::new();
+        let mut inserted = false;
+        {
+            let mut values = self.inner.values.borrow_mut();
+            for (key, value) in entries {
+                inserted |= values.insert(key.clone(), value).is_none();
+                written.insert(key);
+            }
+        }
+        for key in &written {
+            self.inner.notify(key);
+        }
+        if inserted {
+            self.inner.keys.update(|_| ());
+        }
+    }
+
+    /// Removes every key, notifying the subscribers to each key, and to [SignalMap::keys] once,
+    /// and disposes of their signals.
+    pub fn clear(&self) {
+        let removed = std::mem::take(&mut *self.inner.values.borrow_mut());
+        let signals = std::mem::take(&mut *self.inner.signals.borrow_mut());
+        for (signal, _) in signals.values() {
+            signal.update(|_| ());
+        }
+        if !removed.is_empty() {
+            self.inner.keys.update(|_| ());
+        }
+        for (_, (_, disposer)) in signals {
+            disposer.dispose();
+        }
+    }
+}
+
+impl<K, V> SignalMapState<K, V>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: 'static,
+{
+    // subscribes to the key, creating its signal if nothing has read it yet
+    fn track(&self, key: &K) {
+        if self.disposed.get() {
+            return;
+        }
+        let existing = self.signals.borrow().get(key).map(|(signal, _)| *signal);
+        let signal = existing.unwrap_or_else(|| {
+            let (signal, disposer) = self.cx.run_child_scope(|cx| create_rw_signal(cx, ()));
+            self.signals
+                .borrow_mut()
+                .insert(key.clone(), (signal, disposer));
+            signal
+        });
+        signal.with(|_| ());
+    }
+
+    fn notify(&self, key: &K) {
+        let signal = self.signals.borrow().get(key).map(|(signal, _)| *signal);
+        if let Some(signal) = signal {
+            signal.update(|_| ());
+        }
+    }
+}
@@ -0,0 +1,451 @@
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    ops::RangeBounds,
+    rc::{Rc, Weak},
+};
+
+use crate::{create_rw_signal, on_cleanup, RwSignal, Scope, Signal};
+
+/// Creates a reactive `Vec` that records *what* changed, as well as that something did.
+///
+/// A signal that holds a whole [Vec] only tells its subscribers that the list changed, so a
+/// keyed list has to diff the old and new lists to find out which rows to create, move, or
+/// remove. Each change to a [SignalVec] also records a [VecDelta], so a list renderer can
+/// [subscribe](SignalVec::subscribe) to them and apply exactly the rows that changed.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let todos = create_signal_vec::<&str>(cx);
+/// todos.push("write docs");
+///
+/// // a new subscriber is first told about the items that are already there
+/// let deltas = todos.subscribe();
+/// todos.push("write tests");
+/// todos.remove(0);
+///
+/// let mut received = Vec::new();
+/// deltas.for_each_delta(|delta| received.push(delta));
+/// assert_eq!(
+///     received,
+///     vec![
+///         VecDelta::Insert { index: 0, value: "write docs" },
+///         VecDelta::Insert { index: 1, value: "write tests" },
+///         VecDelta::Remove { index: 0 },
+///     ]
+/// );
+///
+/// // consumers that want the whole list can still have it
+/// assert_eq!(todos.as_signal().get(), vec!["write tests"]);
+/// # }).dispose();
+/// ```
+pub fn create_signal_vec<T>(cx: Scope) -> SignalVec<T>
+where
+    T: Clone + 'static,
+{
+    let inner = Rc::new(SignalVecState {
+        cx,
+        values: Default::default(),
+        receivers: Default::default(),
+        version: create_rw_signal(cx, ()),
+        disposed: Cell::new(false),
+        replay: |values| {
+            values
+                .iter()
+                .cloned()
+                .enumerate()
+                .map(|(index, value)| VecDelta::Insert { index, value })
+                .collect()
+        },
+    });
+
+    on_cleanup(cx, {
+        let inner = Rc::clone(&inner);
+        move || {
+            inner.disposed.set(true);
+            inner.values.borrow_mut().clear();
+            inner.receivers.borrow_mut().clear();
+        }
+    });
+
+    SignalVec { inner }
+}
+
+/// A single change to a [SignalVec], as passed to [VecDeltas::for_each_delta]. Applying the
+/// deltas in order to a copy of the list (or to the rows rendered from it) keeps it in sync.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VecDelta<T> {
+    /// `value` was inserted at `index`, shifting everything after it to the right.
+    Insert {
+        /// Where the value was inserted.
+        index: usize,
+        /// The inserted value.
+        value: T,
+    },
+    /// The value at `index` was removed, shifting everything after it to the left.
+    Remove {
+        /// Where the value was removed from.
+        index: usize,
+    },
+    /// The value at `index` was replaced with `value`.
+    Set {
+        /// Which value was replaced.
+        index: usize,
+        /// The new value.
+        value: T,
+    },
+    /// The values at `a` and `b` swapped places.
+    Swap {
+        /// One of the swapped indices.
+        a: usize,
+        /// The other swapped index.
+        b: usize,
+    },
+    /// Every value was removed.
+    Clear,
+}
+
+/// A reactive `Vec` that records a [VecDelta] for each change, created by [create_signal_vec].
+///
+/// Each mutation notifies subscribers once, however many deltas it records. Reading the list
+/// with [SignalVec::with], [SignalVec::as_signal], or (on nightly) by calling it subscribes to
+/// every change, like reading a signal that holds a [Vec].
+pub struct SignalVec<T>
+where
+    T: 'static,
+{
+    inner: Rc<SignalVecState<T>>,
+}
+
+// the deltas a subscriber hasn't seen yet
+type DeltaQueue<T> = RefCell<Vec<VecDelta<T>>>;
+
+struct SignalVecState<T>
+where
+    T: 'static,
+{
+    cx: Scope,
+    values: RefCell<Vec<T>>,
+    receivers: RefCell<Vec<Weak<DeltaQueue<T>>>>,
+    version: RwSignal<()>,
+    disposed: Cell<bool>,
+    // an `Insert` for each value, for a new subscriber; kept here, where `T: Clone` is known,
+    // so that subscribing doesn't need that bound
+    replay: fn(&[T]) -> Vec<VecDelta<T>>,
+}
+
+impl<T> Clone for SignalVec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> Debug for SignalVec<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.inner.values.borrow().iter())
+            .finish()
+    }
+}
+
+impl<T> PartialEq for SignalVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T> Eq for SignalVec<T> {}
+
+impl<T> SignalVec<T>
+where
+    T: Clone + 'static,
+{
+    /// Appends a value to the end of the list.
+    pub fn push(&self, value: T) {
+        let index = self.inner.values.borrow().len();
+        self.insert(index, value);
+    }
+
+    /// Inserts a value at `index`, shifting everything after it to the right.
+    ///
+    /// # Panics
+    /// Panics if `index > len`.
+    pub fn insert(&self, index: usize, value: T) {
+        self.inner.values.borrow_mut().insert(index, value.clone());
+        self.inner.record(VecDelta::Insert { index, value });
+        self.inner.notify();
+    }
+
+    /// Removes and returns the value at `index`, shifting everything after it to the left.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&self, index: usize) -> T {
+        let value = self.inner.values.borrow_mut().remove(index);
+        self.inner.record(VecDelta::Remove { index });
+        self.inner.notify();
+        value
+    }
+
+    /// Swaps the values at `a` and `b`.
+    ///
+    /// # Panics
+    /// Panics if `a` or `b` are out of bounds.
+    pub fn swap(&self, a: usize, b: usize) {
+        self.inner.values.borrow_mut().swap(a, b);
+        if a != b {
+            self.inner.record(VecDelta::Swap { a, b });
+            self.inner.notify();
+        }
+    }
+
+    /// Replaces the value at `index`, and returns the old one.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn set(&self, index: usize, value: T) -> T {
+        let prev = std::mem::replace(&mut self.inner.values.borrow_mut()[index], value.clone());
+        self.inner.record(VecDelta::Set { index, value });
+        self.inner.notify();
+        prev
+    }
+
+    /// Replaces the values in `range` with the values from `replace_with`, like
+    /// [Vec::splice], and returns the removed values. Subscribers are notified once.
+    ///
+    /// # Panics
+    /// Panics if the range is out of bounds.
+    pub fn splice(
+        &self,
+        range: impl RangeBounds<usize>,
+        replace_with: impl IntoIterator<Item = T>,
+    ) -> Vec<T> {
+        let inserted = replace_with.into_iter().collect::<Vec<_>>();
+        let (start, removed) = {
+            let mut values = self.inner.values.borrow_mut();
+            let start = match range.start_bound() {
+                std::ops::Bound::Included(start) => *start,
+                std::ops::Bound::Excluded(start) => start + 1,
+                std::ops::Bound::Unbounded => 0,
+            };
+            let removed = values
+                .splice(range, inserted.iter().cloned())
+                .collect::<Vec<_>>();
+            (start, removed)
+        };
+        for _ in &removed {
+            self.inner.record(VecDelta::Remove { index: start });
+        }
+        for (offset, value) in inserted.into_iter().enumerate() {
+            self.inner.record(VecDelta::Insert {
+                index: start + offset,
+                value,
+            });
+        }
+        self.inner.notify();
+        removed
+    }
+
+    /// Removes every value.
+    pub fn clear(&self) {
+        self.inner.values.borrow_mut().clear();
+        self.inner.record(VecDelta::Clear);
+        self.inner.notify();
+    }
+
+    /// Applies a function to the current values, and subscribes the running effect to every
+    /// change to the list.
+    pub fn with<U>(&self, f: impl FnOnce(&[T]) -> U) -> U {
+        self.inner.track();
+        f(&self.inner.values.borrow())
+    }
+
+    /// The number of values in the list, which subscribes the running effect to every change
+    /// to the list.
+    pub fn len(&self) -> usize {
+        self.with(|values| values.len())
+    }
+
+    /// Whether the list is empty, which subscribes the running effect to every change to the
+    /// list.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a signal holding a copy of the whole list, for consumers that don’t use deltas.
+    /// The list is only cloned when the signal is read.
+    pub fn as_signal(&self) -> Signal<Vec<T>> {
+        let inner = Rc::clone(&self.inner);
+        Signal::derive(self.inner.cx, move || {
+            inner.track();
+            inner.values.borrow().clone()
+        })
+    }
+
+    /// Calls `f` with each change to the list, starting with an [VecDelta::Insert] for each
+    /// value that is already in it, from an effect that runs whenever the list changes. See
+    /// [SignalVec::subscribe].
+    pub fn for_each_delta(&self, cx: Scope, f: impl FnMut(VecDelta<T>) + 'static) {
+        let deltas = self.subscribe();
+        let f = RefCell::new(f);
+        crate::create_isomorphic_effect(cx, move |_| deltas.for_each_delta(&mut *f.borrow_mut()));
+    }
+}
+
+impl<T> SignalVec<T>
+where
+    T: 'static,
+{
+    /// Starts recording the changes to the list for a new subscriber, beginning with an
+    /// [VecDelta::Insert] for each value that is already in it.
+    ///
+    /// Deltas that pile up between two calls to [VecDeltas::for_each_delta] are coalesced
+    /// where possible: for example, a value that is inserted and then removed again is never
+    /// passed on, and a [VecDelta::Clear] drops every delta before it.
+    pub fn subscribe(&self) -> VecDeltas<T> {
+        let initial = (self.inner.replay)(&self.inner.values.borrow());
+        let queue = Rc::new(RefCell::new(initial));
+        if !self.inner.disposed.get() {
+            self.inner
+                .receivers
+                .borrow_mut()
+                .push(Rc::downgrade(&queue));
+        }
+        VecDeltas {
+            inner: Rc::clone(&self.inner),
+            queue,
+        }
+    }
+}
+
+impl<T> SignalVecState<T>
+where
+    T: Clone + 'static,
+{
+    // adds the delta to each subscriber's queue, dropping those that have gone away
+    fn record(&self, delta: VecDelta<T>) {
+        self.receivers
+            .borrow_mut()
+            .retain(|queue| match queue.upgrade() {
+                Some(queue) => {
+                    coalesce(&mut queue.borrow_mut(), delta.clone());
+                    true
+                }
+                None => false,
+            });
+    }
+}
+
+impl<T> SignalVecState<T>
+where
+    T: 'static,
+{
+    fn track(&self) {
+        if !self.disposed.get() {
+            self.version.with(|_| ());
+        }
+    }
+
+    fn notify(&self) {
+        if !self.disposed.get() {
+            self.version.update(|_| ());
+        }
+    }
+}
+
+// appends a delta, merging it with the last one if that's what applying both would do anyway
+fn coalesce<T>(queue: &mut Vec<VecDelta<T>>, delta: VecDelta<T>) {
+    match (queue.last_mut(), delta) {
+        (_, VecDelta::Clear) => {
+            queue.clear();
+            queue.push(VecDelta::Clear);
+        }
+        (
+            Some(VecDelta::Insert { index, value } | VecDelta::Set { index, value }),
+            VecDelta::Set {
+                index: new_index,
+                value: new_value,
+            },
+        ) if *index == new_index => *value = new_value,
+        (Some(VecDelta::Insert { index, .. }), VecDelta::Remove { index: removed })
+            if *index == removed =>
+        {
+            queue.pop();
+        }
+        (_, delta) => queue.push(delta),
+    }
+}
+
+/// The changes to a [SignalVec] that one subscriber hasn’t seen yet, created by
+/// [SignalVec::subscribe].
+pub struct VecDeltas<T>
+where
+    T: 'static,
+{
+    inner: Rc<SignalVecState<T>>,
+    queue: Rc<DeltaQueue<T>>,
+}
+
+impl<T> Debug for VecDeltas<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VecDeltas")
+            .field("pending", &self.queue.borrow())
+            .finish()
+    }
+}
+
+impl<T> VecDeltas<T>
+where
+    T: 'static,
+{
+    /// Calls `f` with each change since the last call, in order, and subscribes the running
+    /// effect (or memo) to further changes.
+    pub fn for_each_delta(&self, mut f: impl FnMut(VecDelta<T>)) {
+        self.inner.track();
+        let pending = std::mem::take(&mut *self.queue.borrow_mut());
+        for delta in pending {
+            f(delta);
+        }
+    }
+}
+
+#[cfg(not(feature = "stable"))]
+impl<T> FnOnce<()> for SignalVec<T>
+where
+    T: Clone,
+{
+    type Output = Vec<T>;
+
+    extern "rust-call" fn call_once(self, _args: ()) -> Self::Output {
+        self.with(|values| values.to_vec())
+    }
+}
+
+#[cfg(not(feature = "stable"))]
+impl<T> FnMut<()> for SignalVec<T>
+where
+    T: Clone,
+{
+    extern "rust-call" fn call_mut(&mut self, _args: ()) -> Self::Output {
+        self.with(|values| values.to_vec())
+    }
+}
+
+#[cfg(not(feature = "stable"))]
+impl<T> Fn<()> for SignalVec<T>
+where
+    T: Clone,
+{
+    extern "rust-call" fn call(&self, _args: ()) -> Self::Output {
+        self.with(|values| values.to_vec())
+    }
+}
@@ -1,6 +1,6 @@
 use std::rc::Rc;
 
-use crate::{Memo, ReadSignal, RwSignal, Scope, UntrackedGettableSignal};
+use crate::{runtime::untrack_all, Memo, ReadSignal, RwSignal, Scope, UntrackedGettableSignal};
 
 /// A wrapper for any kind of readable reactive signal: a [ReadSignal](crate::ReadSignal),
 /// [Memo](crate::Memo), [RwSignal](crate::RwSignal), or derived signal closure.
@@ -28,11 +28,18 @@ use crate::{Memo, ReadSignal, RwSignal, Scope, UntrackedGettableSignal};
 /// assert_eq!(above_3(&memoized_double_count.into()), true);
 /// # });
 /// ```
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Signal<T>(SignalTypes<T>)
 where
     T: 'static;
 
+// not derived, so that signals can be cloned even if their values can't be
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
 /// Please note that using `Signal::with_untracked` still clones the inner value,
 /// so there's no benefit to using it as opposed to calling
 /// `Signal::get_untracked`.
@@ -47,7 +54,7 @@ where
         match &self.0 {
             SignalTypes::ReadSignal(s) => s.get_untracked(),
             SignalTypes::Memo(m) => m.get_untracked(),
-            SignalTypes::DerivedSignal(cx, f) => cx.untrack(|| f()),
+            SignalTypes::DerivedSignal(cx, f) => untrack(*cx, || f()),
         }
     }
 
@@ -55,13 +62,7 @@ where
         match &self.0 {
             SignalTypes::ReadSignal(s) => s.with_untracked(f),
             SignalTypes::Memo(s) => s.with_untracked(f),
-            SignalTypes::DerivedSignal(cx, v_f) => {
-                let mut o = None;
-
-                cx.untrack(|| o = Some(f(&v_f())));
-
-                o.unwrap()
-            }
+            SignalTypes::DerivedSignal(cx, v_f) => untrack(*cx, || f(&v_f())),
         }
     }
 }
@@ -72,6 +73,11 @@ where
 {
     /// Wraps a derived signal, i.e., any computation that accesses one or more
     /// reactive signals.
+    ///
+    /// Unlike a [Memo](crate::Memo), this doesn’t create anything in the reactive graph, so it
+    /// is cheap to create, but it isn’t memoized: the closure runs again every time the signal
+    /// is read, and subscribes whatever is reading it (like an effect or a memo) to the signals
+    /// it reads that time.
     /// ```rust
     /// # use leptos_reactive::*;
     /// # create_scope(create_runtime(), |cx| {
@@ -88,7 +94,10 @@ where
     /// # });
     /// ```
     pub fn derive(cx: Scope, derived_signal: impl Fn() -> T + 'static) -> Self {
-        Self(SignalTypes::DerivedSignal(cx, Rc::new(derived_signal)))
+        Self(SignalTypes::DerivedSignal(
+            Some(cx),
+            Rc::new(derived_signal),
+        ))
     }
 
     /// Applies a function to the current value of the signal, and subscribes
@@ -180,14 +189,46 @@ impl<T> From<Memo<T>> for Signal<T> {
     }
 }
 
-#[derive(Clone)]
+/// Wraps a closure as a derived signal, like [Signal::derive]. This lets APIs that take an
+/// `impl Into<Signal<T>>` accept closures as well as signals and memos.
+///
+/// This is only available with the `stable` feature: otherwise signals and memos can be called
+/// like closures themselves, so they would match this too. Use [Signal::derive] instead.
+#[cfg(feature = "stable")]
+impl<T, F> From<F> for Signal<T>
+where
+    F: Fn() -> T + 'static,
+{
+    fn from(value: F) -> Self {
+        Self(SignalTypes::DerivedSignal(None, Rc::new(value)))
+    }
+}
+
 enum SignalTypes<T>
 where
     T: 'static,
 {
     ReadSignal(ReadSignal<T>),
     Memo(Memo<T>),
-    DerivedSignal(Scope, Rc<dyn Fn() -> T>),
+    // the scope is only used to read the signal without tracking it
+    DerivedSignal(Option<Scope>, Rc<dyn Fn() -> T>),
+}
+
+impl<T> Clone for SignalTypes<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::ReadSignal(s) => Self::ReadSignal(*s),
+            Self::Memo(m) => Self::Memo(*m),
+            Self::DerivedSignal(cx, f) => Self::DerivedSignal(*cx, Rc::clone(f)),
+        }
+    }
+}
+
+fn untrack<T>(cx: Option<Scope>, f: impl FnOnce() -> T) -> T {
+    match cx {
+        Some(cx) => cx.untrack(f),
+        None => untrack_all(f),
+    }
 }
 
 impl<T> std::fmt::Debug for SignalTypes<T>
@@ -211,7 +252,7 @@ where
         match (self, other) {
             (Self::ReadSignal(l0), Self::ReadSignal(r0)) => l0 == r0,
             (Self::Memo(l0), Self::Memo(r0)) => l0 == r0,
-            (Self::DerivedSignal(_, l0), Self::DerivedSignal(_, r0)) => std::ptr::eq(l0, r0),
+            (Self::DerivedSignal(_, l0), Self::DerivedSignal(_, r0)) => Rc::ptr_eq(l0, r0),
             _ => false,
         }
     }
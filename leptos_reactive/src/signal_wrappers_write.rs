@@ -29,11 +29,18 @@ use crate::{RwSignal, Scope, WriteSignal};
 /// assert_eq!(count(), 8);
 /// # });
 /// ```
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct SignalSetter<T>(SignalSetterTypes<T>)
 where
     T: 'static;
 
+// not derived, so that setters can be cloned even if their values can't be
+impl<T> Clone for SignalSetter<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
 impl<T> SignalSetter<T>
 where
     T: 'static,
@@ -63,6 +70,35 @@ where
         Self(SignalSetterTypes::Mapped(cx, Rc::new(mapped_setter)))
     }
 
+    /// Creates a setter for values of another type, which converts each value with `f`
+    /// before passing it to this setter. (This is sometimes called a “contramap.”)
+    ///
+    /// This lets a component accept one kind of setter while it works with another internally.
+    /// ```rust
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (pending, set_pending) = create_signal(cx, false);
+    /// let set_pending = SignalSetter::from(set_pending);
+    ///
+    /// // this function only knows how to set a status message
+    /// fn finish_loading(set_status: &SignalSetter<String>) {
+    ///   set_status.set("done".to_string());
+    /// }
+    ///
+    /// let set_status = set_pending.with_transform(cx, |status: String| status != "done");
+    /// set_pending.set(true);
+    /// finish_loading(&set_status);
+    /// assert_eq!(pending(), false);
+    /// # });
+    /// ```
+    pub fn with_transform<U>(&self, cx: Scope, f: impl Fn(U) -> T + 'static) -> SignalSetter<U>
+    where
+        U: 'static,
+    {
+        let setter = self.clone();
+        SignalSetter::map(cx, move |value| setter.set(f(value)))
+    }
+
     /// Calls the setter function with the given value.
     ///
     /// ```rust
@@ -89,6 +125,33 @@ where
             SignalSetterTypes::Mapped(_, s) => s(value),
         }
     }
+
+    /// Sets the value, unless it is equal to the signal’s current value, and returns whether
+    /// the value was set. See [WriteSignal::set_if_changed].
+    ///
+    /// A [mapped](Self::map) setter can’t compare values, so it is always called.
+    /// ```rust
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 2);
+    /// let set_count = SignalSetter::from(set_count);
+    ///
+    /// assert!(set_count.set_if_changed(3));
+    /// assert!(!set_count.set_if_changed(3));
+    /// # });
+    /// ```
+    pub fn set_if_changed(&self, value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        match &self.0 {
+            SignalSetterTypes::Write(s) => s.set_if_changed(value),
+            SignalSetterTypes::Mapped(_, s) => {
+                s(value);
+                true
+            }
+        }
+    }
 }
 
 impl<T> From<WriteSignal<T>> for SignalSetter<T> {
@@ -103,7 +166,6 @@ impl<T> From<RwSignal<T>> for SignalSetter<T> {
     }
 }
 
-#[derive(Clone)]
 enum SignalSetterTypes<T>
 where
     T: 'static,
@@ -112,6 +174,15 @@ where
     Mapped(Scope, Rc<dyn Fn(T)>),
 }
 
+impl<T> Clone for SignalSetterTypes<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Write(s) => Self::Write(*s),
+            Self::Mapped(cx, s) => Self::Mapped(*cx, Rc::clone(s)),
+        }
+    }
+}
+
 impl<T> std::fmt::Debug for SignalSetterTypes<T>
 where
     T: std::fmt::Debug,
@@ -0,0 +1,247 @@
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+use crate::{
+    debug_warn, runtime::with_runtime, ReadSignal, RuntimeId, RwSignal, Scope, SignalId,
+    WriteSignal,
+};
+
+/// Creates an empty [SnapshotGroup], which captures the values of the signals added to it
+/// so that they can all be restored later, e.g., for an undo feature or between the steps of
+/// a test.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (name, set_name) = create_signal(cx, "Ada".to_string());
+/// let (age, set_age) = create_signal(cx, 36);
+///
+/// let history = create_snapshot_group(cx);
+/// history.track(name);
+/// history.track(age);
+///
+/// let before = history.snapshot();
+/// set_name.set("Grace".to_string());
+/// set_age.set(85);
+/// assert_eq!(history.diff(&before, &history.snapshot()), vec![name.id(), age.id()]);
+///
+/// // undo
+/// history.restore(&before);
+/// assert_eq!(name.get(), "Ada");
+/// assert_eq!(age.get(), 36);
+/// # }).dispose();
+/// ```
+pub fn create_snapshot_group(cx: Scope) -> SnapshotGroup {
+    SnapshotGroup {
+        runtime: cx.runtime,
+        tracked: Default::default(),
+    }
+}
+
+/// A set of signals whose values can be captured together as a [Snapshot], and restored
+/// together later. Created with [create_snapshot_group].
+#[derive(Clone)]
+pub struct SnapshotGroup {
+    runtime: RuntimeId,
+    tracked: Rc<RefCell<Vec<Tracked>>>,
+}
+
+// a signal in a group, with what it takes to copy its value in and out without knowing its type
+#[allow(clippy::type_complexity)]
+struct Tracked {
+    id: SignalId,
+    clone: Box<dyn Fn(&dyn Any) -> Option<Rc<dyn Any>>>,
+    write: Box<dyn Fn(&mut dyn Any, &dyn Any) -> bool>,
+    eq: Box<dyn Fn(&dyn Any, &dyn Any) -> bool>,
+}
+
+/// The values of the signals in a [SnapshotGroup] at one point in time, as returned by
+/// [SnapshotGroup::snapshot].
+#[derive(Clone)]
+pub struct Snapshot {
+    values: Vec<(SignalId, Rc<dyn Any>)>,
+}
+
+impl Snapshot {
+    fn get(&self, id: SignalId) -> Option<&dyn Any> {
+        self.values
+            .iter()
+            .find(|(signal, _)| *signal == id)
+            .map(|(_, value)| value.as_ref())
+    }
+}
+
+impl std::fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Snapshot")
+            .field(
+                "signals",
+                &self.values.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for SnapshotGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapshotGroup")
+            .field(
+                "signals",
+                &self
+                    .tracked
+                    .borrow()
+                    .iter()
+                    .map(|t| t.id)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl SnapshotGroup {
+    /// Adds a signal to the group, so that its value is captured by every later
+    /// [snapshot](Self::snapshot). Snapshots compare its values with [PartialEq] in
+    /// [diff](Self::diff).
+    pub fn track<T>(&self, signal: impl TrackedSignal<T>)
+    where
+        T: Clone + PartialEq + 'static,
+    {
+        self.track_with(signal, T::eq)
+    }
+
+    /// Adds a signal to the group, like [track](Self::track), comparing its values with `eq`,
+    /// e.g., for types that don’t implement [PartialEq].
+    pub fn track_with<T>(
+        &self,
+        signal: impl TrackedSignal<T>,
+        eq: impl Fn(&T, &T) -> bool + 'static,
+    ) where
+        T: Clone + 'static,
+    {
+        let id = signal.tracked_id();
+        let mut tracked = self.tracked.borrow_mut();
+        if tracked.iter().any(|t| t.id == id) {
+            return;
+        }
+        tracked.push(Tracked {
+            id,
+            clone: Box::new(|value| {
+                value
+                    .downcast_ref::<T>()
+                    .map(|value| Rc::new(value.clone()) as Rc<dyn Any>)
+            }),
+            write: Box::new(|value, snapshot| {
+                match (value.downcast_mut::<T>(), snapshot.downcast_ref::<T>()) {
+                    (Some(value), Some(snapshot)) => {
+                        *value = snapshot.clone();
+                        true
+                    }
+                    _ => false,
+                }
+            }),
+            eq: Box::new(
+                move |a, b| match (a.downcast_ref::<T>(), b.downcast_ref::<T>()) {
+                    (Some(a), Some(b)) => eq(a, b),
+                    _ => false,
+                },
+            ),
+        });
+    }
+
+    /// Captures the current values of every signal in the group that still exists, without
+    /// subscribing to them. The snapshot is empty if the runtime has been disposed of.
+    pub fn snapshot(&self) -> Snapshot {
+        let tracked = self.tracked.borrow();
+        let values = with_runtime(self.runtime, |runtime| {
+            let signals = runtime.signals.borrow();
+            tracked
+                .iter()
+                .filter_map(|t| {
+                    let value = signals.get(t.id)?;
+                    let value = (t.clone)(&*value.borrow())?;
+                    Some((t.id, value))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+        Snapshot { values }
+    }
+
+    /// Writes the values in `snapshot` back to their signals, and then notifies everything that
+    /// depends on them at once, so each effect runs (at most) once, seeing all of the values.
+    ///
+    /// Signals that have been disposed of since the snapshot was taken are skipped, with a
+    /// warning, as are signals that were added to the group afterwards.
+    pub fn restore(&self, snapshot: &Snapshot) {
+        let restored = with_runtime(self.runtime, |runtime| {
+            let mut restored = Vec::new();
+            for t in self.tracked.borrow().iter() {
+                let value = match snapshot.get(t.id) {
+                    Some(value) => value,
+                    None => continue,
+                };
+                let signal = {
+                    let signals = runtime.signals.borrow();
+                    signals.get(t.id).cloned()
+                };
+                let disposing = runtime.tombstoned_signals.borrow().contains(&t.id);
+                match signal {
+                    Some(signal) if !disposing => {
+                        if (t.write)(&mut *signal.borrow_mut(), value) {
+                            restored.push(t.id);
+                        }
+                    }
+                    _ => debug_warn!(
+                        "[SnapshotGroup::restore] {} has been disposed of since the snapshot was \
+                         taken, so it was skipped.",
+                        t.id
+                    ),
+                }
+            }
+            // after the group has been released, as the effects may add signals to it
+            runtime.notify_all(self.runtime, &restored);
+        });
+        if let Err(e) = restored {
+            debug_warn!("[SnapshotGroup::restore] The snapshot wasn’t restored, because {e}.");
+        }
+    }
+
+    /// Lists the signals in the group whose values differ between two snapshots, in the order
+    /// they were added to the group. A signal that is only in one of them counts as different.
+    pub fn diff(&self, a: &Snapshot, b: &Snapshot) -> Vec<SignalId> {
+        self.tracked
+            .borrow()
+            .iter()
+            .filter(|t| match (a.get(t.id), b.get(t.id)) {
+                (Some(a), Some(b)) => !(t.eq)(a, b),
+                (None, None) => false,
+                _ => true,
+            })
+            .map(|t| t.id)
+            .collect()
+    }
+}
+
+/// A signal that can be added to a [SnapshotGroup]: a [ReadSignal], [WriteSignal], or
+/// [RwSignal].
+pub trait TrackedSignal<T> {
+    /// The signal that holds the value.
+    fn tracked_id(&self) -> SignalId;
+}
+
+impl<T> TrackedSignal<T> for ReadSignal<T> {
+    fn tracked_id(&self) -> SignalId {
+        self.id
+    }
+}
+
+impl<T> TrackedSignal<T> for WriteSignal<T> {
+    fn tracked_id(&self) -> SignalId {
+        self.id
+    }
+}
+
+impl<T> TrackedSignal<T> for RwSignal<T> {
+    fn tracked_id(&self) -> SignalId {
+        self.id
+    }
+}
@@ -29,10 +29,19 @@ cfg_if! {
 /// Spawns and runs a thread-local [std::future::Future] in a platform-independent way.
 ///
 /// This can be used to interface with any `async` code.
+///
+/// With the `testing` feature, if a `TestRuntime` is running on this thread, the `Future`
+/// is run on the test’s own executor instead, whenever it is flushed.
 pub fn spawn_local<F>(fut: F)
 where
     F: Future<Output = ()> + 'static,
 {
+    #[cfg(any(test, feature = "testing"))]
+    let fut = match crate::testing::spawn(fut) {
+        Ok(()) => return,
+        Err(fut) => fut,
+    };
+
     cfg_if::cfg_if! {
         if #[cfg(any(feature = "csr", feature = "hydrate"))] {
             wasm_bindgen_futures::spawn_local(fut)
@@ -0,0 +1,303 @@
+use std::{cell::RefCell, fmt::Debug, future::Future, rc::Rc};
+
+use futures::{
+    future::{abortable, AbortHandle},
+    Stream, StreamExt,
+};
+
+use crate::{
+    create_effect, create_memo, create_resource, create_signal, on_cleanup,
+    runtime::expect_runtime, spawn_local, Resource, ResourceState, Scope, Serializable, Signal,
+    UntrackedGettableSignal,
+};
+
+/// Creates a [Resource](crate::Resource) whose value is loaded as an initial snapshot, and then
+/// kept up to date by a stream of live updates, like server-sent events or WebSocket messages.
+///
+/// The snapshot is loaded by the `fetcher` exactly like [create_resource]: it can be read
+/// under `<Suspense/>`, and when server-side rendering is used, it is loaded on the server and
+/// serialized to the client. Once the snapshot has resolved in the browser, the `stream` is
+/// created from the same source and each item it yields is applied to the value in place by the
+/// `reducer`. The stream is never opened on the server.
+///
+/// When the `source` changes, the current stream is dropped and the whole cycle starts over: a
+/// new snapshot is fetched, and a new stream is opened once it has loaded. The stream is also
+/// dropped when the [Scope] is disposed.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use futures::Stream;
+/// # create_scope(create_runtime(), |cx| {
+/// #[derive(Clone, Debug)]
+/// enum Message {
+///   Posted(String),
+///   Cleared,
+/// }
+///
+/// async fn load_messages(room: String) -> Vec<String> {
+///   // pretend we're loading the room's history
+///   vec![format!("welcome to {room}")]
+/// }
+///
+/// fn subscribe(room: String) -> impl Stream<Item = Result<Message, ()>> {
+///   // pretend we're subscribing to the room's event stream
+///   futures::stream::iter([Ok(Message::Posted("hello".to_string()))])
+/// }
+///
+/// let (room, set_room) = create_signal(cx, "general".to_string());
+///
+/// # if false {
+/// let messages = create_streaming_resource(
+///   cx,
+///   room,
+///   load_messages,
+///   subscribe,
+///   |messages, message| match message {
+///     Message::Posted(text) => messages.push(text),
+///     Message::Cleared => messages.clear(),
+///   },
+/// );
+///
+/// // the value holds the snapshot, plus every update received since it loaded
+/// let count = move || messages.resource.with(|messages| messages.len());
+/// let live = move || messages.connection_state.get() == StreamState::Open;
+/// # }
+/// # }).dispose();
+/// ```
+pub fn create_streaming_resource<S, T, Fu, St, Item, E>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    stream: impl Fn(S) -> St + 'static,
+    reducer: impl Fn(&mut T, Item) + 'static,
+) -> StreamingResource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Debug + Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+    St: Stream<Item = Result<Item, E>> + 'static,
+    Item: 'static,
+    E: Debug + 'static,
+{
+    let source = create_memo(cx, move |_| source());
+    let resource = create_resource(cx, move || source.get(), fetcher);
+    let (state, set_state) = create_signal(cx, StreamState::Connecting);
+
+    let (loading, value, set_value) = expect_runtime(cx.runtime, |runtime| {
+        runtime.resource(resource.id, |r: &ResourceState<S, T>| {
+            (r.loading, r.value, r.set_value)
+        })
+    });
+    let reducer = Rc::new(reducer);
+    let current = Rc::new(RefCell::new(None::<AbortHandle>));
+
+    create_effect(cx, {
+        let current = Rc::clone(&current);
+        move |_| {
+            // a new snapshot is loading, or has just loaded: either way, the old stream is stale
+            if let Some(handle) = current.borrow_mut().take() {
+                handle.abort();
+            }
+
+            let loaded = value.with_untracked(Option::is_some);
+            if loading.get() || !loaded {
+                set_state.set(StreamState::Connecting);
+                return;
+            }
+
+            let mut items = Box::pin(stream(source.get_untracked()));
+            let reducer = Rc::clone(&reducer);
+            let (task, handle) = abortable(async move {
+                while let Some(item) = items.next().await {
+                    match item {
+                        Ok(item) => set_value.update(|value| {
+                            if let Some(value) = value {
+                                reducer(value, item);
+                            }
+                        }),
+                        Err(e) => {
+                            crate::debug_warn!("[create_streaming_resource] {e:?}");
+                            set_state.set(StreamState::Errored);
+                            return;
+                        }
+                    }
+                }
+                set_state.set(StreamState::Closed);
+            });
+
+            set_state.set(StreamState::Open);
+            *current.borrow_mut() = Some(handle);
+            spawn_local(async move {
+                _ = task.await;
+            });
+        }
+    });
+
+    on_cleanup(cx, move || {
+        if let Some(handle) = current.take() {
+            handle.abort();
+        }
+    });
+
+    StreamingResource {
+        resource,
+        connection_state: state.into(),
+    }
+}
+
+/// A [Resource](crate::Resource) kept up to date by a stream of live updates, as created by
+/// [create_streaming_resource].
+#[derive(Debug, Clone)]
+pub struct StreamingResource<S, T>
+where
+    S: Debug + 'static,
+    T: Debug + 'static,
+{
+    /// The value, which holds the snapshot with every update received since it loaded applied.
+    pub resource: Resource<S, T>,
+    /// The state of the update stream.
+    pub connection_state: Signal<StreamState>,
+}
+
+/// The state of the update stream of a [StreamingResource].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamState {
+    /// The snapshot is loading, so the stream hasn’t been opened yet. This is always the
+    /// state on the server, where the stream is never opened.
+    Connecting,
+    /// Updates from the stream are being applied to the value.
+    Open,
+    /// The stream has ended.
+    Closed,
+    /// The stream yielded an error, and has been dropped.
+    Errored,
+}
+
+#[cfg(all(test, not(any(feature = "csr", feature = "hydrate", feature = "ssr"))))]
+mod tests {
+    use super::*;
+    use crate::testing::{sleep, TestRuntime};
+    use futures::channel::mpsc::{unbounded, UnboundedSender};
+    use std::time::Duration;
+
+    type Senders = Rc<RefCell<Vec<UnboundedSender<Result<String, ()>>>>>;
+
+    fn messages(
+        cx: Scope,
+        room: crate::ReadSignal<u32>,
+        senders: Senders,
+    ) -> StreamingResource<u32, Vec<String>> {
+        create_streaming_resource(
+            cx,
+            move || room.get(),
+            |room| async move {
+                sleep(Duration::from_millis(10)).await;
+                vec![format!("room {room}")]
+            },
+            move |_| {
+                let (tx, rx) = unbounded();
+                senders.borrow_mut().push(tx);
+                rx
+            },
+            |messages: &mut Vec<String>, message| messages.push(message),
+        )
+    }
+
+    #[test]
+    fn snapshot_then_updates() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let senders = Senders::default();
+        let (room, _) = create_signal(cx, 1);
+        let feed = messages(cx, room, Rc::clone(&senders));
+
+        rt.flush();
+        assert_eq!(feed.resource.read(), None);
+        assert_eq!(feed.connection_state.get(), StreamState::Connecting);
+        assert!(senders.borrow().is_empty());
+
+        rt.advance(Duration::from_millis(10));
+        assert_eq!(feed.resource.read(), Some(vec!["room 1".to_string()]));
+        assert_eq!(feed.connection_state.get(), StreamState::Open);
+
+        for message in ["a", "b", "c"] {
+            senders.borrow()[0]
+                .unbounded_send(Ok(message.to_string()))
+                .unwrap();
+        }
+        rt.flush();
+        assert_eq!(
+            feed.resource.read(),
+            Some(vec!["room 1".into(), "a".into(), "b".into(), "c".into()])
+        );
+
+        senders.borrow()[0].close_channel();
+        rt.flush();
+        assert_eq!(feed.connection_state.get(), StreamState::Closed);
+    }
+
+    #[test]
+    fn stream_errors() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let senders = Senders::default();
+        let (room, _) = create_signal(cx, 1);
+        let feed = messages(cx, room, Rc::clone(&senders));
+
+        rt.advance(Duration::from_millis(10));
+        senders.borrow()[0].unbounded_send(Err(())).unwrap();
+        rt.flush();
+        assert_eq!(feed.connection_state.get(), StreamState::Errored);
+        assert!(senders.borrow()[0].is_closed());
+    }
+
+    #[test]
+    fn source_change_restarts_snapshot_and_stream() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let senders = Senders::default();
+        let (room, set_room) = create_signal(cx, 1);
+        let feed = messages(cx, room, Rc::clone(&senders));
+
+        rt.advance(Duration::from_millis(10));
+        senders.borrow()[0].unbounded_send(Ok("a".into())).unwrap();
+        rt.flush();
+
+        set_room.set(2);
+        rt.flush();
+        assert_eq!(feed.connection_state.get(), StreamState::Connecting);
+        assert!(senders.borrow()[0].is_closed());
+
+        rt.advance(Duration::from_millis(10));
+        assert_eq!(feed.resource.read(), Some(vec!["room 2".to_string()]));
+        assert_eq!(feed.connection_state.get(), StreamState::Open);
+        assert_eq!(senders.borrow().len(), 2);
+
+        senders.borrow()[1].unbounded_send(Ok("b".into())).unwrap();
+        rt.flush();
+        assert_eq!(
+            feed.resource.read(),
+            Some(vec!["room 2".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn disposal_closes_stream() {
+        let rt = TestRuntime::new();
+        let senders = Senders::default();
+        let disposer = rt.scope().child_scope({
+            let senders = Rc::clone(&senders);
+            move |cx| {
+                let (room, _) = create_signal(cx, 1);
+                messages(cx, room, senders);
+            }
+        });
+
+        rt.advance(Duration::from_millis(10));
+        assert!(!senders.borrow()[0].is_closed());
+
+        disposer.dispose();
+        rt.flush();
+        assert!(senders.borrow()[0].is_closed());
+    }
+}
@@ -1,4 +1,13 @@
-use crate::{create_signal, spawn::queue_microtask, ReadSignal, Scope, WriteSignal};
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use crate::{
+    create_isomorphic_effect, create_signal,
+    idle::sleep,
+    on_cleanup,
+    runtime::{expect_runtime, with_runtime},
+    spawn::queue_microtask,
+    spawn_local, ReadSignal, Scope, Signal, UntrackedGettableSignal, WriteSignal,
+};
 
 /// Tracks [Resource](crate::Resource)s that are read under a suspense context,
 /// i.e., within a [`Suspense`](https://docs.rs/leptos_core/latest/leptos_core/fn.Suspense.html) component.
@@ -27,6 +36,17 @@ impl SuspenseContext {
     /// Creates an empty suspense context.
     pub fn new(cx: Scope) -> Self {
         let (pending_resources, set_pending_resources) = create_signal(cx, 0);
+
+        // resources that are still pending when the context is disposed will never be
+        // subtracted from the runtime's count by `decrement`
+        on_cleanup(cx, move || {
+            let remaining = pending_resources.get_untracked();
+            if remaining > 0 {
+                expect_runtime(cx.runtime, |runtime| runtime.pending_resources(cx.runtime))
+                    .update(|n| *n = n.saturating_sub(remaining));
+            }
+        });
+
         Self {
             pending_resources,
             set_pending_resources,
@@ -35,19 +55,38 @@ impl SuspenseContext {
 
     /// Notifies the suspense context that a new resource is now pending.
     pub fn increment(&self) {
-        let setter = self.set_pending_resources;
-        queue_microtask(move || setter.update(|n| *n += 1));
+        self.update_pending(|n| n + 1);
     }
 
     /// Notifies the suspense context that a resource has resolved.
     pub fn decrement(&self) {
-        let setter = self.set_pending_resources;
+        self.update_pending(|n| n.saturating_sub(1));
+    }
+
+    // updates this context's count and the runtime's count together
+    fn update_pending(&self, f: impl FnOnce(usize) -> usize + 'static) {
+        let Self {
+            pending_resources,
+            set_pending_resources,
+        } = *self;
         queue_microtask(move || {
-            setter.update(|n| {
-                if *n > 0 {
-                    *n -= 1
-                }
-            })
+            let runtime = pending_resources.runtime;
+            let disposed = expect_runtime(runtime, |runtime| {
+                !runtime.signals.borrow().contains_key(pending_resources.id)
+            });
+            // the context's resources were already subtracted when it was disposed
+            if disposed {
+                return;
+            }
+            let total = expect_runtime(runtime, |rt| rt.pending_resources(runtime));
+
+            let prev = pending_resources.get_untracked();
+            let next = f(prev);
+            set_pending_resources.update(|n| *n = next);
+            if next != prev {
+                total.update(|n| *n = (*n + next).saturating_sub(prev));
+            }
+            expect_runtime(runtime, |runtime| runtime.wake_idle_waiters());
         });
     }
 
@@ -57,4 +96,250 @@ impl SuspenseContext {
             .try_with(|n| *n == 0)
             .unwrap_or(false)
     }
+
+    /// Returns a signal that becomes `true` once this context has had pending resources for
+    /// `after` without a break, and `false` again as soon as they have all resolved, e.g., to
+    /// swap a lightweight loading indicator for a “this is taking longer than expected” message.
+    ///
+    /// The timer only starts when the context becomes pending after this is called, and is
+    /// dropped when `cx` is disposed.
+    pub fn is_slow(&self, cx: Scope, after: Duration) -> ReadSignal<bool> {
+        let (slow, set_slow) = create_signal(cx, false);
+        let pending_resources = self.pending_resources;
+        // bumped whenever the context stops being pending, so that earlier timers are ignored
+        let generation = Rc::new(Cell::new(0_usize));
+        let disposed = Rc::new(Cell::new(false));
+        on_cleanup(cx, {
+            let disposed = Rc::clone(&disposed);
+            move || disposed.set(true)
+        });
+
+        create_isomorphic_effect(cx, move |was_pending: Option<bool>| {
+            let pending = pending_resources.try_with(|n| *n > 0).unwrap_or(false);
+            if pending && was_pending != Some(true) {
+                let started = generation.get();
+                let generation = Rc::clone(&generation);
+                let disposed = Rc::clone(&disposed);
+                spawn_local(async move {
+                    sleep(after).await;
+                    if !disposed.get() && generation.get() == started {
+                        set_slow.set(true);
+                    }
+                });
+            } else if !pending && was_pending == Some(true) {
+                generation.set(generation.get() + 1);
+                if slow.get_untracked() {
+                    set_slow.set(false);
+                }
+            }
+            pending
+        });
+
+        slow
+    }
+
+    /// Calls [refetch](crate::Resource::refetch) on every [Resource](crate::Resource) that has
+    /// been read under this context and is still loading, e.g., for a “retry” button shown once
+    /// loading [is slow](SuspenseContext::is_slow). Resources that have already resolved aren’t
+    /// fetched again.
+    pub fn refetch_pending(&self) {
+        let runtime = self.pending_resources.runtime;
+        let pending = with_runtime(runtime, |runtime| runtime.resources_pending_under(self))
+            .unwrap_or_default();
+        for resource in pending {
+            resource.refetch();
+        }
+    }
+}
+
+/// Returns a signal that is `true` whenever any [SuspenseContext] in the runtime has pending
+/// resources, e.g., to show a progress bar at the top of the page whenever anything is loading,
+/// without passing a `set_pending` setter to every `<Transition/>`.
+///
+/// The signal only becomes `false` once there are still no pending resources after a
+/// microtask, so it stays `true` when one resource resolving causes another to start loading.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let loading = use_global_pending(cx);
+/// let progress_bar_class = move || if loading.get() { "progress visible" } else { "progress" };
+/// # assert_eq!(progress_bar_class(), "progress");
+/// # }).dispose();
+/// ```
+pub fn use_global_pending(cx: Scope) -> Signal<bool> {
+    let total = cx.pending_resources();
+    let (pending, set_pending) = create_signal(cx, total.get_untracked() > 0);
+
+    let disposed = Rc::new(Cell::new(false));
+    on_cleanup(cx, {
+        let disposed = Rc::clone(&disposed);
+        move || disposed.set(true)
+    });
+
+    create_isomorphic_effect(cx, move |_| {
+        if total.get() > 0 {
+            if !pending.get_untracked() {
+                set_pending.set(true);
+            }
+        } else if pending.get_untracked() {
+            // `queue_microtask` would run this right away outside the browser, before any other
+            // effects that are about to start loading resources have had a chance to run
+            let disposed = Rc::clone(&disposed);
+            spawn_local(async move {
+                if !disposed.get() && total.get_untracked() == 0 && pending.get_untracked() {
+                    set_pending.set(false);
+                }
+            });
+        }
+    });
+
+    pending.into()
+}
+
+#[cfg(all(test, not(any(feature = "csr", feature = "hydrate", feature = "ssr"))))]
+mod tests {
+    use super::*;
+    use crate::testing::{sleep, TestRuntime};
+    use crate::{create_resource, provide_context};
+    use std::{cell::RefCell, time::Duration};
+
+    fn record(cx: Scope, signal: Signal<bool>) -> Rc<RefCell<Vec<bool>>> {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect(cx, {
+            let log = Rc::clone(&log);
+            move |_| log.borrow_mut().push(signal.get())
+        });
+        log
+    }
+
+    #[test]
+    fn sequential_resources_stay_pending() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        provide_context(cx, SuspenseContext::new(cx));
+        let log = record(cx, use_global_pending(cx));
+
+        let user = create_resource(
+            cx,
+            || (),
+            |_| async {
+                sleep(Duration::from_millis(10)).await;
+                1
+            },
+        );
+        // only starts loading once `user` has resolved
+        let posts = create_resource(
+            cx,
+            move || user.read(),
+            |user| async move {
+                match user {
+                    Some(user) => {
+                        sleep(Duration::from_millis(10)).await;
+                        Some(user * 10)
+                    }
+                    None => None,
+                }
+            },
+        );
+        // read both, as the children of a `<Suspense/>` would
+        create_isomorphic_effect(cx, move |_| {
+            user.read();
+            posts.read();
+        });
+
+        rt.flush();
+        assert!(cx.pending_resources().get() > 0);
+
+        rt.advance(Duration::from_millis(10));
+        assert_eq!(user.read(), Some(1));
+        assert_eq!(posts.read(), Some(None));
+        assert!(cx.pending_resources().get() > 0);
+
+        rt.advance(Duration::from_millis(10));
+        assert_eq!(posts.read(), Some(Some(10)));
+        assert_eq!(cx.pending_resources().get(), 0);
+        assert_eq!(*log.borrow(), [false, true, false]);
+    }
+
+    // a resource that resolves after `ms`, and counts how many times it has been fetched
+    fn counted(cx: Scope, ms: u64) -> (crate::Resource<(), ()>, Rc<Cell<usize>>) {
+        let fetches = Rc::new(Cell::new(0));
+        let resource = create_resource(cx, || (), {
+            let fetches = Rc::clone(&fetches);
+            move |_| {
+                fetches.set(fetches.get() + 1);
+                sleep(Duration::from_millis(ms))
+            }
+        });
+        create_isomorphic_effect(cx, move |_| {
+            resource.read();
+        });
+        (resource, fetches)
+    }
+
+    #[test]
+    fn fast_resolution_is_never_slow() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let context = SuspenseContext::new(cx);
+        provide_context(cx, context);
+        let slow = context.is_slow(cx, Duration::from_millis(100));
+        let log = record(cx, slow.into());
+
+        counted(cx, 50);
+        rt.flush();
+        rt.advance(Duration::from_millis(50));
+        assert!(context.ready());
+
+        rt.advance(Duration::from_millis(200));
+        assert_eq!(*log.borrow(), [false]);
+    }
+
+    #[test]
+    fn slow_resolution_escalates_and_refetches_only_pending_resources() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let context = SuspenseContext::new(cx);
+        provide_context(cx, context);
+        let slow = context.is_slow(cx, Duration::from_millis(100));
+
+        let (_, fast_fetches) = counted(cx, 10);
+        let (_, slow_fetches) = counted(cx, 1000);
+        rt.flush();
+        rt.advance(Duration::from_millis(99));
+        assert!(!slow.get());
+        rt.advance(Duration::from_millis(1));
+        assert!(slow.get());
+
+        context.refetch_pending();
+        rt.flush();
+        assert_eq!((fast_fetches.get(), slow_fetches.get()), (1, 2));
+
+        rt.advance(Duration::from_millis(1000));
+        assert!(context.ready());
+        assert!(!slow.get());
+    }
+
+    #[test]
+    fn disposed_context_is_no_longer_pending() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let log = record(cx, use_global_pending(cx));
+
+        let disposer = cx.child_scope(|cx| {
+            provide_context(cx, SuspenseContext::new(cx));
+            let never = create_resource(cx, || (), |_| futures::future::pending::<()>());
+            create_isomorphic_effect(cx, move |_| {
+                never.read();
+            });
+        });
+        rt.flush();
+        assert_eq!(cx.pending_resources().get(), 1);
+
+        disposer.dispose();
+        rt.flush();
+        assert_eq!(cx.pending_resources().get(), 0);
+        assert_eq!(*log.borrow(), [false, true, false]);
+    }
 }
@@ -0,0 +1,963 @@
+//! Utilities for testing reactive code natively, without a browser.
+//!
+//! A [TestRuntime] makes everything that would usually happen “later” happen
+//! only when the test asks for it:
+//! - effects are queued instead of running immediately, and run when you call [TestRuntime::flush],
+//! - [spawn_local](crate::spawn_local) (and therefore every [Resource](crate::Resource)) uses
+//!   the test’s own executor, which is also driven by [TestRuntime::flush], and
+//! - timers created with [set_timeout], [set_interval], or [sleep] (and the `set_timeout`
+//!   and `set_interval` helpers in `leptos_dom`) use a virtual clock, which only moves
+//!   forward when you call [TestRuntime::advance], and
+//! - the network is always online, unless the test takes it offline with
+//!   [TestRuntime::set_online].
+//!
+//! Memos are never deferred: they are brought up to date whenever they are read.
+//!
+//! This module is only available with the `testing` feature.
+//!
+//! ```
+//! # use leptos_reactive::{*, testing::*};
+//! # use std::time::Duration;
+//! let rt = TestRuntime::new();
+//! let cx = rt.scope();
+//!
+//! let (count, set_count) = create_signal(cx, 0);
+//! let (logged, set_logged) = create_signal(cx, 0);
+//! let effect = rt.create_effect(cx, move |_| {
+//!     let count = count.get();
+//!     set_timeout(move || set_logged.set(count), Duration::from_millis(100));
+//! });
+//! assert_eq!(rt.effect_run_count(&effect), 0);
+//!
+//! rt.flush();
+//! rt.assert_subscribed(&count, &effect);
+//!
+//! // several updates are batched into a single run of the effect
+//! set_count.set(1);
+//! set_count.set(2);
+//! rt.flush();
+//! assert_eq!(rt.effect_run_count(&effect), 2);
+//!
+//! rt.advance(Duration::from_millis(100));
+//! assert_eq!(logged.get(), 2);
+//! ```
+
+use crate::{
+    create_runtime, runtime::expect_runtime, Effect, EffectId, Memo, ReadSignal, RuntimeId,
+    RwSignal, Scope, ScopeDisposer, ScopeProperty, SignalId, WriteSignal,
+};
+use futures::{
+    executor::{LocalPool, LocalSpawner},
+    task::LocalSpawnExt,
+};
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    rc::Rc,
+    time::Duration,
+};
+
+thread_local! {
+    static CLOCK: RefCell<Option<Clock>> = RefCell::new(None);
+    static EXECUTOR: RefCell<Option<LocalPool>> = RefCell::new(None);
+    static SPAWNER: RefCell<Option<LocalSpawner>> = RefCell::new(None);
+    static NETWORK: RefCell<Option<Network>> = RefCell::new(None);
+}
+
+/// A reactive runtime with deterministic scheduling, for use in tests. See the [module docs](self).
+///
+/// Only one `TestRuntime` can exist on a thread at a time. Everything it owns is disposed
+/// when it is dropped.
+pub struct TestRuntime {
+    runtime: RuntimeId,
+    cx: Scope,
+    disposer: Option<ScopeDisposer>,
+}
+
+impl TestRuntime {
+    /// Creates a new runtime, and starts deferring effects, futures, and timers on this thread.
+    ///
+    /// # Panics
+    /// Panics if another `TestRuntime` is already running on this thread.
+    pub fn new() -> Self {
+        CLOCK.with(|clock| {
+            let mut clock = clock.borrow_mut();
+            assert!(
+                clock.is_none(),
+                "only one TestRuntime can run on a thread at a time"
+            );
+            *clock = Some(Clock::default());
+        });
+        NETWORK.with(|network| *network.borrow_mut() = Some(Network::default()));
+        let pool = LocalPool::new();
+        SPAWNER.with(|spawner| *spawner.borrow_mut() = Some(pool.spawner()));
+        EXECUTOR.with(|executor| *executor.borrow_mut() = Some(pool));
+
+        let runtime = create_runtime();
+        expect_runtime(runtime, |runtime| {
+            *runtime.effect_queue.borrow_mut() = Some(Default::default())
+        });
+        let (cx, disposer) = runtime.raw_scope_and_disposer();
+
+        Self {
+            runtime,
+            cx,
+            disposer: Some(disposer),
+        }
+    }
+
+    /// The root [Scope] of the runtime.
+    pub fn scope(&self) -> Scope {
+        self.cx
+    }
+
+    /// Runs all queued effects and spawned futures, repeating until there is nothing
+    /// left to do.
+    pub fn flush(&self) {
+        loop {
+            EXECUTOR.with(|executor| {
+                if let Some(executor) = &mut *executor.borrow_mut() {
+                    executor.run_until_stalled();
+                }
+            });
+
+            let pending = expect_runtime(self.runtime, |runtime| {
+                runtime
+                    .effect_queue
+                    .borrow_mut()
+                    .as_mut()
+                    .map(|queue| std::mem::take(&mut queue.pending))
+                    .unwrap_or_default()
+            });
+            if pending.is_empty() {
+                break;
+            }
+
+            for id in pending {
+                expect_runtime(self.runtime, |runtime| {
+                    // the effect may have been disposed since it was queued
+                    if runtime.effects.borrow().contains_key(id) {
+                        runtime.update_if_necessary(self.runtime, id);
+                    }
+                });
+            }
+            // anything waiting for the runtime to be idle was waiting for these too
+            expect_runtime(self.runtime, |runtime| runtime.wake_idle_waiters());
+        }
+    }
+
+    /// Moves the virtual clock forward by `duration`, firing every timer that comes due
+    /// in order, and flushing after each one.
+    pub fn advance(&self, duration: Duration) {
+        self.flush();
+
+        let until = self.now() + duration;
+        while let Some(callback) = with_clock(|clock| clock.take_due(until)) {
+            callback();
+            self.flush();
+        }
+        with_clock(|clock| clock.now = until);
+    }
+
+    /// The amount of virtual time that has passed since the runtime was created.
+    pub fn now(&self) -> Duration {
+        with_clock(|clock| clock.now)
+    }
+
+    /// Takes the simulated network offline, or brings it back online, like the browser’s
+    /// `offline` and `online` events, and then flushes.
+    pub fn set_online(&self, online: bool) {
+        let reconnected = NETWORK.with(|network| {
+            let mut network = network.borrow_mut();
+            let network = network.as_mut().expect("the TestRuntime has shut down");
+            network.online = online;
+            if online {
+                std::mem::take(&mut network.waiting)
+            } else {
+                Vec::new()
+            }
+        });
+        for tx in reconnected {
+            _ = tx.send(());
+        }
+        self.flush();
+    }
+
+    /// Creates an effect, like [create_isomorphic_effect](crate::create_isomorphic_effect),
+    /// and returns a handle that can be used to inspect it.
+    ///
+    /// Like every other effect in the `TestRuntime`, it doesn’t run until the next [TestRuntime::flush].
+    pub fn create_effect<T>(&self, cx: Scope, f: impl Fn(Option<T>) -> T + 'static) -> EffectHandle
+    where
+        T: 'static,
+    {
+        let runs = Rc::new(Cell::new(0));
+        let id = cx.runtime.create_effect({
+            let runs = Rc::clone(&runs);
+            move |prev| {
+                runs.set(runs.get() + 1);
+                f(prev)
+            }
+        });
+        cx.with_scope_property(|prop| prop.push(ScopeProperty::Effect(id)));
+        EffectHandle { id, runs }
+    }
+
+    /// The number of times the effect has run.
+    pub fn effect_run_count(&self, effect: &EffectHandle) -> usize {
+        effect.runs.get()
+    }
+
+    /// Returns `true` if the effect will rerun when the signal changes.
+    pub fn is_subscribed(&self, signal: &impl AsSignalId, effect: &EffectHandle) -> bool {
+        expect_runtime(self.runtime, |runtime| {
+            runtime
+                .signal_subscribers
+                .borrow()
+                .get(signal.signal_id())
+                .map(|subs| subs.borrow().contains(&effect.id))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Panics if the effect will not rerun when the signal changes.
+    #[track_caller]
+    pub fn assert_subscribed(&self, signal: &impl AsSignalId, effect: &EffectHandle) {
+        assert!(
+            self.is_subscribed(signal, effect),
+            "expected the effect to be subscribed to {:?}, but it isn't",
+            signal.signal_id()
+        );
+    }
+
+    /// Runs the function, and reports which signals it read in a tracked way
+    /// (i.e., which signals an effect running the same code would subscribe to).
+    pub fn with_tracking_report<T>(&self, f: impl FnOnce() -> T) -> (T, TrackingReport) {
+        let (observer, prev_observer) = expect_runtime(self.runtime, |runtime| {
+            let effect = Effect {
+                f: |_: Option<()>| {},
+                value: RefCell::new(None),
+            };
+            let observer = runtime.effects.borrow_mut().insert(Rc::new(effect));
+            (observer, runtime.observer.replace(Some(observer)))
+        });
+
+        let value = f();
+
+        let signals = expect_runtime(self.runtime, |runtime| {
+            runtime.observer.set(prev_observer);
+            runtime.effects.borrow_mut().remove(observer);
+            runtime.effect_sources.borrow_mut().remove(observer);
+            runtime
+                .signal_subscribers
+                .borrow()
+                .iter()
+                .filter(|(_, subs)| subs.borrow_mut().remove(&observer))
+                .map(|(id, _)| id)
+                .collect()
+        });
+
+        (value, TrackingReport { signals })
+    }
+
+    /// Counts everything that currently exists in the runtime, e.g. to check that disposing
+    /// of a scope has cleaned up everything it owned.
+    pub fn diagnostics(&self) -> Diagnostics {
+        expect_runtime(self.runtime, |runtime| Diagnostics {
+            scopes: runtime.scopes.borrow().len(),
+            signals: runtime.signals.borrow().len(),
+            effects: runtime.effects.borrow().len(),
+            resources: runtime.resources.borrow().len(),
+            disposables: runtime.disposables.borrow().len(),
+            subscriptions: runtime
+                .signal_subscribers
+                .borrow()
+                .values()
+                .map(|subs| subs.borrow().len())
+                .sum(),
+            cleanups: runtime.scope_cleanups.borrow().values().map(Vec::len).sum(),
+        })
+    }
+}
+
+impl Default for TestRuntime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TestRuntime {
+    fn drop(&mut self) {
+        expect_runtime(self.runtime, |runtime| runtime.effect_queue.take());
+        if let Some(disposer) = self.disposer.take() {
+            disposer.dispose();
+        }
+        self.runtime.dispose();
+
+        SPAWNER.with(|spawner| spawner.take());
+        // dropped outside the `RefCell`, in case a pending future touches it as it's dropped
+        let executor = EXECUTOR.with(|executor| executor.take());
+        drop(executor);
+        CLOCK.with(|clock| clock.take());
+        NETWORK.with(|network| network.take());
+    }
+}
+
+/// A handle to an effect created with [TestRuntime::create_effect].
+#[derive(Clone)]
+pub struct EffectHandle {
+    id: EffectId,
+    runs: Rc<Cell<usize>>,
+}
+
+impl std::fmt::Debug for EffectHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EffectHandle")
+            .field("id", &self.id)
+            .field("runs", &self.runs.get())
+            .finish()
+    }
+}
+
+/// The signals read by the function passed to [TestRuntime::with_tracking_report].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackingReport {
+    signals: Vec<SignalId>,
+}
+
+impl TrackingReport {
+    /// The IDs of every signal that was read, in no particular order.
+    pub fn signals(&self) -> &[SignalId] {
+        &self.signals
+    }
+
+    /// Returns `true` if the signal was read.
+    pub fn tracked(&self, signal: &impl AsSignalId) -> bool {
+        self.signals.contains(&signal.signal_id())
+    }
+}
+
+/// The number of each kind of thing in a [TestRuntime], as returned by [TestRuntime::diagnostics].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Diagnostics {
+    /// The number of scopes that haven’t been disposed of.
+    pub scopes: usize,
+    /// The number of signals, including those holding the values of memos.
+    pub signals: usize,
+    /// The number of effects, including those computing memos.
+    pub effects: usize,
+    /// The number of resources.
+    pub resources: usize,
+    /// The number of [Disposable](crate::Disposable)s that haven’t been disposed of.
+    pub disposables: usize,
+    /// The number of times any effect is subscribed to any signal.
+    pub subscriptions: usize,
+    /// The number of cleanup functions that haven’t run yet.
+    pub cleanups: usize,
+}
+
+/// Any signal type whose subscriptions can be inspected by a [TestRuntime].
+pub trait AsSignalId {
+    /// The unique ID of the underlying signal.
+    fn signal_id(&self) -> SignalId;
+}
+
+impl<T> AsSignalId for ReadSignal<T> {
+    fn signal_id(&self) -> SignalId {
+        self.id
+    }
+}
+
+impl<T> AsSignalId for WriteSignal<T> {
+    fn signal_id(&self) -> SignalId {
+        self.id
+    }
+}
+
+impl<T> AsSignalId for RwSignal<T> {
+    fn signal_id(&self) -> SignalId {
+        self.id
+    }
+}
+
+impl<T> AsSignalId for Memo<T> {
+    fn signal_id(&self) -> SignalId {
+        self.0.id
+    }
+}
+
+/// Runs the callback once `duration` has passed on the virtual clock.
+///
+/// # Panics
+/// Panics if no [TestRuntime] is running on this thread.
+pub fn set_timeout(cb: impl FnOnce() + 'static, duration: Duration) {
+    with_clock(|clock| clock.schedule(duration, TimerCallback::Once(Box::new(cb))));
+}
+
+/// Runs the callback every time `duration` passes on the virtual clock, until the
+/// returned ID is passed to [clear_interval].
+///
+/// # Panics
+/// Panics if no [TestRuntime] is running on this thread.
+pub fn set_interval(cb: impl Fn() + 'static, duration: Duration) -> usize {
+    // a zero-length interval would fire forever without the clock moving
+    let duration = duration.max(Duration::from_millis(1));
+    with_clock(|clock| clock.schedule(duration, TimerCallback::Repeat(Rc::new(cb), duration)))
+}
+
+/// Stops an interval created with [set_interval].
+pub fn clear_interval(id: usize) {
+    if is_active() {
+        with_clock(|clock| clock.timers.retain(|timer| timer.id != id));
+    }
+}
+
+/// Resolves once `duration` has passed on the virtual clock.
+///
+/// # Panics
+/// Panics if no [TestRuntime] is running on this thread.
+pub async fn sleep(duration: Duration) {
+    let (tx, rx) = futures::channel::oneshot::channel();
+    set_timeout(
+        move || {
+            _ = tx.send(());
+        },
+        duration,
+    );
+    _ = rx.await;
+}
+
+/// Returns `true` if a [TestRuntime] is running on this thread.
+pub fn is_active() -> bool {
+    CLOCK.with(|clock| clock.borrow().is_some())
+}
+
+/// Resolves once the simulated network is back online.
+pub(crate) async fn until_online() {
+    let rx = NETWORK.with(|network| {
+        let mut network = network.borrow_mut();
+        let network = network.as_mut().filter(|network| !network.online)?;
+        let (tx, rx) = futures::channel::oneshot::channel();
+        network.waiting.push(tx);
+        Some(rx)
+    });
+    if let Some(rx) = rx {
+        _ = rx.await;
+    }
+}
+
+/// Spawns the future on the [TestRuntime]’s executor, or hands it back if none is running.
+pub(crate) fn spawn<F>(fut: F) -> Result<(), F>
+where
+    F: Future<Output = ()> + 'static,
+{
+    SPAWNER.with(|spawner| match &*spawner.borrow() {
+        Some(spawner) => {
+            spawner
+                .spawn_local(fut)
+                .expect("the TestRuntime executor has shut down");
+            Ok(())
+        }
+        None => Err(fut),
+    })
+}
+
+fn with_clock<T>(f: impl FnOnce(&mut Clock) -> T) -> T {
+    CLOCK.with(|clock| {
+        f(clock
+            .borrow_mut()
+            .as_mut()
+            .expect("virtual timers can only be used while a TestRuntime is running"))
+    })
+}
+
+/// Effects that have been queued by a [TestRuntime], but not yet run.
+#[derive(Default)]
+pub(crate) struct EffectQueue {
+    pending: Vec<EffectId>,
+}
+
+impl EffectQueue {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub(crate) fn defer(&mut self, id: EffectId) -> bool {
+        if !self.pending.contains(&id) {
+            self.pending.push(id);
+        }
+        true
+    }
+}
+
+struct Network {
+    online: bool,
+    // resolves the futures returned by `until_online`
+    waiting: Vec<futures::channel::oneshot::Sender<()>>,
+}
+
+impl Default for Network {
+    fn default() -> Self {
+        Self {
+            online: true,
+            waiting: Vec::new(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Clock {
+    now: Duration,
+    next_id: usize,
+    timers: Vec<Timer>,
+}
+
+struct Timer {
+    id: usize,
+    deadline: Duration,
+    callback: TimerCallback,
+}
+
+enum TimerCallback {
+    Once(Box<dyn FnOnce()>),
+    Repeat(Rc<dyn Fn()>, Duration),
+}
+
+impl Clock {
+    fn schedule(&mut self, duration: Duration, callback: TimerCallback) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.timers.push(Timer {
+            id,
+            deadline: self.now + duration,
+            callback,
+        });
+        id
+    }
+
+    /// Removes the next timer that is due by `until`, moves the clock to its deadline,
+    /// and returns its callback. Intervals are rescheduled.
+    fn take_due(&mut self, until: Duration) -> Option<Box<dyn FnOnce()>> {
+        let (idx, _) = self
+            .timers
+            .iter()
+            .enumerate()
+            .filter(|(_, timer)| timer.deadline <= until)
+            .min_by_key(|(_, timer)| (timer.deadline, timer.id))?;
+        let timer = self.timers.remove(idx);
+        self.now = timer.deadline;
+
+        Some(match timer.callback {
+            TimerCallback::Once(f) => f,
+            TimerCallback::Repeat(f, interval) => {
+                self.timers.push(Timer {
+                    id: timer.id,
+                    deadline: timer.deadline + interval,
+                    callback: TimerCallback::Repeat(Rc::clone(&f), interval),
+                });
+                Box::new(move || f())
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        create_isomorphic_effect, create_memo, create_resource, create_signal, IdleResult,
+        SuspenseContext, UntrackedGettableSignal,
+    };
+
+    #[test]
+    fn effects_run_when_flushed() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let (a, set_a) = create_signal(cx, 0);
+        let effect = rt.create_effect(cx, move |_| a.get());
+        assert_eq!(rt.effect_run_count(&effect), 0);
+
+        rt.flush();
+        assert_eq!(rt.effect_run_count(&effect), 1);
+        rt.assert_subscribed(&a, &effect);
+
+        set_a.set(1);
+        set_a.set(2);
+        assert_eq!(rt.effect_run_count(&effect), 1);
+        rt.flush();
+        assert_eq!(rt.effect_run_count(&effect), 2);
+    }
+
+    #[test]
+    fn memos_are_not_deferred() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let (a, set_a) = create_signal(cx, 1);
+        let double = create_memo(cx, move |_| a.get() * 2);
+        assert_eq!(double.get(), 2);
+        set_a.set(2);
+        assert_eq!(double.get(), 4);
+    }
+
+    #[test]
+    fn tracking_report() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let (a, _) = create_signal(cx, 1);
+        let (b, _) = create_signal(cx, 2);
+        let c = create_memo(cx, move |_| a.get() + 1);
+
+        let (sum, report) = rt.with_tracking_report(|| c.get() + b.get_untracked());
+        assert_eq!(sum, 4);
+        assert!(report.tracked(&c));
+        assert!(!report.tracked(&b));
+        assert!(!report.tracked(&a));
+    }
+
+    // Debounces a search input: the query is only updated once the user
+    // has stopped typing for 300ms.
+    fn debounced(cx: Scope, input: ReadSignal<String>, delay: Duration) -> ReadSignal<String> {
+        let (query, set_query) = create_signal(cx, input.get_untracked());
+        let generation = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, move |_| {
+            let value = input.get();
+            let current = generation.get() + 1;
+            generation.set(current);
+            let generation = Rc::clone(&generation);
+            set_timeout(
+                move || {
+                    if generation.get() == current {
+                        set_query.set(value);
+                    }
+                },
+                delay,
+            );
+        });
+        query
+    }
+
+    #[test]
+    fn debounced_search_with_virtual_time() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let (input, set_input) = create_signal(cx, String::new());
+        let query = debounced(cx, input, Duration::from_millis(300));
+
+        for text in ["l", "le", "lep"] {
+            set_input.set(text.to_string());
+            rt.advance(Duration::from_millis(100));
+        }
+        assert_eq!(query.get(), "");
+
+        rt.advance(Duration::from_millis(199));
+        assert_eq!(query.get(), "");
+        rt.advance(Duration::from_millis(1));
+        assert_eq!(query.get(), "lep");
+        assert_eq!(rt.now(), Duration::from_millis(500));
+    }
+
+    // resources queue a browser microtask when they load
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    #[test]
+    fn resources_use_virtual_time() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let (id, set_id) = create_signal(cx, 1);
+        let user = create_resource(
+            cx,
+            move || id.get(),
+            |id| async move {
+                sleep(Duration::from_millis(50)).await;
+                format!("user {id}")
+            },
+        );
+
+        rt.flush();
+        assert!(user.loading().get());
+        assert_eq!(user.read(), None);
+
+        rt.advance(Duration::from_millis(50));
+        assert!(!user.loading().get());
+        assert_eq!(user.read(), Some("user 1".to_string()));
+
+        set_id.set(2);
+        rt.advance(Duration::from_millis(50));
+        assert_eq!(user.read(), Some("user 2".to_string()));
+    }
+
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    #[test]
+    fn cached_resources_share_fetches() {
+        use crate::{create_cached_resource, invalidate_cached_resource};
+
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let fetches = Rc::new(Cell::new(0));
+        let fetch_user = {
+            let fetches = Rc::clone(&fetches);
+            move |id: u32| {
+                fetches.set(fetches.get() + 1);
+                async move {
+                    sleep(Duration::from_millis(50)).await;
+                    format!("user {id}")
+                }
+            }
+        };
+        let (a, set_a) = create_signal(cx, 1);
+        let (b, _) = create_signal(cx, 1);
+        let first = create_cached_resource(cx, move || a.get(), fetch_user.clone(), |id| *id);
+        let second = create_cached_resource(cx, move || b.get(), fetch_user, |id| *id);
+
+        rt.advance(Duration::from_millis(50));
+        assert_eq!(fetches.get(), 1);
+        assert_eq!(first.read(), Some("user 1".to_string()));
+        assert_eq!(second.read(), Some("user 1".to_string()));
+
+        set_a.set(2);
+        rt.advance(Duration::from_millis(50));
+        assert_eq!(fetches.get(), 2);
+        assert_eq!(first.read(), Some("user 2".to_string()));
+
+        // already resolved
+        second.refetch();
+        rt.advance(Duration::from_millis(50));
+        assert_eq!(fetches.get(), 2);
+
+        invalidate_cached_resource::<u32, String>(cx, &1);
+        second.refetch();
+        rt.advance(Duration::from_millis(50));
+        assert_eq!(fetches.get(), 3);
+        assert_eq!(second.read(), Some("user 1".to_string()));
+    }
+
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    #[test]
+    fn resource_errors() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let (id, set_id) = create_signal(cx, 1);
+        let user = create_resource(
+            cx,
+            move || id.get(),
+            |id| async move {
+                sleep(Duration::from_millis(50)).await;
+                if id > 0 {
+                    Ok(format!("user {id}"))
+                } else {
+                    Err(format!("no user {id}"))
+                }
+            },
+        );
+        let error = user.error();
+
+        rt.advance(Duration::from_millis(50));
+        assert_eq!(error.get(), None);
+
+        set_id.set(0);
+        rt.advance(Duration::from_millis(50));
+        assert_eq!(error.get(), Some("no user 0".to_string()));
+
+        // keeps the last error until the next fetch resolves
+        set_id.set(2);
+        rt.flush();
+        assert!(user.loading().get());
+        assert_eq!(error.get(), Some("no user 0".to_string()));
+        rt.advance(Duration::from_millis(50));
+        assert_eq!(error.get(), None);
+    }
+
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    #[test]
+    fn resources_are_loading_while_refetching() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let (id, set_id) = create_signal(cx, 1);
+        let user = create_resource(
+            cx,
+            move || id.get(),
+            |id| async move {
+                // earlier requests take longer
+                sleep(Duration::from_millis(100 / id)).await;
+                format!("user {id}")
+            },
+        );
+        rt.advance(Duration::from_millis(100));
+        assert!(!user.loading().get());
+
+        // keeps the previous value while refetching
+        user.refetch();
+        rt.flush();
+        assert!(user.loading().get());
+        assert_eq!(user.read(), Some("user 1".to_string()));
+        rt.advance(Duration::from_millis(100));
+        assert!(!user.loading().get());
+
+        // still loading until every fetch has resolved, even if a later one resolves first
+        set_id.set(2);
+        rt.flush();
+        set_id.set(4);
+        rt.flush();
+        rt.advance(Duration::from_millis(25));
+        assert!(user.loading().get());
+        rt.advance(Duration::from_millis(25));
+        assert!(!user.loading().get());
+    }
+
+    #[test]
+    fn intervals() {
+        let rt = TestRuntime::new();
+        let ticks = Rc::new(Cell::new(0));
+        let id = set_interval(
+            {
+                let ticks = Rc::clone(&ticks);
+                move || ticks.set(ticks.get() + 1)
+            },
+            Duration::from_secs(1),
+        );
+
+        rt.advance(Duration::from_millis(3500));
+        assert_eq!(ticks.get(), 3);
+        clear_interval(id);
+        rt.advance(Duration::from_secs(10));
+        assert_eq!(ticks.get(), 3);
+    }
+
+    // spawns `wait_for_idle`, and returns where its result will be once it resolves
+    fn spawn_wait_for_idle(
+        cx: Scope,
+        timeout: Option<Duration>,
+    ) -> Rc<RefCell<Option<IdleResult>>> {
+        let result = Rc::new(RefCell::new(None));
+        crate::spawn_local({
+            let result = Rc::clone(&result);
+            let idle = cx.wait_for_idle(timeout);
+            async move {
+                *result.borrow_mut() = Some(idle.await);
+            }
+        });
+        result
+    }
+
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    #[test]
+    fn waits_for_work_started_while_waiting() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let a = create_resource(
+            cx,
+            || (),
+            |_| async {
+                sleep(Duration::from_millis(50)).await;
+                1
+            },
+        );
+        // only fetches once `a` has resolved
+        let b = create_resource(
+            cx,
+            move || a.read(),
+            |a| async move {
+                match a {
+                    Some(a) => {
+                        sleep(Duration::from_millis(50)).await;
+                        Some(a + 1)
+                    }
+                    None => None,
+                }
+            },
+        );
+        rt.flush();
+        assert_eq!(cx.pending_resource_count(), 1);
+
+        let result = spawn_wait_for_idle(cx, None);
+        rt.flush();
+        assert_eq!(*result.borrow(), None);
+
+        rt.advance(Duration::from_millis(50));
+        assert_eq!(a.read(), Some(1));
+        assert_eq!(cx.pending_resource_count(), 1);
+        assert_eq!(*result.borrow(), None);
+
+        rt.advance(Duration::from_millis(50));
+        assert_eq!(b.read(), Some(Some(2)));
+        assert_eq!(cx.pending_resource_count(), 0);
+        assert_eq!(*result.borrow(), Some(IdleResult::Idle));
+    }
+
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    #[test]
+    fn timeouts_report_pending_work() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let a = create_resource(
+            cx,
+            || (),
+            |_| async {
+                sleep(Duration::from_millis(50)).await;
+                1
+            },
+        );
+        let b = create_resource(
+            cx,
+            move || a.read(),
+            |a| async move {
+                if a.is_some() {
+                    futures::future::pending::<()>().await;
+                }
+            },
+        );
+        let context = SuspenseContext::new(cx);
+        context.increment();
+        let key = cx.current_fragment_key();
+        cx.register_suspense(context, &key, String::new);
+
+        let result = spawn_wait_for_idle(cx, Some(Duration::from_millis(100)));
+        rt.advance(Duration::from_millis(99));
+        assert_eq!(*result.borrow(), None);
+
+        rt.advance(Duration::from_millis(1));
+        assert_eq!(
+            *result.borrow(),
+            Some(IdleResult::TimedOut {
+                resources: vec![b.id()],
+                fragments: vec![key],
+            })
+        );
+    }
+
+    // how many fragments the runtime is keeping track of
+    fn tracked_fragments(cx: Scope) -> usize {
+        crate::runtime::with_runtime(cx.runtime, |runtime| {
+            runtime.suspense_fragments.borrow().len()
+        })
+        .unwrap()
+    }
+
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    #[test]
+    fn fragments_are_forgotten_once_resolved_or_disposed() {
+        let rt = TestRuntime::new();
+        let cx = rt.scope();
+        let resolved = SuspenseContext::new(cx);
+        resolved.increment();
+        cx.register_suspense(resolved, "resolved", String::new);
+        let disposer = cx.child_scope(|cx| {
+            let context = SuspenseContext::new(cx);
+            context.increment();
+            cx.register_suspense(context, "disposed", String::new);
+        });
+        for (_, fragment) in cx.pending_fragments() {
+            crate::spawn_local(async move {
+                fragment.await;
+            });
+        }
+        rt.flush();
+        assert_eq!(tracked_fragments(cx), 2);
+
+        resolved.decrement();
+        rt.flush();
+        assert_eq!(tracked_fragments(cx), 1);
+
+        disposer.dispose();
+        assert_eq!(tracked_fragments(cx), 0);
+    }
+}
@@ -0,0 +1,96 @@
+use leptos_reactive::{
+    create_context_provider, create_runtime, create_scope, ctx_provider, provide_context,
+    use_context,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Theme(&'static str);
+
+#[test]
+fn nearest_provider_shadows_outer_ones() {
+    create_scope(create_runtime(), |cx| {
+        provide_context(cx, Theme("light"));
+
+        cx.child_scope(|cx| {
+            assert_eq!(use_context::<Theme>(cx), Some(Theme("light")));
+
+            cx.child_scope(|cx| {
+                provide_context(cx, Theme("dark"));
+                cx.child_scope(|cx| {
+                    assert_eq!(use_context::<Theme>(cx), Some(Theme("dark")));
+                });
+            });
+
+            // siblings of the shadowing scope still see the outer value
+            cx.child_scope(|cx| {
+                assert_eq!(use_context::<Theme>(cx), Some(Theme("light")));
+            });
+        });
+    })
+    .dispose()
+}
+
+#[test]
+fn provider_added_after_children_resolved() {
+    create_scope(create_runtime(), |cx| {
+        provide_context(cx, Theme("light"));
+
+        cx.child_scope(|middle| {
+            middle.child_scope(|row| {
+                assert_eq!(use_context::<Theme>(row), Some(Theme("light")));
+
+                // a context provided later by an ancestor is seen by the next lookup
+                provide_context(middle, Theme("dark"));
+                assert_eq!(use_context::<Theme>(row), Some(Theme("dark")));
+
+                // and so is a new value for the same context
+                provide_context(middle, Theme("sepia"));
+                assert_eq!(use_context::<Theme>(row), Some(Theme("sepia")));
+            });
+        });
+    })
+    .dispose()
+}
+
+#[test]
+fn missing_context_can_be_provided_later() {
+    create_scope(create_runtime(), |cx| {
+        cx.child_scope(|row| {
+            assert_eq!(use_context::<Theme>(row), None);
+            assert_eq!(use_context::<Theme>(row), None);
+
+            provide_context(cx, Theme("light"));
+            assert_eq!(use_context::<Theme>(row), Some(Theme("light")));
+
+            // providing a different type leaves resolved lookups intact
+            provide_context(row, 42_usize);
+            assert_eq!(use_context::<Theme>(row), Some(Theme("light")));
+            assert_eq!(use_context::<usize>(row), Some(42));
+        });
+    })
+    .dispose()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Locale(&'static str);
+
+#[test]
+fn context_provider_provides_every_value() {
+    create_scope(create_runtime(), |cx| {
+        create_context_provider(cx, (Theme("dark"), Locale("fr"), 42_u32));
+
+        cx.child_scope(|cx| {
+            assert_eq!(use_context::<Theme>(cx), Some(Theme("dark")));
+            assert_eq!(use_context::<Locale>(cx), Some(Locale("fr")));
+            assert_eq!(use_context::<u32>(cx), Some(42));
+        });
+
+        cx.child_scope(|cx| {
+            ctx_provider!(cx, [Theme("light"), Locale("de")]);
+            assert_eq!(use_context::<Theme>(cx), Some(Theme("light")));
+            assert_eq!(use_context::<Locale>(cx), Some(Locale("de")));
+            assert_eq!(use_context::<u32>(cx), Some(42));
+        });
+    })
+    .dispose()
+}
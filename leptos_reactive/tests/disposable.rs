@@ -0,0 +1,99 @@
+use std::{cell::RefCell, rc::Rc};
+
+use leptos_reactive::{create_runtime, create_scope, create_signal, on_cleanup, Disposable};
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+struct Logged(&'static str, Log);
+
+impl Disposable for Logged {
+    fn dispose(&mut self) {
+        self.1.borrow_mut().push(format!("dispose {}", self.0));
+    }
+
+    fn debug_name(&self) -> Option<String> {
+        Some(self.0.to_string())
+    }
+}
+
+#[test]
+fn registered_disposables_can_be_looked_up() {
+    let log = Log::default();
+    create_scope(create_runtime(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            let id = cx.register_disposable(Logged("worker", Rc::clone(&log)));
+            let info = cx.lookup_disposable(id).unwrap();
+            assert_eq!(info.owner, Some(cx.id()));
+            assert_eq!(info.name.as_deref(), Some("worker"));
+            assert!(id.to_string().starts_with("disposable#"));
+        }
+    })
+    .dispose();
+    assert_eq!(*log.borrow(), ["dispose worker"]);
+}
+
+// logs when the signal holding it is disposed of
+struct DropLogged(Log);
+
+impl Drop for DropLogged {
+    fn drop(&mut self) {
+        self.0.borrow_mut().push("drop signal".to_string());
+    }
+}
+
+#[test]
+fn disposed_after_cleanups_in_registration_order() {
+    let log = Log::default();
+    create_scope(create_runtime(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            cx.register_disposable(Logged("first", Rc::clone(&log)));
+            _ = create_signal(cx, DropLogged(Rc::clone(&log)));
+            cx.register_disposable(Logged("second", Rc::clone(&log)));
+            on_cleanup(cx, {
+                let log = Rc::clone(&log);
+                move || log.borrow_mut().push("cleanup".to_string())
+            });
+        }
+    })
+    .dispose();
+    assert_eq!(
+        *log.borrow(),
+        ["cleanup", "dispose first", "drop signal", "dispose second"]
+    );
+}
+
+#[test]
+fn taken_disposables_are_disposed_once() {
+    let log = Log::default();
+    create_scope(create_runtime(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            let id = cx.register_disposable(Logged("early", Rc::clone(&log)));
+            assert!(cx.take_disposable(id));
+            assert_eq!(*log.borrow(), ["dispose early"]);
+            assert_eq!(cx.lookup_disposable(id), None);
+            // already gone
+            assert!(!cx.take_disposable(id));
+        }
+    })
+    .dispose();
+    assert_eq!(*log.borrow(), ["dispose early"]);
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn diagnostics_count_disposables() {
+    use leptos_reactive::testing::TestRuntime;
+
+    let rt = TestRuntime::new();
+    let log = Log::default();
+    let ((), disposer) = rt.scope().run_child_scope(|cx| {
+        cx.register_disposable(Logged("a", Rc::clone(&log)));
+        cx.register_disposable(Logged("b", Rc::clone(&log)));
+    });
+    assert_eq!(rt.diagnostics().disposables, 2);
+    disposer.dispose();
+    assert_eq!(rt.diagnostics().disposables, 0);
+}
@@ -1,6 +1,7 @@
 #[cfg(not(feature = "stable"))]
 use leptos_reactive::{
-    create_isomorphic_effect, create_memo, create_runtime, create_scope, create_signal,
+    create_effect_after, create_isomorphic_effect, create_memo, create_runtime, create_scope,
+    create_signal,
 };
 
 #[cfg(not(feature = "stable"))]
@@ -93,3 +94,71 @@ fn untrack_mutes_effect() {
     })
     .dispose()
 }
+
+#[cfg(not(feature = "stable"))]
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn ordered_effects_run_after_their_dependencies() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        // each effect runs after the one before it
+        let mut prev = Vec::new();
+        for n in 0..10 {
+            let id = create_effect_after(cx, prev, {
+                let log = log.clone();
+                move |_| {
+                    a();
+                    log.borrow_mut().push(n);
+                }
+            });
+            prev = vec![id];
+        }
+        log.borrow_mut().clear();
+
+        for _ in 0..5 {
+            set_a.update(|n| *n += 1);
+            assert_eq!(*log.borrow(), (0..10).collect::<Vec<_>>());
+            log.borrow_mut().clear();
+        }
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn effect_cleanups_run_before_the_next_run_and_on_dispose() {
+    use leptos_reactive::{create_effect_with_cleanup, PrevEffect};
+    use std::{cell::RefCell, rc::Rc};
+
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let disposer = create_scope(create_runtime(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            let (a, set_a) = create_signal(cx, 0);
+            create_effect_with_cleanup(cx, {
+                let log = Rc::clone(&log);
+                move |prev: PrevEffect<i32>| {
+                    let a = a.get();
+                    log.borrow_mut()
+                        .push(format!("run {a} after {:?}", prev.value()));
+                    drop(prev);
+                    let log = Rc::clone(&log);
+                    (a, move || log.borrow_mut().push(format!("clean {a}")))
+                }
+            });
+            set_a.set(1);
+        }
+    });
+    assert_eq!(
+        *log.borrow(),
+        ["run 0 after None", "run 1 after Some(0)", "clean 0"]
+    );
+
+    disposer.dispose();
+    assert_eq!(log.borrow().last().unwrap(), "clean 1");
+}
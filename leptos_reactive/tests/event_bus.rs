@@ -0,0 +1,122 @@
+use leptos_reactive::{
+    create_memo, create_runtime, create_scope, create_signal, on_event, provide_event_bus,
+    use_event_emitter,
+};
+use std::{cell::RefCell, rc::Rc};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Clear,
+    Undo,
+}
+
+#[test]
+fn every_subscriber_receives_an_emission() {
+    create_scope(create_runtime(), |cx| {
+        provide_event_bus::<Command>(cx);
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        for name in ["first", "second"] {
+            let received = Rc::clone(&received);
+            cx.child_scope(move |cx| {
+                on_event(cx, move |command: &Command| {
+                    received.borrow_mut().push((name, command.clone()))
+                })
+            });
+        }
+
+        cx.child_scope(|cx| use_event_emitter(cx).emit(Command::Clear));
+        assert_eq!(
+            *received.borrow(),
+            [("first", Command::Clear), ("second", Command::Clear)]
+        );
+    })
+    .dispose()
+}
+
+#[test]
+fn disposed_subscribers_are_removed() {
+    create_scope(create_runtime(), |cx| {
+        provide_event_bus::<Command>(cx);
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        let disposer = cx.child_scope({
+            let received = Rc::clone(&received);
+            move |cx| {
+                on_event(cx, move |command: &Command| {
+                    received.borrow_mut().push(command.clone())
+                })
+            }
+        });
+
+        let emitter = use_event_emitter(cx);
+        emitter.emit(Command::Undo);
+        disposer.dispose();
+        emitter.emit(Command::Clear);
+
+        assert_eq!(*received.borrow(), [Command::Undo]);
+    })
+    .dispose()
+}
+
+#[test]
+fn emitting_from_a_handler_waits_for_the_current_dispatch() {
+    create_scope(create_runtime(), |cx| {
+        provide_event_bus::<Command>(cx);
+        let received = Rc::new(RefCell::new(Vec::new()));
+
+        // undoing also clears, but only after everyone has heard about the undo
+        let emitter = use_event_emitter(cx);
+        on_event(cx, {
+            let received = Rc::clone(&received);
+            move |command: &Command| {
+                received.borrow_mut().push(("first", command.clone()));
+                if *command == Command::Undo {
+                    emitter.emit(Command::Clear);
+                }
+            }
+        });
+        on_event(cx, {
+            let received = Rc::clone(&received);
+            move |command: &Command| received.borrow_mut().push(("second", command.clone()))
+        });
+
+        use_event_emitter(cx).emit(Command::Undo);
+        assert_eq!(
+            *received.borrow(),
+            [
+                ("first", Command::Undo),
+                ("second", Command::Undo),
+                ("first", Command::Clear),
+                ("second", Command::Clear),
+            ]
+        );
+    })
+    .dispose()
+}
+
+#[test]
+fn handlers_do_not_track_signals() {
+    create_scope(create_runtime(), |cx| {
+        provide_event_bus::<Command>(cx);
+        let (count, set_count) = create_signal(cx, 0);
+        on_event(cx, move |_: &Command| _ = count.get());
+
+        let runs = Rc::new(RefCell::new(0));
+        let emits = create_memo(cx, {
+            let runs = Rc::clone(&runs);
+            let emitter = use_event_emitter(cx);
+            move |_| {
+                *runs.borrow_mut() += 1;
+                emitter.emit(Command::Clear);
+            }
+        });
+        emits.get();
+        assert_eq!(*runs.borrow(), 1);
+
+        set_count.set(1);
+        emits.get();
+        assert_eq!(*runs.borrow(), 1);
+    })
+    .dispose()
+}
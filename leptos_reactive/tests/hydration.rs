@@ -0,0 +1,87 @@
+#![cfg(all(target_arch = "wasm32", feature = "hydrate"))]
+
+use futures::{FutureExt, StreamExt};
+use leptos_reactive::{
+    create_resource_with_initial_value, create_runtime, run_scope, Resource, Scope,
+};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type Loaded = Resource<(), String>;
+
+// a component whose resources are already loaded on the server, where one of them is only
+// created on the server
+fn component(cx: Scope, server: bool) -> (Loaded, Loaded) {
+    let loaded = move |value: &str| server.then(|| value.to_string());
+    cx.with_next_context(|| {
+        let first = create_resource_with_initial_value(
+            cx,
+            || (),
+            |_| async { "first (browser)".to_string() },
+            loaded("first"),
+        );
+        if server {
+            _ = create_resource_with_initial_value(
+                cx,
+                || (),
+                |_| async { "server only (browser)".to_string() },
+                loaded("server only"),
+            );
+        }
+        let last = create_resource_with_initial_value(
+            cx,
+            || (),
+            |_| async { "last (browser)".to_string() },
+            loaded("last"),
+        );
+        (first, last)
+    })
+}
+
+fn hydrate<T: 'static>(f: impl FnOnce(Scope) -> T + 'static) -> T {
+    run_scope(create_runtime(), move |cx| {
+        let root = web_sys::window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .create_element("div")
+            .unwrap();
+        cx.start_hydration(&root);
+        let res = f(cx);
+        cx.end_hydration();
+        res
+    })
+}
+
+#[wasm_bindgen_test]
+fn resources_only_created_on_the_server_are_skipped() {
+    // the data the server sends, under the keys it derives for each resource
+    let resolved = hydrate(|cx| {
+        component(cx, true);
+        let keys = cx.resource_keys();
+        let resolved = js_sys::Map::new();
+        let mut resolvers = cx.serialization_resolvers();
+        while let Some(Some((id, json))) = resolvers.next().now_or_never() {
+            resolved.set(&JsValue::from_str(&keys[&id]), &JsValue::from_str(&json));
+        }
+        assert_eq!(resolved.size(), 3);
+        resolved
+    });
+    js_sys::Reflect::set(
+        &web_sys::window().unwrap(),
+        &JsValue::from_str("__LEPTOS_RESOLVED_RESOURCES"),
+        &resolved,
+    )
+    .unwrap();
+
+    let values = hydrate(|cx| {
+        let (first, last) = component(cx, false);
+        (first.read(), last.read())
+    });
+    assert_eq!(
+        values,
+        (Some("first".to_string()), Some("last".to_string()))
+    );
+}
@@ -0,0 +1,148 @@
+use leptos_reactive::{create_local_resource, create_runtime, create_scope, create_signal};
+
+// without `ssr`, so that effects run and `create_effect_after` returns a real ID
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn lookup_finds_owners_and_counts() {
+    use leptos_reactive::{create_effect_after, create_memo};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let ((effect, double), child) = cx.run_child_scope(|cx| {
+            let double = create_memo(cx, move |_| a.get() * 2);
+            let effect = create_effect_after(cx, [], move |_| a.get() + double.get());
+            (effect, double)
+        });
+
+        let signal = cx.lookup_signal(a.id()).unwrap();
+        assert_eq!(signal.id, a.id());
+        assert_eq!(signal.owner, Some(cx.id()));
+        // the effect, and the memo’s own effect
+        assert_eq!(signal.subscribers, 2);
+        assert_eq!(cx.lookup_signal(set_a.id()), Some(signal));
+
+        let info = cx.lookup_effect(effect).unwrap();
+        assert_eq!(info.sources, 2);
+        assert_eq!(info.subscribers, 0);
+        let owner = info.owner.unwrap();
+        assert_ne!(owner, cx.id());
+
+        // the memo is owned by the scope it was created in
+        let memo = cx.lookup_signal(double.id()).unwrap();
+        assert_eq!(memo.owner, Some(owner));
+        assert_eq!(memo.subscribers, 1);
+
+        child.dispose();
+        assert_eq!(cx.lookup_effect(effect), None);
+        assert_eq!(cx.lookup_signal(double.id()), None);
+        assert!(cx.lookup_signal(a.id()).is_some());
+    })
+    .dispose()
+}
+
+// with the test runtime, so that the resource's future is spawned on its executor in every mode
+#[cfg(feature = "testing")]
+#[test]
+fn lookup_finds_resources() {
+    use leptos_reactive::testing::TestRuntime;
+
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let resource = create_local_resource(cx, || (), |_| async { 1 });
+    let info = cx.lookup_resource(resource.id()).unwrap();
+    assert_eq!(info.owner, Some(cx.id()));
+}
+
+#[test]
+fn ids_display_in_a_short_form() {
+    create_scope(create_runtime(), |cx| {
+        let (a, _) = create_signal(cx, 0);
+        assert!(a.id().to_string().starts_with("signal#"));
+        assert!(cx.id().to_string().starts_with("scope#"));
+        // the same form, however the ID is looked at
+        assert_eq!(
+            leptos_reactive::NodeId::from(a.id()).to_string(),
+            a.id().to_string()
+        );
+    })
+    .dispose()
+}
+
+#[cfg(feature = "trace")]
+#[test]
+fn trace_records_where_and_what() {
+    create_scope(create_runtime(), |cx| {
+        let line = line!() + 1;
+        let (a, _) = create_signal(cx, "a".to_string());
+        cx.set_debug_name(a.id(), "name");
+
+        let info = cx.lookup_signal(a.id()).unwrap();
+        let location = info.location.unwrap();
+        assert_eq!(location.file(), file!());
+        assert_eq!(location.line(), line);
+        assert_eq!(info.type_name, Some(std::any::type_name::<String>()));
+        assert_eq!(info.name.as_deref(), Some("name"));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "trace"))]
+#[test]
+fn nothing_is_recorded_without_trace() {
+    create_scope(create_runtime(), |cx| {
+        let (a, _) = create_signal(cx, 0);
+        cx.set_debug_name(a.id(), "name");
+
+        let info = cx.lookup_signal(a.id()).unwrap();
+        assert_eq!(info.location, None);
+        assert_eq!(info.type_name, None);
+        assert_eq!(info.name, None);
+    })
+    .dispose()
+}
+
+#[cfg(feature = "trace-signals")]
+#[test]
+fn signal_writes_are_logged() {
+    use std::sync::Mutex;
+
+    static LOGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct Logger;
+
+    impl log::Log for Logger {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            LOGS.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    _ = log::set_logger(&Logger);
+    log::set_max_level(log::LevelFilter::Info);
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let (b, set_b) = create_signal(cx, "b");
+        cx.trace_values(a);
+
+        let line = line!() + 1;
+        set_a.set(2);
+        set_b.set("c");
+
+        let logs = LOGS.lock().unwrap();
+        let location = format!("{}:{line}", file!());
+        assert!(logs.iter().any(|log| log
+            .starts_with(&format!("[trace-signals] {} set at {location}", a.id()))
+            && log.ends_with(": 1 -> 2")));
+        assert!(logs.iter().any(
+            |log| log.starts_with(&format!("[trace-signals] {}", b.id()))
+                && log.ends_with("(Signal<&str>)")
+        ));
+    })
+    .dispose()
+}
@@ -71,7 +71,8 @@ fn memo_runs_only_when_inputs_change() {
             }
         });
 
-        assert_eq!(call_count.get(), 1);
+        // memos are lazy, so nothing has run yet
+        assert_eq!(call_count.get(), 0);
 
         // here we access the value a bunch of times
         assert_eq!(c(), 0);
@@ -90,3 +91,152 @@ fn memo_runs_only_when_inputs_change() {
     })
     .dispose()
 }
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_that_is_never_read_never_runs() {
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let call_count = Rc::new(Cell::new(0));
+        let (a, set_a) = create_signal(cx, 0);
+        let _unread = create_memo(cx, {
+            let call_count = call_count.clone();
+            move |_| {
+                call_count.set(call_count.get() + 1);
+                a() * 2
+            }
+        });
+
+        set_a(1);
+        set_a(2);
+        assert_eq!(call_count.get(), 0);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn diamond_updates_each_node_once() {
+    use leptos_reactive::create_isomorphic_effect;
+    use std::{cell::RefCell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let (a, set_a) = create_signal(cx, 1);
+        let b = create_memo(cx, {
+            let log = log.clone();
+            move |_| {
+                log.borrow_mut().push("b");
+                a() * 2
+            }
+        });
+        let c = create_memo(cx, {
+            let log = log.clone();
+            move |_| {
+                log.borrow_mut().push("c");
+                a() * 3
+            }
+        });
+        let d = create_memo(cx, {
+            let log = log.clone();
+            move |_| {
+                log.borrow_mut().push("d");
+                b() + c()
+            }
+        });
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        create_isomorphic_effect(cx, {
+            let log = log.clone();
+            let seen = seen.clone();
+            move |_| {
+                log.borrow_mut().push("effect");
+                seen.borrow_mut().push(d());
+            }
+        });
+
+        log.borrow_mut().sort();
+        assert_eq!(*log.borrow(), ["b", "c", "d", "effect"]);
+        log.borrow_mut().clear();
+
+        set_a(2);
+        log.borrow_mut().sort();
+        assert_eq!(*log.borrow(), ["b", "c", "d", "effect"]);
+
+        // the effect never saw `d` computed from a mix of old and new values
+        assert_eq!(*seen.borrow(), [5, 10]);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn unchanged_memo_stops_propagation_after_lazy_recompute() {
+    use leptos_reactive::create_isomorphic_effect;
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let is_even = create_memo(cx, move |_| a() % 2 == 0);
+        let downstream_runs = Rc::new(Cell::new(0));
+        let label = create_memo(cx, {
+            let downstream_runs = downstream_runs.clone();
+            move |_| {
+                downstream_runs.set(downstream_runs.get() + 1);
+                if is_even() {
+                    "even"
+                } else {
+                    "odd"
+                }
+            }
+        });
+        let effect_runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let effect_runs = effect_runs.clone();
+            move |_| {
+                label();
+                effect_runs.set(effect_runs.get() + 1);
+            }
+        });
+        assert_eq!((downstream_runs.get(), effect_runs.get()), (1, 1));
+
+        // `is_even` recomputes, but its value is the same, so nothing downstream runs
+        set_a(3);
+        assert_eq!((downstream_runs.get(), effect_runs.get()), (1, 1));
+
+        set_a(4);
+        assert_eq!(label(), "even");
+        assert_eq!((downstream_runs.get(), effect_runs.get()), (2, 2));
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn seeded_memo_folds_values() {
+    use leptos_reactive::{create_memo_seeded, UntrackedGettableSignal};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (value, set_value) = create_signal(cx, 1);
+        let runs = Rc::new(Cell::new(0));
+        let history = create_memo_seeded(cx, vec![0], {
+            let runs = Rc::clone(&runs);
+            move |prev: &Vec<i32>| {
+                runs.set(runs.get() + 1);
+                let mut next = prev.clone();
+                next.push(value.get());
+                next
+            }
+        });
+
+        // the seed is only passed in once the memo is first read
+        assert_eq!(runs.get(), 0);
+        assert_eq!(history.get_untracked(), vec![0, 1]);
+        set_value.set(2);
+        set_value.set(3);
+        assert_eq!(history.get_untracked(), vec![0, 1, 3]);
+        assert_eq!(runs.get(), 2);
+    })
+    .dispose()
+}
@@ -0,0 +1,150 @@
+use leptos_reactive::{
+    create_isomorphic_effect, create_reducer, create_reducer_with_history, create_runtime,
+    create_scope,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Action {
+    Increment,
+    Decrement,
+    Add(i32),
+}
+
+fn counter(count: &i32, action: Action) -> i32 {
+    match action {
+        Action::Increment => count + 1,
+        Action::Decrement => count - 1,
+        Action::Add(n) => count + n,
+    }
+}
+
+#[test]
+fn counter_with_undo_and_redo() {
+    create_scope(create_runtime(), |cx| {
+        let (count, dispatcher) = create_reducer_with_history(cx, 0, counter, 10);
+        let (can_undo, can_redo) = (dispatcher.can_undo(), dispatcher.can_redo());
+        assert!(!can_undo.get());
+        assert!(!can_redo.get());
+
+        for action in [
+            Action::Increment,
+            Action::Add(10),
+            Action::Decrement,
+            Action::Add(5),
+            Action::Increment,
+        ] {
+            dispatcher.dispatch(action);
+        }
+        assert_eq!(count.get(), 16);
+        assert!(can_undo.get());
+
+        dispatcher.undo();
+        dispatcher.undo();
+        assert_eq!(count.get(), 10);
+        assert!(can_redo.get());
+
+        dispatcher.redo();
+        assert_eq!(count.get(), 15);
+
+        // a new action can't be redone past
+        dispatcher.dispatch(Action::Add(100));
+        assert_eq!(count.get(), 115);
+        assert!(!can_redo.get());
+        dispatcher.redo();
+        assert_eq!(count.get(), 115);
+
+        for _ in 0..5 {
+            dispatcher.undo();
+        }
+        assert_eq!(count.get(), 0);
+        assert!(!can_undo.get());
+        dispatcher.undo();
+        assert_eq!(count.get(), 0);
+    })
+    .dispose()
+}
+
+#[test]
+fn history_is_bounded() {
+    create_scope(create_runtime(), |cx| {
+        let (count, dispatcher) = create_reducer_with_history(cx, 0, counter, 2);
+        for _ in 0..5 {
+            dispatcher.dispatch(Action::Increment);
+        }
+
+        for _ in 0..5 {
+            dispatcher.undo();
+        }
+        assert_eq!(count.get(), 3);
+        assert!(!dispatcher.can_undo().get());
+    })
+    .dispose()
+}
+
+#[test]
+fn middleware_observes_each_action() {
+    create_scope(create_runtime(), |cx| {
+        let (count, dispatcher) = create_reducer(cx, 0, counter);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        dispatcher.with_middleware({
+            let seen = Rc::clone(&seen);
+            move |count: &i32, action: &Action| seen.borrow_mut().push((*count, action.clone()))
+        });
+
+        dispatcher.dispatch(Action::Add(2));
+        dispatcher.dispatch(Action::Decrement);
+        assert_eq!(count.get(), 1);
+        assert_eq!(
+            *seen.borrow(),
+            [(0, Action::Add(2)), (2, Action::Decrement)]
+        );
+    })
+    .dispose()
+}
+
+#[test]
+fn dispatching_from_an_effect_is_queued() {
+    create_scope(create_runtime(), |cx| {
+        let reducing = Rc::new(Cell::new(false));
+        let (count, dispatcher) = create_reducer(cx, 0, {
+            let reducing = Rc::clone(&reducing);
+            move |count: &i32, action| {
+                assert!(!reducing.replace(true), "the reducer was re-entered");
+                let next = counter(count, action);
+                reducing.set(false);
+                next
+            }
+        });
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        dispatcher.with_middleware({
+            let seen = Rc::clone(&seen);
+            move |count: &i32, action: &Action| seen.borrow_mut().push((*count, action.clone()))
+        });
+
+        // rounds odd counts up to the next multiple of 10
+        create_isomorphic_effect(cx, move |_| {
+            let count = count.get();
+            if count % 2 == 1 {
+                dispatcher.dispatch(Action::Add(10 - count % 10));
+            }
+        });
+
+        dispatcher.dispatch(Action::Add(3));
+        dispatcher.dispatch(Action::Increment);
+        assert_eq!(count.get(), 11 + 9);
+        assert_eq!(
+            *seen.borrow(),
+            [
+                (0, Action::Add(3)),
+                (3, Action::Add(7)),
+                (10, Action::Increment),
+                (11, Action::Add(9)),
+            ]
+        );
+    })
+    .dispose()
+}
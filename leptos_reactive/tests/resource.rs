@@ -0,0 +1,146 @@
+#![cfg(not(any(feature = "csr", feature = "hydrate", feature = "ssr")))]
+
+use futures::StreamExt;
+use leptos_reactive::{
+    create_patchable_resource, create_resource, create_runtime, create_scope, create_signal,
+    run_scope, JsonPatch,
+};
+
+#[test]
+fn patchable_resource_sends_value_then_patches() {
+    create_scope(create_runtime(), |cx| {
+        let (count, set_count) = create_signal(cx, 2);
+        let _resource = create_patchable_resource(
+            cx,
+            move || count.get(),
+            |count| async move { (0..count).map(|n| format!("item {n}")).collect::<Vec<_>>() },
+        );
+
+        let resource_id = cx.all_resources()[0];
+        let mut resolvers = cx.serialization_resolvers();
+        let mut patches = cx.serialization_patches();
+
+        let (id, json) = futures::executor::block_on(resolvers.next()).unwrap();
+        assert_eq!(id, resource_id);
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value, serde_json::json!(["item 0", "item 1"]));
+
+        set_count.set(3);
+        let (id, patch) = futures::executor::block_on(patches.next()).unwrap();
+        assert_eq!(id, resource_id);
+        let patch: JsonPatch = serde_json::from_str(&patch).unwrap();
+        assert_eq!(patch.0.len(), 1);
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, serde_json::json!(["item 0", "item 1", "item 2"]));
+    })
+    .dispose()
+}
+
+#[test]
+fn resources_are_listed_by_island() {
+    create_scope(create_runtime(), |cx| {
+        let _outside = create_resource(cx, || (), |_| async { 0 });
+        cx.with_island("widget", || {
+            _ = create_resource(cx, || (), |_| async { 1 });
+            _ = create_resource(cx, || (), |_| async { 2 });
+        });
+        // the same island can be rendered in more than one part
+        cx.with_island("widget", || {
+            _ = create_resource(cx, || (), |_| async { 3 });
+        });
+
+        let all = cx.all_resources();
+        let islands = cx.island_resources();
+        assert_eq!(islands.len(), 1);
+        assert_eq!(islands["widget"], all[1..]);
+    })
+    .dispose()
+}
+
+#[test]
+fn resource_keys_dont_depend_on_other_resources() {
+    // the keys of the first and last resources, with or without a resource in between them
+    fn keys(server_only: bool) -> (String, String) {
+        run_scope(create_runtime(), move |cx| {
+            // as if they were created in a component inside the root element
+            _ = cx.next_hydration_key();
+            cx.with_next_context(|| {
+                _ = create_resource(cx, || (), |_| async { 1 });
+                if server_only {
+                    _ = create_resource(cx, || (), |_| async { 2 });
+                }
+                _ = create_resource(cx, || (), |_| async { 3 });
+            });
+
+            let all = cx.all_resources();
+            let keys = cx.resource_keys();
+            (keys[&all[0]].clone(), keys[all.last().unwrap()].clone())
+        })
+    }
+
+    let (first, last) = keys(false);
+    assert_ne!(first, last);
+    assert_eq!(keys(true), (first, last));
+}
+
+thread_local! {
+    static PRELOADED: std::cell::RefCell<Vec<String>> = Default::default();
+}
+
+#[test]
+fn preload_hrefs_are_passed_on_in_order() {
+    use leptos_reactive::{create_resource_with_options, on_resource_preload, ResourceOptions};
+
+    create_scope(create_runtime(), |cx| {
+        let avatar = |id: u32| async move { format!("/avatars/{id}.png") };
+        _ = create_resource_with_options(
+            cx,
+            || 1,
+            avatar,
+            ResourceOptions::new().preload_href(|| "/avatars/1.png".to_string()),
+        );
+
+        // declared before there was anything to pass them to
+        on_resource_preload(cx, |_, href| PRELOADED.with(|p| p.borrow_mut().push(href)));
+        _ = create_resource_with_options(
+            cx,
+            || 2,
+            avatar,
+            ResourceOptions::new().preload_href(|| "/avatars/2.png".to_string()),
+        );
+        _ = create_resource(cx, || 3, avatar);
+
+        PRELOADED.with(|p| assert_eq!(*p.borrow(), ["/avatars/1.png", "/avatars/2.png"]));
+    })
+    .dispose()
+}
+
+#[test]
+fn equal_sources_dont_refetch() {
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let fetches = Rc::new(Cell::new(0));
+        let (query, set_query) = create_signal(cx, "rust");
+        let (page, set_page) = create_signal(cx, 1);
+        let resource = create_resource(cx, move || (query.get(), page.get()), {
+            let fetches = Rc::clone(&fetches);
+            move |(query, page)| {
+                fetches.set(fetches.get() + 1);
+                async move { format!("{query} {page}") }
+            }
+        });
+        assert_eq!(fetches.get(), 1);
+
+        // the source reruns, but returns an equal tuple each time
+        set_query.set("rust");
+        set_page.set(1);
+        set_query.update(|_| {});
+        assert_eq!(fetches.get(), 1);
+
+        set_page.set(2);
+        assert_eq!(fetches.get(), 2);
+        assert_eq!(resource.source().get(), ("rust", 2));
+    })
+    .dispose()
+}
@@ -0,0 +1,157 @@
+// in the browser, there is only ever one runtime, and it is never disposed
+#![cfg(not(any(feature = "csr", feature = "hydrate")))]
+
+use leptos_reactive::{create_runtime, on_cleanup, raw_scope_and_disposer};
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn disposing_runtime_runs_cleanups() {
+    let runtime = create_runtime();
+    let (cx, _disposer) = raw_scope_and_disposer(runtime);
+    let (child, _child_disposer) = cx.run_child_scope(|cx| cx);
+
+    let cleaned_up = Rc::new(Cell::new(0));
+    for cx in [cx, child] {
+        on_cleanup(cx, {
+            let cleaned_up = Rc::clone(&cleaned_up);
+            move || cleaned_up.set(cleaned_up.get() + 1)
+        });
+    }
+
+    runtime.dispose();
+    assert_eq!(cleaned_up.get(), 2);
+}
+
+#[test]
+fn scope_outliving_runtime_does_not_panic() {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    runtime.dispose();
+
+    // e.g., an async task that finishes after a server render has been torn down
+    let cleaned_up = Rc::new(Cell::new(false));
+    on_cleanup(cx, {
+        let cleaned_up = Rc::clone(&cleaned_up);
+        move || cleaned_up.set(true)
+    });
+    assert!(cleaned_up.get());
+    assert_eq!(cx.untrack(|| 1), 1);
+    assert!(cx.all_resources().is_empty());
+    disposer.dispose();
+}
+
+#[test]
+fn disposers_know_when_their_scope_is_gone() {
+    let runtime = create_runtime();
+    let (cx, disposer) = raw_scope_and_disposer(runtime);
+    let (_, child_disposer) = cx.run_child_scope(|_| ());
+    assert!(!child_disposer.is_disposed());
+
+    // disposed of along with its parent, so disposing of it again does nothing
+    disposer.dispose();
+    assert!(child_disposer.is_disposed());
+    child_disposer.dispose();
+
+    let (_, disposer) = raw_scope_and_disposer(runtime);
+    runtime.dispose();
+    assert!(disposer.is_disposed());
+}
+
+#[test]
+fn disposed_runtime_reads_return_none() {
+    use leptos_reactive::{create_memo, create_rw_signal, create_signal, use_context};
+
+    let runtime = create_runtime();
+    let (cx, _disposer) = raw_scope_and_disposer(runtime);
+    let (count, _) = create_signal(cx, 1);
+    let double = create_memo(cx, move |_| count.get() * 2);
+    let name = create_rw_signal(cx, "Ada");
+    runtime.dispose();
+
+    assert_eq!(count.try_get(), None);
+    assert_eq!(count.try_with(|n| *n), None);
+    assert_eq!(double.try_get(), None);
+    assert_eq!(name.try_get(), None);
+    assert_eq!(use_context::<u8>(cx), None);
+    assert_eq!(cx.lookup_signal(count.id()), None);
+}
+
+#[test]
+fn disposed_runtime_drops_writes() {
+    use leptos_reactive::{create_signal, create_snapshot_group, provide_context};
+
+    let runtime = create_runtime();
+    let (cx, _disposer) = raw_scope_and_disposer(runtime);
+    let (count, set_count) = create_signal(cx, 1);
+    let group = create_snapshot_group(cx);
+    group.track(count);
+    let snapshot = group.snapshot();
+    runtime.dispose();
+
+    set_count.set(2);
+    assert_eq!(set_count.update_returning(|n| *n), None);
+    assert!(!set_count.update_if(|_| true));
+    provide_context(cx, 0u8);
+    group.restore(&snapshot);
+    assert_eq!(group.diff(&group.snapshot(), &snapshot), vec![count.id()]);
+}
+
+#[test]
+fn disposed_runtime_refuses_new_effects_and_resources() {
+    use leptos_reactive::{create_isomorphic_effect, create_local_resource, Disposable};
+
+    struct Handle(Rc<Cell<bool>>);
+
+    impl Disposable for Handle {
+        fn dispose(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    let runtime = create_runtime();
+    let (cx, _disposer) = raw_scope_and_disposer(runtime);
+    runtime.dispose();
+
+    let ran = Rc::new(Cell::new(false));
+    create_isomorphic_effect(cx, {
+        let ran = Rc::clone(&ran);
+        move |_| ran.set(true)
+    });
+    assert!(!ran.get());
+
+    let resource = create_local_resource(cx, || (), |_| async { 1 });
+    assert_eq!(resource.read(), None);
+    resource.refetch();
+
+    // nothing would ever dispose of it later
+    let disposed = Rc::new(Cell::new(false));
+    cx.register_disposable(Handle(Rc::clone(&disposed)));
+    assert!(disposed.get());
+}
+
+#[test]
+fn try_with_runtime_only_runs_while_the_runtime_exists() {
+    use leptos_reactive::{try_with_runtime, RuntimeDisposed};
+
+    let runtime = create_runtime();
+    assert_eq!(try_with_runtime(runtime, || 1), Ok(1));
+    runtime.dispose();
+    let ran = Cell::new(false);
+    assert_eq!(
+        try_with_runtime(runtime, || ran.set(true)),
+        Err(RuntimeDisposed)
+    );
+    assert!(!ran.get());
+}
+
+#[test]
+#[should_panic]
+fn disposed_runtime_panics_on_get() {
+    use leptos_reactive::create_signal;
+
+    let runtime = create_runtime();
+    let (cx, _disposer) = raw_scope_and_disposer(runtime);
+    let (count, _) = create_signal(cx, 1);
+    runtime.dispose();
+    count.get();
+}
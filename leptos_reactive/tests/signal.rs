@@ -28,3 +28,53 @@ fn derived_signals() {
     })
     .dispose()
 }
+
+#[test]
+fn update_returning_notifies_once() {
+    use leptos_reactive::create_isomorphic_effect;
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let (todos, set_todos) = create_signal(cx, vec![1, 2, 3]);
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                todos.with(|_| ());
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        let popped = set_todos.update_returning(|todos| todos.pop());
+        assert_eq!(popped, Some(Some(3)));
+        assert_eq!(todos.with(|todos| todos.len()), 2);
+        assert_eq!(runs.get(), 2);
+    })
+    .dispose()
+}
+
+#[test]
+fn set_if_changed_skips_equal_values() {
+    use leptos_reactive::{create_isomorphic_effect, create_rw_signal};
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let count = create_rw_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                count.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        assert!(!count.set_if_changed(0));
+        assert!(!count.write_only().set_if_changed(0));
+        assert_eq!(runs.get(), 1);
+
+        assert!(count.set_if_changed(1));
+        assert_eq!(runs.get(), 2);
+    })
+    .dispose()
+}
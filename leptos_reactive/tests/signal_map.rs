@@ -0,0 +1,89 @@
+#[cfg(not(feature = "stable"))]
+use leptos_reactive::{
+    create_isomorphic_effect, create_runtime, create_scope, create_signal_map, Scope, Signal,
+};
+#[cfg(not(feature = "stable"))]
+use std::{cell::Cell, rc::Rc};
+
+// counts how many times an effect that reads the signal has run
+#[cfg(not(feature = "stable"))]
+fn count_runs<T: Clone>(cx: Scope, signal: &Signal<T>) -> Rc<Cell<usize>> {
+    let runs = Rc::new(Cell::new(0));
+    create_isomorphic_effect(cx, {
+        let runs = Rc::clone(&runs);
+        let signal = signal.clone();
+        move |_| {
+            signal.get();
+            runs.set(runs.get() + 1);
+        }
+    });
+    runs
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn writes_only_notify_their_key() {
+    create_scope(create_runtime(), |cx| {
+        let map = create_signal_map::<u32, String>(cx);
+        map.insert(1, "a".to_string());
+        map.insert(2, "b".to_string());
+
+        let one = map.get_signal(&1);
+        let two = map.get_signal(&2);
+        let one_runs = count_runs(cx, &one);
+        let two_runs = count_runs(cx, &two);
+
+        assert!(map.update(&1, |value| value.push('!')));
+        assert_eq!(one.get(), Some("a!".to_string()));
+        assert_eq!(one_runs.get(), 2);
+        assert_eq!(two_runs.get(), 1);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn removed_keys_are_none_and_reject_writes() {
+    create_scope(create_runtime(), |cx| {
+        let map = create_signal_map::<u32, u32>(cx);
+        map.insert(1, 10);
+        let one = map.get_signal(&1);
+        let runs = count_runs(cx, &one);
+
+        assert_eq!(map.remove(&1), Some(10));
+        assert_eq!(runs.get(), 2);
+        assert_eq!(one.get(), None);
+
+        assert!(!map.update(&1, |value| *value += 1));
+        assert_eq!(one.get(), None);
+        assert_eq!(runs.get(), 2);
+
+        // the key can be tracked again once it's reinserted
+        map.insert(1, 20);
+        assert_eq!(one.get(), Some(20));
+        assert_eq!(runs.get(), 3);
+    })
+    .dispose()
+}
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn keys_only_notify_on_insert_and_remove() {
+    create_scope(create_runtime(), |cx| {
+        let map = create_signal_map::<u32, u32>(cx);
+        let keys = map.keys();
+        let runs = count_runs(cx, &keys);
+
+        map.extend([(1, 1), (2, 2), (3, 3)]);
+        assert_eq!(runs.get(), 2);
+
+        map.update(&1, |value| *value += 1);
+        map.insert(2, 20);
+        assert_eq!(runs.get(), 2);
+
+        map.clear();
+        assert_eq!(runs.get(), 3);
+        assert!(keys.get().is_empty());
+    })
+    .dispose()
+}
@@ -0,0 +1,62 @@
+use leptos_reactive::{create_runtime, create_scope, create_signal_vec, VecDelta, VecDeltas};
+
+// everything the subscriber hasn't seen yet
+fn received(deltas: &VecDeltas<u32>) -> Vec<VecDelta<u32>> {
+    let mut received = Vec::new();
+    deltas.for_each_delta(|delta| received.push(delta));
+    received
+}
+
+#[test]
+fn deltas_coalesce_between_reads() {
+    create_scope(create_runtime(), |cx| {
+        let list = create_signal_vec::<u32>(cx);
+        let deltas = list.subscribe();
+        assert!(received(&deltas).is_empty());
+
+        // a value that comes and goes before the deltas are read is never passed on
+        list.push(1);
+        list.push(2);
+        list.set(1, 20);
+        list.remove(1);
+        assert_eq!(
+            received(&deltas),
+            vec![VecDelta::Insert { index: 0, value: 1 }]
+        );
+
+        // nothing from before a clear is passed on
+        list.push(3);
+        list.clear();
+        list.push(4);
+        assert_eq!(
+            received(&deltas),
+            vec![VecDelta::Clear, VecDelta::Insert { index: 0, value: 4 }]
+        );
+
+        let deltas = list.subscribe();
+        list.set(0, 5);
+        list.set(0, 6);
+        assert_eq!(
+            received(&deltas),
+            vec![VecDelta::Insert { index: 0, value: 6 }]
+        );
+    })
+    .dispose()
+}
+
+#[test]
+fn whole_vec_fallback_stays_consistent() {
+    create_scope(create_runtime(), |cx| {
+        let list = create_signal_vec::<u32>(cx);
+        let whole = list.as_signal();
+
+        list.splice(.., [1, 2, 3, 4]);
+        list.remove(0);
+        list.insert(2, 7);
+        list.swap(0, 1);
+        assert_eq!(whole.get(), vec![3, 2, 7, 4]);
+        assert_eq!(list.with(|values| values.to_vec()), whole.get());
+        assert_eq!(list.len(), 4);
+    })
+    .dispose()
+}
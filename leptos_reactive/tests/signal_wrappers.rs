@@ -0,0 +1,120 @@
+use std::{cell::Cell, rc::Rc};
+
+use leptos_reactive::{
+    create_isomorphic_effect, create_memo, create_runtime, create_scope, create_signal, Memo,
+    Scope, Signal, UntrackedGettableSignal,
+};
+
+// stands in for a component with a `Signal<i32>` prop, which renders the value doubled
+fn doubled(cx: Scope, value: impl Into<Signal<i32>>) -> Memo<i32> {
+    let value = value.into();
+    create_memo(cx, move |_| value.get() * 2)
+}
+
+#[test]
+fn signal_props_accept_signals_memos_and_derived_signals() {
+    create_scope(create_runtime(), |cx| {
+        let (count, set_count) = create_signal(cx, 1);
+        let plus_one = create_memo(cx, move |_| count.get() + 1);
+
+        let from_signal = doubled(cx, count);
+        let from_memo = doubled(cx, plus_one);
+        let from_closure = doubled(cx, Signal::derive(cx, move || count.get() + 2));
+        assert_eq!(from_signal.get(), 2);
+        assert_eq!(from_memo.get(), 4);
+        assert_eq!(from_closure.get(), 6);
+
+        set_count.set(10);
+        assert_eq!(from_signal.get(), 20);
+        assert_eq!(from_memo.get(), 22);
+        assert_eq!(from_closure.get(), 24);
+    })
+    .dispose()
+}
+
+#[test]
+fn derived_signals_track_what_they_read_each_time() {
+    create_scope(create_runtime(), |cx| {
+        let (use_a, set_use_a) = create_signal(cx, true);
+        let (a, set_a) = create_signal(cx, 1);
+        let (b, set_b) = create_signal(cx, 2);
+        let derived = Signal::derive(cx, move || if use_a.get() { a.get() } else { b.get() });
+
+        let runs = Rc::new(Cell::new(0));
+        let memo = create_memo(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                derived.get()
+            }
+        });
+        assert_eq!(memo.get(), 1);
+        assert_eq!(runs.get(), 1);
+
+        // `b` isn't read yet, so the memo doesn't depend on it
+        set_b.set(3);
+        assert_eq!(memo.get(), 1);
+        assert_eq!(runs.get(), 1);
+
+        set_use_a.set(false);
+        assert_eq!(memo.get(), 3);
+        set_b.set(4);
+        assert_eq!(memo.get(), 4);
+        let after_switch = runs.get();
+
+        // ... and now it doesn't depend on `a`
+        set_a.set(5);
+        assert_eq!(memo.get(), 4);
+        assert_eq!(runs.get(), after_switch);
+    })
+    .dispose()
+}
+
+#[test]
+fn untracked_reads_of_derived_signals_dont_subscribe() {
+    create_scope(create_runtime(), |cx| {
+        let (count, set_count) = create_signal(cx, 0);
+        let derived = Signal::derive(cx, move || count.get());
+
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                derived.get_untracked();
+                derived.with_untracked(|_| {});
+            }
+        });
+
+        set_count.set(1);
+        assert_eq!(runs.get(), 1);
+    })
+    .dispose()
+}
+
+#[cfg(feature = "stable")]
+#[test]
+fn closures_convert_into_signals() {
+    create_scope(create_runtime(), |cx| {
+        let (count, set_count) = create_signal(cx, 1);
+        let from_closure = doubled(cx, move || count.get() + 2);
+        assert_eq!(from_closure.get(), 6);
+
+        set_count.set(10);
+        assert_eq!(from_closure.get(), 24);
+
+        // without a scope, untracked reads still don't subscribe
+        let derived: Signal<i32> = (move || count.get()).into();
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                runs.set(runs.get() + 1);
+                derived.get_untracked();
+            }
+        });
+        set_count.set(11);
+        assert_eq!(runs.get(), 1);
+    })
+    .dispose()
+}
@@ -0,0 +1,91 @@
+use std::{cell::Cell, rc::Rc};
+
+use leptos_reactive::{
+    create_isomorphic_effect, create_runtime, create_rw_signal, create_scope, create_signal,
+    create_snapshot_group,
+};
+
+#[test]
+fn restore_writes_back_every_value_at_once() {
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let (b, set_b) = create_signal(cx, "b".to_string());
+        let c = create_rw_signal(cx, vec![1, 2]);
+
+        let group = create_snapshot_group(cx);
+        group.track(a);
+        group.track(set_b);
+        group.track(c);
+        let snapshot = group.snapshot();
+
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                _ = (a.get(), b.get(), c.get());
+                runs.set(runs.get() + 1);
+            }
+        });
+        set_a.set(2);
+        set_b.set("changed".to_string());
+        c.update(|c| c.push(3));
+        assert_eq!(runs.get(), 4);
+
+        group.restore(&snapshot);
+        assert_eq!(runs.get(), 5);
+        assert_eq!(a.get(), 1);
+        assert_eq!(b.get(), "b");
+        assert_eq!(c.get(), vec![1, 2]);
+    })
+    .dispose()
+}
+
+#[test]
+fn snapshots_outlive_disposed_scopes() {
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let group = create_snapshot_group(cx);
+        group.track(a);
+
+        let (child, disposer) = cx.run_child_scope(|cx| create_signal(cx, 10));
+        group.track(child.0);
+        let snapshot = group.snapshot();
+
+        // an unrelated scope
+        cx.run_child_scope(|cx| create_signal(cx, 0)).1.dispose();
+        // and the one that owned one of the signals, which is skipped
+        disposer.dispose();
+
+        set_a.set(2);
+        group.restore(&snapshot);
+        assert_eq!(a.get(), 1);
+    })
+    .dispose()
+}
+
+#[test]
+fn diff_lists_the_signals_that_changed() {
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let (b, _) = create_signal(cx, 2);
+        // compared by length only
+        let (c, set_c) = create_signal(cx, "abc".to_string());
+
+        let group = create_snapshot_group(cx);
+        group.track(a);
+        group.track(b);
+        group.track_with(c, |x: &String, y: &String| x.len() == y.len());
+
+        let first = group.snapshot();
+        assert_eq!(group.diff(&first, &group.snapshot()), vec![]);
+
+        set_a.set(3);
+        set_c.set("xyz".to_string());
+        let second = group.snapshot();
+        assert_eq!(group.diff(&first, &second), vec![a.id()]);
+
+        set_c.set("abcd".to_string());
+        assert_eq!(group.diff(&second, &group.snapshot()), vec![c.id()]);
+    })
+    .dispose()
+}
@@ -0,0 +1,234 @@
+use std::{cell::RefCell, rc::Rc};
+
+use leptos_reactive::{
+    create_isomorphic_effect, create_runtime, create_scope, create_signal, on_cleanup, ReadSignal,
+};
+
+// stands in for something like `window().add_event_listener(...)`
+type Listeners = Rc<RefCell<Vec<&'static str>>>;
+
+#[test]
+fn strict_mode_shows_up_missing_cleanups() {
+    let runtime = create_runtime();
+    runtime.set_strict_mode(true);
+    let listeners = Listeners::default();
+    create_scope(runtime, {
+        let listeners = Rc::clone(&listeners);
+        move |cx| {
+            // never removes the listener it added before
+            create_isomorphic_effect(cx, move |_| listeners.borrow_mut().push("resize"));
+        }
+    })
+    .dispose();
+    assert_eq!(*listeners.borrow(), ["resize", "resize"]);
+}
+
+// adds a listener each time the effect runs, but only cleans up the one it added last
+fn leaky_listener(strict: bool) -> Listeners {
+    let runtime = create_runtime();
+    runtime.set_strict_mode(strict);
+    let listeners = Listeners::default();
+    create_scope(runtime, {
+        let listeners = Rc::clone(&listeners);
+        move |cx| {
+            let last = Rc::new(RefCell::new(None));
+            create_isomorphic_effect(cx, {
+                let listeners = Rc::clone(&listeners);
+                let last = Rc::clone(&last);
+                move |_| {
+                    listeners.borrow_mut().push("scroll");
+                    *last.borrow_mut() = Some(listeners.borrow().len() - 1);
+                }
+            });
+            on_cleanup(cx, move || {
+                if let Some(idx) = last.borrow_mut().take() {
+                    listeners.borrow_mut().remove(idx);
+                }
+            });
+        }
+    })
+    .dispose();
+    listeners
+}
+
+#[test]
+fn strict_mode_catches_a_leaked_cleanup() {
+    // looks fine when the effect only runs once...
+    assert!(leaky_listener(false).borrow().is_empty());
+    // ...but running it again leaves a listener behind once the scope is disposed
+    assert_eq!(*leaky_listener(true).borrow(), ["scroll"]);
+}
+
+#[test]
+fn strict_mode_cleans_up_before_running_an_effect_again() {
+    let runtime = create_runtime();
+    runtime.set_strict_mode(true);
+    let listeners = Listeners::default();
+    let log = Rc::new(RefCell::new(Vec::new()));
+    create_scope(runtime, {
+        let (listeners, log) = (Rc::clone(&listeners), Rc::clone(&log));
+        move |cx| {
+            create_isomorphic_effect(cx, move |_| {
+                listeners.borrow_mut().push("resize");
+                log.borrow_mut().push("add");
+                let (listeners, log) = (Rc::clone(&listeners), Rc::clone(&log));
+                on_cleanup(cx, move || {
+                    listeners.borrow_mut().retain(|l| *l != "resize");
+                    log.borrow_mut().push("remove");
+                });
+            });
+        }
+    })
+    .dispose();
+    assert_eq!(*log.borrow(), ["add", "remove", "add", "remove"]);
+    assert!(listeners.borrow().is_empty());
+}
+
+// `create_effect` doesn't run on the server
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn strict_mode_runs_the_returned_cleanup_before_running_an_effect_again() {
+    use leptos_reactive::{create_effect_with_cleanup, PrevEffect};
+
+    let runtime = create_runtime();
+    runtime.set_strict_mode(true);
+    let log = Rc::new(RefCell::new(Vec::new()));
+    create_scope(runtime, {
+        let log = Rc::clone(&log);
+        move |cx| {
+            create_effect_with_cleanup(cx, move |_: PrevEffect<()>| {
+                log.borrow_mut().push("subscribe");
+                let log = Rc::clone(&log);
+                ((), move || log.borrow_mut().push("unsubscribe"))
+            });
+        }
+    })
+    .dispose();
+    assert_eq!(
+        *log.borrow(),
+        ["subscribe", "unsubscribe", "subscribe", "unsubscribe"]
+    );
+}
+
+// a component that logs when it is mounted and unmounted, and returns the signal it creates
+fn logged_component(
+    cx: leptos_reactive::Scope,
+    log: &Rc<RefCell<Vec<&'static str>>>,
+) -> ReadSignal<i32> {
+    log.borrow_mut().push("mount");
+    let (count, _) = create_signal(cx, 0);
+    _ = cx.child_scope({
+        let log = Rc::clone(log);
+        move |cx| on_cleanup(cx, move || log.borrow_mut().push("unmount child"))
+    });
+    let log = Rc::clone(log);
+    on_cleanup(cx, move || log.borrow_mut().push("unmount"));
+    count
+}
+
+// components are only mounted twice in the browser
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn strict_mode_mounts_components_twice() {
+    let runtime = create_runtime();
+    runtime.set_strict_mode(true);
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let counts = Rc::new(RefCell::new(Vec::new()));
+    let disposer = create_scope(runtime, {
+        let (log, counts) = (Rc::clone(&log), Rc::clone(&counts));
+        move |cx| {
+            let count = cx.run_component(|| {
+                let count = logged_component(cx, &log);
+                counts.borrow_mut().push(count);
+                count
+            });
+            assert_eq!(count.try_get(), Some(0));
+        }
+    });
+    // the first mount was disposed of before the second
+    assert_eq!(
+        *log.borrow(),
+        ["mount", "unmount child", "unmount", "mount"]
+    );
+    let first = counts.borrow()[0];
+    assert_eq!(first.try_get(), None);
+
+    disposer.dispose();
+    assert_eq!(
+        *log.borrow(),
+        [
+            "mount",
+            "unmount child",
+            "unmount",
+            "mount",
+            "unmount child",
+            "unmount"
+        ]
+    );
+}
+
+#[test]
+fn components_mount_once_without_strict_mode() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    create_scope(create_runtime(), {
+        let log = Rc::clone(&log);
+        move |cx| {
+            cx.run_component(|| logged_component(cx, &log));
+        }
+    })
+    .dispose();
+    assert_eq!(*log.borrow(), ["mount", "unmount child", "unmount"]);
+}
+
+#[cfg(not(feature = "ssr"))]
+#[test]
+fn idempotent_components_are_unaffected() {
+    let runtime = create_runtime();
+    runtime.set_strict_mode(true);
+    let title = Rc::new(RefCell::new(String::new()));
+    create_scope(runtime, {
+        let title = Rc::clone(&title);
+        move |cx| {
+            cx.run_component(|| {
+                let previous = std::mem::replace(&mut *title.borrow_mut(), "Inbox".to_string());
+                let title = Rc::clone(&title);
+                on_cleanup(cx, move || *title.borrow_mut() = previous);
+            });
+            assert_eq!(*title.borrow(), "Inbox");
+        }
+    })
+    .dispose();
+    assert_eq!(*title.borrow(), "");
+}
+
+#[test]
+fn idempotent_effects_are_unaffected() {
+    let runtime = create_runtime();
+    runtime.set_strict_mode(true);
+    let title = Rc::new(RefCell::new(String::new()));
+    create_scope(runtime, {
+        let title = Rc::clone(&title);
+        move |cx| {
+            let (count, set_count) = create_signal(cx, 0);
+            create_isomorphic_effect(cx, move |_| {
+                *title.borrow_mut() = format!("{} unread", count.get());
+            });
+            set_count.set(1);
+        }
+    })
+    .dispose();
+    assert_eq!(*title.borrow(), "1 unread");
+}
+
+#[test]
+fn effects_run_once_without_strict_mode() {
+    let listeners = Listeners::default();
+    create_scope(create_runtime(), {
+        let listeners = Rc::clone(&listeners);
+        move |cx| {
+            create_isomorphic_effect(cx, move |_| listeners.borrow_mut().push("resize"));
+        }
+    })
+    .dispose();
+    assert_eq!(*listeners.borrow(), ["resize"]);
+}
@@ -0,0 +1,139 @@
+use leptos_reactive::{create_runtime, create_scope, RenderingMode};
+
+#[test]
+fn rendering_mode_matches_features() {
+    create_scope(create_runtime(), |cx| {
+        let expected = if cfg!(feature = "hydrate") {
+            RenderingMode::Hydrate
+        } else if cfg!(feature = "csr") {
+            RenderingMode::Csr
+        } else {
+            RenderingMode::Ssr
+        };
+        assert_eq!(cx.rendering_mode(), expected);
+    })
+    .dispose()
+}
+
+#[test]
+fn fragment_keys_are_unique_without_hydration_keys() {
+    create_scope(create_runtime(), |cx| {
+        let first = cx.current_fragment_key();
+        let second = cx.current_fragment_key();
+        cx.child_scope(move |cx| {
+            let third = cx.current_fragment_key();
+            assert_ne!(first, second);
+            assert_ne!(first, third);
+            assert_ne!(second, third);
+        });
+    })
+    .dispose()
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn suspense_registers_fragment_without_shared_context() {
+    use leptos_reactive::SuspenseContext;
+
+    create_scope(create_runtime(), |cx| {
+        let context = SuspenseContext::new(cx);
+        context.increment();
+
+        let key = cx.current_fragment_key();
+        cx.register_suspense(context, &key, || "<p>loaded</p>".to_string());
+        let (fragment_key, fragment) = cx.pending_fragments().pop().unwrap();
+        assert_eq!(fragment_key, key);
+        assert!(cx.pending_fragments().is_empty());
+
+        context.decrement();
+        assert_eq!(
+            tokio_test::block_on(fragment),
+            Some("<p>loaded</p>".to_string())
+        );
+    })
+    .dispose()
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn suspense_skips_fragment_if_scope_is_disposed_first() {
+    use leptos_reactive::SuspenseContext;
+    use std::{cell::Cell, rc::Rc};
+
+    create_scope(create_runtime(), |cx| {
+        let called = Rc::new(Cell::new(false));
+        let ((context, key), disposer) = cx.run_child_scope({
+            let called = Rc::clone(&called);
+            move |cx| {
+                let context = SuspenseContext::new(cx);
+                context.increment();
+
+                let key = cx.current_fragment_key();
+                cx.register_suspense(context, &key, move || {
+                    called.set(true);
+                    "<p>stale</p>".to_string()
+                });
+                (context, key)
+            }
+        });
+        let (fragment_key, fragment) = cx.pending_fragments().pop().unwrap();
+        assert_eq!(fragment_key, key);
+
+        disposer.dispose();
+        context.decrement();
+        assert_eq!(tokio_test::block_on(fragment), None);
+        assert!(!called.get());
+    })
+    .dispose()
+}
+
+#[cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+mod browser {
+    use super::*;
+    use leptos_reactive::SuspenseContext;
+    use std::{cell::Cell, rc::Rc};
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    // resolves once every microtask queued before it has run
+    async fn tick() {
+        let promise = js_sys::Promise::resolve(&wasm_bindgen::JsValue::UNDEFINED);
+        _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+    }
+
+    #[wasm_bindgen_test]
+    async fn suspense_calls_resolver_once_resources_resolve() {
+        let calls = Rc::new(Cell::new(0));
+        let (context, key) = {
+            let calls = Rc::clone(&calls);
+            leptos_reactive::run_scope_undisposed(create_runtime(), move |cx| {
+                let context = SuspenseContext::new(cx);
+                context.increment();
+
+                let key = cx.current_fragment_key();
+                cx.register_suspense(context, &key, move || {
+                    calls.set(calls.get() + 1);
+                    String::new()
+                });
+                (context, key)
+            })
+            .0
+        };
+
+        tick().await;
+        assert_eq!(calls.get(), 0);
+
+        context.decrement();
+        tick().await;
+        tick().await;
+        assert_eq!(calls.get(), 1);
+
+        context.increment();
+        context.decrement();
+        tick().await;
+        tick().await;
+        assert_eq!(calls.get(), 1);
+        assert!(!key.is_empty());
+    }
+}
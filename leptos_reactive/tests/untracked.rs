@@ -77,3 +77,38 @@ fn untracked_get_doesnt_trigger_effect() {
     })
     .dispose()
 }
+
+#[test]
+fn batch_untrack_writes_silently() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 1);
+        let (b, set_b) = create_signal(cx, 2);
+
+        let runs = Rc::new(Cell::new(0));
+        let sum = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let (runs, sum) = (Rc::clone(&runs), Rc::clone(&sum));
+            move |_| {
+                runs.set(runs.get() + 1);
+                sum.set(a.get() + b.get());
+            }
+        });
+        assert_eq!((runs.get(), sum.get()), (1, 3));
+
+        let total = cx.batch_untrack(|| {
+            set_a.set(10);
+            set_b.set(20);
+            a.get() + b.get()
+        });
+        assert_eq!(total, 30);
+        assert_eq!((runs.get(), sum.get()), (1, 3));
+
+        // writes notify as usual afterwards, and see the silent ones
+        set_a.set(100);
+        assert_eq!((runs.get(), sum.get()), (2, 120));
+    })
+    .dispose()
+}
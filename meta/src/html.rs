@@ -0,0 +1,367 @@
+use crate::{use_head, TextProp};
+use cfg_if::cfg_if;
+use leptos::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use typed_builder::TypedBuilder;
+
+/// Manages the attributes set on the `<html>` or `<body>` element by [Html] or [Body] components.
+///
+/// When several components set the same attribute, the one that was created last wins. Classes
+/// are combined instead: the element has every class that any of the components has added.
+#[derive(Clone, Default)]
+pub struct AttributesContext {
+    next_id: Rc<Cell<usize>>,
+    // the current values set by each component, in the order they were created
+    bindings: Rc<RefCell<Vec<(usize, Values)>>>,
+    // the values most recently applied to the element, so they can be removed again later
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    applied: Rc<RefCell<Merged>>,
+}
+
+impl std::fmt::Debug for AttributesContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AttributesContext")
+            .field("bindings", &self.bindings.borrow().len())
+            .finish()
+    }
+}
+
+// the values set by one component
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Values {
+    classes: Vec<String>,
+    attrs: Vec<(&'static str, Option<String>)>,
+}
+
+// the values set by all components together
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Merged {
+    classes: Vec<String>,
+    attrs: Vec<(&'static str, String)>,
+}
+
+impl AttributesContext {
+    fn merged(&self) -> Merged {
+        let mut merged = Merged::default();
+        for (_, values) in self.bindings.borrow().iter() {
+            for class in &values.classes {
+                if !merged.classes.contains(class) {
+                    merged.classes.push(class.clone());
+                }
+            }
+            for (name, value) in &values.attrs {
+                let Some(value) = value else { continue };
+                match merged.attrs.iter_mut().find(|(n, _)| n == name) {
+                    Some((_, prev)) => *prev = value.clone(),
+                    None => merged.attrs.push((name, value.clone())),
+                }
+            }
+        }
+        merged
+    }
+
+    fn insert(&self) -> usize {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.bindings.borrow_mut().push((id, Values::default()));
+        id
+    }
+
+    fn set(&self, id: usize, values: Values) {
+        if let Some((_, prev)) = self
+            .bindings
+            .borrow_mut()
+            .iter_mut()
+            .find(|(i, _)| *i == id)
+        {
+            *prev = values;
+        }
+    }
+
+    fn remove(&self, id: usize) {
+        self.bindings.borrow_mut().retain(|(i, _)| *i != id);
+    }
+
+    /// Converts the attributes into a string that can be added to the element’s opening tag,
+    /// with a space before each attribute, e.g., ` lang="en" class="dark"`.
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    pub(crate) fn as_string(&self) -> String {
+        use leptos::leptos_dom::escape_attr;
+
+        let merged = self.merged();
+        let mut attrs = String::new();
+        for (name, value) in &merged.attrs {
+            attrs.push_str(&format!(r#" {name}="{}""#, escape_attr(value)));
+        }
+        if !merged.classes.is_empty() {
+            let classes = merged.classes.join(" ");
+            attrs.push_str(&format!(r#" class="{}""#, escape_attr(&classes)));
+        }
+        attrs
+    }
+
+    // updates the element to match the current values, leaving alone any classes and attributes
+    // that weren't set by these components
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn apply(&self, el: &web_sys::Element) {
+        let merged = self.merged();
+        let mut applied = self.applied.borrow_mut();
+
+        let class_list = el.class_list();
+        for class in &applied.classes {
+            if !merged.classes.contains(class) {
+                _ = class_list.remove_1(class);
+            }
+        }
+        for class in &merged.classes {
+            if !applied.classes.contains(class) {
+                _ = class_list.add_1(class);
+            }
+        }
+
+        for (name, _) in &applied.attrs {
+            if !merged.attrs.iter().any(|(n, _)| n == name) {
+                _ = el.remove_attribute(name);
+            }
+        }
+        for attr @ (name, value) in &merged.attrs {
+            if !applied.attrs.contains(attr) {
+                _ = el.set_attribute(name, value);
+            }
+        }
+
+        *applied = merged;
+    }
+}
+
+/// Arbitrary attributes to set with an [Html] or [Body] component. Each value can be static or
+/// reactive, like the value of an attribute in the `view` macro.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_meta::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (theme, set_theme) = create_signal(cx, "light".to_string());
+/// let attributes = Attributes::new()
+///   .attr("data-theme", move || theme.get())
+///   .attr("data-version", 2);
+/// # });
+/// ```
+#[derive(Default)]
+pub struct Attributes(Vec<(&'static str, AttributeFn)>);
+
+type AttributeFn = Box<dyn FnOnce(Scope) -> Attribute>;
+
+impl Attributes {
+    /// Creates an empty list of attributes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an attribute. A value of `None` or `false` means that the attribute isn’t set.
+    pub fn attr(mut self, name: &'static str, value: impl IntoAttribute + 'static) -> Self {
+        self.0
+            .push((name, Box::new(move |cx| value.into_attribute(cx))));
+        self
+    }
+}
+
+impl std::fmt::Debug for Attributes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|(name, _)| name))
+            .finish()
+    }
+}
+
+/// Properties for the [Html] component.
+#[derive(TypedBuilder)]
+pub struct HtmlProps {
+    /// Classes to add to the `<html>` element, separated by spaces.
+    #[builder(default, setter(strip_option, into))]
+    class: Option<TextProp>,
+    /// The `lang` attribute of the `<html>` element.
+    #[builder(default, setter(strip_option, into))]
+    lang: Option<TextProp>,
+    /// The `dir` attribute of the `<html>` element.
+    #[builder(default, setter(strip_option, into))]
+    dir: Option<TextProp>,
+    /// Any other attributes to set on the `<html>` element.
+    #[builder(default, setter(strip_option))]
+    attributes: Option<Attributes>,
+}
+
+/// A component to set attributes on the document’s `<html>` element, which components can’t
+/// otherwise reach, e.g., to switch to a dark theme by adding a class.
+///
+/// Classes are added alongside any that are already there, and only the classes and attributes
+/// set by `<Html/>` components are removed again when they change or the component’s [Scope]
+/// is disposed. If several `<Html/>` components set the same attribute, the one that was
+/// created last wins.
+///
+/// When server rendering, the attributes should be added to the `<html>` tag of the document
+/// using [MetaContext::html_attributes](crate::MetaContext::html_attributes).
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp(cx: Scope) -> Element {
+///   provide_context(cx, MetaContext::new());
+///   let (dark, set_dark) = create_signal(cx, false);
+///
+///   view! { cx,
+///     <main>
+///       <Html lang="en" class=move || if dark() { "dark".to_string() } else { String::new() }/>
+///       <button on:click=move |_| set_dark.update(|dark| *dark = !*dark)>"Toggle theme"</button>
+///     </main>
+///   }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Html(cx: Scope, props: HtmlProps) {
+    let HtmlProps {
+        class,
+        lang,
+        dir,
+        attributes,
+    } = props;
+    let meta = use_head(cx);
+    bind(cx, meta.html, class, lang, dir, attributes, || {
+        document().document_element()
+    });
+}
+
+/// Properties for the [Body] component.
+#[derive(TypedBuilder)]
+pub struct BodyProps {
+    /// Classes to add to the `<body>` element, separated by spaces.
+    #[builder(default, setter(strip_option, into))]
+    class: Option<TextProp>,
+    /// The `lang` attribute of the `<body>` element.
+    #[builder(default, setter(strip_option, into))]
+    lang: Option<TextProp>,
+    /// The `dir` attribute of the `<body>` element.
+    #[builder(default, setter(strip_option, into))]
+    dir: Option<TextProp>,
+    /// Any other attributes to set on the `<body>` element.
+    #[builder(default, setter(strip_option))]
+    attributes: Option<Attributes>,
+}
+
+/// A component to set attributes on the document’s `<body>` element, which works just like [Html].
+///
+/// When server rendering, the attributes should be added to the `<body>` tag of the document
+/// using [MetaContext::body_attributes](crate::MetaContext::body_attributes).
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn ThemeSwitcher(cx: Scope) -> Element {
+///   let (theme, set_theme) = create_signal(cx, "light".to_string());
+///
+///   view! { cx,
+///     <div>
+///       <Body attributes=Attributes::new().attr("data-theme", move || theme.get())/>
+///       <button on:click=move |_| set_theme("dark".to_string())>"Dark"</button>
+///     </div>
+///   }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Body(cx: Scope, props: BodyProps) {
+    let BodyProps {
+        class,
+        lang,
+        dir,
+        attributes,
+    } = props;
+    let meta = use_head(cx);
+    bind(cx, meta.body, class, lang, dir, attributes, || {
+        document().body().map(Into::into)
+    });
+}
+
+fn bind(
+    cx: Scope,
+    context: AttributesContext,
+    class: Option<TextProp>,
+    lang: Option<TextProp>,
+    dir: Option<TextProp>,
+    attributes: Option<Attributes>,
+    element: fn() -> Option<web_sys::Element>,
+) {
+    let attributes = attributes
+        .map(|attributes| attributes.0)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, value)| (name, value(cx)))
+        .collect::<Vec<_>>();
+    let values = move || Values {
+        classes: class
+            .as_ref()
+            .map(|class| (class.0)().split_whitespace().map(String::from).collect())
+            .unwrap_or_default(),
+        attrs: [("lang", &lang), ("dir", &dir)]
+            .into_iter()
+            .filter_map(|(name, value)| value.as_ref().map(|value| (name, Some((value.0)()))))
+            .chain(
+                attributes
+                    .iter()
+                    .map(|(name, value)| (*name, attribute_value(value))),
+            )
+            .collect(),
+    };
+
+    let id = context.insert();
+
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let el = element();
+            let apply = {
+                let context = context.clone();
+                move || {
+                    if let Some(el) = &el {
+                        context.apply(el);
+                    }
+                }
+            };
+
+            create_render_effect(cx, {
+                let context = context.clone();
+                let apply = apply.clone();
+                move |_| {
+                    context.set(id, values());
+                    apply();
+                }
+            });
+
+            on_cleanup(cx, move || {
+                context.remove(id);
+                apply();
+            });
+        } else {
+            _ = element;
+            context.set(id, values());
+            on_cleanup(cx, move || context.remove(id));
+        }
+    }
+}
+
+// the current value of an attribute, or `None` if it shouldn't be set
+fn attribute_value(attr: &Attribute) -> Option<String> {
+    match attr {
+        Attribute::String(value) => Some(value.clone()),
+        Attribute::Fn(f) => attribute_value(&f()),
+        Attribute::Option(value) => value.clone(),
+        Attribute::Composite(composite) => Some(composite.value()),
+        Attribute::Bool(true) => Some(String::new()),
+        Attribute::Bool(false) => None,
+    }
+}
@@ -7,7 +7,9 @@
 //!
 //! Document metadata is updated automatically when running in the browser. For server-side
 //! rendering, after the component tree is rendered to HTML, [MetaContext::dehydrate] can generate
-//! HTML that should be injected into the `<head>` of the HTML document being rendered.
+//! HTML that should be injected into the `<head>` of the HTML document being rendered, and
+//! [MetaContext::html_attributes] and [MetaContext::body_attributes] give the attributes that
+//! [Html] and [Body] components have set on the `<html>` and `<body>` tags.
 //!
 //! ```
 //! use leptos::*;
@@ -40,8 +42,12 @@ use std::fmt::Debug;
 
 use leptos::{leptos_dom::debug_warn, *};
 
+mod html;
+mod preload;
 mod stylesheet;
 mod title;
+pub use html::*;
+pub use preload::*;
 pub use stylesheet::*;
 pub use title::*;
 
@@ -52,7 +58,11 @@ pub use title::*;
 #[derive(Debug, Clone, Default)]
 pub struct MetaContext {
     pub(crate) title: TitleContext,
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    pub(crate) preloads: PreloadContext,
     pub(crate) stylesheets: StylesheetContext,
+    pub(crate) html: AttributesContext,
+    pub(crate) body: AttributesContext,
 }
 
 /// Returns the current [MetaContext].
@@ -64,6 +74,13 @@ pub struct MetaContext {
 /// call `use_head()` but a single [MetaContext] has not been provided at the application root.
 /// The best practice is always to `provide_context(cx, MetaContext::new())` early in the application.
 pub fn use_head(cx: Scope) -> MetaContext {
+    // adds the assets declared by resources with `ResourceOptions::preload_href`, including
+    // any declared before the app first used the head
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    on_resource_preload(cx, |cx, href| {
+        use_head(cx).preloads.add(PreloadLink::for_resource(href))
+    });
+
     match use_context::<MetaContext>(cx) {
         None => {
             debug_warn!("use_head() is being called with a MetaContext being provided. We'll automatically create and provide one, but if this is being called in a child route it will cause bugs. To be safe, you should provide_context(cx, MetaContext::new()) somewhere in the root of the app.");
@@ -104,7 +121,7 @@ impl MetaContext {
     ///   };
     ///
     ///   // `app` contains only the body content w/ hydration stuff, not the meta tags
-    ///   assert_eq!(app, r#"<main data-hk="0-0"><!--#--><!--/--><!--#--><!--/--><p>Some text</p></main>"#);
+    ///   assert_eq!(app, r#"<main data-hk="0-0"><!--leptos-0--><!--/leptos-0--><!--leptos-1--><!--/leptos-1--><p>Some text</p></main>"#);
     ///   // `MetaContext::dehydrate()` gives you HTML that should be in the `<head>`
     ///   assert_eq!(use_head(cx).dehydrate(), r#"<title>my title</title><link rel="stylesheet" href="/style.css">"#)
     /// });
@@ -120,11 +137,61 @@ impl MetaContext {
             tags.push_str("</title>");
         }
 
+        // Preloaded assets, before anything that might compete with them
+        tags.push_str(&self.preloads.as_string());
+
         // Stylesheets
         tags.push_str(&self.stylesheets.as_string());
 
         tags
     }
+
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    /// Converts the attributes set by [Html] components into a string that should be added to the
+    /// document’s `<html>` tag, with a space before each attribute.
+    ///
+    /// Like [dehydrate](MetaContext::dehydrate), this should be called *after* the app’s component
+    /// tree has been rendered into HTML.
+    ///
+    /// ```
+    /// use leptos::*;
+    /// use leptos_meta::*;
+    ///
+    /// # #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+    /// run_scope(create_runtime(), |cx| {
+    ///   provide_context(cx, MetaContext::new());
+    ///
+    ///   let app = view! { cx,
+    ///     <main>
+    ///       <Html lang="en" class="dark"/>
+    ///       <Body attributes=Attributes::new().attr("data-theme", "dark")/>
+    ///     </main>
+    ///   };
+    ///
+    ///   let meta = use_head(cx);
+    ///   let html = format!(
+    ///     "<html{}><head>{}</head><body{}>{app}</body></html>",
+    ///     meta.html_attributes(),
+    ///     meta.dehydrate(),
+    ///     meta.body_attributes()
+    ///   );
+    ///   assert!(html.starts_with(r#"<html lang="en" class="dark"><head></head><body data-theme="dark">"#));
+    /// });
+    /// # }
+    /// ```
+    pub fn html_attributes(&self) -> String {
+        self.html.as_string()
+    }
+
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    /// Converts the attributes set by [Body] components into a string that should be added to the
+    /// document’s `<body>` tag, with a space before each attribute.
+    ///
+    /// Like [dehydrate](MetaContext::dehydrate), this should be called *after* the app’s component
+    /// tree has been rendered into HTML.
+    pub fn body_attributes(&self) -> String {
+        self.body.as_string()
+    }
 }
 
 /// Describes a value that is either a static or a reactive string, i.e.,
@@ -0,0 +1,291 @@
+use crate::TextProp;
+use cfg_if::cfg_if;
+use leptos::*;
+use std::{cell::RefCell, rc::Rc};
+use typed_builder::TypedBuilder;
+
+/// Manages the assets preloaded by [Preload] components, and by resources created with
+/// [ResourceOptions::preload_href](leptos::ResourceOptions::preload_href).
+#[derive(Clone, Default, Debug)]
+pub struct PreloadContext {
+    // in the order they were declared, with at most one link for each `href`
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    links: Rc<RefCell<Vec<PreloadLink>>>,
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PreloadLink {
+    href: String,
+    as_: PreloadAs,
+    crossorigin: Option<String>,
+    media: Option<String>,
+    fetchpriority: Option<FetchPriority>,
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+impl PreloadLink {
+    // the link for an asset declared by a resource, which only gives its URL
+    pub(crate) fn for_resource(href: String) -> Self {
+        let as_ = PreloadAs::from_href(&href);
+        Self {
+            href,
+            as_,
+            // fonts are always fetched in CORS mode, so they're only preloaded with this
+            crossorigin: (as_ == PreloadAs::Font).then(|| "anonymous".to_string()),
+            media: None,
+            fetchpriority: None,
+        }
+    }
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+impl PreloadContext {
+    // adds a link, unless one was already declared for the same `href`, in which case that
+    // one is raised to the higher of their priorities
+    pub(crate) fn add(&self, link: PreloadLink) {
+        let mut links = self.links.borrow_mut();
+        match links.iter_mut().find(|prev| prev.href == link.href) {
+            Some(prev) => prev.fetchpriority = prev.fetchpriority.max(link.fetchpriority),
+            None => links.push(link),
+        }
+    }
+
+    /// Converts the preloaded assets into an HTML string that can be injected into the `<head>`.
+    pub fn as_string(&self) -> String {
+        use leptos::leptos_dom::escape_attr;
+
+        self.links
+            .borrow()
+            .iter()
+            .map(|link| {
+                let mut tag = format!(
+                    r#"<link rel="preload" href="{}" as="{}""#,
+                    escape_attr(&link.href),
+                    link.as_.as_str()
+                );
+                if let Some(crossorigin) = &link.crossorigin {
+                    tag.push_str(&format!(r#" crossorigin="{}""#, escape_attr(crossorigin)));
+                }
+                if let Some(media) = &link.media {
+                    tag.push_str(&format!(r#" media="{}""#, escape_attr(media)));
+                }
+                if let Some(fetchpriority) = link.fetchpriority {
+                    tag.push_str(&format!(r#" fetchpriority="{}""#, fetchpriority.as_str()));
+                }
+                tag.push('>');
+                tag
+            })
+            .collect()
+    }
+}
+
+/// The kind of asset a [Preload] component loads, i.e., the `as` attribute of its `<link>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PreloadAs {
+    /// An image, e.g., the largest image above the fold.
+    Image,
+    /// A font, which is always loaded in CORS mode, so it should also have a `crossorigin`.
+    Font,
+    /// A script.
+    Script,
+    /// A stylesheet.
+    Style,
+    /// Data that will be loaded with `fetch()`.
+    Fetch,
+}
+
+impl PreloadAs {
+    /// The value of the `as` attribute.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PreloadAs::Image => "image",
+            PreloadAs::Font => "font",
+            PreloadAs::Script => "script",
+            PreloadAs::Style => "style",
+            PreloadAs::Fetch => "fetch",
+        }
+    }
+
+    // guesses the kind of asset from the extension of its URL
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    fn from_href(href: &str) -> Self {
+        let path = href.split(['?', '#']).next().unwrap_or_default();
+        let extension = path
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "avif" | "gif" | "ico" | "jpeg" | "jpg" | "png" | "svg" | "webp" => PreloadAs::Image,
+            "otf" | "ttf" | "woff" | "woff2" => PreloadAs::Font,
+            "js" | "mjs" => PreloadAs::Script,
+            "css" => PreloadAs::Style,
+            _ => PreloadAs::Fetch,
+        }
+    }
+}
+
+/// A hint to the browser about how soon it needs an asset, relative to others of the same kind:
+/// the `fetchpriority` attribute of a [Preload] component’s `<link>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FetchPriority {
+    /// Lower than the browser would otherwise give it.
+    Low,
+    /// The browser’s own priority.
+    Auto,
+    /// Higher than the browser would otherwise give it, e.g., for the LCP image.
+    High,
+}
+
+impl FetchPriority {
+    /// The value of the `fetchpriority` attribute.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FetchPriority::Low => "low",
+            FetchPriority::Auto => "auto",
+            FetchPriority::High => "high",
+        }
+    }
+
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn parse(value: &str) -> Self {
+        match value {
+            "low" => FetchPriority::Low,
+            "high" => FetchPriority::High,
+            _ => FetchPriority::Auto,
+        }
+    }
+}
+
+/// Properties for the [Preload] component.
+#[derive(TypedBuilder)]
+pub struct PreloadProps {
+    /// The URL of the asset.
+    #[builder(setter(into))]
+    href: TextProp,
+    /// The kind of asset.
+    as_: PreloadAs,
+    /// The `crossorigin` attribute of the link, e.g., `"anonymous"` for fonts.
+    #[builder(default, setter(strip_option, into))]
+    crossorigin: Option<String>,
+    /// A media query, so the asset is only preloaded if it matches, e.g., for responsive images.
+    #[builder(default, setter(strip_option, into))]
+    media: Option<String>,
+    /// How soon the asset is needed, relative to others of the same kind.
+    #[builder(default, setter(strip_option))]
+    fetchpriority: Option<FetchPriority>,
+}
+
+/// Tells the browser to start loading an asset early with a
+/// [`<link rel="preload">`](https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes/rel/preload),
+/// e.g., so that the largest image on the page starts loading before the app has hydrated.
+///
+/// When server rendering, the link is added to the HTML given by
+/// [MetaContext::dehydrate](crate::MetaContext::dehydrate), once for each `href`, in the order
+/// they were declared. In the browser, the link is added to the `<head>` unless there already
+/// is one for the same `href` (in which case its `fetchpriority` is raised to this one’s, if
+/// it is higher), and only a link that this component added is removed when its [Scope] is
+/// disposed.
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn Hero(cx: Scope) -> Element {
+///   view! { cx,
+///     <div>
+///       <Preload href="/hero.avif" as_=PreloadAs::Image fetchpriority=FetchPriority::High/>
+///       <img src="/hero.avif"/>
+///     </div>
+///   }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Preload(cx: Scope, props: PreloadProps) {
+    let PreloadProps {
+        href,
+        as_,
+        crossorigin,
+        media,
+        fetchpriority,
+    } = props;
+
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            // the link this component added, if it didn't find one already there
+            let own = Rc::new(RefCell::new(None::<web_sys::Element>));
+
+            create_render_effect(cx, {
+                let own = Rc::clone(&own);
+                move |_| {
+                    let href = (href.0)();
+                    if let Some(prev) = own.borrow_mut().take() {
+                        prev.remove();
+                    }
+                    match find_link(&href) {
+                        Some(el) => {
+                            let current = el
+                                .get_attribute("fetchpriority")
+                                .map(|value| FetchPriority::parse(&value))
+                                .unwrap_or(FetchPriority::Auto);
+                            if let Some(fetchpriority) = fetchpriority.filter(|p| *p > current) {
+                                _ = el.set_attribute("fetchpriority", fetchpriority.as_str());
+                            }
+                        }
+                        None => {
+                            let el = document().create_element("link").unwrap_throw();
+                            _ = el.set_attribute("rel", "preload");
+                            _ = el.set_attribute("href", &href);
+                            _ = el.set_attribute("as", as_.as_str());
+                            if let Some(crossorigin) = &crossorigin {
+                                _ = el.set_attribute("crossorigin", crossorigin);
+                            }
+                            if let Some(media) = &media {
+                                _ = el.set_attribute("media", media);
+                            }
+                            if let Some(fetchpriority) = fetchpriority {
+                                _ = el.set_attribute("fetchpriority", fetchpriority.as_str());
+                            }
+                            document()
+                                .query_selector("head")
+                                .unwrap_throw()
+                                .unwrap_throw()
+                                .append_child(&el)
+                                .unwrap_throw();
+                            *own.borrow_mut() = Some(el);
+                        }
+                    }
+                }
+            });
+
+            on_cleanup(cx, move || {
+                if let Some(el) = own.borrow_mut().take() {
+                    el.remove();
+                }
+            });
+        } else {
+            use crate::use_head;
+
+            use_head(cx).preloads.add(PreloadLink {
+                href: (href.0)(),
+                as_,
+                crossorigin,
+                media,
+                fetchpriority,
+            });
+        }
+    }
+}
+
+// the `<link rel="preload">` for `href` that is already in the document, if any
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn find_link(href: &str) -> Option<web_sys::Element> {
+    let links = document()
+        .query_selector_all(r#"head link[rel="preload"]"#)
+        .unwrap_throw();
+    (0..links.length())
+        .filter_map(|idx| links.item(idx))
+        .map(|link| link.unchecked_into::<web_sys::Element>())
+        .find(|link| link.get_attribute("href").as_deref() == Some(href))
+}
@@ -1,7 +1,10 @@
 use crate::{use_head, TextProp};
 use cfg_if::cfg_if;
 use leptos::*;
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 use typed_builder::TypedBuilder;
 
 /// Contains the current state of the document's `<title>`.
@@ -11,19 +14,38 @@ pub struct TitleContext {
     el: Rc<RefCell<Option<web_sys::HtmlTitleElement>>>,
     formatter: Rc<RefCell<Option<Formatter>>>,
     text: Rc<RefCell<Option<TextProp>>>,
+    next_id: Rc<Cell<usize>>,
+    // the titles set by `use_title`, in the order they were created
+    hooks: Rc<RefCell<Vec<(usize, ReadSignal<String>)>>>,
+    // `document.title` from before the first `use_title`, restored once none are left
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    original: Rc<RefCell<Option<String>>>,
 }
 
 impl TitleContext {
     /// Converts the title into a string that can be used as the text content of a `<title>` tag.
+    ///
+    /// A title set by [use_title] takes precedence over the `text` of any [Title] component.
     pub fn as_string(&self) -> Option<String> {
-        let title = self.text.borrow().as_ref().map(|f| (f.0)());
-        title.map(|title| {
-            if let Some(formatter) = &*self.formatter.borrow() {
-                (formatter.0)(title)
-            } else {
-                title
-            }
-        })
+        let hook = self.hooks.borrow().last().map(|(_, title)| *title);
+        let title = match hook {
+            Some(title) => title.try_with(Clone::clone),
+            None => self.text.borrow().as_ref().map(|f| (f.0)()),
+        };
+        title.map(|title| self.format(title))
+    }
+
+    fn format(&self, title: String) -> String {
+        if let Some(formatter) = &*self.formatter.borrow() {
+            (formatter.0)(title)
+        } else {
+            title
+        }
+    }
+
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn is_last_hook(&self, id: usize) -> bool {
+        self.hooks.borrow().last().map(|(last, _)| *last) == Some(id)
     }
 }
 
@@ -147,3 +169,76 @@ pub fn Title(cx: Scope, props: TitleProps) {
         }
     }
 }
+
+/// Sets the document’s title to the value of `title`, updating it whenever `title` changes, and
+/// restores the previous title once `cx` is disposed.
+///
+/// This is an alternative to the [Title] component for components that manage their own title
+/// in code. A `formatter` set by a [Title] component is applied to it as well, and it takes
+/// precedence over the `text` of any [Title] component.
+///
+/// If several components call `use_title` at once, the one that was created last (usually the
+/// innermost) wins. When it is disposed, the title set by the one before it takes over again.
+/// On the server, the title is included in [MetaContext::dehydrate](crate::MetaContext::dehydrate).
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn Inbox(cx: Scope) -> Element {
+///   let (unread, set_unread) = create_signal(cx, 3);
+///   let (title, set_title) = create_signal(cx, String::new());
+///   create_effect(cx, move |_| set_title(format!("Inbox ({})", unread())));
+///   use_title(cx, title);
+///
+///   view! { cx,
+///     <button on:click=move |_| set_unread.update(|n| *n = 0)>"Mark all as read"</button>
+///   }
+/// }
+/// ```
+pub fn use_title(cx: Scope, title: ReadSignal<String>) {
+    let meta = use_head(cx);
+    let context = meta.title;
+    let id = context.next_id.get();
+    context.next_id.set(id + 1);
+
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            if context.hooks.borrow().is_empty() {
+                *context.original.borrow_mut() = Some(document().title());
+            }
+            context.hooks.borrow_mut().push((id, title));
+
+            create_render_effect(cx, {
+                let context = context.clone();
+                move |_| {
+                    let title = title.get();
+                    if context.is_last_hook(id) {
+                        document().set_title(&context.format(title));
+                    }
+                }
+            });
+
+            on_cleanup(cx, move || {
+                let was_last = context.is_last_hook(id);
+                context.hooks.borrow_mut().retain(|(other, _)| *other != id);
+                let original = if context.hooks.borrow().is_empty() {
+                    context.original.borrow_mut().take()
+                } else {
+                    None
+                };
+                if was_last {
+                    if let Some(title) = context.as_string().or(original) {
+                        document().set_title(&title);
+                    }
+                }
+            });
+        } else {
+            context.hooks.borrow_mut().push((id, title));
+            on_cleanup(cx, move || {
+                context.hooks.borrow_mut().retain(|(other, _)| *other != id);
+            });
+        }
+    }
+}
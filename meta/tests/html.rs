@@ -0,0 +1,113 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use leptos::*;
+use leptos_meta::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn html() -> web_sys::Element {
+    document().document_element().unwrap()
+}
+
+#[wasm_bindgen_test]
+fn classes_toggle_reactively() {
+    html().class_list().add_1("set-elsewhere").unwrap();
+
+    run_scope(create_runtime(), |cx| {
+        provide_context(cx, MetaContext::new());
+        let (dark, set_dark) = create_signal(cx, false);
+
+        Html(
+            cx,
+            HtmlProps::builder()
+                .class(move || {
+                    if dark.get() {
+                        "dark".to_string()
+                    } else {
+                        "light".to_string()
+                    }
+                })
+                .build(),
+        );
+        assert_eq!(html().class_name(), "set-elsewhere light");
+
+        set_dark.set(true);
+        assert_eq!(html().class_name(), "set-elsewhere dark");
+
+        // a class that some other code added in the meantime isn't touched
+        html().class_list().add_1("also-elsewhere").unwrap();
+        set_dark.set(false);
+        assert_eq!(html().class_name(), "set-elsewhere also-elsewhere light");
+    });
+
+    html().set_class_name("");
+}
+
+#[wasm_bindgen_test]
+fn values_are_removed_when_disposed() {
+    html().set_attribute("lang", "en").unwrap();
+
+    run_scope(create_runtime(), |cx| {
+        provide_context(cx, MetaContext::new());
+
+        let disposer = cx.child_scope(|cx| {
+            Html(
+                cx,
+                HtmlProps::builder()
+                    .lang("fr")
+                    .class("print")
+                    .attributes(Attributes::new().attr("data-theme", "dark"))
+                    .build(),
+            );
+        });
+        Body(cx, BodyProps::builder().class("loaded").build());
+        assert_eq!(html().get_attribute("lang").as_deref(), Some("fr"));
+        assert_eq!(html().get_attribute("data-theme").as_deref(), Some("dark"));
+        assert!(html().class_list().contains("print"));
+
+        disposer.dispose();
+        // attributes the components set are removed, even if they were there before
+        assert_eq!(html().get_attribute("lang"), None);
+        assert_eq!(html().get_attribute("data-theme"), None);
+        assert!(!html().class_list().contains("print"));
+        assert!(document().body().unwrap().class_list().contains("loaded"));
+    });
+
+    document().body().unwrap().set_class_name("");
+}
+
+#[wasm_bindgen_test]
+fn later_instances_win() {
+    run_scope(create_runtime(), |cx| {
+        provide_context(cx, MetaContext::new());
+        let (dir, set_dir) = create_signal(cx, Some("ltr".to_string()));
+
+        Html(
+            cx,
+            HtmlProps::builder()
+                .attributes(Attributes::new().attr("dir", "rtl"))
+                .build(),
+        );
+        let disposer = cx.child_scope(|cx| {
+            Html(
+                cx,
+                HtmlProps::builder()
+                    .attributes(Attributes::new().attr("dir", move || dir.get()))
+                    .build(),
+            );
+        });
+        assert_eq!(html().get_attribute("dir").as_deref(), Some("ltr"));
+
+        // falls back to the earlier instance while the later one doesn't set a value
+        set_dir.set(None);
+        assert_eq!(html().get_attribute("dir").as_deref(), Some("rtl"));
+        set_dir.set(Some("auto".to_string()));
+        assert_eq!(html().get_attribute("dir").as_deref(), Some("auto"));
+
+        disposer.dispose();
+        assert_eq!(html().get_attribute("dir").as_deref(), Some("rtl"));
+    });
+
+    html().remove_attribute("dir").unwrap();
+}
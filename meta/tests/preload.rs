@@ -0,0 +1,85 @@
+#![cfg(all(target_arch = "wasm32", any(feature = "csr", feature = "hydrate")))]
+
+use leptos::*;
+use leptos_meta::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn preload_links() -> Vec<web_sys::Element> {
+    let links = document()
+        .query_selector_all(r#"head link[rel="preload"]"#)
+        .unwrap();
+    (0..links.length())
+        .filter_map(|idx| links.item(idx))
+        .map(|link| link.unchecked_into())
+        .collect()
+}
+
+fn preload(cx: Scope, href: &str, fetchpriority: Option<FetchPriority>) {
+    let props = PreloadProps::builder().href(href).as_(PreloadAs::Image);
+    match fetchpriority {
+        Some(fetchpriority) => Preload(cx, props.fetchpriority(fetchpriority).build()),
+        None => Preload(cx, props.build()),
+    }
+}
+
+#[wasm_bindgen_test]
+fn only_removes_its_own_link() {
+    // as the server rendered it
+    let rendered = document().create_element("link").unwrap();
+    rendered.set_attribute("rel", "preload").unwrap();
+    rendered.set_attribute("href", "/hero.avif").unwrap();
+    rendered.set_attribute("as", "image").unwrap();
+    document()
+        .query_selector("head")
+        .unwrap()
+        .unwrap()
+        .append_child(&rendered)
+        .unwrap();
+
+    run_scope(create_runtime(), |cx| {
+        let disposer = cx.child_scope(|cx| {
+            preload(cx, "/hero.avif", Some(FetchPriority::High));
+            preload(cx, "/logo.svg", None);
+        });
+
+        let links = preload_links();
+        assert_eq!(links.len(), 2);
+        // the existing link is used, with the higher priority
+        assert_eq!(links[0], rendered);
+        assert_eq!(
+            rendered.get_attribute("fetchpriority").as_deref(),
+            Some("high")
+        );
+        assert_eq!(links[1].get_attribute("href").as_deref(), Some("/logo.svg"));
+        assert_eq!(links[1].get_attribute("as").as_deref(), Some("image"));
+
+        disposer.dispose();
+        assert_eq!(preload_links(), [rendered.clone()]);
+    });
+
+    rendered.remove();
+}
+
+#[wasm_bindgen_test]
+fn follows_a_reactive_href() {
+    run_scope(create_runtime(), |cx| {
+        let (href, set_href) = create_signal(cx, "/a.png".to_string());
+        Preload(
+            cx,
+            PreloadProps::builder()
+                .href(move || href.get())
+                .as_(PreloadAs::Image)
+                .build(),
+        );
+        assert_eq!(preload_links().len(), 1);
+
+        set_href("/b.png".to_string());
+        let links = preload_links();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].get_attribute("href").as_deref(), Some("/b.png"));
+    });
+    // removed along with the scope
+    assert!(preload_links().is_empty());
+}
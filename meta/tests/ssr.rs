@@ -0,0 +1,106 @@
+#![cfg(not(any(feature = "csr", feature = "hydrate")))]
+
+use leptos::*;
+use leptos_meta::*;
+
+#[test]
+fn html_and_body_attributes_are_collected() {
+    run_scope(create_runtime(), |cx| {
+        provide_context(cx, MetaContext::new());
+
+        _ = view! { cx,
+            <main>
+                <Html lang="en" class="dark high-contrast"/>
+                <Body class="loading" attributes=Attributes::new().attr("data-theme", "dark")/>
+            </main>
+        };
+
+        let meta = use_head(cx);
+        assert_eq!(
+            meta.html_attributes(),
+            r#" lang="en" class="dark high-contrast""#
+        );
+        assert_eq!(
+            meta.body_attributes(),
+            r#" data-theme="dark" class="loading""#
+        );
+    });
+}
+
+#[test]
+fn later_instances_win() {
+    run_scope(create_runtime(), |cx| {
+        provide_context(cx, MetaContext::new());
+        let (dir, _) = create_signal(cx, "rtl".to_string());
+
+        _ = view! { cx,
+            <main>
+                <Html lang="en" dir="ltr" class="dark"/>
+                <Html
+                    lang="fr"
+                    dir=move || dir.get()
+                    class="dark wide"
+                    attributes=Attributes::new()
+                        .attr("data-hidden", false)
+                        .attr("data-quote", "\"quoted\" & <escaped>")
+                />
+                <Html attributes=Attributes::new().attr("lang", None::<String>)/>
+            </main>
+        };
+
+        assert_eq!(
+            use_head(cx).html_attributes(),
+            r#" lang="fr" dir="rtl" data-quote="&quot;quoted&quot; &amp; &lt;escaped&gt;" class="dark wide""#
+        );
+    });
+}
+
+#[test]
+fn disposed_instances_are_removed() {
+    run_scope(create_runtime(), |cx| {
+        provide_context(cx, MetaContext::new());
+
+        let disposer = cx.child_scope(|cx| {
+            Html(cx, HtmlProps::builder().lang("de").class("print").build());
+        });
+        Html(cx, HtmlProps::builder().class("dark").build());
+        assert_eq!(
+            use_head(cx).html_attributes(),
+            r#" lang="de" class="print dark""#
+        );
+
+        disposer.dispose();
+        assert_eq!(use_head(cx).html_attributes(), r#" class="dark""#);
+    });
+}
+
+#[test]
+fn preloads_are_deduplicated_in_declaration_order() {
+    run_scope(create_runtime(), |cx| {
+        provide_context(cx, MetaContext::new());
+
+        _ = view! { cx,
+            <main>
+                <Preload href="/hero.avif" as_=PreloadAs::Image/>
+                <Preload href="/inter.woff2" as_=PreloadAs::Font crossorigin="anonymous"/>
+                <Preload href="/hero.avif" as_=PreloadAs::Image fetchpriority=FetchPriority::High/>
+            </main>
+        };
+        // declared by a resource, once it has been created
+        _ = create_resource_with_options(
+            cx,
+            || 1,
+            |id| async move { format!("user {id}") },
+            ResourceOptions::new().preload_href(|| "/avatars/1.png".to_string()),
+        );
+
+        assert_eq!(
+            use_head(cx).dehydrate(),
+            concat!(
+                r#"<link rel="preload" href="/hero.avif" as="image" fetchpriority="high">"#,
+                r#"<link rel="preload" href="/inter.woff2" as="font" crossorigin="anonymous">"#,
+                r#"<link rel="preload" href="/avatars/1.png" as="image">"#,
+            )
+        );
+    });
+}
@@ -1,5 +1,6 @@
 use std::{cell::RefCell, rc::Rc};
 
+use super::routes::prerender_child;
 use crate::use_route;
 use leptos::*;
 
@@ -26,7 +27,24 @@ pub fn Outlet(cx: Scope) -> Child {
             (Some(child), _) => {
                 *is_showing.borrow_mut() = Some(child.original_path().to_string());
                 provide_context(child.cx(), child.clone());
-                set_outlet.set(Some(child.outlet().into_child(cx)))
+                let view = child.outlet().into_child(cx);
+                match child.ready() {
+                    // the old route stays on screen until the new one has loaded
+                    Some(ready) if outlet.with_untracked(Option::is_some) => {
+                        cx.untrack(|| prerender_child(&view));
+                        create_effect(child.cx(), move |shown: Option<bool>| {
+                            if shown == Some(true) {
+                                return true;
+                            }
+                            let ready = ready.get();
+                            if ready {
+                                set_outlet.set(Some(view.clone()));
+                            }
+                            ready
+                        });
+                    }
+                    _ => set_outlet.set(Some(view)),
+                }
             }
         }
     });
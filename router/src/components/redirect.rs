@@ -0,0 +1,69 @@
+use leptos::*;
+use typed_builder::TypedBuilder;
+
+use crate::{use_navigate, NavigateOptions, State, ToHref};
+
+/// Properties that can be passed to the [Redirect] component.
+#[derive(TypedBuilder)]
+pub struct RedirectProps<H>
+where
+    H: ToHref + 'static,
+{
+    /// The URL to navigate to. Will be resolved relative to the current route.
+    pub href: H,
+    /// If `true`, the redirect replaces the current entry in the history stack, so pressing
+    /// `Back` won't return to the page that redirected. (Defaults to `false`.)
+    #[builder(default)]
+    pub replace: bool,
+    /// An object of any type that will be pushed to router state
+    #[builder(default, setter(strip_option))]
+    pub state: Option<State>,
+}
+
+/// Navigates to `href` as soon as it is rendered, and renders nothing.
+///
+/// This is useful to send the user elsewhere from a route they can't see, e.g., to a login
+/// page when they aren't signed in.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn Account(cx: Scope) -> Element {
+///   let (user, _) = create_signal(cx, None::<String>);
+///   view! { cx,
+///     <div>
+///       {move || match user.get() {
+///         Some(user) => view! { cx, <p>{format!("Signed in as {user}")}</p> }.into_child(cx),
+///         None => view! { cx, <Redirect href="/login" replace=true/> }.into_child(cx),
+///       }}
+///     </div>
+///   }
+/// }
+/// ```
+#[allow(non_snake_case)]
+pub fn Redirect<H>(cx: Scope, props: RedirectProps<H>) -> Child
+where
+    H: ToHref + 'static,
+{
+    let navigate = use_navigate(cx);
+    let RedirectProps {
+        href,
+        replace,
+        state,
+    } = props;
+
+    create_isomorphic_effect(cx, move |_| {
+        let href = href.to_href()();
+        let options = NavigateOptions {
+            replace,
+            state: state.clone().unwrap_or_default(),
+            ..Default::default()
+        };
+        if let Err(e) = navigate(&href, options) {
+            debug_warn!("[Redirect] could not navigate to {href:?}: {e}");
+        }
+    });
+
+    Child::Null
+}
@@ -1,11 +1,11 @@
-use std::{borrow::Cow, rc::Rc};
+use std::{any::Any, borrow::Cow, rc::Rc};
 
 use leptos::*;
 use typed_builder::TypedBuilder;
 
 use crate::{
-    matching::{resolve_path, PathMatch, RouteDefinition, RouteMatch},
-    ParamsMap, RouterContext,
+    matching::{resolve_path, PathMatch, RouteDefinition, RouteLevelMatch},
+    LazyElement, LazyElementFactory, Loader, ParamsMap, RouterContext,
 };
 
 /// Properties that can be passed to a [Route] component, which describes
@@ -25,6 +25,14 @@ where
     /// that takes a [Scope] and returns an [Element] (like `|cx| view! { cx, <p>"Show this"</p> })`
     /// or `|cx| view! { cx, <MyComponent/>` } or even, for a component with no props, `MyComponent`).
     pub element: F,
+    /// Loads the data for the route whenever it is matched, which can be read with
+    /// [use_route_data](crate::use_route_data). See [Loader].
+    #[builder(default, setter(strip_option))]
+    pub loader: Option<Loader>,
+    /// If `true`, the page this route replaces stays on screen until its `loader` (and anything
+    /// else its element reads) has loaded. See [RouteDefinition::suspense].
+    #[builder(default)]
+    pub suspense: bool,
     /// `children` may be empty or include nested routes.
     #[builder(default, setter(strip_option))]
     pub children: Option<Box<dyn Fn() -> Vec<RouteDefinition>>>,
@@ -42,7 +50,37 @@ where
         path: props.path,
         children: props.children.map(|c| c()).unwrap_or_default(),
         element: Rc::new(move |cx| (props.element)(cx).into_child(cx)),
+        loader: props.loader,
+        suspense: props.suspense,
+    }
+}
+
+/// Properties that can be passed to a [LazyRoute] component.
+#[derive(TypedBuilder)]
+pub struct LazyRouteProps {
+    /// The path fragment that this route should match, as for a [Route].
+    pub path: &'static str,
+    /// Loads the view that should be shown when this route is matched. This is only called
+    /// the first time the route is matched: the view it resolves to is reused after that.
+    pub element: LazyElementFactory,
+    /// A view to show while the `element` is loading.
+    #[builder(default, setter(strip_option))]
+    pub placeholder: Option<LazyElement>,
+    /// `children` may be empty or include nested routes.
+    #[builder(default, setter(strip_option))]
+    pub children: Option<Box<dyn Fn() -> Vec<RouteDefinition>>>,
+}
+
+/// Describes a route, like a [Route], whose element is loaded lazily the first time the route
+/// is matched. See [element_lazy](RouteDefinition::element_lazy).
+#[allow(non_snake_case)]
+pub fn LazyRoute(_cx: Scope, props: LazyRouteProps) -> RouteDefinition {
+    RouteDefinition {
+        path: props.path,
+        children: props.children.map(|c| c()).unwrap_or_default(),
+        ..Default::default()
     }
+    .element_lazy(props.element, props.placeholder)
 }
 
 /// Context type that contains information about the current, matched route.
@@ -56,18 +94,37 @@ impl RouteContext {
         cx: Scope,
         router: &RouterContext,
         child: impl Fn() -> Option<RouteContext> + 'static,
-        matcher: impl Fn() -> Option<RouteMatch> + 'static,
+        matcher: impl Fn() -> Option<RouteLevelMatch> + 'static,
     ) -> Option<Self> {
         let base = router.base();
         let base = base.path();
-        let RouteMatch { path_match, route } = matcher()?;
+        let RouteLevelMatch { path_match, route } = matcher()?;
         let PathMatch { path, .. } = path_match;
-        let RouteDefinition { element, .. } = route.key;
+        let RouteDefinition {
+            element,
+            loader,
+            suspense,
+            ..
+        } = route.key;
         let params = create_memo(cx, move |_| {
             matcher()
                 .map(|matched| matched.path_match.params)
                 .unwrap_or_default()
         });
+        let loaded = loader.map(|loader| (loader.0)(cx, params));
+        let ready = match &loaded {
+            Some((_, loading)) if suspense => {
+                // the boundary that anything the element reads is counted under
+                let loading = *loading;
+                let suspense = SuspenseContext::new(cx);
+                provide_context(cx, suspense);
+                Some(create_memo(cx, move |_| {
+                    !loading.get() && suspense.pending_resources.get() == 0
+                }))
+            }
+            _ => None,
+        };
+        let data = loaded.map(|(data, _)| data);
 
         Some(Self {
             inner: Rc::new(RouteContextInner {
@@ -77,6 +134,8 @@ impl RouteContext {
                 path,
                 original_path: route.original_path.to_string(),
                 params,
+                data,
+                ready,
                 outlet: Box::new(move || Some(element(cx))),
             }),
         })
@@ -110,6 +169,15 @@ impl RouteContext {
         self.inner.params
     }
 
+    pub(crate) fn data(&self) -> Option<&Rc<dyn Any>> {
+        self.inner.data.as_ref()
+    }
+
+    // whether the route is ready to replace the one on screen, if it waits for its data
+    pub(crate) fn ready(&self) -> Option<Memo<bool>> {
+        self.inner.ready
+    }
+
     pub(crate) fn base(cx: Scope, path: &str, fallback: Option<fn() -> Element>) -> Self {
         Self {
             inner: Rc::new(RouteContextInner {
@@ -119,6 +187,8 @@ impl RouteContext {
                 path: path.to_string(),
                 original_path: path.to_string(),
                 params: create_memo(cx, |_| ParamsMap::new()),
+                data: None,
+                ready: None,
                 outlet: Box::new(move || fallback.map(|f| f().into_child(cx))),
             }),
         }
@@ -147,6 +217,11 @@ pub(crate) struct RouteContextInner {
     pub(crate) path: String,
     pub(crate) original_path: String,
     pub(crate) params: Memo<ParamsMap>,
+    // the resource created by the route's loader, if it has one
+    pub(crate) data: Option<Rc<dyn Any>>,
+    // with `RouteDefinition::suspense`, whether its data and everything its element reads
+    // have loaded
+    pub(crate) ready: Option<Memo<bool>>,
     pub(crate) outlet: Box<dyn Fn() -> Option<Child>>,
 }
 
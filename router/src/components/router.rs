@@ -1,5 +1,10 @@
 use cfg_if::cfg_if;
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+};
 
 use leptos::*;
 use thiserror::Error;
@@ -61,6 +66,20 @@ pub(crate) struct RouterContextInner {
     referrers: Rc<RefCell<Vec<LocationChange>>>,
     state: ReadSignal<State>,
     set_state: WriteSignal<State>,
+    guards: RefCell<Vec<NavigationGuard>>,
+    next_guard_id: Cell<usize>,
+    pub navigation: ReadSignal<NavigationPhase>,
+    set_navigation: WriteSignal<NavigationPhase>,
+}
+
+/// How far the router has got with the current navigation, if there is one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NavigationPhase {
+    Idle,
+    /// The navigation has been accepted, but the new location hasn't been set yet.
+    Started,
+    /// The new location has been set, and the new route's resources are loading.
+    Loading,
 }
 
 impl std::fmt::Debug for RouterContextInner {
@@ -75,6 +94,8 @@ impl std::fmt::Debug for RouterContextInner {
             .field("referrers", &self.referrers)
             .field("state", &self.state)
             .field("set_state", &self.set_state)
+            .field("guards", &self.guards.borrow().len())
+            .field("navigation", &self.navigation)
             .finish()
     }
 }
@@ -118,6 +139,8 @@ impl RouterContext {
         // the current History.state
         let (state, set_state) = create_signal(cx, source.with(|s| s.state.clone()));
 
+        let (navigation, set_navigation) = create_signal(cx, NavigationPhase::Idle);
+
         // we'll use this transition to wait for async resources to load when navigating to a new route
         #[cfg(feature = "transition")]
         let transition = use_transition(cx);
@@ -141,10 +164,34 @@ impl RouterContext {
                 if value != reference() {
                     set_reference.update(move |r| *r = value);
                     set_state.update(move |s| *s = state);
+                    set_navigation.set(NavigationPhase::Loading);
                 }
             });
         });
 
+        // a navigation has finished once the new route's resources have loaded, which can only
+        // be known once they've had a chance to start loading, so (as in `use_global_pending`)
+        // this is checked again a tick later, using the raw count, which is never behind
+        let pending = cx.pending_resources();
+        let disposed = Rc::new(Cell::new(false));
+        on_cleanup(cx, {
+            let disposed = Rc::clone(&disposed);
+            move || disposed.set(true)
+        });
+        create_isomorphic_effect(cx, move |_| {
+            if navigation.get() == NavigationPhase::Loading && pending.get() == 0 {
+                let disposed = Rc::clone(&disposed);
+                spawn_local(async move {
+                    if !disposed.get()
+                        && navigation.get_untracked() == NavigationPhase::Loading
+                        && pending.get_untracked() == 0
+                    {
+                        set_navigation.set(NavigationPhase::Idle);
+                    }
+                });
+            }
+        });
+
         let inner = Rc::new(RouterContextInner {
             base_path: base_path.into_owned(),
             location,
@@ -156,6 +203,10 @@ impl RouterContext {
             referrers,
             state,
             set_state,
+            guards: Default::default(),
+            next_guard_id: Default::default(),
+            navigation,
+            set_navigation,
         });
 
         // handle all click events on anchor tags
@@ -185,21 +236,55 @@ impl RouterContextInner {
         self: Rc<Self>,
         to: &str,
         options: &NavigateOptions,
+    ) -> Result<(), NavigationError> {
+        let pending = match self.resolve_to(to, options) {
+            None => return Err(NavigationError::NotRoutable(to.to_string())),
+            Some(resolved_to) => PendingNavigation {
+                to: resolved_to,
+                options: options.clone(),
+            },
+        };
+
+        match self.check_guards(&pending) {
+            GuardDecision::Allow => self.navigate_unguarded(to, options),
+            GuardDecision::Block => Err(NavigationError::Blocked(pending.to)),
+            decision @ GuardDecision::Defer(_) => {
+                let to = to.to_string();
+                let options = options.clone();
+                spawn_local(async move {
+                    if decision.allowed().await {
+                        if let Err(e) = self.navigate_unguarded(&to, &options) {
+                            log::error!("{e:#?}");
+                        }
+                    }
+                });
+                Ok(())
+            }
+        }
+    }
+
+    fn resolve_to(&self, to: &str, options: &NavigateOptions) -> Option<String> {
+        let resolved_to = if options.resolve {
+            self.base.resolve_path(to)
+        } else {
+            resolve_path("", to, None)
+        };
+        resolved_to.map(|resolved_to| resolved_to.to_string())
+    }
+
+    /// Navigates without consulting any navigation guards.
+    pub(crate) fn navigate_unguarded(
+        self: Rc<Self>,
+        to: &str,
+        options: &NavigateOptions,
     ) -> Result<(), NavigationError> {
         let cx = self.cx;
         let this = Rc::clone(&self);
 
         cx.untrack(move || {
-            let resolved_to = if options.resolve {
-                this.base.resolve_path(to)
-            } else {
-                resolve_path("", to, None)
-            };
-
-            match resolved_to {
+            match this.resolve_to(to, options) {
                 None => Err(NavigationError::NotRoutable(to.to_string())),
                 Some(resolved_to) => {
-                    let resolved_to = resolved_to.to_string();
                     if self.referrers.borrow().len() > 32 {
                         return Err(NavigationError::MaxRedirects);
                     }
@@ -229,6 +314,8 @@ impl RouterContextInner {
                             //transition.start({
                             let set_reference = self.set_reference;
                             let set_state = self.set_state;
+                            let set_navigation = self.set_navigation;
+                            set_navigation.set(NavigationPhase::Started);
                             let referrers = self.referrers.clone();
                             let this = Rc::clone(&self);
                             //move || {
@@ -242,6 +329,7 @@ impl RouterContextInner {
                                     let next_state = state.clone();
                                     move |state| *state = next_state
                                 });
+                                set_navigation.set(NavigationPhase::Loading);
                                 if referrers.borrow().len() == len {
                                     this.navigate_end(LocationChange {
                                         value: resolved_to.to_string(),
@@ -262,6 +350,54 @@ impl RouterContextInner {
         })
     }
 
+    /// Returns the decision of the first active navigation guard that does not allow
+    /// the navigation, or [GuardDecision::Allow] if they all allow it.
+    pub(crate) fn check_guards(&self, pending: &PendingNavigation) -> GuardDecision {
+        // handlers are collected first, so they can register or remove guards themselves
+        let handlers = self
+            .guards
+            .borrow()
+            .iter()
+            .filter(|guard| guard.when.get_untracked())
+            .map(|guard| Rc::clone(&guard.handler))
+            .collect::<Vec<_>>();
+        for handler in handlers {
+            match handler(pending.clone()) {
+                GuardDecision::Allow => {}
+                decision => return decision,
+            }
+        }
+        GuardDecision::Allow
+    }
+
+    pub(crate) fn add_guard(
+        &self,
+        when: Signal<bool>,
+        handler: Rc<dyn Fn(PendingNavigation) -> GuardDecision>,
+    ) -> usize {
+        let id = self.next_guard_id.get();
+        self.next_guard_id.set(id + 1);
+        self.guards
+            .borrow_mut()
+            .push(NavigationGuard { id, when, handler });
+        id
+    }
+
+    pub(crate) fn remove_guard(&self, id: usize) {
+        self.guards.borrow_mut().retain(|guard| guard.id != id);
+    }
+
+    /// Navigates the [History] back to the current location, e.g., after the browser has
+    /// already moved to a location that was then blocked by a navigation guard.
+    pub(crate) fn restore_location(&self) {
+        self.history.navigate(&LocationChange {
+            value: self.reference.get_untracked(),
+            replace: false,
+            scroll: false,
+            state: self.state.get_untracked(),
+        });
+    }
+
     pub(crate) fn navigate_end(self: Rc<Self>, mut next: LocationChange) {
         let first = self.referrers.borrow().get(0).cloned();
         if let Some(first) = first {
@@ -347,12 +483,20 @@ impl RouterContextInner {
                     state: State(None), // TODO state
                 },
             ) {
-                log::error!("{e:#?}");
+                if !matches!(e, NavigationError::Blocked(_)) {
+                    log::error!("{e:#?}");
+                }
             }
         }
     }
 }
 
+struct NavigationGuard {
+    id: usize,
+    when: Signal<bool>,
+    handler: Rc<dyn Fn(PendingNavigation) -> GuardDecision>,
+}
+
 /// An error that occurs during navigation.
 #[derive(Debug, Error)]
 pub enum NavigationError {
@@ -362,6 +506,60 @@ pub enum NavigationError {
     /// Too many redirects occurred during routing (prevents and infinite loop.)
     #[error("Too many redirects")]
     MaxRedirects,
+    /// Navigation to the given path was blocked by a navigation guard.
+    /// See [use_navigation_guard](crate::use_navigation_guard).
+    #[error("Navigation to {0:?} was blocked")]
+    Blocked(String),
+}
+
+/// A navigation that has been intercepted by a navigation guard, and is waiting to be
+/// allowed or blocked. See [use_navigation_guard](crate::use_navigation_guard).
+#[derive(Clone, Debug)]
+pub struct PendingNavigation {
+    /// The (resolved) path the router is trying to navigate to.
+    pub to: String,
+    /// The options with which the navigation was started.
+    pub options: NavigateOptions,
+}
+
+/// The decision made by a navigation guard about a [PendingNavigation].
+pub enum GuardDecision {
+    /// The navigation should continue.
+    Allow,
+    /// The navigation should be cancelled.
+    Block,
+    /// The navigation should wait until the given `Future` resolves, and then continue or
+    /// be cancelled depending on the result. This can be used for an async confirmation dialog.
+    Defer(Pin<Box<dyn Future<Output = GuardDecision>>>),
+}
+
+impl GuardDecision {
+    /// Defers the decision until the given `Future` resolves.
+    pub fn defer(decision: impl Future<Output = GuardDecision> + 'static) -> Self {
+        Self::Defer(Box::pin(decision))
+    }
+
+    /// Waits for the final decision, returning `true` if the navigation should continue.
+    pub async fn allowed(self) -> bool {
+        let mut decision = self;
+        loop {
+            match decision {
+                GuardDecision::Allow => return true,
+                GuardDecision::Block => return false,
+                GuardDecision::Defer(next) => decision = next.await,
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for GuardDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Allow => write!(f, "Allow"),
+            Self::Block => write!(f, "Block"),
+            Self::Defer(_) => f.debug_tuple("Defer").finish(),
+        }
+    }
 }
 
 /// Options that can be used to configure a navigation. Used with [use_navigate](crate::use_navigate).
@@ -1,18 +1,51 @@
-use std::{cmp::Reverse, rc::Rc, cell::{RefCell, Cell}, ops::IndexMut};
+use std::{rc::Rc, cell::{RefCell, Cell}, ops::IndexMut, time::Duration};
 
 use leptos::*;
+use leptos::leptos_dom::Child;
 use typed_builder::TypedBuilder;
 
-use crate::{matching::{expand_optionals, join_paths, Branch, Matcher, RouteDefinition, get_route_matches, RouteMatch}, RouterContext, RouteContext};
+use crate::{matching::{create_branches, get_route_matches, RouteDefinition, RouteLevelMatch}, NavigationPhase, RouterContext, RouteContext};
 
 /// Props for the [Routes] component, which contains route definitions and manages routing.
 #[derive(TypedBuilder)]
 pub struct RoutesProps {
     #[builder(default, setter(strip_option))]
     base: Option<String>,
+    /// How the new route replaces the old one when navigating. Defaults to
+    /// [NavigationMode::Immediate].
+    #[builder(default)]
+    mode: NavigationMode,
+    /// If set, routes that are navigated away from are disposed of a chunk at a time, spending
+    /// roughly this long on each chunk, rather than all at once, so that leaving a very large
+    /// page doesn’t freeze it. See [ScopeDisposer::dispose_deferred].
+    #[builder(default, setter(strip_option))]
+    dispose_budget: Option<Duration>,
     children: Box<dyn Fn() -> Vec<RouteDefinition>>,
 }
 
+/// How [Routes] replaces the current route with the new one when navigating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NavigationMode {
+    /// The new route is shown as soon as the location changes. Any `<Suspense/>` in it shows its
+    /// fallback until its resources have loaded.
+    #[default]
+    Immediate,
+    /// The old route stays on screen until the new route is ready, like a `<Transition/>` for
+    /// the whole page.
+    ///
+    /// The new route is created in its own scope and rendered off-screen, so its resources start
+    /// loading, and it is only swapped in (and the old route disposed of) once the navigation has
+    /// finished, as reported by [use_is_navigating](crate::use_is_navigating), or once `timeout`
+    /// has passed, in which case any fallbacks it is still showing are swapped in with it. If
+    /// another navigation starts in the meantime, the route that was loading is disposed of.
+    ///
+    /// Every level of the new route is created again, even those it shares with the old route.
+    Deferred {
+        /// How long to wait for the new route before showing it anyway, if at all.
+        timeout: Option<Duration>,
+    },
+}
+
 /// Contains route definitions and manages the actual routing process. 
 /// 
 /// You should locate the `<Routes/>` component wherever on the page you want the routes to appear.
@@ -23,22 +56,21 @@ pub fn Routes(cx: Scope, props: RoutesProps) -> impl IntoChild {
         panic!()
     });
 
-    let mut branches = Vec::new();
-    create_branches(
-        &(props.children)(),
-        &props.base.unwrap_or_default(),
-        &mut Vec::new(),
-        &mut branches,
-    );
+    let branches = create_branches(&(props.children)(), &props.base.unwrap_or_default());
 
     // whenever path changes, update matches
     let matches = create_memo(cx, {
         let router = router.clone();
         move |_| {
-            get_route_matches(branches.clone(), router.pathname().get())
+            router.pathname().with(|pathname| get_route_matches(&branches, pathname))
         }
     });
 
+    let dispose_budget = props.dispose_budget;
+    if let NavigationMode::Deferred { timeout } = props.mode {
+        return deferred_routes(cx, router, matches, timeout, dispose_budget);
+    }
+
     // Rebuild the list of nested routes conservatively, and show the root route here
     let disposers = RefCell::new(Vec::<ScopeDisposer>::new());
 
@@ -120,7 +152,7 @@ pub fn Routes(cx: Scope, props: RoutesProps) -> impl IntoChild {
                     if disposers.borrow().len() > i + 1 {
                         let mut disposers = disposers.borrow_mut();
                         let old_route_disposer = std::mem::replace(&mut disposers[i], disposer);
-                        old_route_disposer.dispose();
+                        dispose_route(old_route_disposer, dispose_budget);
                     } else {
                         disposers.borrow_mut().push(disposer);
                     }
@@ -130,7 +162,7 @@ pub fn Routes(cx: Scope, props: RoutesProps) -> impl IntoChild {
             if disposers.borrow().len() > next_matches.len() {
                 let surplus_disposers = disposers.borrow_mut().split_off(next_matches.len() + 1);
                 for disposer in surplus_disposers {
-                    disposer.dispose();
+                    dispose_route(disposer, dispose_budget);
                 }
             }
 
@@ -151,8 +183,12 @@ pub fn Routes(cx: Scope, props: RoutesProps) -> impl IntoChild {
         }
     });
     
+    // the root route's view, and whether it is on screen yet: a route that waits for its data
+    // (see `RouteDefinition::suspense`) is rendered once, and held back until it's ready
+    let rendered = RefCell::new(None::<(Scope, Child, bool)>);
+
     // show the root route
-    create_memo(cx, move |prev| {
+    create_memo(cx, move |prev: Option<&Option<Child>>| {
         provide_context(cx, route_states);
         route_states.with(|state| {
             let root = state.routes.borrow();
@@ -161,9 +197,33 @@ pub fn Routes(cx: Scope, props: RoutesProps) -> impl IntoChild {
                 provide_context(cx, route.clone());
             }
 
-            if prev.is_none() || !root_equal.get() {
-                root.as_ref().map(|route| {
-                    route.outlet().into_child(cx)
+            let held = rendered.borrow().as_ref().map_or(false, |(_, _, shown)| !shown);
+            if prev.is_none() || !root_equal.get() || held {
+                root.and_then(|route| {
+                    let mut rendered = rendered.borrow_mut();
+                    let cached = match &*rendered {
+                        Some((route_cx, view, shown)) if *route_cx == route.cx() => {
+                            Some((view.clone(), *shown))
+                        }
+                        _ => None,
+                    };
+                    let fresh = cached.is_none();
+                    let (view, shown) =
+                        cached.unwrap_or_else(|| (route.outlet().into_child(cx), false));
+
+                    // the old route stays on screen until the new one has loaded
+                    let waiting = !shown
+                        && prev.map_or(false, Option::is_some)
+                        && route.ready().map_or(false, |ready| !ready.get());
+                    *rendered = Some((route.cx(), view.clone(), !waiting));
+                    if waiting {
+                        if fresh {
+                            cx.untrack(|| prerender_child(&view));
+                        }
+                        prev.cloned().flatten()
+                    } else {
+                        Some(view)
+                    }
                 })
             } else {
                 prev.cloned().unwrap()
@@ -172,92 +232,187 @@ pub fn Routes(cx: Scope, props: RoutesProps) -> impl IntoChild {
     })
 }
 
-#[derive(Clone, Debug, PartialEq)]
-struct RouterState {
-    matches: Vec<RouteMatch>,
-    routes: Rc<RefCell<Vec<RouteContext>>>,
-    root: Option<RouteContext>,
+// Disposes of routes that are no longer needed, a chunk at a time if there is a budget.
+fn dispose_route(disposer: ScopeDisposer, budget: Option<Duration>) {
+    match budget {
+        Some(budget) => _ = disposer.dispose_deferred(budget),
+        None => disposer.dispose(),
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct RouteData {
-    pub key: RouteDefinition,
-    pub pattern: String,
-    pub original_path: String,
-    pub matcher: Matcher,
+// A route tree that has been created for a navigation, but isn't on screen yet.
+struct PendingRoutes {
+    id: usize,
+    child: Option<Child>,
+    disposer: ScopeDisposer,
+    prerender: Option<ScopeDisposer>,
 }
 
-impl RouteData {
-    fn score(&self) -> i32 {
-        let (pattern, splat) = match self.pattern.split_once("/*") {
-            Some((p, s)) => (p, Some(s)),
-            None => (self.pattern.as_str(), None),
-        };
-        let segments = pattern
-            .split('/')
-            .filter(|n| !n.is_empty())
-            .collect::<Vec<_>>();
-        segments.iter().fold(
-            (segments.len() as i32) - if splat.is_none() { 0 } else { 1 },
-            |score, segment| score + if segment.starts_with(':') { 2 } else { 3 },
-        )
-    }
+#[derive(Default)]
+struct DeferredState {
+    next_id: usize,
+    shown: Option<ScopeDisposer>,
+    pending: Option<PendingRoutes>,
 }
 
-fn create_branches(
-    route_defs: &[RouteDefinition],
-    base: &str,
-    stack: &mut Vec<RouteData>,
-    branches: &mut Vec<Branch>,
-) {
-    for def in route_defs {
-        let routes = create_routes(def, base);
-        for route in routes {
-            stack.push(route.clone());
-
-            if def.children.is_empty() {
-                let branch = create_branch(stack, branches.len());
-                branches.push(branch);
-            } else {
-                create_branches(&def.children, &route.pattern, stack, branches);
+// Shows the routes for each set of matches once they have finished loading. See
+// [NavigationMode::Deferred].
+fn deferred_routes(
+    cx: Scope,
+    router: RouterContext,
+    matches: Memo<Vec<RouteLevelMatch>>,
+    timeout: Option<Duration>,
+    dispose_budget: Option<Duration>,
+) -> Memo<Option<Child>> {
+    let (shown, set_shown) = create_signal(cx, None::<Child>);
+    let state = Rc::new(RefCell::new(DeferredState::default()));
+    let navigation = router.inner.navigation;
+    let disposed = Rc::new(Cell::new(false));
+
+    // swaps in the pending routes, if they are still the ones with this ID
+    let commit = {
+        let state = Rc::clone(&state);
+        Rc::new(move |id: usize| {
+            let pending = {
+                let mut state = state.borrow_mut();
+                match state.pending.take() {
+                    Some(pending) if pending.id == id => pending,
+                    other => {
+                        state.pending = other;
+                        return;
+                    }
+                }
+            };
+
+            // the renderer takes over from here
+            if let Some(prerender) = pending.prerender {
+                prerender.dispose();
+            }
+            set_shown.set(pending.child);
+
+            let old = state.borrow_mut().shown.replace(pending.disposer);
+            if let Some(old) = old {
+                dispose_route(old, dispose_budget);
+            }
+        })
+    };
+
+    create_isomorphic_effect(cx, {
+        let state = Rc::clone(&state);
+        let commit = Rc::clone(&commit);
+        let disposed = Rc::clone(&disposed);
+        move |_| {
+            let matches = matches.get();
+            cx.untrack(|| {
+                let ((child, prerender), disposer) = cx.run_child_scope(|cx| {
+                    let child = create_route_tree(cx, &router, &matches);
+                    let prerender = child.clone().map(|child| {
+                        cx.child_scope(move |cx| {
+                            create_isomorphic_effect(cx, move |_| prerender_child(&child));
+                        })
+                    });
+                    (child, prerender)
+                });
+
+                let (id, first, cancelled) = {
+                    let mut state = state.borrow_mut();
+                    let id = state.next_id;
+                    state.next_id += 1;
+                    let cancelled = state.pending.replace(PendingRoutes {
+                        id,
+                        child,
+                        disposer,
+                        prerender,
+                    });
+                    (id, state.shown.is_none(), cancelled)
+                };
+                if let Some(cancelled) = cancelled {
+                    dispose_route(cancelled.disposer, dispose_budget);
+                }
+
+                if first {
+                    commit(id);
+                } else {
+                    // the location can change before the navigation starts (e.g., on `popstate`),
+                    // so this waits a tick to check whether there is a navigation to wait for
+                    let commit = Rc::clone(&commit);
+                    let disposed = Rc::clone(&disposed);
+                    spawn_local(async move {
+                        if !disposed.get() && navigation.get_untracked() == NavigationPhase::Idle {
+                            commit(id);
+                        }
+                    });
+                    if let Some(timeout) = timeout {
+                        let commit = Rc::clone(&commit);
+                        leptos_dom::set_timeout(move || commit(id), timeout);
+                    }
+                }
+            })
+        }
+    });
+
+    // once the navigation has finished, the new routes are ready
+    create_isomorphic_effect(cx, {
+        let state = Rc::clone(&state);
+        move |_| {
+            if navigation.get() == NavigationPhase::Idle {
+                let pending = state.borrow().pending.as_ref().map(|pending| pending.id);
+                if let Some(id) = pending {
+                    commit(id);
+                }
             }
+        }
+    });
+
+    on_cleanup(cx, move || {
+        disposed.set(true);
+        // the scopes themselves are disposed along with `cx`
+        state.borrow_mut().pending = None;
+    });
 
-            stack.pop();
+    create_memo(cx, move |_| shown.get())
+}
+
+// Creates a route for each of the matches, each nested in the one before, and renders the
+// outermost one.
+fn create_route_tree(cx: Scope, router: &RouterContext, matches: &[RouteLevelMatch]) -> Option<Child> {
+    let routes: Rc<RefCell<Vec<RouteContext>>> = Default::default();
+    for (i, route_match) in matches.iter().enumerate() {
+        let route_match = route_match.clone();
+        let (route, _) = cx.run_child_scope({
+            let routes = Rc::clone(&routes);
+            move |cx| {
+                RouteContext::new(
+                    cx,
+                    router,
+                    move || routes.borrow().get(i + 1).cloned(),
+                    move || Some(route_match.clone()),
+                )
+            }
+        });
+        match route {
+            Some(route) => routes.borrow_mut().push(route),
+            None => break,
         }
     }
 
-    if stack.is_empty() {
-        branches.sort_by_key(|branch| Reverse(branch.score));
-    }
+    let root = routes.borrow().get(0).cloned()?;
+    provide_context(root.cx(), root.clone());
+    Some(root.outlet().into_child(root.cx()))
 }
 
-pub(crate) fn create_branch(routes: &[RouteData], index: usize) -> Branch {
-    Branch {
-        routes: routes.to_vec(),
-        score: routes.last().unwrap().score() * 10000 - (index as i32),
+// Runs any reactive parts of the view, as the renderer would, so that the resources they read
+// start loading while it is still off-screen.
+pub(crate) fn prerender_child(child: &Child) {
+    if let Child::Fn(f) = child {
+        let next = (f.borrow_mut())();
+        prerender_child(&next);
     }
 }
 
-fn create_routes(route_def: &RouteDefinition, base: &str) -> Vec<RouteData> {
-    let RouteDefinition { children, .. } = route_def;
-    let is_leaf = children.is_empty();
-    let mut acc = Vec::new();
-    for original_path in expand_optionals(route_def.path) {
-        let path = join_paths(base, &original_path);
-        let pattern = if is_leaf {
-            path
-        } else {
-            path.split("/*")
-                .next()
-                .map(|n| n.to_string())
-                .unwrap_or(path)
-        };
-        acc.push(RouteData {
-            key: route_def.clone(),
-            matcher: Matcher::new_with_partial(&pattern, !is_leaf),
-            pattern,
-            original_path: original_path.to_string(),
-        });
-    }
-    acc
+#[derive(Clone, Debug, PartialEq)]
+struct RouterState {
+    matches: Vec<RouteLevelMatch>,
+    routes: Rc<RefCell<Vec<RouteContext>>>,
+    root: Option<RouteContext>,
 }
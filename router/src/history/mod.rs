@@ -50,8 +50,6 @@ impl BrowserIntegration {
 
 impl History for BrowserIntegration {
     fn location(&self, cx: Scope) -> ReadSignal<LocationChange> {
-        use crate::{NavigateOptions, RouterContext};
-
         let (location, set_location) = create_signal(cx, Self::current());
 
         leptos_dom::window_event_listener("popstate", move |_| {
@@ -59,24 +57,9 @@ impl History for BrowserIntegration {
                 "[BrowserIntegration::location] popstate fired {:#?}",
                 Self::current()
             );
-            let router = use_context::<RouterContext>(cx);
-            if let Some(router) = router {
-                let change = Self::current();
-                if let Err(e) = router.inner.navigate_from_route(
-                    &change.value,
-                    &NavigateOptions {
-                        resolve: false,
-                        replace: change.replace,
-                        scroll: change.scroll,
-                        state: change.state,
-                    },
-                ) {
-                    log::error!("{e:#?}");
-                }
-                set_location.set(Self::current());
-            } else {
-                log::warn!("RouterContext not found");
-            }
+            handle_history_change(cx, Self::current(), move || {
+                set_location.set(Self::current())
+            });
         });
 
         location
@@ -112,6 +95,62 @@ impl History for BrowserIntegration {
     }
 }
 
+/// Lets the navigation guards of the [Router](crate::Router) above `cx` decide whether `change`,
+/// a change of location that has already happened outside the router (like the browser’s back
+/// and forward buttons), should go ahead.
+///
+/// If the guards allow it, the router navigates to the new location and `on_navigated` is called,
+/// even if they only allow it after a [deferred](crate::GuardDecision::Defer) decision. Otherwise,
+/// the [History] is moved back to the router’s current location.
+///
+/// [BrowserIntegration] calls this on every `popstate` event. A custom [History] should do the
+/// same whenever its location changes for a reason other than [History::navigate].
+pub fn handle_history_change(
+    cx: Scope,
+    change: LocationChange,
+    on_navigated: impl FnOnce() + 'static,
+) {
+    use crate::{GuardDecision, NavigateOptions, PendingNavigation, RouterContext};
+
+    let Some(router) = use_context::<RouterContext>(cx) else {
+        log::warn!("RouterContext not found");
+        return;
+    };
+    let options = NavigateOptions {
+        resolve: false,
+        replace: change.replace,
+        scroll: change.scroll,
+        state: change.state,
+    };
+    let pending = PendingNavigation {
+        to: change.value.clone(),
+        options: options.clone(),
+    };
+
+    // the history has already moved to the new location, so if a guard blocks or defers the
+    // navigation, we need to restore the history position
+    match router.inner.check_guards(&pending) {
+        GuardDecision::Allow => {
+            if let Err(e) = router.inner.navigate_unguarded(&change.value, &options) {
+                log::error!("{e:#?}");
+            }
+            on_navigated();
+        }
+        GuardDecision::Block => router.inner.restore_location(),
+        decision @ GuardDecision::Defer(_) => {
+            router.inner.restore_location();
+            spawn_local(async move {
+                if decision.allowed().await {
+                    if let Err(e) = router.inner.navigate_unguarded(&change.value, &options) {
+                        log::error!("{e:#?}");
+                    }
+                    on_navigated();
+                }
+            });
+        }
+    }
+}
+
 /// The wrapper type that the [Router](crate::Router) uses to interact with a [History].
 /// This is automatically provided in the browser. For the server, it should be provided
 /// as a context.
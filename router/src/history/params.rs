@@ -35,6 +35,26 @@ impl ParamsMap {
         self.0.remove(key)
     }
 
+    /// Whether the map has a value for the key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// The number of values in the map.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over the keys and values in the map, in the order they were inserted.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
     #[cfg(any(feature = "csr", feature = "hydrate", feature = "ssr"))]
     /// Converts the map to a query string.
     pub fn to_query_string(&self) -> String {
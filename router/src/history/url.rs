@@ -73,7 +73,9 @@ impl TryFrom<&str> for Url {
     type Error = String;
 
     fn try_from(url: &str) -> Result<Self, Self::Error> {
-        let url = url::Url::parse(url).map_err(|e| e.to_string())?;
+        // as in the browser, paths are resolved against a fake host
+        let fake_host = url::Url::parse("http://leptos").map_err(|e| e.to_string())?;
+        let url = fake_host.join(url).map_err(|e| e.to_string())?;
         Ok(Self {
             origin: url.origin().unicode_serialization(),
             pathname: url.path().to_string(),
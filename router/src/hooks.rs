@@ -1,10 +1,12 @@
 use std::rc::Rc;
 
-use leptos::{create_memo, use_context, Memo, Scope};
+use leptos::{
+    create_local_resource, create_memo, on_cleanup, use_context, Memo, Resource, Scope, Signal,
+};
 
 use crate::{
-    Location, NavigateOptions, NavigationError, Params, ParamsError, ParamsMap, RouteContext,
-    RouterContext,
+    matching::Matcher, GuardDecision, Location, NavigateOptions, NavigationError, NavigationPhase,
+    Params, ParamsError, ParamsMap, PendingNavigation, RouteContext, RouterContext,
 };
 
 /// Returns the current [RouterContext], containing information about the router's state.
@@ -28,6 +30,11 @@ pub fn use_location(cx: Scope) -> Location {
 }
 
 /// Returns a raw key-value map of route params.
+///
+/// Unlike [use_params], this doesn’t need a type that implements [Params], so it can be used
+/// by components that don’t know the names of the params ahead of time, or to read the rest
+/// of the path matched by a catch-all route (e.g., the `rest` param of `/files/*rest`). The
+/// map is updated whenever a navigation changes the params of the matched route.
 pub fn use_params_map(cx: Scope) -> Memo<ParamsMap> {
     let route = use_route(cx);
     route.params()
@@ -42,6 +49,114 @@ where
     create_memo(cx, move |_| route.params().with(T::from_map))
 }
 
+/// Returns the [Resource] that holds the data loaded by the current route’s [Loader](crate::Loader),
+/// which loads the data in the route definition, so the component that shows it doesn’t have
+/// to know how to fetch it.
+///
+/// `T` must be the output type of the loader. If the route has no loader (or it loads another
+/// type), this warns in debug mode and returns a resource that never resolves, i.e., it always
+/// returns [None].
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// async fn load_user(params: ParamsMap) -> Option<String> {
+///   params.get("id").map(|id| format!("user {id}"))
+/// }
+///
+/// #[component]
+/// fn User(cx: Scope) -> Element {
+///   let user = use_route_data::<Option<String>>(cx);
+///   view! { cx,
+///     <Suspense fallback={"Loading...".to_string()}>
+///       {move || user.read().map(|user| view! { cx,
+///         <p>{user.unwrap_or_else(|| "No such user".to_string())}</p>
+///       })}
+///     </Suspense>
+///   }
+/// }
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///   view! { cx,
+///     <Router>
+///       <Routes>
+///         <Route path="users/:id" element=|cx| view! { cx, <User/> } loader=Loader::new(load_user)/>
+///       </Routes>
+///     </Router>
+///   }
+/// }
+/// ```
+pub fn use_route_data<T>(cx: Scope) -> Resource<(), T>
+where
+    T: std::fmt::Debug + Clone + 'static,
+{
+    let route = use_route(cx);
+    let data = route
+        .data()
+        .and_then(|data| data.downcast_ref::<Resource<(), T>>());
+    match data {
+        Some(data) => *data,
+        None => {
+            leptos::leptos_dom::debug_warn!(
+                "use_route_data::<{}>() was called in the route {:?}, which has no loader for that type",
+                std::any::type_name::<T>(),
+                route.original_path()
+            );
+            create_local_resource(cx, || (), |_| futures::future::pending())
+        }
+    }
+}
+
+/// The part of the current URL that a pattern matched. See [use_route_match].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteMatchData {
+    /// The part of the path that the pattern matched, with param values in their places.
+    pub path: String,
+    /// The values of the pattern’s params, including any wildcard.
+    pub params: ParamsMap,
+}
+
+/// Returns a memo that holds the match when the current URL matches `pattern`, or its beginning
+/// (so `/users` also matches `/users/42`), and [None] otherwise. Like the path of a
+/// [Route](crate::Route), `pattern` can contain params like `:id` and end in a wildcard like
+/// `/*rest`; if it doesn’t start with `/`, it is resolved relative to the current route.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn NavItem(cx: Scope) -> Element {
+///   // highlighted on `/users` and any page below it
+///   let matched = use_route_match(cx, "/users");
+///   view! { cx,
+///     <li class=move || if matched.with(Option::is_some) { "active" } else { "" }>
+///       <A href="/users">"Users"</A>
+///     </li>
+///   }
+/// }
+/// ```
+pub fn use_route_match(cx: Scope, pattern: &str) -> Memo<Option<RouteMatchData>> {
+    let pattern = if pattern.starts_with('/') {
+        pattern.to_string()
+    } else {
+        use_route(cx)
+            .resolve_path(pattern)
+            .map(String::from)
+            .unwrap_or_else(|| pattern.to_string())
+    };
+    let matcher = Matcher::new_with_partial(&pattern, true);
+    let pathname = use_location(cx).pathname;
+    create_memo(cx, move |_| {
+        pathname
+            .with(|path| matcher.test(path))
+            .map(|matched| RouteMatchData {
+                path: matched.path,
+                params: matched.params,
+            })
+    })
+}
+
 /// Returns a raw key-value map of the URL search query.
 pub fn use_query_map(cx: Scope) -> Memo<ParamsMap> {
     use_router(cx).inner.location.query
@@ -77,3 +192,109 @@ pub fn use_navigate(cx: Scope) -> impl Fn(&str, NavigateOptions) -> Result<(), N
     let router = use_router(cx);
     move |to, options| Rc::clone(&router.inner).navigate_from_route(to, &options)
 }
+
+/// Returns a signal that is `true` from the moment the router accepts a navigation (after any
+/// [navigation guards](use_navigation_guard) have allowed it) until the resources read under
+/// `<Suspense/>` or `<Transition/>` by the new route have loaded, e.g., to show a progress bar.
+///
+/// Refetching a resource outside of a navigation doesn’t affect this signal: to track those as
+/// well, use [use_global_pending](leptos::use_global_pending).
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn ProgressBar(cx: Scope) -> Element {
+///   let navigating = use_is_navigating(cx);
+///
+///   view! { cx,
+///     <div class=move || if navigating.get() { "progress-bar visible" } else { "progress-bar" }/>
+///   }
+/// }
+/// ```
+pub fn use_is_navigating(cx: Scope) -> Signal<bool> {
+    let navigation = use_router(cx).inner.navigation;
+    create_memo(cx, move |_| navigation.get() != NavigationPhase::Idle).into()
+}
+
+/// Intercepts navigations while `when` is `true`, e.g., to ask for confirmation before
+/// leaving a form with unsaved changes.
+///
+/// Whenever the router is about to navigate (via a link, [use_navigate], or the browser’s
+/// back and forward buttons), `handler` is called with the [PendingNavigation], and can
+/// [Allow](GuardDecision::Allow) or [Block](GuardDecision::Block) it, or
+/// [Defer](GuardDecision::Defer) the decision until a `Future` resolves, so it can be
+/// made by an async confirmation dialog. Blocked back/forward navigations restore the
+/// previous position in the history stack.
+///
+/// While `when` is `true`, a [`beforeunload`](https://developer.mozilla.org/en-US/docs/Web/API/Window/beforeunload_event)
+/// listener is also registered, so the browser asks for confirmation before hard navigations
+/// like reloading or closing the tab. The guard and the listener are removed when `cx` is disposed.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn EditProfile(cx: Scope) -> Element {
+///   let (unsaved, set_unsaved) = create_signal(cx, false);
+///
+///   use_navigation_guard(cx, unsaved, |_pending| {
+///     if leptos::window().confirm_with_message("Discard unsaved changes?").unwrap_or(false) {
+///       GuardDecision::Allow
+///     } else {
+///       GuardDecision::Block
+///     }
+///   });
+///
+///   view! { cx,
+///     <input on:input=move |_| set_unsaved(true)/>
+///   }
+/// }
+/// ```
+pub fn use_navigation_guard(
+    cx: Scope,
+    when: impl Into<Signal<bool>>,
+    handler: impl Fn(PendingNavigation) -> GuardDecision + 'static,
+) {
+    let router = use_router(cx);
+    let when = when.into();
+
+    let id = router.inner.add_guard(when.clone(), Rc::new(handler));
+    on_cleanup(cx, move || router.inner.remove_guard(id));
+
+    #[cfg(not(feature = "ssr"))]
+    before_unload_while(cx, when);
+}
+
+#[cfg(not(feature = "ssr"))]
+fn before_unload_while(cx: Scope, when: Signal<bool>) {
+    use leptos::create_effect;
+    use std::cell::Cell;
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let listener = Rc::new(Closure::wrap(Box::new(|ev: web_sys::BeforeUnloadEvent| {
+        ev.prevent_default();
+        ev.set_return_value("");
+    })
+        as Box<dyn Fn(web_sys::BeforeUnloadEvent)>));
+    let registered = Rc::new(Cell::new(false));
+
+    let toggle = move |register: bool| {
+        if register != registered.get() {
+            let window = leptos::window();
+            let listener = listener.as_ref().as_ref().unchecked_ref();
+            _ = if register {
+                window.add_event_listener_with_callback("beforeunload", listener)
+            } else {
+                window.remove_event_listener_with_callback("beforeunload", listener)
+            };
+            registered.set(register);
+        }
+    };
+
+    create_effect(cx, {
+        let toggle = toggle.clone();
+        move |_| toggle(when.get())
+    });
+    on_cleanup(cx, move || toggle(false));
+}
@@ -148,4 +148,7 @@ mod matching;
 pub use components::*;
 pub use history::*;
 pub use hooks::*;
-pub use matching::Branch;
+pub use matching::{
+    match_route, Branch, LazyElement, LazyElementFactory, Loader, RouteData, RouteDefinition,
+    RouteMatch,
+};
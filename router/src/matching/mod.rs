@@ -3,22 +3,99 @@ mod matcher;
 mod resolve_path;
 mod route;
 
+use std::cmp::Reverse;
+
 pub(crate) use expand_optionals::*;
 pub(crate) use matcher::*;
 pub(crate) use resolve_path::*;
 pub use route::*;
 
-use crate::RouteData;
+use crate::ParamsMap;
+
+/// Finds the route that matches `path` in a table of route definitions, the same way the
+/// [Routes](crate::Routes) component does, but without rendering anything: no reactive
+/// runtime or [Scope](leptos::Scope) is needed, and the routes’ elements aren’t called.
+///
+/// This lets a server answer questions about a path cheaply before (or instead of) rendering
+/// the app, like whether it matches any route at all, or which route it matches. Like the
+/// rest of the router, this needs the `ssr` feature to run outside the browser.
+///
+/// ```no_run
+/// # use leptos_router::*;
+/// use axum::{http::{Request, StatusCode}, middleware::Next, response::Response};
+///
+/// fn routes() -> Vec<RouteDefinition> {
+///   vec![
+///     RouteDefinition { path: "", ..Default::default() },
+///     RouteDefinition {
+///       path: "users",
+///       children: vec![RouteDefinition { path: ":id", ..Default::default() }],
+///       ..Default::default()
+///     },
+///   ]
+/// }
+///
+/// // answers paths that can’t match any route with a 404 right away, without rendering
+/// async fn not_found_fast_path<B>(req: Request<B>, next: Next<B>) -> Result<Response, StatusCode> {
+///   let matched = match_route(&routes(), req.uri().path()).is_some();
+///   if matched {
+///     Ok(next.run(req).await)
+///   } else {
+///     Err(StatusCode::NOT_FOUND)
+///   }
+/// }
+///
+/// let matched = match_route(&routes(), "/users/42").unwrap();
+/// assert_eq!(matched.pattern, "/users/:id");
+/// assert_eq!(matched.params.get("id").map(String::as_str), Some("42"));
+/// assert!(match_route(&routes(), "/admin").is_none());
+/// ```
+pub fn match_route<'a>(routes: &'a [RouteDefinition], path: &str) -> Option<RouteMatch<'a>> {
+    let matches = get_route_matches(&create_branches(routes, ""), path);
+    let last = matches.last()?;
+
+    // each level of the match was created from one of the definitions at the same level
+    let mut defs = routes;
+    let mut matched_defs = Vec::with_capacity(matches.len());
+    for level in &matches {
+        let def = defs.iter().find(|def| **def == level.route.key)?;
+        matched_defs.push(def);
+        defs = &def.children;
+    }
+
+    Some(RouteMatch {
+        routes: matched_defs,
+        pattern: last.route.pattern.clone(),
+        path: last.path_match.path.clone(),
+        params: last.path_match.params.clone(),
+    })
+}
 
+/// A route that a path matched, as found by [match_route].
 #[derive(Debug, Clone, PartialEq)]
-pub(crate) struct RouteMatch {
+pub struct RouteMatch<'a> {
+    /// The definitions of the matched route and the routes it is nested in, from the
+    /// outermost to the innermost.
+    pub routes: Vec<&'a RouteDefinition>,
+    /// The full pattern of the innermost route, with the paths of the routes it is nested in
+    /// joined together, like `/users/:id`.
+    pub pattern: String,
+    /// The part of the path that the route matched, with param values in their places.
+    pub path: String,
+    /// The values of the route’s params, including any wildcard.
+    pub params: ParamsMap,
+}
+
+/// One level of the nested routes that a path matched.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RouteLevelMatch {
     pub path_match: PathMatch,
     pub route: RouteData,
 }
 
-pub(crate) fn get_route_matches(branches: Vec<Branch>, location: String) -> Vec<RouteMatch> {
+pub(crate) fn get_route_matches(branches: &[Branch], location: &str) -> Vec<RouteLevelMatch> {
     for branch in branches {
-        if let Some(matches) = branch.matcher(&location) {
+        if let Some(matches) = branch.matcher(location) {
             return matches;
         }
     }
@@ -35,12 +112,12 @@ pub struct Branch {
 }
 
 impl Branch {
-    fn matcher<'a>(&'a self, location: &'a str) -> Option<Vec<RouteMatch>> {
+    fn matcher<'a>(&'a self, location: &'a str) -> Option<Vec<RouteLevelMatch>> {
         let mut matches = Vec::new();
         for route in self.routes.iter().rev() {
             match route.matcher.test(location) {
                 None => return None,
-                Some(m) => matches.push(RouteMatch {
+                Some(m) => matches.push(RouteLevelMatch {
                     path_match: m,
                     route: route.clone(),
                 }),
@@ -50,3 +127,90 @@ impl Branch {
         Some(matches)
     }
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteData {
+    pub key: RouteDefinition,
+    pub pattern: String,
+    pub original_path: String,
+    pub matcher: Matcher,
+}
+
+impl RouteData {
+    fn score(&self) -> i32 {
+        let (pattern, splat) = match self.pattern.split_once("/*") {
+            Some((p, s)) => (p, Some(s)),
+            None => (self.pattern.as_str(), None),
+        };
+        let segments = pattern
+            .split('/')
+            .filter(|n| !n.is_empty())
+            .collect::<Vec<_>>();
+        segments.iter().fold(
+            (segments.len() as i32) - if splat.is_none() { 0 } else { 1 },
+            |score, segment| score + if segment.starts_with(':') { 2 } else { 3 },
+        )
+    }
+}
+
+/// Flattens the route definitions into their branches, from the closest match to the loosest.
+pub(crate) fn create_branches(route_defs: &[RouteDefinition], base: &str) -> Vec<Branch> {
+    let mut branches = Vec::new();
+    collect_branches(route_defs, base, &mut Vec::new(), &mut branches);
+    branches.sort_by_key(|branch| Reverse(branch.score));
+    branches
+}
+
+fn collect_branches(
+    route_defs: &[RouteDefinition],
+    base: &str,
+    stack: &mut Vec<RouteData>,
+    branches: &mut Vec<Branch>,
+) {
+    for def in route_defs {
+        let routes = create_routes(def, base);
+        for route in routes {
+            stack.push(route.clone());
+
+            if def.children.is_empty() {
+                let branch = create_branch(stack, branches.len());
+                branches.push(branch);
+            } else {
+                collect_branches(&def.children, &route.pattern, stack, branches);
+            }
+
+            stack.pop();
+        }
+    }
+}
+
+fn create_branch(routes: &[RouteData], index: usize) -> Branch {
+    Branch {
+        routes: routes.to_vec(),
+        score: routes.last().unwrap().score() * 10000 - (index as i32),
+    }
+}
+
+fn create_routes(route_def: &RouteDefinition, base: &str) -> Vec<RouteData> {
+    let RouteDefinition { children, .. } = route_def;
+    let is_leaf = children.is_empty();
+    let mut acc = Vec::new();
+    for original_path in expand_optionals(route_def.path) {
+        let path = join_paths(base, &original_path);
+        let pattern = if is_leaf {
+            path
+        } else {
+            path.split("/*")
+                .next()
+                .map(|n| n.to_string())
+                .unwrap_or(path)
+        };
+        acc.push(RouteData {
+            key: route_def.clone(),
+            matcher: Matcher::new_with_partial(&pattern, !is_leaf),
+            pattern,
+            original_path: original_path.to_string(),
+        });
+    }
+    acc
+}
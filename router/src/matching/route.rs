@@ -1,13 +1,164 @@
-use std::rc::Rc;
+use std::{any::Any, cell::RefCell, fmt::Debug, future::Future, pin::Pin, rc::Rc};
 
 use leptos::leptos_dom::Child;
 use leptos::*;
 
+use crate::ParamsMap;
+
+/// The element of a route whose code is loaded lazily, as resolved by a [LazyElementFactory].
+pub type LazyElement = Rc<dyn Fn(Scope) -> Child>;
+
+/// Loads the element of a [lazy route](RouteDefinition::element_lazy), e.g., by fetching and
+/// instantiating a separate wasm module with `wasm_bindgen`’s dynamic imports.
+pub type LazyElementFactory = Rc<dyn Fn(Scope) -> Pin<Box<dyn Future<Output = LazyElement>>>>;
+
+/// Loads the data for a route whenever it is matched, so that it can be read by the route’s
+/// element (or anything inside it) with [use_route_data](crate::use_route_data).
+///
+/// The data is loaded by a [Resource] created in the route’s scope, as soon as the route is
+/// matched, and reloaded whenever the route’s params change. Like any other resource created
+/// with [create_resource], it is loaded on the server and sent to the client when server-side
+/// rendering is used.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// async fn load_user(params: ParamsMap) -> Option<String> {
+///   // pretend we're fetching the user from an API
+///   params.get("id").map(|id| format!("user {id}"))
+/// }
+///
+/// let route = RouteDefinition {
+///   path: "users/:id",
+///   loader: Some(Loader::new(load_user)),
+///   ..Default::default()
+/// };
+/// ```
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct Loader(pub(crate) Rc<dyn Fn(Scope, Memo<ParamsMap>) -> (Rc<dyn Any>, ReadSignal<bool>)>);
+
+impl Loader {
+    /// Creates a loader that calls `load` with the route’s params.
+    pub fn new<T, Fu>(load: impl Fn(ParamsMap) -> Fu + 'static) -> Self
+    where
+        T: Debug + Clone + Serializable + 'static,
+        Fu: Future<Output = T> + 'static,
+    {
+        let load = Rc::new(load);
+        Self(Rc::new(move |cx, params| {
+            let load = Rc::clone(&load);
+            let data = create_resource(cx, || (), move |_| load(params.get_untracked()));
+            // the route is reused when only its params change, so this reloads its data
+            create_isomorphic_effect(cx, move |prev: Option<()>| {
+                params.with(|_| ());
+                if prev.is_some() {
+                    data.refetch();
+                }
+            });
+            (Rc::new(data) as Rc<dyn Any>, data.loading())
+        }))
+    }
+}
+
+impl std::fmt::Debug for Loader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Loader").finish()
+    }
+}
+
 #[derive(Clone)]
 pub struct RouteDefinition {
     pub path: &'static str,
     pub children: Vec<RouteDefinition>,
     pub element: Rc<dyn Fn(Scope) -> Child>,
+    /// Loads the data for the route; see [Loader].
+    pub loader: Option<Loader>,
+    /// If `true` (and the route has a `loader`), the route’s element gets a suspense boundary of
+    /// its own, and navigating to the route works like a `<Transition/>`: the page it replaces
+    /// stays on screen while the route is rendered off-screen, and is only swapped for it once
+    /// its data, and any other resources its element reads, have loaded.
+    ///
+    /// This makes no difference to the first page that is rendered (including when hydrating),
+    /// as there is nothing to keep on screen in the meantime.
+    pub suspense: bool,
+}
+
+impl RouteDefinition {
+    /// Replaces the route’s element with one that is loaded lazily by the `factory`, so that
+    /// the code for rarely-visited routes doesn’t need to be loaded up front.
+    ///
+    /// The first time the route is matched, the `placeholder` (if any) is shown while the
+    /// `factory` loads the element, which is then rendered in its place in the route’s scope.
+    /// The loaded element is kept, so later visits render it right away without calling the
+    /// `factory` again. While it is loading, it counts as a pending resource for the nearest
+    /// `<Suspense/>` or `<Transition/>`, so it holds back their fallbacks (and the router’s
+    /// [use_is_navigating](crate::use_is_navigating)) the same way the route’s data does, and
+    /// any resources that the rest of the page reads load at the same time.
+    ///
+    /// On the server, the `factory` is awaited inline, so the element is always rendered.
+    pub fn element_lazy(
+        mut self,
+        factory: LazyElementFactory,
+        placeholder: Option<LazyElement>,
+    ) -> Self {
+        let resolved: Rc<RefCell<Option<LazyElement>>> = Default::default();
+
+        self.element = Rc::new(move |cx| {
+            let cached = resolved.borrow().clone();
+            if let Some(element) = cached {
+                return element(cx);
+            }
+
+            cfg_if::cfg_if! {
+                if #[cfg(feature = "ssr")] {
+                    _ = &placeholder;
+                    let element = futures::executor::block_on(factory(cx));
+                    *resolved.borrow_mut() = Some(Rc::clone(&element));
+                    element(cx)
+                } else {
+                    use std::cell::Cell;
+
+                    let (loaded, set_loaded) = create_signal(cx, None::<LazyElement>);
+                    let suspense = use_context::<SuspenseContext>(cx);
+                    if let Some(suspense) = &suspense {
+                        suspense.increment();
+                    }
+
+                    let disposed = Rc::new(Cell::new(false));
+                    on_cleanup(cx, {
+                        let disposed = Rc::clone(&disposed);
+                        move || disposed.set(true)
+                    });
+
+                    let fut = factory(cx);
+                    let resolved = Rc::clone(&resolved);
+                    spawn_local(async move {
+                        let element = fut.await;
+                        *resolved.borrow_mut() = Some(Rc::clone(&element));
+                        // the user may have navigated away in the meantime
+                        if !disposed.get() {
+                            set_loaded.set(Some(element));
+                        }
+                        if let Some(suspense) = suspense {
+                            suspense.decrement();
+                        }
+                    });
+
+                    let placeholder = placeholder.clone();
+                    (move || match loaded.get() {
+                        Some(element) => element(cx),
+                        None => placeholder
+                            .as_ref()
+                            .map(|placeholder| placeholder(cx))
+                            .unwrap_or(Child::Null),
+                    })
+                    .into_child(cx)
+                }
+            }
+        });
+        self
+    }
 }
 
 impl std::fmt::Debug for RouteDefinition {
@@ -15,6 +166,7 @@ impl std::fmt::Debug for RouteDefinition {
         f.debug_struct("RouteDefinition")
             .field("path", &self.path)
             .field("children", &self.children)
+            .field("suspense", &self.suspense)
             .finish()
     }
 }
@@ -31,6 +183,8 @@ impl Default for RouteDefinition {
             path: Default::default(),
             children: Default::default(),
             element: Rc::new(|_| Child::Null),
+            loader: None,
+            suspense: false,
         }
     }
 }
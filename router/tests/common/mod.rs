@@ -0,0 +1,50 @@
+// helpers shared by the router's tests, which each only use some of them
+#![allow(dead_code)]
+
+use leptos::leptos_dom::Child;
+use leptos::*;
+use leptos_router::*;
+
+// runs any reactive functions, as the renderer would
+pub fn render(child: &Child) -> Child {
+    match child {
+        Child::Fn(f) => {
+            let child = (f.borrow_mut())();
+            render(&child)
+        }
+        child => child.clone(),
+    }
+}
+
+pub fn text(text: &str) -> Child {
+    Child::Text(text.to_string())
+}
+
+// a router at `/`, as rendered on the server
+pub fn router(cx: Scope) {
+    provide_context(
+        cx,
+        RouterIntegrationContext::new(ServerIntegration {
+            path: "/".to_string(),
+        }),
+    );
+    _ = Router(
+        cx,
+        RouterProps {
+            base: None,
+            fallback: None,
+            children: Box::new(Vec::new),
+        },
+    );
+}
+
+pub fn routes(cx: Scope, defs: Vec<RouteDefinition>) -> Child {
+    router(cx);
+    Routes(
+        cx,
+        RoutesProps::builder()
+            .children(Box::new(move || defs.clone()))
+            .build(),
+    )
+    .into_child(cx)
+}
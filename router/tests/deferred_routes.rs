@@ -0,0 +1,149 @@
+// the router can only run outside the browser with the `ssr` feature
+#![cfg(feature = "ssr")]
+
+mod common;
+
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use leptos::leptos_dom::Child;
+use leptos::testing::{sleep, TestRuntime};
+use leptos::*;
+use leptos_router::*;
+
+use common::{render, router, text};
+
+// a route that loads its data for `delay`, showing a fallback until then
+fn slow_route(path: &'static str, delay: Duration) -> RouteDefinition {
+    RouteDefinition {
+        path,
+        children: Vec::new(),
+        element: Rc::new(move |cx| {
+            provide_context(cx, SuspenseContext::new(cx));
+            let data = create_resource(
+                cx,
+                || (),
+                move |_| async move {
+                    sleep(delay).await;
+                    path
+                },
+            );
+            (move || match data.read() {
+                Some(path) => text(path),
+                None => text("loading"),
+            })
+            .into_child(cx)
+        }),
+        loader: None,
+        suspense: false,
+    }
+}
+
+fn routes(cx: Scope, timeout: Option<Duration>, defs: Vec<RouteDefinition>) -> Child {
+    router(cx);
+    Routes(
+        cx,
+        RoutesProps::builder()
+            .mode(NavigationMode::Deferred { timeout })
+            .children(Box::new(move || defs.clone()))
+            .build(),
+    )
+    .into_child(cx)
+}
+
+fn home() -> RouteDefinition {
+    RouteDefinition {
+        path: "/",
+        children: Vec::new(),
+        element: Rc::new(|_| text("home")),
+        loader: None,
+        suspense: false,
+    }
+}
+
+#[test]
+fn old_route_is_shown_until_new_route_is_ready() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let view = routes(
+        cx,
+        None,
+        vec![home(), slow_route("/a", Duration::from_millis(20))],
+    );
+    let navigating = use_is_navigating(cx);
+    rt.flush();
+    assert_eq!(render(&view), text("home"));
+
+    use_navigate(cx)("/a", Default::default()).unwrap();
+    rt.flush();
+    assert!(navigating.get());
+    assert_eq!(render(&view), text("home"));
+
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(render(&view), text("home"));
+
+    rt.advance(Duration::from_millis(10));
+    assert!(!navigating.get());
+    assert_eq!(render(&view), text("/a"));
+}
+
+#[test]
+fn only_the_last_navigation_is_shown() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let a_disposed = Rc::new(Cell::new(false));
+    let a = slow_route("/a", Duration::from_millis(20));
+    let a_element = Rc::clone(&a.element);
+    let a = RouteDefinition {
+        element: Rc::new({
+            let a_disposed = Rc::clone(&a_disposed);
+            move |cx| {
+                let a_disposed = Rc::clone(&a_disposed);
+                on_cleanup(cx, move || a_disposed.set(true));
+                a_element(cx)
+            }
+        }),
+        ..a
+    };
+    let view = routes(
+        cx,
+        None,
+        vec![home(), a, slow_route("/b", Duration::from_millis(40))],
+    );
+    rt.flush();
+
+    let navigate = use_navigate(cx);
+    navigate("/a", Default::default()).unwrap();
+    rt.flush();
+    navigate("/b", Default::default()).unwrap();
+    rt.flush();
+    // the route for the first navigation has been thrown away
+    assert!(a_disposed.get());
+
+    rt.advance(Duration::from_millis(20));
+    assert_eq!(render(&view), text("home"));
+
+    rt.advance(Duration::from_millis(20));
+    assert_eq!(render(&view), text("/b"));
+}
+
+#[test]
+fn timeout_shows_new_route_with_fallback() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let view = routes(
+        cx,
+        Some(Duration::from_millis(100)),
+        vec![home(), slow_route("/slow", Duration::from_millis(500))],
+    );
+    rt.flush();
+
+    use_navigate(cx)("/slow", Default::default()).unwrap();
+    rt.advance(Duration::from_millis(99));
+    assert_eq!(render(&view), text("home"));
+
+    rt.advance(Duration::from_millis(1));
+    assert_eq!(render(&view), text("loading"));
+
+    rt.advance(Duration::from_millis(400));
+    assert_eq!(render(&view), text("/slow"));
+}
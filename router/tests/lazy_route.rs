@@ -0,0 +1,62 @@
+// on the server, lazy routes are always rendered inline
+#![cfg(not(feature = "ssr"))]
+
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use leptos::testing::{sleep, TestRuntime};
+use leptos::*;
+use leptos_router::*;
+
+// runs any reactive functions, as the renderer would
+fn render(child: Child) -> Child {
+    match child {
+        Child::Fn(f) => {
+            let child = (f.borrow_mut())();
+            render(child)
+        }
+        child => child,
+    }
+}
+
+fn text(text: &str) -> Child {
+    Child::Text(text.to_string())
+}
+
+#[test]
+fn lazy_route_loads_its_element_once() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+
+    let loads = Rc::new(Cell::new(0));
+    let route = LazyRoute(
+        cx,
+        LazyRouteProps {
+            path: "admin",
+            element: Rc::new({
+                let loads = Rc::clone(&loads);
+                move |_| {
+                    loads.set(loads.get() + 1);
+                    Box::pin(async {
+                        sleep(Duration::from_millis(10)).await;
+                        Rc::new(|_| text("admin")) as LazyElement
+                    })
+                }
+            }),
+            placeholder: Some(Rc::new(|_| text("loading"))),
+            children: None,
+        },
+    );
+
+    // first visit: the placeholder is shown until the element has loaded
+    let (first, disposer) = cx.run_child_scope(|cx| (route.element)(cx));
+    rt.flush();
+    assert_eq!(render(first.clone()), text("loading"));
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(render(first), text("admin"));
+    disposer.dispose();
+
+    // second visit: the loaded element is rendered right away
+    let (second, _) = cx.run_child_scope(|cx| (route.element)(cx));
+    assert_eq!(render(second), text("admin"));
+    assert_eq!(loads.get(), 1);
+}
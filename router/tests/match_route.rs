@@ -0,0 +1,109 @@
+// the router can only run outside the browser with the `ssr` feature
+#![cfg(feature = "ssr")]
+
+use leptos_router::*;
+
+fn route(path: &'static str, children: Vec<RouteDefinition>) -> RouteDefinition {
+    RouteDefinition {
+        path,
+        children,
+        ..Default::default()
+    }
+}
+
+fn routes() -> Vec<RouteDefinition> {
+    vec![
+        route("", vec![]),
+        route("about", vec![]),
+        route(
+            "users",
+            vec![
+                route("", vec![]),
+                route("new", vec![]),
+                route(
+                    ":id",
+                    vec![route("", vec![]), route("posts/:post?", vec![])],
+                ),
+            ],
+        ),
+        route("files/*path", vec![]),
+        route("*any", vec![]),
+    ]
+}
+
+// the paths of the matched definitions, from the outermost to the innermost
+fn chain(matched: &RouteMatch) -> Vec<&'static str> {
+    matched.routes.iter().map(|route| route.path).collect()
+}
+
+fn param<'a>(matched: &'a RouteMatch, key: &str) -> Option<&'a str> {
+    matched.params.get(key).map(String::as_str)
+}
+
+#[test]
+fn matches_nested_routes() {
+    let routes = routes();
+
+    let matched = match_route(&routes, "/about").unwrap();
+    assert_eq!(chain(&matched), ["about"]);
+    assert_eq!(matched.pattern, "/about");
+
+    let matched = match_route(&routes, "/users").unwrap();
+    assert_eq!(chain(&matched), ["users", ""]);
+    assert_eq!(matched.pattern, "/users");
+
+    let matched = match_route(&routes, "/").unwrap();
+    assert_eq!(chain(&matched), [""]);
+}
+
+#[test]
+fn extracts_params() {
+    let routes = routes();
+
+    let matched = match_route(&routes, "/users/42").unwrap();
+    assert_eq!(chain(&matched), ["users", ":id", ""]);
+    assert_eq!(matched.pattern, "/users/:id");
+    assert_eq!(matched.path, "/users/42");
+    assert_eq!(param(&matched, "id"), Some("42"));
+
+    let matched = match_route(&routes, "/users/42/posts/7").unwrap();
+    assert_eq!(chain(&matched), ["users", ":id", "posts/:post?"]);
+    assert_eq!(matched.pattern, "/users/:id/posts/:post");
+    assert_eq!(param(&matched, "id"), Some("42"));
+    assert_eq!(param(&matched, "post"), Some("7"));
+
+    // the optional param expands to a route without it
+    let matched = match_route(&routes, "/users/42/posts").unwrap();
+    assert_eq!(matched.pattern, "/users/:id/posts");
+    assert_eq!(param(&matched, "post"), None);
+}
+
+#[test]
+fn static_segments_win_over_params_and_wildcards() {
+    let routes = routes();
+
+    let matched = match_route(&routes, "/users/new").unwrap();
+    assert_eq!(chain(&matched), ["users", "new"]);
+    assert!(matched.params.0.is_empty());
+
+    let matched = match_route(&routes, "/files/docs/readme.md").unwrap();
+    assert_eq!(chain(&matched), ["files/*path"]);
+    assert_eq!(param(&matched, "path"), Some("docs/readme.md"));
+
+    let matched = match_route(&routes, "/somewhere/else").unwrap();
+    assert_eq!(chain(&matched), ["*any"]);
+    assert_eq!(param(&matched, "any"), Some("somewhere/else"));
+}
+
+#[test]
+fn unmatched_paths_return_none() {
+    let routes = vec![
+        route("", vec![]),
+        route("users", vec![route(":id", vec![])]),
+    ];
+
+    assert!(match_route(&routes, "/admin").is_none());
+    assert!(match_route(&routes, "/users").is_none());
+    assert!(match_route(&routes, "/users/42/edit").is_none());
+    assert!(match_route(&[], "/").is_none());
+}
@@ -0,0 +1,119 @@
+// the router can only run outside the browser with the `ssr` feature
+#![cfg(feature = "ssr")]
+
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use leptos::testing::{sleep, TestRuntime};
+use leptos::*;
+use leptos_router::*;
+
+fn router(cx: Scope) {
+    provide_context(
+        cx,
+        RouterIntegrationContext::new(ServerIntegration {
+            path: "/".to_string(),
+        }),
+    );
+    _ = Router(
+        cx,
+        RouterProps {
+            base: None,
+            fallback: None,
+            children: Box::new(Vec::new),
+        },
+    );
+}
+
+fn record(cx: Scope, signal: Signal<bool>) -> Rc<RefCell<Vec<bool>>> {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    create_isomorphic_effect(cx, {
+        let log = Rc::clone(&log);
+        move |_| log.borrow_mut().push(signal.get())
+    });
+    log
+}
+
+#[test]
+fn navigating_while_sequential_resources_load() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    router(cx);
+    provide_context(cx, SuspenseContext::new(cx));
+
+    let navigating = record(cx, use_is_navigating(cx));
+    let pending = record(cx, use_global_pending(cx));
+
+    let pathname = use_location(cx).pathname;
+    let page = create_resource(
+        cx,
+        move || pathname.get(),
+        |path| async move {
+            sleep(Duration::from_millis(10)).await;
+            path
+        },
+    );
+    // only starts loading once `page` has resolved
+    let comments = create_resource(
+        cx,
+        move || page.read(),
+        |page| async move {
+            sleep(Duration::from_millis(10)).await;
+            page.map(|page| format!("comments on {page}"))
+        },
+    );
+    // read both, as the children of a `<Suspense/>` would
+    create_isomorphic_effect(cx, move |_| {
+        page.read();
+        comments.read();
+    });
+    rt.advance(Duration::from_millis(20));
+    assert_eq!(comments.read(), Some(Some("comments on /".to_string())));
+
+    use_navigate(cx)("/about", Default::default()).unwrap();
+    rt.flush();
+    assert_eq!(navigating.borrow().last(), Some(&true));
+
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(page.read(), Some("/about".to_string()));
+    assert_eq!(navigating.borrow().last(), Some(&true));
+
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(
+        comments.read(),
+        Some(Some("comments on /about".to_string()))
+    );
+    assert_eq!(*navigating.borrow(), [false, true, false]);
+    assert_eq!(*pending.borrow(), [false, true, false, true, false]);
+}
+
+#[test]
+fn refetching_is_not_navigating() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    router(cx);
+    provide_context(cx, SuspenseContext::new(cx));
+
+    let navigating = record(cx, use_is_navigating(cx));
+    let pending = record(cx, use_global_pending(cx));
+
+    let count = create_resource(
+        cx,
+        || (),
+        |_| async {
+            sleep(Duration::from_millis(10)).await;
+            1
+        },
+    );
+    create_isomorphic_effect(cx, move |_| {
+        count.read();
+    });
+    rt.advance(Duration::from_millis(10));
+
+    count.refetch();
+    rt.flush();
+    assert_eq!(pending.borrow().last(), Some(&true));
+
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(*pending.borrow(), [false, true, false, true, false]);
+    assert_eq!(*navigating.borrow(), [false]);
+}
@@ -0,0 +1,191 @@
+// the router can only run outside the browser with the `ssr` feature
+#![cfg(feature = "ssr")]
+
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use leptos::testing::{sleep, TestRuntime};
+use leptos::*;
+use leptos_router::*;
+
+fn change(path: &str) -> LocationChange {
+    LocationChange {
+        value: path.to_string(),
+        replace: false,
+        scroll: true,
+        state: State(None),
+    }
+}
+
+// a history that starts at `/`, and records where the router tells it to go
+#[derive(Clone, Default)]
+struct TestHistory {
+    location: Rc<RefCell<Option<(ReadSignal<LocationChange>, WriteSignal<LocationChange>)>>>,
+    navigated: Rc<RefCell<Vec<String>>>,
+}
+
+impl History for TestHistory {
+    fn location(&self, cx: Scope) -> ReadSignal<LocationChange> {
+        let (location, set_location) = create_signal(cx, change("/"));
+        *self.location.borrow_mut() = Some((location, set_location));
+        location
+    }
+
+    fn navigate(&self, loc: &LocationChange) {
+        self.navigated.borrow_mut().push(loc.value.clone());
+    }
+}
+
+impl TestHistory {
+    // moves to `path` outside the router, as the browser’s back and forward buttons do
+    fn pop_to(&self, cx: Scope, path: &'static str) {
+        let (_, set_location) = self.location.borrow().unwrap();
+        handle_history_change(cx, change(path), move || set_location.set(change(path)));
+    }
+
+    fn current(&self) -> String {
+        let (location, _) = self.location.borrow().unwrap();
+        location.get().value
+    }
+}
+
+fn router(cx: Scope, decide: fn() -> GuardDecision) -> TestHistory {
+    let history = TestHistory::default();
+    provide_context(cx, RouterIntegrationContext::new(history.clone()));
+    _ = Router(
+        cx,
+        RouterProps {
+            base: None,
+            fallback: None,
+            children: Box::new(Vec::new),
+        },
+    );
+    let (when, _) = create_signal(cx, true);
+    use_navigation_guard(cx, when, move |_| decide());
+    history
+}
+
+fn defer_allow() -> GuardDecision {
+    GuardDecision::defer(async {
+        sleep(Duration::from_millis(10)).await;
+        GuardDecision::Allow
+    })
+}
+
+fn defer_block() -> GuardDecision {
+    GuardDecision::defer(async {
+        sleep(Duration::from_millis(10)).await;
+        GuardDecision::Block
+    })
+}
+
+#[test]
+fn guard_allows_navigate() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    router(cx, || GuardDecision::Allow);
+    let pathname = use_location(cx).pathname;
+
+    use_navigate(cx)("/about", Default::default()).unwrap();
+    rt.flush();
+    assert_eq!(pathname.get(), "/about");
+}
+
+#[test]
+fn guard_blocks_navigate() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    router(cx, || GuardDecision::Block);
+    let pathname = use_location(cx).pathname;
+
+    let result = use_navigate(cx)("/about", Default::default());
+    assert!(matches!(result, Err(NavigationError::Blocked(to)) if to == "/about"));
+    rt.flush();
+    assert_eq!(pathname.get(), "/");
+}
+
+#[test]
+fn guard_defers_navigate() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    router(cx, defer_allow);
+    let pathname = use_location(cx).pathname;
+
+    use_navigate(cx)("/about", Default::default()).unwrap();
+    rt.flush();
+    assert_eq!(pathname.get(), "/");
+
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(pathname.get(), "/about");
+}
+
+#[test]
+fn deferred_guard_can_still_block_navigate() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    router(cx, defer_block);
+    let pathname = use_location(cx).pathname;
+
+    use_navigate(cx)("/about", Default::default()).unwrap();
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(pathname.get(), "/");
+}
+
+#[test]
+fn guard_allows_popstate() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let history = router(cx, || GuardDecision::Allow);
+    let pathname = use_location(cx).pathname;
+
+    history.pop_to(cx, "/about");
+    rt.flush();
+    assert_eq!(pathname.get(), "/about");
+    assert_eq!(history.current(), "/about");
+    assert!(!history.navigated.borrow().contains(&"/".to_string()));
+}
+
+#[test]
+fn guard_blocks_popstate() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let history = router(cx, || GuardDecision::Block);
+    let pathname = use_location(cx).pathname;
+
+    history.pop_to(cx, "/about");
+    rt.flush();
+    assert_eq!(pathname.get(), "/");
+    assert_eq!(history.current(), "/");
+    // the history is moved back to where the router still is
+    assert_eq!(*history.navigated.borrow(), ["/"]);
+}
+
+#[test]
+fn guard_defers_popstate() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let history = router(cx, defer_allow);
+    let pathname = use_location(cx).pathname;
+
+    history.pop_to(cx, "/about");
+    rt.flush();
+    assert_eq!(pathname.get(), "/");
+    assert_eq!(*history.navigated.borrow(), ["/"]);
+
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(pathname.get(), "/about");
+    // the history's location is updated too, once the navigation is allowed
+    assert_eq!(history.current(), "/about");
+}
+
+#[test]
+fn deferred_guard_can_still_block_popstate() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let history = router(cx, defer_block);
+    let pathname = use_location(cx).pathname;
+
+    history.pop_to(cx, "/about");
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(pathname.get(), "/");
+    assert_eq!(history.current(), "/");
+}
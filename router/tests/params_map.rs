@@ -0,0 +1,50 @@
+// the router can only run outside the browser with the `ssr` feature
+#![cfg(feature = "ssr")]
+
+mod common;
+
+use std::rc::Rc;
+
+use leptos::leptos_dom::Child;
+use leptos::testing::TestRuntime;
+use leptos::*;
+use leptos_router::*;
+
+use common::{render, routes, text};
+
+// shows every param, without knowing their names
+fn all_params(cx: Scope) -> Child {
+    let params = use_params_map(cx);
+    (move || {
+        params.with(|params| {
+            let params = params
+                .iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>();
+            text(&params.join(" "))
+        })
+    })
+    .into_child(cx)
+}
+
+#[test]
+fn params_map_follows_navigation() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let view = routes(
+        cx,
+        vec![RouteDefinition {
+            path: "/repos/:owner/*rest",
+            element: Rc::new(all_params),
+            ..Default::default()
+        }],
+    );
+
+    use_navigate(cx)("/repos/leptos/src/lib.rs", Default::default()).unwrap();
+    rt.flush();
+    assert_eq!(render(&view), text("owner=leptos rest=src/lib.rs"));
+
+    use_navigate(cx)("/repos/solid/README.md", Default::default()).unwrap();
+    rt.flush();
+    assert_eq!(render(&view), text("owner=solid rest=README.md"));
+}
@@ -0,0 +1,54 @@
+// the router can only run outside the browser with the `ssr` feature
+#![cfg(feature = "ssr")]
+
+mod common;
+
+use std::rc::Rc;
+
+use leptos::leptos_dom::Child;
+use leptos::testing::TestRuntime;
+use leptos::*;
+use leptos_router::*;
+
+use common::{render, routes, text};
+
+fn login(_cx: Scope) -> Child {
+    text("log in")
+}
+
+// a page the user can't see, which sends them to the login page
+fn account(cx: Scope) -> Child {
+    Redirect(
+        cx,
+        RedirectProps::builder()
+            .href("/login")
+            .replace(true)
+            .build(),
+    )
+}
+
+#[test]
+fn redirect_navigates_when_rendered() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let view = routes(
+        cx,
+        vec![
+            RouteDefinition {
+                path: "/account",
+                element: Rc::new(account),
+                ..Default::default()
+            },
+            RouteDefinition {
+                path: "/login",
+                element: Rc::new(login),
+                ..Default::default()
+            },
+        ],
+    );
+
+    use_navigate(cx)("/account", Default::default()).unwrap();
+    rt.flush();
+    assert_eq!(use_location(cx).pathname.get(), "/login");
+    assert_eq!(render(&view), text("log in"));
+}
@@ -0,0 +1,70 @@
+// the router can only run outside the browser with the `ssr` feature
+#![cfg(feature = "ssr")]
+
+mod common;
+
+use std::{rc::Rc, time::Duration};
+
+use leptos::leptos_dom::Child;
+use leptos::testing::{sleep, TestRuntime};
+use leptos::*;
+use leptos_router::*;
+
+use common::{render, routes, text};
+
+// shows the data loaded for the route
+fn user(cx: Scope) -> Child {
+    let user = use_route_data::<String>(cx);
+    (move || match user.read() {
+        Some(user) => text(&user),
+        None => text("loading"),
+    })
+    .into_child(cx)
+}
+
+#[test]
+fn route_data_is_loaded_for_the_params() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let view = routes(
+        cx,
+        vec![RouteDefinition {
+            path: "/users/:id",
+            element: Rc::new(user),
+            loader: Some(Loader::new(|params: ParamsMap| async move {
+                sleep(Duration::from_millis(10)).await;
+                format!("user {}", params.get("id").unwrap())
+            })),
+            ..Default::default()
+        }],
+    );
+
+    use_navigate(cx)("/users/1", Default::default()).unwrap();
+    rt.flush();
+    assert_eq!(render(&view), text("loading"));
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(render(&view), text("user 1"));
+
+    // the same route is reused, but its data is loaded again
+    use_navigate(cx)("/users/2", Default::default()).unwrap();
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(render(&view), text("user 2"));
+}
+
+#[test]
+fn route_data_without_a_loader_never_resolves() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let view = routes(
+        cx,
+        vec![RouteDefinition {
+            path: "/users/:id",
+            element: Rc::new(user),
+            ..Default::default()
+        }],
+    );
+
+    use_navigate(cx)("/users/1", Default::default()).unwrap();
+    rt.advance(Duration::from_millis(100));
+    assert_eq!(render(&view), text("loading"));
+}
@@ -0,0 +1,51 @@
+// the router can only run outside the browser with the `ssr` feature
+#![cfg(feature = "ssr")]
+
+mod common;
+
+use std::rc::Rc;
+
+use leptos::leptos_dom::Child;
+use leptos::testing::TestRuntime;
+use leptos::*;
+use leptos_router::*;
+
+use common::{render, routes, text};
+
+// shows what `/users/:id` matches, for any route
+fn user_match(cx: Scope) -> Child {
+    let matched = use_route_match(cx, "/users/:id");
+    (move || {
+        matched.with(|matched| match matched {
+            Some(m) => text(&format!("{} id={}", m.path, m.params.get("id").unwrap())),
+            None => text("none"),
+        })
+    })
+    .into_child(cx)
+}
+
+#[test]
+fn route_match_covers_sub_paths() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let view = routes(
+        cx,
+        vec![RouteDefinition {
+            path: "/*any",
+            element: Rc::new(user_match),
+            ..Default::default()
+        }],
+    );
+
+    use_navigate(cx)("/users/42", Default::default()).unwrap();
+    rt.flush();
+    assert_eq!(render(&view), text("/users/42 id=42"));
+
+    use_navigate(cx)("/users/7/posts", Default::default()).unwrap();
+    rt.flush();
+    assert_eq!(render(&view), text("/users/7 id=7"));
+
+    use_navigate(cx)("/about", Default::default()).unwrap();
+    rt.flush();
+    assert_eq!(render(&view), text("none"));
+}
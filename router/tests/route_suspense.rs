@@ -0,0 +1,75 @@
+// the router can only run outside the browser with the `ssr` feature
+#![cfg(feature = "ssr")]
+
+mod common;
+
+use std::{rc::Rc, time::Duration};
+
+use leptos::leptos_dom::Child;
+use leptos::testing::{sleep, TestRuntime};
+use leptos::*;
+use leptos_router::*;
+
+use common::{render, routes, text};
+
+// shows the data loaded for the route
+fn user(cx: Scope) -> Child {
+    let user = use_route_data::<String>(cx);
+    (move || match user.read() {
+        Some(user) => text(&user),
+        None => text("loading"),
+    })
+    .into_child(cx)
+}
+
+fn home() -> RouteDefinition {
+    RouteDefinition {
+        path: "/",
+        element: Rc::new(|_| text("home")),
+        ..Default::default()
+    }
+}
+
+fn users(suspense: bool) -> RouteDefinition {
+    RouteDefinition {
+        path: "/users/:id",
+        element: Rc::new(user),
+        loader: Some(Loader::new(|params: ParamsMap| async move {
+            sleep(Duration::from_millis(10)).await;
+            format!("user {}", params.get("id").unwrap())
+        })),
+        suspense,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn suspended_route_waits_for_its_data() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let view = routes(cx, vec![home(), users(true)]);
+    rt.flush();
+    assert_eq!(render(&view), text("home"));
+
+    // the old page stays on screen until the data has loaded
+    use_navigate(cx)("/users/1", Default::default()).unwrap();
+    rt.flush();
+    assert_eq!(render(&view), text("home"));
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(render(&view), text("user 1"));
+}
+
+#[test]
+fn route_without_suspense_is_shown_immediately() {
+    let rt = TestRuntime::new();
+    let cx = rt.scope();
+    let view = routes(cx, vec![home(), users(false)]);
+    rt.flush();
+    assert_eq!(render(&view), text("home"));
+
+    use_navigate(cx)("/users/1", Default::default()).unwrap();
+    rt.flush();
+    assert_eq!(render(&view), text("loading"));
+    rt.advance(Duration::from_millis(10));
+    assert_eq!(render(&view), text("user 1"));
+}